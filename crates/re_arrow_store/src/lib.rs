@@ -33,6 +33,7 @@ pub mod test_util;
 pub use self::arrow_util::ArrayExt;
 pub use self::store::{
     DataStore, DataStoreConfig, IndexBucket, IndexRowNr, IndexTable, RowIndex, RowIndexKind,
+    StoreGeneration,
 };
 pub use self::store_gc::GarbageCollectionTarget;
 pub use self::store_read::{LatestAtQuery, RangeQuery};
@@ -1,4 +1,4 @@
-use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
 use std::num::NonZeroU64;
 use std::sync::atomic::AtomicU64;
 
@@ -10,9 +10,27 @@ use nohash_hasher::{IntMap, IntSet};
 use parking_lot::RwLock;
 use re_format::{arrow, format_bytes, format_number};
 use re_log_types::{
-    ComponentName, EntityPath, EntityPathHash, MsgId, TimeInt, TimePoint, TimeRange, Timeline,
+    msg_bundle::ComponentBundle, ComponentName, EntityPath, EntityPathHash, MsgId, TimeInt,
+    TimePoint, TimeRange, Timeline,
 };
 
+/// Seeds the hashers of a [`HashMap`] the same way on every run,
+/// so that it iterates in the same order given the same insertions.
+///
+/// That way, e.g. dumping the store gives identical results for identical inputs,
+/// which snapshot tests rely on.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct FixedHashState;
+
+impl std::hash::BuildHasher for FixedHashState {
+    type Hasher = ahash::AHasher;
+
+    #[inline]
+    fn build_hasher(&self) -> Self::Hasher {
+        std::hash::BuildHasher::build_hasher(&re_log_types::hash::HASH_RANDOM_STATE)
+    }
+}
+
 // --- Indices & offsets ---
 
 /// A vector of times. Our primary column, always densely filled.
@@ -178,6 +196,18 @@ pub struct DataStoreConfig {
     ///
     /// See [`DataStore::insert_id_key`].
     pub store_insert_ids: bool,
+
+    /// Entities (and everything under them) that are logged far more often than they change,
+    /// e.g. a static transform logged at 100 Hz.
+    ///
+    /// Inserting a row into one of these that is identical to the previous row of that entity
+    /// is a no-op, so that a run of identical values takes up a single row.
+    /// Latest-at queries are unaffected, but range queries only see the changes.
+    ///
+    /// Only applies to temporal data, and only when time moves forward on all timelines.
+    ///
+    /// See [`DataStore::set_deduplicated_entities`].
+    pub deduplicated_entities: BTreeSet<EntityPath>,
 }
 
 impl Default for DataStoreConfig {
@@ -193,6 +223,7 @@ impl DataStoreConfig {
         index_bucket_size_bytes: 32 * 1024, // 32kiB
         index_bucket_nb_rows: 1024,
         store_insert_ids: cfg!(debug_assertions),
+        deduplicated_entities: BTreeSet::new(),
     };
 }
 
@@ -249,7 +280,9 @@ pub struct DataStore {
     /// Maps an entity to its index, for a specific timeline.
     ///
     /// An index maps specific points in time to rows in component tables.
-    pub(crate) indices: HashMap<(Timeline, EntityPathHash), IndexTable>,
+    ///
+    /// Iterates in the same order on every run, see [`FixedHashState`].
+    pub(crate) indices: HashMap<(Timeline, EntityPathHash), IndexTable, FixedHashState>,
 
     /// Maps a component name to its associated table, for all timelines and all entities.
     ///
@@ -259,6 +292,11 @@ pub struct DataStore {
     /// Monotonically increasing ID for insertions.
     pub(crate) insert_id: u64,
 
+    /// The last row inserted for each deduplicated entity.
+    ///
+    /// See [`DataStoreConfig::deduplicated_entities`].
+    pub(crate) last_rows: IntMap<EntityPathHash, (TimePoint, Vec<ComponentBundle>)>,
+
     /// Monotonically increasing ID for queries.
     pub(crate) query_id: AtomicU64,
 
@@ -266,6 +304,17 @@ pub struct DataStore {
     pub(crate) gc_id: u64,
 }
 
+/// Identifies a specific state of a [`DataStore`].
+///
+/// Two generations compare equal if and only if nothing was inserted nor garbage collected
+/// in between, i.e. everything read from the store in the meantime is consistent.
+/// Useful to take once per frame and to invalidate caches derived from the store's contents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct StoreGeneration {
+    insert_id: u64,
+    gc_id: u64,
+}
+
 impl DataStore {
     /// See [`Self::cluster_key`] for more information about the cluster key.
     pub fn new(cluster_key: ComponentName, config: DataStoreConfig) -> Self {
@@ -279,6 +328,7 @@ impl DataStore {
             timeless_indices: Default::default(),
             timeless_components: Default::default(),
             insert_id: 0,
+            last_rows: Default::default(),
             query_id: AtomicU64::new(0),
             gc_id: 0,
         }
@@ -299,6 +349,27 @@ impl DataStore {
         self.cluster_key
     }
 
+    /// Change which entities are deduplicated from now on.
+    ///
+    /// Data that has already been inserted is left as is.
+    /// See [`DataStoreConfig::deduplicated_entities`].
+    pub fn set_deduplicated_entities(&mut self, entities: &BTreeSet<EntityPath>) {
+        if &self.config.deduplicated_entities != entities {
+            self.config.deduplicated_entities = entities.clone();
+            self.last_rows.clear();
+        }
+    }
+
+    /// The current state of the store.
+    ///
+    /// See [`StoreGeneration`] for more information.
+    pub fn generation(&self) -> StoreGeneration {
+        StoreGeneration {
+            insert_id: self.insert_id,
+            gc_id: self.gc_id,
+        }
+    }
+
     /// Lookup the arrow `DataType` of a `Component`
     pub fn lookup_data_type(&self, component: &ComponentName) -> Option<&DataType> {
         self.components.get(component).map(|c| &c.datatype)
@@ -875,6 +946,7 @@ pub struct IndexBucket {
 pub struct IndexBucketIndices {
     /// Whether the indices (all of them!) are currently sorted.
     ///
+    /// Insertions keep the indices sorted, even when data arrives out of order.
     /// Querying an `IndexBucket` will always trigger a sort if the indices aren't already sorted.
     pub(crate) is_sorted: bool,
 
@@ -1322,7 +1394,7 @@ pub struct ComponentBucket {
     /// Buckets are never sorted over time, so these time ranges can grow arbitrarily large.
     ///
     /// These are only used for garbage collection.
-    pub(crate) time_ranges: HashMap<Timeline, TimeRange>,
+    pub(crate) time_ranges: BTreeMap<Timeline, TimeRange>,
 
     /// All the data for this bucket: many rows of a single column.
     ///
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 use arrow2::array::{Array, ListArray};
 use re_log::info;
@@ -49,6 +49,9 @@ impl DataStore {
 
         self.gc_id += 1;
 
+        // The rows we would deduplicate against might be about to go.
+        self.last_rows.clear();
+
         let initial_nb_rows = self.total_temporal_component_rows();
         let initial_size_bytes = self.total_temporal_component_size_bytes() as f64;
 
@@ -164,7 +167,7 @@ impl ComponentBucket {
     fn encompasses(
         &self,
         primary_timeline: Timeline,
-        time_ranges: &HashMap<Timeline, TimeRange>,
+        time_ranges: &BTreeMap<Timeline, TimeRange>,
     ) -> bool {
         if let (Some(time_range1), Some(time_range2)) = (
             self.time_ranges.get(&primary_timeline),
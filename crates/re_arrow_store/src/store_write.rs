@@ -8,7 +8,7 @@ use parking_lot::RwLock;
 
 use re_log::{debug, trace};
 use re_log_types::{
-    msg_bundle::{wrap_in_listarray, ComponentBundle, MsgBundle},
+    msg_bundle::{wrap_in_listarray, Component as _, ComponentBundle, MsgBundle},
     ComponentName, EntityPath, MsgId, TimeInt, TimePoint, TimeRange, Timeline,
 };
 
@@ -126,6 +126,16 @@ impl DataStore {
             "insertion started..."
         );
 
+        if self.is_redundant(ent_path, time_point, bundles) {
+            trace!(
+                kind = "insert",
+                id = self.insert_id,
+                entity = %ent_path,
+                "insertion skipped: identical to the previous row"
+            );
+            return Ok(());
+        }
+
         let cluster_comp_pos = bundles
             .iter()
             .find_position(|bundle| bundle.name() == self.cluster_key)
@@ -171,9 +181,69 @@ impl DataStore {
         // This is valuable information, even for a timeless timepoint!
         self.messages.insert(*msg_id, time_point.clone());
 
+        if self.is_deduplicated(ent_path, time_point) {
+            self.last_rows
+                .insert(ent_path_hash, (time_point.clone(), bundles.clone()));
+        }
+
         Ok(())
     }
 
+    /// Are temporal rows of this entity deduplicated?
+    ///
+    /// See [`DataStoreConfig::deduplicated_entities`].
+    fn is_deduplicated(&self, ent_path: &EntityPath, time_point: &TimePoint) -> bool {
+        !time_point.is_timeless()
+            && self
+                .config
+                .deduplicated_entities
+                .iter()
+                .any(|deduplicated| {
+                    ent_path == deduplicated || ent_path.is_descendant_of(deduplicated)
+                })
+    }
+
+    /// Is this row identical to the previous row of a deduplicated entity?
+    ///
+    /// The previous row is only remembered once it has been successfully inserted.
+    fn is_redundant(
+        &self,
+        ent_path: &EntityPath,
+        time_point: &TimePoint,
+        bundles: &[ComponentBundle],
+    ) -> bool {
+        if !self.is_deduplicated(ent_path, time_point) {
+            return false;
+        }
+
+        crate::profile_function!();
+
+        self.last_rows
+            .get(&ent_path.hash())
+            .map_or(false, |(last_time_point, last_bundles)| {
+                // Only collapse runs that move forward in time, otherwise
+                // we'd leave a hole in the past.
+                let is_later = time_point.timelines().eq(last_time_point.timelines())
+                    && time_point.iter().all(|(timeline, time)| {
+                        last_time_point
+                            .get(timeline)
+                            .map_or(false, |last_time| last_time <= time)
+                    });
+
+                // Every row has its own `MsgId`, which is not part of its value.
+                let msg_id = MsgId::name();
+                is_later
+                    && bundles.len() == last_bundles.len()
+                    && bundles.iter().all(|bundle| {
+                        bundle.name() == msg_id
+                            || last_bundles.iter().any(|last| {
+                                last.name() == bundle.name()
+                                    && last.value_list() == bundle.value_list()
+                            })
+                    })
+            })
+    }
+
     fn insert_timeless_row(
         &mut self,
         row_nr: usize,
@@ -656,20 +726,29 @@ impl IndexBucket {
             indices,
         } = &mut *guard;
 
-        // append time to primary index and update time range approriately
-        times.push(time.as_i64());
-        *time_range = TimeRange::new(time_range.min.min(time), time_range.max.max(time));
+        // Keep the bucket sorted as we go, so that queries never have to re-sort it:
+        // in-order data (the common case) is appended, while out-of-order data (e.g. backfilled
+        // or post-processed data) is inserted in place, after any rows at the same time.
+        let row_nr = if *is_sorted {
+            times.partition_point(|t| *t <= time.as_i64())
+        } else {
+            times.len()
+        };
 
-        // append components to secondary indices (2-way merge)
+        // insert time into primary index and update time range approriately
+        times.insert(row_nr, time.as_i64());
+        time_range.add(time);
+
+        // insert components into secondary indices (2-way merge)
 
         // 2-way merge, step1: left-to-right
         //
-        // push new row indices to their associated secondary index
+        // insert new row indices into their associated secondary index
         for (name, row_idx) in row_indices {
             let index = indices
                 .entry(*name)
                 .or_insert_with(|| vec![None; times.len().saturating_sub(1)]);
-            index.push(Some(*row_idx));
+            index.insert(row_nr, Some(*row_idx));
         }
 
         // 2-way merge, step2: right-to-left
@@ -677,13 +756,10 @@ impl IndexBucket {
         // fill unimpacted secondary indices with null values
         for (name, index) in &mut *indices {
             if !row_indices.contains_key(name) {
-                index.push(None);
+                index.insert(row_nr, None);
             }
         }
 
-        // TODO(#433): re_datastore: properly handle already sorted data during insertion
-        *is_sorted = false;
-
         #[cfg(debug_assertions)]
         {
             drop(guard); // sanity checking will grab the lock!
@@ -1195,10 +1271,8 @@ impl ComponentBucket {
         for (timeline, &time) in time_point {
             self.time_ranges
                 .entry(*timeline)
-                .and_modify(|range| {
-                    *range = TimeRange::new(range.min.min(time), range.max.max(time));
-                })
-                .or_insert_with(|| TimeRange::new(time, time));
+                .and_modify(|range| range.add(time))
+                .or_insert_with(|| TimeRange::point(time));
         }
 
         self.total_rows += 1;
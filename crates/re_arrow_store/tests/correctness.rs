@@ -311,10 +311,7 @@ fn range_join_across_single_row_impl(store: &mut DataStore) {
     store.insert(&bundle).unwrap();
 
     let timeline_frame_nr = Timeline::new("frame_nr", TimeType::Sequence);
-    let query = RangeQuery::new(
-        timeline_frame_nr,
-        TimeRange::new(i64::MIN.into(), i64::MAX.into()),
-    );
+    let query = RangeQuery::new(timeline_frame_nr, TimeRange::EVERYTHING);
     let components = [InstanceKey::name(), Point2D::name(), ColorRGBA::name()];
     let dfs = polars_util::range_components(
         store,
@@ -444,6 +441,70 @@ fn gc_correct() {
 
 // ---
 
+#[test]
+fn deduplicated_entities() {
+    init_logs();
+
+    let ent_static = EntityPath::from("robot/base_link");
+    let ent_other = EntityPath::from("robot/camera");
+
+    let mut store = DataStore::new(
+        InstanceKey::name(),
+        DataStoreConfig {
+            deduplicated_entities: [EntityPath::from("robot/base_link")].into(),
+            ..Default::default()
+        },
+    );
+
+    let points = build_some_point2d(3);
+    for frame_nr in 0..10 {
+        for ent_path in [&ent_static, &ent_other] {
+            let bundle = test_bundle!(ent_path @ [build_frame_nr(frame_nr.into())] => [
+                points.clone(),
+            ]);
+            store.insert(&bundle).unwrap();
+        }
+    }
+
+    // One row for the points and one for the `MsgId` of the deduplicated entity,
+    // two of each for the other one.
+    assert_eq!(2 + 2 * 10, store.total_temporal_component_rows());
+
+    // A change is always kept…
+    let bundle = test_bundle!(ent_static @ [build_frame_nr(10.into())] => [
+        build_some_point2d(3),
+    ]);
+    store.insert(&bundle).unwrap();
+    assert_eq!(2 + 2 + 2 * 10, store.total_temporal_component_rows());
+
+    // …and so is going back in time.
+    let bundle = test_bundle!(ent_static @ [build_frame_nr(5.into())] => [points.clone()]);
+    store.insert(&bundle).unwrap();
+    let bundle = test_bundle!(ent_static @ [build_frame_nr(4.into())] => [points.clone()]);
+    store.insert(&bundle).unwrap();
+    assert_eq!(2 + 2 + 4 + 2 * 10, store.total_temporal_component_rows());
+
+    if let err @ Err(_) = store.sanity_check() {
+        store.sort_indices_if_needed();
+        eprintln!("{store}");
+        err.unwrap();
+    }
+
+    // The collapsed run is still there for latest-at queries.
+    let timeline_frame_nr = Timeline::new("frame_nr", TimeType::Sequence);
+    let df = polars_util::latest_components(
+        &store,
+        &LatestAtQuery::new(timeline_frame_nr, 3.into()),
+        &ent_static,
+        &[Point2D::name()],
+        &JoinType::Outer,
+    )
+    .unwrap();
+    assert_eq!(points.len(), df.height());
+}
+
+// ---
+
 pub fn init_logs() {
     static INIT: AtomicBool = AtomicBool::new(false);
 
@@ -968,6 +968,43 @@ fn gc_impl(store: &mut DataStore) {
     }
 }
 
+// --- Generation ---
+
+#[test]
+fn generation() {
+    init_logs();
+
+    for config in re_arrow_store::test_util::all_configs() {
+        let mut store = DataStore::new(InstanceKey::name(), config.clone());
+        generation_impl(&mut store);
+    }
+}
+
+fn generation_impl(store: &mut DataStore) {
+    let ent_path = EntityPath::from("this/that");
+
+    let gen0 = store.generation();
+    assert_eq!(gen0, store.generation());
+
+    let bundle = test_bundle!(ent_path @ [build_frame_nr(1.into())] => [build_some_rects(2)]);
+    store.insert(&bundle).unwrap();
+
+    let gen1 = store.generation();
+    assert_ne!(gen0, gen1);
+    assert_eq!(gen1, store.generation());
+
+    // Reads don't change the generation.
+    _ = store.to_dataframe();
+    assert_eq!(gen1, store.generation());
+
+    store.gc(
+        GarbageCollectionTarget::DropAtLeastPercentage(1.0),
+        Timeline::new("frame_nr", TimeType::Sequence),
+        MsgId::name(),
+    );
+    assert_ne!(gen1, store.generation());
+}
+
 // ---
 
 pub fn init_logs() {
@@ -139,6 +139,47 @@ fn pathological_bucket_topology() {
     }
 }
 
+// Backfilled and post-processed data is often logged out of order: make sure we keep the
+// indices sorted as we go instead of leaving it to the next query.
+#[test]
+fn out_of_order_insertion() {
+    use rand::seq::SliceRandom as _;
+
+    init_logs();
+
+    let ent_path = EntityPath::from("this/that");
+    for index_bucket_nb_rows in [0, 1, 10, 1024] {
+        let mut store = DataStore::new(
+            InstanceKey::name(),
+            DataStoreConfig {
+                index_bucket_nb_rows,
+                ..Default::default()
+            },
+        );
+
+        let mut frames = (0..100).chain(40..60).collect::<Vec<i64>>();
+        frames.shuffle(&mut rand::thread_rng());
+        for frame_nr in frames {
+            let msg = MsgBundle::new(
+                MsgId::random(),
+                ent_path.clone(),
+                TimePoint::from([build_frame_nr(frame_nr.into())]),
+                vec![build_some_instances(1).try_into().unwrap()],
+            );
+            store.insert(&msg).unwrap();
+        }
+
+        assert!(
+            store
+                .iter_indices()
+                .flat_map(|(_, table)| table.iter_buckets())
+                .all(|bucket| bucket.is_sorted()),
+            "out-of-order insertion left unsorted buckets: {store}",
+        );
+        store.sanity_check().unwrap();
+    }
+}
+
 fn init_logs() {
     static INIT: AtomicBool = AtomicBool::new(false);
 
@@ -0,0 +1,259 @@
+//! Logs the video of RTSP/IP cameras, or any other GStreamer source, to Rerun.
+//!
+//! Cameras that stream MJPEG are passed through without re-encoding.
+//! Everything else (e.g. H.264) is decoded and re-encoded as JPEG,
+//! since the Rerun Viewer can't decode video streams.
+
+use std::{str::FromStr as _, sync::mpsc, time::Duration};
+
+use anyhow::Context as _;
+use gstreamer as gst;
+use gstreamer::prelude::*;
+use gstreamer_app as gst_app;
+
+use rerun::external::re_log;
+use rerun::{components::Tensor, EntityPath, MsgSender, Session};
+
+#[derive(Debug, clap::Parser)]
+#[clap(author, version, about)]
+struct Args {
+    #[command(flatten)]
+    rerun: rerun::clap::RerunArgs,
+
+    /// The cameras to log, e.g. `rtsp://192.168.1.10/stream1`.
+    ///
+    /// Any uri that GStreamer can play works, including `file://` and `http://`.
+    #[clap(required = true)]
+    uris: Vec<String>,
+
+    /// Names of the cameras, in the same order as the uris, e.g. `--names front,back`.
+    ///
+    /// Each camera is logged to `cameras/<name>`. Defaults to `cameras/0`, `cameras/1`, etc.
+    #[clap(long, value_delimiter = ',')]
+    names: Vec<String>,
+
+    /// Quality (0-100) of the JPEG encoding, for cameras that don't stream MJPEG.
+    #[clap(long, default_value_t = 85)]
+    jpeg_quality: i32,
+
+    /// How long to wait before reconnecting to a camera that went away, e.g. `2.5`.
+    #[clap(long, default_value_t = 2.0)]
+    reconnect_delay_sec: f32,
+}
+
+/// A JPEG-encoded video frame.
+struct Frame {
+    entity_path: EntityPath,
+    jpeg: Vec<u8>,
+}
+
+fn main() -> anyhow::Result<()> {
+    re_log::setup_native_logging();
+
+    use clap::Parser as _;
+    let args = Args::parse();
+
+    gst::init().context("Failed to initialize GStreamer")?;
+
+    let mut session = Session::init("camera_bridge", true);
+
+    let should_spawn = args.rerun.on_startup(&mut session);
+    if should_spawn {
+        return session
+            .spawn(move |mut session| run(&mut session, &args))
+            .map_err(Into::into);
+    }
+
+    run(&mut session, &args)?;
+
+    args.rerun.on_teardown(&mut session)?;
+
+    Ok(())
+}
+
+fn run(session: &mut Session, args: &Args) -> anyhow::Result<()> {
+    let (tx, rx) = mpsc::sync_channel(2 * args.uris.len());
+
+    for (i, uri) in args.uris.iter().enumerate() {
+        let name = args.names.get(i).cloned().unwrap_or_else(|| i.to_string());
+        let entity_path = EntityPath::from(format!("cameras/{name}"));
+        let camera = Camera {
+            uri: uri.clone(),
+            entity_path,
+            jpeg_quality: args.jpeg_quality,
+            reconnect_delay: Duration::from_secs_f32(args.reconnect_delay_sec),
+        };
+        let tx = tx.clone();
+        std::thread::Builder::new()
+            .name(format!("camera {name}"))
+            .spawn(move || camera.run(&tx))
+            .context("Failed to spawn camera thread")?;
+    }
+    drop(tx);
+
+    // The session isn't thread-safe, so we log all frames from here:
+    for Frame { entity_path, jpeg } in rx {
+        match Tensor::tensor_from_jpeg_bytes(jpeg) {
+            Ok(tensor) => {
+                MsgSender::new(entity_path)
+                    .with_component(&[tensor])?
+                    .send(session)?;
+            }
+            Err(err) => {
+                re_log::warn_once!("Bad JPEG from {entity_path}: {err}");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+struct Camera {
+    uri: String,
+    entity_path: EntityPath,
+    jpeg_quality: i32,
+    reconnect_delay: Duration,
+}
+
+impl Camera {
+    /// Keeps the camera streaming until the logging side hangs up.
+    fn run(&self, tx: &mpsc::SyncSender<Frame>) {
+        loop {
+            re_log::info!("Connecting to {}…", self.uri);
+            match self.stream(tx) {
+                Ok(StreamEnd::EndOfStream) => {
+                    re_log::info!("{} ended", self.uri);
+                }
+                Ok(StreamEnd::Hangup) => {
+                    return;
+                }
+                Err(err) => {
+                    re_log::warn!("{}: {err:#}", self.uri);
+                }
+            }
+            std::thread::sleep(self.reconnect_delay);
+        }
+    }
+
+    fn stream(&self, tx: &mpsc::SyncSender<Frame>) -> anyhow::Result<StreamEnd> {
+        let pipeline = gst::Pipeline::new(None);
+
+        // Stop decoding at JPEG, so that MJPEG cameras are passed through as-is:
+        let source = gst::ElementFactory::make("uridecodebin")
+            .property("uri", &self.uri)
+            .property("caps", gst::Caps::from_str("image/jpeg;video/x-raw")?)
+            .build()?;
+
+        // Only keep the latest frame, so that a slow viewer doesn't make us fall behind:
+        let sink = gst_app::AppSink::builder()
+            .caps(&gst::Caps::builder("image/jpeg").build())
+            .max_buffers(1)
+            .drop(true)
+            .sync(false)
+            .build();
+
+        pipeline.add_many(&[&source, sink.upcast_ref()])?;
+
+        let pipeline_weak = pipeline.downgrade();
+        let sink_weak = sink.downgrade();
+        let jpeg_quality = self.jpeg_quality;
+        source.connect_pad_added(move |_source, pad| {
+            if let (Some(pipeline), Some(sink)) = (pipeline_weak.upgrade(), sink_weak.upgrade()) {
+                if let Err(err) = link_video_pad(&pipeline, pad, &sink, jpeg_quality) {
+                    re_log::warn!("Failed to link video stream: {err:#}");
+                }
+            }
+        });
+
+        pipeline.set_state(gst::State::Playing)?;
+        let result = self.pull_frames(&pipeline, &sink, tx);
+        pipeline.set_state(gst::State::Null).ok();
+        result
+    }
+
+    fn pull_frames(
+        &self,
+        pipeline: &gst::Pipeline,
+        sink: &gst_app::AppSink,
+        tx: &mpsc::SyncSender<Frame>,
+    ) -> anyhow::Result<StreamEnd> {
+        // `pull_sample` fails on both errors and end-of-stream:
+        while let Ok(sample) = sink.pull_sample() {
+            let buffer = sample.buffer().context("Sample without buffer")?;
+            let map = buffer.map_readable()?;
+            let frame = Frame {
+                entity_path: self.entity_path.clone(),
+                jpeg: map.as_slice().to_vec(),
+            };
+            if tx.send(frame).is_err() {
+                return Ok(StreamEnd::Hangup);
+            }
+        }
+
+        let error = pipeline.bus().and_then(|bus| {
+            bus.timed_pop_filtered(gst::ClockTime::ZERO, &[gst::MessageType::Error])
+        });
+        if let Some(message) = error {
+            if let gst::MessageView::Error(err) = message.view() {
+                anyhow::bail!("{} ({:?})", err.error(), err.debug());
+            }
+        }
+
+        Ok(StreamEnd::EndOfStream)
+    }
+}
+
+enum StreamEnd {
+    /// The camera stopped streaming.
+    EndOfStream,
+
+    /// We have stopped logging.
+    Hangup,
+}
+
+/// Link a newly decoded stream to the sink, re-encoding it as JPEG if needed.
+fn link_video_pad(
+    pipeline: &gst::Pipeline,
+    pad: &gst::Pad,
+    sink: &gst_app::AppSink,
+    jpeg_quality: i32,
+) -> anyhow::Result<()> {
+    let sink_pad = sink
+        .static_pad("sink")
+        .context("AppSink without sink pad")?;
+    if sink_pad.is_linked() {
+        return Ok(()); // We only log the first video stream of each camera.
+    }
+
+    let caps = pad.current_caps().context("Pad without caps")?;
+    let media_type = caps
+        .structure(0)
+        .map(|structure| structure.name().to_owned())
+        .unwrap_or_default();
+
+    match media_type.as_str() {
+        "image/jpeg" => {
+            re_log::debug!("Passing through MJPEG");
+            pad.link(&sink_pad)?;
+        }
+        "video/x-raw" => {
+            re_log::debug!("Re-encoding video as JPEG");
+            let convert = gst::ElementFactory::make("videoconvert").build()?;
+            let encode = gst::ElementFactory::make("jpegenc")
+                .property("quality", jpeg_quality)
+                .build()?;
+            pipeline.add_many(&[&convert, &encode])?;
+            gst::Element::link_many(&[&convert, &encode, sink.upcast_ref()])?;
+            convert.sync_state_with_parent()?;
+            encode.sync_state_with_parent()?;
+
+            let convert_pad = convert
+                .static_pad("sink")
+                .context("videoconvert without sink pad")?;
+            pad.link(&convert_pad)?;
+        }
+        _ => {} // e.g. audio
+    }
+
+    Ok(())
+}
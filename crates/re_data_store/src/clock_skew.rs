@@ -0,0 +1,329 @@
+use std::collections::BTreeMap;
+
+use re_log_types::{ApplicationId, Duration, MsgId, Time, TimeInt, TimePoint, TimeType, Timeline};
+
+/// Data with timestamps that are far off from the rest, e.g. from a device with a bad real-time clock.
+///
+/// Such data is held back instead of being added to the store, where it would stretch the timelines
+/// to e.g. the year 1970. Once the clock of the application is corrected (see
+/// [`crate::LogDb::correct_clock`]) it is added after all.
+///
+/// Only wall-clock timestamps are checked and corrected, see [`is_wall_clock`].
+pub struct ClockSkew {
+    /// Timestamps further than this from the rest of the data on their timeline are considered skewed.
+    pub max_skew: Duration,
+
+    /// The range of the (corrected) wall-clock timestamps of the data added so far, per timeline.
+    accepted: BTreeMap<Timeline, (Time, Time)>,
+
+    /// Added to the wall-clock timestamps of each application.
+    corrections: BTreeMap<ApplicationId, Duration>,
+
+    held_back: BTreeMap<ApplicationId, SkewedData>,
+}
+
+impl Default for ClockSkew {
+    fn default() -> Self {
+        Self {
+            max_skew: Duration::from_secs(24.0 * 60.0 * 60.0),
+            accepted: Default::default(),
+            corrections: Default::default(),
+            held_back: Default::default(),
+        }
+    }
+}
+
+/// The data of one application that is held back because of its timestamps.
+#[derive(Clone, Debug)]
+pub struct SkewedData {
+    /// In the order they arrived.
+    pub msg_ids: Vec<MsgId>,
+
+    /// How many of the oldest held back messages were dropped to free up memory,
+    /// see [`crate::LogDb::purge_fraction_of_ram`].
+    pub num_dropped: usize,
+
+    /// What to add to the timestamps to bring them in line with the rest of the data,
+    /// estimated from the latest held back message.
+    pub suggested_correction: Duration,
+}
+
+impl ClockSkew {
+    /// The offset to add to the timestamps of the message, or `None` if it should be held back.
+    ///
+    /// `reference` is what to compare with when there is no other data on a timeline yet,
+    /// see [`reference_range`].
+    pub(crate) fn check(
+        &mut self,
+        application_id: &ApplicationId,
+        msg_id: MsgId,
+        time_point: &TimePoint,
+        reference: Option<(Time, Time)>,
+    ) -> Option<Duration> {
+        let correction = self.correction(application_id);
+        let times: Vec<(Timeline, Time)> = time_point
+            .iter()
+            .filter(|(timeline, time)| is_wall_clock(timeline, **time))
+            .map(|(timeline, time)| {
+                (
+                    *timeline,
+                    Time::from_ns_since_epoch(time.as_i64()) + correction,
+                )
+            })
+            .collect();
+
+        // The skewed timestamp, and the closest one we accept:
+        let skewed = times.iter().find_map(|(timeline, time)| {
+            let (min, max) = self.accepted.get(timeline).copied().or(reference)?;
+            if *time < min.saturating_sub(self.max_skew) {
+                Some((*time, min))
+            } else if max.saturating_add(self.max_skew) < *time {
+                Some((*time, max))
+            } else {
+                None
+            }
+        });
+        if let Some((skewed, target)) = skewed {
+            let held_back = self
+                .held_back
+                .entry(application_id.clone())
+                .or_insert_with(|| SkewedData {
+                    msg_ids: vec![],
+                    num_dropped: 0,
+                    suggested_correction: Duration::ZERO,
+                });
+            held_back.msg_ids.push(msg_id);
+            held_back.suggested_correction = round_to_seconds(target - skewed);
+            return None;
+        }
+
+        for (timeline, time) in times {
+            let (min, max) = self.accepted.entry(timeline).or_insert((time, time));
+            *min = (*min).min(time);
+            *max = (*max).max(time);
+        }
+        Some(correction)
+    }
+
+    /// Currently added to the wall-clock timestamps of data from this application.
+    pub fn correction(&self, application_id: &ApplicationId) -> Duration {
+        self.corrections
+            .get(application_id)
+            .copied()
+            .unwrap_or(Duration::ZERO)
+    }
+
+    /// The applications with held back data.
+    pub fn skewed_applications(&self) -> impl Iterator<Item = (&ApplicationId, &SkewedData)> {
+        self.held_back.iter()
+    }
+
+    /// Add `correction` to the timestamps of this application from now on,
+    /// and return its held back messages to check them again.
+    pub(crate) fn correct(
+        &mut self,
+        application_id: &ApplicationId,
+        correction: Duration,
+    ) -> Vec<MsgId> {
+        let total = self.correction(application_id).saturating_add(correction);
+        self.corrections.insert(application_id.clone(), total);
+        self.held_back
+            .remove(application_id)
+            .map(|held_back| held_back.msg_ids)
+            .unwrap_or_default()
+    }
+
+    /// Drop the oldest fraction of the held back data of each application, to free up memory.
+    ///
+    /// Returns the messages to forget.
+    pub(crate) fn purge_fraction(&mut self, fraction_to_purge: f32) -> Vec<MsgId> {
+        let mut dropped = vec![];
+        for held_back in self.held_back.values_mut() {
+            let num_msgs = held_back.msg_ids.len();
+            let num_to_drop = ((num_msgs as f32 * fraction_to_purge).ceil() as usize).min(num_msgs);
+            dropped.extend(held_back.msg_ids.drain(..num_to_drop));
+            held_back.num_dropped += num_to_drop;
+        }
+        dropped
+    }
+}
+
+/// 2000-01-01 00:00:00 UTC.
+const YEAR_2000_NS: i64 = 946_684_800 * 1_000_000_000;
+
+/// What to compare wall-clock timestamps with when there is no other data on a timeline yet.
+///
+/// Live data should be from about `now`. Recorded data (e.g. from a file) could be from any time
+/// before that, but not from before the year 2000: that is what a clock that was never set says.
+/// Otherwise whichever application came first would decide which data is skewed.
+pub(crate) fn reference_range(now: Time, is_live: bool) -> (Time, Time) {
+    if is_live {
+        (now, now)
+    } else {
+        (Time::from_ns_since_epoch(YEAR_2000_NS), now)
+    }
+}
+
+/// Bad clocks are usually off by a lot, so there is no point in suggesting sub-second corrections.
+fn round_to_seconds(duration: Duration) -> Duration {
+    const NANOS_PER_SEC: i64 = 1_000_000_000;
+    let nanos = duration.as_nanos();
+    let mut secs = nanos / NANOS_PER_SEC;
+    let remainder = nanos % NANOS_PER_SEC;
+    if NANOS_PER_SEC / 2 <= remainder {
+        secs += 1;
+    } else if remainder <= -NANOS_PER_SEC / 2 {
+        secs -= 1;
+    }
+    Duration::from_nanos(secs.saturating_mul(NANOS_PER_SEC))
+}
+
+/// Is this a timestamp from a (possibly bad) wall clock?
+///
+/// [`Timeline::log_time`] is always set from the wall clock of the SDK. Other [`TimeType::Time`]
+/// timelines may just as well be relative, e.g. the seconds since a simulation started,
+/// so we only count their timestamps if they are after the year 2000.
+fn is_wall_clock(timeline: &Timeline, time: TimeInt) -> bool {
+    timeline.typ() == TimeType::Time
+        && (*timeline == Timeline::log_time() || YEAR_2000_NS <= time.as_i64())
+}
+
+/// Add `offset` to the wall-clock timestamps.
+pub(crate) fn shift_time_point(time_point: &TimePoint, offset: Duration) -> TimePoint {
+    time_point
+        .iter()
+        .map(|(&timeline, &time)| {
+            let time = if is_wall_clock(&timeline, time) {
+                TimeInt::from(time.as_i64().saturating_add(offset.as_nanos()))
+            } else {
+                time
+            };
+            (timeline, time)
+        })
+        .collect()
+}
+
+#[test]
+fn test_clock_skew() {
+    let time_point = |secs: f64| -> TimePoint {
+        [(
+            Timeline::log_time(),
+            Time::from_seconds_since_epoch(secs).into(),
+        )]
+        .into()
+    };
+    let good = ApplicationId("good".to_owned());
+    let bad = ApplicationId("bad".to_owned());
+    let year_2023 = 1_680_000_000.0;
+
+    let mut clock_skew = ClockSkew::default();
+    let check = |clock_skew: &mut ClockSkew, application_id: &ApplicationId, secs: f64| {
+        clock_skew.check(application_id, MsgId::random(), &time_point(secs), None)
+    };
+    assert_eq!(
+        check(&mut clock_skew, &good, year_2023),
+        Some(Duration::ZERO)
+    );
+    assert_eq!(
+        check(&mut clock_skew, &good, year_2023 + 60.0),
+        Some(Duration::ZERO)
+    );
+
+    // An RTC that was never set:
+    assert_eq!(check(&mut clock_skew, &bad, 5.0), None);
+    let (application_id, held_back) = clock_skew.skewed_applications().next().unwrap();
+    assert_eq!(application_id, &bad);
+    assert_eq!(held_back.msg_ids.len(), 1);
+    assert_eq!(
+        held_back.suggested_correction,
+        Duration::from_nanos((year_2023 as i64 - 5) * 1_000_000_000)
+    );
+
+    let suggested_correction = held_back.suggested_correction;
+    assert_eq!(clock_skew.correct(&bad, suggested_correction).len(), 1);
+    assert_eq!(clock_skew.skewed_applications().count(), 0);
+    assert_eq!(
+        check(&mut clock_skew, &bad, 6.0),
+        Some(suggested_correction)
+    );
+
+    // Sequence timelines are never skewed:
+    let frame = [(Timeline::new_sequence("frame"), TimeInt::from(0))].into();
+    assert_eq!(
+        clock_skew.check(&bad, MsgId::random(), &frame, None),
+        Some(suggested_correction)
+    );
+
+    // A recording where the application with the bad clock comes first:
+    let mut clock_skew = ClockSkew::default();
+    let recorded = Some(reference_range(
+        Time::from_seconds_since_epoch(year_2023 + 3600.0),
+        false,
+    ));
+    for secs in [5.0, 6.0] {
+        assert_eq!(
+            clock_skew.check(&bad, MsgId::random(), &time_point(secs), recorded),
+            None
+        );
+    }
+    assert_eq!(
+        clock_skew.check(&good, MsgId::random(), &time_point(year_2023), recorded),
+        Some(Duration::ZERO)
+    );
+
+    // Freeing up memory drops the oldest held back data:
+    assert_eq!(clock_skew.purge_fraction(0.5).len(), 1);
+    let (_, held_back) = clock_skew.skewed_applications().next().unwrap();
+    assert_eq!(held_back.msg_ids.len(), 1);
+    assert_eq!(held_back.num_dropped, 1);
+}
+
+#[test]
+fn test_clock_skew_with_relative_time() {
+    let year_2023 = Time::from_seconds_since_epoch(1_680_000_000.0);
+    let sim_time = Timeline::new("sim_time", TimeType::Time);
+    let time_point = |log_time: Time, sim_secs: f64| -> TimePoint {
+        [
+            (Timeline::log_time(), log_time.into()),
+            (sim_time, Time::from_seconds_since_epoch(sim_secs).into()),
+        ]
+        .into()
+    };
+    let app = ApplicationId("sim".to_owned());
+    let live = Some(reference_range(year_2023, true));
+
+    // Seconds since the simulation started are neither compared with the viewer's clock,
+    // nor with the `log_time` next to them:
+    let mut clock_skew = ClockSkew::default();
+    for sim_secs in [1.0, 2.0, 3.0] {
+        assert_eq!(
+            clock_skew.check(
+                &app,
+                MsgId::random(),
+                &time_point(year_2023, sim_secs),
+                live
+            ),
+            Some(Duration::ZERO)
+        );
+    }
+    assert_eq!(clock_skew.skewed_applications().count(), 0);
+
+    // A bad `log_time` is still caught:
+    let bad_log_time = Time::from_seconds_since_epoch(5.0);
+    assert_eq!(
+        clock_skew.check(&app, MsgId::random(), &time_point(bad_log_time, 4.0), live),
+        None
+    );
+
+    // …and correcting it leaves `sim_time` alone:
+    let correction = Duration::from_secs(1000.0);
+    let shifted = shift_time_point(&time_point(bad_log_time, 4.0), correction);
+    assert_eq!(
+        shifted.get(&sim_time),
+        Some(&Time::from_seconds_since_epoch(4.0).into())
+    );
+    assert_eq!(
+        shifted.get(&Timeline::log_time()),
+        Some(&Time::from_seconds_since_epoch(1005.0).into())
+    );
+}
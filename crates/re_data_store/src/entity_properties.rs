@@ -39,6 +39,15 @@ pub struct EntityProperties {
     pub visible_history: ExtraQueryHistory,
     pub interactive: bool,
     pinhole_image_plane_distance: Option<ordered_float::NotNan<f32>>,
+
+    /// None indicates the user never edited this field, and that it is inherited.
+    missing_scalars: Option<MissingScalars>,
+
+    /// None indicates the user never edited this field, and that it is inherited.
+    angular: Option<bool>,
+
+    /// None indicates the user never edited this field, and that it is inherited.
+    point_coloring: Option<PointColoring>,
 }
 
 impl EntityProperties {
@@ -64,6 +73,36 @@ impl EntityProperties {
         self.pinhole_image_plane_distance = ordered_float::NotNan::new(distance).ok();
     }
 
+    /// How to plot NaN and infinite scalars.
+    pub fn missing_scalars(&self) -> MissingScalars {
+        self.missing_scalars.unwrap_or_default()
+    }
+
+    /// see `missing_scalars()`
+    pub fn set_missing_scalars(&mut self, missing_scalars: MissingScalars) {
+        self.missing_scalars = Some(missing_scalars);
+    }
+
+    /// Are these scalars angles, which wrap around at ±π (or ±180°)?
+    pub fn angular(&self) -> bool {
+        self.angular.unwrap_or(false)
+    }
+
+    /// see `angular()`
+    pub fn set_angular(&mut self, angular: bool) {
+        self.angular = Some(angular);
+    }
+
+    /// How to color points, e.g. by height instead of by their logged colors.
+    pub fn point_coloring(&self) -> PointColoring {
+        self.point_coloring.unwrap_or_default()
+    }
+
+    /// see `point_coloring()`
+    pub fn set_point_coloring(&mut self, point_coloring: PointColoring) {
+        self.point_coloring = Some(point_coloring);
+    }
+
     /// Multiply/and these together.
     pub fn with_child(&self, child: &Self) -> Self {
         Self {
@@ -73,6 +112,9 @@ impl EntityProperties {
             pinhole_image_plane_distance: child
                 .pinhole_image_plane_distance
                 .or(self.pinhole_image_plane_distance),
+            missing_scalars: child.missing_scalars.or(self.missing_scalars),
+            angular: child.angular.or(self.angular),
+            point_coloring: child.point_coloring.or(self.point_coloring),
         }
     }
 }
@@ -84,6 +126,113 @@ impl Default for EntityProperties {
             visible_history: ExtraQueryHistory::default(),
             interactive: true,
             pinhole_image_plane_distance: None,
+            missing_scalars: None,
+            angular: None,
+            point_coloring: None,
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// How to plot scalars that are NaN or infinite, e.g. because a sensor had no reading.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum MissingScalars {
+    /// Leave a gap in the line.
+    #[default]
+    Gap,
+
+    /// Skip the sample, connecting its neighbors.
+    Connect,
+
+    /// Plot the sample as zero.
+    Zero,
+}
+
+impl MissingScalars {
+    pub const ALL: [Self; 3] = [Self::Gap, Self::Connect, Self::Zero];
+}
+
+impl std::fmt::Display for MissingScalars {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Gap => "Gap".fmt(f),
+            Self::Connect => "Connect".fmt(f),
+            Self::Zero => "Zero".fmt(f),
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// How to color the points of a point cloud, without having to log them again.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct PointColoring {
+    pub source: PointColorSource,
+
+    /// Not used for [`PointColorSource::Logged`].
+    pub colormap: Colormap,
+
+    /// The values at the ends of the colormap, or `None` for the range of the data.
+    ///
+    /// Not used for [`PointColorSource::Time`], which always spans the visible history.
+    pub range: Option<[ordered_float::NotNan<f32>; 2]>,
+}
+
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum PointColorSource {
+    /// The logged colors, or the colors of the annotation context.
+    #[default]
+    Logged,
+
+    /// The Z coordinate of each point.
+    Height,
+
+    /// The [`re_log_types::component_types::Scalar`] logged with each point.
+    Intensity,
+
+    /// When each point was logged, most useful with a visible history.
+    Time,
+}
+
+impl PointColorSource {
+    pub const ALL: [Self; 4] = [Self::Logged, Self::Height, Self::Intensity, Self::Time];
+}
+
+impl std::fmt::Display for PointColorSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Logged => "Logged color".fmt(f),
+            Self::Height => "Height".fmt(f),
+            Self::Intensity => "Intensity".fmt(f),
+            Self::Time => "Time".fmt(f),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Colormap {
+    #[default]
+    Turbo,
+    Viridis,
+    Grayscale,
+}
+
+impl Colormap {
+    pub const ALL: [Self; 3] = [Self::Turbo, Self::Viridis, Self::Grayscale];
+}
+
+impl std::fmt::Display for Colormap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Turbo => "Turbo".fmt(f),
+            Self::Viridis => "Viridis".fmt(f),
+            Self::Grayscale => "Grayscale".fmt(f),
         }
     }
 }
@@ -100,6 +249,17 @@ pub struct ExtraQueryHistory {
 
     /// Zero = off.
     pub sequences: i64,
+
+    /// Show at most one entry of the history per this many nanoseconds,
+    /// e.g. to keep long trails of high-rate data readable and fast.
+    ///
+    /// Zero = off.
+    pub decimation_nanos: i64,
+
+    /// Show at most one entry of the history per this many sequence numbers.
+    ///
+    /// Zero = off.
+    pub decimation_sequences: i64,
 }
 
 impl ExtraQueryHistory {
@@ -108,6 +268,8 @@ impl ExtraQueryHistory {
         Self {
             nanos: self.nanos.max(child.nanos),
             sequences: self.sequences.max(child.sequences),
+            decimation_nanos: self.decimation_nanos.max(child.decimation_nanos),
+            decimation_sequences: self.decimation_sequences.max(child.decimation_sequences),
         }
     }
 }
@@ -4,15 +4,19 @@
 #![doc = document_features::document_features!()]
 //!
 
+mod clock_skew;
 pub mod entity_properties;
 pub mod entity_tree;
 mod instance_path;
 pub mod log_db;
+mod trace_spans;
 
+pub use clock_skew::{ClockSkew, SkewedData};
 pub use entity_properties::*;
 pub use entity_tree::*;
 pub use instance_path::*;
-pub use log_db::LogDb;
+pub use log_db::{LogDb, PurgeStats};
+pub use trace_spans::TraceSpans;
 
 use re_log_types::msg_bundle;
 
@@ -31,6 +35,9 @@ pub enum Error {
 
     #[error(transparent)]
     WriteError(#[from] re_arrow_store::WriteError),
+
+    #[error(transparent)]
+    TensorCastError(#[from] re_log_types::component_types::TensorCastError),
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
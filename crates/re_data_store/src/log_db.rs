@@ -1,15 +1,22 @@
+use std::collections::{BTreeMap, BTreeSet};
+
 use nohash_hasher::IntMap;
 
-use re_arrow_store::{DataStoreConfig, GarbageCollectionTarget, TimeInt};
+use re_arrow_store::{DataStoreConfig, DataStoreStats, GarbageCollectionTarget, TimeInt};
 use re_log_types::{
-    component_types::InstanceKey,
+    component_types::{InstanceKey, Tensor, Transform},
+    external::arrow2::array::{Array, BooleanArray, ListArray, StructArray},
     external::arrow2_convert::deserialize::arrow_array_deserialize_iterator,
-    msg_bundle::{Component as _, ComponentBundle, MsgBundle},
-    ArrowMsg, BeginRecordingMsg, ComponentPath, EntityPath, EntityPathHash, EntityPathOpMsg,
-    LogMsg, MsgId, PathOp, RecordingId, RecordingInfo, TimePoint, Timeline,
+    msg_bundle::{Component as _, ComponentBundle, MsgBundle, MsgBundleError},
+    ApplicationId, ArrowMsg, BeginRecordingMsg, ComponentPath, Duration, EntityPath,
+    EntityPathHash, EntityPathOpMsg, LogMsg, MsgId, PathOp, RecordingId, RecordingInfo, Time,
+    TimePoint, Timeline,
 };
 
-use crate::{Error, TimesPerTimeline};
+use crate::{
+    clock_skew::{reference_range, shift_time_point},
+    ClockSkew, Error, TimesPerTimeline, TraceSpans,
+};
 
 // ----------------------------------------------------------------------------
 
@@ -26,6 +33,21 @@ pub struct EntityDb {
 
     /// Stores all components for all entities for all timelines.
     pub data_store: re_arrow_store::DataStore,
+
+    /// The latest [`Timeline::log_time`] of each entity, used to show how fresh live data is.
+    latest_log_times: IntMap<EntityPathHash, Time>,
+
+    /// The first [`Timeline::log_time`] of each entity, used to tell streams from one-off data.
+    first_log_times: IntMap<EntityPathHash, Time>,
+
+    /// Which applications logged to each entity, for when several log to the same recording.
+    applications: IntMap<EntityPathHash, BTreeSet<ApplicationId>>,
+
+    /// The times at which a [`Transform`] was logged, on each timeline.
+    transform_times: BTreeMap<Timeline, BTreeSet<TimeInt>>,
+
+    /// See [`Self::transform_generation`].
+    transform_generation: u64,
 }
 
 impl Default for EntityDb {
@@ -42,6 +64,11 @@ impl Default for EntityDb {
                     ..Default::default()
                 },
             ),
+            latest_log_times: Default::default(),
+            first_log_times: Default::default(),
+            applications: Default::default(),
+            transform_times: Default::default(),
+            transform_generation: 0,
         }
     }
 }
@@ -52,21 +79,116 @@ impl EntityDb {
         self.entity_path_from_hash.get(entity_path_hash)
     }
 
+    /// When the latest data of this entity was logged, according to the clock of the logging side.
+    ///
+    /// `None` if the entity has no data on the [`Timeline::log_time`] timeline.
+    #[inline]
+    pub fn latest_log_time(&self, entity_path: &EntityPath) -> Option<Time> {
+        self.latest_log_times.get(&entity_path.hash()).copied()
+    }
+
+    /// Has this entity been logged at more than one [`Timeline::log_time`]?
+    ///
+    /// Entities that are only logged once (e.g. a static scene) never go stale.
+    pub fn is_stream(&self, entity_path: &EntityPath) -> bool {
+        let entity_path_hash = entity_path.hash();
+        match (
+            self.first_log_times.get(&entity_path_hash),
+            self.latest_log_times.get(&entity_path_hash),
+        ) {
+            (Some(first), Some(latest)) => first < latest,
+            _ => false,
+        }
+    }
+
+    /// The latest [`Self::latest_log_time`] of all entities.
+    pub fn latest_log_time_of_any_entity(&self) -> Option<Time> {
+        self.latest_log_times.values().max().copied()
+    }
+
+    /// The applications that logged data to this entity, usually just one.
+    pub fn applications(&self, entity_path: &EntityPath) -> Option<&BTreeSet<ApplicationId>> {
+        self.applications.get(&entity_path.hash())
+    }
+
+    /// Changes whenever a [`Transform`] may have changed at a time where one was already logged,
+    /// i.e. when one is logged timeless or at the same time as an earlier one,
+    /// or when data is cleared or purged.
+    ///
+    /// A transform logged at a new time instead shows up in [`Self::latest_transform_time`].
+    /// Together they tell when transforms resolved at some time need to be resolved again.
+    #[inline]
+    pub fn transform_generation(&self) -> u64 {
+        self.transform_generation
+    }
+
+    /// The latest time at or before `time` at which any [`Transform`] was logged on the timeline.
+    ///
+    /// All transforms are the same between two such times (see also [`Self::transform_generation`]).
+    pub fn latest_transform_time(&self, timeline: &Timeline, time: TimeInt) -> Option<TimeInt> {
+        self.transform_times
+            .get(timeline)?
+            .range(..=time)
+            .next_back()
+            .copied()
+    }
+
     fn register_entity_path(&mut self, entity_path: &EntityPath) {
         self.entity_path_from_hash
             .entry(entity_path.hash())
             .or_insert_with(|| entity_path.clone());
     }
 
-    fn try_add_arrow_data_msg(&mut self, msg: &ArrowMsg) -> Result<(), Error> {
-        let msg_bundle = MsgBundle::try_from(msg).map_err(Error::MsgBundleError)?;
+    /// `time_offset` is added to the wall-clock timestamps, see [`ClockSkew`].
+    fn try_add_arrow_data_msg(
+        &mut self,
+        application_id: &ApplicationId,
+        msg: &ArrowMsg,
+        time_offset: Duration,
+    ) -> Result<(), Error> {
+        let mut msg_bundle = MsgBundle::try_from(msg).map_err(Error::MsgBundleError)?;
+        upgrade_legacy_tensors(&mut msg_bundle);
+        normalize_tensor_layouts(&mut msg_bundle)?;
+        if time_offset != Duration::ZERO {
+            msg_bundle.time_point = shift_time_point(&msg_bundle.time_point, time_offset);
+        }
+
+        let applications = self
+            .applications
+            .entry(msg_bundle.entity_path.hash())
+            .or_default();
+        if !applications.contains(application_id) {
+            applications.insert(application_id.clone());
+        }
 
         for (&timeline, &time_int) in msg_bundle.time_point.iter() {
             self.times_per_timeline.insert(timeline, time_int);
         }
 
+        if msg_bundle
+            .components
+            .iter()
+            .any(|component| component.name() == Transform::name())
+        {
+            self.add_transform_time(&msg_bundle.time_point);
+        }
+
         self.register_entity_path(&msg_bundle.entity_path);
 
+        if let Some(log_time) = msg_bundle.time_point.get(&Timeline::log_time()) {
+            let log_time = Time::from_ns_since_epoch(log_time.as_i64());
+            let latest = self
+                .latest_log_times
+                .entry(msg_bundle.entity_path.hash())
+                .or_insert(log_time);
+            *latest = (*latest).max(log_time);
+            let first = self
+                .first_log_times
+                .entry(msg_bundle.entity_path.hash())
+                .or_insert(log_time);
+            *first = (*first).min(log_time);
+        }
+
         for component in &msg_bundle.components {
             let component_path =
                 ComponentPath::new(msg_bundle.entity_path.clone(), component.name());
@@ -98,7 +220,24 @@ impl EntityDb {
         self.data_store.insert(&msg_bundle).map_err(Into::into)
     }
 
+    fn add_transform_time(&mut self, time_point: &TimePoint) {
+        let mut is_new_time = !time_point.is_timeless();
+        for (&timeline, &time_int) in time_point.iter() {
+            is_new_time &= self
+                .transform_times
+                .entry(timeline)
+                .or_default()
+                .insert(time_int);
+        }
+        if !is_new_time {
+            self.transform_generation += 1;
+        }
+    }
+
     fn add_path_op(&mut self, msg_id: MsgId, time_point: &TimePoint, path_op: &PathOp) {
+        // A clear may remove transforms, now or (if recursive) of entities logged later.
+        self.transform_generation += 1;
+
         let cleared_paths = self.tree.add_path_op(msg_id, time_point, path_op);
 
         for component_path in cleared_paths {
@@ -135,8 +274,16 @@ impl EntityDb {
             times_per_timeline,
             tree,
             data_store: _, // purged before this function is called
+            latest_log_times: _,
+            first_log_times: _,
+            applications: _,
+            transform_times: _,
+            transform_generation,
         } = self;
 
+        // We don't know if any transforms were purged:
+        *transform_generation += 1;
+
         {
             crate::profile_scope!("times_per_timeline");
             times_per_timeline.purge(cutoff_times);
@@ -149,6 +296,93 @@ impl EntityDb {
     }
 }
 
+/// Upgrade [`Tensor`]s logged by older versions of Rerun (e.g. in old `.rrd` files)
+/// to the current layout, see [`Tensor::upgrade_legacy_array`].
+fn upgrade_legacy_tensors(msg_bundle: &mut MsgBundle) {
+    for component in &mut msg_bundle.components {
+        if component.name() != Tensor::name() {
+            continue;
+        }
+        let list = component.value_list();
+        if let Some(tensors) = Tensor::upgrade_legacy_array(list.values().as_ref()) {
+            let list = ListArray::<i32>::new(
+                ListArray::<i32>::default_datatype(tensors.data_type().clone()),
+                list.offsets().clone(),
+                tensors,
+                list.validity().cloned(),
+            );
+            *component = ComponentBundle::new(Tensor::name(), list);
+        }
+    }
+}
+
+/// Convert any [`Tensor`]s logged with explicit strides or in big-endian byte order
+/// (e.g. column-major or sliced numpy arrays) to the contiguous row-major layout
+/// that the rest of the viewer expects.
+fn normalize_tensor_layouts(msg_bundle: &mut MsgBundle) -> Result<(), Error> {
+    for component in &mut msg_bundle.components {
+        if component.name() != Tensor::name()
+            || !has_non_standard_tensor_layout(component.value_list().values().as_ref())
+        {
+            continue;
+        }
+
+        crate::profile_function!();
+
+        let mut tensors: Vec<Tensor> =
+            arrow_array_deserialize_iterator(component.value_list().values().as_ref())
+                .map_err(MsgBundleError::from)?
+                .collect();
+        for tensor in &mut tensors {
+            tensor.normalize_layout()?;
+        }
+        *component = ComponentBundle::try_from(tensors)?;
+    }
+    Ok(())
+}
+
+/// Cheap check of the serialized [`Tensor`]s, so that we only deserialize the ones we need to convert.
+fn has_non_standard_tensor_layout(tensors: &dyn Array) -> bool {
+    match tensors.as_any().downcast_ref::<StructArray>() {
+        Some(tensors) => tensors
+            .fields()
+            .iter()
+            .zip(tensors.values())
+            .any(|(field, values)| match field.name.as_str() {
+                "strides" => values.null_count() < values.len(),
+                "big_endian" => {
+                    values
+                        .as_any()
+                        .downcast_ref::<BooleanArray>()
+                        .map_or(false, |values| {
+                            // `None` means native byte order.
+                            values.iter().any(|big_endian| {
+                                big_endian.map_or(false, |big_endian| {
+                                    big_endian != cfg!(target_endian = "big")
+                                })
+                            })
+                        })
+                }
+                _ => false,
+            }),
+        None => false,
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// What [`LogDb::purge_fraction_of_ram`] has dropped so far, to tell the user.
+#[derive(Clone, Debug, Default)]
+pub struct PurgeStats {
+    /// How many times data was purged.
+    pub num_purges: usize,
+
+    pub num_dropped_msgs: usize,
+
+    /// Everything before these times has been dropped.
+    pub cutoff_times: BTreeMap<Timeline, TimeInt>,
+}
+
 // ----------------------------------------------------------------------------
 
 /// A in-memory database built from a stream of [`LogMsg`]es.
@@ -158,6 +392,9 @@ pub struct LogDb {
     chronological_message_ids: Vec<MsgId>,
     log_messages: ahash::HashMap<MsgId, LogMsg>,
 
+    /// When each message reached us, for debugging.
+    arrival_times: ahash::HashMap<MsgId, Time>,
+
     /// Data that was logged with [`TimePoint::timeless`].
     /// We need to re-insert those in any new timelines
     /// that are created after they were logged.
@@ -171,6 +408,14 @@ pub struct LogDb {
 
     /// Where we store the entities.
     pub entity_db: EntityDb,
+
+    /// Comes in [`LogMsg::TraceSpansMsg`]es.
+    pub trace_spans: TraceSpans,
+
+    /// Holds back data with bad timestamps.
+    clock_skew: ClockSkew,
+
+    purge_stats: PurgeStats,
 }
 
 impl LogDb {
@@ -198,11 +443,30 @@ impl LogDb {
         self.entity_db.tree.num_timeless_messages()
     }
 
+    /// The current state of the underlying store.
+    ///
+    /// See [`re_arrow_store::StoreGeneration`].
+    pub fn generation(&self) -> re_arrow_store::StoreGeneration {
+        self.entity_db.data_store.generation()
+    }
+
     pub fn is_empty(&self) -> bool {
         self.log_messages.is_empty()
     }
 
     pub fn add(&mut self, msg: LogMsg) -> Result<(), Error> {
+        let application_id = self
+            .recording_info
+            .as_ref()
+            .map_or_else(ApplicationId::unknown, |info| info.application_id.clone());
+        self.add_from(&application_id, msg)
+    }
+
+    /// Like [`Self::add`], for when we know which application sent the message,
+    /// e.g. because several applications are logging to the same recording.
+    ///
+    /// See [`EntityDb::applications`].
+    pub fn add_from(&mut self, application_id: &ApplicationId, msg: LogMsg) -> Result<(), Error> {
         crate::profile_function!();
         match &msg {
             LogMsg::BeginRecordingMsg(msg) => self.add_begin_recording_msg(msg),
@@ -215,11 +479,27 @@ impl LogDb {
                 self.entity_db.add_path_op(*msg_id, time_point, path_op);
             }
             LogMsg::ArrowMsg(msg) => {
-                self.entity_db.try_add_arrow_data_msg(msg)?;
+                let time_point = msg.time_point()?;
+                let is_live = self
+                    .data_source
+                    .as_ref()
+                    .map_or(false, |source| source.is_live());
+                let reference = reference_range(Time::now(), is_live);
+                if let Some(time_offset) =
+                    self.clock_skew
+                        .check(application_id, msg.msg_id, &time_point, Some(reference))
+                {
+                    self.entity_db
+                        .try_add_arrow_data_msg(application_id, msg, time_offset)?;
+                }
+            }
+            LogMsg::TraceSpansMsg(msg) => {
+                self.trace_spans.add(&msg.spans);
             }
-            LogMsg::Goodbye(_) => {}
+            LogMsg::Goodbye(_) | LogMsg::BlueprintMsg(_) => {}
         }
         self.chronological_message_ids.push(msg.id());
+        self.arrival_times.insert(msg.id(), Time::now());
         self.log_messages.insert(msg.id(), msg);
         Ok(())
     }
@@ -243,12 +523,107 @@ impl LogDb {
         self.log_messages.get(msg_id)
     }
 
+    /// When the message was added to this [`LogDb`], e.g. when it was received or loaded.
+    pub fn arrival_time(&self, msg_id: &MsgId) -> Option<Time> {
+        self.arrival_times.get(msg_id).copied()
+    }
+
+    /// Data held back because its timestamps are far off from the rest, e.g. because of a bad clock.
+    pub fn clock_skew(&self) -> &ClockSkew {
+        &self.clock_skew
+    }
+
+    /// Has data been dropped to free up memory?
+    pub fn purge_stats(&self) -> &PurgeStats {
+        &self.purge_stats
+    }
+
+    /// Approximately how much memory the data of this recording uses, in bytes.
+    pub fn num_data_bytes(&self) -> u64 {
+        let stats = DataStoreStats::from_store(&self.entity_db.data_store);
+        stats.total_index_size_bytes + stats.total_component_size_bytes
+    }
+
+    /// Add `correction` to the wall-clock timestamps of the data from this application,
+    /// e.g. [`crate::SkewedData::suggested_correction`] to make up for a bad clock.
+    ///
+    /// Applies to the data that was held back because of its timestamps and to all data arriving later,
+    /// but not to the data that was already added.
+    pub fn correct_clock(&mut self, application_id: &ApplicationId, correction: Duration) {
+        crate::profile_function!();
+
+        for msg_id in self.clock_skew.correct(application_id, correction) {
+            let Some(LogMsg::ArrowMsg(msg)) = self.log_messages.get(&msg_id) else {
+                continue;
+            };
+            let time_point = match msg.time_point() {
+                Ok(time_point) => time_point,
+                Err(err) => {
+                    re_log::error!("Failed to add held back message: {err}");
+                    continue;
+                }
+            };
+            let Some(time_offset) =
+                self.clock_skew
+                    .check(application_id, msg_id, &time_point, None)
+            else {
+                continue; // Still off
+            };
+            if let Err(err) =
+                self.entity_db
+                    .try_add_arrow_data_msg(application_id, msg, time_offset)
+            {
+                re_log::error!("Failed to add held back message: {err}");
+            }
+        }
+    }
+
+    /// Write all messages, in the order they arrived, as an `.rrd` file.
+    ///
+    /// See [`re_log_types::encoding`] for the format.
+    #[cfg(feature = "save")]
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save_to(&self, write: impl std::io::Write) -> anyhow::Result<()> {
+        crate::profile_function!();
+        re_log_types::encoding::encode(self.chronological_log_messages(), write)
+    }
+
+    /// Read an `.rrd` file, e.g. one written by [`Self::save_to`].
+    #[cfg(feature = "load")]
+    pub fn load_from(read: impl std::io::Read) -> anyhow::Result<Self> {
+        crate::profile_function!();
+        let decoder = re_log_types::encoding::Decoder::new(read)?;
+
+        let mut log_db = Self::default();
+        for msg in decoder {
+            log_db.add(msg?)?;
+        }
+        Ok(log_db)
+    }
+
+    /// Add the messages of another recording to this one, e.g. to view two `.rrd` files together.
+    ///
+    /// The [`LogMsg::BeginRecordingMsg`] of `other` is skipped, so this keeps its recording info.
+    /// Messages we already have are skipped too, so merging the same file twice is harmless.
+    pub fn merge(&mut self, other: &Self) -> Result<(), Error> {
+        crate::profile_function!();
+        for msg in other.chronological_log_messages() {
+            if matches!(msg, LogMsg::BeginRecordingMsg(_))
+                || self.log_messages.contains_key(&msg.id())
+            {
+                continue;
+            }
+            self.add(msg.clone())?;
+        }
+        Ok(())
+    }
+
     /// Free up some RAM by forgetting the older parts of all timelines.
     pub fn purge_fraction_of_ram(&mut self, fraction_to_purge: f32) {
         crate::profile_function!();
         assert!((0.0..=1.0).contains(&fraction_to_purge));
 
-        let drop_msg_ids = {
+        let mut drop_msg_ids = {
             let msg_id_chunks = self.entity_db.data_store.gc(
                 GarbageCollectionTarget::DropAtLeastPercentage(fraction_to_purge as _),
                 Timeline::log_time(),
@@ -264,17 +639,42 @@ impl LogDb {
                 .collect::<ahash::HashSet<_>>()
         };
 
+        // Held back data isn't in the store, and would otherwise grow forever
+        // if the clock of its application is never corrected:
+        drop_msg_ids.extend(self.clock_skew.purge_fraction(fraction_to_purge));
+
         let cutoff_times = self.entity_db.data_store.oldest_time_per_timeline();
 
+        // Trace spans aren't in the data store, so we use the same cutoff for them as for the data:
+        let trace_spans_cutoff = cutoff_times
+            .get(&Timeline::log_time())
+            .map(|time| Time::from_ns_since_epoch(time.as_i64()));
+        if let Some(cutoff) = trace_spans_cutoff {
+            drop_msg_ids.extend(self.log_messages.values().filter_map(|msg| match msg {
+                LogMsg::TraceSpansMsg(msg) if msg.spans.iter().all(|span| span.end < cutoff) => {
+                    Some(msg.msg_id)
+                }
+                _ => None,
+            }));
+        }
+
         let Self {
             chronological_message_ids,
             log_messages,
+            arrival_times,
             timeless_message_ids,
             data_source: _,
             recording_info: _,
             entity_db,
+            trace_spans,
+            clock_skew: _, // Purged above.
+            purge_stats,
         } = self;
 
+        purge_stats.num_purges += 1;
+        purge_stats.num_dropped_msgs += drop_msg_ids.len();
+        purge_stats.cutoff_times.extend(cutoff_times.iter());
+
         {
             crate::profile_scope!("chronological_message_ids");
             chronological_message_ids.retain(|msg_id| !drop_msg_ids.contains(msg_id));
@@ -284,11 +684,126 @@ impl LogDb {
             crate::profile_scope!("log_messages");
             log_messages.retain(|msg_id, _| !drop_msg_ids.contains(msg_id));
         }
+        {
+            crate::profile_scope!("arrival_times");
+            arrival_times.retain(|msg_id, _| !drop_msg_ids.contains(msg_id));
+        }
         {
             crate::profile_scope!("timeless_message_ids");
             timeless_message_ids.retain(|msg_id| !drop_msg_ids.contains(msg_id));
         }
 
         entity_db.purge(&cutoff_times, &drop_msg_ids);
+
+        if let Some(cutoff) = trace_spans_cutoff {
+            trace_spans.purge(cutoff);
+        }
+    }
+}
+
+#[cfg(all(feature = "load", feature = "save", not(target_arch = "wasm32")))]
+#[test]
+fn test_save_and_load() {
+    let mut log_db = LogDb::default();
+    for recursive in [false, true] {
+        log_db
+            .add(LogMsg::EntityPathOpMsg(EntityPathOpMsg {
+                msg_id: MsgId::random(),
+                time_point: TimePoint::default(),
+                path_op: PathOp::clear(recursive, EntityPath::from("world/points")),
+            }))
+            .unwrap();
     }
+
+    let mut file = vec![];
+    log_db.save_to(&mut file).unwrap();
+    let loaded = LogDb::load_from(file.as_slice()).unwrap();
+
+    let msg_ids = |log_db: &LogDb| {
+        log_db
+            .chronological_log_messages()
+            .map(LogMsg::id)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(loaded.len(), 2);
+    assert_eq!(msg_ids(&loaded), msg_ids(&log_db));
+}
+
+#[test]
+fn test_entity_applications() {
+    use re_log_types::{component_types::Label, msg_bundle::try_build_msg_bundle1};
+
+    let mut log_db = LogDb::default();
+    for (application_id, entity_path) in [
+        ("planner", "world/path"),
+        ("driver", "world/path"),
+        ("driver", "world/car"),
+    ] {
+        let msg_bundle = try_build_msg_bundle1(
+            MsgId::random(),
+            entity_path,
+            TimePoint::default(),
+            vec![Label("label".to_owned())],
+        )
+        .unwrap();
+        log_db
+            .add_from(
+                &ApplicationId(application_id.to_owned()),
+                LogMsg::ArrowMsg(ArrowMsg::try_from(msg_bundle).unwrap()),
+            )
+            .unwrap();
+    }
+
+    let applications = |entity_path: &str| {
+        log_db
+            .entity_db
+            .applications(&EntityPath::from(entity_path))
+            .map(|applications| {
+                applications
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+            })
+    };
+    assert_eq!(applications("world/path").unwrap(), ["driver", "planner"]);
+    assert_eq!(applications("world/car").unwrap(), ["driver"]);
+    assert_eq!(applications("world"), None);
+}
+
+#[test]
+fn test_transform_times() {
+    use re_log_types::{msg_bundle::try_build_msg_bundle1, TimeType};
+
+    fn log_transform(log_db: &mut LogDb, timeline: Timeline, frame_nr: i64) {
+        let msg_bundle = try_build_msg_bundle1(
+            MsgId::random(),
+            "world/camera",
+            [(timeline, TimeInt::from(frame_nr))],
+            vec![Transform::Unknown],
+        )
+        .unwrap();
+        log_db
+            .add(LogMsg::ArrowMsg(ArrowMsg::try_from(msg_bundle).unwrap()))
+            .unwrap();
+    }
+
+    let timeline = Timeline::new("frame_nr", TimeType::Sequence);
+    let mut log_db = LogDb::default();
+    log_transform(&mut log_db, timeline, 10);
+    log_transform(&mut log_db, timeline, 20);
+    let generation = log_db.entity_db.transform_generation();
+
+    let latest = |frame_nr: i64| {
+        log_db
+            .entity_db
+            .latest_transform_time(&timeline, frame_nr.into())
+    };
+    assert_eq!(latest(5), None);
+    assert_eq!(latest(10), Some(10.into()));
+    assert_eq!(latest(15), Some(10.into()));
+    assert_eq!(latest(25), Some(20.into()));
+
+    // Logging a new transform at an old time changes what was already resolved:
+    log_transform(&mut log_db, timeline, 10);
+    assert_ne!(log_db.entity_db.transform_generation(), generation);
 }
@@ -0,0 +1,157 @@
+use re_log_types::{Time, TraceSpan};
+
+/// Identifies a span: span ids are only unique within a trace.
+type SpanKey = (u128, u64);
+
+/// All the [`TraceSpan`]s of a recording, laid out for showing as a flamegraph.
+#[derive(Default)]
+pub struct TraceSpans {
+    spans: Vec<TraceSpan>,
+
+    /// How deeply nested each span in [`Self::spans`] is, with zero for root spans.
+    depths: Vec<usize>,
+
+    max_depth: usize,
+
+    /// Index into [`Self::spans`].
+    index_from_key: ahash::HashMap<SpanKey, usize>,
+
+    /// Indices into [`Self::spans`] of the children of each parent,
+    /// including parents that haven't arrived (yet).
+    children: ahash::HashMap<SpanKey, Vec<usize>>,
+}
+
+impl TraceSpans {
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+
+    /// How deeply nested the most deeply nested span is.
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    /// All spans, in the order they arrived, together with how deeply nested they are.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (&TraceSpan, usize)> {
+        self.spans.iter().zip(self.depths.iter().copied())
+    }
+
+    /// Children finish (and so are usually sent) before their parents,
+    /// so a new span can move spans we already have further down.
+    pub fn add(&mut self, spans: &[TraceSpan]) {
+        crate::profile_function!();
+
+        let first_new = self.spans.len();
+        for span in spans {
+            let index = self.spans.len();
+            Self::insert_into(&mut self.index_from_key, &mut self.children, index, span);
+            self.spans.push(span.clone());
+            self.depths.push(0);
+        }
+
+        for index in first_new..self.spans.len() {
+            let span = &self.spans[index];
+            let depth = span
+                .parent_span_id
+                .and_then(|parent_span_id| {
+                    self.index_from_key.get(&(span.trace_id, parent_span_id))
+                })
+                .map_or(0, |&parent_index| self.depths[parent_index] + 1);
+            self.set_depth(index, depth);
+        }
+    }
+
+    /// Forget all spans that ended before the cutoff.
+    ///
+    /// The spans that are left keep their depths, even if their parents are forgotten.
+    pub fn purge(&mut self, cutoff: Time) {
+        crate::profile_function!();
+
+        let (spans, depths) = std::mem::take(&mut self.spans)
+            .into_iter()
+            .zip(std::mem::take(&mut self.depths))
+            .filter(|(span, _)| cutoff <= span.end)
+            .unzip();
+        self.spans = spans;
+        self.depths = depths;
+
+        self.index_from_key.clear();
+        self.children.clear();
+        for (index, span) in self.spans.iter().enumerate() {
+            Self::insert_into(&mut self.index_from_key, &mut self.children, index, span);
+        }
+        self.max_depth = self.depths.iter().copied().max().unwrap_or_default();
+    }
+
+    fn insert_into(
+        index_from_key: &mut ahash::HashMap<SpanKey, usize>,
+        children: &mut ahash::HashMap<SpanKey, Vec<usize>>,
+        index: usize,
+        span: &TraceSpan,
+    ) {
+        index_from_key.insert((span.trace_id, span.span_id), index);
+        if let Some(parent_span_id) = span.parent_span_id {
+            children
+                .entry((span.trace_id, parent_span_id))
+                .or_default()
+                .push(index);
+        }
+    }
+
+    /// Set the depth of a span, and move all its descendants along with it.
+    fn set_depth(&mut self, index: usize, depth: usize) {
+        let mut visited = ahash::HashSet::default();
+        let mut stack = vec![(index, depth)];
+        while let Some((index, depth)) = stack.pop() {
+            if !visited.insert(index) {
+                continue; // A cycle - the spans are malformed.
+            }
+            self.depths[index] = depth;
+            self.max_depth = self.max_depth.max(depth);
+
+            let span = &self.spans[index];
+            if let Some(children) = self.children.get(&(span.trace_id, span.span_id)) {
+                stack.extend(children.iter().map(|&child| (child, depth + 1)));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_trace_span_depths() {
+    fn span(span_id: u64, parent_span_id: Option<u64>) -> TraceSpan {
+        TraceSpan {
+            trace_id: 1,
+            span_id,
+            parent_span_id,
+            name: format!("span {span_id}"),
+            service_name: "test".to_owned(),
+            start: Time::from_seconds_since_epoch(0.0),
+            end: Time::from_seconds_since_epoch(1.0),
+        }
+    }
+
+    let mut spans = TraceSpans::default();
+
+    // Children arrive before their parents:
+    spans.add(&[span(3, Some(2)), span(4, Some(1))]);
+    assert_eq!(
+        spans.iter().map(|(_, depth)| depth).collect::<Vec<_>>(),
+        [0, 0]
+    );
+
+    spans.add(&[span(2, Some(1)), span(1, None)]);
+    assert_eq!(
+        spans.iter().map(|(_, depth)| depth).collect::<Vec<_>>(),
+        [2, 1, 1, 0]
+    );
+    assert_eq!(spans.max_depth(), 2);
+
+    // Malformed spans that are each other's parents:
+    spans.add(&[span(5, Some(6)), span(6, Some(5))]);
+    assert_eq!(spans.len(), 6);
+}
@@ -24,6 +24,20 @@ impl ArrowMsg {
     pub fn time_point(&self) -> Result<TimePoint, crate::msg_bundle::MsgBundleError> {
         crate::msg_bundle::extract_timelines(&self.schema, &self.chunk)
     }
+
+    /// Does this message contain the given component?
+    ///
+    /// Only looks at the schema, so this is a lot cheaper than building a [`crate::msg_bundle::MsgBundle`].
+    pub fn has_component(&self, component: &crate::ComponentName) -> bool {
+        crate::msg_bundle::has_component(&self.schema, component)
+    }
+
+    /// The entity path, as logged.
+    ///
+    /// Like [`Self::has_component`], this only looks at the schema.
+    pub fn entity_path_str(&self) -> Option<&str> {
+        crate::msg_bundle::entity_path_str(&self.schema)
+    }
 }
 
 #[cfg(feature = "serde")]
@@ -80,7 +94,8 @@ impl<'de> serde::Deserialize<'de> for ArrowMsg {
 
                 if let (Some(msg_id), Some(buf)) = (msg_id, buf) {
                     let mut cursor = std::io::Cursor::new(buf);
-                    let metadata = read_stream_metadata(&mut cursor).unwrap();
+                    let metadata = read_stream_metadata(&mut cursor)
+                        .map_err(|err| serde::de::Error::custom(err.to_string()))?;
                     let mut stream = StreamReader::new(cursor, metadata, None);
                     let chunk = stream
                         .find_map(|state| match state {
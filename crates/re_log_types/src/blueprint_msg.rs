@@ -0,0 +1,103 @@
+//! [`BlueprintMsg`] is the [`crate::LogMsg`] sub-type that tells the viewer how to lay out the data.
+//!
+//! This lets a logging program define the resulting dashboard in code,
+//! instead of relying on the viewer heuristics or on the user setting it up by hand.
+
+use crate::{EntityPath, MsgId};
+
+/// A directive for how the viewer should present the logged data.
+#[must_use]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct BlueprintMsg {
+    /// A unique id per [`crate::LogMsg`].
+    pub msg_id: MsgId,
+
+    /// What to do.
+    pub op: BlueprintOp,
+}
+
+/// Operation to perform on the blueprint of the viewer.
+///
+/// The blueprint is shared by all recordings with the same [`crate::ApplicationId`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum BlueprintOp {
+    /// Remove all space views, including the ones the user created last time.
+    ///
+    /// Send this before any [`Self::AddSpaceView`] to get the exact same layout every time.
+    ClearSpaceViews,
+
+    /// Add a space view, or replace the one with the same name.
+    ///
+    /// Once a space view has been added this way, the viewer stops adding
+    /// space views of its own based on heuristics.
+    AddSpaceView(SpaceViewDescription),
+}
+
+/// What kind of space view to create.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum SpaceViewKind {
+    /// 2D or 3D view.
+    Spatial,
+
+    /// Time series plot of scalars.
+    TimeSeries,
+
+    /// Bar-chart plots made from 1D tensor data.
+    BarChart,
+
+    /// Text log view.
+    Text,
+
+    /// High-dimensional tensor view.
+    Tensor,
+}
+
+/// Describes a space view to be shown in the viewer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct SpaceViewDescription {
+    /// Shown in the tab of the space view.
+    ///
+    /// Also identifies the space view, so that sending the same description twice
+    /// replaces the space view instead of adding a second one.
+    pub name: String,
+
+    pub kind: SpaceViewKind,
+
+    /// The origin of the space view.
+    ///
+    /// All entities are shown relative to the transform at this path.
+    pub space_path: EntityPath,
+
+    /// The entities to show in the space view.
+    ///
+    /// If empty, the viewer picks the entities under [`Self::space_path`] that fit the [`Self::kind`].
+    pub entity_paths: Vec<EntityPath>,
+}
+
+impl SpaceViewDescription {
+    pub fn new(
+        name: impl Into<String>,
+        kind: SpaceViewKind,
+        space_path: impl Into<EntityPath>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            kind,
+            space_path: space_path.into(),
+            entity_paths: Default::default(),
+        }
+    }
+
+    /// Only show these entities, instead of letting the viewer pick.
+    pub fn with_entities(
+        mut self,
+        entity_paths: impl IntoIterator<Item = impl Into<EntityPath>>,
+    ) -> Self {
+        self.entity_paths = entity_paths.into_iter().map(Into::into).collect();
+        self
+    }
+}
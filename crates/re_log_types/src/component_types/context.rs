@@ -172,6 +172,69 @@ pub struct AnnotationContext {
     pub class_map: HashMap<ClassId, ClassDescription>,
 }
 
+impl AnnotationContext {
+    /// A hash of the contents, independent of the order of the classes, keypoints and
+    /// keypoint connections.
+    ///
+    /// Used to refer to this context from other recordings (and other versions of Rerun),
+    /// see [`AnnotationContextRef`], so this must never change for the same contents.
+    pub fn content_hash(&self) -> u64 {
+        fn encode_info(info: &AnnotationInfo, bytes: &mut Vec<u8>) {
+            let AnnotationInfo { id, label, color } = info;
+            bytes.extend(id.to_le_bytes());
+            match label {
+                Some(label) => {
+                    bytes.push(1);
+                    bytes.extend((label.0.len() as u64).to_le_bytes());
+                    bytes.extend(label.0.as_bytes());
+                }
+                None => bytes.push(0),
+            }
+            match color {
+                Some(color) => {
+                    bytes.push(1);
+                    bytes.extend(color.0.to_le_bytes());
+                }
+                None => bytes.push(0),
+            }
+        }
+
+        // A canonical encoding of the contents: everything sorted, with lengths up front.
+        let mut bytes = vec![];
+
+        let mut classes: Vec<_> = self.class_map.iter().collect();
+        classes.sort_by_key(|(class_id, _)| **class_id);
+        bytes.extend((classes.len() as u64).to_le_bytes());
+        for (class_id, class) in classes {
+            bytes.extend(class_id.0.to_le_bytes());
+            encode_info(&class.info, &mut bytes);
+
+            let mut keypoints: Vec<_> = class.keypoint_map.iter().collect();
+            keypoints.sort_by_key(|(keypoint_id, _)| **keypoint_id);
+            bytes.extend((keypoints.len() as u64).to_le_bytes());
+            for (keypoint_id, info) in keypoints {
+                bytes.extend(keypoint_id.0.to_le_bytes());
+                encode_info(info, &mut bytes);
+            }
+
+            // A connection is a line between two keypoints, so it has no direction either.
+            let mut connections: Vec<_> = class
+                .keypoint_connections
+                .iter()
+                .map(|(a, b)| (a.0.min(b.0), a.0.max(b.0)))
+                .collect();
+            connections.sort_unstable();
+            bytes.extend((connections.len() as u64).to_le_bytes());
+            for (a, b) in connections {
+                bytes.extend(a.to_le_bytes());
+                bytes.extend(b.to_le_bytes());
+            }
+        }
+
+        xxhash_rust::xxh3::xxh3_64(&bytes)
+    }
+}
+
 impl Component for AnnotationContext {
     #[inline]
     fn name() -> crate::ComponentName {
@@ -179,6 +242,42 @@ impl Component for AnnotationContext {
     }
 }
 
+/// Refers to an [`AnnotationContext`] by its [`AnnotationContext::content_hash`].
+///
+/// Log this instead of the full context to reuse one that was logged in another recording,
+/// e.g. the static setup of a robot that is the same in every session.
+/// The viewer remembers the annotation contexts it has seen, also between sessions.
+///
+/// ```
+/// use re_log_types::component_types::AnnotationContextRef;
+/// use arrow2_convert::field::ArrowField;
+/// use arrow2::datatypes::{DataType, Field};
+///
+/// assert_eq!(AnnotationContextRef::data_type(), DataType::UInt64);
+/// ```
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    derive_more::From,
+    derive_more::Into,
+    ArrowField,
+    ArrowSerialize,
+    ArrowDeserialize,
+)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[arrow_field(transparent)]
+pub struct AnnotationContextRef(pub u64);
+
+impl Component for AnnotationContextRef {
+    #[inline]
+    fn name() -> crate::ComponentName {
+        "rerun.annotation_context_ref".into()
+    }
+}
+
 /// Helper struct for converting `AnnotationContext` to arrow
 #[derive(ArrowField, ArrowSerialize, ArrowDeserialize)]
 pub struct ClassMapElemArrow {
@@ -297,3 +396,57 @@ fn test_context_roundtrip() {
         TryIntoCollection::try_into_collection(array).unwrap();
     assert_eq!(context_in, context_out);
 }
+
+#[test]
+fn test_context_content_hash() {
+    let class = |id: u16, label: &str| {
+        (
+            ClassId(id),
+            ClassDescription {
+                info: AnnotationInfo {
+                    id,
+                    label: Some(Label(label.to_owned())),
+                    color: None,
+                },
+                ..Default::default()
+            },
+        )
+    };
+
+    let context = AnnotationContext {
+        class_map: [class(1, "person"), class(2, "car")].into_iter().collect(),
+    };
+    let reordered = AnnotationContext {
+        class_map: [class(2, "car"), class(1, "person")].into_iter().collect(),
+    };
+    let changed = AnnotationContext {
+        class_map: [class(1, "person"), class(2, "truck")]
+            .into_iter()
+            .collect(),
+    };
+
+    assert_eq!(context.content_hash(), reordered.content_hash());
+    assert_ne!(context.content_hash(), changed.content_hash());
+
+    // Other recordings may refer to this, so it must never change.
+    assert_eq!(context.content_hash(), 0x5cec_a770_15b4_01b8);
+
+    let with_connections = |keypoint_connections: Vec<(u16, u16)>| {
+        let (class_id, mut class) = class(1, "person");
+        class.keypoint_connections = keypoint_connections
+            .into_iter()
+            .map(|(a, b)| (KeypointId(a), KeypointId(b)))
+            .collect();
+        AnnotationContext {
+            class_map: [(class_id, class)].into_iter().collect(),
+        }
+    };
+    assert_eq!(
+        with_connections(vec![(1, 2), (2, 3)]).content_hash(),
+        with_connections(vec![(3, 2), (1, 2)]).content_hash()
+    );
+    assert_ne!(
+        with_connections(vec![(1, 2), (2, 3)]).content_hash(),
+        with_connections(vec![(1, 2), (1, 3)]).content_hash()
+    );
+}
@@ -0,0 +1,50 @@
+use arrow2_convert::{ArrowDeserialize, ArrowField, ArrowSerialize};
+
+use crate::msg_bundle::Component;
+
+/// A semantic group an entity belongs to, e.g. `perception` or `left arm`,
+/// independent of where it is in the entity path hierarchy.
+///
+/// The viewer can group the entity tree by this.
+///
+/// ```
+/// use re_log_types::component_types::Group;
+/// use arrow2_convert::field::ArrowField;
+/// use arrow2::datatypes::{DataType, Field};
+///
+/// assert_eq!(Group::data_type(), DataType::Utf8);
+/// ```
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    derive_more::From,
+    derive_more::Into,
+    ArrowField,
+    ArrowSerialize,
+    ArrowDeserialize,
+)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[arrow_field(transparent)]
+pub struct Group(pub String);
+
+impl Group {
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+impl Component for Group {
+    #[inline]
+    fn name() -> crate::ComponentName {
+        "rerun.group".into()
+    }
+}
+
+impl std::fmt::Display for Group {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
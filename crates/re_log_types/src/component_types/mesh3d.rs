@@ -34,7 +34,7 @@ impl std::hash::Hash for MeshId {
 impl MeshId {
     #[inline]
     pub fn random() -> Self {
-        Self(uuid::Uuid::new_v4())
+        Self(crate::random_uuid())
     }
 }
 
@@ -232,6 +232,32 @@ pub struct EncodedMesh3D {
     pub transform: [[f32; 3]; 4],
 }
 
+impl EncodedMesh3D {
+    /// Columns of the identity transform, i.e. the mesh as is.
+    pub const IDENTITY_TRANSFORM: [[f32; 3]; 4] = [
+        [1.0, 0.0, 0.0], // col 0
+        [0.0, 1.0, 0.0], // col 1
+        [0.0, 0.0, 1.0], // col 2
+        [0.0, 0.0, 0.0], // col 3 = translation
+    ];
+
+    /// The contents of e.g. a `.glb` or `.obj` file, with a new [`MeshId`].
+    pub fn new(format: MeshFormat, bytes: impl Into<Arc<[u8]>>) -> Self {
+        Self {
+            mesh_id: MeshId::random(),
+            format,
+            bytes: bytes.into(),
+            transform: Self::IDENTITY_TRANSFORM,
+        }
+    }
+
+    /// Four columns of an affine transformation matrix, applied to the whole mesh.
+    pub fn with_transform(mut self, transform: [[f32; 3]; 4]) -> Self {
+        self.transform = transform;
+        self
+    }
+}
+
 /// Helper struct for converting `EncodedMesh3D` to arrow
 #[derive(ArrowField, ArrowSerialize, ArrowDeserialize)]
 pub struct EncodedMesh3DArrow {
@@ -341,6 +367,20 @@ pub enum MeshFormat {
     Obj,
 }
 
+impl MeshFormat {
+    pub const ALL: [Self; 3] = [Self::Gltf, Self::Glb, Self::Obj];
+
+    /// Guess the format from a file extension, e.g. `"glb"` or `"OBJ"`.
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_lowercase().as_str() {
+            "gltf" => Some(Self::Gltf),
+            "glb" => Some(Self::Glb),
+            "obj" => Some(Self::Obj),
+            _ => None,
+        }
+    }
+}
+
 impl std::fmt::Display for MeshFormat {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -392,6 +432,19 @@ impl Mesh3D {
     }
 }
 
+#[test]
+fn test_mesh_format_from_extension() {
+    for format in MeshFormat::ALL {
+        let extension = format.to_string();
+        assert_eq!(MeshFormat::from_extension(&extension), Some(format));
+        assert_eq!(
+            MeshFormat::from_extension(&extension.to_lowercase()),
+            Some(format)
+        );
+    }
+    assert_eq!(MeshFormat::from_extension("rrd"), None);
+}
+
 #[test]
 fn test_mesh_roundtrip() {
     use arrow2::array::Array;
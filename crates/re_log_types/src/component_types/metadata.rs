@@ -0,0 +1,83 @@
+use arrow2_convert::{ArrowDeserialize, ArrowField, ArrowSerialize};
+
+use crate::msg_bundle::Component;
+
+/// One key/value pair of [`Metadata`].
+#[derive(Clone, Debug, PartialEq, Eq, ArrowField, ArrowSerialize, ArrowDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct MetadataEntry {
+    pub key: String,
+    pub value: String,
+}
+
+/// A small set of arbitrary key/value pairs for an instance, e.g. the model version or
+/// track age of a detection.
+///
+/// Shown when hovering or selecting the instance.
+///
+/// ```
+/// use re_log_types::component_types::{Metadata, MetadataEntry};
+/// use arrow2_convert::field::ArrowField;
+/// use arrow2::datatypes::{DataType, Field};
+///
+/// assert_eq!(
+///     Metadata::data_type(),
+///     DataType::Struct(vec![Field::new(
+///         "entries",
+///         DataType::List(Box::new(Field::new(
+///             "item",
+///             MetadataEntry::data_type(),
+///             false
+///         ))),
+///         false
+///     )])
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, ArrowField, ArrowSerialize, ArrowDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Metadata {
+    pub entries: Vec<MetadataEntry>,
+}
+
+impl Metadata {
+    pub fn new(entries: impl IntoIterator<Item = (impl Into<String>, impl Into<String>)>) -> Self {
+        Self {
+            entries: entries
+                .into_iter()
+                .map(|(key, value)| MetadataEntry {
+                    key: key.into(),
+                    value: value.into(),
+                })
+                .collect(),
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|entry| entry.key == key)
+            .map(|entry| entry.value.as_str())
+    }
+}
+
+impl Component for Metadata {
+    #[inline]
+    fn name() -> crate::ComponentName {
+        "rerun.metadata".into()
+    }
+}
+
+#[test]
+fn test_metadata_roundtrip() {
+    use arrow2::array::Array;
+    use arrow2_convert::{deserialize::TryIntoCollection, serialize::TryIntoArrow};
+
+    let metadata_in = vec![
+        Metadata::new([("model", "yolo-v8"), ("track_age", "12")]),
+        Metadata::default(),
+    ];
+    let array: Box<dyn Array> = metadata_in.try_into_arrow().unwrap();
+    let metadata_out: Vec<Metadata> = TryIntoCollection::try_into_collection(array).unwrap();
+    assert_eq!(metadata_in, metadata_out);
+    assert_eq!(metadata_out[0].get("track_age"), Some("12"));
+}
@@ -22,12 +22,14 @@ mod class_id;
 mod color;
 pub mod context;
 pub mod coordinates;
+mod group;
 mod instance_key;
 mod keypoint_id;
 mod label;
 mod linestrip;
 mod mat;
 mod mesh3d;
+mod metadata;
 mod msg_id;
 mod point;
 mod quaternion;
@@ -38,20 +40,23 @@ mod size;
 mod tensor;
 mod text_entry;
 mod transform;
+mod unit;
 mod vec;
 
 pub use arrow::Arrow3D;
 pub use bbox::Box3D;
 pub use class_id::ClassId;
 pub use color::ColorRGBA;
-pub use context::{AnnotationContext, AnnotationInfo, ClassDescription};
+pub use context::{AnnotationContext, AnnotationContextRef, AnnotationInfo, ClassDescription};
 pub use coordinates::ViewCoordinates;
+pub use group::Group;
 pub use instance_key::InstanceKey;
 pub use keypoint_id::KeypointId;
 pub use label::Label;
 pub use linestrip::{LineStrip2D, LineStrip3D};
 pub use mat::Mat3x3;
 pub use mesh3d::{EncodedMesh3D, Mesh3D, MeshFormat, MeshId, RawMesh3D};
+pub use metadata::{Metadata, MetadataEntry};
 pub use msg_id::MsgId;
 pub use point::{Point2D, Point3D};
 pub use quaternion::Quaternion;
@@ -59,25 +64,32 @@ pub use radius::Radius;
 pub use rect::Rect2D;
 pub use scalar::{Scalar, ScalarPlotProps};
 pub use size::Size3D;
-pub use tensor::{Tensor, TensorData, TensorDataMeaning, TensorDimension, TensorId, TensorTrait};
+pub use tensor::{
+    PixelCountError, Tensor, TensorCastError, TensorData, TensorDataMeaning, TensorDimension,
+    TensorId, TensorTrait,
+};
 pub use text_entry::TextEntry;
 pub use transform::{Pinhole, Rigid3, Transform};
+pub use unit::Unit;
 pub use vec::{Vec2D, Vec3D, Vec4D};
 
 lazy_static! {
     //TODO(john): use a run-time type registry
-    static ref FIELDS: [Field; 26] = [
+    static ref FIELDS: [Field; 30] = [
         <AnnotationContext as Component>::field(),
+        <AnnotationContextRef as Component>::field(),
         <Arrow3D as Component>::field(),
         <Box3D as Component>::field(),
         <ClassId as Component>::field(),
         <ColorRGBA as Component>::field(),
+        <Group as Component>::field(),
         <InstanceKey as Component>::field(),
         <KeypointId as Component>::field(),
         <Label as Component>::field(),
         <LineStrip2D as Component>::field(),
         <LineStrip3D as Component>::field(),
         <Mesh3D as Component>::field(),
+        <Metadata as Component>::field(),
         <MsgId as Component>::field(),
         <Point2D as Component>::field(),
         <Point3D as Component>::field(),
@@ -90,6 +102,7 @@ lazy_static! {
         <Tensor as Component>::field(),
         <TextEntry as Component>::field(),
         <Transform as Component>::field(),
+        <Unit as Component>::field(),
         <Vec2D as Component>::field(),
         <Vec3D as Component>::field(),
         <ViewCoordinates as Component>::field(),
@@ -1,7 +1,11 @@
 use std::sync::Arc;
 
-use arrow2::array::{FixedSizeBinaryArray, MutableFixedSizeBinaryArray};
+use arrow2::array::{
+    new_empty_array, new_null_array, Array, FixedSizeBinaryArray, MutableFixedSizeBinaryArray,
+    StructArray, UnionArray,
+};
 use arrow2::buffer::Buffer;
+use arrow2::datatypes::{DataType, UnionMode};
 use arrow2_convert::deserialize::ArrowDeserialize;
 use arrow2_convert::field::ArrowField;
 use arrow2_convert::{serialize::ArrowSerialize, ArrowDeserialize, ArrowField, ArrowSerialize};
@@ -42,7 +46,7 @@ impl std::hash::Hash for TensorId {
 impl TensorId {
     #[inline]
     pub fn random() -> Self {
-        Self(uuid::Uuid::new_v4())
+        Self(crate::random_uuid())
     }
 }
 
@@ -146,6 +150,16 @@ impl ArrowDeserialize for TensorId {
 ///                 false
 ///             ),
 ///             Field::new("JPEG", DataType::Binary, false),
+///             Field::new(
+///                 "F16",
+///                 DataType::List(Box::new(Field::new("item", DataType::UInt16, false))),
+///                 false
+///             ),
+///             Field::new(
+///                 "BF16",
+///                 DataType::List(Box::new(Field::new("item", DataType::UInt16, false))),
+///                 false
+///             ),
 ///         ],
 ///         None,
 ///         UnionMode::Dense
@@ -165,11 +179,52 @@ pub enum TensorData {
     I32(Buffer<i32>),
     I64(Buffer<i64>),
     // ---
-    // TODO(#854): Native F16 support for arrow tensors
-    //F16(Vec<arrow2::types::f16>),
     F32(Buffer<f32>),
     F64(Buffer<f64>),
     JPEG(Vec<u8>),
+    // ---
+    // New variants go last: the arrow union type ids follow the order of the variants,
+    // so inserting one earlier would change the meaning of already serialized data.
+    // See [`Tensor::upgrade_legacy_array`].
+    //
+    /// The raw bits of [`half::f16`].
+    ///
+    /// TODO(#854): Native F16 support for arrow tensors
+    F16(Buffer<u16>),
+
+    /// The raw bits of [`half::bf16`], which arrow has no type for.
+    BF16(Buffer<u16>),
+}
+
+impl TensorData {
+    /// Number of elements in the buffer, or `None` for encoded data (i.e. JPEG).
+    pub fn num_elements(&self) -> Option<usize> {
+        match self {
+            Self::U8(buf) => Some(buf.len()),
+            Self::U16(buf) => Some(buf.len()),
+            Self::U32(buf) => Some(buf.len()),
+            Self::U64(buf) => Some(buf.len()),
+            Self::I8(buf) => Some(buf.len()),
+            Self::I16(buf) => Some(buf.len()),
+            Self::I32(buf) => Some(buf.len()),
+            Self::I64(buf) => Some(buf.len()),
+            Self::F16(buf) | Self::BF16(buf) => Some(buf.len()),
+            Self::F32(buf) => Some(buf.len()),
+            Self::F64(buf) => Some(buf.len()),
+            Self::JPEG(_) => None,
+        }
+    }
+
+    /// Size of each element in bytes, or `None` for encoded data (i.e. JPEG).
+    pub fn element_size(&self) -> Option<usize> {
+        match self {
+            Self::U8(_) | Self::I8(_) => Some(1),
+            Self::U16(_) | Self::I16(_) | Self::F16(_) | Self::BF16(_) => Some(2),
+            Self::U32(_) | Self::I32(_) | Self::F32(_) => Some(4),
+            Self::U64(_) | Self::I64(_) | Self::F64(_) => Some(8),
+            Self::JPEG(_) => None,
+        }
+    }
 }
 
 /// Flattened `Tensor` data payload
@@ -305,6 +360,12 @@ pub enum TensorDataMeaning {
 ///             false
 ///         ),
 ///         Field::new("meter", DataType::Float32, true),
+///         Field::new(
+///             "strides",
+///             DataType::List(Box::new(Field::new("item", DataType::UInt64, false))),
+///             true
+///         ),
+///         Field::new("big_endian", DataType::Boolean, true),
 ///     ])
 /// );
 /// ```
@@ -325,6 +386,272 @@ pub struct Tensor {
 
     /// Reciprocal scale of meter unit for depth images
     pub meter: Option<f32>,
+
+    /// How many elements to step in [`Self::data`] to move one step along each dimension.
+    ///
+    /// `None` means contiguous row-major order (numpy's default "C order").
+    /// Explicit strides let column-major (Fortran/MATLAB) data and slices be logged without
+    /// first making a contiguous copy. See [`Self::normalize_layout`].
+    pub strides: Option<Vec<u64>>,
+
+    /// Are the elements of [`Self::data`] stored big-endian, e.g. numpy arrays with a `>` dtype?
+    ///
+    /// `None` means native byte order, like tensors logged before this existed.
+    pub big_endian: Option<bool>,
+}
+
+impl Tensor {
+    /// Is this tensor stored in contiguous row-major order, in native byte order?
+    ///
+    /// Most of the viewer assumes this, so tensors are normalized on ingestion
+    /// using [`Self::normalize_layout`].
+    pub fn is_standard_layout(&self) -> bool {
+        let native_byte_order = self.is_big_endian() == cfg!(target_endian = "big")
+            || self.data.element_size() == Some(1);
+        let contiguous = self
+            .strides
+            .as_ref()
+            .map_or(true, |strides| *strides == row_major_strides(&self.shape));
+        native_byte_order && contiguous
+    }
+
+    /// Convert the data to contiguous row-major order and native byte order,
+    /// which is what [`Self::is_standard_layout`] checks.
+    pub fn normalize_layout(&mut self) -> Result<(), TensorCastError> {
+        crate::profile_function!();
+
+        if self.is_standard_layout() {
+            self.strides = None;
+            self.big_endian = None;
+            return Ok(());
+        }
+
+        let offsets = self.element_offsets()?;
+        let big_endian = self.is_big_endian();
+        self.data = match &self.data {
+            TensorData::U8(buf) => TensorData::U8(restride(buf, &offsets, big_endian)),
+            TensorData::U16(buf) => TensorData::U16(restride(buf, &offsets, big_endian).into()),
+            TensorData::U32(buf) => TensorData::U32(restride(buf, &offsets, big_endian).into()),
+            TensorData::U64(buf) => TensorData::U64(restride(buf, &offsets, big_endian).into()),
+            TensorData::I8(buf) => TensorData::I8(restride(buf, &offsets, big_endian).into()),
+            TensorData::I16(buf) => TensorData::I16(restride(buf, &offsets, big_endian).into()),
+            TensorData::I32(buf) => TensorData::I32(restride(buf, &offsets, big_endian).into()),
+            TensorData::I64(buf) => TensorData::I64(restride(buf, &offsets, big_endian).into()),
+            TensorData::F16(buf) => TensorData::F16(restride(buf, &offsets, big_endian).into()),
+            TensorData::BF16(buf) => TensorData::BF16(restride(buf, &offsets, big_endian).into()),
+            TensorData::F32(buf) => TensorData::F32(restride(buf, &offsets, big_endian).into()),
+            TensorData::F64(buf) => TensorData::F64(restride(buf, &offsets, big_endian).into()),
+            TensorData::JPEG(_) => return Err(TensorCastError::BadStrides),
+        };
+        self.strides = None;
+        self.big_endian = None;
+        Ok(())
+    }
+
+    fn is_big_endian(&self) -> bool {
+        self.big_endian.unwrap_or(cfg!(target_endian = "big"))
+    }
+
+    /// Upgrade tensors serialized by an older version of Rerun (e.g. in an old `.rrd` file)
+    /// to the current [`Self::data_type`], so that they can be deserialized.
+    ///
+    /// `None` if they already have the current type, or if we don't know how to upgrade them.
+    pub fn upgrade_legacy_array(tensors: &dyn Array) -> Option<Box<dyn Array>> {
+        let data_type = Self::data_type();
+        if tensors.data_type() == &data_type {
+            return None;
+        }
+        let fields = match &data_type {
+            DataType::Struct(fields) => fields,
+            _ => return None,
+        };
+        let tensors = tensors.as_any().downcast_ref::<StructArray>()?;
+
+        let values = fields
+            .iter()
+            .map(|field| {
+                let index = tensors
+                    .fields()
+                    .iter()
+                    .position(|old_field| old_field.name == field.name);
+                let values = match index {
+                    Some(index) => tensors.values()[index].as_ref(),
+                    // Added later, e.g. `strides` and `big_endian`:
+                    None if field.is_nullable => {
+                        return Some(new_null_array(field.data_type.clone(), tensors.len()));
+                    }
+                    None => return None,
+                };
+                if field.name == "data" {
+                    upgrade_legacy_tensor_data(values)
+                } else {
+                    Some(values.to_boxed())
+                }
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        StructArray::try_new(data_type, values, tensors.validity().cloned())
+            .ok()
+            .map(|tensors| tensors.boxed())
+    }
+
+    /// The offset into [`Self::data`] of every element, in row-major order.
+    fn element_offsets(&self) -> Result<Vec<usize>, TensorCastError> {
+        let strides = self
+            .strides
+            .clone()
+            .unwrap_or_else(|| row_major_strides(&self.shape));
+        if strides.len() != self.shape.len() {
+            return Err(TensorCastError::BadStrides);
+        }
+
+        // The shape and strides come from whoever logged the tensor, so check them against the data
+        // before we allocate or loop over anything:
+        let data_len = self
+            .data
+            .num_elements()
+            .ok_or(TensorCastError::BadStrides)? as u64;
+        let mut num_elements: u64 = 1;
+        let mut max_offset: u64 = 0;
+        for (dim, &stride) in self.shape.iter().zip(&strides) {
+            num_elements = num_elements
+                .checked_mul(dim.size)
+                .ok_or(TensorCastError::BadStrides)?;
+            max_offset = dim
+                .size
+                .saturating_sub(1)
+                .checked_mul(stride)
+                .and_then(|extent| extent.checked_add(max_offset))
+                .ok_or(TensorCastError::BadStrides)?;
+        }
+        if num_elements == 0 {
+            return Ok(vec![]);
+        }
+        if num_elements > data_len || max_offset >= data_len {
+            return Err(TensorCastError::BadStrides);
+        }
+
+        let mut offsets = Vec::with_capacity(num_elements as usize);
+        let mut index = vec![0_u64; self.shape.len()];
+        for _ in 0..num_elements {
+            offsets.push(
+                index
+                    .iter()
+                    .zip(&strides)
+                    .map(|(index, stride)| index * stride)
+                    .sum::<u64>() as usize,
+            );
+
+            // Step to the next index, with the last dimension changing the fastest:
+            for (index, dim) in index.iter_mut().zip(&self.shape).rev() {
+                *index += 1;
+                if *index < dim.size {
+                    break;
+                }
+                *index = 0;
+            }
+        }
+
+        Ok(offsets)
+    }
+}
+
+/// Older versions had fewer variants of [`TensorData`], but in the same order,
+/// so we only need to add the missing ones at the end.
+fn upgrade_legacy_tensor_data(data: &dyn Array) -> Option<Box<dyn Array>> {
+    let data_type = TensorData::data_type();
+    let (old_fields, new_fields) = match (data.data_type(), &data_type) {
+        (
+            DataType::Union(old_fields, None, UnionMode::Dense),
+            DataType::Union(new_fields, None, UnionMode::Dense),
+        ) => (old_fields, new_fields),
+        _ => return None,
+    };
+    if old_fields.len() > new_fields.len()
+        || old_fields
+            .iter()
+            .zip(new_fields)
+            .any(|(old, new)| old != new)
+    {
+        return None;
+    }
+
+    let data = data.as_any().downcast_ref::<UnionArray>()?;
+    let variants = data
+        .fields()
+        .iter()
+        .cloned()
+        .chain(
+            new_fields[old_fields.len()..]
+                .iter()
+                .map(|field| new_empty_array(field.data_type.clone())),
+        )
+        .collect();
+    UnionArray::try_new(
+        data_type,
+        data.types().clone(),
+        variants,
+        data.offsets().cloned(),
+    )
+    .ok()
+    .map(|data| data.boxed())
+}
+
+/// The strides of contiguous row-major data, in elements.
+fn row_major_strides(shape: &[TensorDimension]) -> Vec<u64> {
+    let mut strides = vec![0; shape.len()];
+    let mut stride = 1;
+    for (dim, out) in shape.iter().zip(&mut strides).rev() {
+        *out = stride;
+        stride *= dim.size;
+    }
+    strides
+}
+
+fn restride<T: TensorElementBits>(buf: &[T], offsets: &[usize], big_endian: bool) -> Vec<T> {
+    offsets
+        .iter()
+        .map(|&offset| buf[offset].to_native(big_endian))
+        .collect()
+}
+
+/// The element types of [`TensorData`].
+trait TensorElementBits: Copy {
+    /// Convert from the given byte order to the native one.
+    fn to_native(self, big_endian: bool) -> Self;
+}
+
+macro_rules! impl_tensor_element_bits_int {
+    ($($type:ty),*) => {
+        $(
+            impl TensorElementBits for $type {
+                #[inline]
+                fn to_native(self, big_endian: bool) -> Self {
+                    if big_endian {
+                        <$type>::from_be(self)
+                    } else {
+                        <$type>::from_le(self)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_tensor_element_bits_int!(u8, u16, u32, u64, i8, i16, i32, i64);
+
+impl TensorElementBits for f32 {
+    #[inline]
+    fn to_native(self, big_endian: bool) -> Self {
+        f32::from_bits(self.to_bits().to_native(big_endian))
+    }
+}
+
+impl TensorElementBits for f64 {
+    #[inline]
+    fn to_native(self, big_endian: bool) -> Self {
+        f64::from_bits(self.to_bits().to_native(big_endian))
+    }
 }
 
 impl TensorTrait for Tensor {
@@ -366,27 +693,58 @@ impl TensorTrait for Tensor {
     }
 
     fn get(&self, index: &[u64]) -> Option<TensorElement> {
-        let mut stride: usize = 1;
+        if index.len() != self.shape.len() {
+            return None;
+        }
+
+        let strides = self
+            .strides
+            .clone()
+            .unwrap_or_else(|| row_major_strides(&self.shape));
         let mut offset: usize = 0;
-        for (TensorDimension { size, .. }, index) in self.shape.iter().zip(index).rev() {
+        for ((TensorDimension { size, .. }, index), stride) in
+            self.shape.iter().zip(index).zip(strides)
+        {
             if size <= index {
                 return None;
             }
-            offset += *index as usize * stride;
-            stride *= *size as usize;
+            offset += (*index * stride) as usize;
         }
 
+        let big_endian = self.is_big_endian();
         match &self.data {
-            TensorData::U8(buf) => Some(TensorElement::U8(buf[offset])),
-            TensorData::U16(buf) => Some(TensorElement::U16(buf[offset])),
-            TensorData::U32(buf) => Some(TensorElement::U32(buf[offset])),
-            TensorData::U64(buf) => Some(TensorElement::U64(buf[offset])),
-            TensorData::I8(buf) => Some(TensorElement::I8(buf[offset])),
-            TensorData::I16(buf) => Some(TensorElement::I16(buf[offset])),
-            TensorData::I32(buf) => Some(TensorElement::I32(buf[offset])),
-            TensorData::I64(buf) => Some(TensorElement::I64(buf[offset])),
-            TensorData::F32(buf) => Some(TensorElement::F32(buf[offset])),
-            TensorData::F64(buf) => Some(TensorElement::F64(buf[offset])),
+            TensorData::U8(buf) => Some(TensorElement::U8(*buf.get(offset)?)),
+            TensorData::U16(buf) => {
+                Some(TensorElement::U16(buf.get(offset)?.to_native(big_endian)))
+            }
+            TensorData::U32(buf) => {
+                Some(TensorElement::U32(buf.get(offset)?.to_native(big_endian)))
+            }
+            TensorData::U64(buf) => {
+                Some(TensorElement::U64(buf.get(offset)?.to_native(big_endian)))
+            }
+            TensorData::I8(buf) => Some(TensorElement::I8(*buf.get(offset)?)),
+            TensorData::I16(buf) => {
+                Some(TensorElement::I16(buf.get(offset)?.to_native(big_endian)))
+            }
+            TensorData::I32(buf) => {
+                Some(TensorElement::I32(buf.get(offset)?.to_native(big_endian)))
+            }
+            TensorData::I64(buf) => {
+                Some(TensorElement::I64(buf.get(offset)?.to_native(big_endian)))
+            }
+            TensorData::F16(buf) => Some(TensorElement::F16(half::f16::from_bits(
+                buf.get(offset)?.to_native(big_endian),
+            ))),
+            TensorData::BF16(buf) => Some(TensorElement::BF16(half::bf16::from_bits(
+                buf.get(offset)?.to_native(big_endian),
+            ))),
+            TensorData::F32(buf) => {
+                Some(TensorElement::F32(buf.get(offset)?.to_native(big_endian)))
+            }
+            TensorData::F64(buf) => {
+                Some(TensorElement::F64(buf.get(offset)?.to_native(big_endian)))
+            }
             TensorData::JPEG(_) => None, // Too expensive to unpack here.
         }
     }
@@ -399,6 +757,113 @@ impl Component for Tensor {
     }
 }
 
+// ----------------------------------------------------------------------------
+
+/// The number of values passed to e.g. [`Tensor::from_rgb8`] doesn't match the size of the image.
+#[derive(thiserror::Error, Clone, Copy, Debug, PartialEq, Eq)]
+#[error("A {width}x{height} image with {channels} channels needs {expected} values, got {actual}")]
+pub struct PixelCountError {
+    pub width: u32,
+    pub height: u32,
+    pub channels: u64,
+    pub expected: u64,
+    pub actual: usize,
+}
+
+/// Images from raw pixels, in row-major order.
+///
+/// For other formats, use [`Tensor::from_image`] or build the [`Tensor`] yourself.
+impl Tensor {
+    /// 8-bit sRGB: `r, g, b, r, g, b, …`.
+    pub fn from_rgb8(width: u32, height: u32, pixels: Vec<u8>) -> Result<Self, PixelCountError> {
+        Self::from_pixels(width, height, 3, pixels.len(), TensorData::U8(pixels))
+    }
+
+    /// 8-bit sRGB with linear alpha: `r, g, b, a, r, g, b, a, …`.
+    pub fn from_rgba8(width: u32, height: u32, pixels: Vec<u8>) -> Result<Self, PixelCountError> {
+        Self::from_pixels(width, height, 4, pixels.len(), TensorData::U8(pixels))
+    }
+
+    /// 8-bit grayscale.
+    pub fn from_mono8(width: u32, height: u32, pixels: Vec<u8>) -> Result<Self, PixelCountError> {
+        Self::from_pixels(width, height, 1, pixels.len(), TensorData::U8(pixels))
+    }
+
+    /// 16-bit grayscale, e.g. from a thermal camera.
+    ///
+    /// Use [`Self::with_depth`] for depth cameras, most of which give depth in millimeters as `u16`.
+    pub fn from_mono16(width: u32, height: u32, pixels: Vec<u16>) -> Result<Self, PixelCountError> {
+        Self::from_pixels(
+            width,
+            height,
+            1,
+            pixels.len(),
+            TensorData::U16(pixels.into()),
+        )
+    }
+
+    /// A depth image, where `meter` is how many units of depth make a meter.
+    pub fn from_depth_f32(
+        width: u32,
+        height: u32,
+        pixels: Vec<f32>,
+        meter: Option<f32>,
+    ) -> Result<Self, PixelCountError> {
+        Self::from_pixels(
+            width,
+            height,
+            1,
+            pixels.len(),
+            TensorData::F32(pixels.into()),
+        )
+        .map(|tensor| tensor.with_depth(meter))
+    }
+
+    /// Show the pixels as depth, e.g. colormapped and projected into 3D.
+    ///
+    /// `meter` is how many units of depth make a meter, e.g. `1000.0` for millimeters.
+    pub fn with_depth(self, meter: Option<f32>) -> Self {
+        Self {
+            meaning: TensorDataMeaning::Depth,
+            meter,
+            ..self
+        }
+    }
+
+    fn from_pixels(
+        width: u32,
+        height: u32,
+        channels: u64,
+        num_values: usize,
+        data: TensorData,
+    ) -> Result<Self, PixelCountError> {
+        let expected = width as u64 * height as u64 * channels;
+        if expected != num_values as u64 {
+            return Err(PixelCountError {
+                width,
+                height,
+                channels,
+                expected,
+                actual: num_values,
+            });
+        }
+
+        Ok(Self {
+            tensor_id: TensorId::random(),
+            shape: vec![
+                TensorDimension::height(height as _),
+                TensorDimension::width(width as _),
+                TensorDimension::depth(channels),
+            ],
+            data,
+            meaning: TensorDataMeaning::Unknown,
+            meter: None,
+            strides: None,
+            big_endian: None,
+        })
+    }
+}
+
 #[derive(thiserror::Error, Debug)]
 pub enum TensorCastError {
     #[error("ndarray type mismatch with tensor storage")]
@@ -412,6 +877,12 @@ pub enum TensorCastError {
 
     #[error("ndarray Array is not contiguous and in standard order")]
     NotContiguousStdOrder,
+
+    #[error("tensor strides did not match its shape and storage length")]
+    BadStrides,
+
+    #[error("tensor is not stored in native byte order")]
+    NotNativeByteOrder,
 }
 
 impl From<&Tensor> for ClassicTensor {
@@ -449,6 +920,14 @@ impl From<&Tensor> for ClassicTensor {
                 crate::TensorDataType::I64,
                 TensorDataStore::Dense(Arc::from(bytemuck::cast_slice(data.as_slice()))),
             ),
+            TensorData::F16(data) => (
+                crate::TensorDataType::F16,
+                TensorDataStore::Dense(Arc::from(bytemuck::cast_slice(data.as_slice()))),
+            ),
+            TensorData::BF16(data) => (
+                crate::TensorDataType::BF16,
+                TensorDataStore::Dense(Arc::from(bytemuck::cast_slice(data.as_slice()))),
+            ),
             TensorData::F32(data) => (
                 crate::TensorDataType::F32,
                 TensorDataStore::Dense(Arc::from(bytemuck::cast_slice(data.as_slice()))),
@@ -479,11 +958,25 @@ macro_rules! tensor_type {
             type Error = TensorCastError;
 
             fn try_from(value: &'a Tensor) -> Result<Self, Self::Error> {
+                use ::ndarray::ShapeBuilder as _;
+
                 let shape: Vec<_> = value.shape.iter().map(|d| d.size as usize).collect();
 
+                if std::mem::size_of::<$type>() > 1
+                    && value.is_big_endian() != cfg!(target_endian = "big")
+                {
+                    return Err(TensorCastError::NotNativeByteOrder);
+                }
+
                 if let TensorData::$variant(data) = &value.data {
-                    ndarray::ArrayViewD::from_shape(shape, data.as_slice())
-                        .map_err(|err| TensorCastError::BadTensorShape { source: err })
+                    if let Some(strides) = &value.strides {
+                        let strides: Vec<_> = strides.iter().map(|&s| s as usize).collect();
+                        ndarray::ArrayViewD::from_shape(shape.strides(strides), data.as_slice())
+                            .map_err(|err| TensorCastError::BadTensorShape { source: err })
+                    } else {
+                        ndarray::ArrayViewD::from_shape(shape, data.as_slice())
+                            .map_err(|err| TensorCastError::BadTensorShape { source: err })
+                    }
                 } else {
                     Err(TensorCastError::TypeMismatch)
                 }
@@ -510,6 +1003,8 @@ macro_rules! tensor_type {
                         data: TensorData::$variant(Vec::from(slice).into()),
                         meaning: TensorDataMeaning::Unknown,
                         meter: None,
+                        strides: None,
+                        big_endian: None,
                     })
             }
         }
@@ -534,6 +1029,8 @@ macro_rules! tensor_type {
                         data: TensorData::$variant(value.into_raw_vec().into()),
                         meaning: TensorDataMeaning::Unknown,
                         meter: None,
+                        strides: None,
+                        big_endian: None,
                     })
                     .ok_or(TensorCastError::NotContiguousStdOrder)
             }
@@ -554,6 +1051,48 @@ tensor_type!(i64, I64);
 tensor_type!(f32, F32);
 tensor_type!(f64, F64);
 
+/// For types that we store as their raw bits, since arrow lacks them.
+macro_rules! tensor_type_bits {
+    ($type:ty, $variant:ident) => {
+        impl<'a, D: ::ndarray::Dimension> TryFrom<::ndarray::ArrayView<'a, $type, D>> for Tensor {
+            type Error = TensorCastError;
+
+            fn try_from(view: ::ndarray::ArrayView<'a, $type, D>) -> Result<Self, Self::Error> {
+                let shape = view
+                    .shape()
+                    .iter()
+                    .map(|dim| TensorDimension {
+                        size: *dim as u64,
+                        name: None,
+                    })
+                    .collect();
+                // We copy anyway, so any memory layout works:
+                let bits: Vec<u16> = view.iter().map(|value| value.to_bits()).collect();
+                Ok(Tensor {
+                    tensor_id: TensorId::random(),
+                    shape,
+                    data: TensorData::$variant(bits.into()),
+                    meaning: TensorDataMeaning::Unknown,
+                    meter: None,
+                    strides: None,
+                    big_endian: None,
+                })
+            }
+        }
+
+        impl<D: ::ndarray::Dimension> TryFrom<::ndarray::Array<$type, D>> for Tensor {
+            type Error = TensorCastError;
+
+            fn try_from(value: ndarray::Array<$type, D>) -> Result<Self, Self::Error> {
+                Self::try_from(value.view())
+            }
+        }
+    };
+}
+
+tensor_type_bits!(half::f16, F16);
+tensor_type_bits!(half::bf16, BF16);
+
 // ----------------------------------------------------------------------------
 
 #[cfg(feature = "image")]
@@ -562,7 +1101,7 @@ pub enum ImageError {
     #[error(transparent)]
     Image(#[from] image::ImageError),
 
-    #[error("Unsupported JPEG color type: {0:?}. Only RGB Jpegs are supported")]
+    #[error("Unsupported JPEG color type: {0:?}. Only RGB and grayscale Jpegs are supported")]
     UnsupportedJpegColorType(image::ColorType),
 
     #[error("Unsupported color type: {0:?}. We support 8-bit, 16-bit, and f32 images, and RGB, RGBA, Luminance, and Luminance-Alpha.")]
@@ -587,14 +1126,17 @@ impl Tensor {
 
     /// Construct a tensor from the contents of a JPEG file.
     ///
+    /// The JPEG is kept as is, and only decoded by the viewer.
+    ///
     /// Requires the `image` feature.
     pub fn tensor_from_jpeg_bytes(jpeg_bytes: Vec<u8>) -> Result<Self, ImageError> {
         use image::ImageDecoder as _;
         let jpeg = image::codecs::jpeg::JpegDecoder::new(std::io::Cursor::new(&jpeg_bytes))?;
-        if jpeg.color_type() != image::ColorType::Rgb8 {
-            // TODO(emilk): support gray-scale jpeg as well
-            return Err(ImageError::UnsupportedJpegColorType(jpeg.color_type()));
-        }
+        let depth = match jpeg.color_type() {
+            image::ColorType::Rgb8 => 3,
+            image::ColorType::L8 => 1,
+            color_type => return Err(ImageError::UnsupportedJpegColorType(color_type)),
+        };
         let (w, h) = jpeg.dimensions();
 
         Ok(Self {
@@ -602,11 +1144,13 @@ impl Tensor {
             shape: vec![
                 TensorDimension::height(h as _),
                 TensorDimension::width(w as _),
-                TensorDimension::depth(3),
+                TensorDimension::depth(depth),
             ],
             data: TensorData::JPEG(jpeg_bytes),
             meaning: TensorDataMeaning::Unknown,
             meter: None,
+            strides: None,
+            big_endian: None,
         })
     }
 
@@ -668,6 +1212,8 @@ impl Tensor {
             data,
             meaning: TensorDataMeaning::Unknown,
             meter: None,
+            strides: None,
+            big_endian: None,
         })
     }
 }
@@ -712,6 +1258,8 @@ fn test_arrow() {
             data: TensorData::U16(vec![1, 2, 3, 4].into()),
             meaning: TensorDataMeaning::Unknown,
             meter: Some(1000.0),
+            strides: None,
+            big_endian: None,
         },
         Tensor {
             tensor_id: TensorId(std::default::Default::default()),
@@ -722,6 +1270,8 @@ fn test_arrow() {
             data: TensorData::F32(vec![1.23, 2.45].into()),
             meaning: TensorDataMeaning::Unknown,
             meter: None,
+            strides: None,
+            big_endian: None,
         },
     ];
 
@@ -730,6 +1280,85 @@ fn test_arrow() {
     assert_eq!(tensors_in, tensors_out);
 }
 
+#[test]
+fn test_upgrade_legacy_array() {
+    use arrow2::datatypes::Field;
+    use arrow2_convert::{deserialize::TryIntoCollection, serialize::TryIntoArrow};
+
+    let tensors_in = vec![Tensor {
+        tensor_id: TensorId(std::default::Default::default()),
+        shape: vec![TensorDimension::unnamed(2)],
+        data: TensorData::F32(vec![1.23, 2.45].into()),
+        meaning: TensorDataMeaning::Unknown,
+        meter: None,
+        strides: None,
+        big_endian: None,
+    }];
+    let array: Box<dyn Array> = tensors_in.iter().try_into_arrow().unwrap();
+
+    // Serialize the way we did before f16 and bf16 were supported, when `TensorData` ended with `JPEG`:
+    let tensors = array.as_any().downcast_ref::<StructArray>().unwrap();
+    let legacy_values = tensors
+        .fields()
+        .iter()
+        .zip(tensors.values())
+        .map(|(field, values)| {
+            if field.name != "data" {
+                return values.clone();
+            }
+            let data = values.as_any().downcast_ref::<UnionArray>().unwrap();
+            let legacy_variants = match data.data_type() {
+                DataType::Union(variants, _, _) => variants
+                    .iter()
+                    .take_while(|variant| variant.name != "F16")
+                    .cloned()
+                    .collect::<Vec<_>>(),
+                _ => unreachable!(),
+            };
+            UnionArray::new(
+                DataType::Union(legacy_variants.clone(), None, UnionMode::Dense),
+                data.types().clone(),
+                data.fields()[..legacy_variants.len()].to_vec(),
+                data.offsets().cloned(),
+            )
+            .boxed()
+        })
+        .collect::<Vec<_>>();
+    let legacy_fields = tensors
+        .fields()
+        .iter()
+        .zip(&legacy_values)
+        .map(|(field, values)| {
+            Field::new(&field.name, values.data_type().clone(), field.is_nullable)
+        })
+        .collect();
+    let legacy = StructArray::new(DataType::Struct(legacy_fields), legacy_values, None).boxed();
+
+    let not_upgraded: Result<Vec<Tensor>, _> =
+        TryIntoCollection::try_into_collection(legacy.clone());
+    assert!(not_upgraded.is_err());
+
+    let upgraded = Tensor::upgrade_legacy_array(legacy.as_ref()).unwrap();
+    let tensors_out: Vec<Tensor> = TryIntoCollection::try_into_collection(upgraded).unwrap();
+    assert_eq!(tensors_in, tensors_out);
+
+    assert!(Tensor::upgrade_legacy_array(array.as_ref()).is_none());
+
+    // Before strided and big-endian tensors were supported:
+    let legacy = legacy.as_any().downcast_ref::<StructArray>().unwrap();
+    let (legacy_fields, legacy_values): (Vec<_>, Vec<_>) = legacy
+        .fields()
+        .iter()
+        .cloned()
+        .zip(legacy.values().iter().cloned())
+        .filter(|(field, _)| field.name != "strides" && field.name != "big_endian")
+        .unzip();
+    let legacy = StructArray::new(DataType::Struct(legacy_fields), legacy_values, None);
+    let upgraded = Tensor::upgrade_legacy_array(&legacy).unwrap();
+    let tensors_out: Vec<Tensor> = TryIntoCollection::try_into_collection(upgraded).unwrap();
+    assert_eq!(tensors_in, tensors_out);
+}
+
 #[test]
 fn test_concat_and_slice() {
     use crate::msg_bundle::wrap_in_listarray;
@@ -746,6 +1375,8 @@ fn test_concat_and_slice() {
         data: TensorData::JPEG(vec![1, 2, 3, 4]),
         meaning: TensorDataMeaning::Unknown,
         meter: Some(1000.0),
+        strides: None,
+        big_endian: None,
     }];
 
     let tensor2 = vec![Tensor {
@@ -757,6 +1388,8 @@ fn test_concat_and_slice() {
         data: TensorData::JPEG(vec![5, 6, 7, 8]),
         meaning: TensorDataMeaning::Unknown,
         meter: None,
+        strides: None,
+        big_endian: None,
     }];
 
     let array1: Box<dyn arrow2::array::Array> = tensor1.iter().try_into_arrow().unwrap();
@@ -786,3 +1419,120 @@ fn test_concat_and_slice() {
 
     assert_eq!(tensor2[0], tensor_out[0]);
 }
+
+#[test]
+fn test_normalize_layout() {
+    // A column-major 2x3 matrix:
+    // [[1, 2, 3],
+    //  [4, 5, 6]]
+    let mut tensor = Tensor {
+        tensor_id: TensorId::random(),
+        shape: vec![TensorDimension::unnamed(2), TensorDimension::unnamed(3)],
+        data: TensorData::U16(vec![1, 4, 2, 5, 3, 6].into()),
+        meaning: TensorDataMeaning::Unknown,
+        meter: None,
+        strides: Some(vec![1, 2]),
+        big_endian: None,
+    };
+    assert!(!tensor.is_standard_layout());
+    assert_eq!(tensor.get(&[1, 0]), Some(TensorElement::U16(4)));
+
+    tensor.normalize_layout().unwrap();
+    assert!(tensor.is_standard_layout());
+    assert_eq!(tensor.data, TensorData::U16(vec![1, 2, 3, 4, 5, 6].into()));
+    assert_eq!(tensor.get(&[1, 0]), Some(TensorElement::U16(4)));
+
+    // Every other element of a big-endian vector:
+    let mut tensor = Tensor {
+        tensor_id: TensorId::random(),
+        shape: vec![TensorDimension::unnamed(2)],
+        data: TensorData::U16(vec![1_u16.to_be(), 0, 2_u16.to_be()].into()),
+        meaning: TensorDataMeaning::Unknown,
+        meter: None,
+        strides: Some(vec![2]),
+        big_endian: Some(true),
+    };
+    assert_eq!(tensor.get(&[1]), Some(TensorElement::U16(2)));
+    tensor.normalize_layout().unwrap();
+    assert_eq!(tensor.data, TensorData::U16(vec![1, 2].into()));
+
+    // Strides reaching outside of the data:
+    let mut tensor = Tensor {
+        strides: Some(vec![3]),
+        ..tensor
+    };
+    assert!(matches!(
+        tensor.normalize_layout(),
+        Err(TensorCastError::BadStrides)
+    ));
+
+    // Many more elements than there is data, which we shouldn't even try to allocate:
+    let mut tensor = Tensor {
+        shape: vec![TensorDimension::unnamed(1 << 40)],
+        strides: Some(vec![0]),
+        ..tensor
+    };
+    assert!(matches!(
+        tensor.normalize_layout(),
+        Err(TensorCastError::BadStrides)
+    ));
+
+    // A shape whose number of elements overflows:
+    let mut tensor = Tensor {
+        shape: vec![TensorDimension::unnamed(1 << 40); 2],
+        strides: Some(vec![0, 0]),
+        ..tensor
+    };
+    assert!(matches!(
+        tensor.normalize_layout(),
+        Err(TensorCastError::BadStrides)
+    ));
+}
+
+#[test]
+fn test_half_floats() {
+    use half::{bf16, f16};
+
+    let array = ndarray::arr2(&[[f16::from_f32(1.5), f16::from_f32(-2.0)]]);
+    let tensor = Tensor::try_from(array).unwrap();
+    assert_eq!(
+        tensor.get(&[0, 1]),
+        Some(TensorElement::F16(f16::from_f32(-2.0)))
+    );
+
+    let array = ndarray::arr1(&[bf16::from_f32(1e30)]);
+    let tensor = Tensor::try_from(array).unwrap();
+    assert_eq!(
+        tensor.get(&[0]),
+        Some(TensorElement::BF16(bf16::from_f32(1e30)))
+    );
+    assert_eq!(
+        ClassicTensor::from(&tensor).dtype(),
+        crate::TensorDataType::BF16
+    );
+}
+
+#[test]
+fn test_from_pixels() {
+    let tensor = Tensor::from_rgb8(2, 1, vec![255, 0, 0, 0, 255, 0]).unwrap();
+    assert!(tensor.is_shaped_like_an_image());
+    assert_eq!(tensor.get(&[0, 1, 1]), Some(TensorElement::U8(255)));
+
+    let tensor = Tensor::from_mono16(1, 2, vec![1, 2]).unwrap();
+    assert_eq!(tensor.get(&[1, 0, 0]), Some(TensorElement::U16(2)));
+
+    let tensor = Tensor::from_depth_f32(1, 1, vec![2.5], Some(1.0)).unwrap();
+    assert_eq!(tensor.meaning, TensorDataMeaning::Depth);
+    assert_eq!(tensor.meter, Some(1.0));
+
+    assert_eq!(
+        Tensor::from_rgba8(2, 2, vec![0; 15]).unwrap_err(),
+        PixelCountError {
+            width: 2,
+            height: 2,
+            channels: 4,
+            expected: 16,
+            actual: 15,
+        }
+    );
+}
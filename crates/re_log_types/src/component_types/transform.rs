@@ -53,10 +53,10 @@ pub struct Rigid3 {
 impl Rigid3 {
     pub const IDENTITY: Rigid3 = Rigid3 {
         rotation: Quaternion {
-            x: 1.0,
+            x: 0.0,
             y: 0.0,
             z: 0.0,
-            w: 0.0,
+            w: 1.0,
         },
         translation: Vec3D([0.0, 0.0, 0.0]),
     };
@@ -87,6 +87,18 @@ impl Rigid3 {
     }
 }
 
+/// Chains transforms down the entity hierarchy, e.g. `world_from_base_link * base_link_from_camera`
+/// gives the pose of the camera in the world.
+#[cfg(feature = "glam")]
+impl std::ops::Mul for Rigid3 {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, child: Self) -> Self {
+        Self::new_parent_from_child(self.parent_from_child() * child.parent_from_child())
+    }
+}
+
 /// Camera perspective projection (a.k.a. intrinsics).
 ///
 ///
@@ -268,3 +280,31 @@ fn test_transform_roundtrip() {
     let transforms_out: Vec<Transform> = TryIntoCollection::try_into_collection(array).unwrap();
     assert_eq!(transforms_in, transforms_out);
 }
+
+#[cfg(feature = "glam")]
+#[test]
+fn test_rigid3_chain() {
+    let world_from_base_link =
+        Rigid3::new_parent_from_child(macaw::IsoTransform::from_rotation_translation(
+            glam::Quat::from_rotation_z(std::f32::consts::FRAC_PI_2),
+            glam::vec3(10.0, 0.0, 0.0),
+        ));
+    let base_link_from_camera =
+        Rigid3::new_parent_from_child(macaw::IsoTransform::from_rotation_translation(
+            glam::Quat::IDENTITY,
+            glam::vec3(1.0, 0.0, 2.0),
+        ));
+
+    let world_from_camera = world_from_base_link * base_link_from_camera;
+    let camera_in_world = world_from_camera
+        .parent_from_child()
+        .transform_point3(glam::Vec3::ZERO);
+    assert!(camera_in_world.abs_diff_eq(glam::vec3(10.0, 1.0, 2.0), 1e-5));
+
+    assert_eq!(Rigid3::IDENTITY * world_from_camera, world_from_camera);
+    let point = glam::vec3(1.0, 2.0, 3.0);
+    assert_eq!(
+        Rigid3::IDENTITY.parent_from_child().transform_point3(point),
+        point
+    );
+}
@@ -0,0 +1,49 @@
+use arrow2_convert::{ArrowDeserialize, ArrowField, ArrowSerialize};
+
+use crate::msg_bundle::Component;
+
+/// The unit of a scalar or tensor, e.g. `m`, `m/s`, `rad` or `°C`.
+///
+/// Shown next to the values in plots and hover readouts.
+///
+/// ```
+/// use re_log_types::component_types::Unit;
+/// use arrow2_convert::field::ArrowField;
+/// use arrow2::datatypes::{DataType, Field};
+///
+/// assert_eq!(Unit::data_type(), DataType::Utf8);
+/// ```
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    derive_more::From,
+    derive_more::Into,
+    ArrowField,
+    ArrowSerialize,
+    ArrowDeserialize,
+)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[arrow_field(transparent)]
+pub struct Unit(pub String);
+
+impl Unit {
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+impl Component for Unit {
+    #[inline]
+    fn name() -> crate::ComponentName {
+        "rerun.unit".into()
+    }
+}
+
+impl std::fmt::Display for Unit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
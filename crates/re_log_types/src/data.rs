@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use half::f16;
+use half::{bf16, f16};
 
 use crate::component_types;
 
@@ -70,6 +70,12 @@ pub enum TensorDataType {
     /// Set <https://en.wikipedia.org/wiki/Half-precision_floating-point_format>.
     F16,
 
+    /// 16-bit "brain" floating point number.
+    ///
+    /// Same range as `f32`, but with less precision. Common for ML activations.
+    /// See <https://en.wikipedia.org/wiki/Bfloat16_floating-point_format>.
+    BF16,
+
     /// 32-bit floating point number.
     F32,
 
@@ -93,6 +99,7 @@ impl TensorDataType {
             Self::I64 => std::mem::size_of::<i64>() as _,
 
             Self::F16 => std::mem::size_of::<f16>() as _,
+            Self::BF16 => std::mem::size_of::<bf16>() as _,
             Self::F32 => std::mem::size_of::<f32>() as _,
             Self::F64 => std::mem::size_of::<f64>() as _,
         }
@@ -113,6 +120,7 @@ impl std::fmt::Display for TensorDataType {
             Self::I64 => "int64".fmt(f),
 
             Self::F16 => "float16".fmt(f),
+            Self::BF16 => "bfloat16".fmt(f),
             Self::F32 => "float32".fmt(f),
             Self::F64 => "float64".fmt(f),
         }
@@ -161,6 +169,10 @@ impl TensorDataTypeTrait for f16 {
     const DTYPE: TensorDataType = TensorDataType::F16;
 }
 
+impl TensorDataTypeTrait for bf16 {
+    const DTYPE: TensorDataType = TensorDataType::BF16;
+}
+
 impl TensorDataTypeTrait for f32 {
     const DTYPE: TensorDataType = TensorDataType::F32;
 }
@@ -207,6 +219,11 @@ pub enum TensorElement {
     /// Set <https://en.wikipedia.org/wiki/Half-precision_floating-point_format>.
     F16(f16),
 
+    /// 16-bit "brain" floating point number.
+    ///
+    /// See <https://en.wikipedia.org/wiki/Bfloat16_floating-point_format>.
+    BF16(bf16),
+
     /// 32-bit floating point number.
     F32(f32),
 
@@ -229,6 +246,7 @@ impl TensorElement {
             Self::I64(value) => *value as _,
 
             Self::F16(value) => value.to_f64(),
+            Self::BF16(value) => value.to_f64(),
             Self::F32(value) => *value as _,
             Self::F64(value) => *value,
         }
@@ -254,6 +272,7 @@ impl TensorElement {
             Self::I64(value) => u16::try_from(*value).ok(),
 
             Self::F16(value) => u16_from_f64(value.to_f64()),
+            Self::BF16(value) => u16_from_f64(value.to_f64()),
             Self::F32(value) => u16_from_f64(*value as f64),
             Self::F64(value) => u16_from_f64(*value),
         }
@@ -495,6 +514,7 @@ impl ClassicTensor {
                     TensorDataType::I64 => TensorElement::I64(bytemuck::pod_read_unaligned(data)),
 
                     TensorDataType::F16 => TensorElement::F16(bytemuck::pod_read_unaligned(data)),
+                    TensorDataType::BF16 => TensorElement::BF16(bytemuck::pod_read_unaligned(data)),
                     TensorDataType::F32 => TensorElement::F32(bytemuck::pod_read_unaligned(data)),
                     TensorDataType::F64 => TensorElement::F64(bytemuck::pod_read_unaligned(data)),
                 })
@@ -1,36 +1,208 @@
 //! Saving/loading [`LogMsg`]:es to/from a file.
+//!
+//! The `.rrd` format is:
+//! * the magic bytes `RRF0`
+//! * a byte of flags, e.g. [`FLAG_DICTIONARY`]
+//! * a byte with the [`FORMAT_VERSION`]
+//! * two reserved bytes, always zero
+//! * if [`FLAG_DICTIONARY`] is set: the length of the zstd dictionary as a little-endian `u32`, followed by the dictionary
+//! * a zstd stream of messages, each a little-endian `u64` length followed by the MessagePack-encoded [`LogMsg`]
 use crate::LogMsg;
 
+/// Set in the header if the file embeds the zstd dictionary it was compressed with.
+#[cfg(any(feature = "load", feature = "save"))]
+const FLAG_DICTIONARY: u8 = 1;
+
+/// Bumped on every change to the format that older versions of Rerun can't read.
+///
+/// We can read files of this and all earlier versions.
+pub const FORMAT_VERSION: u8 = 0;
+
+/// Check the version byte of the header.
+#[cfg(feature = "load")]
+fn check_format_version(version: u8) -> anyhow::Result<()> {
+    anyhow::ensure!(
+        version <= FORMAT_VERSION,
+        "The file was written by a newer version of Rerun (format version {version}, \
+        this version of Rerun can read up to {FORMAT_VERSION})"
+    );
+    Ok(())
+}
+
 #[cfg(feature = "save")]
 #[cfg(not(target_arch = "wasm32"))]
 pub fn encode<'a>(
     messages: impl Iterator<Item = &'a LogMsg>,
-    mut write: impl std::io::Write,
+    write: impl std::io::Write,
 ) -> anyhow::Result<()> {
     crate::profile_function!();
-    use anyhow::Context as _;
-    use std::io::Write as _;
 
-    write.write_all(b"RRF0").context("header")?;
-    write.write_all(&[0, 0, 0, 0]).context("header")?; // reserved for future use
+    let mut encoder = Encoder::new(write)?;
+    for message in messages {
+        encoder.append(message)?;
+    }
+    encoder.finish()
+}
 
-    let level = 3;
-    let mut encoder = zstd::stream::Encoder::new(write, level).context("zstd start")?;
+/// Encodes [`LogMsg`]:es one at a time, e.g. while they are being logged.
+///
+/// Call [`Self::flush`] to make sure everything appended so far can be read back
+/// by a [`Decoder`] that is following the file as it grows.
+#[cfg(feature = "save")]
+#[cfg(not(target_arch = "wasm32"))]
+pub struct Encoder<W: std::io::Write> {
+    zstd_encoder: zstd::stream::Encoder<'static, W>,
+    buffer: Vec<u8>,
+}
 
-    let mut buffer = vec![];
+#[cfg(feature = "save")]
+#[cfg(not(target_arch = "wasm32"))]
+impl<W: std::io::Write> Encoder<W> {
+    pub fn new(write: W) -> anyhow::Result<Self> {
+        Self::new_with_compression_level(write, 3)
+    }
 
-    for message in messages {
-        buffer.clear();
-        rmp_serde::encode::write_named(&mut buffer, message).context("MessagePack encoding")?;
-        encoder
-            .write_all(&(buffer.len() as u64).to_le_bytes())
+    /// The `level` is a zstd compression level from 1 to 22.
+    /// Higher levels give smaller files, but are slower to encode.
+    pub fn new_with_compression_level(write: W, level: i32) -> anyhow::Result<Self> {
+        Self::new_impl(write, level, None)
+    }
+
+    /// Compress using a dictionary from [`train_dictionary`], which is embedded in the file.
+    ///
+    /// Such files can not yet be loaded by the web viewer.
+    pub fn new_with_dictionary(write: W, level: i32, dictionary: &[u8]) -> anyhow::Result<Self> {
+        Self::new_impl(write, level, Some(dictionary))
+    }
+
+    fn new_impl(mut write: W, level: i32, dictionary: Option<&[u8]>) -> anyhow::Result<Self> {
+        use anyhow::Context as _;
+
+        write.write_all(b"RRF0").context("header")?;
+        let flags = if dictionary.is_some() {
+            FLAG_DICTIONARY
+        } else {
+            0
+        };
+        write
+            .write_all(&[flags, FORMAT_VERSION, 0, 0])
+            .context("header")?; // the rest is reserved for future use
+
+        let zstd_encoder = if let Some(dictionary) = dictionary {
+            write
+                .write_all(&(dictionary.len() as u32).to_le_bytes())
+                .context("dictionary")?;
+            write.write_all(dictionary).context("dictionary")?;
+            zstd::stream::Encoder::with_dictionary(write, level, dictionary)
+                .context("zstd start")?
+        } else {
+            zstd::stream::Encoder::new(write, level).context("zstd start")?
+        };
+
+        Ok(Self {
+            zstd_encoder,
+            buffer: vec![],
+        })
+    }
+
+    pub fn append(&mut self, message: &LogMsg) -> anyhow::Result<()> {
+        use anyhow::Context as _;
+        use std::io::Write as _;
+
+        self.buffer.clear();
+        rmp_serde::encode::write_named(&mut self.buffer, message)
+            .context("MessagePack encoding")?;
+        self.zstd_encoder
+            .write_all(&(self.buffer.len() as u64).to_le_bytes())
             .context("zstd write")?;
-        encoder.write_all(&buffer).context("zstd write")?;
+        self.zstd_encoder
+            .write_all(&self.buffer)
+            .context("zstd write")?;
+
+        Ok(())
     }
 
-    encoder.finish().context("zstd finish")?;
+    /// Flush everything appended so far to the underlying writer.
+    pub fn flush(&mut self) -> anyhow::Result<()> {
+        use anyhow::Context as _;
+        use std::io::Write as _;
 
-    Ok(())
+        self.zstd_encoder.flush().context("zstd flush")
+    }
+
+    pub fn finish(self) -> anyhow::Result<()> {
+        use anyhow::Context as _;
+
+        self.zstd_encoder.finish().context("zstd finish")?;
+        Ok(())
+    }
+}
+
+/// Trains a zstd dictionary on the small messages (poses, scalars, …) of a recording.
+///
+/// Recordings dominated by such high-rate telemetry compress much better with a dictionary.
+/// Returns `None` if there are too few small messages for a dictionary to be worth it.
+#[cfg(feature = "save")]
+#[cfg(not(target_arch = "wasm32"))]
+pub fn train_dictionary(messages: impl Iterator<Item = LogMsg>) -> anyhow::Result<Option<Vec<u8>>> {
+    crate::profile_function!();
+    use anyhow::Context as _;
+
+    /// Large messages (images, meshes, …) are dominated by their payload,
+    /// and don't benefit from a dictionary.
+    const MAX_SAMPLE_SIZE: usize = 16 * 1024;
+    const MAX_NUM_SAMPLES: usize = 100_000;
+    const MIN_NUM_SAMPLES: usize = 1_000;
+    const MAX_DICTIONARY_SIZE: usize = 112 * 1024;
+
+    let mut samples = Vec::new();
+    for message in messages {
+        let mut sample = Vec::new();
+        rmp_serde::encode::write_named(&mut sample, &message).context("MessagePack encoding")?;
+        if sample.len() <= MAX_SAMPLE_SIZE {
+            samples.push(sample);
+            if samples.len() == MAX_NUM_SAMPLES {
+                break;
+            }
+        }
+    }
+
+    if samples.len() < MIN_NUM_SAMPLES {
+        return Ok(None);
+    }
+
+    let dictionary =
+        zstd::dict::from_samples(&samples, MAX_DICTIONARY_SIZE).context("zstd dictionary")?;
+    Ok(Some(dictionary))
+}
+
+/// Reads `len` bytes.
+///
+/// Unlike `read_exact` into a buffer of `len` bytes, this never allocates much more than is actually read,
+/// so a corrupt or malicious length can't make us run out of memory.
+#[cfg(feature = "load")]
+fn read_exactly_into(
+    read: &mut impl std::io::Read,
+    len: u64,
+    buffer: &mut Vec<u8>,
+) -> std::io::Result<()> {
+    use std::io::Read as _;
+
+    buffer.clear();
+    read.take(len).read_to_end(buffer)?;
+    if buffer.len() as u64 == len {
+        Ok(())
+    } else {
+        Err(std::io::ErrorKind::UnexpectedEof.into())
+    }
+}
+
+#[cfg(feature = "load")]
+#[cfg(not(target_arch = "wasm32"))]
+fn read_exactly(read: &mut impl std::io::Read, len: u64) -> std::io::Result<Vec<u8>> {
+    let mut buffer = vec![];
+    read_exactly_into(read, len, &mut buffer)?;
+    Ok(buffer)
 }
 
 // ----------------------------------------------------------------------------
@@ -54,9 +226,23 @@ impl<'r, R: std::io::Read> Decoder<'r, std::io::BufReader<R>> {
         read.read_exact(&mut header).context("missing header")?;
         anyhow::ensure!(&header == b"RRF0", "Not a rerun file");
         read.read_exact(&mut header).context("missing header")?;
-        anyhow::ensure!(header == [0, 0, 0, 0], "Incompatible rerun file format");
-
-        let zdecoder = zstd::stream::read::Decoder::new(read).context("zstd")?;
+        let [flags, version, reserved @ ..] = header;
+        check_format_version(version)?;
+        anyhow::ensure!(
+            flags & !FLAG_DICTIONARY == 0 && reserved == [0, 0],
+            "Incompatible rerun file format"
+        );
+
+        let zdecoder = if flags & FLAG_DICTIONARY == 0 {
+            zstd::stream::read::Decoder::new(read).context("zstd")?
+        } else {
+            let mut len = [0_u8; 4];
+            read.read_exact(&mut len).context("missing dictionary")?;
+            let dictionary = read_exactly(&mut read, u32::from_le_bytes(len) as u64)
+                .context("missing dictionary")?;
+            zstd::stream::read::Decoder::with_dictionary(std::io::BufReader::new(read), &dictionary)
+                .context("zstd")?
+        };
         Ok(Self {
             zdecoder,
             buffer: vec![],
@@ -75,19 +261,17 @@ impl<'r, R: std::io::BufRead> Iterator for Decoder<'r, R> {
 
         let mut len = [0_u8; 8];
         self.zdecoder.read_exact(&mut len).ok()?;
-        let len = u64::from_le_bytes(len) as usize;
-
-        self.buffer.resize(len, 0);
+        let len = u64::from_le_bytes(len);
 
         {
             crate::profile_scope!("zstd");
-            if let Err(err) = self.zdecoder.read_exact(&mut self.buffer) {
+            if let Err(err) = read_exactly_into(&mut self.zdecoder, len, &mut self.buffer) {
                 return Some(Err(anyhow::anyhow!("zstd: {err}")));
             }
         }
 
         crate::profile_scope!("MsgPack deser");
-        match rmp_serde::from_read(&mut self.buffer.as_slice()) {
+        match rmp_serde::from_slice(&self.buffer) {
             Ok(msg) => Some(Ok(msg)),
             Err(err) => Some(Err(anyhow::anyhow!("MessagePack: {err}"))),
         }
@@ -115,7 +299,16 @@ impl<R: std::io::Read> Decoder<R> {
         read.read_exact(&mut header).context("missing header")?;
         anyhow::ensure!(&header == b"RRF0", "Not a rerun file");
         read.read_exact(&mut header).context("missing header")?;
-        anyhow::ensure!(header == [0, 0, 0, 0], "Incompatible rerun file format");
+        let [flags, version, reserved @ ..] = header;
+        check_format_version(version)?;
+        anyhow::ensure!(
+            flags & FLAG_DICTIONARY == 0,
+            "Recordings compressed with a dictionary can not yet be loaded on the web"
+        );
+        anyhow::ensure!(
+            flags == 0 && reserved == [0, 0],
+            "Incompatible rerun file format"
+        );
 
         let zdecoder =
             ruzstd::StreamingDecoder::new(read).map_err(|err| anyhow::anyhow!("ruzstd: {err}"))?;
@@ -137,19 +330,17 @@ impl<R: std::io::Read> Iterator for Decoder<R> {
 
         let mut len = [0_u8; 8];
         self.zdecoder.read_exact(&mut len).ok()?;
-        let len = u64::from_le_bytes(len) as usize;
-
-        self.buffer.resize(len, 0);
+        let len = u64::from_le_bytes(len);
 
         {
             crate::profile_scope!("ruzstd");
-            if let Err(err) = self.zdecoder.read_exact(&mut self.buffer) {
+            if let Err(err) = read_exactly_into(&mut self.zdecoder, len, &mut self.buffer) {
                 return Some(Err(anyhow::anyhow!("ruzstd: {err}")));
             }
         }
 
         crate::profile_scope!("MsgPack deser");
-        match rmp_serde::from_read(&mut self.buffer.as_slice()) {
+        match rmp_serde::from_slice(&self.buffer) {
             Ok(msg) => Some(Ok(msg)),
             Err(err) => Some(Err(anyhow::anyhow!("MessagePack: {err}"))),
         }
@@ -161,20 +352,44 @@ impl<R: std::io::Read> Iterator for Decoder<R> {
 #[cfg(all(feature = "load", feature = "save"))]
 #[test]
 fn test_encode_decode() {
-    use crate::{BeginRecordingMsg, LogMsg, MsgId, Time};
-
-    let messages = vec![LogMsg::BeginRecordingMsg(BeginRecordingMsg {
-        msg_id: MsgId::random(),
-        info: crate::RecordingInfo {
-            application_id: crate::ApplicationId("test".to_owned()),
-            recording_id: crate::RecordingId::random(),
-            is_official_example: true,
-            started: Time::now(),
-            recording_source: crate::RecordingSource::RustSdk {
-                rust_version: env!("CARGO_PKG_RUST_VERSION").into(),
+    use crate::{
+        BeginRecordingMsg, BlueprintMsg, BlueprintOp, LogMsg, MsgId, SpaceViewDescription,
+        SpaceViewKind, Time, TraceSpan, TraceSpansMsg,
+    };
+
+    let messages = vec![
+        LogMsg::BeginRecordingMsg(BeginRecordingMsg {
+            msg_id: MsgId::random(),
+            info: crate::RecordingInfo {
+                application_id: crate::ApplicationId("test".to_owned()),
+                recording_id: crate::RecordingId::random(),
+                is_official_example: true,
+                started: Time::now(),
+                recording_source: crate::RecordingSource::RustSdk {
+                    rust_version: env!("CARGO_PKG_RUST_VERSION").into(),
+                },
             },
-        },
-    })];
+        }),
+        LogMsg::BlueprintMsg(BlueprintMsg {
+            msg_id: MsgId::random(),
+            op: BlueprintOp::AddSpaceView(
+                SpaceViewDescription::new("plot", SpaceViewKind::TimeSeries, "sensors")
+                    .with_entities(["sensors/a", "sensors/b"]),
+            ),
+        }),
+        LogMsg::TraceSpansMsg(TraceSpansMsg {
+            msg_id: MsgId::random(),
+            spans: vec![TraceSpan {
+                trace_id: u128::MAX,
+                span_id: 42,
+                parent_span_id: None,
+                name: "detect_objects".to_owned(),
+                service_name: "perception".to_owned(),
+                start: Time::from_seconds_since_epoch(1.0),
+                end: Time::from_seconds_since_epoch(1.5),
+            }],
+        }),
+    ];
 
     let mut file = vec![];
     encode(messages.iter(), &mut file).unwrap();
@@ -186,3 +401,227 @@ fn test_encode_decode() {
 
     assert_eq!(messages, decoded_messages);
 }
+
+#[cfg(all(feature = "load", feature = "save"))]
+#[test]
+fn test_decode_flushed_but_unfinished() {
+    use crate::{LogMsg, MsgId};
+
+    let messages = vec![
+        LogMsg::Goodbye(MsgId::random()),
+        LogMsg::Goodbye(MsgId::random()),
+    ];
+
+    let mut file = vec![];
+    let mut encoder = Encoder::new(&mut file).unwrap();
+    for message in &messages {
+        encoder.append(message).unwrap();
+    }
+    encoder.flush().unwrap();
+    drop(encoder); // simulate a writer that is still going
+
+    let decoded_messages = Decoder::new(&mut file.as_slice())
+        .unwrap()
+        .collect::<anyhow::Result<Vec<LogMsg>>>()
+        .unwrap();
+
+    assert_eq!(messages, decoded_messages);
+}
+
+#[cfg(all(feature = "load", feature = "save"))]
+#[test]
+fn test_encode_decode_with_dictionary() {
+    use crate::{LogMsg, MsgId};
+
+    let messages: Vec<LogMsg> = (0..2_000)
+        .map(|_| LogMsg::Goodbye(MsgId::random()))
+        .collect();
+
+    let dictionary = train_dictionary(messages.iter().cloned()).unwrap().unwrap();
+
+    let mut file = vec![];
+    let mut encoder = Encoder::new_with_dictionary(&mut file, 19, &dictionary).unwrap();
+    for message in &messages {
+        encoder.append(message).unwrap();
+    }
+    encoder.finish().unwrap();
+
+    let decoded_messages = Decoder::new(&mut file.as_slice())
+        .unwrap()
+        .collect::<anyhow::Result<Vec<LogMsg>>>()
+        .unwrap();
+
+    assert_eq!(messages, decoded_messages);
+}
+
+#[cfg(all(feature = "load", feature = "save"))]
+#[test]
+fn test_decode_newer_format_version() {
+    let mut file = vec![];
+    encode(std::iter::empty(), &mut file).unwrap();
+    assert_eq!(file[5], FORMAT_VERSION);
+
+    file[5] = FORMAT_VERSION + 1;
+    let err = Decoder::new(&mut file.as_slice()).err().unwrap();
+    assert!(err.to_string().contains("newer version of Rerun"));
+}
+
+/// A [`LogMsg::ArrowMsg`] with points of random positions and colors.
+#[cfg(all(test, feature = "load", feature = "save"))]
+fn arbitrary_arrow_msg() -> impl proptest::strategy::Strategy<Value = LogMsg> {
+    use crate::{
+        component_types::{ColorRGBA, Point2D},
+        msg_bundle::try_build_msg_bundle2,
+        MsgId, TimeInt, Timeline,
+    };
+    use proptest::prelude::*;
+
+    // Finite coordinates, since NaN != NaN.
+    let coordinate = -1e6_f32..1e6_f32;
+    (
+        "[a-z]{1,8}(/[a-z]{1,8}){0,2}",
+        any::<i64>(),
+        proptest::collection::vec((coordinate.clone(), coordinate, any::<u32>()), 0..8),
+    )
+        .prop_map(|(entity_path, frame_nr, points)| {
+            let (positions, colors): (Vec<_>, Vec<_>) = points
+                .into_iter()
+                .map(|(x, y, color)| (Point2D { x, y }, ColorRGBA(color)))
+                .unzip();
+            let bundle = try_build_msg_bundle2(
+                MsgId::random(),
+                entity_path.as_str(),
+                [(Timeline::new_sequence("frame_nr"), TimeInt::from(frame_nr))],
+                (positions, colors),
+            )
+            .unwrap();
+            LogMsg::ArrowMsg(bundle.try_into().unwrap())
+        })
+}
+
+#[cfg(all(test, feature = "load", feature = "save"))]
+fn arbitrary_log_msg() -> impl proptest::strategy::Strategy<Value = LogMsg> {
+    use crate::{
+        ApplicationId, BeginRecordingMsg, MsgId, RecordingId, RecordingInfo, RecordingSource, Time,
+        TraceSpan, TraceSpansMsg,
+    };
+    use proptest::prelude::*;
+
+    let begin_recording = (".*", any::<bool>(), any::<i64>(), ".*").prop_map(
+        |(application_id, is_official_example, started, source)| {
+            LogMsg::BeginRecordingMsg(BeginRecordingMsg {
+                msg_id: MsgId::random(),
+                info: RecordingInfo {
+                    application_id: ApplicationId(application_id),
+                    recording_id: RecordingId::random(),
+                    is_official_example,
+                    started: Time::from_ns_since_epoch(started),
+                    recording_source: RecordingSource::Other(source),
+                },
+            })
+        },
+    );
+
+    let span = (
+        any::<u128>(),
+        any::<u64>(),
+        any::<Option<u64>>(),
+        ".*",
+        ".*",
+        any::<i64>(),
+        any::<i64>(),
+    )
+        .prop_map(
+            |(trace_id, span_id, parent_span_id, name, service_name, start, end)| TraceSpan {
+                trace_id,
+                span_id,
+                parent_span_id,
+                name,
+                service_name,
+                start: Time::from_ns_since_epoch(start),
+                end: Time::from_ns_since_epoch(end),
+            },
+        );
+    let trace_spans = proptest::collection::vec(span, 0..8).prop_map(|spans| {
+        LogMsg::TraceSpansMsg(TraceSpansMsg {
+            msg_id: MsgId::random(),
+            spans,
+        })
+    });
+
+    let goodbye = Just(()).prop_map(|()| LogMsg::Goodbye(MsgId::random()));
+
+    prop_oneof![arbitrary_arrow_msg(), begin_recording, trace_spans, goodbye]
+}
+
+#[cfg(all(test, feature = "load", feature = "save"))]
+proptest::proptest! {
+    #[test]
+    fn proptest_encode_decode(
+        messages in proptest::collection::vec(arbitrary_log_msg(), 0..8)
+    ) {
+        let mut file = vec![];
+        encode(messages.iter(), &mut file).unwrap();
+
+        let decoded_messages = Decoder::new(&mut file.as_slice())
+            .unwrap()
+            .collect::<anyhow::Result<Vec<LogMsg>>>()
+            .unwrap();
+        proptest::prop_assert_eq!(messages, decoded_messages);
+    }
+
+    /// Files come from untrusted sources, so garbage must be rejected without panicking.
+    #[test]
+    fn proptest_decode_garbage(
+        bytes in proptest::collection::vec(proptest::arbitrary::any::<u8>(), 0..256)
+    ) {
+        if let Ok(decoder) = Decoder::new(&mut bytes.as_slice()) {
+            for _ in decoder {}
+        }
+    }
+
+    /// A valid header and zstd stream, but garbage messages (e.g. huge lengths).
+    #[test]
+    fn proptest_decode_garbage_messages(
+        bytes in proptest::collection::vec(proptest::arbitrary::any::<u8>(), 0..256)
+    ) {
+        let mut file = vec![];
+        encode(std::iter::empty(), &mut file).unwrap();
+        file.truncate(8); // Just the header
+        file.extend(zstd::stream::encode_all(bytes.as_slice(), 0).unwrap());
+
+        if let Ok(decoder) = Decoder::new(&mut file.as_slice()) {
+            for _ in decoder {}
+        }
+    }
+
+    /// A valid [`crate::ArrowMsg`], except for some bytes of its arrow IPC stream.
+    #[test]
+    fn proptest_decode_garbage_arrow(
+        msg in arbitrary_arrow_msg(),
+        garbage in proptest::collection::vec(
+            (proptest::arbitrary::any::<usize>(), proptest::arbitrary::any::<u8>()),
+            1..8,
+        )
+    ) {
+        let mut msg = rmp_serde::to_vec_named(&msg).unwrap();
+        // The arrow IPC stream starts with a continuation marker, after the `MsgId`.
+        let ipc_start = msg
+            .windows(4)
+            .position(|window| window == [0xff; 4])
+            .unwrap();
+        let ipc_len = msg.len() - ipc_start;
+        for (index, byte) in garbage {
+            msg[ipc_start + index % ipc_len] = byte;
+        }
+
+        let mut file = vec![];
+        encode(std::iter::empty(), &mut file).unwrap();
+        file.truncate(8); // Just the header
+        let mut payload = (msg.len() as u64).to_le_bytes().to_vec();
+        payload.extend(msg);
+        file.extend(zstd::stream::encode_all(payload.as_slice(), 0).unwrap());
+
+        for _ in Decoder::new(&mut file.as_slice()).unwrap() {}
+    }
+}
@@ -13,17 +13,21 @@ pub mod encoding;
 pub mod datagen;
 
 pub mod arrow_msg;
+pub mod blueprint_msg;
 pub mod component_types;
 pub use arrow_msg::ArrowMsg;
+pub use blueprint_msg::{BlueprintMsg, BlueprintOp, SpaceViewDescription, SpaceViewKind};
 mod data;
 pub mod hash;
 mod index;
 pub mod msg_bundle;
 pub mod path;
+pub mod point_cloud;
 mod time;
 pub mod time_point;
 mod time_range;
 mod time_real;
+pub mod trace_span;
 
 pub mod external {
     pub use arrow2;
@@ -46,10 +50,14 @@ pub use self::component_types::{EncodedMesh3D, Mesh3D, MeshFormat, MeshId, RawMe
 pub use self::data::*;
 pub use self::index::*;
 pub use self::path::*;
-pub use self::time::{Duration, Time};
+pub use self::point_cloud::{PointCloud, PointCloudError};
+pub use self::time::{
+    Duration, ParseTimeError, Time, TimeFormatOptions, TimePrecision, TimeScale, TimeZone,
+};
 pub use self::time_point::{TimeInt, TimePoint, TimeType, Timeline, TimelineName};
 pub use self::time_range::{TimeRange, TimeRangeF};
 pub use self::time_real::TimeReal;
+pub use self::trace_span::{TraceSpan, TraceSpansMsg};
 
 #[macro_export]
 macro_rules! impl_into_enum {
@@ -93,7 +101,7 @@ impl RecordingId {
 
     #[inline]
     pub fn random() -> Self {
-        Self(uuid::Uuid::new_v4())
+        Self(random_uuid())
     }
 
     #[inline]
@@ -168,6 +176,12 @@ pub enum LogMsg {
 
     /// Sent when the client shuts down the connection.
     Goodbye(MsgId),
+
+    /// Tell the viewer how to lay out the data, using a [`BlueprintMsg`].
+    BlueprintMsg(BlueprintMsg),
+
+    /// Spans from a tracing system, e.g. OpenTelemetry, using a [`TraceSpansMsg`].
+    TraceSpansMsg(TraceSpansMsg),
 }
 
 impl LogMsg {
@@ -177,6 +191,8 @@ impl LogMsg {
             Self::EntityPathOpMsg(msg) => msg.msg_id,
             Self::ArrowMsg(msg) => msg.msg_id,
             Self::Goodbye(msg_id) => *msg_id,
+            Self::BlueprintMsg(msg) => msg.msg_id,
+            Self::TraceSpansMsg(msg) => msg.msg_id,
         }
     }
 }
@@ -184,6 +200,39 @@ impl LogMsg {
 impl_into_enum!(BeginRecordingMsg, LogMsg, BeginRecordingMsg);
 impl_into_enum!(EntityPathOpMsg, LogMsg, EntityPathOpMsg);
 impl_into_enum!(ArrowMsg, LogMsg, ArrowMsg);
+impl_into_enum!(BlueprintMsg, LogMsg, BlueprintMsg);
+impl_into_enum!(TraceSpansMsg, LogMsg, TraceSpansMsg);
+
+// ----------------------------------------------------------------------------
+
+/// Make the random ids ([`MsgId`], [`RecordingId`], …) and [`Time::now`] count up instead,
+/// so that logging the same data gives identical messages on every run, e.g. for snapshot tests.
+///
+/// See [`re_tuid::set_deterministic`] for the caveats.
+pub fn set_deterministic(deterministic: bool) {
+    re_tuid::set_deterministic(deterministic);
+}
+
+/// A random [`uuid::Uuid`], or the next one in line if [`set_deterministic`] is on.
+pub(crate) fn random_uuid() -> uuid::Uuid {
+    #[cfg(not(target_arch = "wasm32"))]
+    if re_tuid::is_deterministic() {
+        return uuid::Uuid::from_u128(re_tuid::Tuid::random().as_u128());
+    }
+    uuid::Uuid::new_v4()
+}
+
+/// Many [`LogMsg`]es sent together, to save the per-message overhead of the transport.
+///
+/// Receivers handle the messages as if they had arrived one by one.
+#[must_use]
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(test, derive(PartialEq))]
+pub struct LogMsgBatch {
+    /// In the order they were logged.
+    pub msgs: Vec<LogMsg>,
+}
 
 // ----------------------------------------------------------------------------
 
@@ -390,6 +390,23 @@ impl std::fmt::Display for MsgBundle {
     }
 }
 
+/// The entity path in the metadata of this schema, without parsing it.
+pub(crate) fn entity_path_str(schema: &Schema) -> Option<&str> {
+    schema.metadata.get(ENTITY_PATH_KEY).map(String::as_str)
+}
+
+/// Does the components column of this schema contain the given component?
+pub(crate) fn has_component(schema: &Schema, component: &ComponentName) -> bool {
+    schema
+        .fields
+        .iter()
+        .filter(|field| field.name == COL_COMPONENTS)
+        .any(|field| match field.data_type() {
+            DataType::Struct(fields) => fields.iter().any(|f| f.name == component.as_str()),
+            _ => false,
+        })
+}
+
 /// Pack the passed iterator of `ComponentBundle` into a `(Schema, StructArray)` tuple.
 #[inline]
 fn pack_components(components: impl Iterator<Item = ComponentBundle>) -> (Schema, StructArray) {
@@ -0,0 +1,223 @@
+//! Logging millions of points at once.
+
+use arrow2::array::{Array, PrimitiveArray, StructArray};
+use arrow2_convert::field::ArrowField as _;
+
+use crate::{
+    component_types::{ColorRGBA, InstanceKey, Point3D, Radius},
+    msg_bundle::{wrap_in_listarray, Component as _, ComponentBundle},
+};
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum PointCloudError {
+    #[error("Packed positions must be x, y, z triplets, got {0} floats")]
+    NotTriplets(usize),
+
+    #[error("Expected one {component} per point, i.e. {expected}, got {actual}")]
+    WrongLength {
+        component: &'static str,
+        expected: usize,
+        actual: usize,
+    },
+
+    #[error("InstanceKey(u64::MAX) is reserved for Rerun internals")]
+    IllegalInstanceKey,
+}
+
+pub type Result<T> = std::result::Result<T, PointCloudError>;
+
+/// 3D points with optional per-point colors, radii and instance keys.
+///
+/// Kept as flat arrays, and turned into the [`Point3D`], [`ColorRGBA`], [`Radius`] and
+/// [`InstanceKey`] components without serializing the points one by one,
+/// so the viewer shows it like any other 3D points.
+///
+/// ```
+/// # use re_log_types::PointCloud;
+/// let point_cloud = PointCloud::from_packed_positions(&[0.0, 0.0, 0.0, 1.0, 2.0, 3.0])?
+///     .with_colors([[255, 0, 0, 255], [0, 255, 0, 255]])?
+///     .with_radii([0.1, 0.2])?;
+/// assert_eq!(point_cloud.len(), 2);
+/// # Ok::<(), re_log_types::PointCloudError>(())
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PointCloud {
+    /// One coordinate per axis, so that each can be sent as is.
+    xs: Vec<f32>,
+    ys: Vec<f32>,
+    zs: Vec<f32>,
+
+    /// Packed like [`ColorRGBA`].
+    colors: Option<Vec<u32>>,
+    radii: Option<Vec<f32>>,
+    instance_keys: Option<Vec<u64>>,
+}
+
+impl PointCloud {
+    pub fn new(positions: impl IntoIterator<Item = impl Into<[f32; 3]>>) -> Self {
+        let positions = positions.into_iter();
+        let capacity = positions.size_hint().0;
+        let mut this = Self {
+            xs: Vec::with_capacity(capacity),
+            ys: Vec::with_capacity(capacity),
+            zs: Vec::with_capacity(capacity),
+            ..Default::default()
+        };
+        for position in positions {
+            let [x, y, z] = position.into();
+            this.xs.push(x);
+            this.ys.push(y);
+            this.zs.push(z);
+        }
+        this
+    }
+
+    /// From `x, y, z, x, y, z, …`, the way most point cloud files and sensors store them.
+    pub fn from_packed_positions(xyz: &[f32]) -> Result<Self> {
+        if xyz.len() % 3 != 0 {
+            return Err(PointCloudError::NotTriplets(xyz.len()));
+        }
+        Ok(Self::new(
+            xyz.chunks_exact(3).map(|xyz| [xyz[0], xyz[1], xyz[2]]),
+        ))
+    }
+
+    pub fn with_colors(
+        mut self,
+        colors: impl IntoIterator<Item = impl Into<ColorRGBA>>,
+    ) -> Result<Self> {
+        let colors: Vec<u32> = colors.into_iter().map(|color| color.into().0).collect();
+        self.check_len("color", colors.len())?;
+        self.colors = Some(colors);
+        Ok(self)
+    }
+
+    pub fn with_radii(mut self, radii: impl IntoIterator<Item = f32>) -> Result<Self> {
+        let radii: Vec<f32> = radii.into_iter().collect();
+        self.check_len("radius", radii.len())?;
+        self.radii = Some(radii);
+        Ok(self)
+    }
+
+    /// Without these, the points get the keys `0..len`.
+    pub fn with_instance_keys(
+        mut self,
+        instance_keys: impl IntoIterator<Item = u64>,
+    ) -> Result<Self> {
+        let instance_keys: Vec<u64> = instance_keys.into_iter().collect();
+        self.check_len("instance key", instance_keys.len())?;
+        if instance_keys.contains(&u64::MAX) {
+            return Err(PointCloudError::IllegalInstanceKey);
+        }
+        self.instance_keys = Some(instance_keys);
+        Ok(self)
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.xs.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.xs.is_empty()
+    }
+
+    fn check_len(&self, component: &'static str, actual: usize) -> Result<()> {
+        if actual == self.len() {
+            Ok(())
+        } else {
+            Err(PointCloudError::WrongLength {
+                component,
+                expected: self.len(),
+                actual,
+            })
+        }
+    }
+
+    /// One component per kind of data, each with a single row with one instance per point.
+    pub fn component_bundles(&self) -> Vec<ComponentBundle> {
+        crate::profile_function!();
+
+        fn bundle(name: crate::ComponentName, array: Box<dyn Array>) -> ComponentBundle {
+            ComponentBundle::new(name, wrap_in_listarray(array))
+        }
+
+        let positions = StructArray::new(
+            Point3D::data_type(),
+            vec![
+                PrimitiveArray::from_vec(self.xs.clone()).boxed(),
+                PrimitiveArray::from_vec(self.ys.clone()).boxed(),
+                PrimitiveArray::from_vec(self.zs.clone()).boxed(),
+            ],
+            None,
+        );
+        let mut bundles = vec![bundle(Point3D::name(), positions.boxed())];
+
+        if let Some(colors) = &self.colors {
+            let colors = PrimitiveArray::from_vec(colors.clone());
+            bundles.push(bundle(ColorRGBA::name(), colors.boxed()));
+        }
+        if let Some(radii) = &self.radii {
+            let radii = PrimitiveArray::from_vec(radii.clone());
+            bundles.push(bundle(Radius::name(), radii.boxed()));
+        }
+        if let Some(instance_keys) = &self.instance_keys {
+            let instance_keys = PrimitiveArray::from_vec(instance_keys.clone());
+            bundles.push(bundle(InstanceKey::name(), instance_keys.boxed()));
+        }
+
+        bundles
+    }
+}
+
+#[test]
+fn test_point_cloud_bundles() {
+    let positions = vec![Point3D::new(1.0, 2.0, 3.0), Point3D::new(4.0, 5.0, 6.0)];
+    let colors = vec![
+        ColorRGBA::from_rgb(255, 0, 0),
+        ColorRGBA::from_rgb(0, 0, 255),
+    ];
+    let radii = vec![Radius(0.5), Radius(1.5)];
+    let instance_keys = vec![InstanceKey(7), InstanceKey(42)];
+
+    let point_cloud = PointCloud::from_packed_positions(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0])
+        .unwrap()
+        .with_colors(colors.clone())
+        .unwrap()
+        .with_radii(radii.iter().map(|radius| radius.0))
+        .unwrap()
+        .with_instance_keys(instance_keys.iter().map(|key| key.0))
+        .unwrap();
+
+    // Exactly what serializing the components one by one gives:
+    let expected = [
+        ComponentBundle::try_from(positions).unwrap(),
+        ComponentBundle::try_from(colors).unwrap(),
+        ComponentBundle::try_from(radii).unwrap(),
+        ComponentBundle::try_from(instance_keys).unwrap(),
+    ];
+    let bundles = point_cloud.component_bundles();
+    assert_eq!(bundles.len(), expected.len());
+    for (bundle, expected) in bundles.iter().zip(&expected) {
+        assert_eq!(bundle.name(), expected.name());
+        assert_eq!(bundle.value_list(), expected.value_list());
+    }
+
+    assert_eq!(
+        PointCloud::from_packed_positions(&[1.0, 2.0]),
+        Err(PointCloudError::NotTriplets(2))
+    );
+    assert_eq!(
+        PointCloud::new([[0.0_f32; 3]]).with_radii([1.0, 2.0]),
+        Err(PointCloudError::WrongLength {
+            component: "radius",
+            expected: 1,
+            actual: 2,
+        })
+    );
+    assert_eq!(
+        PointCloud::new([[0.0_f32; 3]]).with_instance_keys([u64::MAX]),
+        Err(PointCloudError::IllegalInstanceKey)
+    );
+}
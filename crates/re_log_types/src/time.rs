@@ -5,10 +5,29 @@ use std::ops::RangeInclusive;
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Time(i64);
 
+#[cfg(not(target_arch = "wasm32"))]
+lazy_static::lazy_static! {
+    /// When we first read the monotonic clock, and the wall-clock time at that point.
+    ///
+    /// See [`Time::from_monotonic`].
+    static ref MONOTONIC_EPOCH: (std::time::Instant, Time) =
+        (std::time::Instant::now(), Time::now());
+}
+
 impl Time {
+    /// The current wall-clock time.
+    ///
+    /// This follows any adjustments to the system clock, so consecutive calls can go backwards.
+    /// Use [`Self::monotonic_now`] for timestamps that must not.
+    ///
+    /// With [`crate::set_deterministic`], this is a clock that ticks once per call.
     #[cfg(not(target_arch = "wasm32"))]
     #[inline]
     pub fn now() -> Self {
+        if re_tuid::is_deterministic() {
+            return Self(re_tuid::Tuid::random().nanoseconds_since_epoch() as _);
+        }
+
         let nanos_since_epoch = std::time::SystemTime::UNIX_EPOCH
             .elapsed()
             .expect("Expected system clock to be set to after 1970")
@@ -16,6 +35,41 @@ impl Time {
         Self(nanos_since_epoch)
     }
 
+    /// The current wall-clock time, according to the browser.
+    #[cfg(target_arch = "wasm32")]
+    #[inline]
+    pub fn now() -> Self {
+        let now = chrono::Utc::now();
+        Self(now.timestamp() * 1_000_000_000 + now.timestamp_subsec_nanos() as i64)
+    }
+
+    /// Convert a reading of the monotonic clock into a wall-clock time.
+    ///
+    /// The first time this (or [`Self::monotonic_now`]) is called, we pair the current
+    /// [`std::time::Instant`] with [`Self::now`]. Every instant is then converted to that
+    /// wall-clock time plus the monotonic time elapsed since.
+    ///
+    /// So the results never jump when the system clock is adjusted (e.g. by NTP),
+    /// but can slowly drift away from the system clock in a long-running process.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_monotonic(instant: std::time::Instant) -> Self {
+        let (epoch_instant, epoch_time) = *MONOTONIC_EPOCH;
+        if epoch_instant <= instant {
+            epoch_time + Duration::from_nanos((instant - epoch_instant).as_nanos() as i64)
+        } else {
+            epoch_time - Duration::from_nanos((epoch_instant - instant).as_nanos() as i64)
+        }
+    }
+
+    /// The current time according to the monotonic clock.
+    ///
+    /// Never goes backwards. See [`Self::from_monotonic`].
+    #[cfg(not(target_arch = "wasm32"))]
+    #[inline]
+    pub fn monotonic_now() -> Self {
+        Self::from_monotonic(std::time::Instant::now())
+    }
+
     #[inline]
     pub fn nanos_since_epoch(&self) -> i64 {
         self.0
@@ -37,28 +91,41 @@ impl Time {
     }
 
     /// If true, this time is likely relative to unix epoch.
+    ///
+    /// See [`TimeFormatOptions::absolute_threshold`].
     pub fn is_abolute_date(&self) -> bool {
-        let nanos_since_epoch = self.nanos_since_epoch();
-        let years_since_epoch = nanos_since_epoch / 1_000_000_000 / 60 / 60 / 24 / 365;
+        self.is_absolute_with(&TimeFormatOptions::default())
+    }
 
-        20 <= years_since_epoch && years_since_epoch <= 150
+    /// Far enough from the epoch, in either direction, to be shown as a date.
+    fn is_absolute_with(&self, options: &TimeFormatOptions) -> bool {
+        options.absolute_threshold.as_nanos() <= self.0.saturating_abs()
     }
 
     /// Returns the absolute datetime, if this is a valid, unambiguous, absolute time.
     pub fn to_chrono(&self) -> Option<chrono::DateTime<chrono::Utc>> {
-        let ns_since_epoch = self.nanos_since_epoch();
-        if self.is_abolute_date() {
-            use chrono::TimeZone as _;
-            if let chrono::LocalResult::Single(datetime) = chrono::Utc.timestamp_opt(
-                ns_since_epoch / 1_000_000_000,
-                (ns_since_epoch % 1_000_000_000) as _,
-            ) {
-                Some(datetime)
-            } else {
-                None
-            }
+        self.to_chrono_with(&TimeFormatOptions::default())
+    }
+
+    /// Like [`Self::to_chrono`], using the threshold and [`TimeScale`] of `options`.
+    ///
+    /// During a leap second the nanoseconds are a billion or more, which chrono shows as `23:59:60`.
+    pub fn to_chrono_with(
+        &self,
+        options: &TimeFormatOptions,
+    ) -> Option<chrono::DateTime<chrono::Utc>> {
+        use chrono::{TimeZone as _, Timelike as _};
+
+        if !self.is_absolute_with(options) {
+            return None;
+        }
+
+        let (unix_nanos, is_leap_second) = options.time_scale.to_unix_nanos(self.0);
+        let datetime = chrono::Utc.timestamp_nanos(unix_nanos);
+        if is_leap_second {
+            datetime.with_nanosecond(datetime.nanosecond() + 1_000_000_000)
         } else {
-            None
+            Some(datetime)
         }
     }
 
@@ -70,64 +137,68 @@ impl Time {
         self.nanos_since_epoch() % (24 * 60 * 60 * 1_000_000_000) == 0
     }
 
-    /// Human-readable formatting
+    /// Human-readable formatting, in UTC.
     pub fn format(&self) -> String {
+        self.format_with(&TimeFormatOptions::default())
+    }
+
+    /// Human-readable formatting.
+    ///
+    /// Times close to the unix epoch are shown relative to it, e.g. `+1.5s`,
+    /// see [`TimeFormatOptions::absolute_threshold`].
+    pub fn format_with(&self, options: &TimeFormatOptions) -> String {
         let nanos_since_epoch = self.nanos_since_epoch();
 
-        if self.is_abolute_date() {
-            use chrono::TimeZone as _;
-            let datetime = chrono::Utc.timestamp_opt(
-                nanos_since_epoch / 1_000_000_000,
-                (nanos_since_epoch % 1_000_000_000) as _,
-            );
-            match datetime {
-                chrono::LocalResult::Single(datetime) => {
-                    let is_whole_second = nanos_since_epoch % 1_000_000_000 == 0;
-                    let is_whole_millisecond = nanos_since_epoch % 1_000_000 == 0;
-
-                    let time_format = if is_whole_second {
-                        "%H:%M:%SZ"
-                    } else if is_whole_millisecond {
-                        "%H:%M:%S%.3fZ"
-                    } else {
-                        "%H:%M:%S%.6fZ"
-                    };
-
-                    if datetime.date_naive() == chrono::offset::Utc::now().date_naive() {
-                        datetime.format(time_format).to_string()
-                    } else {
-                        let date_format = format!("%Y-%m-%d {time_format}");
-                        datetime.format(&date_format).to_string()
-                    }
-                }
-                chrono::LocalResult::None => "Invalid timestamp".to_owned(),
-                chrono::LocalResult::Ambiguous(_, _) => "Ambiguous timestamp".to_owned(),
+        if let Some(datetime) = self.to_chrono_with(options) {
+            let subsec_format = options
+                .precision
+                .subsec_format(datetime.timestamp_subsec_nanos() as i64);
+            match options.timezone {
+                TimeZone::Utc => format_datetime(datetime, &format!("%H:%M:%S{subsec_format}Z")),
+                TimeZone::Local => format_datetime(
+                    datetime.with_timezone(&chrono::Local),
+                    &format!("%H:%M:%S{subsec_format}"),
+                ),
             }
         } else {
             // Relative time
             let secs = nanos_since_epoch as f64 * 1e-9;
-
-            let is_whole_second = nanos_since_epoch % 1_000_000_000 == 0;
-            if is_whole_second {
-                format!("{secs:+.0}s")
-            } else {
-                format!("{secs:+.3}s")
-            }
+            let decimals = options.precision.relative_decimals(nanos_since_epoch);
+            format!("{secs:+.decimals$}s")
         }
     }
 
+    /// Format as UTC with a [`chrono::format::strftime`] format string.
     pub fn format_time(&self, format_str: &str) -> String {
         use chrono::TimeZone as _;
-        let nanos_since_epoch = self.nanos_since_epoch();
-        let datetime = chrono::Utc.timestamp_opt(
-            nanos_since_epoch / 1_000_000_000,
-            (nanos_since_epoch % 1_000_000_000) as _,
-        );
-        match datetime {
-            chrono::LocalResult::Single(datetime) => datetime.format(format_str).to_string(),
-            chrono::LocalResult::None => "Invalid timestamp".to_owned(),
-            chrono::LocalResult::Ambiguous(_, _) => "Ambiguous timestamp".to_owned(),
-        }
+        chrono::Utc
+            .timestamp_nanos(self.nanos_since_epoch())
+            .format(format_str)
+            .to_string()
+    }
+
+    /// `None` on overflow. Unlike `+`, which saturates.
+    #[inline]
+    pub fn checked_add(self, duration: Duration) -> Option<Self> {
+        self.0.checked_add(duration.0).map(Self)
+    }
+
+    /// `None` on overflow. Unlike `-`, which saturates.
+    #[inline]
+    pub fn checked_sub(self, duration: Duration) -> Option<Self> {
+        self.0.checked_sub(duration.0).map(Self)
+    }
+
+    /// Same as `+`.
+    #[inline]
+    pub fn saturating_add(self, duration: Duration) -> Self {
+        Self(self.0.saturating_add(duration.0))
+    }
+
+    /// Same as `-`.
+    #[inline]
+    pub fn saturating_sub(self, duration: Duration) -> Self {
+        Self(self.0.saturating_sub(duration.0))
     }
 
     #[inline]
@@ -135,6 +206,47 @@ impl Time {
         let (min, max) = (range.start().0, range.end().0);
         Self(min + ((max - min) as f64 * (t as f64)).round() as i64)
     }
+
+    /// Parse user input, e.g. from a command line argument.
+    ///
+    /// Accepts an RFC3339 date-time like `2023-01-31T12:34:56.789Z`
+    /// (optionally with a space instead of the `T`, like [`Self::format`] uses),
+    /// or a time relative to the start, like `+1.5s` or `-1m 30s` (see [`Duration::parse`]).
+    pub fn parse(s: &str) -> Result<Self, ParseTimeError> {
+        let s = s.trim();
+        if let Some(relative) = s.strip_prefix('+') {
+            return Duration::parse(relative).map(|duration| Self(duration.0));
+        }
+        if s.starts_with('-') {
+            return Duration::parse(s).map(|duration| Self(duration.0));
+        }
+
+        let datetime = chrono::DateTime::parse_from_rfc3339(s)
+            .or_else(|_| chrono::DateTime::parse_from_rfc3339(&s.replacen(' ', "T", 1)))
+            .map_err(|err| ParseTimeError::BadDateTime(err.to_string()))?;
+        datetime
+            .timestamp()
+            .checked_mul(Duration::NANOS_PER_SEC)
+            .and_then(|nanos| nanos.checked_add(datetime.timestamp_subsec_nanos() as i64))
+            .map(Self)
+            .ok_or(ParseTimeError::OutOfRange)
+    }
+}
+
+/// Leaves out the date if it is today.
+fn format_datetime<Tz>(datetime: chrono::DateTime<Tz>, time_format: &str) -> String
+where
+    Tz: chrono::TimeZone,
+    Tz::Offset: std::fmt::Display,
+{
+    let now = chrono::Utc::now().with_timezone(&datetime.timezone());
+    if datetime.date_naive() == now.date_naive() {
+        datetime.format(time_format).to_string()
+    } else {
+        datetime
+            .format(&format!("%Y-%m-%d {time_format}"))
+            .to_string()
+    }
 }
 
 impl std::fmt::Debug for Time {
@@ -152,28 +264,30 @@ impl std::ops::Sub for Time {
     }
 }
 
+/// Saturates, see [`Time::checked_add`].
 impl std::ops::Add<Duration> for Time {
     type Output = Time;
 
     #[inline]
     fn add(self, duration: Duration) -> Self::Output {
-        Time(self.0.saturating_add(duration.0))
+        self.saturating_add(duration)
     }
 }
 
 impl std::ops::AddAssign<Duration> for Time {
     #[inline]
     fn add_assign(&mut self, duration: Duration) {
-        self.0 = self.0.saturating_add(duration.0);
+        *self = self.saturating_add(duration);
     }
 }
 
+/// Saturates, see [`Time::checked_sub`].
 impl std::ops::Sub<Duration> for Time {
     type Output = Time;
 
     #[inline]
     fn sub(self, duration: Duration) -> Self::Output {
-        Time(self.0.saturating_sub(duration.0))
+        self.saturating_sub(duration)
     }
 }
 
@@ -188,12 +302,244 @@ impl TryFrom<std::time::SystemTime> for Time {
 
 // ----------------------------------------------------------------------------
 
+/// The time zone [`Time::format_with`] shows absolute times in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum TimeZone {
+    #[default]
+    Utc,
+
+    /// The time zone of the computer showing the time.
+    Local,
+}
+
+impl TimeZone {
+    pub const ALL: [Self; 2] = [Self::Utc, Self::Local];
+}
+
+impl std::fmt::Display for TimeZone {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Utc => "UTC",
+            Self::Local => "Local time",
+        })
+    }
+}
+
+/// How many fractional digits of a second [`Time::format_with`] shows.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum TimePrecision {
+    /// As many as needed, in steps of three.
+    #[default]
+    Auto,
+    Seconds,
+    Milliseconds,
+    Microseconds,
+    Nanoseconds,
+}
+
+impl TimePrecision {
+    pub const ALL: [Self; 5] = [
+        Self::Auto,
+        Self::Seconds,
+        Self::Milliseconds,
+        Self::Microseconds,
+        Self::Nanoseconds,
+    ];
+
+    fn subsec_format(self, nanos: i64) -> &'static str {
+        match self {
+            Self::Auto => {
+                if nanos % 1_000_000_000 == 0 {
+                    ""
+                } else if nanos % 1_000_000 == 0 {
+                    "%.3f"
+                } else {
+                    "%.6f"
+                }
+            }
+            Self::Seconds => "",
+            Self::Milliseconds => "%.3f",
+            Self::Microseconds => "%.6f",
+            Self::Nanoseconds => "%.9f",
+        }
+    }
+
+    fn relative_decimals(self, nanos: i64) -> usize {
+        match self {
+            Self::Auto => {
+                if nanos % 1_000_000_000 == 0 {
+                    0
+                } else {
+                    3
+                }
+            }
+            Self::Seconds => 0,
+            Self::Milliseconds => 3,
+            Self::Microseconds => 6,
+            Self::Nanoseconds => 9,
+        }
+    }
+}
+
+impl std::fmt::Display for TimePrecision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Auto => "Auto",
+            Self::Seconds => "Seconds",
+            Self::Milliseconds => "Milliseconds",
+            Self::Microseconds => "Microseconds",
+            Self::Nanoseconds => "Nanoseconds",
+        })
+    }
+}
+
+/// What the nanoseconds of a [`Time`] count, for recordings from systems that don't log unix time.
+///
+/// Unix time leaves out leap seconds, so it is UTC. GPS receivers and PTP clocks count them,
+/// so converting their times to UTC needs the table of leap seconds, see [`LEAP_SECONDS`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum TimeScale {
+    /// Nanoseconds since 1970-01-01 00:00:00 UTC, without leap seconds.
+    #[default]
+    Unix,
+
+    /// International Atomic Time: nanoseconds since 1970-01-01 00:00:00 TAI, like PTP clocks.
+    Tai,
+
+    /// GPS time: nanoseconds since the GPS epoch, 1980-01-06 00:00:00 UTC, with leap seconds.
+    Gps,
+}
+
+impl TimeScale {
+    pub const ALL: [Self; 3] = [Self::Unix, Self::Tai, Self::Gps];
+
+    /// The unix time of a time in this scale, and whether it is during a leap second.
+    ///
+    /// Unix time can't represent leap seconds, so those get the unix time of the second before.
+    fn to_unix_nanos(self, nanos: i64) -> (i64, bool) {
+        match self {
+            Self::Unix => (nanos, false),
+            Self::Tai => tai_to_unix_nanos(nanos),
+            Self::Gps => tai_to_unix_nanos(nanos.saturating_add(GPS_EPOCH_IN_TAI_NANOS)),
+        }
+    }
+}
+
+impl std::fmt::Display for TimeScale {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Unix => "Unix time (UTC)",
+            Self::Tai => "TAI",
+            Self::Gps => "GPS time",
+        })
+    }
+}
+
+/// When TAI - UTC changed: the unix time it changed at, in seconds, and the new difference.
+///
+/// From <https://www.ietf.org/timezones/data/leap-seconds.list>.
+/// Before 1972 the difference wasn't a whole number of seconds, and we just use the first one.
+const LEAP_SECONDS: [(i64, i64); 28] = [
+    (63_072_000, 10),    // 1972-01-01
+    (78_796_800, 11),    // 1972-07-01
+    (94_694_400, 12),    // 1973-01-01
+    (126_230_400, 13),   // 1974-01-01
+    (157_766_400, 14),   // 1975-01-01
+    (189_302_400, 15),   // 1976-01-01
+    (220_924_800, 16),   // 1977-01-01
+    (252_460_800, 17),   // 1978-01-01
+    (283_996_800, 18),   // 1979-01-01
+    (315_532_800, 19),   // 1980-01-01
+    (362_793_600, 20),   // 1981-07-01
+    (394_329_600, 21),   // 1982-07-01
+    (425_865_600, 22),   // 1983-07-01
+    (489_024_000, 23),   // 1985-07-01
+    (567_993_600, 24),   // 1988-01-01
+    (631_152_000, 25),   // 1990-01-01
+    (662_688_000, 26),   // 1991-01-01
+    (709_948_800, 27),   // 1992-07-01
+    (741_484_800, 28),   // 1993-07-01
+    (773_020_800, 29),   // 1994-07-01
+    (820_454_400, 30),   // 1996-01-01
+    (867_715_200, 31),   // 1997-07-01
+    (915_148_800, 32),   // 1999-01-01
+    (1_136_073_600, 33), // 2006-01-01
+    (1_230_768_000, 34), // 2009-01-01
+    (1_341_100_800, 35), // 2012-07-01
+    (1_435_708_800, 36), // 2015-07-01
+    (1_483_228_800, 37), // 2017-01-01
+];
+
+/// The GPS epoch (1980-01-06 00:00:00 UTC) in TAI, when TAI - UTC was 19 seconds.
+const GPS_EPOCH_IN_TAI_NANOS: i64 = (315_964_800 + 19) * Duration::NANOS_PER_SEC;
+
+/// See [`TimeScale::to_unix_nanos`].
+fn tai_to_unix_nanos(tai_nanos: i64) -> (i64, bool) {
+    for (i, &(unix_secs, tai_minus_utc)) in LEAP_SECONDS.iter().enumerate().rev() {
+        let offset = tai_minus_utc * Duration::NANOS_PER_SEC;
+        let start_in_tai = unix_secs * Duration::NANOS_PER_SEC + offset;
+        if start_in_tai <= tai_nanos {
+            return (tai_nanos - offset, false);
+        }
+        // The second before the difference grew was inserted into UTC, as 23:59:60:
+        if 0 < i && start_in_tai - Duration::NANOS_PER_SEC <= tai_nanos {
+            return (tai_nanos - offset, true);
+        }
+    }
+    (
+        tai_nanos.saturating_sub(LEAP_SECONDS[0].1 * Duration::NANOS_PER_SEC),
+        false,
+    )
+}
+
+/// How [`Time::format_with`] shows times.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct TimeFormatOptions {
+    pub timezone: TimeZone,
+
+    pub precision: TimePrecision,
+
+    /// Times at least this long after the unix epoch are shown as dates,
+    /// earlier ones relative to the epoch, e.g. `+1.5s`.
+    ///
+    /// Twenty years by default, so that times relative to e.g. the start of a process
+    /// are not shown as dates in 1970.
+    pub absolute_threshold: Duration,
+
+    /// How to interpret the nanoseconds of absolute times.
+    ///
+    /// Only affects how times are shown, not [`Time::parse`].
+    pub time_scale: TimeScale,
+}
+
+impl Default for TimeFormatOptions {
+    fn default() -> Self {
+        Self {
+            timezone: TimeZone::Utc,
+            precision: TimePrecision::Auto,
+            absolute_threshold: Duration::from_nanos(
+                20 * 365 * Duration::SEC_PER_DAY * 1_000_000_000,
+            ),
+            time_scale: TimeScale::Unix,
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 /// A signed duration represented as nanoseconds since unix epoch
 #[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Duration(i64);
 
 impl Duration {
+    pub const MIN: Duration = Duration(std::i64::MIN);
+    pub const ZERO: Duration = Duration(0);
     pub const MAX: Duration = Duration(std::i64::MAX);
     const NANOS_PER_SEC: i64 = 1_000_000_000;
     const NANOS_PER_MILLI: i64 = 1_000_000;
@@ -231,6 +577,46 @@ impl Duration {
         self.0 as f64 * 1e-9
     }
 
+    /// `None` on overflow.
+    #[inline]
+    pub fn checked_add(self, rhs: Duration) -> Option<Self> {
+        self.0.checked_add(rhs.0).map(Self)
+    }
+
+    /// `None` on overflow.
+    #[inline]
+    pub fn checked_sub(self, rhs: Duration) -> Option<Self> {
+        self.0.checked_sub(rhs.0).map(Self)
+    }
+
+    /// `None` on overflow. Unlike `*`, which saturates.
+    #[inline]
+    pub fn checked_mul(self, rhs: i64) -> Option<Self> {
+        self.0.checked_mul(rhs).map(Self)
+    }
+
+    /// `None` if `rhs` is zero, or on overflow (`Duration::MIN / -1`).
+    #[inline]
+    pub fn checked_div(self, rhs: i64) -> Option<Self> {
+        self.0.checked_div(rhs).map(Self)
+    }
+
+    #[inline]
+    pub fn saturating_add(self, rhs: Duration) -> Self {
+        Self(self.0.saturating_add(rhs.0))
+    }
+
+    #[inline]
+    pub fn saturating_sub(self, rhs: Duration) -> Self {
+        Self(self.0.saturating_sub(rhs.0))
+    }
+
+    /// Same as `*`.
+    #[inline]
+    pub fn saturating_mul(self, rhs: i64) -> Self {
+        Self(self.0.saturating_mul(rhs))
+    }
+
     pub fn exact_format(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let total_nanos = if self.0 < 0 {
             // negative duration
@@ -310,8 +696,353 @@ impl std::ops::Neg for Duration {
     }
 }
 
+/// Saturates, see [`Duration::checked_mul`].
+impl std::ops::Mul<i64> for Duration {
+    type Output = Duration;
+
+    #[inline]
+    fn mul(self, rhs: i64) -> Duration {
+        self.saturating_mul(rhs)
+    }
+}
+
+/// Panics if `rhs` is zero, like integer division. Saturates on overflow.
+///
+/// See [`Duration::checked_div`].
+impl std::ops::Div<i64> for Duration {
+    type Output = Duration;
+
+    #[inline]
+    fn div(self, rhs: i64) -> Duration {
+        assert!(rhs != 0, "Division of a Duration by zero");
+        self.checked_div(rhs).unwrap_or(Self::MAX) // Only `MIN / -1` overflows
+    }
+}
+
+impl std::fmt::Debug for Duration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.exact_format(f)
+    }
+}
+
 impl std::fmt::Display for Duration {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         self.exact_format(f)
     }
 }
+
+// ----------------------------------------------------------------------------
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum ParseTimeError {
+    #[error("Expected a duration, found empty string")]
+    EmptyString,
+
+    #[error("Expected a number, found {0:?}")]
+    ExpectedNumber(String),
+
+    #[error("Missing unit after {0:?} (expected one of d, h, m, s, ms, us, ns)")]
+    MissingUnit(String),
+
+    #[error("Unknown unit {0:?} (expected one of d, h, m, s, ms, us, ns)")]
+    UnknownUnit(String),
+
+    #[error("Time is out of range")]
+    OutOfRange,
+
+    #[error("Expected a date-time like 2023-01-31T12:34:56Z, or a relative time like +1.5s: {0}")]
+    BadDateTime(String),
+}
+
+impl Duration {
+    /// Parse the output of [`Self::exact_format`], e.g. `1d 2h 3m 4.5s` or `-250ms`.
+    ///
+    /// Each number needs a unit: `d`, `h`, `m`, `s`, `ms`, `us` (or `µs`) or `ns`.
+    /// The spaces between the parts are optional, so `1h30m` works too.
+    pub fn parse(s: &str) -> Result<Self, ParseTimeError> {
+        let s = s.trim();
+        let (is_negative, mut rest) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest.trim_start()),
+            None => (false, s),
+        };
+        if rest.is_empty() {
+            return Err(ParseTimeError::EmptyString);
+        }
+
+        let mut total_nanos: i128 = 0;
+        while !rest.is_empty() {
+            let number_len = rest
+                .find(|c: char| !c.is_ascii_digit() && c != '.')
+                .unwrap_or(rest.len());
+            let (number, after_number) = rest.split_at(number_len);
+            let unit_len = after_number
+                .find(|c: char| !c.is_alphabetic())
+                .unwrap_or(after_number.len());
+            let (unit, after_unit) = after_number.split_at(unit_len);
+
+            let (whole, fraction) = number.split_once('.').unwrap_or((number, ""));
+            if whole.is_empty() && fraction.is_empty()
+                || !fraction.chars().all(|c| c.is_ascii_digit())
+                || 18 < whole.len()
+            {
+                let part = rest.split_whitespace().next().unwrap_or_default();
+                return Err(ParseTimeError::ExpectedNumber(part.to_owned()));
+            }
+
+            let nanos_per_unit: i128 = match unit {
+                "d" => (Self::SEC_PER_DAY * Self::NANOS_PER_SEC) as _,
+                "h" => (Self::SEC_PER_HOUR * Self::NANOS_PER_SEC) as _,
+                "m" => (Self::SEC_PER_MINUTE * Self::NANOS_PER_SEC) as _,
+                "s" => Self::NANOS_PER_SEC as _,
+                "ms" => Self::NANOS_PER_MILLI as _,
+                "us" | "µs" => 1_000,
+                "ns" => 1,
+                "" => return Err(ParseTimeError::MissingUnit(number.to_owned())),
+                _ => return Err(ParseTimeError::UnknownUnit(unit.to_owned())),
+            };
+
+            // Exact decimal arithmetic, so that e.g. `0.1s` is exactly 100ms:
+            let whole: i128 = whole.parse().unwrap_or(0);
+            total_nanos += whole * nanos_per_unit;
+            let mut fraction_scale = nanos_per_unit;
+            for digit in fraction.chars().take(18) {
+                fraction_scale /= 10;
+                total_nanos += digit.to_digit(10).unwrap_or(0) as i128 * fraction_scale;
+            }
+            if i64::MAX as i128 <= total_nanos {
+                return Err(ParseTimeError::OutOfRange);
+            }
+
+            rest = after_unit.trim_start();
+        }
+
+        Ok(Self(if is_negative {
+            (-total_nanos) as i64
+        } else {
+            total_nanos as i64
+        }))
+    }
+}
+
+impl std::str::FromStr for Duration {
+    type Err = ParseTimeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl std::str::FromStr for Time {
+    type Err = ParseTimeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn test_monotonic_time() {
+    let start = std::time::Instant::now();
+    let before = Time::monotonic_now();
+    let after = Time::from_monotonic(start + std::time::Duration::from_millis(1500));
+    assert_eq!(
+        (after - Time::from_monotonic(start)).as_nanos(),
+        1_500_000_000
+    );
+    assert!(Time::from_monotonic(start) <= before);
+
+    // Close to the wall clock, at least in the short run:
+    assert!((Time::now() - before).as_secs_f64().abs() < 60.0);
+}
+
+#[test]
+fn test_parse_duration() {
+    let nanos = |s: &str| Duration::parse(s).map(|duration| duration.as_nanos());
+    assert_eq!(nanos("1d 2h 3m 4.5s"), Ok(93_784_500_000_000));
+    assert_eq!(nanos("1h30m"), Ok(5_400_000_000_000));
+    assert_eq!(nanos("0.1s"), Ok(100_000_000));
+    assert_eq!(nanos("-250ms"), Ok(-250_000_000));
+    assert_eq!(nanos("3us 7ns"), Ok(3_007));
+    assert_eq!(nanos(""), Err(ParseTimeError::EmptyString));
+    assert_eq!(nanos("5"), Err(ParseTimeError::MissingUnit("5".to_owned())));
+    assert_eq!(
+        nanos("5y"),
+        Err(ParseTimeError::UnknownUnit("y".to_owned()))
+    );
+    assert_eq!(
+        nanos("1h x"),
+        Err(ParseTimeError::ExpectedNumber("x".to_owned()))
+    );
+    assert_eq!(nanos("999999999d"), Err(ParseTimeError::OutOfRange));
+
+    // The inverse of `exact_format`:
+    for nanos in [0, 1_500_000_000, -93_784_500_000_000, 42_000_000] {
+        let duration = Duration::from_nanos(nanos);
+        assert_eq!(Duration::parse(&duration.to_string()), Ok(duration));
+    }
+}
+
+#[test]
+fn test_parse_time() {
+    assert_eq!(
+        Time::parse("2023-01-31T12:34:56.5Z").map(|time| time.nanos_since_epoch()),
+        Ok(1_675_168_496_500_000_000)
+    );
+    assert_eq!(
+        Time::parse("2023-01-31 12:34:56.5Z"),
+        Time::parse("2023-01-31T13:34:56.5+01:00")
+    );
+    assert_eq!(
+        Time::parse("+1.5s"),
+        Ok(Time::from_seconds_since_epoch(1.5))
+    );
+    assert_eq!(
+        Time::parse("-2m"),
+        Ok(Time::from_seconds_since_epoch(-120.0))
+    );
+    assert!(matches!(
+        Time::parse("yesterday"),
+        Err(ParseTimeError::BadDateTime(_))
+    ));
+}
+
+#[test]
+fn test_time_arithmetic() {
+    let time = Time::from_ns_since_epoch(i64::MAX - 10);
+    assert_eq!(time.checked_add(Duration::from_nanos(11)), None);
+    assert_eq!(
+        time.checked_add(Duration::from_nanos(10)),
+        Some(Time::from_ns_since_epoch(i64::MAX))
+    );
+    assert_eq!(time + Duration::MAX, Time::from_ns_since_epoch(i64::MAX));
+    assert_eq!(
+        Time::from_ns_since_epoch(0).checked_sub(Duration::MIN),
+        None
+    );
+
+    let second = Duration::from_secs(1.0);
+    assert_eq!(second * 3, Duration::from_millis(3_000));
+    assert_eq!(second / 4, Duration::from_millis(250));
+    assert_eq!(second.checked_mul(i64::MAX), None);
+    assert_eq!(second * i64::MAX, Duration::MAX);
+    assert_eq!(second.checked_div(0), None);
+    assert_eq!(Duration::MIN.checked_div(-1), None);
+    assert_eq!(Duration::MIN / -1, Duration::MAX);
+    assert_eq!(
+        Duration::MAX.checked_add(Duration::ZERO),
+        Some(Duration::MAX)
+    );
+    assert_eq!(Duration::MAX.saturating_add(second), Duration::MAX);
+    assert_eq!(Duration::MIN.saturating_sub(second), Duration::MIN);
+}
+
+#[test]
+fn test_format_time() {
+    let time = Time::from_ns_since_epoch(1_600_000_000_123_000_000);
+    assert_eq!(time.format(), "2020-09-13 12:26:40.123Z");
+
+    let options = TimeFormatOptions {
+        precision: TimePrecision::Seconds,
+        ..Default::default()
+    };
+    assert_eq!(time.format_with(&options), "2020-09-13 12:26:40Z");
+
+    let time = Time::from_ns_since_epoch(1_500_000_000);
+    assert_eq!(time.format(), "+1.500s");
+
+    let options = TimeFormatOptions {
+        absolute_threshold: Duration::ZERO,
+        ..Default::default()
+    };
+    assert_eq!(time.format_with(&options), "1970-01-01 00:00:01.500Z");
+
+    // Far from the epoch, in either direction:
+    let time = Time::from_ns_since_epoch(-631_152_000_500_000_000);
+    assert_eq!(time.format(), "1949-12-31 23:59:59.500Z");
+    assert_eq!(Time::parse(&time.format()), Ok(time));
+    let time = Time::from_ns_since_epoch(7_258_118_400_000_000_000);
+    assert_eq!(time.format(), "2200-01-01 00:00:00Z");
+    assert_eq!(Time::from_seconds_since_epoch(-1.5).format(), "-1.500s");
+}
+
+#[test]
+fn test_format_time_scale() {
+    let tai = TimeFormatOptions {
+        time_scale: TimeScale::Tai,
+        ..Default::default()
+    };
+    let tai_millis = |millis: i64| Time::from_ns_since_epoch(millis * 1_000_000).format_with(&tai);
+    // TAI - UTC went from 36 to 37 seconds at the start of 2017:
+    assert_eq!(tai_millis(1_483_228_835_000), "2016-12-31 23:59:59Z");
+    assert_eq!(tai_millis(1_483_228_836_500), "2016-12-31 23:59:60.500Z");
+    assert_eq!(tai_millis(1_483_228_837_000), "2017-01-01 00:00:00Z");
+
+    let gps = TimeFormatOptions {
+        time_scale: TimeScale::Gps,
+        absolute_threshold: Duration::ZERO,
+        ..Default::default()
+    };
+    assert_eq!(
+        Time::from_ns_since_epoch(0).format_with(&gps),
+        "1980-01-06 00:00:00Z"
+    );
+    // GPS time was 15 seconds ahead of UTC in 2011:
+    assert_eq!(
+        Time::from_ns_since_epoch(1_000_000_000_000_000_000).format_with(&gps),
+        "2011-09-14 01:46:25Z"
+    );
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    /// [`Duration::exact_format`] shows milliseconds, so those round-trip exactly.
+    #[test]
+    fn proptest_duration_format_parse(
+        millis in -i64::MAX / Duration::NANOS_PER_MILLI..=i64::MAX / Duration::NANOS_PER_MILLI
+    ) {
+        let duration = Duration::from_millis(millis);
+        proptest::prop_assert_eq!(Duration::parse(&duration.to_string()), Ok(duration));
+    }
+
+    /// Anything we parse can be formatted and parsed again.
+    #[test]
+    fn proptest_duration_parse_any(s in "\\PC*") {
+        if let Ok(duration) = Duration::parse(&s) {
+            let formatted = duration.to_string();
+            let reparsed = Duration::parse(&formatted);
+            proptest::prop_assert!(reparsed.is_ok(), "{s:?} -> {formatted:?} -> {reparsed:?}");
+
+            // Formatting truncates to milliseconds, after which it is exact:
+            let truncated = reparsed.unwrap();
+            proptest::prop_assert_eq!(Duration::parse(&truncated.to_string()), Ok(truncated));
+        }
+    }
+
+    /// Close to the epoch, times are shown relative to it, with millisecond precision.
+    #[test]
+    fn proptest_relative_time_format_parse(millis in -600_000_000_000_i64..600_000_000_000) {
+        let time = Time::from_ns_since_epoch(millis * Duration::NANOS_PER_MILLI);
+        proptest::prop_assert_eq!(Time::parse(&time.format()), Ok(time));
+    }
+
+    /// Dates round-trip down to the nanosecond.
+    ///
+    /// Limited to 1990-2010, since the date is left out for times today.
+    #[test]
+    fn proptest_absolute_time_format_parse(
+        nanos in 631_152_000_000_000_000_i64..1_262_304_000_000_000_000
+    ) {
+        let time = Time::from_ns_since_epoch(nanos);
+        let options = TimeFormatOptions {
+            precision: TimePrecision::Nanoseconds,
+            ..Default::default()
+        };
+        proptest::prop_assert_eq!(Time::parse(&time.format_with(&options)), Ok(time));
+    }
+
+    #[test]
+    fn proptest_time_parse_any(s in "\\PC*") {
+        let _ = Time::parse(&s);
+    }
+}
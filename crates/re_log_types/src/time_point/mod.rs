@@ -4,7 +4,10 @@ mod arrow;
 mod time_int;
 mod timeline;
 
-use crate::{time::Time, TimeRange};
+use crate::{
+    time::{Time, TimeFormatOptions},
+    TimeRange,
+};
 
 // Re-exports
 pub use time_int::TimeInt;
@@ -89,13 +92,18 @@ impl TimeType {
     }
 
     pub fn format(&self, time_int: TimeInt) -> String {
+        self.format_with(time_int, &TimeFormatOptions::default())
+    }
+
+    /// Like [`Self::format`], but with `options` for time-based timelines.
+    pub fn format_with(&self, time_int: TimeInt, options: &TimeFormatOptions) -> String {
         if time_int <= TimeInt::BEGINNING {
             "-∞".into()
         } else if time_int >= TimeInt::MAX {
             "+∞".into()
         } else {
             match self {
-                Self::Time => Time::from(time_int).format(),
+                Self::Time => Time::from(time_int).format_with(options),
                 Self::Sequence => format!("#{}", time_int.0),
             }
         }
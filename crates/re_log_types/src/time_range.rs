@@ -12,6 +12,12 @@ pub struct TimeRange {
 }
 
 impl TimeRange {
+    /// Contains all times.
+    pub const EVERYTHING: Self = Self {
+        min: TimeInt::MIN,
+        max: TimeInt::MAX,
+    };
+
     #[inline]
     pub fn new(min: TimeInt, max: TimeInt) -> Self {
         Self { min, max }
@@ -36,6 +42,37 @@ impl TimeRange {
     pub fn contains(&self, time: TimeInt) -> bool {
         self.min <= time && time <= self.max
     }
+
+    /// Is all of `other` within this range?
+    #[inline]
+    pub fn contains_range(&self, other: &Self) -> bool {
+        self.min <= other.min && other.max <= self.max
+    }
+
+    /// Do the two ranges have at least one time in common?
+    #[inline]
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.min <= other.max && other.min <= self.max
+    }
+
+    /// The times in both ranges, if any.
+    #[inline]
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        self.intersects(other)
+            .then(|| Self::new(self.min.max(other.min), self.max.min(other.max)))
+    }
+
+    /// The smallest range containing both ranges, including any gap between them.
+    #[inline]
+    pub fn union(&self, other: &Self) -> Self {
+        Self::new(self.min.min(other.min), self.max.max(other.max))
+    }
+
+    /// Grow the range to include `time`.
+    #[inline]
+    pub fn add(&mut self, time: TimeInt) {
+        *self = self.union(&Self::point(time));
+    }
 }
 
 impl From<TimeRange> for RangeInclusive<TimeInt> {
@@ -78,16 +115,38 @@ impl TimeRangeF {
         }
     }
 
-    // pub fn add(&mut self, value: TimeReal) {
-    //     self.min = self.min.min(value);
-    //     self.max = self.max.max(value);
-    // }
+    /// Grow the range to include `value`.
+    pub fn add(&mut self, value: TimeReal) {
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+    }
 
     /// Inclusive
     pub fn contains(&self, value: TimeReal) -> bool {
         self.min <= value && value <= self.max
     }
 
+    /// Do the two ranges overlap, or at least touch?
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.min <= other.max && other.min <= self.max
+    }
+
+    /// The overlap of the two ranges, if any.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        self.intersects(other)
+            .then(|| Self::new(self.min.max(other.min), self.max.min(other.max)))
+    }
+
+    /// The smallest range containing both ranges, including any gap between them.
+    pub fn union(&self, other: &Self) -> Self {
+        Self::new(self.min.min(other.min), self.max.max(other.max))
+    }
+
+    /// The smallest [`TimeRange`] that contains all of this range.
+    pub fn to_outer_time_range(&self) -> TimeRange {
+        TimeRange::new(self.min.floor(), self.max.ceil())
+    }
+
     /// Where in the range is this value? Returns 0-1 if within the range.
     ///
     /// Returns <0 if before and >1 if after.
@@ -132,3 +191,41 @@ impl From<TimeRange> for TimeRangeF {
         Self::new(range.min, range.max)
     }
 }
+
+#[test]
+fn test_time_range_ops() {
+    let range = |min: i64, max: i64| TimeRange::new(min.into(), max.into());
+
+    assert!(range(0, 10).contains_range(&range(2, 10)));
+    assert!(!range(0, 10).contains_range(&range(2, 11)));
+
+    assert_eq!(range(0, 10).intersection(&range(5, 20)), Some(range(5, 10)));
+    assert_eq!(
+        range(0, 10).intersection(&range(10, 20)),
+        Some(range(10, 10))
+    );
+    assert_eq!(range(0, 10).intersection(&range(11, 20)), None);
+    assert_eq!(range(0, 10).union(&range(20, 30)), range(0, 30));
+    assert_eq!(
+        TimeRange::EVERYTHING.intersection(&range(-5, 5)),
+        Some(range(-5, 5))
+    );
+
+    let mut grown = TimeRange::point(3_i64);
+    grown.add((-2_i64).into());
+    assert_eq!(grown, range(-2, 3));
+
+    let range_f = TimeRangeF::new(0.5_f64, 9.5_f64);
+    assert_eq!(range_f.to_outer_time_range(), range(0, 10));
+    assert_eq!(
+        range_f.intersection(&TimeRangeF::new(9.0_f64, 20.0_f64)),
+        Some(TimeRangeF::new(9.0_f64, 9.5_f64))
+    );
+    assert_eq!(
+        range_f.intersection(&TimeRangeF::new(10.0_f64, 20.0_f64)),
+        None
+    );
+    let range_f = TimeRangeF::new(0_i64, 8_i64);
+    assert_eq!(range_f.inverse_lerp(2_i64.into()), 0.25);
+    assert_eq!(range_f.lerp(0.25), 2_i64.into());
+}
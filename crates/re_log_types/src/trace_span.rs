@@ -0,0 +1,48 @@
+//! [`TraceSpansMsg`] is the [`crate::LogMsg`] sub-type for spans from a tracing system, e.g. OpenTelemetry.
+//!
+//! The viewer shows these as a flamegraph next to the logged data,
+//! so that latency spikes in the software can be correlated with what the sensors saw.
+
+use crate::{Duration, MsgId, Time};
+
+/// A batch of finished [`TraceSpan`]s.
+#[must_use]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct TraceSpansMsg {
+    /// A unique id per [`crate::LogMsg`].
+    pub msg_id: MsgId,
+
+    pub spans: Vec<TraceSpan>,
+}
+
+/// A timed operation, e.g. a function call or the handling of a request.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct TraceSpan {
+    /// Shared by all spans of the same trace.
+    pub trace_id: u128,
+
+    /// Unique within the trace.
+    pub span_id: u64,
+
+    /// The span this one is nested in, if any.
+    pub parent_span_id: Option<u64>,
+
+    /// What was done, e.g. `"detect_objects"`.
+    pub name: String,
+
+    /// What did it, e.g. `"perception"`.
+    pub service_name: String,
+
+    pub start: Time,
+
+    pub end: Time,
+}
+
+impl TraceSpan {
+    #[inline]
+    pub fn duration(&self) -> Duration {
+        self.end - self.start
+    }
+}
@@ -18,7 +18,7 @@ pub mod dataframe_util;
 pub use self::entity_view::{ComponentWithInstances, EntityView};
 pub use self::query::{get_component_with_instances, query_entity_with_primary};
 pub use self::range::range_entity_with_primary;
-pub use self::util::query_primary_with_history;
+pub use self::util::{query_primary_with_history, query_primary_with_history_and_time};
 
 // Used for doc-tests
 #[doc(hidden)]
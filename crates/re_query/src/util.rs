@@ -1,3 +1,4 @@
+use itertools::Itertools as _;
 use re_arrow_store::{DataStore, LatestAtQuery, RangeQuery, TimeInt, TimeRange, Timeline};
 use re_data_store::ExtraQueryHistory;
 use re_log_types::{msg_bundle::Component, ComponentName, EntityPath};
@@ -6,6 +7,9 @@ use crate::{query_entity_with_primary, range_entity_with_primary, EntityView};
 
 /// Either dispatch to `query_entity_with_primary` or `range_entity_with_primary`
 /// depending on whether `ExtraQueryHistory` is set.
+///
+/// With [`ExtraQueryHistory::decimation_nanos`] (or `decimation_sequences`) set,
+/// only the latest entity view of each such interval of the history is returned.
 pub fn query_primary_with_history<'a, Primary: Component + 'a, const N: usize>(
     store: &'a DataStore,
     timeline: &'a Timeline,
@@ -14,6 +18,23 @@ pub fn query_primary_with_history<'a, Primary: Component + 'a, const N: usize>(
     ent_path: &'a EntityPath,
     components: [ComponentName; N],
 ) -> crate::Result<impl Iterator<Item = EntityView<Primary>> + 'a> {
+    let entities = query_primary_with_history_and_time::<Primary, N>(
+        store, timeline, time, history, ent_path, components,
+    )?;
+    Ok(entities.map(|(_, entity)| entity))
+}
+
+/// Like [`query_primary_with_history`], but also returns when each [`EntityView`] was logged.
+///
+/// The time is `None` for timeless data, and when `ExtraQueryHistory` isn't set.
+pub fn query_primary_with_history_and_time<'a, Primary: Component + 'a, const N: usize>(
+    store: &'a DataStore,
+    timeline: &'a Timeline,
+    time: &'a TimeInt,
+    history: &ExtraQueryHistory,
+    ent_path: &'a EntityPath,
+    components: [ComponentName; N],
+) -> crate::Result<impl Iterator<Item = (Option<TimeInt>, EntityView<Primary>)> + 'a> {
     let visible_history = match timeline.typ() {
         re_log_types::TimeType::Time => history.nanos,
         re_log_types::TimeType::Sequence => history.sequences,
@@ -24,7 +45,7 @@ pub fn query_primary_with_history<'a, Primary: Component + 'a, const N: usize>(
         let latest =
             query_entity_with_primary::<Primary>(store, &latest_query, ent_path, &components)?;
 
-        Ok(itertools::Either::Left(std::iter::once(latest)))
+        Ok(itertools::Either::Left(std::iter::once((None, latest))))
     } else {
         let min_time = *time - TimeInt::from(visible_history);
         let range_query = RangeQuery::new(*timeline, TimeRange::new(min_time, *time));
@@ -32,6 +53,25 @@ pub fn query_primary_with_history<'a, Primary: Component + 'a, const N: usize>(
         let range =
             range_entity_with_primary::<Primary, N>(store, &range_query, ent_path, components);
 
-        Ok(itertools::Either::Right(range.map(|(_, entity)| entity)))
+        let decimation = match timeline.typ() {
+            re_log_types::TimeType::Time => history.decimation_nanos,
+            re_log_types::TimeType::Sequence => history.decimation_sequences,
+        };
+
+        // Keep the latest entity view of each interval, so that e.g. a trail still ends at
+        // the current position. Timeless data has no interval, and is always kept.
+        let interval = move |time: Option<TimeInt>| {
+            time.filter(|_| decimation > 1)
+                .map(|time| time.as_i64().div_euclid(decimation))
+        };
+        let range = range.coalesce(move |previous, current| {
+            if interval(previous.0).is_some() && interval(previous.0) == interval(current.0) {
+                Ok(current)
+            } else {
+                Err((previous, current))
+            }
+        });
+
+        Ok(itertools::Either::Right(range))
     }
 }
@@ -886,3 +886,51 @@ fn simple_splatted_range() {
         _ = results;
     }
 }
+
+#[test]
+fn decimated_history() {
+    let mut store = DataStore::new(InstanceKey::name(), Default::default());
+
+    let ent_path: EntityPath = "point".into();
+
+    for frame_nr in 0..10_i64 {
+        let points = vec![Point2D {
+            x: frame_nr as f32,
+            y: 0.0,
+        }];
+        let bundle = try_build_msg_bundle1(
+            MsgId::random(),
+            ent_path.clone(),
+            [build_frame_nr(frame_nr.into())],
+            &points,
+        )
+        .unwrap();
+        store.insert(&bundle).unwrap();
+    }
+
+    let (timeline, time) = build_frame_nr(9.into());
+    let times = |decimation_sequences| {
+        let history = re_data_store::ExtraQueryHistory {
+            sequences: 10,
+            decimation_sequences,
+            ..Default::default()
+        };
+        re_query::query_primary_with_history_and_time::<Point2D, 2>(
+            &store,
+            &timeline,
+            &time,
+            &history,
+            &ent_path,
+            [InstanceKey::name(), Point2D::name()],
+        )
+        .unwrap()
+        .map(|(time, _)| time.unwrap().as_i64())
+        .collect::<Vec<_>>()
+    };
+
+    assert_eq!(times(0), (0..10).collect::<Vec<_>>());
+
+    // The latest of each interval, so that a trail still ends at the current time.
+    assert_eq!(times(5), vec![4, 9]);
+    assert_eq!(times(4), vec![3, 7, 9]);
+}
@@ -0,0 +1,128 @@
+//! Benchmarks the cpu side of uploading point clouds to the gpu,
+//! i.e. filling a [`PointCloudBuilder`] and turning it into a [`PointCloudDrawData`].
+//!
+//! Requires a gpu adapter (any will do, including software rasterizers like lavapipe).
+//! If none is found, the benchmarks are skipped.
+
+use std::sync::Arc;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use re_renderer::{
+    config::{supported_backends, HardwareTier, RenderContextConfig},
+    renderer::PointCloudDrawData,
+    Color32, PointCloudBuilder, RenderContext, Size,
+};
+
+// ---
+
+#[cfg(not(debug_assertions))]
+const NUM_POINTS: usize = 1_000_000;
+
+// `cargo test` also runs the benchmark setup code, so make sure they run quickly:
+#[cfg(debug_assertions)]
+const NUM_POINTS: usize = 1_000;
+
+// --- Benchmarks ---
+
+fn point_cloud_upload(c: &mut Criterion) {
+    let Some(mut re_ctx) = create_headless_render_context() else {
+        eprintln!("No suitable gpu adapter found, skipping point cloud benchmarks");
+        return;
+    };
+
+    let positions = build_positions(NUM_POINTS);
+    let colors = build_colors(NUM_POINTS);
+
+    let mut group = c.benchmark_group("point_cloud");
+    group.throughput(criterion::Throughput::Elements(NUM_POINTS as _));
+
+    group.bench_function("build", |b| {
+        b.iter(|| {
+            let builder = build_point_cloud(&mut re_ctx, &positions, &colors);
+            re_ctx.before_submit();
+            re_ctx.begin_frame();
+            builder
+        });
+    });
+
+    group.bench_function("build_and_upload", |b| {
+        b.iter(|| {
+            let builder = build_point_cloud(&mut re_ctx, &positions, &colors);
+            let draw_data = builder.to_draw_data(&mut re_ctx).unwrap();
+            re_ctx.before_submit();
+            re_ctx.begin_frame();
+            draw_data
+        });
+    });
+}
+
+criterion_group!(benches, point_cloud_upload);
+criterion_main!(benches);
+
+// --- Helpers ---
+
+fn create_headless_render_context() -> Option<RenderContext> {
+    let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
+        backends: supported_backends(),
+        dx12_shader_compiler: wgpu::Dx12Compiler::Fxc,
+    });
+    let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+        power_preference: wgpu::PowerPreference::HighPerformance,
+        force_fallback_adapter: false,
+        compatible_surface: None,
+    }))?;
+
+    let hardware_tier = HardwareTier::Web;
+    hardware_tier
+        .check_downlevel_capabilities(&adapter.get_downlevel_capabilities())
+        .ok()?;
+    let (device, queue) = pollster::block_on(adapter.request_device(
+        &wgpu::DeviceDescriptor {
+            label: None,
+            features: wgpu::Features::empty(),
+            limits: wgpu::Limits::downlevel_webgl2_defaults().using_resolution(adapter.limits()),
+        },
+        None,
+    ))
+    .ok()?;
+
+    Some(RenderContext::new(
+        Arc::new(device),
+        Arc::new(queue),
+        RenderContextConfig {
+            output_format_color: wgpu::TextureFormat::Rgba8Unorm,
+            hardware_tier,
+        },
+    ))
+}
+
+fn build_positions(n: usize) -> Vec<glam::Vec3> {
+    assert!(n <= PointCloudDrawData::MAX_NUM_POINTS);
+    (0..n)
+        .map(|i| {
+            let t = i as f32 / n as f32;
+            glam::vec3(t.cos(), t.sin(), t) * 10.0
+        })
+        .collect()
+}
+
+fn build_colors(n: usize) -> Vec<Color32> {
+    (0..n)
+        .map(|i| Color32::from_rgb(i as u8, (i >> 8) as u8, (i >> 16) as u8))
+        .collect()
+}
+
+fn build_point_cloud(
+    re_ctx: &mut RenderContext,
+    positions: &[glam::Vec3],
+    colors: &[Color32],
+) -> PointCloudBuilder<()> {
+    let mut builder = PointCloudBuilder::new(re_ctx);
+    builder
+        .batch("bench points")
+        .add_points(positions.len(), positions.iter().copied())
+        .radii(std::iter::repeat(Size::new_scene(0.1)).take(positions.len()))
+        .colors(colors.iter().copied());
+    builder
+}
@@ -280,6 +280,7 @@ impl framework::Example for Render2D {
                             projection_from_view: Projection::Perspective {
                                 vertical_fov: 70.0 * std::f32::consts::TAU / 360.0,
                                 near_plane_distance: 0.01,
+                                far_plane_distance: f32::INFINITY,
                             },
                             pixels_from_point,
                             ..Default::default()
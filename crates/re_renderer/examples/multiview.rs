@@ -293,6 +293,7 @@ impl Example for Multiview {
             Projection::Perspective {
                 vertical_fov: 70.0 * TAU / 360.0,
                 near_plane_distance: 0.01,
+                far_plane_distance: f32::INFINITY,
             }
         } else {
             Projection::Orthographic {
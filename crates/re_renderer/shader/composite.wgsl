@@ -7,18 +7,63 @@ struct VertexOutput {
     @location(0) texcoord: Vec2,
 };
 
+// Keep in sync with mirror in compositor.rs
+struct UniformBuffer {
+    outline_color_layer_a: Vec4,
+    outline_color_layer_b: Vec4,
+    outline_radius_pixel: f32,
+};
+
 @group(1) @binding(0)
 var input_texture: texture_2d<f32>;
 
+@group(1) @binding(1)
+var outline_mask_layer_a: texture_2d<f32>;
+
+@group(1) @binding(2)
+var outline_mask_layer_b: texture_2d<f32>;
+
+@group(1) @binding(3)
+var<uniform> uniforms: UniformBuffer;
+
+// How much of an outline there is at the given position.
+//
+// Everything drawn into the mask is outlined on the outside only, leaving its inside untouched:
+// We look for coverage in a ring of samples around the position and take away the coverage at the position itself.
+fn outline_coverage(mask: texture_2d<f32>, texcoord: Vec2) -> f32 {
+    let coverage_here = textureSampleLevel(mask, trilinear_sampler, texcoord, 0.0).a;
+
+    let radius = uniforms.outline_radius_pixel / Vec2(textureDimensions(mask));
+    var coverage_around = 0.0;
+    for (var i = 0; i < 16; i += 1) {
+        let angle = f32(i) * (TAU / 16.0);
+        let direction = Vec2(cos(angle), sin(angle)) * radius;
+        coverage_around = max(coverage_around, textureSampleLevel(mask, trilinear_sampler, texcoord + direction, 0.0).a);
+        coverage_around = max(coverage_around, textureSampleLevel(mask, trilinear_sampler, texcoord + direction * 0.5, 0.0).a);
+    }
+
+    return coverage_around * (1.0 - coverage_here);
+}
+
 @fragment
 fn main(in: VertexOutput) -> @location(0) Vec4 {
-    // Note that we can't use a simple textureLoad using @builtin(position) here despite the lack of filtering.
+    // Note that we can't use a simple textureLoad using @builtin(position) here.
     // The issue is that positions provided by @builtin(position) are not dependent on the set viewport,
     // but are about the location of the texel in the target texture.
-    var input = textureSample(input_texture, nearest_sampler, in.texcoord).rgb;
+    // The input may have a different resolution than the viewport, so we filter.
+    // (if it doesn't, we sample at texel centers and the filtering has no effect)
+    var input = textureSample(input_texture, trilinear_sampler, in.texcoord).rgb;
     // TODO(andreas): Do something meaningful with values above 1
     input = clamp(input, ZERO, ONE);
 
+    if uniforms.outline_radius_pixel > 0.0 {
+        // Outline colors have premultiplied alpha.
+        let color_a = uniforms.outline_color_layer_a * outline_coverage(outline_mask_layer_a, in.texcoord);
+        input = input * (1.0 - color_a.a) + color_a.rgb;
+        let color_b = uniforms.outline_color_layer_b * outline_coverage(outline_mask_layer_b, in.texcoord);
+        input = input * (1.0 - color_b.a) + color_b.rgb;
+    }
+
     // Convert to srgb - this is necessary since the final eframe output does *not* have an srgb format.
     // Note that the input here is assumed to be linear - if the input texture was an srgb texture it would have been converted on load.
     return Vec4(srgb_from_linear(input), 1.0);
@@ -30,6 +30,21 @@ struct FrameUniformBuffer {
 
     /// Factor used to compute depth offsets, see `depth_offset.wgsl`.
     depth_offset_factor: f32,
+
+    /// Direction towards the directional light, in world space.
+    light_direction: Vec3,
+
+    /// Brightness of what the directional light doesn't reach.
+    ambient_light: f32,
+
+    /// Projects world positions into the shadow map.
+    shadow_map_from_world: Mat4,
+
+    /// 1 if the shadow map should be used, 0 otherwise.
+    shadows_enabled: u32,
+
+    /// 1 if points should be drawn as flat discs, 0 otherwise.
+    fast_point_splatting: u32,
 };
 @group(0) @binding(0)
 var<uniform> frame: FrameUniformBuffer;
@@ -39,3 +54,10 @@ var nearest_sampler: sampler;
 
 @group(0) @binding(2)
 var trilinear_sampler: sampler;
+
+/// Only valid if `frame.shadows_enabled` is set.
+@group(0) @binding(3)
+var shadow_map: texture_depth_2d;
+
+@group(0) @binding(4)
+var shadow_map_sampler: sampler_comparison;
@@ -18,6 +18,7 @@ struct VertexOut {
     @location(0) texcoord: Vec2,
     @location(1) normal_world_space: Vec3,
     @location(2) additive_tint_rgb: Vec3,
+    @location(3) position_world_space: Vec3,
 };
 
 @vertex
@@ -38,6 +39,7 @@ fn vs_main(in_vertex: VertexIn, in_instance: InstanceIn) -> VertexOut {
     out.texcoord = in_vertex.texcoord;
     out.normal_world_space = world_normal;
     out.additive_tint_rgb = linear_from_srgb(in_instance.additive_tint_srgb.rgb);
+    out.position_world_space = world_position;
 
     return out;
 }
@@ -47,12 +49,30 @@ fn fs_main(in: VertexOut) -> @location(0) Vec4 {
     let albedo = textureSample(albedo_texture, trilinear_sampler, in.texcoord).rgb
                  * material.albedo_factor.rgb + in.additive_tint_rgb;
 
-    // Hardcoded lambert lighting. TODO(andreas): Some microfacet model.
-    let light_dir = normalize(vec3(1.0, 2.0, 0.0)); // TODO(andreas): proper lighting
+    // Lambert lighting from a single directional light. TODO(andreas): Some microfacet model.
     let normal = normalize(in.normal_world_space);
-    let shading = clamp(dot(normal, light_dir), 0.0, 1.0) + 0.2;
+    let direct = clamp(dot(normal, frame.light_direction), 0.0, 1.0) * light_visibility(in.position_world_space);
+    let shading = direct + frame.ambient_light;
 
     let radiance = albedo * shading;
 
     return Vec4(radiance, 1.0);
 }
+
+/// How much of the directional light reaches the given position, from 0 (in shadow) to 1.
+fn light_visibility(position_world_space: Vec3) -> f32 {
+    if frame.shadows_enabled == 0u {
+        return 1.0;
+    }
+    let position_shadow_map = frame.shadow_map_from_world * Vec4(position_world_space, 1.0);
+    let ndc = position_shadow_map.xyz / position_shadow_map.w;
+    let uv = Vec2(ndc.x * 0.5 + 0.5, 0.5 - ndc.y * 0.5);
+    // Reverse-z: the position is lit if it is at least as close to the light as the closest caster.
+    // The small bias avoids surfaces shadowing themselves.
+    return textureSampleCompareLevel(shadow_map, shadow_map_sampler, uv, ndc.z + 0.002);
+}
+
+/// Used when drawing the shadow map, which only needs the depth.
+@fragment
+fn fs_shadow_caster() {
+}
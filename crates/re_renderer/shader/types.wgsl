@@ -17,6 +17,8 @@ const f32eps = 0.00000011920928955078125;
 const u32min = 0u;
 const u32max = 0xFFFFFFFFu;
 
+const TAU = 6.283185307179586;
+
 const X = Vec3(1.0, 0.0, 0.0);
 const Y = Vec3(0.0, 1.0, 0.0);
 const Z = Vec3(0.0, 0.0, 1.0);
@@ -7,8 +7,9 @@ use crate::{
     allocator::CpuWriteGpuReadBelt,
     config::RenderContextConfig,
     global_bindings::GlobalBindings,
-    renderer::Renderer,
+    renderer::{MeshRenderer, Renderer},
     resource_managers::{MeshManager, TextureManager2D},
+    view_builder::ViewBuilder,
     wgpu_resources::WgpuResourcePools,
     FileResolver, FileServer, FileSystem, RecommendedFileResolver,
 };
@@ -47,6 +48,9 @@ pub struct SharedRendererData {
     /// Global bindings, always bound to 0 bind group slot zero.
     /// [`Renderer`] are not allowed to use bind group 0 themselves!
     pub(crate) global_bindings: GlobalBindings,
+
+    /// Samples per pixel of the main targets, see [`RenderContext::set_msaa_sample_count`].
+    pub(crate) msaa_sample_count: u32,
 }
 
 /// Struct owning *all* [`Renderer`].
@@ -152,6 +156,7 @@ impl RenderContext {
         let shared_renderer_data = SharedRendererData {
             config,
             global_bindings,
+            msaa_sample_count: ViewBuilder::MAIN_TARGET_DEFAULT_SAMPLE_COUNT,
         };
 
         let mut resolver = crate::new_recommended_file_resolver();
@@ -198,6 +203,31 @@ impl RenderContext {
         }
     }
 
+    /// Number of samples per pixel used for anti-aliasing the main targets, 1 to disable MSAA.
+    ///
+    /// Only 1 and 4 are supported everywhere.
+    /// Changing this recreates all renderers, so do it before setting up any views of a frame,
+    /// and not every frame.
+    pub fn set_msaa_sample_count(&mut self, msaa_sample_count: u32) {
+        if self.shared_renderer_data.msaa_sample_count == msaa_sample_count {
+            return;
+        }
+        re_log::debug!("Switching to {msaa_sample_count}x MSAA");
+
+        self.shared_renderer_data.msaa_sample_count = msaa_sample_count;
+        // Renderers are created lazily, with pipelines for the current sample count.
+        self.renderers = Renderers {
+            renderers: TypeMap::new(),
+        };
+        // …except for this one, which the mesh manager relies on.
+        self.renderers.get_or_create::<_, MeshRenderer>(
+            &self.shared_renderer_data,
+            &mut self.gpu_resources,
+            &self.device,
+            &mut self.resolver,
+        );
+    }
+
     fn poll_device(&mut self) {
         crate::profile_function!();
 
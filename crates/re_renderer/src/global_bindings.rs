@@ -2,7 +2,7 @@ use crate::{
     wgpu_buffer_types,
     wgpu_resources::{
         BindGroupDesc, BindGroupEntry, BindGroupLayoutDesc, GpuBindGroup, GpuBindGroupLayoutHandle,
-        GpuBuffer, GpuSamplerHandle, SamplerDesc, WgpuResourcePools,
+        GpuBuffer, GpuSamplerHandle, GpuTexture, SamplerDesc, WgpuResourcePools,
     },
 };
 
@@ -48,12 +48,30 @@ pub(crate) struct FrameUniformBuffer {
     /// Factor used to compute depth offsets, see `depth_offset.wgsl`.
     pub depth_offset_factor: f32,
     pub _padding: glam::Vec3,
+
+    /// Direction towards the directional light, in world space.
+    pub light_direction: glam::Vec3,
+
+    /// Brightness of what the directional light doesn't reach.
+    pub ambient_light: f32,
+
+    /// Projects world positions into the shadow map.
+    pub shadow_map_from_world: wgpu_buffer_types::Mat4,
+
+    /// 1 if the shadow map should be used, 0 otherwise.
+    pub shadows_enabled: u32,
+
+    /// 1 if points should be drawn as flat discs, 0 otherwise.
+    pub fast_point_splatting: u32,
+
+    pub _padding_2: [u32; 2],
 }
 
 pub(crate) struct GlobalBindings {
     pub(crate) layout: GpuBindGroupLayoutHandle,
     nearest_neighbor_sampler: GpuSamplerHandle,
     trilinear_sampler: GpuSamplerHandle,
+    shadow_map_sampler: GpuSamplerHandle,
 }
 
 impl GlobalBindings {
@@ -94,6 +112,24 @@ impl GlobalBindings {
                             ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
                             count: None,
                         },
+                        // Shadow map of the directional light.
+                        wgpu::BindGroupLayoutEntry {
+                            binding: 3,
+                            visibility: wgpu::ShaderStages::FRAGMENT,
+                            ty: wgpu::BindingType::Texture {
+                                sample_type: wgpu::TextureSampleType::Depth,
+                                view_dimension: wgpu::TextureViewDimension::D2,
+                                multisampled: false,
+                            },
+                            count: None,
+                        },
+                        // Comparison sampler for the shadow map.
+                        wgpu::BindGroupLayoutEntry {
+                            binding: 4,
+                            visibility: wgpu::ShaderStages::FRAGMENT,
+                            ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Comparison),
+                            count: None,
+                        },
                     ],
                 },
             ),
@@ -120,15 +156,30 @@ impl GlobalBindings {
                     ..Default::default()
                 },
             ),
+            shadow_map_sampler: pools.samplers.get_or_create(
+                device,
+                &SamplerDesc {
+                    label: "shadow map".into(),
+                    mag_filter: wgpu::FilterMode::Linear,
+                    min_filter: wgpu::FilterMode::Linear,
+                    // Shadow maps use reverse z like the main target, so closer to the light is greater.
+                    compare: Some(wgpu::CompareFunction::GreaterEqual),
+                    ..Default::default()
+                },
+            ),
         }
     }
 
     /// Creates a bind group that follows the global bind group layout.
+    ///
+    /// `shadow_map` needs to be a depth texture, it is only sampled if
+    /// [`FrameUniformBuffer::shadows_enabled`] is set.
     pub fn create_bind_group(
         &self,
         pools: &mut WgpuResourcePools,
         device: &wgpu::Device,
         frame_uniform_buffer: &GpuBuffer,
+        shadow_map: &GpuTexture,
     ) -> GpuBindGroup {
         pools.bind_groups.alloc(
             device,
@@ -143,6 +194,8 @@ impl GlobalBindings {
                     },
                     BindGroupEntry::Sampler(self.nearest_neighbor_sampler),
                     BindGroupEntry::Sampler(self.trilinear_sampler),
+                    BindGroupEntry::DefaultTextureView(shadow_map.handle),
+                    BindGroupEntry::Sampler(self.shadow_map_sampler),
                 ],
                 layout: self.layout,
             },
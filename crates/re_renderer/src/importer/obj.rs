@@ -29,22 +29,31 @@ pub fn load_obj_from_buffer(
     .context("failed loading obj")?;
 
     // TODO(andreas) Merge all obj meshes into a single re_renderer mesh with multiple materials.
-    Ok(models
+    models
         .into_iter()
-        .map(|model| {
+        .map(|model| -> anyhow::Result<MeshInstance> {
             let mesh = model.mesh;
-            let vertex_positions = mesh
+            let vertex_positions: Vec<glam::Vec3> = mesh
                 .positions
                 .chunks_exact(3)
                 .map(|p| glam::vec3(p[0], p[1], p[2]))
                 .collect();
-            let vertex_data = mesh
-                .normals
-                .chunks_exact(3)
-                .zip(mesh.texcoords.chunks(2))
-                .map(|(n, t)| MeshVertexData {
-                    normal: glam::vec3(n[0], n[1], n[2]),
-                    texcoord: glam::vec2(t[0], t[1]),
+
+            // Normals and texture coordinates are optional in .obj files.
+            let normal = |i: usize| {
+                mesh.normals
+                    .get(i * 3..i * 3 + 3)
+                    .map_or(glam::Vec3::ZERO, |n| glam::vec3(n[0], n[1], n[2]))
+            };
+            let texcoord = |i: usize| {
+                mesh.texcoords
+                    .get(i * 2..i * 2 + 2)
+                    .map_or(glam::Vec2::ZERO, |t| glam::vec2(t[0], t[1]))
+            };
+            let vertex_data = (0..vertex_positions.len())
+                .map(|i| MeshVertexData {
+                    normal: normal(i),
+                    texcoord: texcoord(i),
                 })
                 .collect();
 
@@ -65,21 +74,18 @@ pub fn load_obj_from_buffer(
                     albedo_multiplier: crate::Rgba::WHITE,
                 }],
             };
-            let gpu_mesh = ctx
-                .mesh_manager
-                .create(
-                    &mut ctx.gpu_resources,
-                    &ctx.texture_manager_2d,
-                    &mesh,
-                    lifetime,
-                )
-                .unwrap(); // TODO(andreas): Handle error
-            MeshInstance {
+            let gpu_mesh = ctx.mesh_manager.create(
+                &mut ctx.gpu_resources,
+                &ctx.texture_manager_2d,
+                &mesh,
+                lifetime,
+            )?;
+            Ok(MeshInstance {
                 gpu_mesh,
                 mesh: Some(Arc::new(mesh)),
                 world_from_mesh: glam::Affine3A::IDENTITY,
                 additive_tint: Color32::TRANSPARENT,
-            }
+            })
         })
-        .collect())
+        .collect()
 }
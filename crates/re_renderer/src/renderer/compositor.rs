@@ -1,16 +1,34 @@
 use crate::{
-    context::SharedRendererData,
+    context::{uniform_buffer_allocation_size, SharedRendererData},
     include_file,
+    view_builder::OutlineConfig,
     wgpu_resources::{
-        BindGroupDesc, BindGroupEntry, BindGroupLayoutDesc, GpuBindGroup, GpuBindGroupLayoutHandle,
-        GpuRenderPipelineHandle, GpuTexture, PipelineLayoutDesc, RenderPipelineDesc,
-        ShaderModuleDesc, WgpuResourcePools,
+        BindGroupDesc, BindGroupEntry, BindGroupLayoutDesc, BufferDesc, GpuBindGroup,
+        GpuBindGroupLayoutHandle, GpuRenderPipelineHandle, GpuTexture, PipelineLayoutDesc,
+        RenderPipelineDesc, ShaderModuleDesc, WgpuResourcePools,
     },
 };
 
 use super::{DrawData, FileResolver, FileSystem, RenderContext, Renderer};
 
 use smallvec::smallvec;
+use std::num::NonZeroU64;
+
+mod gpu_data {
+    use crate::wgpu_buffer_types;
+
+    // Keep in sync with mirror in composite.wgsl
+    #[repr(C)]
+    #[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+    pub struct UniformBuffer {
+        pub outline_color_layer_a: wgpu_buffer_types::Vec4,
+        pub outline_color_layer_b: wgpu_buffer_types::Vec4,
+
+        /// In pixels of the input textures, 0 disables outlines.
+        pub outline_radius_pixel: f32,
+        pub _padding: glam::Vec3,
+    }
+}
 
 pub struct Compositor {
     render_pipeline: GpuRenderPipelineHandle,
@@ -19,7 +37,7 @@ pub struct Compositor {
 
 #[derive(Clone)]
 pub struct CompositorDrawData {
-    /// [`GpuBindGroup`] pointing at the current image source and
+    /// [`GpuBindGroup`] pointing at the current image source, the outline masks and
     /// a uniform buffer for describing a tonemapper/compositor configuration.
     bind_group: GpuBindGroup,
 }
@@ -29,7 +47,16 @@ impl DrawData for CompositorDrawData {
 }
 
 impl CompositorDrawData {
-    pub fn new(ctx: &mut RenderContext, target: &GpuTexture) -> Self {
+    /// Composites `target`, outlining what was drawn into the `outline_masks` (at the same resolution)
+    /// as configured by `outline_config`.
+    ///
+    /// `outline_config` is expected to have its radius in pixels of `target`.
+    pub fn new(
+        ctx: &mut RenderContext,
+        target: &GpuTexture,
+        outline_masks: [&GpuTexture; 2],
+        outline_config: Option<&OutlineConfig>,
+    ) -> Self {
         let pools = &mut ctx.gpu_resources;
         let compositor = ctx.renderers.get_or_create::<_, Compositor>(
             &ctx.shared_renderer_data,
@@ -37,12 +64,50 @@ impl CompositorDrawData {
             &ctx.device,
             &mut ctx.resolver,
         );
+
+        let uniform_buffer = pools.buffers.alloc(
+            &ctx.device,
+            &BufferDesc {
+                label: "compositor uniform buffer".into(),
+                size: uniform_buffer_allocation_size::<gpu_data::UniformBuffer>(&ctx.device),
+                usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            },
+        );
+        let uniform = match outline_config {
+            Some(outline_config) => gpu_data::UniformBuffer {
+                outline_color_layer_a: outline_config.color_layer_a.into(),
+                outline_color_layer_b: outline_config.color_layer_b.into(),
+                outline_radius_pixel: outline_config.outline_radius_pixel,
+                _padding: glam::Vec3::ZERO,
+            },
+            None => gpu_data::UniformBuffer {
+                outline_color_layer_a: crate::Rgba::TRANSPARENT.into(),
+                outline_color_layer_b: crate::Rgba::TRANSPARENT.into(),
+                outline_radius_pixel: 0.0,
+                _padding: glam::Vec3::ZERO,
+            },
+        };
+        ctx.queue
+            .write_buffer(&uniform_buffer, 0, bytemuck::bytes_of(&uniform));
+
         CompositorDrawData {
             bind_group: pools.bind_groups.alloc(
                 &ctx.device,
                 &BindGroupDesc {
                     label: "compositor".into(),
-                    entries: smallvec![BindGroupEntry::DefaultTextureView(target.handle)],
+                    entries: smallvec![
+                        BindGroupEntry::DefaultTextureView(target.handle),
+                        BindGroupEntry::DefaultTextureView(outline_masks[0].handle),
+                        BindGroupEntry::DefaultTextureView(outline_masks[1].handle),
+                        BindGroupEntry::Buffer {
+                            handle: uniform_buffer.handle,
+                            offset: 0,
+                            size: NonZeroU64::new(
+                                std::mem::size_of::<gpu_data::UniformBuffer>() as _
+                            ),
+                        },
+                    ],
                     layout: compositor.bind_group_layout,
                 },
                 &pools.bind_group_layouts,
@@ -67,16 +132,50 @@ impl Renderer for Compositor {
             device,
             &BindGroupLayoutDesc {
                 label: "compositor".into(),
-                entries: vec![wgpu::BindGroupLayoutEntry {
-                    binding: 0,
-                    visibility: wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Texture {
-                        sample_type: wgpu::TextureSampleType::Float { filterable: false },
-                        view_dimension: wgpu::TextureViewDimension::D2,
-                        multisampled: false,
+                entries: vec![
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 2,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 3,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: NonZeroU64::new(std::mem::size_of::<
+                                gpu_data::UniformBuffer,
+                            >() as _),
+                        },
+                        count: None,
                     },
-                    count: None,
-                }],
+                ],
             },
         );
 
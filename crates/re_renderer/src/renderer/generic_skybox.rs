@@ -94,7 +94,7 @@ impl Renderer for GenericSkybox {
                     stencil: Default::default(),
                     bias: Default::default(),
                 }),
-                multisample: ViewBuilder::MAIN_TARGET_DEFAULT_MSAA_STATE,
+                multisample: ViewBuilder::main_target_default_msaa_state(shared_data),
             },
             &pools.pipeline_layouts,
             &pools.shader_modules,
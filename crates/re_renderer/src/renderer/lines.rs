@@ -675,8 +675,9 @@ impl Renderer for LineRenderer {
                 depth_stencil: ViewBuilder::MAIN_TARGET_DEFAULT_DEPTH_STATE,
                 multisample: wgpu::MultisampleState {
                     // We discard pixels to do the round cutout, therefore we need to calculate our own sampling mask.
-                    alpha_to_coverage_enabled: true,
-                    ..ViewBuilder::MAIN_TARGET_DEFAULT_MSAA_STATE
+                    // (only possible with more than one sample per pixel)
+                    alpha_to_coverage_enabled: shared_data.msaa_sample_count > 1,
+                    ..ViewBuilder::main_target_default_msaa_state(shared_data)
                 },
             },
             &pools.pipeline_layouts,
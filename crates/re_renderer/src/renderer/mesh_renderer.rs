@@ -223,6 +223,7 @@ impl MeshDrawData {
 
 pub struct MeshRenderer {
     render_pipeline: GpuRenderPipelineHandle,
+    shadow_caster_pipeline: GpuRenderPipelineHandle,
     pub bind_group_layout: GpuBindGroupLayoutHandle,
 }
 
@@ -285,29 +286,43 @@ impl Renderer for MeshRenderer {
             },
         );
 
+        let render_pipeline_desc = RenderPipelineDesc {
+            label: "mesh renderer".into(),
+            pipeline_layout,
+            vertex_entrypoint: "vs_main".into(),
+            vertex_handle: shader_module,
+            fragment_entrypoint: "fs_main".into(),
+            fragment_handle: shader_module,
+
+            // Put instance vertex buffer on slot 0 since it doesn't change for several draws.
+            vertex_buffers: std::iter::once(gpu_data::InstanceData::vertex_buffer_layout())
+                .chain(mesh_vertices::vertex_buffer_layouts())
+                .collect(),
+
+            render_targets: smallvec![Some(ViewBuilder::MAIN_TARGET_COLOR_FORMAT.into())],
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                cull_mode: None, //Some(wgpu::Face::Back), // TODO(andreas): Need to specify from outside if mesh is CW or CCW?
+                ..Default::default()
+            },
+            depth_stencil: ViewBuilder::MAIN_TARGET_DEFAULT_DEPTH_STATE,
+            multisample: ViewBuilder::main_target_default_msaa_state(shared_data),
+        };
         let render_pipeline = pools.render_pipelines.get_or_create(
+            device,
+            &render_pipeline_desc,
+            &pools.pipeline_layouts,
+            &pools.shader_modules,
+        );
+        let shadow_caster_pipeline = pools.render_pipelines.get_or_create(
             device,
             &RenderPipelineDesc {
-                label: "mesh renderer".into(),
-                pipeline_layout,
-                vertex_entrypoint: "vs_main".into(),
-                vertex_handle: shader_module,
-                fragment_entrypoint: "fs_main".into(),
-                fragment_handle: shader_module,
-
-                // Put instance vertex buffer on slot 0 since it doesn't change for several draws.
-                vertex_buffers: std::iter::once(gpu_data::InstanceData::vertex_buffer_layout())
-                    .chain(mesh_vertices::vertex_buffer_layouts())
-                    .collect(),
-
-                render_targets: smallvec![Some(ViewBuilder::MAIN_TARGET_COLOR_FORMAT.into())],
-                primitive: wgpu::PrimitiveState {
-                    topology: wgpu::PrimitiveTopology::TriangleList,
-                    cull_mode: None, //Some(wgpu::Face::Back), // TODO(andreas): Need to specify from outside if mesh is CW or CCW?
-                    ..Default::default()
-                },
-                depth_stencil: ViewBuilder::MAIN_TARGET_DEFAULT_DEPTH_STATE,
-                multisample: ViewBuilder::MAIN_TARGET_DEFAULT_MSAA_STATE,
+                label: "mesh renderer shadow casters".into(),
+                fragment_entrypoint: "fs_shadow_caster".into(),
+                render_targets: smallvec![],
+                depth_stencil: ViewBuilder::SHADOW_MAP_DEPTH_STATE,
+                multisample: wgpu::MultisampleState::default(),
+                ..render_pipeline_desc
             },
             &pools.pipeline_layouts,
             &pools.shader_modules,
@@ -315,6 +330,7 @@ impl Renderer for MeshRenderer {
 
         MeshRenderer {
             render_pipeline,
+            shadow_caster_pipeline,
             bind_group_layout,
         }
     }
@@ -326,47 +342,65 @@ impl Renderer for MeshRenderer {
         draw_data: &'a Self::RendererDrawData,
     ) -> anyhow::Result<()> {
         crate::profile_function!();
+        draw_batches(pools, pass, draw_data, self.render_pipeline)
+    }
 
-        let Some(instance_buffer) = &draw_data.instance_buffer else {
-            return Ok(()); // Instance buffer was empty.
-        };
+    fn draw_shadow_casters<'a>(
+        &self,
+        pools: &'a WgpuResourcePools,
+        pass: &mut wgpu::RenderPass<'a>,
+        draw_data: &'a Self::RendererDrawData,
+    ) -> anyhow::Result<()> {
+        crate::profile_function!();
+        draw_batches(pools, pass, draw_data, self.shadow_caster_pipeline)
+    }
+}
 
-        let pipeline = pools.render_pipelines.get_resource(self.render_pipeline)?;
-        pass.set_pipeline(pipeline);
+fn draw_batches<'a>(
+    pools: &'a WgpuResourcePools,
+    pass: &mut wgpu::RenderPass<'a>,
+    draw_data: &'a MeshDrawData,
+    render_pipeline: GpuRenderPipelineHandle,
+) -> anyhow::Result<()> {
+    let Some(instance_buffer) = &draw_data.instance_buffer else {
+        return Ok(()); // Instance buffer was empty.
+    };
 
-        pass.set_vertex_buffer(0, instance_buffer.slice(..));
-        let mut instance_start_index = 0;
+    let pipeline = pools.render_pipelines.get_resource(render_pipeline)?;
+    pass.set_pipeline(pipeline);
 
-        for mesh_batch in &draw_data.batches {
-            let vertex_buffer_combined = &mesh_batch.mesh.vertex_buffer_combined;
-            let index_buffer = &mesh_batch.mesh.index_buffer;
+    pass.set_vertex_buffer(0, instance_buffer.slice(..));
+    let mut instance_start_index = 0;
 
-            pass.set_vertex_buffer(
-                1,
-                vertex_buffer_combined.slice(mesh_batch.mesh.vertex_buffer_positions_range.clone()),
-            );
-            pass.set_vertex_buffer(
-                2,
-                vertex_buffer_combined.slice(mesh_batch.mesh.vertex_buffer_data_range.clone()),
-            );
-            pass.set_index_buffer(
-                index_buffer.slice(mesh_batch.mesh.index_buffer_range.clone()),
-                wgpu::IndexFormat::Uint32,
-            );
+    for mesh_batch in &draw_data.batches {
+        let vertex_buffer_combined = &mesh_batch.mesh.vertex_buffer_combined;
+        let index_buffer = &mesh_batch.mesh.index_buffer;
 
-            let instance_range = instance_start_index..(instance_start_index + mesh_batch.count);
+        pass.set_vertex_buffer(
+            1,
+            vertex_buffer_combined.slice(mesh_batch.mesh.vertex_buffer_positions_range.clone()),
+        );
+        pass.set_vertex_buffer(
+            2,
+            vertex_buffer_combined.slice(mesh_batch.mesh.vertex_buffer_data_range.clone()),
+        );
+        pass.set_index_buffer(
+            index_buffer.slice(mesh_batch.mesh.index_buffer_range.clone()),
+            wgpu::IndexFormat::Uint32,
+        );
 
-            for material in &mesh_batch.mesh.materials {
-                debug_assert!(mesh_batch.count > 0);
+        let instance_range = instance_start_index..(instance_start_index + mesh_batch.count);
 
-                pass.set_bind_group(1, &material.bind_group, &[]);
+        for material in &mesh_batch.mesh.materials {
+            debug_assert!(mesh_batch.count > 0);
 
-                pass.draw_indexed(material.index_range.clone(), 0, instance_range.clone());
-            }
+            pass.set_bind_group(1, &material.bind_group, &[]);
 
-            instance_start_index = instance_range.end;
+            pass.draw_indexed(material.index_range.clone(), 0, instance_range.clone());
         }
 
-        Ok(())
+        instance_start_index = instance_range.end;
     }
+
+    Ok(())
 }
@@ -69,6 +69,19 @@ pub trait Renderer {
         draw_data: &'a Self::RendererDrawData,
     ) -> anyhow::Result<()>;
 
+    /// Draws the depth of everything that casts shadows, as seen from the directional light.
+    ///
+    /// Called in a depth-only pass before the main pass, if the view has shadows.
+    /// Most renderers don't cast shadows.
+    fn draw_shadow_casters<'a>(
+        &self,
+        _pools: &'a WgpuResourcePools,
+        _pass: &mut wgpu::RenderPass<'a>,
+        _draw_data: &'a Self::RendererDrawData,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
     /// Relative location in the rendering process when this renderer should be executed.
     /// TODO(andreas): We might want to take [`DrawData`] into account for this.
     ///                But this touches on the [`Renderer::draw`] method might be split in the future, which haven't designed yet.
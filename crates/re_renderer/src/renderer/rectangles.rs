@@ -48,7 +48,7 @@ mod gpu_data {
 }
 
 /// Texture filter setting for magnification (a texel covers several pixels).
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub enum TextureFilterMag {
     Linear,
     Nearest,
@@ -56,13 +56,14 @@ pub enum TextureFilterMag {
 }
 
 /// Texture filter setting for minification (several texels fall to one pixel).
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub enum TextureFilterMin {
     Linear,
     Nearest,
     // TODO(andreas): Offer mipmapping here?
 }
 
+#[derive(Clone)]
 pub struct TexturedRect {
     /// Top left corner position in world space.
     pub top_left_corner_position: glam::Vec3,
@@ -330,7 +331,7 @@ impl Renderer for RectangleRenderer {
                 },
                 // We're rendering with transparency, so disable depth write.
                 depth_stencil: ViewBuilder::MAIN_TARGET_DEFAULT_DEPTH_STATE,
-                multisample: ViewBuilder::MAIN_TARGET_DEFAULT_MSAA_STATE,
+                multisample: ViewBuilder::main_target_default_msaa_state(shared_data),
             },
             &pools.pipeline_layouts,
             &pools.shader_modules,
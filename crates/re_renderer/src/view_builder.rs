@@ -3,13 +3,13 @@ use parking_lot::RwLock;
 use std::sync::Arc;
 
 use crate::{
-    context::RenderContext,
+    context::{RenderContext, SharedRendererData},
     global_bindings::FrameUniformBuffer,
     renderer::{
         compositor::{Compositor, CompositorDrawData},
         DrawData, Renderer,
     },
-    wgpu_resources::{BufferDesc, GpuBindGroup, GpuTexture, TextureDesc},
+    wgpu_resources::{BufferDesc, GpuBindGroup, GpuBuffer, GpuTexture, TextureDesc},
     DebugLabel, Rgba, Size,
 };
 
@@ -23,6 +23,7 @@ type DrawFn = dyn for<'a, 'b> Fn(
 
 struct QueuedDraw {
     draw_func: Box<DrawFn>,
+    shadow_caster_draw_func: Box<DrawFn>,
     draw_data: Box<dyn std::any::Any + std::marker::Send + std::marker::Sync>,
     sorting_index: u32,
 }
@@ -34,6 +35,9 @@ pub struct ViewBuilder {
     /// Result of [`ViewBuilder::setup_view`] - needs to be `Option` sine some of the fields don't have a default.
     setup: Option<ViewTargetSetup>,
     queued_draws: Vec<QueuedDraw>, // &mut wgpu::RenderPass
+
+    /// Drawn into the outline masks, see [`ViewBuilder::queue_outline_draw`].
+    queued_outline_draws: [Vec<QueuedDraw>; 2],
 }
 
 struct ViewTargetSetup {
@@ -42,13 +46,35 @@ struct ViewTargetSetup {
     compositor_draw_data: CompositorDrawData,
 
     bind_group_0: GpuBindGroup,
-    main_target_msaa: GpuTexture,
+    /// `None` if MSAA is disabled, in which case we draw directly into [`Self::main_target_resolved`].
+    main_target_msaa: Option<GpuTexture>,
     main_target_resolved: GpuTexture,
     depth_buffer: GpuTexture,
 
+    /// Set if the view has shadows.
+    shadow_pass: Option<ShadowPass>,
+
+    /// Set if the view has outlines, see [`TargetConfiguration::outline_config`].
+    outline_masks: Option<[OutlineMask; 2]>,
+
+    /// The size on screen, which may differ from the size of the targets.
     resolution_in_pixel: [u32; 2],
 }
 
+struct ShadowPass {
+    shadow_map: GpuTexture,
+
+    /// Like [`ViewTargetSetup::bind_group_0`], but as seen from the light.
+    bind_group_0: GpuBindGroup,
+}
+
+/// Target for one [`OutlineLayer`], drawn with the same format, sample count and depth buffer as the main target.
+struct OutlineMask {
+    /// `None` if MSAA is disabled, in which case we draw directly into [`Self::resolved`].
+    msaa: Option<GpuTexture>,
+    resolved: GpuTexture,
+}
+
 /// [`ViewBuilder`] that can be shared between threads.
 ///
 /// Innermost field is an Option, so it can be consumed for `composite`.
@@ -90,6 +116,9 @@ pub enum Projection {
 
         /// Distance of the near plane.
         near_plane_distance: f32,
+
+        /// Distance of the far plane, [`f32::INFINITY`] for no far plane.
+        far_plane_distance: f32,
     },
 
     /// Orthographic projection with the camera position at the near plane's center,
@@ -129,6 +158,53 @@ impl Default for AutoSizeConfig {
     }
 }
 
+/// A directional light, e.g. the sun, plus an ambient term for what it doesn't reach.
+#[derive(Debug, Clone)]
+pub struct Lighting {
+    /// Direction towards the light, in world space.
+    pub light_direction: glam::Vec3,
+
+    /// Brightness of what the directional light doesn't reach, where the light itself is 1.
+    pub ambient_light: f32,
+
+    /// If set, meshes in these bounds cast shadows (onto meshes).
+    ///
+    /// Costs an extra pass over all meshes.
+    pub shadow_bounds: Option<macaw::BoundingBox>,
+}
+
+impl Default for Lighting {
+    fn default() -> Self {
+        Self {
+            light_direction: glam::vec3(1.0, 2.0, 0.0).normalize(),
+            ambient_light: 0.2,
+            shadow_bounds: None,
+        }
+    }
+}
+
+/// Outlines drawn around everything queued with [`ViewBuilder::queue_outline_draw`].
+#[derive(Clone, Debug)]
+pub struct OutlineConfig {
+    /// How far the outline reaches out from the outlined objects, in screen pixels.
+    pub outline_radius_pixel: f32,
+
+    /// Color of the outlines of [`OutlineLayer::A`]. Premultiplied alpha.
+    pub color_layer_a: Rgba,
+
+    /// Color of the outlines of [`OutlineLayer::B`], drawn on top of [`OutlineLayer::A`]. Premultiplied alpha.
+    pub color_layer_b: Rgba,
+}
+
+/// Each layer has its own outline color, see [`OutlineConfig`].
+///
+/// Objects in the same layer share an outline where they overlap.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutlineLayer {
+    A,
+    B,
+}
+
 /// Basic configuration for a target view.
 #[derive(Debug, Clone)]
 pub struct TargetConfiguration {
@@ -143,6 +219,23 @@ pub struct TargetConfiguration {
 
     /// How [`Size::AUTO`] is interpreted.
     pub auto_size_config: AutoSizeConfig,
+
+    pub lighting: Lighting,
+
+    /// Render at this multiple of [`Self::resolution_in_pixel`], and scale to it when compositing.
+    ///
+    /// Below 1 trades sharpness for speed, above 1 is supersampling.
+    pub resolution_scale: f32,
+
+    /// Draw points as flat discs with hard edges, instead of shaded spheres with smooth edges.
+    ///
+    /// Faster for very large point clouds.
+    pub fast_point_splatting: bool,
+
+    /// If set, draw outlines around everything queued with [`ViewBuilder::queue_outline_draw`].
+    ///
+    /// Outlines are drawn on top of everything else, so they stay visible when the outlined objects are occluded.
+    pub outline_config: Option<OutlineConfig>,
 }
 
 impl Default for TargetConfiguration {
@@ -154,9 +247,14 @@ impl Default for TargetConfiguration {
             projection_from_view: Projection::Perspective {
                 vertical_fov: 70.0 * std::f32::consts::TAU / 360.0,
                 near_plane_distance: 0.01,
+                far_plane_distance: f32::INFINITY,
             },
             pixels_from_point: 1.0,
             auto_size_config: Default::default(),
+            lighting: Default::default(),
+            resolution_scale: 1.0,
+            fast_point_splatting: false,
+            outline_config: None,
         }
     }
 }
@@ -183,20 +281,31 @@ impl ViewBuilder {
     /// [`wgpu::TextureFormat::Depth32Float`] on the other hand is widely supported and has the best possible precision (with reverse infinite z projection which we're already using).
     pub const MAIN_TARGET_DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
 
-    /// Enable MSAA always. This makes our pipeline less variable as well, as we need MSAA resolve steps if we want any MSAA at all!
+    /// Enable MSAA by default, see [`RenderContext::set_msaa_sample_count`].
     ///
-    /// 4 samples are the only thing `WebGPU` supports, and currently wgpu as well
+    /// 4 samples are the only thing `WebGPU` supports (besides 1), and currently wgpu as well
     /// ([tracking issue for more options on native](https://github.com/gfx-rs/wgpu/issues/2910))
-    pub const MAIN_TARGET_SAMPLE_COUNT: u32 = 4;
+    pub const MAIN_TARGET_DEFAULT_SAMPLE_COUNT: u32 = 4;
+
+    /// Width and height of the shadow map of the directional light, see [`Lighting::shadow_bounds`].
+    pub const SHADOW_MAP_RESOLUTION: u32 = 2048;
+
+    /// Depth state for drawing into the shadow map, which has the same format as the main target's depth.
+    pub const SHADOW_MAP_DEPTH_STATE: Option<wgpu::DepthStencilState> =
+        Self::MAIN_TARGET_DEFAULT_DEPTH_STATE;
 
     /// Default multisample state that any [`wgpu::RenderPipeline`] drawing to the main target needs to use.
     ///
     /// In rare cases, pipelines may want to enable alpha to coverage and/or sample masks.
-    pub const MAIN_TARGET_DEFAULT_MSAA_STATE: wgpu::MultisampleState = wgpu::MultisampleState {
-        count: ViewBuilder::MAIN_TARGET_SAMPLE_COUNT,
-        mask: !0,
-        alpha_to_coverage_enabled: false,
-    };
+    pub fn main_target_default_msaa_state(
+        shared_data: &SharedRendererData,
+    ) -> wgpu::MultisampleState {
+        wgpu::MultisampleState {
+            count: shared_data.msaa_sample_count,
+            mask: !0,
+            alpha_to_coverage_enabled: false,
+        }
+    }
 
     /// Default depth state for enabled depth write & read.
     pub const MAIN_TARGET_DEFAULT_DEPTH_STATE: Option<wgpu::DepthStencilState> =
@@ -228,24 +337,34 @@ impl ViewBuilder {
         assert_ne!(config.resolution_in_pixel[0], 0);
         assert_ne!(config.resolution_in_pixel[1], 0);
 
+        // We may render at a different resolution than we show on screen, see `resolution_scale`.
+        let max_texture_size = ctx.device.limits().max_texture_dimension_2d;
+        let render_resolution = config.resolution_in_pixel.map(|size| {
+            ((size as f32 * config.resolution_scale).round() as u32).clamp(1, max_texture_size)
+        });
+        let render_scale = render_resolution[1] as f32 / config.resolution_in_pixel[1] as f32;
+
         // TODO(andreas): Should tonemapping preferences go here as well? Likely!
+        let msaa_sample_count = ctx.shared_renderer_data.msaa_sample_count;
         let main_target_desc = TextureDesc {
             label: format!("{:?} - main target", config.name).into(),
             size: wgpu::Extent3d {
-                width: config.resolution_in_pixel[0],
-                height: config.resolution_in_pixel[1],
+                width: render_resolution[0],
+                height: render_resolution[1],
                 depth_or_array_layers: 1,
             },
             mip_level_count: 1,
-            sample_count: Self::MAIN_TARGET_SAMPLE_COUNT,
+            sample_count: msaa_sample_count,
             dimension: wgpu::TextureDimension::D2,
             format: Self::MAIN_TARGET_COLOR_FORMAT,
             usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
         };
-        let hdr_render_target_msaa = ctx
-            .gpu_resources
-            .textures
-            .alloc(&ctx.device, &main_target_desc);
+        // Without MSAA, we draw directly into the resolved target.
+        let hdr_render_target_msaa = (msaa_sample_count > 1).then(|| {
+            ctx.gpu_resources
+                .textures
+                .alloc(&ctx.device, &main_target_desc)
+        });
         // Like hdr_render_target, but with MSAA resolved.
         let main_target_resolved = ctx.gpu_resources.textures.alloc(
             &ctx.device,
@@ -266,36 +385,90 @@ impl ViewBuilder {
             },
         );
 
-        let tonemapping_draw_data = CompositorDrawData::new(ctx, &main_target_resolved);
-
-        // Setup frame uniform buffer
-        let frame_uniform_buffer = ctx.gpu_resources.buffers.alloc(
+        let outline_masks = config.outline_config.as_ref().map(|_| {
+            [OutlineLayer::A, OutlineLayer::B].map(|layer| OutlineMask {
+                msaa: (msaa_sample_count > 1).then(|| {
+                    ctx.gpu_resources.textures.alloc(
+                        &ctx.device,
+                        &TextureDesc {
+                            label: format!("{:?} - outline mask {layer:?}", config.name).into(),
+                            ..main_target_desc
+                        },
+                    )
+                }),
+                resolved: ctx.gpu_resources.textures.alloc(
+                    &ctx.device,
+                    &TextureDesc {
+                        label: format!("{:?} - outline mask {layer:?} resolved", config.name)
+                            .into(),
+                        sample_count: 1,
+                        usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                            | wgpu::TextureUsages::TEXTURE_BINDING,
+                        ..main_target_desc
+                    },
+                ),
+            })
+        });
+        // Bound instead of the outline masks if there are none.
+        let no_outline_mask = ctx.gpu_resources.textures.alloc(
             &ctx.device,
-            &BufferDesc {
-                label: format!("{:?} - frame uniform buffer", config.name).into(),
-                size: std::mem::size_of::<FrameUniformBuffer>() as _,
-                usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
-                mapped_at_creation: false,
+            &TextureDesc {
+                label: format!("{:?} - no outline mask", config.name).into(),
+                size: wgpu::Extent3d {
+                    width: 1,
+                    height: 1,
+                    depth_or_array_layers: 1,
+                },
+                sample_count: 1,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING,
+                ..main_target_desc
             },
         );
 
-        let aspect_ratio =
-            config.resolution_in_pixel[0] as f32 / config.resolution_in_pixel[1] as f32;
+        let tonemapping_draw_data = CompositorDrawData::new(
+            ctx,
+            &main_target_resolved,
+            match &outline_masks {
+                Some([mask_a, mask_b]) => [&mask_a.resolved, &mask_b.resolved],
+                None => [&no_outline_mask, &no_outline_mask],
+            },
+            config
+                .outline_config
+                .as_ref()
+                .map(|outline_config| OutlineConfig {
+                    outline_radius_pixel: outline_config.outline_radius_pixel * render_scale,
+                    ..outline_config.clone()
+                })
+                .as_ref(),
+        );
+
+        let aspect_ratio = render_resolution[0] as f32 / render_resolution[1] as f32;
 
         let (projection_from_view, tan_half_fov, pixel_world_size_from_camera_distance) =
             match config.projection_from_view.clone() {
                 Projection::Perspective {
                     vertical_fov,
                     near_plane_distance,
+                    far_plane_distance,
                 } => {
                     // We use infinite reverse-z projection matrix
                     // * great precision both with floating point and integer: https://developer.nvidia.com/content/depth-precision-visualized
                     // * no need to worry about far plane
-                    let projection_from_view = glam::Mat4::perspective_infinite_reverse_rh(
-                        vertical_fov,
-                        aspect_ratio,
-                        near_plane_distance,
-                    );
+                    // unless one was asked for, in which case we reverse z by swapping near and far plane.
+                    let projection_from_view = if far_plane_distance.is_finite() {
+                        glam::Mat4::perspective_rh(
+                            vertical_fov,
+                            aspect_ratio,
+                            far_plane_distance,
+                            near_plane_distance,
+                        )
+                    } else {
+                        glam::Mat4::perspective_infinite_reverse_rh(
+                            vertical_fov,
+                            aspect_ratio,
+                            near_plane_distance,
+                        )
+                    };
 
                     // Calculate ratio between screen size and screen distance.
                     // Great for getting directions from normalized device coordinates.
@@ -315,7 +488,7 @@ impl ViewBuilder {
                     // => (resolution / screen_in_world / distance) = tan(FOV / 2) * distance * 2 / resolution / distance =
                     //                                              = tan(FOV / 2) * 2.0 / resolution
                     let pixel_world_size_from_camera_distance =
-                        tan_half_fov.y * 2.0 / config.resolution_in_pixel[1] as f32;
+                        tan_half_fov.y * 2.0 / render_resolution[1] as f32;
 
                     (
                         projection_from_view,
@@ -353,7 +526,7 @@ impl ViewBuilder {
 
                     let tan_half_fov = glam::vec2(f32::INFINITY, f32::INFINITY);
                     let pixel_world_size_from_camera_distance =
-                        vertical_world_size / config.resolution_in_pixel[1] as f32;
+                        vertical_world_size / render_resolution[1] as f32;
 
                     (
                         projection_from_view,
@@ -393,38 +566,98 @@ impl ViewBuilder {
         // Factor applied to depth offsets.
         let depth_offset_factor = 1.0e-08; // Value determined by experimentation. Quite close to the f32 machine epsilon but a bit lower.
 
-        {
-            let mut frame_uniform_buffer_cpu = ctx
-                .cpu_write_gpu_read_belt
-                .lock()
-                .allocate::<FrameUniformBuffer>(&ctx.device, &mut ctx.gpu_resources.buffers, 1);
-            frame_uniform_buffer_cpu.push(&FrameUniformBuffer {
-                view_from_world: glam::Affine3A::from_mat4(view_from_world).into(),
-                projection_from_view: projection_from_view.into(),
-                projection_from_world: projection_from_world.into(),
-                camera_position,
-                camera_forward,
-                tan_half_fov: tan_half_fov.into(),
-                pixel_world_size_from_camera_distance,
-                pixels_from_point: config.pixels_from_point,
-
-                auto_size_points: auto_size_points.0,
-                auto_size_lines: auto_size_lines.0,
-
-                depth_offset_factor,
-                _padding: glam::Vec3::ZERO,
-            });
-            frame_uniform_buffer_cpu.copy_to_buffer(
-                ctx.active_frame.frame_global_command_encoder(&ctx.device),
-                &frame_uniform_buffer,
-                0,
-            );
-        }
+        let light_direction = config.lighting.light_direction.normalize_or_zero();
+        let light_view_projection = config
+            .lighting
+            .shadow_bounds
+            .and_then(|bounds| light_view_projection(&bounds, light_direction));
+
+        let frame_uniform = FrameUniformBuffer {
+            view_from_world: glam::Affine3A::from_mat4(view_from_world).into(),
+            projection_from_view: projection_from_view.into(),
+            projection_from_world: projection_from_world.into(),
+            camera_position,
+            camera_forward,
+            tan_half_fov: tan_half_fov.into(),
+            pixel_world_size_from_camera_distance,
+            pixels_from_point: config.pixels_from_point * render_scale,
+
+            auto_size_points: auto_size_points.0,
+            auto_size_lines: auto_size_lines.0,
+
+            depth_offset_factor,
+            _padding: glam::Vec3::ZERO,
+
+            light_direction,
+            ambient_light: config.lighting.ambient_light,
+            shadow_map_from_world: light_view_projection
+                .map_or(glam::Mat4::ZERO, |(light_view, light_projection)| {
+                    light_projection * light_view
+                })
+                .into(),
+            shadows_enabled: light_view_projection.is_some() as u32,
+            fast_point_splatting: config.fast_point_splatting as u32,
+            _padding_2: [0; 2],
+        };
+        let frame_uniform_buffer = upload_frame_uniform_buffer(
+            ctx,
+            format!("{:?} - frame uniform buffer", config.name).into(),
+            &frame_uniform,
+        );
+
+        // Bound instead of the shadow map if there is none, and while drawing the shadow map.
+        let no_shadow_map = ctx.gpu_resources.textures.alloc(
+            &ctx.device,
+            &shadow_map_desc(format!("{:?} - no shadow map", config.name).into(), 1),
+        );
+
+        let shadow_pass = match light_view_projection {
+            Some((light_view, light_projection)) => {
+                let shadow_map = ctx.gpu_resources.textures.alloc(
+                    &ctx.device,
+                    &shadow_map_desc(
+                        format!("{:?} - shadow map", config.name).into(),
+                        Self::SHADOW_MAP_RESOLUTION,
+                    ),
+                );
+                let light_frame_uniform_buffer = upload_frame_uniform_buffer(
+                    ctx,
+                    format!("{:?} - light frame uniform buffer", config.name).into(),
+                    &FrameUniformBuffer {
+                        view_from_world: glam::Affine3A::from_mat4(light_view).into(),
+                        projection_from_view: light_projection.into(),
+                        projection_from_world: (light_projection * light_view).into(),
+                        camera_position: light_view.inverse().transform_point3(glam::Vec3::ZERO),
+                        camera_forward: -light_direction,
+                        tan_half_fov: glam::vec2(f32::INFINITY, f32::INFINITY).into(),
+                        pixel_world_size_from_camera_distance: 2.0
+                            / light_projection.y_axis.y
+                            / Self::SHADOW_MAP_RESOLUTION as f32,
+                        shadows_enabled: 0,
+                        ..frame_uniform
+                    },
+                );
+                let bind_group_0 = ctx.shared_renderer_data.global_bindings.create_bind_group(
+                    &mut ctx.gpu_resources,
+                    &ctx.device,
+                    &light_frame_uniform_buffer,
+                    &no_shadow_map,
+                );
+                Some(ShadowPass {
+                    shadow_map,
+                    bind_group_0,
+                })
+            }
+            None => None,
+        };
 
         let bind_group_0 = ctx.shared_renderer_data.global_bindings.create_bind_group(
             &mut ctx.gpu_resources,
             &ctx.device,
             &frame_uniform_buffer,
+            shadow_pass
+                .as_ref()
+                .map_or(&no_shadow_map, |shadow_pass| &shadow_pass.shadow_map),
         );
 
         self.setup = Some(ViewTargetSetup {
@@ -434,6 +667,8 @@ impl ViewBuilder {
             main_target_msaa: hdr_render_target_msaa,
             main_target_resolved,
             depth_buffer,
+            shadow_pass,
+            outline_masks,
             resolution_in_pixel: config.resolution_in_pixel,
         });
 
@@ -445,8 +680,27 @@ impl ViewBuilder {
         draw_data: &D,
     ) -> &mut Self {
         crate::profile_function!();
+        self.queued_draws.push(Self::new_queued_draw(draw_data));
+        self
+    }
+
+    /// Draw into the mask of the given outline layer, which gets outlined during [`Self::composite`].
+    ///
+    /// Has no effect unless [`TargetConfiguration::outline_config`] is set.
+    /// This is in addition to [`Self::queue_draw`], so usually the same things are queued for both.
+    /// The color of what is drawn doesn't matter, only its coverage.
+    pub fn queue_outline_draw<D: DrawData + Sync + Send + Clone + 'static>(
+        &mut self,
+        layer: OutlineLayer,
+        draw_data: &D,
+    ) -> &mut Self {
+        crate::profile_function!();
+        self.queued_outline_draws[layer as usize].push(Self::new_queued_draw(draw_data));
+        self
+    }
 
-        self.queued_draws.push(QueuedDraw {
+    fn new_queued_draw<D: DrawData + Sync + Send + Clone + 'static>(draw_data: &D) -> QueuedDraw {
+        QueuedDraw {
             draw_func: Box::new(move |ctx, pass, draw_data| {
                 let renderer = ctx
                     .renderers
@@ -457,11 +711,19 @@ impl ViewBuilder {
                     .expect("passed wrong type of draw data");
                 renderer.draw(&ctx.gpu_resources, pass, draw_data)
             }),
+            shadow_caster_draw_func: Box::new(move |ctx, pass, draw_data| {
+                let renderer = ctx
+                    .renderers
+                    .get::<D::Renderer>()
+                    .context("failed to retrieve renderer")?;
+                let draw_data = draw_data
+                    .downcast_ref::<D>()
+                    .expect("passed wrong type of draw data");
+                renderer.draw_shadow_casters(&ctx.gpu_resources, pass, draw_data)
+            }),
             draw_data: Box::new(draw_data.clone()),
             sorting_index: D::Renderer::draw_order(),
-        });
-
-        self
+        }
     }
 
     /// Draws the frame as instructed to a temporary HDR target.
@@ -483,14 +745,54 @@ impl ViewBuilder {
                 label: setup.name.clone().get(),
             });
 
+        self.queued_draws
+            .sort_by(|a, b| a.sorting_index.cmp(&b.sorting_index));
+        for queued_outline_draws in &mut self.queued_outline_draws {
+            queued_outline_draws.sort_by(|a, b| a.sorting_index.cmp(&b.sorting_index));
+        }
+
+        if let Some(shadow_pass) = &setup.shadow_pass {
+            crate::profile_scope!("view builder shadow pass");
+
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: DebugLabel::from(format!("{:?} - shadow pass", setup.name)).get(),
+                color_attachments: &[],
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                    view: &shadow_pass.shadow_map.default_view,
+                    depth_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(0.0), // 0.0 == far since we're using reverse-z
+                        store: true,
+                    }),
+                    stencil_ops: None,
+                }),
+            });
+
+            pass.set_bind_group(0, &shadow_pass.bind_group_0, &[]);
+
+            for queued_draw in &self.queued_draws {
+                (queued_draw.shadow_caster_draw_func)(
+                    ctx,
+                    &mut pass,
+                    queued_draw.draw_data.as_ref(),
+                )
+                .context("drawing shadow casters")?;
+            }
+        }
+
         {
             crate::profile_scope!("view builder main target pass");
 
             let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                 label: DebugLabel::from(format!("{:?} - main pass", setup.name)).get(),
                 color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &setup.main_target_msaa.default_view,
-                    resolve_target: Some(&setup.main_target_resolved.default_view),
+                    view: setup.main_target_msaa.as_ref().map_or(
+                        &setup.main_target_resolved.default_view,
+                        |main_target_msaa| &main_target_msaa.default_view,
+                    ),
+                    resolve_target: setup
+                        .main_target_msaa
+                        .as_ref()
+                        .map(|_| &setup.main_target_resolved.default_view),
                     ops: wgpu::Operations {
                         load: wgpu::LoadOp::Clear(wgpu::Color {
                             r: clear_color.r() as f64,
@@ -500,7 +802,7 @@ impl ViewBuilder {
                         }),
                         // Don't care about the result, it's going to be resolved to the resolve target.
                         // This can have be much better perf, especially on tiler gpus.
-                        store: false,
+                        store: setup.main_target_msaa.is_none(),
                     },
                 })],
                 depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
@@ -517,14 +819,55 @@ impl ViewBuilder {
 
             pass.set_bind_group(0, &setup.bind_group_0, &[]);
 
-            self.queued_draws
-                .sort_by(|a, b| a.sorting_index.cmp(&b.sorting_index));
             for queued_draw in &self.queued_draws {
                 (queued_draw.draw_func)(ctx, &mut pass, queued_draw.draw_data.as_ref())
                     .context("drawing a view")?;
             }
         }
 
+        if let Some(outline_masks) = &setup.outline_masks {
+            crate::profile_scope!("view builder outline mask passes");
+
+            for (outline_mask, queued_draws) in outline_masks.iter().zip(&self.queued_outline_draws)
+            {
+                let (view, resolve_target) = match &outline_mask.msaa {
+                    Some(msaa) => (
+                        &msaa.default_view,
+                        Some(&outline_mask.resolved.default_view),
+                    ),
+                    None => (&outline_mask.resolved.default_view, None),
+                };
+                let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: DebugLabel::from(format!("{:?} - outline mask pass", setup.name)).get(),
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view,
+                        resolve_target,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                            store: outline_mask.msaa.is_none(),
+                        },
+                    })],
+                    // The main pass doesn't need its depth anymore, and the outlined objects
+                    // shouldn't be occluded by anything else, so start over with a cleared depth buffer.
+                    depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                        view: &setup.depth_buffer.default_view,
+                        depth_ops: Some(wgpu::Operations {
+                            load: wgpu::LoadOp::Clear(0.0), // 0.0 == far since we're using reverse-z
+                            store: false,
+                        }),
+                        stencil_ops: None,
+                    }),
+                });
+
+                pass.set_bind_group(0, &setup.bind_group_0, &[]);
+
+                for queued_draw in queued_draws {
+                    (queued_draw.draw_func)(ctx, &mut pass, queued_draw.draw_data.as_ref())
+                        .context("drawing an outline mask")?;
+                }
+            }
+        }
+
         Ok(encoder.finish())
     }
 
@@ -565,3 +908,72 @@ impl ViewBuilder {
             .context("composite into main view")
     }
 }
+
+/// Depth texture for the shadow map of the directional light.
+fn shadow_map_desc(label: DebugLabel, resolution: u32) -> TextureDesc {
+    TextureDesc {
+        label,
+        size: wgpu::Extent3d {
+            width: resolution,
+            height: resolution,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: ViewBuilder::MAIN_TARGET_DEPTH_FORMAT,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+    }
+}
+
+/// View and (reverse-z orthographic) projection of the directional light,
+/// such that it sees all of `bounds`.
+fn light_view_projection(
+    bounds: &macaw::BoundingBox,
+    light_direction: glam::Vec3,
+) -> Option<(glam::Mat4, glam::Mat4)> {
+    let radius = bounds.half_size().length();
+    if light_direction == glam::Vec3::ZERO || !radius.is_finite() || radius <= 0.0 {
+        return None;
+    }
+
+    let center = bounds.center();
+    let up = if light_direction.z.abs() < 0.99 {
+        glam::Vec3::Z
+    } else {
+        glam::Vec3::Y
+    };
+    let light_view = glam::Mat4::look_at_rh(center + radius * light_direction, center, up);
+    let light_projection =
+        glam::Mat4::orthographic_rh(-radius, radius, -radius, radius, 2.0 * radius, 0.0);
+    Some((light_view, light_projection))
+}
+
+fn upload_frame_uniform_buffer(
+    ctx: &mut RenderContext,
+    label: DebugLabel,
+    frame_uniform: &FrameUniformBuffer,
+) -> GpuBuffer {
+    let frame_uniform_buffer = ctx.gpu_resources.buffers.alloc(
+        &ctx.device,
+        &BufferDesc {
+            label,
+            size: std::mem::size_of::<FrameUniformBuffer>() as _,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        },
+    );
+
+    let mut frame_uniform_buffer_cpu = ctx
+        .cpu_write_gpu_read_belt
+        .lock()
+        .allocate::<FrameUniformBuffer>(&ctx.device, &mut ctx.gpu_resources.buffers, 1);
+    frame_uniform_buffer_cpu.push(frame_uniform);
+    frame_uniform_buffer_cpu.copy_to_buffer(
+        ctx.active_frame.frame_global_command_encoder(&ctx.device),
+        &frame_uniform_buffer,
+        0,
+    );
+
+    frame_uniform_buffer
+}
@@ -0,0 +1,299 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+
+use crossbeam::channel::{Receiver, Sender};
+
+use re_log_types::LogMsg;
+
+/// When to continue a recording in a new file, so that long captures are split into
+/// manageable parts that can e.g. be uploaded while the capture is still running.
+///
+/// See [`crate::Session::save_while_logging_in_parts`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FileSplit {
+    /// Start a new file after this long.
+    pub max_duration: Option<Duration>,
+
+    /// Start a new file once the current one is this many bytes.
+    pub max_bytes: Option<u64>,
+}
+
+impl FileSplit {
+    /// Start a new file every `minutes` minutes.
+    pub fn every_minutes(minutes: u64) -> Self {
+        Self {
+            max_duration: Some(Duration::from_secs(60 * minutes)),
+            max_bytes: None,
+        }
+    }
+
+    /// Start a new file every `gigabytes` GB.
+    pub fn every_gigabytes(gigabytes: u64) -> Self {
+        Self {
+            max_duration: None,
+            max_bytes: Some(gigabytes * 1_000_000_000),
+        }
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.max_duration.is_some() || self.max_bytes.is_some()
+    }
+}
+
+enum Command {
+    Send(LogMsg),
+    Flush(Sender<()>),
+}
+
+/// Writes [`LogMsg`]es to an `.rrd` file as they are logged.
+///
+/// The encoding and writing happens on a separate thread, with its own unbounded buffer,
+/// so a slow disk never holds up the other sinks of the [`crate::Session`], and vice versa.
+/// If writing fails we log an error and drop the rest of the messages for this file.
+///
+/// Usually created with [`crate::Session::save_while_logging`], but can also be used on its own,
+/// e.g. to save the messages received by a Rerun Server.
+pub struct FileSink {
+    path: PathBuf,
+    tx: Option<Sender<Command>>,
+    join: Option<JoinHandle<()>>,
+}
+
+impl FileSink {
+    /// Creates the (first) file right away, so that we can report if that fails.
+    pub fn new(path: impl Into<PathBuf>, split: FileSplit) -> anyhow::Result<Self> {
+        use anyhow::Context as _;
+
+        let path = path.into();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("rrd") {
+            re_log::warn!("Expected path to end with .rrd, got {path:?}");
+        }
+
+        let writer = PartWriter::new(path.clone(), split)?;
+
+        let (tx, rx) = crossbeam::channel::unbounded();
+        let thread_path = path.clone();
+        let join = std::thread::Builder::new()
+            .name("file_sink".into())
+            .spawn(move || {
+                if let Err(err) = write_to_file(writer, &rx) {
+                    re_log::error!(
+                        "Failed to write to {thread_path:?}: {err:#} - no more data will be saved to it"
+                    );
+                }
+            })
+            .context("Failed to spawn thread")?;
+
+        re_log::info!("Saving the log stream to {path:?}");
+
+        Ok(Self {
+            path,
+            tx: Some(tx),
+            join: Some(join),
+        })
+    }
+
+    /// Queue a message for writing.
+    pub fn send(&self, msg: LogMsg) {
+        if let Some(tx) = &self.tx {
+            tx.send(Command::Send(msg)).ok(); // Errors have already been logged by the thread.
+        }
+    }
+
+    /// Stall until all messages so far have been written.
+    pub fn flush(&self) {
+        if let Some(tx) = &self.tx {
+            let (flushed_tx, flushed_rx) = crossbeam::channel::bounded(1);
+            if tx.send(Command::Flush(flushed_tx)).is_ok() {
+                flushed_rx.recv().ok();
+            }
+        }
+    }
+}
+
+impl Drop for FileSink {
+    /// Wait until everything has been written.
+    fn drop(&mut self) {
+        self.tx = None; // Hang up, so that the thread finishes the file.
+        if let Some(join) = self.join.take() {
+            join.join().ok();
+        }
+        re_log::debug!("Closed {:?}", self.path);
+    }
+}
+
+fn write_to_file(mut writer: PartWriter, rx: &Receiver<Command>) -> anyhow::Result<()> {
+    while let Ok(command) = rx.recv() {
+        match command {
+            Command::Send(msg) => {
+                writer.append(msg)?;
+
+                // Keep the file readable (e.g. by `rerun --follow`) whenever we are idle:
+                if rx.is_empty() {
+                    writer.encoder.flush()?;
+                }
+            }
+            Command::Flush(flushed_tx) => {
+                writer.encoder.flush()?;
+                flushed_tx.send(()).ok();
+            }
+        }
+    }
+    writer.encoder.finish()
+}
+
+/// Writes to one file, or to a sequence of numbered files if we are splitting the recording.
+struct PartWriter {
+    path: PathBuf,
+    split: FileSplit,
+
+    /// Which part we are writing, if we are splitting the recording.
+    part_index: usize,
+    part_started: Instant,
+    part_bytes: Arc<AtomicU64>,
+
+    encoder: re_log_types::encoding::Encoder<CountingWriter>,
+
+    /// Written at the start of every part, so that each part can be viewed on its own,
+    /// and so that opening all parts together shows them as a single recording:
+    /// the [`LogMsg::BeginRecordingMsg`], the timeless data, and the blueprint.
+    header_msgs: Vec<LogMsg>,
+}
+
+impl PartWriter {
+    fn new(path: PathBuf, split: FileSplit) -> anyhow::Result<Self> {
+        let part_bytes = Arc::new(AtomicU64::new(0));
+        let encoder = create_encoder(&part_path(&path, split, 0), &part_bytes)?;
+        Ok(Self {
+            path,
+            split,
+            part_index: 0,
+            part_started: Instant::now(),
+            part_bytes,
+            encoder,
+            header_msgs: Vec::new(),
+        })
+    }
+
+    fn append(&mut self, msg: LogMsg) -> anyhow::Result<()> {
+        if !self.split.is_enabled() {
+            return self.encoder.append(&msg);
+        }
+
+        if self.is_part_full() {
+            self.start_next_part()?;
+        }
+
+        self.encoder.append(&msg)?;
+        if is_header_msg(&msg) {
+            self.header_msgs.push(msg);
+        }
+        Ok(())
+    }
+
+    fn is_part_full(&self) -> bool {
+        let FileSplit {
+            max_duration,
+            max_bytes,
+        } = self.split;
+        max_duration.map_or(false, |max_duration| {
+            max_duration <= self.part_started.elapsed()
+        }) || max_bytes.map_or(false, |max_bytes| {
+            max_bytes <= self.part_bytes.load(Ordering::Relaxed)
+        })
+    }
+
+    fn start_next_part(&mut self) -> anyhow::Result<()> {
+        self.part_index += 1;
+        self.part_started = Instant::now();
+        self.part_bytes = Arc::new(AtomicU64::new(0));
+
+        let path = part_path(&self.path, self.split, self.part_index);
+        let encoder = create_encoder(&path, &self.part_bytes)?;
+        std::mem::replace(&mut self.encoder, encoder).finish()?;
+        re_log::info!("Continuing the recording in {path:?}");
+
+        for msg in &self.header_msgs {
+            self.encoder.append(msg)?;
+        }
+        Ok(())
+    }
+}
+
+/// `capture.rrd` becomes `capture.0000.rrd`, `capture.0001.rrd`, etc. if we are splitting.
+fn part_path(path: &Path, split: FileSplit, part_index: usize) -> PathBuf {
+    if !split.is_enabled() {
+        return path.to_owned();
+    }
+
+    let stem = path
+        .file_stem()
+        .map_or_else(Default::default, |stem| stem.to_string_lossy());
+    let extension = path
+        .extension()
+        .map_or_else(|| "rrd".into(), |ext| ext.to_string_lossy());
+    path.with_file_name(format!("{stem}.{part_index:04}.{extension}"))
+}
+
+fn is_header_msg(msg: &LogMsg) -> bool {
+    match msg {
+        LogMsg::BeginRecordingMsg(_) | LogMsg::BlueprintMsg(_) => true,
+        LogMsg::EntityPathOpMsg(msg) => msg.time_point.is_timeless(),
+        LogMsg::ArrowMsg(msg) => msg
+            .time_point()
+            .map_or(false, |time_point| time_point.is_timeless()),
+        LogMsg::TraceSpansMsg(_) | LogMsg::Goodbye(_) => false,
+    }
+}
+
+fn create_encoder(
+    path: &Path,
+    bytes_written: &Arc<AtomicU64>,
+) -> anyhow::Result<re_log_types::encoding::Encoder<CountingWriter>> {
+    use anyhow::Context as _;
+
+    let file = std::fs::File::create(path)
+        .with_context(|| format!("Failed to create file at {path:?}"))?;
+    let writer = CountingWriter {
+        file,
+        bytes_written: bytes_written.clone(),
+    };
+    re_log_types::encoding::Encoder::new(writer)
+        .with_context(|| format!("Failed to write to file at {path:?}"))
+}
+
+/// Keeps track of how large the file is, so we know when to split it.
+struct CountingWriter {
+    file: std::fs::File,
+    bytes_written: Arc<AtomicU64>,
+}
+
+impl std::io::Write for CountingWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let num_bytes = self.file.write(buf)?;
+        self.bytes_written
+            .fetch_add(num_bytes as u64, Ordering::Relaxed);
+        Ok(num_bytes)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.file.flush()
+    }
+}
+
+#[test]
+fn test_part_path() {
+    let path = Path::new("captures/field_run.rrd");
+    assert_eq!(part_path(path, FileSplit::default(), 0), path);
+    assert_eq!(
+        part_path(path, FileSplit::every_minutes(10), 12),
+        Path::new("captures/field_run.0012.rrd")
+    );
+}
@@ -7,11 +7,22 @@
 #![warn(missing_docs)] // Let's keep the this crate well-documented!
 
 // Send data to a rerun session
+#[cfg(feature = "save")]
+#[cfg(not(target_arch = "wasm32"))]
+mod file_sink;
 mod global;
+mod middleware;
 mod msg_sender;
 mod session;
 
+#[cfg(any(test, feature = "test_utils"))]
+pub mod test;
+
+#[cfg(feature = "save")]
+#[cfg(not(target_arch = "wasm32"))]
+pub use self::file_sink::{FileSink, FileSplit};
 pub use self::global::{global_session, global_session_with_default_enabled};
+pub use self::middleware::Middleware;
 pub use self::msg_sender::{MsgSender, MsgSenderError};
 pub use self::session::Session;
 
@@ -31,6 +42,13 @@ pub mod log {
     };
 }
 
+/// Telling the viewer how to lay out the logged data.
+///
+/// See [`Session::send_blueprint_op`].
+pub mod blueprint {
+    pub use re_log_types::{BlueprintOp, SpaceViewDescription, SpaceViewKind};
+}
+
 /// Time-related types.
 pub mod time {
     pub use re_log_types::{Time, TimeInt, TimePoint, TimeType, Timeline};
@@ -42,12 +60,12 @@ pub mod time {
 /// and can be used in [`MsgSender::with_component`].
 pub mod components {
     pub use re_log_types::component_types::{
-        AnnotationContext, AnnotationInfo, Arrow3D, Box3D, ClassDescription, ClassId, ColorRGBA,
-        EncodedMesh3D, InstanceKey, KeypointId, Label, LineStrip2D, LineStrip3D, Mat3x3, Mesh3D,
-        MeshFormat, MeshId, Pinhole, Point2D, Point3D, Quaternion, Radius, RawMesh3D, Rect2D,
-        Rigid3, Scalar, ScalarPlotProps, Size3D, Tensor, TensorData, TensorDataMeaning,
-        TensorDimension, TensorId, TensorTrait, TextEntry, Transform, Vec2D, Vec3D, Vec4D,
-        ViewCoordinates,
+        AnnotationContext, AnnotationContextRef, AnnotationInfo, Arrow3D, Box3D, ClassDescription,
+        ClassId, ColorRGBA, EncodedMesh3D, Group, InstanceKey, KeypointId, Label, LineStrip2D,
+        LineStrip3D, Mat3x3, Mesh3D, MeshFormat, MeshId, Metadata, MetadataEntry, Pinhole, Point2D,
+        Point3D, Quaternion, Radius, RawMesh3D, Rect2D, Rigid3, Scalar, ScalarPlotProps, Size3D,
+        Tensor, TensorData, TensorDataMeaning, TensorDimension, TensorId, TensorTrait, TextEntry,
+        Transform, Unit, Vec2D, Vec3D, Vec4D, ViewCoordinates,
     };
 }
 
@@ -77,7 +95,8 @@ pub mod external {
 
 pub use re_log_types::{
     msg_bundle::{Component, SerializableComponent},
-    ApplicationId, ComponentName, EntityPath, RecordingId,
+    set_deterministic, ApplicationId, ComponentName, EntityPath, PointCloud, PointCloudError,
+    RecordingId,
 };
 
 const RERUN_ENV_VAR: &str = "RERUN";
@@ -0,0 +1,36 @@
+use re_log_types::LogMsg;
+
+/// Intercepts every [`LogMsg`] of a [`crate::Session`] before it is sent anywhere.
+///
+/// Use this to e.g. add metadata, encrypt payloads, or mirror the messages to a second
+/// destination. Add one with [`crate::Session::with_middleware`].
+///
+/// Any `FnMut(LogMsg) -> Option<LogMsg>` closure is a [`Middleware`]:
+///
+/// ```
+/// # use re_sdk::{log::LogMsg, Session};
+/// let session = Session::init("my_app", false).with_middleware(|msg: LogMsg| {
+///     // Keep trace spans out of the recording:
+///     if matches!(msg, LogMsg::TraceSpansMsg(_)) {
+///         None
+///     } else {
+///         Some(msg)
+///     }
+/// });
+/// ```
+pub trait Middleware: Send {
+    /// Return the (possibly modified) message to send, or `None` to drop it.
+    ///
+    /// Middlewares are run in the order they were added,
+    /// each one getting the output of the previous.
+    fn process(&mut self, msg: LogMsg) -> Option<LogMsg>;
+}
+
+impl<F> Middleware for F
+where
+    F: FnMut(LogMsg) -> Option<LogMsg> + Send,
+{
+    fn process(&mut self, msg: LogMsg) -> Option<LogMsg> {
+        self(msg)
+    }
+}
@@ -7,7 +7,7 @@ use re_log_types::{component_types::InstanceKey, msg_bundle::wrap_in_listarray};
 use crate::components::Transform;
 use crate::log::{ComponentBundle, LogMsg, MsgBundle, MsgId};
 use crate::time::{Time, TimeInt, TimePoint, Timeline};
-use crate::{Component, ComponentName, EntityPath, SerializableComponent, Session};
+use crate::{Component, ComponentName, EntityPath, PointCloud, SerializableComponent, Session};
 
 // ---
 
@@ -181,11 +181,25 @@ impl MsgSender {
     //
     // TODO(#589): batch insertions
     pub fn with_component<'a, C: SerializableComponent>(
-        mut self,
+        self,
         data: impl IntoIterator<Item = &'a C>,
     ) -> Result<Self, MsgSenderError> {
-        let bundle = bundle_from_iter(data)?;
+        self.with_bundle(bundle_from_iter(data)?)
+    }
+
+    /// Appends the positions of a [`PointCloud`], and whichever of its colors, radii and
+    /// instance keys it has.
+    ///
+    /// Much faster than calling [`Self::with_component`] for each of them when logging
+    /// millions of points, since the point cloud is already packed the way it is sent.
+    pub fn with_point_cloud(mut self, point_cloud: &PointCloud) -> Result<Self, MsgSenderError> {
+        for bundle in point_cloud.component_bundles() {
+            self = self.with_bundle(bundle)?;
+        }
+        Ok(self)
+    }
 
+    fn with_bundle(mut self, bundle: ComponentBundle) -> Result<Self, MsgSenderError> {
         let num_instances = bundle.num_instances(0).unwrap(); // must have exactly 1 row atm
 
         // If this is the first appended collection, it gets to decide the row-length (i.e. number
@@ -196,7 +210,7 @@ impl MsgSender {
 
         // Detect mismatched row-lengths early on... unless it's a Transform bundle: transforms
         // behave differently and will be sent in their own message!
-        if C::name() != Transform::name() && self.num_instances.unwrap() != num_instances {
+        if bundle.name() != Transform::name() && self.num_instances.unwrap() != num_instances {
             let collections = self
                 .instanced
                 .into_iter()
@@ -253,11 +267,24 @@ impl MsgSender {
 
     /// Consumes, packs, sanity checkes and finally sends the message to the currently configured
     /// target of the SDK.
-    pub fn send(self, session: &mut Session) -> Result<(), MsgSenderError> {
+    ///
+    /// The message is also stamped with the times set on the session,
+    /// e.g. with [`Session::set_time_sequence`].
+    pub fn send(mut self, session: &mut Session) -> Result<(), MsgSenderError> {
         if !session.is_enabled() {
             return Ok(()); // silently drop the message
         }
 
+        // The times of the message itself take precedence over those of the session:
+        if !self.timeless {
+            self.timepoint = session
+                .time()
+                .clone()
+                .into_iter()
+                .chain(self.timepoint)
+                .collect();
+        }
+
         let [msg_standard, msg_transforms, msg_splats] = self.into_messages()?;
 
         if let Some(msg_transforms) = msg_transforms {
@@ -444,6 +471,36 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn point_cloud() -> Result<(), MsgSenderError> {
+        let point_cloud = PointCloud::new([[0.0_f32, 1.0, 2.0], [3.0, 4.0, 5.0]])
+            .with_radii([0.5, 1.0])
+            .unwrap();
+
+        let [standard, _, _] = MsgSender::new("some/path")
+            .with_point_cloud(&point_cloud)?
+            .into_messages()
+            .unwrap();
+
+        let standard = standard.unwrap();
+        for component in [components::Point3D::name(), components::Radius::name()] {
+            let idx = standard.find_component(&component).unwrap();
+            assert_eq!(standard.components[idx].num_instances(0), Some(2));
+        }
+        assert!(standard
+            .find_component(&components::ColorRGBA::name())
+            .is_none());
+
+        // Must match the other components:
+        let labels = vec![components::Label("label".into())];
+        assert!(MsgSender::new("some/path")
+            .with_component(&labels)?
+            .with_point_cloud(&point_cloud)
+            .is_err());
+
+        Ok(())
+    }
+
     #[test]
     fn timepoint_last_write_wins() {
         let my_timeline = Timeline::new("my_timeline", time::TimeType::Sequence);
@@ -457,6 +514,49 @@ mod tests {
         );
     }
 
+    #[test]
+    fn timepoint_from_session() -> Result<(), MsgSenderError> {
+        let mut session = Session::init("test", true);
+        let sink = crate::test::MemorySink::default();
+        session.log_to_memory(sink.clone());
+
+        let frame_nr = Timeline::new_sequence("frame_nr");
+        let sim_time = Timeline::new_temporal("sim_time");
+        session.set_time_sequence("frame_nr", Some(42));
+        session.set_time_seconds("sim_time", Some(1.5));
+
+        MsgSender::new("some/path")
+            .with_component(&vec![components::Label("label1".into())])?
+            .send(&mut session)?;
+        MsgSender::new("some/path")
+            .with_component(&vec![components::Label("label2".into())])?
+            .with_time(frame_nr, 43)
+            .send(&mut session)?;
+        session.set_time_seconds("sim_time", None);
+        MsgSender::new("some/path")
+            .with_component(&vec![components::Label("label3".into())])?
+            .send(&mut session)?;
+
+        let bundles = sink.bundles();
+        assert_eq!(bundles.len(), 3);
+
+        let time_point = &bundles[0].time_point;
+        assert_eq!(time_point.get(&frame_nr), Some(&TimeInt::from(42)));
+        assert_eq!(
+            time_point.get(&sim_time),
+            Some(&Time::from_seconds_since_epoch(1.5).into())
+        );
+        assert!(time_point.get(&Timeline::log_time()).is_some());
+
+        assert_eq!(
+            bundles[1].time_point.get(&frame_nr),
+            Some(&TimeInt::from(43))
+        );
+        assert_eq!(bundles[2].time_point.get(&sim_time), None);
+
+        Ok(())
+    }
+
     #[test]
     fn timepoint_timeless() -> Result<(), MsgSenderError> {
         let my_timeline = Timeline::new("my_timeline", time::TimeType::Sequence);
@@ -1,8 +1,8 @@
 use std::net::SocketAddr;
 
 use re_log_types::{
-    ApplicationId, BeginRecordingMsg, LogMsg, MsgId, PathOp, RecordingId, RecordingInfo,
-    RecordingSource, Time, TimePoint,
+    ApplicationId, BeginRecordingMsg, BlueprintOp, LogMsg, MsgId, PathOp, RecordingId,
+    RecordingInfo, RecordingSource, Time, TimeInt, TimePoint, Timeline,
 };
 
 /// This is the main object you need to create to use the Rerun SDK.
@@ -21,11 +21,27 @@ pub struct Session {
 
     sender: Sender,
 
+    /// Run on every message before it is handed to the [`Sender`].
+    middlewares: Vec<Box<dyn crate::Middleware>>,
+
+    /// Also gets every message, in addition to the [`Sender`].
+    #[cfg(feature = "save")]
+    #[cfg(not(target_arch = "wasm32"))]
+    file_sinks: Vec<crate::file_sink::FileSink>,
+
+    /// So that we can start new file sinks with it.
+    #[cfg(feature = "save")]
+    #[cfg(not(target_arch = "wasm32"))]
+    last_begin_recording_msg: Option<LogMsg>,
+
     application_id: Option<ApplicationId>,
     recording_id: Option<RecordingId>,
     is_official_example: Option<bool>,
 
     has_sent_begin_recording_msg: bool,
+
+    /// Added to every message sent with [`crate::MsgSender`], see [`Self::set_time_sequence`].
+    time: TimePoint,
 }
 
 impl Default for Session {
@@ -111,13 +127,30 @@ impl Session {
             tokio_rt: tokio::runtime::Runtime::new().unwrap(),
 
             sender: Default::default(),
+            middlewares: Vec::new(),
+            #[cfg(feature = "save")]
+            #[cfg(not(target_arch = "wasm32"))]
+            file_sinks: Vec::new(),
+            #[cfg(feature = "save")]
+            #[cfg(not(target_arch = "wasm32"))]
+            last_begin_recording_msg: None,
             application_id: None,
             recording_id: None,
             is_official_example: None,
             has_sent_begin_recording_msg: false,
+            time: TimePoint::default(),
         }
     }
 
+    /// Run every logged message through this [`crate::Middleware`] before it is sent,
+    /// after any previously added ones.
+    ///
+    /// Add middlewares before logging anything, so that they see all messages.
+    pub fn with_middleware(mut self, middleware: impl crate::Middleware + 'static) -> Self {
+        self.middlewares.push(Box::new(middleware));
+        self
+    }
+
     /// Check if logging is enabled on this `Session`.
     pub fn is_enabled(&self) -> bool {
         self.enabled
@@ -169,6 +202,53 @@ impl Session {
         self.recording_source = recording_source;
     }
 
+    /// Stamp everything sent with [`crate::MsgSender`] from now on with this sequence number
+    /// (e.g. a frame index) on the given timeline, in addition to the log time.
+    ///
+    /// Call it for several timelines to log on all of them, e.g. `frame_nr` and `sim_time`.
+    /// Times set with [`crate::MsgSender::with_time`] take precedence.
+    /// `None` stops stamping on that timeline.
+    pub fn set_time_sequence(&mut self, timeline: &str, sequence: Option<i64>) {
+        self.set_time(
+            Timeline::new_sequence(timeline),
+            sequence.map(TimeInt::from),
+        );
+    }
+
+    /// Like [`Self::set_time_sequence`], but for a time in seconds, e.g. the time of a simulation.
+    pub fn set_time_seconds(&mut self, timeline: &str, seconds: Option<f64>) {
+        self.set_time(
+            Timeline::new_temporal(timeline),
+            seconds.map(|seconds| Time::from_seconds_since_epoch(seconds).into()),
+        );
+    }
+
+    /// Like [`Self::set_time_sequence`], but for a time in nanoseconds.
+    pub fn set_time_nanos(&mut self, timeline: &str, nanos: Option<i64>) {
+        self.set_time(
+            Timeline::new_temporal(timeline),
+            nanos.map(|nanos| Time::from_ns_since_epoch(nanos).into()),
+        );
+    }
+
+    fn set_time(&mut self, timeline: Timeline, time: Option<TimeInt>) {
+        if let Some(time) = time {
+            self.time.insert(timeline, time);
+        } else {
+            self.time.remove(&timeline);
+        }
+    }
+
+    /// Stop stamping messages with the times set with e.g. [`Self::set_time_sequence`].
+    pub fn reset_time(&mut self) {
+        self.time = TimePoint::default();
+    }
+
+    /// The times set with e.g. [`Self::set_time_sequence`].
+    pub fn time(&self) -> &TimePoint {
+        &self.time
+    }
+
     /// Send log data to a remote server.
     ///
     /// Send all currently buffered messages.
@@ -193,6 +273,10 @@ impl Session {
                 }
                 self.sender = Sender::Remote(client);
             }
+            #[cfg(any(test, feature = "test_utils"))]
+            Sender::Memory(_) => {
+                self.sender = Sender::Remote(re_sdk_comms::Client::new(addr));
+            }
 
             #[cfg(feature = "re_viewer")]
             Sender::NativeViewer(_) => {}
@@ -258,6 +342,22 @@ impl Session {
         }
     }
 
+    /// Keep all log messages in `sink`, to check in tests what was logged.
+    ///
+    /// See [`crate::test`].
+    #[cfg(any(test, feature = "test_utils"))]
+    pub fn log_to_memory(&mut self, sink: crate::test::MemorySink) {
+        if !self.enabled {
+            re_log::debug!("Rerun disabled - call to log_to_memory() ignored");
+            return;
+        }
+
+        for msg in self.drain_log_messages_buffer() {
+            sink.send(msg);
+        }
+        self.sender = Sender::Memory(sink);
+    }
+
     /// Are we streaming log messages over TCP?
     ///
     /// Returns true after a call to [`Self::connect`].
@@ -270,11 +370,18 @@ impl Session {
         matches!(&self.sender, &Sender::Remote(_))
     }
 
-    /// Wait until all logged data have been sent to the remove server (if any).
+    /// Wait until all logged data have been sent to the remove server (if any),
+    /// and written to the files of [`Self::save_while_logging`].
     pub fn flush(&mut self) {
         if let Sender::Remote(sender) = &mut self.sender {
             sender.flush();
         }
+
+        #[cfg(feature = "save")]
+        #[cfg(not(target_arch = "wasm32"))]
+        for file_sink in &self.file_sinks {
+            file_sink.flush();
+        }
     }
 
     /// If the tcp session is disconnected, allow it to quit early and drop unsent messages
@@ -291,6 +398,9 @@ impl Session {
 
             Sender::Buffered(log_messages) => std::mem::take(log_messages),
 
+            #[cfg(any(test, feature = "test_utils"))]
+            Sender::Memory(_) => vec![],
+
             #[cfg(feature = "re_viewer")]
             Sender::NativeViewer(_) => vec![],
 
@@ -321,7 +431,7 @@ impl Session {
                     recording_id
                 );
 
-                self.sender.send(
+                self.send_through_middlewares(
                     BeginRecordingMsg {
                         msg_id: MsgId::random(),
                         info: RecordingInfo {
@@ -338,6 +448,28 @@ impl Session {
             }
         }
 
+        self.send_through_middlewares(log_msg);
+    }
+
+    fn send_through_middlewares(&mut self, mut log_msg: LogMsg) {
+        for middleware in &mut self.middlewares {
+            log_msg = match middleware.process(log_msg) {
+                Some(log_msg) => log_msg,
+                None => return,
+            };
+        }
+
+        #[cfg(feature = "save")]
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            if matches!(log_msg, LogMsg::BeginRecordingMsg(_)) {
+                self.last_begin_recording_msg = Some(log_msg.clone());
+            }
+            for file_sink in &self.file_sinks {
+                file_sink.send(log_msg.clone());
+            }
+        }
+
         self.sender.send(log_msg);
     }
 
@@ -350,6 +482,144 @@ impl Session {
         }));
     }
 
+    /// Send a [`BlueprintOp`], telling the viewer how to lay out the logged data.
+    ///
+    /// This lets you define the resulting dashboard in code, e.g.:
+    ///
+    /// ```
+    /// # use re_sdk::blueprint::{BlueprintOp, SpaceViewDescription, SpaceViewKind};
+    /// # let mut session = re_sdk::Session::init("dashboard", false);
+    /// session.send_blueprint_op(BlueprintOp::ClearSpaceViews);
+    /// session.send_blueprint_op(BlueprintOp::AddSpaceView(SpaceViewDescription::new(
+    ///     "Robot",
+    ///     SpaceViewKind::Spatial,
+    ///     "world",
+    /// )));
+    /// session.send_blueprint_op(BlueprintOp::AddSpaceView(
+    ///     SpaceViewDescription::new("Wheel speeds", SpaceViewKind::TimeSeries, "wheels")
+    ///         .with_entities(["wheels/left", "wheels/right"]),
+    /// ));
+    /// ```
+    pub fn send_blueprint_op(&mut self, op: BlueprintOp) {
+        self.send(LogMsg::BlueprintMsg(re_log_types::BlueprintMsg {
+            msg_id: MsgId::random(),
+            op,
+        }));
+    }
+
+    /// Loads an existing rrd file into this session, so that anything logged afterwards ends up
+    /// in the same recording as the loaded data.
+    ///
+    /// This is useful for analysis programs that compute derived data (smoothed trajectories,
+    /// error metrics, …) from an existing recording: load the source recording, log the derived
+    /// data under new entity paths, then [`Self::save`] the result to a new file.
+    ///
+    /// The [`ApplicationId`] and [`RecordingId`] of this session are taken from the file.
+    ///
+    /// All loaded messages are forwarded to the current sender, and also returned so that
+    /// they can be used as input for the derivation.
+    #[cfg(feature = "load")]
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load_rrd(&mut self, path: impl AsRef<std::path::Path>) -> anyhow::Result<Vec<LogMsg>> {
+        use anyhow::Context as _;
+
+        let path = path.as_ref();
+
+        if !self.enabled {
+            re_log::debug!("Rerun disabled - call to load_rrd() ignored");
+            return Ok(vec![]);
+        }
+
+        re_log::debug!("Loading file from {path:?}…");
+
+        let file = std::fs::File::open(path).with_context(|| format!("Failed to open {path:?}"))?;
+        let decoder = re_log_types::encoding::Decoder::new(file)
+            .with_context(|| format!("Failed to decode {path:?}"))?;
+
+        let mut log_messages = Vec::new();
+        for msg in decoder {
+            let msg = msg.with_context(|| format!("Failed to decode {path:?}"))?;
+
+            if let LogMsg::BeginRecordingMsg(msg) = &msg {
+                let RecordingInfo {
+                    application_id,
+                    recording_id,
+                    is_official_example,
+                    started: _,
+                    recording_source,
+                } = &msg.info;
+
+                // We forward the loaded `BeginRecordingMsg` as-is, so there is no need to send
+                // a new one.
+                self.application_id = Some(application_id.clone());
+                self.recording_id = Some(*recording_id);
+                self.is_official_example = Some(*is_official_example);
+                self.recording_source = recording_source.clone();
+                self.has_sent_begin_recording_msg = true;
+            }
+
+            self.send_through_middlewares(msg.clone());
+            log_messages.push(msg);
+        }
+
+        re_log::debug!("Loaded {} messages from {path:?}", log_messages.len());
+
+        Ok(log_messages)
+    }
+
+    /// Write everything logged from now on to an rrd file, in addition to wherever it is sent.
+    ///
+    /// This works together with [`Self::connect`] and the other ways of viewing the data live,
+    /// so that you always have a local copy of the data.
+    /// The file is written on a separate thread with its own buffer,
+    /// and any failure to write it is logged without affecting the other destinations.
+    ///
+    /// You can call this several times to save to several files.
+    /// The files are completed on [`Self::stop_saving_while_logging`],
+    /// or when the [`Session`] is dropped.
+    #[cfg(feature = "save")]
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save_while_logging(
+        &mut self,
+        path: impl Into<std::path::PathBuf>,
+    ) -> anyhow::Result<()> {
+        self.save_while_logging_in_parts(path, Default::default())
+    }
+
+    /// Like [`Self::save_while_logging`], but continues in a new file every now and then,
+    /// as decided by `split`, e.g. [`crate::FileSplit::every_minutes`].
+    ///
+    /// `capture.rrd` is saved as `capture.0000.rrd`, `capture.0001.rrd`, etc.
+    /// Each part starts with the timeless data and the layout logged so far,
+    /// so each part can be viewed on its own.
+    /// Open them all at once to see the whole recording.
+    #[cfg(feature = "save")]
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save_while_logging_in_parts(
+        &mut self,
+        path: impl Into<std::path::PathBuf>,
+        split: crate::FileSplit,
+    ) -> anyhow::Result<()> {
+        if !self.enabled {
+            re_log::debug!("Rerun disabled - call to save_while_logging_in_parts() ignored");
+            return Ok(());
+        }
+
+        let file_sink = crate::file_sink::FileSink::new(path, split)?;
+        if let Some(begin_recording_msg) = &self.last_begin_recording_msg {
+            file_sink.send(begin_recording_msg.clone());
+        }
+        self.file_sinks.push(file_sink);
+        Ok(())
+    }
+
+    /// Complete and close all files of [`Self::save_while_logging`].
+    #[cfg(feature = "save")]
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn stop_saving_while_logging(&mut self) {
+        self.file_sinks.clear();
+    }
+
     /// Drains all pending log messages and saves them to disk into an rrd file.
     // TODO(cmc): We're gonna have to properly type all these errors all the way up to the encoding
     // methods in re_log_types at some point...
@@ -464,8 +734,12 @@ impl Session {
         // to spawn the viewer in place and migrate the user callback to a new thread.
         re_viewer::run_native_app(Box::new(move |cc, re_ui| {
             // TODO(cmc): it'd be nice to centralize all the UI wake up logic somewhere.
-            let rx = re_viewer::wake_up_ui_thread_on_each_msg(rx, cc.egui_ctx.clone());
             let startup_options = re_viewer::StartupOptions::default();
+            let rx = re_viewer::wake_up_ui_thread_on_each_msg(
+                rx,
+                cc.egui_ctx.clone(),
+                startup_options.min_repaint_interval(),
+            );
             Box::new(re_viewer::App::from_receiver(
                 app_env,
                 startup_options,
@@ -483,6 +757,10 @@ enum Sender {
     #[allow(unused)] // only used with `#[cfg(feature = "re_viewer")]`
     Buffered(Vec<LogMsg>),
 
+    /// For tests, see [`crate::test`].
+    #[cfg(any(test, feature = "test_utils"))]
+    Memory(crate::test::MemorySink),
+
     #[cfg(feature = "re_viewer")]
     NativeViewer(re_smart_channel::Sender<LogMsg>),
 
@@ -505,6 +783,8 @@ impl Sender {
         match self {
             Self::Remote(client) => client.send(msg),
             Self::Buffered(buffer) => buffer.push(msg),
+            #[cfg(any(test, feature = "test_utils"))]
+            Self::Memory(sink) => sink.send(msg),
 
             #[cfg(feature = "re_viewer")]
             Self::NativeViewer(sender) => {
@@ -0,0 +1,202 @@
+//! Helpers for testing that your code logs what you expect, without a viewer.
+//!
+//! Requires the `test_utils` feature.
+//!
+//! Log to a [`MemorySink`] to inspect the messages in-process,
+//! or connect to a [`MockServer`] to also exercise the TCP connection:
+//!
+//! ```
+//! # use re_sdk::{components::Point3D, test::MemorySink, time::Timeline, Component as _, MsgSender, Session};
+//! let mut session = Session::init("my_app", true);
+//! let sink = MemorySink::default();
+//! session.log_to_memory(sink.clone());
+//!
+//! MsgSender::new("world/points")
+//!     .with_time(Timeline::new_sequence("frame"), 42_i64)
+//!     .with_component(&[Point3D::new(1.0, 2.0, 3.0)])?
+//!     .send(&mut session)?;
+//!
+//! let bundles = sink.bundles();
+//! assert_eq!(bundles.len(), 1);
+//! assert_eq!(bundles[0].entity_path, "world/points".into());
+//! assert!(bundles[0].find_component(&Point3D::name()).is_some());
+//! # Ok::<(), re_sdk::MsgSenderError>(())
+//! ```
+
+use std::sync::{Arc, Mutex};
+
+use re_log_types::{msg_bundle::MsgBundle, LogMsg};
+
+/// Keeps every [`LogMsg`] of a [`crate::Session`] in memory.
+///
+/// Clones share the same messages, so keep one to inspect what was logged after handing
+/// another one to [`crate::Session::log_to_memory`].
+#[derive(Clone, Default)]
+pub struct MemorySink {
+    msgs: Arc<Mutex<Vec<LogMsg>>>,
+}
+
+impl MemorySink {
+    pub(crate) fn send(&self, msg: LogMsg) {
+        self.msgs.lock().unwrap().push(msg);
+    }
+
+    /// Everything logged so far, in order.
+    pub fn msgs(&self) -> Vec<LogMsg> {
+        self.msgs.lock().unwrap().clone()
+    }
+
+    /// Everything logged so far, emptying the sink.
+    pub fn take(&self) -> Vec<LogMsg> {
+        std::mem::take(&mut *self.msgs.lock().unwrap())
+    }
+
+    /// The logged data (i.e. the [`LogMsg::ArrowMsg`]es), with their entity paths,
+    /// times and components.
+    ///
+    /// Panics if a message can't be decoded, which is a bug in the SDK.
+    pub fn bundles(&self) -> Vec<MsgBundle> {
+        bundles(&self.msgs.lock().unwrap())
+    }
+}
+
+/// A Rerun Server on localhost that collects what is sent to it, instead of showing it.
+///
+/// ```no_run
+/// # use re_sdk::{test::MockServer, Session};
+/// let server = MockServer::new()?;
+/// let mut session = Session::init("my_app", true);
+/// session.connect(server.addr());
+/// // … log something …
+/// session.flush();
+/// let msgs = server.receive(std::time::Duration::from_millis(100));
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[cfg(all(feature = "test_utils", not(target_arch = "wasm32")))]
+pub struct MockServer {
+    addr: std::net::SocketAddr,
+    rx: re_smart_channel::Receiver<LogMsg>,
+    shutdown: Arc<std::sync::atomic::AtomicBool>,
+    server_thread: Option<std::thread::JoinHandle<()>>,
+}
+
+#[cfg(all(feature = "test_utils", not(target_arch = "wasm32")))]
+impl MockServer {
+    /// Listen on a free port.
+    pub fn new() -> anyhow::Result<Self> {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+        let shutdown = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let (rx, server_thread) = re_sdk_comms::serve_listener(
+            listener,
+            re_sdk_comms::ServerOptions {
+                quiet: true,
+                ..Default::default()
+            },
+            Default::default(),
+            shutdown.clone(),
+        )?;
+        Ok(Self {
+            addr,
+            rx,
+            shutdown,
+            server_thread: Some(server_thread),
+        })
+    }
+
+    /// Pass this to [`crate::Session::connect`].
+    pub fn addr(&self) -> std::net::SocketAddr {
+        self.addr
+    }
+
+    /// Everything received since the last call, waiting until nothing has arrived for `timeout`.
+    ///
+    /// Call [`crate::Session::flush`] first, so that all messages are on their way.
+    pub fn receive(&self, timeout: std::time::Duration) -> Vec<LogMsg> {
+        let mut msgs = vec![];
+        let mut last_received = std::time::Instant::now();
+        while last_received.elapsed() < timeout {
+            if let Ok(msg) = self.rx.try_recv() {
+                msgs.push(msg);
+                last_received = std::time::Instant::now();
+            } else {
+                std::thread::sleep(std::time::Duration::from_millis(1));
+            }
+        }
+        msgs
+    }
+
+    /// Like [`Self::receive`], but only the logged data.
+    pub fn receive_bundles(&self, timeout: std::time::Duration) -> Vec<MsgBundle> {
+        bundles(&self.receive(timeout))
+    }
+
+    /// The logged data received since the last call, waiting until there are `num_bundles`
+    /// or `timeout` has passed.
+    ///
+    /// Use this rather than [`Self::receive_bundles`] when you know what to expect,
+    /// so that a slow machine doesn't make your test fail.
+    pub fn wait_for_bundles(
+        &self,
+        num_bundles: usize,
+        timeout: std::time::Duration,
+    ) -> Vec<MsgBundle> {
+        let start = std::time::Instant::now();
+        let mut received = vec![];
+        while received.len() < num_bundles && start.elapsed() < timeout {
+            if let Ok(msg) = self.rx.try_recv() {
+                received.extend(bundles(&[msg]));
+            } else {
+                std::thread::sleep(std::time::Duration::from_millis(1));
+            }
+        }
+        received
+    }
+}
+
+#[cfg(all(feature = "test_utils", not(target_arch = "wasm32")))]
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.shutdown
+            .store(true, std::sync::atomic::Ordering::Relaxed);
+        // Wake up the server thread, which is waiting for the next client.
+        if std::net::TcpStream::connect(self.addr).is_ok() {
+            if let Some(server_thread) = self.server_thread.take() {
+                server_thread.join().ok();
+            }
+        }
+    }
+}
+
+fn bundles(msgs: &[LogMsg]) -> Vec<MsgBundle> {
+    msgs.iter()
+        .filter_map(|msg| match msg {
+            LogMsg::ArrowMsg(msg) => Some(MsgBundle::try_from(msg).unwrap()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_memory_sink() {
+    use re_log_types::component_types::Point3D;
+
+    let mut session = crate::Session::init("test", true);
+    let sink = MemorySink::default();
+    session.log_to_memory(sink.clone());
+
+    crate::MsgSender::new("points")
+        .with_component(&[Point3D::new(1.0, 2.0, 3.0)])
+        .unwrap()
+        .send(&mut session)
+        .unwrap();
+
+    let msgs = sink.msgs();
+    assert!(matches!(msgs[0], LogMsg::BeginRecordingMsg(_)));
+    let bundles = sink.bundles();
+    assert_eq!(bundles.len(), 1);
+    assert_eq!(bundles[0].entity_path, "points".into());
+
+    assert_eq!(sink.take().len(), msgs.len());
+    assert!(sink.msgs().is_empty());
+}
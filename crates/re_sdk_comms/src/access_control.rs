@@ -0,0 +1,225 @@
+//! Which recordings each auth token may write and read, so that many teams can share a server.
+//!
+//! Configured in a TOML file like this:
+//!
+//! ```toml
+//! [[token]]
+//! name = "team-a"
+//! secret = "correct-horse-battery-staple"
+//! write = ["team_a_*"]
+//! read = ["team_a_*", "shared_*"]
+//! ```
+//!
+//! Recordings are matched by their application id, where `*` matches any number of characters.
+//! Writing doesn't imply reading: list the patterns in both if a team should see its own data.
+//! SDK:s present their token in the [`crate::AUTH_TOKEN_ENV_VAR`] environment variable,
+//! and viewers when connecting to the websocket server.
+//! Without a known token, nothing may be written or read.
+//!
+//! The server merges the messages of all clients into one stream, so it remembers which
+//! recording each message came from, for whoever decides what the viewers may read
+//! (see [`AccessControl::take_application_id_of`]).
+
+use std::{collections::VecDeque, path::Path, sync::Arc};
+
+use anyhow::Context as _;
+use parking_lot::Mutex;
+
+use re_log_types::{ApplicationId, MsgId};
+
+/// How many messages we remember the recording of, in case no one takes them.
+///
+/// Messages that are forgotten can't be read by anyone with a restricted token.
+const MAX_TAGGED_MSGS: usize = 100_000;
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    #[serde(default)]
+    token: Vec<TokenConfig>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TokenConfig {
+    name: String,
+    secret: String,
+    #[serde(default)]
+    write: Vec<String>,
+    #[serde(default)]
+    read: Vec<String>,
+}
+
+/// The recordings each auth token may write and read, see the module docs.
+///
+/// Cheap to clone. Read once at startup.
+#[derive(Clone)]
+pub struct AccessControl {
+    tokens: Arc<[TokenConfig]>,
+    tagged_msgs: Arc<Mutex<TaggedMsgs>>,
+}
+
+/// The application of the recording each forwarded message is part of.
+#[derive(Default)]
+struct TaggedMsgs {
+    application_ids: ahash::HashMap<MsgId, ApplicationId>,
+
+    /// Oldest first. May contain messages that have already been taken.
+    order: VecDeque<MsgId>,
+}
+
+impl std::fmt::Debug for AccessControl {
+    /// Without the secrets.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.tokens.iter().map(|token| &token.name))
+            .finish()
+    }
+}
+
+impl AccessControl {
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let contents =
+            std::fs::read_to_string(path).with_context(|| format!("Failed to read {path:?}"))?;
+        let access_control =
+            Self::parse(&contents).with_context(|| format!("Bad access control list {path:?}"))?;
+        re_log::info!(
+            "Loaded the access control list of {} token(s) from {path:?}",
+            access_control.tokens.len()
+        );
+        Ok(access_control)
+    }
+
+    pub(crate) fn parse(toml: &str) -> anyhow::Result<Self> {
+        let config: ConfigFile = toml::from_str(toml)?;
+        for (i, token) in config.token.iter().enumerate() {
+            anyhow::ensure!(
+                !token.secret.is_empty(),
+                "The secret of {:?} is empty",
+                token.name
+            );
+            if let Some(other) = config.token[..i]
+                .iter()
+                .find(|other| other.secret == token.secret)
+            {
+                anyhow::bail!("{:?} and {:?} have the same secret", other.name, token.name);
+            }
+        }
+        Ok(Self {
+            tokens: config.token.into(),
+            tagged_msgs: Default::default(),
+        })
+    }
+
+    /// The name of the token with this secret, if it is one of ours.
+    pub fn token_name(&self, secret: &str) -> Option<&str> {
+        self.find(Some(secret)).map(|token| token.name.as_str())
+    }
+
+    /// May an SDK presenting this token log to recordings of this application?
+    pub fn may_write(&self, secret: Option<&str>, application_id: &ApplicationId) -> bool {
+        self.find(secret).map_or(false, |token| {
+            token
+                .write
+                .iter()
+                .any(|pattern| glob_matches(pattern, &application_id.0))
+        })
+    }
+
+    /// May a viewer presenting this token see recordings of this application?
+    pub fn may_read(&self, secret: Option<&str>, application_id: &ApplicationId) -> bool {
+        self.find(secret).map_or(false, |token| {
+            token
+                .read
+                .iter()
+                .any(|pattern| glob_matches(pattern, &application_id.0))
+        })
+    }
+
+    /// Remember which recording a message is part of,
+    /// before it is merged with the messages of the other clients.
+    pub(crate) fn tag(&self, msg_id: MsgId, application_id: &ApplicationId) {
+        let mut tagged = self.tagged_msgs.lock();
+        if tagged.order.len() == MAX_TAGGED_MSGS {
+            if let Some(oldest) = tagged.order.pop_front() {
+                tagged.application_ids.remove(&oldest);
+            }
+        }
+        tagged.order.push_back(msg_id);
+        tagged
+            .application_ids
+            .insert(msg_id, application_id.clone());
+    }
+
+    /// The application of the recording that this message, forwarded by the server, is part of.
+    ///
+    /// Each message can only be taken once. `None` for messages that didn't come from a client
+    /// that may write them, which no one with a restricted token may then read.
+    pub fn take_application_id_of(&self, msg_id: MsgId) -> Option<ApplicationId> {
+        self.tagged_msgs.lock().application_ids.remove(&msg_id)
+    }
+
+    fn find(&self, secret: Option<&str>) -> Option<&TokenConfig> {
+        let secret = secret?;
+        self.tokens
+            .iter()
+            .find(|token| re_ws_comms::constant_time_eq(token.secret.as_bytes(), secret.as_bytes()))
+    }
+}
+
+/// `*` matches any number of characters (including none).
+fn glob_matches(pattern: &str, text: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == text,
+        Some((prefix, rest)) => text.strip_prefix(prefix).map_or(false, |text| {
+            text.char_indices()
+                .map(|(i, _)| i)
+                .chain([text.len()])
+                .any(|i| glob_matches(rest, &text[i..]))
+        }),
+    }
+}
+
+#[test]
+fn test_access_control() {
+    assert!(glob_matches("team_a_*", "team_a_robot"));
+    assert!(glob_matches("team_a_*", "team_a_"));
+    assert!(!glob_matches("team_a_*", "team_b_robot"));
+    assert!(glob_matches("*_sim", "team_a_sim"));
+    assert!(glob_matches("*", "anything"));
+    assert!(!glob_matches("robot", "robot2"));
+
+    let access_control = AccessControl::parse(
+        r#"
+        [[token]]
+        name = "team-a"
+        secret = "aaa"
+        write = ["team_a_*"]
+        read = ["team_a_*", "shared_*"]
+
+        [[token]]
+        name = "team-b"
+        secret = "bbb"
+        write = ["team_b_*"]
+        "#,
+    )
+    .unwrap();
+
+    let robot_a = ApplicationId::from("team_a_robot");
+    let shared = ApplicationId::from("shared_map");
+    assert_eq!(access_control.token_name("bbb"), Some("team-b"));
+    assert_eq!(access_control.token_name("ccc"), None);
+    assert!(access_control.may_write(Some("aaa"), &robot_a));
+    assert!(access_control.may_read(Some("aaa"), &shared));
+    assert!(!access_control.may_write(Some("aaa"), &shared));
+    assert!(!access_control.may_write(Some("bbb"), &robot_a));
+    assert!(!access_control.may_read(Some("bbb"), &robot_a));
+    assert!(!access_control.may_write(None, &robot_a));
+    assert!(!format!("{access_control:?}").contains("aaa"));
+
+    assert!(AccessControl::parse("[[token]]\nname = \"x\"\nsecret = \"\"").is_err());
+    assert!(AccessControl::parse(
+        "[[token]]\nname = \"x\"\nsecret = \"s\"\n[[token]]\nname = \"y\"\nsecret = \"s\""
+    )
+    .is_err());
+}
@@ -0,0 +1,173 @@
+//! A structured log of what a Rerun Server received from the SDK:s, one JSON object per line.
+//!
+//! Meant for answering "where did my data go?": which clients connected, which recordings
+//! they started, how much was ingested, and whether anything was dropped on the way.
+
+use std::{io::Write as _, net::SocketAddr, path::Path, sync::Arc};
+
+use anyhow::Context as _;
+use parking_lot::Mutex;
+
+use re_log_types::{ApplicationId, RecordingId, Time};
+
+/// One line of the audit log.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct AuditEvent {
+    /// When it happened, in UTC, e.g. `2023-02-20T13:37:00.123Z`.
+    pub time: String,
+
+    /// The SDK client it is about, or the viewer for [`DropReason::SlowViewer`].
+    pub peer_addr: SocketAddr,
+
+    #[serde(flatten)]
+    pub kind: AuditEventKind,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum AuditEventKind {
+    Connected {
+        hostname: Option<String>,
+        protocol_version: u16,
+    },
+
+    RecordingStarted {
+        recording_id: RecordingId,
+        application_id: ApplicationId,
+    },
+
+    /// The first message of a recording that was dropped. The total is in [`Self::RecordingEnded`].
+    ///
+    /// Also for the first message a client sent before starting a recording that was dropped,
+    /// and whenever a viewer starts falling behind.
+    Dropping { reason: DropReason },
+
+    /// When the client started another recording, or disconnected.
+    RecordingEnded {
+        recording_id: RecordingId,
+        num_messages: u64,
+        num_bytes: u64,
+        num_dropped: u64,
+    },
+
+    /// Totals over all the recordings of the client.
+    Disconnected {
+        num_messages: u64,
+        num_bytes: u64,
+        num_dropped: u64,
+
+        /// Why the connection was closed, unless the client said goodbye.
+        error: Option<String>,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DropReason {
+    /// See [`crate::ServerOptions::max_latency_sec`].
+    Latency,
+
+    /// A large message was cancelled in the viewer.
+    Cancelled,
+
+    /// See [`crate::BandwidthLimits`].
+    Bandwidth,
+
+    /// The client's auth token may not write to the recording, see [`crate::AccessControl`].
+    Forbidden,
+
+    /// A viewer connected to the websocket server couldn't keep up,
+    /// so some of the messages weren't sent to it.
+    SlowViewer,
+}
+
+impl AuditEvent {
+    /// One line of the audit log, without the newline.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("Failed to serialize audit event")
+    }
+
+    /// The recording the event is about, if any.
+    pub fn recording_id(&self) -> Option<RecordingId> {
+        match &self.kind {
+            AuditEventKind::RecordingStarted { recording_id, .. }
+            | AuditEventKind::RecordingEnded { recording_id, .. } => Some(*recording_id),
+            AuditEventKind::Connected { .. }
+            | AuditEventKind::Dropping { .. }
+            | AuditEventKind::Disconnected { .. } => None,
+        }
+    }
+}
+
+/// Appends [`AuditEvent`]s to a file.
+///
+/// Each line is written as soon as it happens, so the log is complete even if the server crashes.
+///
+/// Cheap to clone, so that the SDK server and the websocket servers can write to the same log.
+#[derive(Clone, Debug)]
+pub struct AuditLog {
+    file: Arc<Mutex<std::fs::File>>,
+}
+
+impl AuditLog {
+    /// Appends to the file if it already exists.
+    pub fn open(path: &Path) -> anyhow::Result<Self> {
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Failed to open audit log {path:?}"))?;
+        re_log::info!("Writing an audit log of the SDK connections to {path:?}");
+        Ok(Self {
+            file: Arc::new(Mutex::new(file)),
+        })
+    }
+
+    pub fn log(&self, peer_addr: SocketAddr, kind: AuditEventKind) {
+        let event = AuditEvent {
+            time: Time::now().format_time("%Y-%m-%dT%H:%M:%S%.3fZ"),
+            peer_addr,
+            kind,
+        };
+        let mut line = event.to_json();
+        line.push('\n');
+        if let Err(err) = self.file.lock().write_all(line.as_bytes()) {
+            re_log::warn_once!("Failed to write to the audit log: {err}");
+        }
+    }
+}
+
+/// Read all events of an audit log, skipping (and warning about) lines we don't understand.
+pub fn read_audit_log(path: &Path) -> anyhow::Result<Vec<AuditEvent>> {
+    let contents =
+        std::fs::read_to_string(path).with_context(|| format!("Failed to read {path:?}"))?;
+    Ok(contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .filter_map(|(i, line)| match serde_json::from_str(line) {
+            Ok(event) => Some(event),
+            Err(err) => {
+                re_log::warn!("Skipping line {} of {path:?}: {err}", i + 1);
+                None
+            }
+        })
+        .collect())
+}
+
+#[test]
+fn test_audit_event_json() {
+    let event = AuditEvent {
+        time: "2023-02-20T13:37:00.123Z".to_owned(),
+        peer_addr: "192.168.1.42:51234".parse().unwrap(),
+        kind: AuditEventKind::Dropping {
+            reason: DropReason::Latency,
+        },
+    };
+    let json = event.to_json();
+    assert_eq!(
+        json,
+        r#"{"time":"2023-02-20T13:37:00.123Z","peer_addr":"192.168.1.42:51234","event":"dropping","reason":"latency"}"#
+    );
+    assert_eq!(serde_json::from_str::<AuditEvent>(&json).unwrap(), event);
+}
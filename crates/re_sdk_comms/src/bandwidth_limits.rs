@@ -0,0 +1,274 @@
+//! Per-entity-path bandwidth limits for a Rerun Server, e.g. so that the cameras of a robot
+//! can't crowd out everything else on a slow link.
+//!
+//! Configured in a TOML file like this, which is re-read whenever it changes:
+//!
+//! ```toml
+//! [[limit]]
+//! path = "cameras/**"
+//! max = "20 Mbps"
+//!
+//! [[limit]]
+//! path = "lidar/*/points"
+//! max = "2 MB/s"
+//! ```
+//!
+//! In a path, `*` matches one part and `**` any number of parts.
+//! Each message counts towards the first limit that matches its entity path,
+//! and all the entities of a client matching a limit share its bandwidth.
+//! Each client has its own budget, so one client going over can't starve the others.
+//! Messages over the limit are dropped rather than queued, so that the latency stays low.
+//! Timeless messages are never dropped.
+
+use std::{
+    path::{Path, PathBuf},
+    time::{Instant, SystemTime},
+};
+
+use anyhow::Context as _;
+
+use arrow2::compute::aggregate::estimated_bytes_size;
+use re_log_types::LogMsg;
+
+/// How often we check if the file has changed.
+const RELOAD_INTERVAL_SEC: f32 = 1.0;
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    #[serde(default)]
+    limit: Vec<LimitConfig>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LimitConfig {
+    path: String,
+    max: String,
+}
+
+#[derive(Clone, Debug)]
+struct Limit {
+    pattern: Vec<String>,
+    bytes_per_sec: f64,
+
+    /// How many bytes we may still send. Up to one second's worth, and negative after
+    /// a message larger than that, so that large messages get through too, just less often.
+    budget: f64,
+    last_refill: Instant,
+}
+
+impl Limit {
+    fn try_spend(&mut self, num_bytes: usize) -> bool {
+        let now = Instant::now();
+        let dt = (now - self.last_refill).as_secs_f64();
+        self.last_refill = now;
+        self.budget = (self.budget + dt * self.bytes_per_sec).min(self.bytes_per_sec);
+
+        if 0.0 < self.budget {
+            self.budget -= num_bytes as f64;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Decides which messages of a client to drop to stay within the limits of a config file.
+///
+/// See the module docs for the file format.
+///
+/// Clone it for each client: each clone spends its own budget, and checks the file for changes.
+#[derive(Clone, Debug)]
+pub struct BandwidthLimits {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    last_checked: Instant,
+    limits: Vec<Limit>,
+}
+
+impl BandwidthLimits {
+    /// Fails if the file can't be read or parsed.
+    ///
+    /// If it later changes into something invalid, we warn and keep the previous limits.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let limits = read_limits(path)?;
+        re_log::info!("Loaded {} bandwidth limit(s) from {path:?}", limits.len());
+        Ok(Self {
+            path: path.to_owned(),
+            modified: modified_time(path),
+            last_checked: Instant::now(),
+            limits,
+        })
+    }
+
+    /// Returns `false` if the message is over its limit, and should be dropped.
+    pub fn should_send(&mut self, msg: &LogMsg) -> bool {
+        self.reload_if_changed();
+
+        let LogMsg::ArrowMsg(arrow_msg) = msg else {
+            return true; // Recording info, blueprints etc are tiny, and important.
+        };
+        let Some(entity_path) = arrow_msg.entity_path_str() else {
+            return true;
+        };
+        let parts: Vec<&str> = split_path(entity_path).collect();
+        let limit = self
+            .limits
+            .iter_mut()
+            .find(|limit| matches(&limit.pattern, &parts));
+        let Some(limit) = limit else {
+            return true; // Unlimited
+        };
+
+        if arrow_msg
+            .time_point()
+            .map_or(false, |time_point| time_point.is_timeless())
+        {
+            return true;
+        }
+
+        let num_bytes = arrow_msg
+            .chunk
+            .arrays()
+            .iter()
+            .map(|array| estimated_bytes_size(array.as_ref()))
+            .sum();
+        limit.try_spend(num_bytes)
+    }
+
+    fn reload_if_changed(&mut self) {
+        if self.last_checked.elapsed().as_secs_f32() < RELOAD_INTERVAL_SEC {
+            return;
+        }
+        self.last_checked = Instant::now();
+
+        let modified = modified_time(&self.path);
+        if modified == self.modified {
+            return;
+        }
+        self.modified = modified;
+
+        match read_limits(&self.path) {
+            Ok(limits) => {
+                // Once for each client:
+                re_log::debug!("Reloaded the bandwidth limits from {:?}", self.path);
+                self.limits = limits;
+            }
+            Err(err) => {
+                re_log::warn!("{err:#} - keeping the previous bandwidth limits");
+            }
+        }
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .ok()
+}
+
+fn read_limits(path: &Path) -> anyhow::Result<Vec<Limit>> {
+    let contents =
+        std::fs::read_to_string(path).with_context(|| format!("Failed to read {path:?}"))?;
+    parse_limits(&contents).with_context(|| format!("Bad bandwidth limits in {path:?}"))
+}
+
+fn parse_limits(toml: &str) -> anyhow::Result<Vec<Limit>> {
+    let config: ConfigFile = toml::from_str(toml)?;
+    let now = Instant::now();
+    config
+        .limit
+        .into_iter()
+        .map(|LimitConfig { path, max }| {
+            let bytes_per_sec =
+                parse_rate(&max).map_err(|err| anyhow::anyhow!("{path:?}: {err}"))?;
+            Ok(Limit {
+                pattern: split_path(&path).map(ToOwned::to_owned).collect(),
+                bytes_per_sec,
+                budget: bytes_per_sec,
+                last_refill: now,
+            })
+        })
+        .collect()
+}
+
+fn split_path(path: &str) -> impl Iterator<Item = &str> {
+    path.split('/').filter(|part| !part.is_empty())
+}
+
+/// `*` matches one part of the path, and `**` any number of parts (including none).
+fn matches(pattern: &[String], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((first, rest)) if first == "**" => {
+            (0..=path.len()).any(|skipped| matches(rest, &path[skipped..]))
+        }
+        Some((first, rest)) => path.split_first().map_or(false, |(part, path)| {
+            (first == "*" || first == part) && matches(rest, path)
+        }),
+    }
+}
+
+/// E.g. `20 Mbps` (bits) or `2.5 MB/s` (bytes), in bytes per second.
+fn parse_rate(rate: &str) -> Result<f64, String> {
+    let rate = rate.trim();
+    let split = rate
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(rate.len());
+    let (number, unit) = rate.split_at(split);
+    let number: f64 = number
+        .parse()
+        .map_err(|_err| format!("Expected a rate like '20 Mbps' or '2 MB/s', got {rate:?}"))?;
+
+    let bytes_per_unit = match unit.trim() {
+        "B/s" => 1.0,
+        "kB/s" => 1e3,
+        "MB/s" => 1e6,
+        "GB/s" => 1e9,
+        unit => match unit.to_lowercase().as_str() {
+            "bps" => 1.0 / 8.0,
+            "kbps" => 1e3 / 8.0,
+            "mbps" => 1e6 / 8.0,
+            "gbps" => 1e9 / 8.0,
+            _ => return Err(format!("Unknown unit {unit:?}, expected e.g. Mbps or MB/s")),
+        },
+    };
+    Ok(number * bytes_per_unit)
+}
+
+#[test]
+fn test_bandwidth_limits() {
+    let pattern = |s: &str| split_path(s).map(ToOwned::to_owned).collect::<Vec<_>>();
+    let path = |s| split_path(s).collect::<Vec<_>>();
+    assert!(matches(&pattern("cameras/**"), &path("cameras")));
+    assert!(matches(&pattern("cameras/**"), &path("cameras/front/rgb")));
+    assert!(!matches(&pattern("cameras/**"), &path("lidar/front")));
+    assert!(matches(
+        &pattern("lidar/*/points"),
+        &path("/lidar/front/points")
+    ));
+    assert!(!matches(&pattern("lidar/*/points"), &path("lidar/points")));
+    assert!(matches(&pattern("**/rgb"), &path("cameras/front/rgb")));
+
+    assert_eq!(parse_rate("20 Mbps"), Ok(2.5e6));
+    assert_eq!(parse_rate("2.5MB/s"), Ok(2.5e6));
+    assert!(parse_rate("20 parsecs").is_err());
+
+    let limits = parse_limits(
+        r#"
+        [[limit]]
+        path = "cameras/**"
+        max = "8 kbps"
+        "#,
+    )
+    .unwrap();
+    assert_eq!(limits.len(), 1);
+    assert_eq!(limits[0].bytes_per_sec, 1000.0);
+    assert!(parse_limits("[[limit]]\npath = \"x\"\nmax = \"fast\"").is_err());
+
+    // The first message may exceed the budget, but then we drop until it has refilled:
+    let mut limit = limits.into_iter().next().unwrap();
+    assert!(limit.try_spend(5000));
+    assert!(!limit.try_spend(10));
+}
@@ -1,4 +1,8 @@
-use std::{net::SocketAddr, thread::JoinHandle};
+use std::{
+    net::SocketAddr,
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
 
 use crossbeam::channel::{select, Receiver, Sender};
 
@@ -24,6 +28,36 @@ enum InterruptMsg {
     Quit,
 }
 
+/// When the [`Client`] sends the messages it has collected so far, as one packet.
+///
+/// Sending many small messages one by one is dominated by the per-packet overhead,
+/// so we collect them for a little while first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BatchThresholds {
+    /// Send once the collected messages are this many bytes.
+    pub max_bytes: u64,
+
+    /// Send messages at most this long after they were logged.
+    pub max_delay: Duration,
+}
+
+impl Default for BatchThresholds {
+    fn default() -> Self {
+        Self {
+            max_bytes: 1024 * 1024,
+            max_delay: Duration::from_millis(10),
+        }
+    }
+}
+
+impl BatchThresholds {
+    /// Send every message as soon as it is logged.
+    pub const NO_BATCHING: Self = Self {
+        max_bytes: 0,
+        max_delay: Duration::ZERO,
+    };
+}
+
 enum MsgMsg {
     LogMsg(LogMsg),
     SetAddr(SocketAddr),
@@ -59,6 +93,10 @@ impl Default for Client {
 
 impl Client {
     pub fn new(addr: SocketAddr) -> Self {
+        Self::new_with_batching(addr, BatchThresholds::default())
+    }
+
+    pub fn new_with_batching(addr: SocketAddr, batching: BatchThresholds) -> Self {
         // TODO(emilk): keep track of how much memory is in each pipe
         // and apply back-pressure to not use too much RAM.
         let (msg_tx, msg_rx) = crossbeam::channel::unbounded();
@@ -72,7 +110,7 @@ impl Client {
         let encode_join = std::thread::Builder::new()
             .name("msg_encoder".into())
             .spawn(move || {
-                msg_encode(&msg_rx, &msg_drop_tx, &encode_quit_rx, &packet_tx);
+                msg_encode(batching, &msg_rx, &msg_drop_tx, &encode_quit_rx, &packet_tx);
                 re_log::debug!("Shutting down msg encoder thread");
             })
             .expect("Failed to spawn thread");
@@ -181,34 +219,68 @@ fn msg_drop(msg_drop_rx: &Receiver<MsgMsg>, quit_rx: &Receiver<QuitMsg>) {
 }
 
 fn msg_encode(
+    batching: BatchThresholds,
     msg_rx: &Receiver<MsgMsg>,
     msg_drop_tx: &Sender<MsgMsg>,
     quit_rx: &Receiver<QuitMsg>,
     packet_tx: &Sender<PacketMsg>,
 ) {
+    let mut batch = crate::BatchEncoder::default();
+    let mut batch_started = Instant::now();
+
+    let send_batch = |batch: &mut crate::BatchEncoder| {
+        if !batch.is_empty() {
+            let packet = batch.finish();
+            re_log::trace!("Encoded batch of size {}", packet.len());
+            packet_tx
+                .send(PacketMsg::Packet(packet))
+                .expect("tcp_sender thread should live longer");
+        }
+    };
+
     loop {
+        let batch_deadline = if batch.is_empty() {
+            crossbeam::channel::never()
+        } else {
+            crossbeam::channel::at(batch_started + batching.max_delay)
+        };
+
         select! {
             recv(msg_rx) -> msg_msg => {
                 if let Ok(msg_msg) = msg_msg {
-                    let packet_msg = match &msg_msg {
+                    match &msg_msg {
                         MsgMsg::LogMsg(log_msg) => {
-                            let packet = crate::encode_log_msg(log_msg);
-                            re_log::trace!("Encoded message of size {}", packet.len());
-                            PacketMsg::Packet(packet)
+                            if batch.is_empty() {
+                                batch_started = Instant::now();
+                            }
+                            batch.push(log_msg);
+                            if batching.max_bytes <= batch.num_bytes() as u64 {
+                                send_batch(&mut batch);
+                            }
                         }
-                        MsgMsg::SetAddr(new_addr) => PacketMsg::SetAddr(*new_addr),
-                        MsgMsg::Flush => PacketMsg::Flush,
-                    };
-
-                    packet_tx
-                        .send(packet_msg)
-                        .expect("tcp_sender thread should live longer");
+                        MsgMsg::SetAddr(new_addr) => {
+                            send_batch(&mut batch);
+                            packet_tx
+                                .send(PacketMsg::SetAddr(*new_addr))
+                                .expect("tcp_sender thread should live longer");
+                        }
+                        MsgMsg::Flush => {
+                            send_batch(&mut batch);
+                            packet_tx
+                                .send(PacketMsg::Flush)
+                                .expect("tcp_sender thread should live longer");
+                        }
+                    }
 
                     msg_drop_tx.send(msg_msg).expect("Main thread should still be alive");
                 } else {
+                    send_batch(&mut batch);
                     return; // channel has closed
                 }
             }
+            recv(batch_deadline) -> _ => {
+                send_batch(&mut batch);
+            }
             recv(quit_rx) -> _quit_msg => {
                 return;
             }
@@ -0,0 +1,318 @@
+use std::{net::SocketAddr, sync::Arc, time::Instant};
+
+use parking_lot::Mutex;
+
+use re_log_types::{ApplicationId, LogMsg, RecordingId};
+
+use crate::{
+    audit_log::{AuditEventKind, AuditLog, DropReason},
+    AccessControl, BandwidthLimits, TransferProgress,
+};
+
+/// Information about an SDK client connected to a server started with [`crate::serve_with_clients`].
+#[derive(Clone, Debug)]
+pub struct ClientInfo {
+    pub peer_addr: SocketAddr,
+
+    /// The hostname of the machine the SDK is running on.
+    ///
+    /// `None` for clients using an old protocol version, which didn't send it.
+    pub hostname: Option<String>,
+
+    /// Set once the client has started a recording.
+    pub application_id: Option<ApplicationId>,
+
+    pub connected_at: Instant,
+
+    /// Total number of messages received from this client.
+    pub num_messages: u64,
+
+    /// Total size of the messages received from this client, as sent over the network.
+    pub num_bytes: u64,
+
+    /// Messages that were received but not passed on, e.g. because of `--drop-at-latency`.
+    pub num_dropped: u64,
+
+    pub last_message_at: Option<Instant>,
+
+    /// Messages per second, averaged over the last second or so.
+    pub message_rate: f32,
+
+    /// Set while we are receiving a message large enough to be sent in chunks.
+    pub incoming_transfer: Option<TransferProgress>,
+}
+
+impl ClientInfo {
+    /// Like [`Self::message_rate`], but zero if the client has gone quiet.
+    pub fn current_message_rate(&self) -> f32 {
+        match self.last_message_at {
+            Some(last_message_at) if last_message_at.elapsed().as_secs_f32() < 2.0 => {
+                self.message_rate
+            }
+            _ => 0.0,
+        }
+    }
+}
+
+/// The SDK clients currently connected to a server.
+///
+/// Cheap to clone, and shared between the server and whoever wants to show or control the clients.
+#[derive(Clone, Default)]
+pub struct ConnectedClients {
+    clients: Arc<Mutex<Vec<ConnectedClient>>>,
+    audit_log: Option<AuditLog>,
+    access_control: Option<AccessControl>,
+    bandwidth_limits: Option<BandwidthLimits>,
+}
+
+struct ConnectedClient {
+    info: ClientInfo,
+
+    /// Used to close the connection from the outside.
+    stream: std::net::TcpStream,
+
+    rate_window_start: Instant,
+    num_messages_in_rate_window: u64,
+
+    /// Skip the rest of the message currently being received.
+    cancel_transfer: bool,
+
+    /// The recording the client is currently sending, for the audit log.
+    recording: Option<RecordingStats>,
+}
+
+struct RecordingStats {
+    recording_id: RecordingId,
+    num_messages: u64,
+    num_bytes: u64,
+    num_dropped: u64,
+}
+
+impl RecordingStats {
+    fn ended(&self) -> AuditEventKind {
+        AuditEventKind::RecordingEnded {
+            recording_id: self.recording_id,
+            num_messages: self.num_messages,
+            num_bytes: self.num_bytes,
+            num_dropped: self.num_dropped,
+        }
+    }
+}
+
+impl ConnectedClients {
+    /// Write what the clients do to `audit_log`.
+    pub fn with_audit_log(mut self, audit_log: AuditLog) -> Self {
+        self.audit_log = Some(audit_log);
+        self
+    }
+
+    /// Only accept the recordings that the auth token of each client may write.
+    pub fn with_access_control(mut self, access_control: AccessControl) -> Self {
+        self.access_control = Some(access_control);
+        self
+    }
+
+    /// Drop the messages of entities that use more than their share of the bandwidth.
+    ///
+    /// Each client gets its own budget.
+    pub fn with_bandwidth_limits(mut self, bandwidth_limits: BandwidthLimits) -> Self {
+        self.bandwidth_limits = Some(bandwidth_limits);
+        self
+    }
+
+    pub(crate) fn access_control(&self) -> Option<&AccessControl> {
+        self.access_control.as_ref()
+    }
+
+    /// The limits for a newly connected client, with a budget of its own.
+    pub(crate) fn bandwidth_limits(&self) -> Option<BandwidthLimits> {
+        self.bandwidth_limits.clone()
+    }
+
+    /// A snapshot of all the currently connected clients, in the order they connected.
+    pub fn infos(&self) -> Vec<ClientInfo> {
+        self.clients
+            .lock()
+            .iter()
+            .map(|client| client.info.clone())
+            .collect()
+    }
+
+    /// Close the connection to the client at this address.
+    ///
+    /// The client will see this as a network error.
+    pub fn disconnect(&self, peer_addr: SocketAddr) {
+        for client in self.clients.lock().iter() {
+            if client.info.peer_addr == peer_addr {
+                re_log::info!("Disconnecting SDK client at {peer_addr}");
+                client.stream.shutdown(std::net::Shutdown::Both).ok();
+            }
+        }
+    }
+
+    /// Stop receiving the large message that this client is currently sending,
+    /// without disconnecting it. The message is dropped.
+    pub fn cancel_transfer(&self, peer_addr: SocketAddr) {
+        for client in self.clients.lock().iter_mut() {
+            if client.info.peer_addr == peer_addr && client.info.incoming_transfer.is_some() {
+                re_log::info!("Cancelling incoming message from SDK client at {peer_addr}");
+                client.cancel_transfer = true;
+            }
+        }
+    }
+
+    fn audit(&self, peer_addr: SocketAddr, events: impl IntoIterator<Item = AuditEventKind>) {
+        if let Some(audit_log) = &self.audit_log {
+            for event in events {
+                audit_log.log(peer_addr, event);
+            }
+        }
+    }
+
+    pub(crate) fn add(
+        &self,
+        peer_addr: SocketAddr,
+        hostname: Option<String>,
+        protocol_version: u16,
+        stream: std::net::TcpStream,
+    ) {
+        let now = Instant::now();
+        self.clients.lock().push(ConnectedClient {
+            info: ClientInfo {
+                peer_addr,
+                hostname: hostname.clone(),
+                application_id: None,
+                connected_at: now,
+                num_messages: 0,
+                num_bytes: 0,
+                num_dropped: 0,
+                last_message_at: None,
+                message_rate: 0.0,
+                incoming_transfer: None,
+            },
+            stream,
+            rate_window_start: now,
+            num_messages_in_rate_window: 0,
+            cancel_transfer: false,
+            recording: None,
+        });
+        self.audit(
+            peer_addr,
+            [AuditEventKind::Connected {
+                hostname,
+                protocol_version,
+            }],
+        );
+    }
+
+    /// `error` is why the connection was closed, unless the client said goodbye.
+    pub(crate) fn remove(&self, peer_addr: SocketAddr, error: Option<String>) {
+        let mut events = vec![];
+        self.clients.lock().retain(|client| {
+            if client.info.peer_addr != peer_addr {
+                return true;
+            }
+            events.extend(client.recording.as_ref().map(RecordingStats::ended));
+            events.push(AuditEventKind::Disconnected {
+                num_messages: client.info.num_messages,
+                num_bytes: client.info.num_bytes,
+                num_dropped: client.info.num_dropped,
+                error: error.clone(),
+            });
+            false
+        });
+        self.audit(peer_addr, events);
+    }
+
+    /// Returns `false` if the transfer has been cancelled with [`Self::cancel_transfer`].
+    pub(crate) fn on_transfer_progress(
+        &self,
+        peer_addr: SocketAddr,
+        progress: TransferProgress,
+    ) -> bool {
+        let mut clients = self.clients.lock();
+        if let Some(client) = clients.iter_mut().find(|c| c.info.peer_addr == peer_addr) {
+            client.info.incoming_transfer = Some(progress);
+            !client.cancel_transfer
+        } else {
+            true
+        }
+    }
+
+    pub(crate) fn on_transfer_done(&self, peer_addr: SocketAddr) {
+        let mut clients = self.clients.lock();
+        if let Some(client) = clients.iter_mut().find(|c| c.info.peer_addr == peer_addr) {
+            client.info.incoming_transfer = None;
+            client.cancel_transfer = false;
+        }
+    }
+
+    pub(crate) fn on_dropped(&self, peer_addr: SocketAddr, reason: DropReason) {
+        let mut events = vec![];
+        let mut clients = self.clients.lock();
+        if let Some(client) = clients.iter_mut().find(|c| c.info.peer_addr == peer_addr) {
+            let is_first = match &mut client.recording {
+                Some(recording) => {
+                    recording.num_dropped += 1;
+                    recording.num_dropped == 1
+                }
+                // Before the client has started a recording:
+                None => client.info.num_dropped == 0,
+            };
+            if is_first {
+                events.push(AuditEventKind::Dropping { reason });
+            }
+            client.info.num_dropped += 1;
+        }
+        drop(clients);
+        self.audit(peer_addr, events);
+    }
+
+    /// A message that took up `num_bytes` of what was sent over the network.
+    ///
+    /// Called before the message is passed on or dropped,
+    /// so that a [`LogMsg::BeginRecordingMsg`] is counted towards the recording it starts.
+    pub(crate) fn on_message(&self, peer_addr: SocketAddr, msg: &LogMsg, num_bytes: u64) {
+        /// How often we update [`ClientInfo::message_rate`].
+        const RATE_WINDOW_SEC: f32 = 1.0;
+
+        let mut events = vec![];
+        let mut clients = self.clients.lock();
+        if let Some(client) = clients.iter_mut().find(|c| c.info.peer_addr == peer_addr) {
+            let now = Instant::now();
+            client.info.num_messages += 1;
+            client.info.num_bytes += num_bytes;
+            client.info.last_message_at = Some(now);
+            client.num_messages_in_rate_window += 1;
+
+            let window_sec = (now - client.rate_window_start).as_secs_f32();
+            if window_sec >= RATE_WINDOW_SEC {
+                client.info.message_rate = client.num_messages_in_rate_window as f32 / window_sec;
+                client.rate_window_start = now;
+                client.num_messages_in_rate_window = 0;
+            }
+
+            if let LogMsg::BeginRecordingMsg(msg) = msg {
+                client.info.application_id = Some(msg.info.application_id.clone());
+
+                events.extend(client.recording.as_ref().map(RecordingStats::ended));
+                client.recording = Some(RecordingStats {
+                    recording_id: msg.info.recording_id,
+                    num_messages: 0,
+                    num_bytes: 0,
+                    num_dropped: 0,
+                });
+                events.push(AuditEventKind::RecordingStarted {
+                    recording_id: msg.info.recording_id,
+                    application_id: msg.info.application_id.clone(),
+                });
+            }
+            if let Some(recording) = &mut client.recording {
+                recording.num_messages += 1;
+                recording.num_bytes += num_bytes;
+            }
+        }
+        drop(clients);
+        self.audit(peer_addr, events);
+    }
+}
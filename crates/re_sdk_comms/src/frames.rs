@@ -0,0 +1,269 @@
+//! How encoded messages are framed on the wire, from protocol version 2 and up.
+//!
+//! Each frame is a one-byte [`FrameKind`], a little-endian `u32` payload length, and the payload.
+//! Messages larger than [`MAX_CHUNK_SIZE`] are split into several frames, so that we never
+//! hit the `u32` limit, and so that the server can report progress on large messages
+//! (e.g. a 500 MB mesh).
+
+/// Messages larger than this are sent in chunks.
+pub const MAX_CHUNK_SIZE: usize = 16 * 1024 * 1024;
+
+/// No frame we write has a larger payload than this: a chunk plus the size of the message.
+#[cfg(feature = "server")]
+const MAX_FRAME_PAYLOAD_SIZE: usize = MAX_CHUNK_SIZE + 8;
+
+/// We refuse chunked messages larger than this, so that a client can't make us run out of memory.
+#[cfg(feature = "server")]
+const MAX_MESSAGE_SIZE: u64 = 1024 * 1024 * 1024;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum FrameKind {
+    /// A whole encoded message.
+    Message = 0,
+
+    /// The start of a chunked message: the total size of the message as a little-endian `u64`,
+    /// followed by the first chunk.
+    FirstChunk = 1,
+
+    /// The next chunk of a chunked message.
+    Chunk = 2,
+}
+
+impl FrameKind {
+    #[cfg(feature = "server")]
+    fn from_u8(kind: u8) -> Option<Self> {
+        match kind {
+            0 => Some(Self::Message),
+            1 => Some(Self::FirstChunk),
+            2 => Some(Self::Chunk),
+            _ => None,
+        }
+    }
+}
+
+/// How far along we are in receiving a chunked message.
+#[cfg(feature = "server")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TransferProgress {
+    pub received_bytes: u64,
+    pub total_bytes: u64,
+}
+
+#[cfg(feature = "server")]
+impl TransferProgress {
+    /// In the range `[0, 1]`.
+    pub fn fraction(&self) -> f32 {
+        if self.total_bytes == 0 {
+            1.0
+        } else {
+            self.received_bytes as f32 / self.total_bytes as f32
+        }
+    }
+}
+
+/// Write an encoded message, splitting it into chunks if it is large.
+#[cfg(feature = "client")]
+pub fn write_message(write: &mut impl std::io::Write, message: &[u8]) -> std::io::Result<()> {
+    if message.len() <= MAX_CHUNK_SIZE {
+        return write_frame(write, FrameKind::Message, &[message]);
+    }
+
+    let mut chunks = message.chunks(MAX_CHUNK_SIZE);
+    if let Some(first_chunk) = chunks.next() {
+        let total_bytes = (message.len() as u64).to_le_bytes();
+        write_frame(
+            write,
+            FrameKind::FirstChunk,
+            &[&total_bytes[..], first_chunk],
+        )?;
+    }
+    for chunk in chunks {
+        write_frame(write, FrameKind::Chunk, &[chunk])?;
+    }
+    Ok(())
+}
+
+#[cfg(feature = "client")]
+fn write_frame(
+    write: &mut impl std::io::Write,
+    kind: FrameKind,
+    parts: &[&[u8]],
+) -> std::io::Result<()> {
+    let len: usize = parts.iter().map(|part| part.len()).sum();
+    write.write_all(&[kind as u8])?;
+    write.write_all(&(len as u32).to_le_bytes())?;
+    for part in parts {
+        write.write_all(part)?;
+    }
+    Ok(())
+}
+
+/// Read the next encoded message, reassembling it if it was chunked.
+///
+/// `on_progress` is called for every chunk of a chunked message.
+/// If it returns `false` the message is cancelled: the rest of it is skipped,
+/// and `None` is returned.
+#[cfg(feature = "server")]
+pub fn read_message(
+    read: &mut impl std::io::Read,
+    mut on_progress: impl FnMut(TransferProgress) -> bool,
+) -> std::io::Result<Option<Vec<u8>>> {
+    let (kind, mut payload) = read_frame(read)?;
+    match kind {
+        FrameKind::Message => Ok(Some(payload)),
+        FrameKind::FirstChunk => {
+            if payload.len() < 8 {
+                return Err(invalid_data("Chunked message without a size"));
+            }
+            let total_bytes = u64::from_le_bytes(payload[..8].try_into().unwrap());
+            if total_bytes > MAX_MESSAGE_SIZE {
+                return Err(invalid_data(format!(
+                    "Chunked message of {total_bytes} bytes is larger than the maximum of {MAX_MESSAGE_SIZE}"
+                )));
+            }
+            payload.drain(..8);
+
+            let mut message = payload;
+            let mut progress = TransferProgress {
+                received_bytes: message.len() as u64,
+                total_bytes,
+            };
+            let mut cancelled = false;
+
+            loop {
+                if !cancelled && !on_progress(progress) {
+                    cancelled = true;
+                    message = Vec::new(); // We still need to read the rest, but not keep it.
+                }
+
+                if progress.received_bytes >= total_bytes {
+                    break;
+                }
+
+                let (kind, chunk) = read_frame(read)?;
+                if kind != FrameKind::Chunk {
+                    return Err(invalid_data("Chunked message was interrupted"));
+                }
+                progress.received_bytes += chunk.len() as u64;
+                if !cancelled {
+                    message.extend_from_slice(&chunk);
+                }
+            }
+
+            if progress.received_bytes != total_bytes {
+                return Err(invalid_data("Chunked message had the wrong size"));
+            }
+
+            Ok((!cancelled).then_some(message))
+        }
+        FrameKind::Chunk => Err(invalid_data("Got a chunk without a chunked message")),
+    }
+}
+
+/// Read a message from a client older than protocol version 2,
+/// which sends a little-endian `u32` length followed by the message.
+#[cfg(feature = "server")]
+pub fn read_legacy_message(read: &mut impl std::io::Read) -> std::io::Result<Vec<u8>> {
+    let mut len = [0_u8; 4];
+    read.read_exact(&mut len)?;
+    read_payload(read, u32::from_le_bytes(len) as usize)
+}
+
+#[cfg(feature = "server")]
+fn read_frame(read: &mut impl std::io::Read) -> std::io::Result<(FrameKind, Vec<u8>)> {
+    let mut header = [0_u8; 5];
+    read.read_exact(&mut header)?;
+    let kind = FrameKind::from_u8(header[0])
+        .ok_or_else(|| invalid_data(format!("Unknown frame kind {}", header[0])))?;
+    let len = u32::from_le_bytes(header[1..].try_into().unwrap()) as usize;
+    Ok((kind, read_payload(read, len)?))
+}
+
+#[cfg(feature = "server")]
+fn read_payload(read: &mut impl std::io::Read, len: usize) -> std::io::Result<Vec<u8>> {
+    if len > MAX_FRAME_PAYLOAD_SIZE {
+        return Err(invalid_data(format!(
+            "Frame of {len} bytes is larger than the maximum of {MAX_FRAME_PAYLOAD_SIZE}"
+        )));
+    }
+
+    // Only allocate what actually arrives.
+    use std::io::Read as _;
+    let mut payload = Vec::new();
+    read.take(len as u64).read_to_end(&mut payload)?;
+    if payload.len() != len {
+        return Err(std::io::ErrorKind::UnexpectedEof.into());
+    }
+    Ok(payload)
+}
+
+#[cfg(feature = "server")]
+fn invalid_data(msg: impl Into<String>) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, msg.into())
+}
+
+#[cfg(all(feature = "client", feature = "server"))]
+#[test]
+fn test_chunked_messages() {
+    let small = vec![1_u8; 100];
+    let large: Vec<u8> = (0..2 * MAX_CHUNK_SIZE + 3).map(|i| i as u8).collect();
+
+    let mut wire = Vec::new();
+    write_message(&mut wire, &small).unwrap();
+    write_message(&mut wire, &large).unwrap();
+    write_message(&mut wire, &large).unwrap();
+    write_message(&mut wire, &small).unwrap();
+
+    let mut read = std::io::Cursor::new(wire);
+    let mut progress = vec![];
+
+    let msg = read_message(&mut read, |_| unreachable!()).unwrap();
+    assert_eq!(msg, Some(small.clone()));
+
+    let msg = read_message(&mut read, |p| {
+        progress.push(p.received_bytes);
+        true
+    })
+    .unwrap();
+    assert_eq!(msg, Some(large.clone()));
+    assert_eq!(
+        progress,
+        vec![
+            MAX_CHUNK_SIZE as u64,
+            2 * MAX_CHUNK_SIZE as u64,
+            large.len() as u64
+        ]
+    );
+
+    // Cancelling skips the rest of the message:
+    let msg = read_message(&mut read, |_| false).unwrap();
+    assert_eq!(msg, None);
+
+    let msg = read_message(&mut read, |_| unreachable!()).unwrap();
+    assert_eq!(msg, Some(small));
+}
+
+#[cfg(feature = "server")]
+#[test]
+fn test_oversized_frame() {
+    let mut wire = vec![FrameKind::Message as u8];
+    wire.extend_from_slice(&u32::MAX.to_le_bytes());
+    let err = read_message(&mut std::io::Cursor::new(wire), |_| true).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+    let mut wire = vec![FrameKind::Message as u8];
+    wire.extend_from_slice(&100_u32.to_le_bytes());
+    wire.extend_from_slice(&[0; 10]);
+    let err = read_message(&mut std::io::Cursor::new(wire), |_| true).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+
+    let mut wire = vec![FrameKind::FirstChunk as u8];
+    wire.extend_from_slice(&8_u32.to_le_bytes());
+    wire.extend_from_slice(&u64::MAX.to_le_bytes());
+    let err = read_message(&mut std::io::Cursor::new(wire), |_| true).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+    let wire = u32::MAX.to_le_bytes().to_vec();
+    let err = read_legacy_message(&mut std::io::Cursor::new(wire)).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
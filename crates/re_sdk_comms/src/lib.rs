@@ -4,6 +4,9 @@
 #![doc = document_features::document_features!()]
 //!
 
+#[cfg(any(feature = "client", feature = "server"))]
+mod frames;
+
 #[cfg(feature = "client")]
 pub(crate) mod tcp_client;
 
@@ -11,19 +14,47 @@ pub(crate) mod tcp_client;
 mod buffered_client;
 
 #[cfg(feature = "client")]
-pub use buffered_client::Client;
+pub use buffered_client::{BatchThresholds, Client};
 
 #[cfg(feature = "server")]
 mod server;
 
 #[cfg(feature = "server")]
-pub use server::{serve, ServerOptions};
+mod access_control;
+
+#[cfg(feature = "server")]
+pub use access_control::AccessControl;
+
+#[cfg(feature = "server")]
+pub mod audit_log;
+
+#[cfg(feature = "server")]
+mod bandwidth_limits;
+
+#[cfg(feature = "server")]
+pub use bandwidth_limits::BandwidthLimits;
+
+#[cfg(feature = "server")]
+mod connected_clients;
+
+#[cfg(feature = "server")]
+pub use connected_clients::{ClientInfo, ConnectedClients};
+
+#[cfg(feature = "server")]
+pub use frames::TransferProgress;
+
+#[cfg(feature = "server")]
+pub use server::{serve, serve_listener, serve_with_clients, ServerOptions};
 
-use re_log_types::LogMsg;
+use re_log_types::{LogMsg, LogMsgBatch};
 
 pub type Result<T> = anyhow::Result<T>;
 
-pub const PROTOCOL_VERSION: u16 = 0;
+/// Version 1: the client sends its hostname right after the protocol version.
+/// Version 2: large messages are sent in chunks (see `frames.rs`).
+/// Version 3: many messages can be sent as one [`LogMsgBatch`].
+/// Version 4: the client sends an auth token (maybe empty) right after its hostname.
+pub const PROTOCOL_VERSION: u16 = 4;
 
 pub const DEFAULT_SERVER_PORT: u16 = 9876;
 
@@ -32,8 +63,14 @@ pub fn default_server_addr() -> std::net::SocketAddr {
     std::net::SocketAddr::from(([127, 0, 0, 1], DEFAULT_SERVER_PORT))
 }
 
+/// If set, the SDK presents this auth token to the server,
+/// which needs it to accept data if it has an access control list (`rerun --acl`).
+pub const AUTH_TOKEN_ENV_VAR: &str = "RERUN_AUTH_TOKEN";
+
 const PREFIX: [u8; 4] = *b"RR00";
 
+const BATCH_PREFIX: [u8; 4] = *b"RRB0";
+
 pub fn encode_log_msg(log_msg: &LogMsg) -> Vec<u8> {
     use bincode::Options as _;
     let mut bytes = PREFIX.to_vec();
@@ -54,3 +91,94 @@ pub fn decode_log_msg(data: &[u8]) -> Result<LogMsg> {
         .deserialize(payload)
         .context("bincode")
 }
+
+/// Encodes a [`LogMsgBatch`] one message at a time,
+/// so that we know how big it is before deciding to send it.
+#[derive(Default)]
+pub struct BatchEncoder {
+    /// The encoded messages, back to back.
+    payloads: Vec<u8>,
+    num_msgs: u64,
+}
+
+impl BatchEncoder {
+    pub fn push(&mut self, log_msg: &LogMsg) {
+        use bincode::Options as _;
+        bincode::DefaultOptions::new()
+            .serialize_into(&mut self.payloads, log_msg)
+            .unwrap();
+        self.num_msgs += 1;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.num_msgs == 0
+    }
+
+    /// Size of the encoded messages so far.
+    pub fn num_bytes(&self) -> usize {
+        self.payloads.len()
+    }
+
+    /// The packet to send, leaving the encoder empty.
+    ///
+    /// A single message is encoded as such, so that batching doesn't cost anything when
+    /// messages are logged slowly.
+    pub fn finish(&mut self) -> Vec<u8> {
+        use bincode::Options as _;
+
+        let prefix = if self.num_msgs == 1 {
+            PREFIX.to_vec()
+        } else {
+            // bincode encodes a `Vec` as its length followed by the elements,
+            // so this is exactly the encoding of a `LogMsgBatch`:
+            let mut prefix = BATCH_PREFIX.to_vec();
+            bincode::DefaultOptions::new()
+                .serialize_into(&mut prefix, &self.num_msgs)
+                .unwrap();
+            prefix
+        };
+        self.num_msgs = 0;
+        let payloads = std::mem::take(&mut self.payloads);
+        [prefix, payloads].concat()
+    }
+}
+
+/// Decodes what [`encode_log_msg`] or [`BatchEncoder`] produced.
+pub fn decode_log_msgs(data: &[u8]) -> Result<Vec<LogMsg>> {
+    if let Some(payload) = data.strip_prefix(&BATCH_PREFIX) {
+        use anyhow::Context as _;
+        use bincode::Options as _;
+        let batch: LogMsgBatch = bincode::DefaultOptions::new()
+            .deserialize(payload)
+            .context("bincode")?;
+        Ok(batch.msgs)
+    } else {
+        Ok(vec![decode_log_msg(data)?])
+    }
+}
+
+#[test]
+fn test_batch_encoding() {
+    use re_log_types::MsgId;
+
+    let msgs = [
+        LogMsg::Goodbye(MsgId::random()),
+        LogMsg::Goodbye(MsgId::random()),
+        LogMsg::Goodbye(MsgId::random()),
+    ];
+    let ids = |msgs: &[LogMsg]| msgs.iter().map(LogMsg::id).collect::<Vec<_>>();
+
+    let mut encoder = BatchEncoder::default();
+    for msg in &msgs {
+        encoder.push(msg);
+    }
+    let packet = encoder.finish();
+    assert!(encoder.is_empty());
+    assert_eq!(ids(&decode_log_msgs(&packet).unwrap()), ids(&msgs));
+
+    // A batch of one is a plain message:
+    encoder.push(&msgs[0]);
+    let packet = encoder.finish();
+    assert_eq!(packet, encode_log_msg(&msgs[0]));
+    assert_eq!(ids(&decode_log_msgs(&packet).unwrap()), ids(&msgs[..1]));
+}
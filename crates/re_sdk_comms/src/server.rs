@@ -1,5 +1,9 @@
 //! TODO(emilk): use tokio instead
 
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
 use std::time::Instant;
 
 use anyhow::Context as _;
@@ -8,6 +12,8 @@ use rand::{Rng as _, SeedableRng};
 use re_log_types::{LogMsg, TimePoint, TimeType, TimelineName};
 use re_smart_channel::{Receiver, Sender};
 
+use crate::{audit_log::DropReason, ConnectedClients};
+
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct ServerOptions {
     /// If the latency in the [`LogMsg`] channel is greater than this,
@@ -35,21 +41,53 @@ impl Default for ServerOptions {
 /// # Ok::<(), anyhow::Error>(())
 /// ```
 pub fn serve(port: u16, options: ServerOptions) -> anyhow::Result<Receiver<LogMsg>> {
+    serve_with_clients(port, options, ConnectedClients::default())
+}
+
+/// Like [`serve`], but keeps track of the connected SDK:s in `clients`,
+/// e.g. so that a viewer can show them and disconnect them.
+pub fn serve_with_clients(
+    port: u16,
+    options: ServerOptions,
+    clients: ConnectedClients,
+) -> anyhow::Result<Receiver<LogMsg>> {
     let bind_addr = format!("0.0.0.0:{port}");
 
     let listener = std::net::TcpListener::bind(&bind_addr)
         .with_context(|| format!("Failed to bind address {bind_addr:?}"))?;
 
-    let (tx, rx) = re_smart_channel::smart_channel(re_smart_channel::Source::TcpServer { port });
+    let (rx, _server_thread) = serve_listener(listener, options, clients, Default::default())?;
+    Ok(rx)
+}
 
-    std::thread::Builder::new()
+/// Like [`serve_with_clients`], but on a listener you have already bound,
+/// e.g. to port 0 to get a free port without racing anyone else for it.
+///
+/// The returned thread stops accepting clients once `shutdown` is set and the next client
+/// connects, so connect once after setting it to be able to join the thread.
+pub fn serve_listener(
+    listener: std::net::TcpListener,
+    options: ServerOptions,
+    clients: ConnectedClients,
+    shutdown: Arc<AtomicBool>,
+) -> anyhow::Result<(Receiver<LogMsg>, std::thread::JoinHandle<()>)> {
+    let bind_addr = listener.local_addr()?;
+
+    let (tx, rx) = re_smart_channel::smart_channel(re_smart_channel::Source::TcpServer {
+        port: bind_addr.port(),
+    });
+
+    let server_thread = std::thread::Builder::new()
         .name("sdk-server".into())
         .spawn(move || {
             for stream in listener.incoming() {
+                if shutdown.load(Ordering::Relaxed) {
+                    break;
+                }
                 match stream {
                     Ok(stream) => {
                         let tx = tx.clone();
-                        spawn_client(stream, tx, options);
+                        spawn_client(stream, tx, options, clients.clone());
                     }
                     Err(err) => {
                         re_log::warn!("Failed to accept incoming SDK client: {err}");
@@ -69,10 +107,15 @@ pub fn serve(port: u16, options: ServerOptions) -> anyhow::Result<Receiver<LogMs
         );
     }
 
-    Ok(rx)
+    Ok((rx, server_thread))
 }
 
-fn spawn_client(stream: std::net::TcpStream, tx: Sender<LogMsg>, options: ServerOptions) {
+fn spawn_client(
+    stream: std::net::TcpStream,
+    tx: Sender<LogMsg>,
+    options: ServerOptions,
+    clients: ConnectedClients,
+) {
     std::thread::Builder::new()
         .name(format!(
             "sdk-server-client-handler-{:?}",
@@ -85,7 +128,7 @@ fn spawn_client(stream: std::net::TcpStream, tx: Sender<LogMsg>, options: Server
                 re_log::info!("New SDK client connected: {:?}", stream.peer_addr());
             }
 
-            if let Err(err) = run_client(stream, &tx, options) {
+            if let Err(err) = run_client(stream, &tx, options, &clients) {
                 re_log::warn!("Closing connection to client: {err}");
             }
         })
@@ -96,9 +139,8 @@ fn run_client(
     mut stream: std::net::TcpStream,
     tx: &Sender<LogMsg>,
     options: ServerOptions,
+    clients: &ConnectedClients,
 ) -> anyhow::Result<()> {
-    #![allow(clippy::read_zero_byte_vec)] // false positive: https://github.com/rust-lang/rust-clippy/issues/9274
-
     use std::io::Read as _;
 
     let mut client_version = [0_u8; 2];
@@ -107,11 +149,8 @@ fn run_client(
 
     match client_version.cmp(&crate::PROTOCOL_VERSION) {
         std::cmp::Ordering::Less => {
-            anyhow::bail!(
-                "sdk client is using an older protocol version ({}) than the sdk server ({}).",
-                client_version,
-                crate::PROTOCOL_VERSION
-            );
+            // Older versions only lack the client metadata, chunking, batching and auth token,
+            // so we still accept them.
         }
         std::cmp::Ordering::Equal => {}
         std::cmp::Ordering::Greater => {
@@ -123,36 +162,144 @@ fn run_client(
         }
     }
 
+    let hostname = if client_version >= 1 {
+        Some(read_short_string(&mut stream)?)
+    } else {
+        None
+    };
+    let auth_token = if client_version >= 4 {
+        Some(read_short_string(&mut stream)?).filter(|auth_token| !auth_token.is_empty())
+    } else {
+        None
+    };
+
+    let peer_addr = stream.peer_addr()?;
+    if let Some(access_control) = clients.access_control() {
+        let token_name = auth_token
+            .as_deref()
+            .and_then(|auth_token| access_control.token_name(auth_token));
+        if let Some(token_name) = token_name {
+            re_log::debug!("Client at {peer_addr} presented the token of {token_name:?}");
+        } else {
+            re_log::warn!(
+                "Client at {peer_addr} has no valid auth token, so it may not write anything. \
+                See {}.",
+                crate::AUTH_TOKEN_ENV_VAR
+            );
+        }
+    }
+
+    clients.add(peer_addr, hostname, client_version, stream.try_clone()?);
+    let result = receive_msgs(
+        stream,
+        client_version,
+        auth_token.as_deref(),
+        tx,
+        options,
+        clients,
+        peer_addr,
+    );
+    clients.remove(peer_addr, result.as_ref().err().map(ToString::to_string));
+    result
+}
+
+/// Prefixed with its length as a `u16`.
+fn read_short_string(stream: &mut std::net::TcpStream) -> std::io::Result<String> {
+    use std::io::Read as _;
+
+    let mut len = [0_u8; 2];
+    stream.read_exact(&mut len)?;
+    let mut bytes = vec![0_u8; u16::from_le_bytes(len) as usize];
+    stream.read_exact(&mut bytes)?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+fn receive_msgs(
+    mut stream: std::net::TcpStream,
+    client_version: u16,
+    auth_token: Option<&str>,
+    tx: &Sender<LogMsg>,
+    options: ServerOptions,
+    clients: &ConnectedClients,
+    peer_addr: std::net::SocketAddr,
+) -> anyhow::Result<()> {
     let mut congestion_manager = CongestionManager::new(options.max_latency_sec);
+    let mut bandwidth_limits = clients.bandwidth_limits();
 
-    let mut packet = Vec::new();
+    // With access control, we don't accept anything until we know which recording it is for.
+    let access_control = clients.access_control();
+    let mut may_write = access_control.is_none();
+    let mut application_id = None;
 
     loop {
-        let mut packet_size = [0_u8; 4];
-        stream.read_exact(&mut packet_size)?;
-        let packet_size = u32::from_le_bytes(packet_size);
-
-        packet.resize(packet_size as usize, 0_u8);
-        stream.read_exact(&mut packet)?;
+        let packet = if client_version >= 2 {
+            let message = crate::frames::read_message(&mut stream, |progress| {
+                clients.on_transfer_progress(peer_addr, progress)
+            });
+            clients.on_transfer_done(peer_addr);
+            match message? {
+                Some(message) => message,
+                None => {
+                    re_log::info!("Cancelled receiving a large message from {peer_addr}");
+                    clients.on_dropped(peer_addr, DropReason::Cancelled);
+                    continue;
+                }
+            }
+        } else {
+            crate::frames::read_legacy_message(&mut stream)?
+        };
 
-        re_log::trace!("Received log message of size {packet_size}.");
+        re_log::trace!("Received log message of size {}.", packet.len());
 
         congestion_manager.register_latency(tx.latency_sec());
 
-        let msg = crate::decode_log_msg(&packet)?;
+        let msgs = crate::decode_log_msgs(&packet)?;
+        let num_msgs = msgs.len();
+        for (i, msg) in msgs.into_iter().enumerate() {
+            // The size of a packet is shared evenly between its messages:
+            let num_bytes = packet.len() / num_msgs + usize::from(i < packet.len() % num_msgs);
+            clients.on_message(peer_addr, &msg, num_bytes as u64);
 
-        if matches!(msg, LogMsg::Goodbye(_)) {
-            re_log::debug!("Client sent goodbye message.");
-            return Ok(());
-        }
+            if matches!(msg, LogMsg::Goodbye(_)) {
+                re_log::debug!("Client sent goodbye message.");
+                return Ok(());
+            }
 
-        if congestion_manager.should_send(&msg) {
-            tx.send(msg)?;
-        } else {
-            re_log::warn_once!(
-                "Input latency is over the max ({} s) - dropping packets.",
-                options.max_latency_sec
-            );
+            if let (LogMsg::BeginRecordingMsg(msg), Some(access_control)) = (&msg, access_control) {
+                let new_application_id = &msg.info.application_id;
+                may_write = access_control.may_write(auth_token, new_application_id);
+                if !may_write {
+                    re_log::warn!(
+                        "Client at {peer_addr} may not write to '{new_application_id}' - dropping its messages"
+                    );
+                }
+                application_id = Some(new_application_id.clone());
+            }
+
+            if !may_write {
+                clients.on_dropped(peer_addr, DropReason::Forbidden);
+            } else if !congestion_manager.should_send(&msg) {
+                clients.on_dropped(peer_addr, DropReason::Latency);
+                re_log::warn_once!(
+                    "Input latency is over the max ({} s) - dropping packets.",
+                    options.max_latency_sec
+                );
+            } else if !bandwidth_limits
+                .as_mut()
+                .map_or(true, |limits| limits.should_send(&msg))
+            {
+                clients.on_dropped(peer_addr, DropReason::Bandwidth);
+                re_log::debug_once!("Over the bandwidth limits - dropping messages.");
+            } else {
+                if let (Some(access_control), Some(application_id)) =
+                    (access_control, &application_id)
+                {
+                    // Before it is merged with the messages of the other clients,
+                    // so that we know who may read it.
+                    access_control.tag(msg.id(), application_id);
+                }
+                tx.send(msg)?;
+            }
         }
     }
 }
@@ -192,7 +339,11 @@ impl CongestionManager {
 
         #[allow(clippy::match_same_arms)]
         match msg {
-            LogMsg::BeginRecordingMsg(_) | LogMsg::EntityPathOpMsg(_) | LogMsg::Goodbye(_) => true, // we don't want to drop any of these
+            LogMsg::BeginRecordingMsg(_)
+            | LogMsg::EntityPathOpMsg(_)
+            | LogMsg::Goodbye(_)
+            | LogMsg::BlueprintMsg(_)
+            | LogMsg::TraceSpansMsg(_) => true, // we don't want to drop any of these
 
             LogMsg::ArrowMsg(arrow_msg) => match arrow_msg.time_point() {
                 Ok(time_point) => self.should_send_time_point(&time_point),
@@ -316,3 +467,116 @@ impl Throttling {
         }
     }
 }
+
+#[cfg(all(feature = "client", feature = "server"))]
+#[test]
+fn test_access_control_with_interleaved_clients() {
+    use std::io::Write as _;
+
+    use re_log_types::{
+        ApplicationId, BeginRecordingMsg, MsgId, RecordingId, RecordingInfo, RecordingSource, Time,
+        TraceSpansMsg,
+    };
+
+    let access_control = crate::AccessControl::parse(
+        r#"
+        [[token]]
+        name = "team-a"
+        secret = "aaa"
+        write = ["team_a"]
+        read = ["team_a"]
+
+        [[token]]
+        name = "team-b"
+        secret = "bbb"
+        write = ["team_b"]
+        read = ["team_b"]
+        "#,
+    )
+    .unwrap();
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let (rx, _server_thread) = serve_listener(
+        listener,
+        ServerOptions {
+            quiet: true,
+            ..Default::default()
+        },
+        ConnectedClients::default().with_access_control(access_control.clone()),
+        Default::default(),
+    )
+    .unwrap();
+
+    let connect = |secret: &str| {
+        let mut stream = std::net::TcpStream::connect(("127.0.0.1", port)).unwrap();
+        stream
+            .write_all(&crate::PROTOCOL_VERSION.to_le_bytes())
+            .unwrap();
+        for string in ["test-host", secret] {
+            stream
+                .write_all(&(string.len() as u16).to_le_bytes())
+                .unwrap();
+            stream.write_all(string.as_bytes()).unwrap();
+        }
+        stream
+    };
+
+    // Sends a message and waits for the server to pass it on.
+    let send = |stream: &mut std::net::TcpStream, msg: LogMsg| {
+        crate::frames::write_message(stream, &crate::encode_log_msg(&msg)).unwrap();
+        let start = Instant::now();
+        loop {
+            if let Ok(received) = rx.try_recv() {
+                assert_eq!(received.id(), msg.id());
+                return received;
+            }
+            assert!(start.elapsed().as_secs() < 10, "Timed out");
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+    };
+    let begin_recording = |application_id: &str| {
+        LogMsg::BeginRecordingMsg(BeginRecordingMsg {
+            msg_id: MsgId::random(),
+            info: RecordingInfo {
+                application_id: ApplicationId::from(application_id),
+                recording_id: RecordingId::random(),
+                is_official_example: false,
+                started: Time::now(),
+                recording_source: RecordingSource::Unknown,
+            },
+        })
+    };
+    let data = || {
+        LogMsg::TraceSpansMsg(TraceSpansMsg {
+            msg_id: MsgId::random(),
+            spans: vec![],
+        })
+    };
+
+    let mut client_a = connect("aaa");
+    let mut client_b = connect("bbb");
+    let team_a = ApplicationId::from("team_a");
+    let team_b = ApplicationId::from("team_b");
+
+    // The recordings of both clients are interleaved in what the server passes on,
+    // but each message is still attributed to the recording of the client that sent it:
+    for i in 0..4 {
+        let (msg_a, msg_b) = if i == 0 {
+            (begin_recording("team_a"), begin_recording("team_b"))
+        } else {
+            (data(), data())
+        };
+
+        let msg = send(&mut client_a, msg_a);
+        let application_id = access_control.take_application_id_of(msg.id());
+        assert_eq!(application_id.as_ref(), Some(&team_a));
+
+        let msg = send(&mut client_b, msg_b);
+        let application_id = access_control.take_application_id_of(msg.id());
+        assert_eq!(application_id.as_ref(), Some(&team_b));
+    }
+
+    assert!(access_control.may_read(Some("aaa"), &team_a));
+    assert!(!access_control.may_read(Some("bbb"), &team_a));
+}
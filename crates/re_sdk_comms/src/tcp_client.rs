@@ -76,7 +76,7 @@ impl TcpClient {
             re_log::debug!("Connecting to {:?}…", self.addrs);
             match TcpStream::connect(&self.addrs[..]) {
                 Ok(mut stream) => {
-                    if let Err(err) = stream.write(&crate::PROTOCOL_VERSION.to_le_bytes()) {
+                    if let Err(err) = send_hello(&mut stream) {
                         self.stream_state = TcpStreamState::Disconnected;
                         anyhow::bail!("Failed to send to Rerun server at {:?}: {err}", self.addrs);
                     } else {
@@ -97,18 +97,11 @@ impl TcpClient {
 
     /// blocks until it is sent
     pub fn send(&mut self, packet: &[u8]) -> anyhow::Result<()> {
-        use std::io::Write as _;
-
         self.connect()?;
 
         if let TcpStreamState::Connected(stream) = &mut self.stream_state {
             re_log::trace!("Sending a packet of size {}…", packet.len());
-            if let Err(err) = stream.write(&(packet.len() as u32).to_le_bytes()) {
-                self.stream_state = TcpStreamState::Disconnected;
-                anyhow::bail!("Failed to send to Rerun server at {:?}: {err}", self.addrs);
-            }
-
-            if let Err(err) = stream.write(packet) {
+            if let Err(err) = crate::frames::write_message(stream, packet) {
                 self.stream_state = TcpStreamState::Disconnected;
                 anyhow::bail!("Failed to send to Rerun server at {:?}: {err}", self.addrs);
             }
@@ -140,3 +133,21 @@ impl TcpClient {
         }
     }
 }
+
+/// The first thing we send: the protocol version, followed by metadata about us.
+fn send_hello(stream: &mut TcpStream) -> std::io::Result<()> {
+    stream.write_all(&crate::PROTOCOL_VERSION.to_le_bytes())?;
+
+    let hostname = gethostname::gethostname().to_string_lossy().into_owned();
+    write_short_string(stream, &hostname)?;
+
+    let auth_token = std::env::var(crate::AUTH_TOKEN_ENV_VAR).unwrap_or_default();
+    write_short_string(stream, &auth_token)
+}
+
+/// Prefixed with its length as a `u16`, and truncated to fit.
+fn write_short_string(stream: &mut TcpStream, string: &str) -> std::io::Result<()> {
+    let bytes = &string.as_bytes()[..string.len().min(u16::MAX as usize)];
+    stream.write_all(&(bytes.len() as u16).to_le_bytes())?;
+    stream.write_all(bytes)
+}
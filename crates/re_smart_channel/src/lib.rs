@@ -1,7 +1,7 @@
 //! A channel that keeps track of latency and queue length.
 
 use std::sync::{
-    atomic::{AtomicU64, Ordering::Relaxed},
+    atomic::{AtomicBool, AtomicU64, Ordering::Relaxed},
     Arc,
 };
 
@@ -14,6 +14,10 @@ pub enum Source {
     /// The source if a file on disk
     File { path: std::path::PathBuf },
 
+    /// The source is a file on disk that is still being written to,
+    /// and which we keep reading as it grows (like `tail -f`).
+    FollowedFile { path: std::path::PathBuf },
+
     /// The source is the logging sdk directly, same process.
     Sdk,
 
@@ -32,15 +36,37 @@ pub enum Source {
 impl Source {
     pub fn is_network(&self) -> bool {
         match self {
-            Self::File { .. } | Self::Sdk => false,
+            Self::File { .. } | Self::FollowedFile { .. } | Self::Sdk => false,
             Self::WsClient { .. } | Self::TcpServer { .. } => true,
         }
     }
+
+    /// Can more data arrive over time?
+    pub fn is_live(&self) -> bool {
+        match self {
+            Self::File { .. } => false,
+            Self::FollowedFile { .. }
+            | Self::Sdk
+            | Self::WsClient { .. }
+            | Self::TcpServer { .. } => true,
+        }
+    }
 }
 
 pub fn smart_channel<T: Send>(source: Source) -> (Sender<T>, Receiver<T>) {
     let stats = Arc::new(SharedStats::default());
-    smart_channel_with_stats(source, stats)
+    smart_channel_with_stats(source, stats, None)
+}
+
+/// Like [`smart_channel`], but holding at most `capacity` messages.
+///
+/// [`Sender::send`] will block while the channel is full,
+/// applying back-pressure on the sender instead of growing the queue without bounds.
+///
+/// Don't use this on the main thread on web, where blocking is not an option.
+pub fn smart_channel_bounded<T: Send>(source: Source, capacity: usize) -> (Sender<T>, Receiver<T>) {
+    let stats = Arc::new(SharedStats::default());
+    smart_channel_with_stats(source, stats, Some(capacity))
 }
 
 /// Create a new channel using the same stats as some other.
@@ -49,13 +75,25 @@ pub fn smart_channel<T: Send>(source: Source) -> (Sender<T>, Receiver<T>) {
 fn smart_channel_with_stats<T: Send>(
     source: Source,
     stats: Arc<SharedStats>,
+    capacity: Option<usize>,
 ) -> (Sender<T>, Receiver<T>) {
-    let (tx, rx) = crossbeam::channel::unbounded();
+    let (tx, rx) = if let Some(capacity) = capacity {
+        crossbeam::channel::bounded(capacity)
+    } else {
+        crossbeam::channel::unbounded()
+    };
+    let disconnected = Arc::new(AtomicBool::new(false));
     let sender = Sender {
         tx,
         stats: stats.clone(),
+        disconnected: disconnected.clone(),
+    };
+    let receiver = Receiver {
+        rx,
+        stats,
+        source,
+        disconnected,
     };
-    let receiver = Receiver { rx, stats, source };
     (sender, receiver)
 }
 
@@ -70,6 +108,9 @@ struct SharedStats {
 pub struct Sender<T: Send> {
     tx: crossbeam::channel::Sender<(Instant, T)>,
     stats: Arc<SharedStats>,
+
+    /// Set once the [`Receiver`] is dropped.
+    disconnected: Arc<AtomicBool>,
 }
 
 impl<T: Send> Sender<T> {
@@ -90,6 +131,24 @@ impl<T: Send> Sender<T> {
         self.tx.is_empty()
     }
 
+    /// Has the [`Receiver`] been dropped, so that sending would fail?
+    ///
+    /// Lets a sender that is waiting for something to send know when to give up.
+    #[inline]
+    pub fn is_disconnected(&self) -> bool {
+        self.disconnected.load(Relaxed)
+    }
+
+    /// Send a message, and return `true` if the receiver needs waking up.
+    ///
+    /// This lets the sender coalesce wake-ups (e.g. repaint requests):
+    /// if there were already messages in the queue, then the receiver has already been woken up,
+    /// and will drain this message together with the others.
+    pub fn send_and_check_wake_up(&self, msg: T) -> Result<bool, SendError<T>> {
+        self.send(msg)?;
+        Ok(self.len() <= 1)
+    }
+
     /// Number of messages in the channel right now.
     #[inline]
     pub fn len(&self) -> usize {
@@ -112,6 +171,13 @@ pub struct Receiver<T: Send> {
     rx: crossbeam::channel::Receiver<(Instant, T)>,
     stats: Arc<SharedStats>,
     source: Source,
+    disconnected: Arc<AtomicBool>,
+}
+
+impl<T: Send> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        self.disconnected.store(true, Relaxed);
+    }
 }
 
 impl<T: Send> Receiver<T> {
@@ -173,7 +239,7 @@ impl<T: Send> Receiver<T> {
     /// Care must be taken to use [`Self::recv_with_send_time`] and [`Sender::send_at`].
     /// This is a very leaky abstraction, and it would be nice with a refactor.
     pub fn chained_channel(&self) -> (Sender<T>, Receiver<T>) {
-        smart_channel_with_stats(self.source.clone(), self.stats.clone())
+        smart_channel_with_stats(self.source.clone(), self.stats.clone(), None)
     }
 }
 
@@ -198,4 +264,25 @@ fn test_smart_channel() {
     assert_eq!(tx.len(), 0);
     assert_eq!(rx.len(), 0);
     assert!(tx.latency_ns() > 1_000_000);
+
+    assert!(!tx.is_disconnected());
+    drop(rx);
+    assert!(tx.is_disconnected());
+    assert!(tx.send(43).is_err());
+}
+
+#[test]
+fn test_smart_channel_bounded() {
+    let (tx, rx) = smart_channel_bounded(Source::Sdk, 2); // whatever source
+
+    assert_eq!(tx.send_and_check_wake_up(1), Ok(true));
+    assert_eq!(tx.send_and_check_wake_up(2), Ok(false));
+    assert_eq!(rx.len(), 2);
+
+    let sender = std::thread::spawn(move || tx.send(3)); // blocks until there is room
+
+    assert_eq!(rx.recv(), Ok(1));
+    assert_eq!(rx.recv(), Ok(2));
+    assert_eq!(rx.recv(), Ok(3));
+    assert!(sender.join().unwrap().is_ok());
 }
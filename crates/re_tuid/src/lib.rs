@@ -6,6 +6,8 @@
 #![doc = document_features::document_features!()]
 //!
 
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
 use arrow2_convert::{ArrowDeserialize, ArrowField, ArrowSerialize};
 
 #[derive(
@@ -46,6 +48,11 @@ impl Tuid {
     pub fn random() -> Self {
         use std::cell::RefCell;
 
+        if is_deterministic() {
+            let n = NEXT_DETERMINISTIC.fetch_add(1, Ordering::Relaxed);
+            return Tuid { time_ns: n, inc: n };
+        }
+
         thread_local! {
             pub static LATEST_TUID: RefCell<Tuid> = RefCell::new(Tuid{
                 time_ns: monotonic_nanos_since_epoch(),
@@ -78,6 +85,33 @@ impl Tuid {
     pub fn as_u128(&self) -> u128 {
         ((self.time_ns as u128) << 64) | (self.inc as u128)
     }
+
+    /// When the id was created, approximately.
+    #[inline]
+    pub fn nanoseconds_since_epoch(&self) -> u64 {
+        self.time_ns
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+static DETERMINISTIC: AtomicBool = AtomicBool::new(false);
+static NEXT_DETERMINISTIC: AtomicU64 = AtomicU64::new(1);
+
+/// Make [`Tuid::random`] count up from one instead, so that the same inputs give the same ids,
+/// e.g. for snapshot tests.
+///
+/// The ids are then only unique within this process, and only in order if they are all
+/// created on the same thread.
+pub fn set_deterministic(deterministic: bool) {
+    DETERMINISTIC.store(deterministic, Ordering::Relaxed);
+    NEXT_DETERMINISTIC.store(1, Ordering::Relaxed);
+}
+
+/// See [`set_deterministic`].
+#[inline]
+pub fn is_deterministic() -> bool {
+    DETERMINISTIC.load(Ordering::Relaxed)
 }
 
 /// Returns a high-precision, monotonically increasing count that approximates nanoseconds since unix epoch.
@@ -11,10 +11,16 @@ pub enum Command {
     #[cfg(not(target_arch = "wasm32"))]
     Open,
     #[cfg(not(target_arch = "wasm32"))]
+    OpenAndMerge,
+    #[cfg(not(target_arch = "wasm32"))]
     Save,
     #[cfg(not(target_arch = "wasm32"))]
     SaveSelection,
     #[cfg(not(target_arch = "wasm32"))]
+    ExportBlueprint,
+    #[cfg(not(target_arch = "wasm32"))]
+    ImportBlueprint,
+    #[cfg(not(target_arch = "wasm32"))]
     Quit,
 
     ResetViewer,
@@ -23,6 +29,10 @@ pub enum Command {
     OpenProfiler,
 
     ToggleMemoryPanel,
+    #[cfg(not(target_arch = "wasm32"))]
+    ToggleDataSourcesPanel,
+    ToggleMessageInspector,
+    CopyBookmarkLink,
     ToggleBlueprintPanel,
     ToggleSelectionPanel,
     ToggleTimePanel,
@@ -39,6 +49,8 @@ pub enum Command {
     PlaybackTogglePlayPause,
     PlaybackStepBack,
     PlaybackStepForward,
+    PlaybackSpeedUp,
+    PlaybackSlowDown,
 }
 
 impl Command {
@@ -60,10 +72,26 @@ impl Command {
                 "Save loop selection…",
                 "Save data for the current loop selection to a Rerun data file (.rrd)",
             ),
+            #[cfg(not(target_arch = "wasm32"))]
+            Command::ExportBlueprint => (
+                "Export blueprint…",
+                "Save the layout of the current application to a file, e.g. to share it with teammates",
+            ),
+            #[cfg(not(target_arch = "wasm32"))]
+            Command::ImportBlueprint => (
+                "Import blueprint…",
+                "Replace the layout of the current application with one from a file",
+            ),
 
             #[cfg(not(target_arch = "wasm32"))]
             Command::Open => ("Open…", "Open a Rerun Data File (.rrd)"),
 
+            #[cfg(not(target_arch = "wasm32"))]
+            Command::OpenAndMerge => (
+                "Open and merge…",
+                "Add the data of a Rerun Data File (.rrd) to the current recording",
+            ),
+
             #[cfg(not(target_arch = "wasm32"))]
             Command::Quit => ("Quit", "Close the Rerun Viewer"),
 
@@ -82,6 +110,19 @@ impl Command {
                 "Toggle memory panel",
                 "Investigate what is using up RAM in Rerun Viewer",
             ),
+            #[cfg(not(target_arch = "wasm32"))]
+            Command::ToggleDataSourcesPanel => (
+                "Toggle data sources panel",
+                "See which SDK:s are connected, and disconnect them",
+            ),
+            Command::ToggleMessageInspector => (
+                "Toggle message inspector",
+                "See the raw bytes and decoded contents of the selected message, e.g. to debug an SDK",
+            ),
+            Command::CopyBookmarkLink => (
+                "Copy link to this view",
+                "Copy a rerun:// link that connects to the same server and goes to the current time and selection",
+            ),
             Command::ToggleBlueprintPanel => ("Toggle blueprint panel", "Toggle the left panel"),
             Command::ToggleSelectionPanel => ("Toggle selection panel", "Toggle the right panel"),
             Command::ToggleTimePanel => ("Toggle time panel", "Toggle the bottom time panel"),
@@ -109,6 +150,14 @@ impl Command {
                 "Step time forward",
                 "Move the time marker to the next point in time with any data",
             ),
+            Command::PlaybackSpeedUp => (
+                "Speed up playback",
+                "Switch to the next faster playback speed, e.g. from 1x to 2x",
+            ),
+            Command::PlaybackSlowDown => (
+                "Slow down playback",
+                "Switch to the next slower playback speed, e.g. from 1x to 0.5x",
+            ),
         }
     }
 
@@ -136,7 +185,11 @@ impl Command {
             #[cfg(not(target_arch = "wasm32"))]
             Command::SaveSelection => Some(cmd_shift(Key::S)),
             #[cfg(not(target_arch = "wasm32"))]
+            Command::ExportBlueprint | Command::ImportBlueprint => None,
+            #[cfg(not(target_arch = "wasm32"))]
             Command::Open => Some(cmd(Key::O)),
+            #[cfg(not(target_arch = "wasm32"))]
+            Command::OpenAndMerge => Some(cmd_shift(Key::O)),
 
             #[cfg(all(not(target_arch = "wasm32"), target_os = "windows"))]
             Command::Quit => Some(KeyboardShortcut::new(Modifiers::ALT, Key::F4)),
@@ -148,6 +201,10 @@ impl Command {
             #[cfg(not(target_arch = "wasm32"))]
             Command::OpenProfiler => Some(ctrl_shift(Key::P)),
             Command::ToggleMemoryPanel => Some(ctrl_shift(Key::M)),
+            #[cfg(not(target_arch = "wasm32"))]
+            Command::ToggleDataSourcesPanel => Some(ctrl_shift(Key::D)),
+            Command::ToggleMessageInspector => Some(ctrl_shift(Key::I)),
+            Command::CopyBookmarkLink => Some(ctrl_shift(Key::L)),
             Command::ToggleBlueprintPanel => Some(ctrl_shift(Key::B)),
             Command::ToggleSelectionPanel => Some(ctrl_shift(Key::S)),
             Command::ToggleTimePanel => Some(ctrl_shift(Key::T)),
@@ -160,6 +217,8 @@ impl Command {
             Command::PlaybackTogglePlayPause => Some(key(Key::Space)),
             Command::PlaybackStepBack => Some(key(Key::ArrowLeft)),
             Command::PlaybackStepForward => Some(key(Key::ArrowRight)),
+            Command::PlaybackSpeedUp => Some(key(Key::ArrowUp)),
+            Command::PlaybackSlowDown => Some(key(Key::ArrowDown)),
         }
     }
 
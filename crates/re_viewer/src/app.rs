@@ -11,25 +11,40 @@ use poll_promise::Promise;
 use re_arrow_store::DataStoreStats;
 use re_data_store::log_db::LogDb;
 use re_format::format_number;
-use re_log_types::{ApplicationId, LogMsg, RecordingId};
+use re_log_types::{ApplicationId, LogMsg, RecordingId, RecordingInfo};
 use re_renderer::WgpuResourcePoolStatistics;
 use re_smart_channel::Receiver;
 use re_ui::Command;
 
 use crate::{
     app_icon::setup_app_icon,
-    misc::{AppOptions, Caches, RecordingConfig, ViewerContext},
-    ui::{data_ui::ComponentUiRegistry, Blueprint},
+    misc::{
+        AppOptions, Caches, EntityDisplayNames, FrameBudgetWatchdog, QualityChange, RecentItem,
+        RecentItems, RecordingConfig, SharedAnnotationContexts, ViewerContext,
+    },
+    remote_viewer_app::{WsConnection, WsConnectionStatus},
+    ui::{
+        data_ui::ComponentUiRegistry,
+        welcome_screen::{WelcomeScreen, WelcomeScreenAction},
+        Blueprint,
+    },
     viewer_analytics::ViewerAnalytics,
 };
 
 #[cfg(not(target_arch = "wasm32"))]
 use re_log_types::TimeRangeF;
 
+#[cfg(not(target_arch = "wasm32"))]
+use crate::ui::welcome_screen::ExampleRecording;
+
 use super::app_icon::AppIconStatus;
 
 const WATERMARK: bool = false; // Nice for recording media material
 
+/// How often we repaint when nothing is happening,
+/// so that e.g. the latency indicators stay fresh without pegging a core.
+const IDLE_REPAINT_INTERVAL: std::time::Duration = std::time::Duration::from_millis(500);
+
 // ----------------------------------------------------------------------------
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -37,14 +52,55 @@ enum TimeControlCommand {
     TogglePlayPause,
     StepBack,
     StepForward,
+    SpeedUp,
+    SlowDown,
 }
 
 // ----------------------------------------------------------------------------
 
 /// Settings set once at startup (e.g. via command-line options) and not serialized.
-#[derive(Clone, Copy, Default)]
+#[derive(Clone)]
 pub struct StartupOptions {
     pub memory_limit: re_memory::MemoryLimit,
+
+    /// An upper limit on how much GPU memory the viewer should use.
+    ///
+    /// When exceeded, the viewer evicts the least recently used image textures and meshes.
+    pub gpu_memory_limit: re_memory::MemoryLimit,
+
+    /// The maximum rate at which we repaint while new data is streaming in, in Hz.
+    ///
+    /// Without new data, we still repaint twice a second.
+    pub max_fps: f32,
+
+    /// Use this instead of the render quality chosen in the settings, e.g. on a slow machine.
+    pub render_quality: Option<crate::RenderQuality>,
+
+    /// How to connect to Rerun Servers, e.g. which extra root certificates to trust for `wss://`.
+    pub ws_connection_options: re_ws_comms::ConnectionOptions,
+
+    /// Where to go once the data arrives, e.g. from a `rerun://` link a teammate shared.
+    pub bookmark: Option<crate::Bookmark>,
+}
+
+impl Default for StartupOptions {
+    fn default() -> Self {
+        Self {
+            memory_limit: Default::default(),
+            gpu_memory_limit: Default::default(),
+            max_fps: 60.0,
+            render_quality: None,
+            ws_connection_options: Default::default(),
+            bookmark: None,
+        }
+    }
+}
+
+impl StartupOptions {
+    /// The shortest time we allow between two repaints caused by incoming data.
+    pub fn min_repaint_interval(&self) -> std::time::Duration {
+        std::time::Duration::from_secs_f32(1.0 / self.max_fps.at_least(1.0))
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -58,6 +114,16 @@ pub struct App {
 
     rx: Receiver<LogMsg>,
 
+    /// The Rerun Servers we receive data from, in addition to [`Self::rx`].
+    ws_connections: Vec<WsConnection>,
+
+    /// The recording that each data source is currently logging to,
+    /// so that the messages of several sources at once end up in the right place.
+    recording_of_source: HashMap<re_smart_channel::Source, RecordingInfo>,
+
+    /// Which data source to receive from next, so that they take turns.
+    next_source: usize,
+
     /// Where the logs are stored.
     log_dbs: IntMap<RecordingId, LogDb>,
 
@@ -75,14 +141,40 @@ pub struct App {
     toasts: Toasts,
 
     latest_memory_purge: instant::Instant,
+    latest_gpu_memory_purge: instant::Instant,
     memory_panel: crate::memory_panel::MemoryPanel,
     memory_panel_open: bool,
 
+    message_inspector: crate::ui::message_inspector::MessageInspector,
+
+    /// The SDK:s connected to us, if we are hosting a server for them.
+    #[cfg(not(target_arch = "wasm32"))]
+    sdk_clients: Option<re_sdk_comms::ConnectedClients>,
+    #[cfg(not(target_arch = "wasm32"))]
+    data_sources_panel_open: bool,
+
+    welcome_screen: WelcomeScreen,
+
     latest_queue_interest: instant::Instant,
 
+    /// If set, incoming messages are held back in [`Self::paused_messages`] instead of being
+    /// added to the store, so that a live view can be inspected without it changing.
+    ingestion_paused: bool,
+    paused_messages: crate::misc::PausedMessages,
+
+    /// See [`AppOptions::auto_save_directory`].
+    #[cfg(not(target_arch = "wasm32"))]
+    auto_saver: crate::misc::auto_save::AutoSaver,
+
     /// Measures how long a frame takes to paint
     frame_time_history: egui::util::History<f32>,
 
+    /// See [`AppOptions::frame_budget`].
+    frame_budget_watchdog: FrameBudgetWatchdog,
+
+    /// See [`StartupOptions::bookmark`]. Cleared once applied.
+    pending_bookmark: Option<crate::Bookmark>,
+
     /// Commands to run at the end of the frame.
     pending_commands: Vec<Command>,
     cmd_palette: re_ui::CommandPalette,
@@ -119,18 +211,40 @@ impl App {
             .expect("Error setting Ctrl-C handler");
         }
 
-        let state: AppState = storage
+        let mut state: AppState = storage
             .and_then(|storage| eframe::get_value(storage, eframe::APP_KEY))
             .unwrap_or_default();
+        if let Some((blueprints, entity_display_names)) =
+            storage.and_then(|storage| eframe::get_value(storage, BLUEPRINTS_KEY))
+        {
+            state.blueprints = blueprints;
+            state.entity_display_names = entity_display_names;
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(dir) = crate::misc::blueprint_file::stored_blueprints_dir() {
+            for (app_id, file) in crate::misc::blueprint_file::load_stored(&dir) {
+                state.blueprints.insert(app_id.clone(), file.blueprint);
+                state
+                    .entity_display_names
+                    .insert(app_id, file.entity_display_names);
+            }
+        }
+        state.recent.add_source(rx.source());
+        state.app_options.render_quality_override = startup_options.render_quality;
 
         let mut analytics = ViewerAnalytics::new();
         analytics.on_viewer_started(app_env);
 
+        let pending_bookmark = startup_options.bookmark.clone();
+
         Self {
             startup_options,
             re_ui,
             component_ui_registry: Default::default(),
             rx,
+            ws_connections: Default::default(),
+            recording_of_source: Default::default(),
+            next_source: 0,
             log_dbs: Default::default(),
             state,
             #[cfg(not(target_arch = "wasm32"))]
@@ -138,13 +252,27 @@ impl App {
             pending_promises: Default::default(),
             toasts: Toasts::new(),
             latest_memory_purge: instant::Instant::now(), // TODO(emilk): `Instant::MIN` when we have our own `Instant` that supports it.
+            latest_gpu_memory_purge: instant::Instant::now(),
             memory_panel: Default::default(),
             memory_panel_open: false,
+            message_inspector: Default::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            sdk_clients: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            data_sources_panel_open: false,
+
+            welcome_screen: Default::default(),
 
             latest_queue_interest: instant::Instant::now(), // TODO(emilk): `Instant::MIN` when we have our own `Instant` that supports it.
+            ingestion_paused: false,
+            paused_messages: Default::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            auto_saver: Default::default(),
 
             frame_time_history: egui::util::History::new(1..100, 0.5),
+            frame_budget_watchdog: Default::default(),
 
+            pending_bookmark,
             pending_commands: Default::default(),
             cmd_palette: Default::default(),
 
@@ -159,6 +287,19 @@ impl App {
         self.state.profiler = profiler;
     }
 
+    /// Show these SDK clients in the data sources panel.
+    ///
+    /// See [`re_sdk_comms::serve_with_clients`].
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn set_sdk_clients(&mut self, sdk_clients: re_sdk_comms::ConnectedClients) {
+        self.sdk_clients = Some(sdk_clients);
+    }
+
+    /// Also receive from this Rerun Server.
+    pub(crate) fn add_ws_connection(&mut self, ws_connection: WsConnection) {
+        self.ws_connections.push(ws_connection);
+    }
+
     /// Creates a promise with the specified name that will run `f` on a background
     /// thread using the `poll_promise` crate.
     ///
@@ -217,6 +358,20 @@ impl App {
             self.pending_commands.push(cmd);
         }
 
+        // The back and forward buttons of the mouse, like in a browser:
+        let (back, forward) = egui_ctx.input(|i| {
+            (
+                i.pointer.button_pressed(egui::PointerButton::Extra1),
+                i.pointer.button_pressed(egui::PointerButton::Extra2),
+            )
+        });
+        if back {
+            self.pending_commands.push(Command::SelectionPrevious);
+        }
+        if forward {
+            self.pending_commands.push(Command::SelectionNext);
+        }
+
         if !frame.is_web() {
             egui::gui_zoom::zoom_with_keyboard_shortcuts(
                 egui_ctx,
@@ -257,8 +412,20 @@ impl App {
                 save(self, self.loop_selection());
             }
             #[cfg(not(target_arch = "wasm32"))]
+            Command::ExportBlueprint => {
+                export_blueprint(self);
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            Command::ImportBlueprint => {
+                import_blueprint(self);
+            }
+            #[cfg(not(target_arch = "wasm32"))]
             Command::Open => {
-                open(self);
+                open(self, false);
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            Command::OpenAndMerge => {
+                open(self, true);
             }
             #[cfg(not(target_arch = "wasm32"))]
             Command::Quit => {
@@ -277,6 +444,23 @@ impl App {
             Command::ToggleMemoryPanel => {
                 self.memory_panel_open ^= true;
             }
+            #[cfg(not(target_arch = "wasm32"))]
+            Command::ToggleDataSourcesPanel => {
+                self.data_sources_panel_open ^= true;
+            }
+            Command::ToggleMessageInspector => {
+                self.message_inspector.open ^= true;
+            }
+            Command::CopyBookmarkLink => {
+                if let Some(bookmark) = self.bookmark() {
+                    let url = bookmark.to_url();
+                    re_log::info!("Copied {url}");
+                    egui_ctx.output_mut(|o| o.copied_text = url);
+                } else {
+                    self.toasts
+                        .warning("Only recordings streamed from a Rerun Server can be linked to");
+                }
+            }
             Command::ToggleBlueprintPanel => {
                 self.blueprint_mut().blueprint_panel_expanded ^= true;
             }
@@ -295,13 +479,17 @@ impl App {
             Command::SelectionPrevious => {
                 let state = &mut self.state;
                 if let Some(rec_cfg) = state.recording_configs.get_mut(&state.selected_rec_id) {
-                    rec_cfg.selection_state.select_previous();
+                    if let Some(previous) = rec_cfg.selection_state.select_previous() {
+                        rec_cfg.restore_time_of(&previous);
+                    }
                 }
             }
             Command::SelectionNext => {
                 let state = &mut self.state;
                 if let Some(rec_cfg) = state.recording_configs.get_mut(&state.selected_rec_id) {
-                    rec_cfg.selection_state.select_next();
+                    if let Some(next) = rec_cfg.selection_state.select_next() {
+                        rec_cfg.restore_time_of(&next);
+                    }
                 }
             }
             Command::ToggleCommandPalette => {
@@ -317,7 +505,96 @@ impl App {
             Command::PlaybackStepForward => {
                 self.run_time_control_command(TimeControlCommand::StepForward);
             }
+            Command::PlaybackSpeedUp => {
+                self.run_time_control_command(TimeControlCommand::SpeedUp);
+            }
+            Command::PlaybackSlowDown => {
+                self.run_time_control_command(TimeControlCommand::SlowDown);
+            }
+        }
+    }
+
+    /// A link to the time and selection of the current recording, if others can connect to it.
+    fn bookmark(&self) -> Option<crate::Bookmark> {
+        let rec_id = self.state.selected_rec_id;
+        let log_db = self.log_dbs.get(&rec_id)?;
+        let Some(re_smart_channel::Source::WsClient { ws_server_url }) = &log_db.data_source else {
+            return None;
+        };
+        let host_and_port = crate::Bookmark::host_and_port_of_ws_url(ws_server_url)?;
+        let rec_cfg = self.state.recording_configs.get(&rec_id)?;
+
+        let time_ctrl = &rec_cfg.time_ctrl;
+        let time = time_ctrl
+            .time_int()
+            .map(|time| (*time_ctrl.timeline().name(), time));
+        let selection = rec_cfg
+            .selection_state
+            .current()
+            .iter()
+            .filter_map(|item| match item {
+                crate::Item::InstancePath(_, instance_path) => Some(instance_path.clone()),
+                _ => None,
+            })
+            .collect();
+
+        Some(crate::Bookmark {
+            host_and_port: host_and_port.to_owned(),
+            recording_id: Some(rec_id),
+            time,
+            selection,
+        })
+    }
+
+    /// Go to the [`StartupOptions::bookmark`] once its recording and timeline have arrived.
+    fn apply_pending_bookmark(&mut self) {
+        let Some(bookmark) = &self.pending_bookmark else {
+            return;
+        };
+
+        let rec_id = bookmark.recording_id.unwrap_or(self.state.selected_rec_id);
+        let Some(log_db) = self.log_dbs.get(&rec_id) else {
+            return; // not here yet
+        };
+        if log_db.is_empty() {
+            return;
+        }
+        let timeline = if let Some((timeline_name, _)) = &bookmark.time {
+            let Some(timeline) = log_db
+                .timelines()
+                .find(|timeline| timeline.name() == timeline_name)
+            else {
+                return; // not here yet
+            };
+            Some(*timeline)
+        } else {
+            None
+        };
+
+        re_log::debug!("Going to {}", bookmark.to_url());
+        self.state.selected_rec_id = rec_id;
+        let data_source = log_db.data_source.as_ref().unwrap_or(self.rx.source());
+        let rec_cfg = recording_config_entry(
+            &mut self.state.recording_configs,
+            rec_id,
+            data_source,
+            log_db,
+        );
+
+        if let (Some(timeline), Some((_, time))) = (timeline, bookmark.time.as_ref()) {
+            rec_cfg.time_ctrl.pause();
+            rec_cfg.time_ctrl.set_timeline_and_time(timeline, *time);
+        }
+        if !bookmark.selection.is_empty() {
+            rec_cfg.selection_state.set_multi_selection(
+                bookmark
+                    .selection
+                    .iter()
+                    .map(|instance_path| crate::Item::InstancePath(None, instance_path.clone())),
+            );
         }
+
+        self.pending_bookmark = None;
     }
 
     fn run_time_control_command(&mut self, command: TimeControlCommand) {
@@ -338,6 +615,12 @@ impl App {
             TimeControlCommand::StepForward => {
                 time_ctrl.step_time_fwd(times_per_timeline);
             }
+            TimeControlCommand::SpeedUp => {
+                time_ctrl.speed_up();
+            }
+            TimeControlCommand::SlowDown => {
+                time_ctrl.slow_down();
+            }
         }
     }
 
@@ -372,11 +655,14 @@ impl App {
             .resizable(true)
             .frame(frame)
             .show_animated_inside(ui, self.memory_panel_open, |ui| {
+                let log_dbs = self.log_dbs.values().collect_vec();
                 self.memory_panel.ui(
                     ui,
-                    &self.startup_options.memory_limit,
+                    &mut self.startup_options.memory_limit,
+                    &self.startup_options.gpu_memory_limit,
                     gpu_resource_stats,
                     store_stats,
+                    &log_dbs,
                 );
             });
     }
@@ -389,6 +675,38 @@ impl eframe::App for App {
 
     fn save(&mut self, storage: &mut dyn eframe::Storage) {
         eframe::set_value(storage, eframe::APP_KEY, &self.state);
+
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(dir) = crate::misc::blueprint_file::stored_blueprints_dir() {
+            let result = crate::misc::blueprint_file::save_stored(
+                &dir,
+                &self.state.blueprints,
+                &self.state.entity_display_names,
+            );
+            match result {
+                Ok(()) => {
+                    // They now live in the files, so forget the copies of older versions:
+                    eframe::set_value(
+                        storage,
+                        BLUEPRINTS_KEY,
+                        &(HashMap::<(), ()>::default(), HashMap::<(), ()>::default()),
+                    );
+                    return;
+                }
+                Err(err) => {
+                    re_log::warn_once!(
+                        "Failed to store the blueprints: {}",
+                        re_error::format(&err)
+                    );
+                }
+            }
+        }
+
+        eframe::set_value(
+            storage,
+            BLUEPRINTS_KEY,
+            &(&self.state.blueprints, &self.state.entity_display_names),
+        );
     }
 
     fn update(&mut self, egui_ctx: &egui::Context, frame: &mut eframe::Frame) {
@@ -398,6 +716,10 @@ impl eframe::App for App {
             self.icon_status = setup_app_icon();
         }
 
+        for ws_connection in &mut self.ws_connections {
+            ws_connection.update();
+        }
+
         #[cfg(not(target_arch = "wasm32"))]
         if self.ctrl_c.load(std::sync::atomic::Ordering::Relaxed) {
             frame.close();
@@ -425,10 +747,13 @@ impl eframe::App for App {
         self.check_keyboard_shortcuts(egui_ctx, frame);
 
         self.purge_memory_if_needed();
+        self.purge_gpu_memory_if_needed(&gpu_resource_stats);
 
         self.state.cache.new_frame();
 
         self.receive_messages(egui_ctx);
+        self.apply_pending_bookmark();
+        self.check_silent_alerts(egui_ctx);
 
         self.cleanup();
 
@@ -439,6 +764,8 @@ impl eframe::App for App {
             // Add some margin so that we can later paint an outline around it all.
             main_panel_frame.inner_margin = 1.0.into();
         }
+        let is_downloading_example = self.promise_exists(EXAMPLE_DOWNLOAD_PROMISE);
+        let mut welcome_screen_action = None;
         egui::CentralPanel::default()
             .frame(main_panel_frame)
             .show(egui_ctx, |ui| {
@@ -458,14 +785,20 @@ impl eframe::App for App {
                     });
                 let blueprint = self.state.blueprints.entry(selected_app_id).or_default();
 
-                recording_config_entry(
+                let data_source = log_db.data_source.as_ref().unwrap_or(self.rx.source());
+                let rec_cfg = recording_config_entry(
                     &mut self.state.recording_configs,
                     self.state.selected_rec_id,
-                    self.rx.source(),
+                    data_source,
                     log_db,
-                )
-                .selection_state
-                .on_frame_start(log_db, blueprint);
+                );
+                let time = rec_cfg
+                    .time_ctrl
+                    .time_int()
+                    .map(|time| (*rec_cfg.time_ctrl.timeline(), time));
+                rec_cfg
+                    .selection_state
+                    .on_frame_start(log_db, blueprint, time);
 
                 {
                     // TODO(andreas): store the re_renderer somewhere else.
@@ -478,9 +811,17 @@ impl eframe::App for App {
                         .get_mut::<re_renderer::RenderContext>()
                         .unwrap();
                     render_ctx.begin_frame();
+                    render_ctx.set_msaa_sample_count(
+                        self.state.app_options.render_quality().msaa_sample_count(),
+                    );
 
                     if log_db.is_empty() {
-                        wait_screen_ui(ui, &self.rx);
+                        welcome_screen_action = self.welcome_screen.ui(
+                            ui,
+                            &self.rx,
+                            &self.state.recent,
+                            is_downloading_example,
+                        );
                     } else {
                         self.state.show(
                             ui,
@@ -488,7 +829,7 @@ impl eframe::App for App {
                             log_db,
                             &self.re_ui,
                             &self.component_ui_registry,
-                            self.rx.source(),
+                            data_source,
                         );
                     }
 
@@ -501,6 +842,35 @@ impl eframe::App for App {
             paint_native_window_frame(egui_ctx);
         }
 
+        if let Some(action) = welcome_screen_action {
+            self.on_welcome_screen_action(action);
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        self.poll_example_download();
+
+        #[cfg(not(target_arch = "wasm32"))]
+        crate::ui::data_sources_panel::data_sources_window(
+            egui_ctx,
+            &mut self.data_sources_panel_open,
+            self.sdk_clients.as_ref(),
+        );
+
+        if self.message_inspector.open {
+            if let (Some(log_db), Some(rec_cfg)) = (
+                self.log_dbs.get(&self.state.selected_rec_id),
+                self.state
+                    .recording_configs
+                    .get(&self.state.selected_rec_id),
+            ) {
+                self.message_inspector.window(
+                    egui_ctx,
+                    log_db,
+                    rec_cfg.selection_state.current(),
+                    &self.state.app_options.time_format,
+                );
+            }
+        }
+
         self.handle_dropping_files(egui_ctx);
         self.toasts.show(egui_ctx);
 
@@ -510,10 +880,14 @@ impl eframe::App for App {
 
         self.run_pending_commands(egui_ctx, frame);
 
-        self.frame_time_history.add(
-            egui_ctx.input(|i| i.time),
-            frame_start.elapsed().as_secs_f32(),
-        );
+        let now = egui_ctx.input(|i| i.time);
+        self.frame_time_history
+            .add(now, frame_start.elapsed().as_secs_f32());
+        if let Some(frame_time) = self.frame_time_history.average() {
+            self.check_frame_budget(now, frame_time);
+        }
+
+        egui_ctx.request_repaint_after(IDLE_REPAINT_INTERVAL);
     }
 }
 
@@ -546,74 +920,142 @@ fn paint_native_window_frame(egui_ctx: &egui::Context) {
     );
 }
 
-fn wait_screen_ui(ui: &mut egui::Ui, rx: &Receiver<LogMsg>) {
-    ui.centered_and_justified(|ui| {
-        fn ready_and_waiting(ui: &mut egui::Ui, txt: &str) {
-            let style = ui.style();
-            let mut layout_job = egui::text::LayoutJob::default();
-            layout_job.append(
-                "Ready",
-                0.0,
-                egui::TextFormat::simple(
-                    egui::TextStyle::Heading.resolve(style),
-                    style.visuals.strong_text_color(),
-                ),
-            );
-            layout_job.append(
-                &format!("\n\n{txt}"),
-                0.0,
-                egui::TextFormat::simple(
-                    egui::TextStyle::Body.resolve(style),
-                    style.visuals.text_color(),
-                ),
-            );
-            layout_job.halign = egui::Align::Center;
-            ui.label(layout_job);
+impl App {
+    /// Fire the [`crate::misc::alerts::AlertCondition::Silent`] alerts of live recordings
+    /// that have gone quiet.
+    fn check_silent_alerts(&mut self, egui_ctx: &egui::Context) {
+        let rules = &self.state.app_options.alert_rules;
+        if !rules.iter().any(|rule| rule.is_silence()) {
+            return;
         }
 
-        match rx.source() {
-            re_smart_channel::Source::File { path } => {
-                ui.strong(format!("Loading {}…", path.display()));
-            }
-            re_smart_channel::Source::Sdk => {
-                ready_and_waiting(ui, "Waiting for logging data from SDK");
+        for (rec_id, log_db) in &self.log_dbs {
+            let is_live = !matches!(
+                log_db.data_source,
+                None | Some(re_smart_channel::Source::File { .. })
+            );
+            if !is_live {
+                continue;
             }
-            re_smart_channel::Source::WsClient { ws_server_url } => {
-                // TODO(emilk): it would be even better to know wether or not we are connected, or are attempting to connect
-                ready_and_waiting(ui, &format!("Waiting for data from {ws_server_url}"));
+            if let Some(rec_cfg) = self.state.recording_configs.get_mut(rec_id) {
+                for text in rec_cfg.alerts.check_silence(rules) {
+                    self.toasts.warning(text);
+                }
             }
-            re_smart_channel::Source::TcpServer { port } => {
-                ready_and_waiting(ui, &format!("Listening on port {port}"));
+        }
+
+        // Notice the silence even if nothing else happens:
+        egui_ctx.request_repaint_after(std::time::Duration::from_secs(1));
+    }
+
+    /// The next message from any of our data sources, and where it came from.
+    ///
+    /// The sources take turns, so that a busy one can't hold up the others.
+    fn try_recv(&mut self) -> Option<(re_smart_channel::Source, LogMsg)> {
+        let num_sources = 1 + self.ws_connections.len();
+        for _ in 0..num_sources {
+            let index = self.next_source % num_sources;
+            self.next_source = self.next_source.wrapping_add(1);
+
+            let rx = match index.checked_sub(1) {
+                None => &self.rx,
+                Some(index) => {
+                    let ws_connection = &self.ws_connections[index];
+                    if !ws_connection.is_enabled() {
+                        continue;
+                    }
+                    ws_connection.rx()
+                }
+            };
+            if let Ok(msg) = rx.try_recv() {
+                return Some((rx.source().clone(), msg));
             }
-        };
-    });
-}
+        }
+        None
+    }
 
-impl App {
     fn receive_messages(&mut self, egui_ctx: &egui::Context) {
         crate::profile_function!();
 
+        if self.ingestion_paused {
+            // Keep draining the channels so that the senders aren't held up.
+            while let Some((source, msg)) = self.try_recv() {
+                self.paused_messages.push_back(source, msg);
+            }
+            return;
+        }
+
         let start = instant::Instant::now();
 
-        while let Ok(msg) = self.rx.try_recv() {
+        // Catch up on whatever arrived while we were paused before taking in anything new:
+        while let Some((source, msg)) = self.paused_messages.pop_front().or_else(|| self.try_recv())
+        {
             let is_new_recording = if let LogMsg::BeginRecordingMsg(msg) = &msg {
                 re_log::debug!("Opening a new recording: {:?}", msg.info);
                 self.state.selected_rec_id = msg.info.recording_id;
+                self.recording_of_source
+                    .insert(source.clone(), msg.info.clone());
                 true
             } else {
                 false
             };
+            let source_info = self.recording_of_source.get(&source);
+            let rec_id = source_info.map_or(self.state.selected_rec_id, |info| info.recording_id);
+            let application_id = source_info.map(|info| info.application_id.clone());
 
-            let log_db = self.log_dbs.entry(self.state.selected_rec_id).or_default();
+            let blueprint_op = if let LogMsg::BlueprintMsg(msg) = &msg {
+                Some(msg.op.clone())
+            } else {
+                None
+            };
+
+            let log_db = self.log_dbs.entry(rec_id).or_default();
 
             if log_db.data_source.is_none() {
-                log_db.data_source = Some(self.rx.source().clone());
+                log_db.data_source = Some(source);
+            }
+
+            if let Some(rec_cfg) = self.state.recording_configs.get_mut(&rec_id) {
+                log_db
+                    .entity_db
+                    .data_store
+                    .set_deduplicated_entities(&rec_cfg.deduplicated_entities);
+
+                for text in rec_cfg
+                    .alerts
+                    .on_message(&self.state.app_options.alert_rules, &msg)
+                {
+                    self.toasts.warning(text);
+                }
             }
 
-            if let Err(err) = log_db.add(msg) {
+            self.state.shared_annotation_contexts.on_message(&msg);
+
+            #[cfg(not(target_arch = "wasm32"))]
+            self.auto_saver.on_message(
+                self.state.app_options.auto_save_directory.as_deref(),
+                rec_id,
+                &msg,
+            );
+
+            let result = match &application_id {
+                Some(application_id) => log_db.add_from(application_id, msg),
+                None => log_db.add(msg),
+            };
+            if let Err(err) = result {
                 re_log::error!("Failed to add incoming msg: {err}");
             };
 
+            if let Some(blueprint_op) = blueprint_op {
+                let app_id = log_db
+                    .recording_info()
+                    .map_or_else(ApplicationId::unknown, |rec_info| {
+                        rec_info.application_id.clone()
+                    });
+                let blueprint = self.state.blueprints.entry(app_id).or_default();
+                blueprint.viewport.apply_blueprint_op(&blueprint_op);
+            }
+
             if is_new_recording {
                 // Do analytics after ingesting the new message,
                 // because thats when the `log_db.recording_info` is set,
@@ -622,7 +1064,8 @@ impl App {
             }
 
             if start.elapsed() > instant::Duration::from_millis(10) {
-                egui_ctx.request_repaint(); // make sure we keep receiving messages asap
+                // make sure we keep receiving messages asap
+                egui_ctx.request_repaint_after(self.startup_options.min_repaint_interval());
                 break; // don't block the main thread for too long
             }
         }
@@ -660,6 +1103,26 @@ impl App {
         }
     }
 
+    fn check_frame_budget(&mut self, now: f64, frame_time: f32) {
+        let change =
+            self.frame_budget_watchdog
+                .update(&mut self.state.app_options, now, frame_time);
+        match change {
+            Some(QualityChange::Lowered(quality)) => {
+                self.toasts.warning(format!(
+                    "Frames take {:.0} ms, so the render quality was lowered to {quality}",
+                    frame_time * 1e3
+                ));
+            }
+            Some(QualityChange::Restored(quality)) => {
+                self.toasts.info(format!(
+                    "Frames are fast again: render quality is {quality}"
+                ));
+            }
+            None => {}
+        }
+    }
+
     fn purge_memory_if_needed(&mut self) {
         crate::profile_function!();
 
@@ -705,6 +1168,7 @@ impl App {
                 for log_db in self.log_dbs.values_mut() {
                     log_db.purge_fraction_of_ram(fraction_to_purge);
                 }
+                self.paused_messages.purge_fraction(fraction_to_purge);
                 self.state.cache.purge_memory();
             }
 
@@ -725,6 +1189,43 @@ impl App {
             self.latest_memory_purge = instant::Instant::now();
 
             self.memory_panel.note_memory_purge();
+
+            self.toasts.warning(format!(
+                "Dropped the oldest data to stay below the memory limit of {}. \
+                See the memory panel for details.",
+                format_limit(limit.limit)
+            ));
+        }
+    }
+
+    /// Evict the least recently used GPU cache entries if we are over the GPU memory budget.
+    ///
+    /// Must be called before [`Caches::new_frame`], so that whatever was used last frame is kept.
+    fn purge_gpu_memory_if_needed(&mut self, gpu_resource_stats: &WgpuResourcePoolStatistics) {
+        crate::profile_function!();
+
+        let Some(limit) = self.startup_options.gpu_memory_limit.limit else {
+            return;
+        };
+
+        if self.latest_gpu_memory_purge.elapsed() < instant::Duration::from_secs(1) {
+            // The pools only free what we evicted a few frames later,
+            // so until then the statistics still include it.
+            return;
+        }
+
+        let gpu_bytes_used = gpu_resource_stats.total_bytes() as i64;
+        if limit < gpu_bytes_used {
+            let bytes_to_free = (gpu_bytes_used - limit) as u64;
+            let bytes_freed = self.state.cache.evict_lru_gpu_memory(bytes_to_free);
+            self.latest_gpu_memory_purge = instant::Instant::now();
+
+            re_log::debug!(
+                "GPU memory budget of {} exceeded ({} used) - evicted {} of cached resources",
+                re_format::format_bytes(limit as _),
+                re_format::format_bytes(gpu_bytes_used as _),
+                re_format::format_bytes(bytes_freed as _),
+            );
         }
     }
 
@@ -748,14 +1249,163 @@ impl App {
     fn show_log_db(&mut self, log_db: LogDb) {
         self.analytics.on_open_recording(&log_db);
         self.state.selected_rec_id = log_db.recording_id();
+        for msg in log_db.chronological_log_messages() {
+            self.state.shared_annotation_contexts.on_message(msg);
+        }
         self.log_dbs.insert(log_db.recording_id(), log_db);
     }
 
+    /// Add the data of a loaded file to the recording we are showing,
+    /// or just show it if there is nothing to merge it with.
+    fn merge_log_db(&mut self, log_db: LogDb) {
+        if self.log_db().is_empty() {
+            self.show_log_db(log_db);
+            return;
+        }
+
+        for msg in log_db.chronological_log_messages() {
+            self.state.shared_annotation_contexts.on_message(msg);
+        }
+        if let Err(err) = self.log_db().merge(&log_db) {
+            re_log::error!("Failed to merge recordings: {err}");
+        }
+    }
+
+    fn show_or_merge_log_db(&mut self, log_db: LogDb, merge: bool) {
+        if merge {
+            self.merge_log_db(log_db);
+        } else {
+            self.show_log_db(log_db);
+        }
+    }
+
+    /// Load a `.rrd` file and show it, remembering it as a recent file.
+    ///
+    /// With `merge`, its data is added to the recording we are showing instead.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn open_file_path(&mut self, path: &std::path::Path, merge: bool) {
+        if let Some(log_db) = load_file_path(path) {
+            self.state.recent.add(RecentItem::File(path.to_owned()));
+            self.show_or_merge_log_db(log_db, merge);
+        }
+    }
+
+    /// Start receiving data from the Rerun Server at the given websocket url,
+    /// in addition to any other servers we are connected to.
+    ///
+    /// Without an `auth_token`, we use the one given at startup, if any.
+    /// If we are already connected to it, we switch to the given `auth_token`.
+    fn connect_to_ws_server(&mut self, url: String, auth_token: Option<String>) {
+        if let Some(ws_connection) = self
+            .ws_connections
+            .iter_mut()
+            .find(|ws_connection| ws_connection.url() == url)
+        {
+            if auth_token.is_some() {
+                ws_connection.set_auth_token(auth_token);
+            }
+            ws_connection.set_enabled(true);
+            return;
+        }
+
+        let mut options = self.startup_options.ws_connection_options.clone();
+        if auth_token.is_some() {
+            options.auth_token = auth_token;
+        }
+        match WsConnection::new(
+            url.clone(),
+            options,
+            self.re_ui.egui_ctx.clone(),
+            self.startup_options.min_repaint_interval(),
+        ) {
+            Ok(connection) => {
+                self.state.recent.add(RecentItem::Connection(url));
+                self.ws_connections.push(connection);
+            }
+            Err(err) => {
+                re_log::error!("Failed to connect to {url:?}: {}", re_error::format(&err));
+            }
+        }
+    }
+
+    fn on_welcome_screen_action(&mut self, action: WelcomeScreenAction) {
+        match action {
+            #[cfg(not(target_arch = "wasm32"))]
+            WelcomeScreenAction::OpenExample(example) => self.download_example(example),
+            #[cfg(target_arch = "wasm32")]
+            WelcomeScreenAction::OpenExample(_) => {} // The buttons are disabled on web
+            WelcomeScreenAction::ConnectToServer { url, auth_token } => {
+                self.connect_to_ws_server(url, auth_token);
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            WelcomeScreenAction::OpenFile => self.pending_commands.push(Command::Open),
+            #[cfg(target_arch = "wasm32")]
+            WelcomeScreenAction::OpenFile => {} // The button is hidden on web
+            WelcomeScreenAction::OpenRecent(item) => self.open_recent(item),
+        }
+    }
+
+    /// Download an example recording in the background, and show it once done.
+    ///
+    /// See [`Self::poll_example_download`].
+    #[cfg(not(target_arch = "wasm32"))]
+    fn download_example(&mut self, example: &'static ExampleRecording) {
+        let url = example.url();
+        re_log::info!("Downloading {url:?}…");
+
+        let f = move || download_bytes(&url).map(|bytes| (example.name, bytes));
+        if let Err(err) = self.spawn_threaded_promise(EXAMPLE_DOWNLOAD_PROMISE, f) {
+            re_log::error!("Failed to download example recording: {err}");
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn poll_example_download(&mut self) {
+        type DownloadResult = anyhow::Result<(&'static str, Vec<u8>)>;
+
+        match self.poll_promise::<DownloadResult>(EXAMPLE_DOWNLOAD_PROMISE) {
+            Some(Ok((name, bytes))) => {
+                if let Some(log_db) = load_file_contents(name, bytes.as_slice()) {
+                    self.show_log_db(log_db);
+                }
+            }
+            Some(Err(err)) => {
+                let msg = format!(
+                    "Failed to download example recording: {}",
+                    re_error::format(&err)
+                );
+                re_log::error!("{msg}");
+                self.toasts.error(msg);
+            }
+            None => {}
+        }
+    }
+
+    fn open_recent(&mut self, item: RecentItem) {
+        match item {
+            #[cfg(not(target_arch = "wasm32"))]
+            RecentItem::File(path) => {
+                if path.exists() {
+                    self.open_file_path(&path, false);
+                } else {
+                    re_log::warn!("{path:?} no longer exists");
+                    self.state.recent.remove(&RecentItem::File(path));
+                }
+            }
+            #[cfg(target_arch = "wasm32")]
+            RecentItem::File(_) => {} // We only remember absolute paths, which we never have on web
+            RecentItem::Connection(url) => self.connect_to_ws_server(url, None),
+        }
+    }
+
     fn handle_dropping_files(&mut self, egui_ctx: &egui::Context) {
         preview_files_being_dropped(egui_ctx);
 
+        // Holding shift while dropping adds the data to the current recording:
+        let merge = egui_ctx.input(|i| i.modifiers.shift);
+
         // Collect dropped files:
-        if egui_ctx.input(|i| i.raw.dropped_files.len()) > 2 {
+        if egui_ctx.input(|i| i.raw.dropped_files.len()) > 1 {
             rfd::MessageDialog::new()
                 .set_level(rfd::MessageLevel::Error)
                 .set_description("Can only load one file at a time")
@@ -765,7 +1415,7 @@ impl App {
             if let Some(bytes) = &file.bytes {
                 let mut bytes: &[u8] = &(*bytes)[..];
                 if let Some(log_db) = load_file_contents(&file.name, &mut bytes) {
-                    self.show_log_db(log_db);
+                    self.show_or_merge_log_db(log_db, merge);
 
                     #[allow(clippy::needless_return)] // false positive on wasm32
                     return;
@@ -774,9 +1424,7 @@ impl App {
 
             #[cfg(not(target_arch = "wasm32"))]
             if let Some(path) = &file.path {
-                if let Some(log_db) = load_file_path(path) {
-                    self.show_log_db(log_db);
-                }
+                self.open_file_path(path, merge);
             }
         }
     }
@@ -789,7 +1437,11 @@ fn preview_files_being_dropped(egui_ctx: &egui::Context) {
     if !egui_ctx.input(|i| i.raw.hovered_files.is_empty()) {
         use std::fmt::Write as _;
 
-        let mut text = "Drop to load:\n".to_owned();
+        let mut text = if egui_ctx.input(|i| i.modifiers.shift) {
+            "Drop to add to the current recording:\n".to_owned()
+        } else {
+            "Drop to load (hold shift to add to the current recording):\n".to_owned()
+        };
         egui_ctx.input(|input| {
             for file in &input.raw.hovered_files {
                 if let Some(path) = &file.path {
@@ -825,6 +1477,14 @@ enum PanelSelection {
     EventLog,
 }
 
+/// The storage key of the per-application blueprints, which are kept apart from the user settings
+/// in [`AppState`] so that they can be reset or shared on their own.
+///
+/// Only used on the web, and if the files of the native viewer can't be written
+/// (see `blueprint_file::stored_blueprints_dir`).
+/// Natively, it is still read to keep the layouts stored by older versions.
+const BLUEPRINTS_KEY: &str = "blueprints";
+
 #[derive(Default, serde::Deserialize, serde::Serialize)]
 #[serde(default)]
 struct AppState {
@@ -840,8 +1500,22 @@ struct AppState {
     /// Configuration for the current recording (found in [`LogDb`]).
     recording_configs: IntMap<RecordingId, RecordingConfig>,
 
+    /// Stored apart from the rest, under [`BLUEPRINTS_KEY`].
+    ///
+    /// Still read from here, to keep the layouts stored by older versions.
+    #[serde(skip_serializing)]
     blueprints: HashMap<ApplicationId, crate::ui::Blueprint>,
 
+    /// Per application, like the blueprints.
+    #[serde(skip_serializing)]
+    entity_display_names: HashMap<ApplicationId, EntityDisplayNames>,
+
+    /// Recently opened files and server connections.
+    recent: RecentItems,
+
+    /// Annotation contexts that recordings can refer to by hash.
+    shared_annotation_contexts: SharedAnnotationContexts,
+
     /// Which view panel is currently being shown
     panel_selection: PanelSelection,
 
@@ -875,6 +1549,9 @@ impl AppState {
             panel_selection,
             event_log_view,
             blueprints,
+            entity_display_names,
+            recent: _,
+            shared_annotation_contexts,
             selection_panel,
             time_panel,
             #[cfg(not(target_arch = "wasm32"))]
@@ -883,6 +1560,7 @@ impl AppState {
 
         let rec_cfg =
             recording_config_entry(recording_configs, *selected_rec_id, data_source, log_db);
+        rec_cfg.clock_sync.update(log_db);
         let selected_app_id = log_db
             .recording_info()
             .map_or_else(ApplicationId::unknown, |rec_info| {
@@ -897,6 +1575,10 @@ impl AppState {
             rec_cfg,
             re_ui,
             render_ctx,
+            shared_annotation_contexts,
+            entity_display_names: entity_display_names
+                .entry(selected_app_id.clone())
+                .or_default(),
         };
 
         let blueprint = blueprints.entry(selected_app_id.clone()).or_default();
@@ -1022,9 +1704,15 @@ fn rerun_menu_button_ui(ui: &mut egui::Ui, _frame: &mut eframe::Frame, app: &mut
         #[cfg(not(target_arch = "wasm32"))]
         {
             Command::Open.menu_button_ui(ui, &mut app.pending_commands);
+            Command::OpenAndMerge.menu_button_ui(ui, &mut app.pending_commands);
 
             save_buttons_ui(ui, app);
 
+            Command::ExportBlueprint.menu_button_ui(ui, &mut app.pending_commands);
+            Command::ImportBlueprint.menu_button_ui(ui, &mut app.pending_commands);
+
+            auto_save_ui(ui, &mut app.state.app_options);
+
             ui.add_space(spacing);
 
             // On the web the browser controls the zoom
@@ -1042,6 +1730,12 @@ fn rerun_menu_button_ui(ui: &mut egui::Ui, _frame: &mut eframe::Frame, app: &mut
             Command::OpenProfiler.menu_button_ui(ui, &mut app.pending_commands);
 
             Command::ToggleMemoryPanel.menu_button_ui(ui, &mut app.pending_commands);
+
+            #[cfg(not(target_arch = "wasm32"))]
+            Command::ToggleDataSourcesPanel.menu_button_ui(ui, &mut app.pending_commands);
+
+            Command::ToggleMessageInspector.menu_button_ui(ui, &mut app.pending_commands);
+            Command::CopyBookmarkLink.menu_button_ui(ui, &mut app.pending_commands);
         }
 
         ui.add_space(spacing);
@@ -1050,6 +1744,20 @@ fn rerun_menu_button_ui(ui: &mut egui::Ui, _frame: &mut eframe::Frame, app: &mut
             recordings_menu(ui, app);
         });
 
+        ui.menu_button("Open recent", |ui| {
+            recent_menu(ui, app);
+        });
+
+        if !app.ws_connections.is_empty() {
+            ui.menu_button("Servers", |ui| {
+                servers_menu(ui, app);
+            });
+        }
+
+        ui.menu_button("Options", |ui| {
+            options_menu(&mut app.state.app_options, ui);
+        });
+
         #[cfg(debug_assertions)]
         ui.menu_button("Debug", |ui| {
             debug_menu(&mut app.state.app_options, ui);
@@ -1078,6 +1786,11 @@ fn top_bar_ui(
         input_latency_label_ui(ui, app);
     }
 
+    pause_ingestion_ui(ui, app);
+    connection_status_ui(ui, app);
+    degraded_streams_ui(ui, app);
+    clock_skew_ui(ui, app);
+
     if let Some(log_db) = app.log_dbs.get(&app.state.selected_rec_id) {
         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
             let selected_app_id = log_db
@@ -1186,6 +1899,190 @@ fn memory_use_label_ui(ui: &mut egui::Ui, gpu_resource_stats: &WgpuResourcePoolS
     }
 }
 
+/// Lets the user freeze a live stream, to inspect a moment without the view drifting.
+fn pause_ingestion_ui(ui: &mut egui::Ui, app: &mut App) {
+    if !app.rx.source().is_live() && !app.ingestion_paused && app.paused_messages.is_empty() {
+        return;
+    }
+
+    ui.separator();
+
+    if app.ingestion_paused {
+        if app
+            .re_ui
+            .small_icon_button(ui, &re_ui::icons::PLAY)
+            .on_hover_text("Resume, and catch up on the data that arrived while paused")
+            .clicked()
+        {
+            app.ingestion_paused = false;
+        }
+        let num_dropped = app.paused_messages.num_dropped();
+        let text = if num_dropped == 0 {
+            format!(
+                "Paused, {} messages held back",
+                format_number(app.paused_messages.len())
+            )
+        } else {
+            format!(
+                "Paused, {} messages held back, {} dropped",
+                format_number(app.paused_messages.len()),
+                format_number(num_dropped)
+            )
+        };
+        ui.label(app.re_ui.warning_text(text)).on_hover_text(
+            "Incoming data is kept in memory until you resume.\n\
+            When it takes up too much memory, the oldest is dropped.",
+        );
+    } else {
+        if app
+            .re_ui
+            .small_icon_button(ui, &re_ui::icons::PAUSE)
+            .on_hover_text(
+                "Pause the live stream.\n\
+                Incoming data is held back until you resume, so the view doesn't change while you inspect it.",
+            )
+            .clicked()
+        {
+            app.ingestion_paused = true;
+            app.paused_messages.reset_num_dropped();
+        }
+        if !app.paused_messages.is_empty() {
+            ui.weak(format!(
+                "Catching up: {} messages",
+                format_number(app.paused_messages.len())
+            ));
+        }
+    }
+}
+
+fn connection_status_ui(ui: &mut egui::Ui, app: &mut App) {
+    let num_connections = app.ws_connections.len();
+    for ws_connection in &mut app.ws_connections {
+        let mut text = match ws_connection.status() {
+            WsConnectionStatus::Connected | WsConnectionStatus::Disabled => continue,
+            WsConnectionStatus::Connecting => "Connecting…".to_owned(),
+            WsConnectionStatus::WaitingToReconnect { seconds_left } => {
+                // Count down:
+                ui.ctx()
+                    .request_repaint_after(std::time::Duration::from_secs(1));
+                format!("Disconnected - reconnecting in {}s", seconds_left.ceil())
+            }
+        };
+        if 1 < num_connections {
+            text = format!("{}: {text}", ws_connection.url());
+        }
+
+        ui.separator();
+        ui.label(app.re_ui.warning_text(text))
+            .on_hover_text(format!(
+                "Not connected to the Rerun Server at {:?}.\n\
+                Everything received so far is kept, and streaming resumes once we are connected.",
+                ws_connection.url()
+            ));
+        if matches!(
+            ws_connection.status(),
+            WsConnectionStatus::WaitingToReconnect { .. }
+        ) && ui.small_button("Reconnect now").clicked()
+        {
+            ws_connection.reconnect_now();
+        }
+    }
+}
+
+fn degraded_streams_ui(ui: &mut egui::Ui, app: &App) {
+    let degraded_streams = app
+        .ws_connections
+        .iter()
+        .flat_map(|ws_connection| ws_connection.degraded_streams().get())
+        .collect::<Vec<_>>();
+    if degraded_streams.is_empty() {
+        return;
+    }
+
+    // The server tells us when we have caught up, but we only hear about it on the next frame:
+    ui.ctx()
+        .request_repaint_after(std::time::Duration::from_secs(1));
+
+    ui.separator();
+    let text = format!("Reduced quality: {} streams", degraded_streams.len());
+    let hover_text = format!(
+        "The Rerun Viewer can't keep up with the data, so the server is only sending \
+        the latest images, and no text logs, for:\n{}",
+        degraded_streams
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("\n")
+    );
+    ui.label(app.re_ui.warning_text(text))
+        .on_hover_text(hover_text);
+}
+
+/// Warns about data that is held back because of bad timestamps,
+/// and lets the user correct the clock of the application that logged it.
+fn clock_skew_ui(ui: &mut egui::Ui, app: &mut App) {
+    let Some(log_db) = app.log_dbs.get_mut(&app.state.selected_rec_id) else {
+        return;
+    };
+    let skewed = log_db
+        .clock_skew()
+        .skewed_applications()
+        .map(|(application_id, skewed_data)| {
+            (
+                application_id.clone(),
+                skewed_data.msg_ids.len(),
+                skewed_data.num_dropped,
+                skewed_data.suggested_correction,
+            )
+        })
+        .collect::<Vec<_>>();
+    if skewed.is_empty() {
+        return;
+    }
+
+    ui.separator();
+    let num_messages: usize = skewed
+        .iter()
+        .map(|(_, num_messages, _, _)| num_messages)
+        .sum();
+    let text = format!(
+        "Clock skew: {} messages held back",
+        format_number(num_messages)
+    );
+    ui.menu_button(app.re_ui.warning_text(text), |ui| {
+        ui.label(
+            "Some data has timestamps far off from the rest, e.g. because of a bad clock.\n\
+            It is held back, so that it doesn't stretch the timelines.",
+        );
+        egui::Grid::new("clock_skew").num_columns(3).show(ui, |ui| {
+            for (application_id, num_messages, num_dropped, suggested_correction) in &skewed {
+                ui.label(application_id.to_string());
+                if *num_dropped == 0 {
+                    ui.label(format!("{} messages", format_number(*num_messages)));
+                } else {
+                    ui.label(format!(
+                        "{} messages ({} older ones dropped to free up memory)",
+                        format_number(*num_messages),
+                        format_number(*num_dropped)
+                    ));
+                }
+                if ui
+                    .button(format!("Shift by {suggested_correction}"))
+                    .on_hover_text(
+                        "Add this to the timestamps of the held back data, \
+                        and of all later data from this application",
+                    )
+                    .clicked()
+                {
+                    log_db.correct_clock(application_id, *suggested_correction);
+                    ui.close_menu();
+                }
+                ui.end_row();
+            }
+        });
+    });
+}
+
 fn input_latency_label_ui(ui: &mut egui::Ui, app: &mut App) {
     // TODO(emilk): it would be nice to know if the network stream is still open
     let is_latency_interesting = app.rx.source().is_network();
@@ -1228,6 +2125,14 @@ fn input_latency_label_ui(ui: &mut egui::Ui, app: &mut App) {
 
 // ----------------------------------------------------------------------------
 
+const EXAMPLE_DOWNLOAD_PROMISE: &str = "example_download";
+
+#[cfg(not(target_arch = "wasm32"))]
+fn download_bytes(url: &str) -> anyhow::Result<Vec<u8>> {
+    let response = reqwest::blocking::get(url)?.error_for_status()?;
+    Ok(response.bytes()?.to_vec())
+}
+
 const FILE_SAVER_PROMISE: &str = "file_saver";
 const FILE_SAVER_NOTIF_DURATION: Option<std::time::Duration> =
     Some(std::time::Duration::from_secs(4));
@@ -1326,14 +2231,38 @@ fn save_buttons_ui(ui: &mut egui::Ui, app: &mut App) {
 }
 
 #[cfg(not(target_arch = "wasm32"))]
-fn open(app: &mut App) {
+fn auto_save_ui(ui: &mut egui::Ui, options: &mut AppOptions) {
+    let mut auto_save = options.auto_save_directory.is_some();
+    if ui
+        .checkbox(&mut auto_save, "Auto-save")
+        .on_hover_text(
+            "Write everything received to Rerun data files (.rrd) in a folder of your choice, \
+            one per recording, as it arrives.",
+        )
+        .changed()
+    {
+        ui.close_menu();
+        options.auto_save_directory = if auto_save {
+            rfd::FileDialog::new()
+                .set_title("Auto-save recordings to")
+                .pick_folder()
+        } else {
+            None
+        };
+    }
+
+    if let Some(directory) = &options.auto_save_directory {
+        ui.weak(directory.display().to_string());
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn open(app: &mut App, merge: bool) {
     if let Some(path) = rfd::FileDialog::new()
         .add_filter("rerun data file", &["rrd"])
         .pick_file()
     {
-        if let Some(log_db) = load_file_path(&path) {
-            app.show_log_db(log_db);
-        }
+        app.open_file_path(&path, merge);
     }
 }
 
@@ -1361,6 +2290,59 @@ fn save(app: &mut App, loop_selection: Option<(re_data_store::Timeline, TimeRang
     }
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+fn export_blueprint(app: &mut App) {
+    use crate::misc::blueprint_file;
+
+    let app_id = app.selected_app_id();
+    if let Some(path) = rfd::FileDialog::new()
+        .set_file_name(&format!("{app_id}.{}", blueprint_file::FILE_EXTENSION))
+        .add_filter("Rerun blueprint", &[blueprint_file::FILE_EXTENSION])
+        .set_title("Export blueprint")
+        .save_file()
+    {
+        let blueprint = app.state.blueprints.entry(app_id.clone()).or_default();
+        let entity_display_names = app.state.entity_display_names.entry(app_id).or_default();
+        match blueprint_file::save(&path, blueprint, entity_display_names) {
+            Ok(()) => re_log::info!("Blueprint exported to {path:?}"),
+            Err(err) => {
+                app.toasts.error(format!(
+                    "Failed to export blueprint: {}",
+                    re_error::format(&err)
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn import_blueprint(app: &mut App) {
+    use crate::misc::blueprint_file;
+
+    if let Some(path) = rfd::FileDialog::new()
+        .add_filter("Rerun blueprint", &[blueprint_file::FILE_EXTENSION])
+        .set_title("Import blueprint")
+        .pick_file()
+    {
+        match blueprint_file::load(&path) {
+            Ok(file) => {
+                let app_id = app.selected_app_id();
+                re_log::info!("Loaded blueprint for {app_id} from {path:?}");
+                app.state.blueprints.insert(app_id.clone(), file.blueprint);
+                app.state
+                    .entity_display_names
+                    .insert(app_id, file.entity_display_names);
+            }
+            Err(err) => {
+                app.toasts.error(format!(
+                    "Failed to import blueprint: {}",
+                    re_error::format(&err)
+                ));
+            }
+        }
+    }
+}
+
 fn main_view_selector_ui(ui: &mut egui::Ui, app: &mut App) {
     if !app.log_db().is_empty() {
         ui.horizontal(|ui| {
@@ -1389,11 +2371,23 @@ fn main_view_selector_ui(ui: &mut egui::Ui, app: &mut App) {
     }
 }
 
+/// Lists all recordings, grouped by application id.
+///
+/// Each application has its own blueprint, so switching to a recording of a different
+/// application also switches the layout.
 fn recordings_menu(ui: &mut egui::Ui, app: &mut App) {
+    let app_id = |log_db: &LogDb| {
+        log_db
+            .recording_info()
+            .map_or_else(ApplicationId::unknown, |rec_info| {
+                rec_info.application_id.clone()
+            })
+    };
+
     let log_dbs = app
         .log_dbs
         .values()
-        .sorted_by_key(|log_db| log_db.recording_info().map(|ri| ri.started))
+        .sorted_by_key(|log_db| (app_id(log_db), log_db.recording_info().map(|ri| ri.started)))
         .collect_vec();
 
     if log_dbs.is_empty() {
@@ -1402,23 +2396,307 @@ fn recordings_menu(ui: &mut egui::Ui, app: &mut App) {
     }
 
     ui.style_mut().wrap = Some(false);
-    for log_db in log_dbs {
-        let info = if let Some(rec_info) = log_db.recording_info() {
-            format!(
-                "{} - {}",
-                rec_info.application_id,
-                rec_info.started.format()
-            )
-        } else {
-            "<UNKNOWN>".to_owned()
+    for (app_id, log_dbs) in &log_dbs.into_iter().group_by(|log_db| app_id(log_db)) {
+        ui.strong(app_id.to_string());
+        ui.indent(&app_id, |ui| {
+            for log_db in log_dbs {
+                let info = if let Some(rec_info) = log_db.recording_info() {
+                    rec_info
+                        .started
+                        .format_with(&app.state.app_options.time_format)
+                } else {
+                    "<UNKNOWN>".to_owned()
+                };
+                if ui
+                    .radio(app.state.selected_rec_id == log_db.recording_id(), info)
+                    .clicked()
+                {
+                    app.state.selected_rec_id = log_db.recording_id();
+                }
+            }
+        });
+    }
+}
+
+/// The Rerun Servers we receive from, each of which can be switched off.
+fn servers_menu(ui: &mut egui::Ui, app: &mut App) {
+    ui.style_mut().wrap = Some(false);
+
+    for ws_connection in &mut app.ws_connections {
+        let mut enabled = ws_connection.is_enabled();
+        let status = match ws_connection.status() {
+            WsConnectionStatus::Connected => "connected".to_owned(),
+            WsConnectionStatus::Connecting => "connecting…".to_owned(),
+            WsConnectionStatus::WaitingToReconnect { seconds_left } => {
+                format!("reconnecting in {}s", seconds_left.ceil())
+            }
+            WsConnectionStatus::Disabled => "off".to_owned(),
         };
         if ui
-            .radio(app.state.selected_rec_id == log_db.recording_id(), info)
-            .clicked()
+            .checkbox(&mut enabled, format!("{} ({status})", ws_connection.url()))
+            .on_hover_text(
+                "Switch off to disconnect and ignore this server. \
+                When switched back on, we catch up on what it sent in the meantime.",
+            )
+            .changed()
         {
-            app.state.selected_rec_id = log_db.recording_id();
+            ws_connection.set_enabled(enabled);
         }
     }
+
+    ui.separator();
+
+    // The url and auth token being typed in:
+    let id = ui.id().with("new_server");
+    let (mut url, mut auth_token) = ui
+        .data_mut(|data| data.get_temp::<(String, String)>(id))
+        .unwrap_or_default();
+    let url_response = ui
+        .add(egui::TextEdit::singleline(&mut url).hint_text("ws://localhost:9877"))
+        .on_hover_text("Also connect to this Rerun Server. Press enter to connect.");
+    let token_response = ui
+        .add(
+            egui::TextEdit::singleline(&mut auth_token)
+                .password(true)
+                .hint_text("Auth token, if required"),
+        )
+        .on_hover_text(
+            "Present this token to the server. \
+            Enter the url of a server we are already connected to, to change its token.",
+        );
+    if (url_response.lost_focus() || token_response.lost_focus())
+        && ui.input(|i| i.key_pressed(egui::Key::Enter))
+        && !url.trim().is_empty()
+    {
+        let url = std::mem::take(&mut url).trim().to_owned();
+        let auth_token = std::mem::take(&mut auth_token);
+        ui.close_menu();
+        app.connect_to_ws_server(url, (!auth_token.is_empty()).then_some(auth_token));
+    }
+    ui.data_mut(|data| data.insert_temp(id, (url, auth_token)));
+}
+
+fn recent_menu(ui: &mut egui::Ui, app: &mut App) {
+    if app.state.recent.is_empty() {
+        ui.weak("(empty)");
+        return;
+    }
+
+    ui.style_mut().wrap = Some(false);
+    if let Some(item) = app.state.recent.ui(ui) {
+        ui.close_menu();
+        app.open_recent(item);
+    }
+}
+
+fn options_menu(options: &mut AppOptions, ui: &mut egui::Ui) {
+    ui.style_mut().wrap = Some(false);
+
+    if ui
+        .checkbox(&mut options.show_data_age, "Show data age")
+        .on_hover_text(format!(
+            "Show how old the data in each live Space View is.\n\
+            Data older than {:.1}s is highlighted.",
+            options.warn_data_age
+        ))
+        .clicked()
+    {
+        ui.close_menu();
+    }
+
+    ui.horizontal(|ui| {
+        ui.label("Stalled after").on_hover_text(
+            "Mark live streams that stop producing data (e.g. because a sensor driver \
+            crashed) after this long. Zero to never do so.",
+        );
+        ui.add(
+            egui::DragValue::new(&mut options.stall_timeout)
+                .clamp_range(0.0..=600.0)
+                .speed(0.1)
+                .suffix(" s"),
+        );
+    });
+
+    ui.horizontal(|ui| {
+        ui.label("Render quality")
+            .on_hover_text("Trade rendering speed for image quality in the 2D and 3D views.");
+        ui.add_enabled_ui(options.render_quality_override.is_none(), |ui| {
+            egui::ComboBox::from_id_source("render_quality")
+                .selected_text(options.render_quality().to_string())
+                .show_ui(ui, |ui| {
+                    for quality in crate::RenderQuality::ALL {
+                        ui.selectable_value(
+                            &mut options.render_quality,
+                            quality,
+                            quality.to_string(),
+                        );
+                    }
+                })
+                .response
+                .on_disabled_hover_text("Set with --render-quality on the command line");
+        });
+    });
+
+    ui.horizontal(|ui| {
+        ui.label("Frame budget").on_hover_text(
+            "Lower the render quality while frames take longer than this, \
+            and restore it once they are fast again. Zero to never do so.",
+        );
+        let mut budget_ms = options.frame_budget * 1e3;
+        if ui
+            .add(
+                egui::DragValue::new(&mut budget_ms)
+                    .clamp_range(0.0..=1000.0)
+                    .speed(1.0)
+                    .suffix(" ms"),
+            )
+            .changed()
+        {
+            options.frame_budget = budget_ms / 1e3;
+        }
+    });
+    if let Some(fallback) = options.render_quality_fallback {
+        ui.label(
+            egui::RichText::new(format!("Lowered to {fallback} because frames are slow")).weak(),
+        );
+    }
+
+    ui.horizontal(|ui| {
+        ui.label("Numbers").on_hover_text(
+            "How numbers are shown in tooltips, the selection panel and plots.\n\
+            Can be overridden per Space View.",
+        );
+        options.number_format.ui(ui, "number_format");
+    });
+
+    ui.menu_button("Time format", |ui| {
+        time_format_menu(&mut options.time_format, ui);
+    });
+
+    ui.menu_button("Visible history", |ui| {
+        options.visible_history.ui(ui);
+    });
+
+    ui.menu_button("Alerts", |ui| {
+        alerts_menu(&mut options.alert_rules, ui);
+    });
+}
+
+fn time_format_menu(time_format: &mut re_log_types::TimeFormatOptions, ui: &mut egui::Ui) {
+    let re_log_types::TimeFormatOptions {
+        timezone,
+        precision,
+        absolute_threshold,
+        time_scale,
+    } = time_format;
+
+    egui::Grid::new("time_format")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Time zone");
+            egui::ComboBox::from_id_source("timezone")
+                .selected_text(timezone.to_string())
+                .show_ui(ui, |ui| {
+                    for value in re_log_types::TimeZone::ALL {
+                        ui.selectable_value(timezone, value, value.to_string());
+                    }
+                });
+            ui.end_row();
+
+            ui.label("Precision")
+                .on_hover_text("Fractions of a second to show");
+            egui::ComboBox::from_id_source("time_precision")
+                .selected_text(precision.to_string())
+                .show_ui(ui, |ui| {
+                    for value in re_log_types::TimePrecision::ALL {
+                        ui.selectable_value(precision, value, value.to_string());
+                    }
+                });
+            ui.end_row();
+
+            ui.label("Dates after").on_hover_text(
+                "Times this long after 1970 are shown as dates. \
+                Earlier times are shown relative to 1970, e.g. as seconds since a process started.",
+            );
+            let mut years = absolute_threshold.as_secs_f64() / (365.0 * 24.0 * 60.0 * 60.0);
+            if ui
+                .add(
+                    egui::DragValue::new(&mut years)
+                        .clamp_range(0.0..=100.0)
+                        .speed(0.1)
+                        .suffix(" years"),
+                )
+                .changed()
+            {
+                let secs = years * 365.0 * 24.0 * 60.0 * 60.0;
+                *absolute_threshold =
+                    re_log_types::Duration::from_nanos((secs * 1e9).round() as i64);
+            }
+            ui.end_row();
+
+            ui.label("Time scale").on_hover_text(
+                "What the logged timestamps count. \
+                Pick TAI or GPS time for recordings from systems with such clocks, \
+                to show them in UTC with the right leap seconds.",
+            );
+            egui::ComboBox::from_id_source("time_scale")
+                .selected_text(time_scale.to_string())
+                .show_ui(ui, |ui| {
+                    for value in re_log_types::TimeScale::ALL {
+                        ui.selectable_value(time_scale, value, value.to_string());
+                    }
+                });
+            ui.end_row();
+        });
+}
+
+fn alerts_menu(rules: &mut Vec<crate::misc::alerts::AlertRule>, ui: &mut egui::Ui) {
+    ui.label("Notify when incoming data meets any of these rules:");
+
+    let mut removed = None;
+    for (index, rule) in rules.iter().enumerate() {
+        ui.horizontal(|ui| {
+            if ui.small_button("❌").on_hover_text("Remove rule").clicked() {
+                removed = Some(index);
+            }
+            ui.monospace(rule.to_string());
+        });
+    }
+    if let Some(index) = removed {
+        rules.remove(index);
+    }
+
+    // The rule being typed in:
+    let id = ui.id().with("new_alert_rule");
+    let mut text = ui
+        .data_mut(|data| data.get_temp::<String>(id))
+        .unwrap_or_default();
+
+    let response = ui
+        .add(egui::TextEdit::singleline(&mut text).hint_text("robot/temperature > 80"))
+        .on_hover_text(
+            "An entity path followed by '> value', '< value' or 'silent seconds', \
+            e.g. 'robot/imu silent 5s'.\n\
+            Applies to the entity and everything under it. Press enter to add it.",
+        );
+
+    if !text.trim().is_empty() {
+        match text.parse::<crate::misc::alerts::AlertRule>() {
+            Ok(rule) => {
+                if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                    if !rules.contains(&rule) {
+                        rules.push(rule);
+                    }
+                    text.clear();
+                }
+            }
+            Err(err) => {
+                ui.colored_label(ui.visuals().warn_fg_color, err);
+            }
+        }
+    }
+
+    ui.data_mut(|data| data.insert_temp(id, text));
 }
 
 #[cfg(debug_assertions)]
@@ -1546,7 +2824,7 @@ fn save_database_to_file(
     path: std::path::PathBuf,
     time_selection: Option<(re_data_store::Timeline, TimeRangeF)>,
 ) -> impl FnOnce() -> anyhow::Result<std::path::PathBuf> {
-    use re_log_types::{EntityPathOpMsg, TimeInt};
+    use re_log_types::{EntityPathOpMsg, TimeRange, TimeType, TraceSpansMsg};
 
     let msgs = match time_selection {
         // Fast path: no query, just dump everything.
@@ -1558,20 +2836,31 @@ fn save_database_to_file(
 
         // Query path: time to filter!
         Some((timeline, range)) => {
-            use std::ops::RangeInclusive;
-            let range: RangeInclusive<TimeInt> = range.min.floor()..=range.max.ceil();
+            let range = range.to_outer_time_range();
             app.log_db()
                 .chronological_log_messages()
                 .filter(|msg| {
                     match msg {
-                        LogMsg::BeginRecordingMsg(_) | LogMsg::Goodbye(_) => {
+                        LogMsg::BeginRecordingMsg(_)
+                        | LogMsg::Goodbye(_)
+                        | LogMsg::BlueprintMsg(_) => {
                             true // timeless
                         }
+                        LogMsg::TraceSpansMsg(TraceSpansMsg { spans, .. }) => {
+                            // Spans are in wall-clock time, so can't be filtered by e.g. frame nr.
+                            timeline.typ() != TimeType::Time
+                                || spans.iter().any(|span| {
+                                    range.intersects(&TimeRange::new(
+                                        span.start.into(),
+                                        span.end.into(),
+                                    ))
+                                })
+                        }
                         LogMsg::EntityPathOpMsg(EntityPathOpMsg { time_point, .. }) => {
                             time_point.is_timeless() || {
                                 let is_within_range = time_point
                                     .get(&timeline)
-                                    .map_or(false, |t| range.contains(t));
+                                    .map_or(false, |t| range.contains(*t));
                                 is_within_range
                             }
                         }
@@ -1597,19 +2886,6 @@ fn save_database_to_file(
     }
 }
 
-#[allow(unused_mut)]
-fn load_rrd_to_log_db(mut read: impl std::io::Read) -> anyhow::Result<LogDb> {
-    crate::profile_function!();
-
-    let decoder = re_log_types::encoding::Decoder::new(read)?;
-
-    let mut log_db = LogDb::default();
-    for msg in decoder {
-        log_db.add(msg?)?;
-    }
-    Ok(log_db)
-}
-
 #[cfg(not(target_arch = "wasm32"))]
 #[must_use]
 fn load_file_path(path: &std::path::Path) -> Option<LogDb> {
@@ -1617,7 +2893,7 @@ fn load_file_path(path: &std::path::Path) -> Option<LogDb> {
         crate::profile_function!();
         use anyhow::Context as _;
         let file = std::fs::File::open(path).context("Failed to open file")?;
-        load_rrd_to_log_db(file)
+        LogDb::load_from(file)
     }
 
     re_log::info!("Loading {path:?}…");
@@ -1642,7 +2918,7 @@ fn load_file_path(path: &std::path::Path) -> Option<LogDb> {
 
 #[must_use]
 fn load_file_contents(name: &str, read: impl std::io::Read) -> Option<LogDb> {
-    match load_rrd_to_log_db(read) {
+    match LogDb::load_from(read) {
         Ok(mut log_db) => {
             re_log::info!("Loaded {name:?}");
             log_db.data_source = Some(re_smart_channel::Source::File { path: name.into() });
@@ -1682,7 +2958,8 @@ fn new_recording_confg(
         re_smart_channel::Source::File { .. } => PlayState::Playing,
 
         // Live data - follow it!
-        re_smart_channel::Source::Sdk
+        re_smart_channel::Source::FollowedFile { .. }
+        | re_smart_channel::Source::Sdk
         | re_smart_channel::Source::WsClient { .. }
         | re_smart_channel::Source::TcpServer { .. } => PlayState::Following,
     };
@@ -2,3 +2,8 @@
 ///
 /// `RERUN_TRACK_ALLOCATIONS=1`
 pub const RERUN_TRACK_ALLOCATIONS: &str = "RERUN_TRACK_ALLOCATIONS";
+
+/// Where to download the example recordings offered on the welcome screen from.
+///
+/// `RERUN_EXAMPLES_URL=https://example.com/rerun/examples`
+pub const RERUN_EXAMPLES_URL: &str = "RERUN_EXAMPLES_URL";
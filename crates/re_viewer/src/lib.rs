@@ -11,7 +11,7 @@ mod remote_viewer_app;
 mod ui;
 mod viewer_analytics;
 
-pub use self::misc::color_map;
+pub use self::misc::{color_map, Bookmark, RenderQuality};
 pub(crate) use misc::{mesh_loader, Item, TimeControl, TimeView, ViewerContext};
 use re_log_types::PythonVersion;
 pub(crate) use ui::{event_log_view, memory_panel, selection_panel, time_panel, UiVerbosity};
@@ -151,12 +151,19 @@ pub(crate) fn customize_eframe(cc: &eframe::CreationContext<'_>) -> re_ui::ReUi
 
 // ---------------------------------------------------------------------------
 
-/// This wakes up the ui thread each time we receive a new message.
+/// This wakes up the ui thread when we receive new messages.
+///
+/// Repaint requests are coalesced: if the ui thread has yet to drain earlier messages,
+/// it will get to the new ones in the same frame, so there is no need to wake it up again.
+///
+/// The ui thread is woken up at most once every `min_repaint_interval`
+/// (see [`StartupOptions::min_repaint_interval`]).
 #[cfg(not(feature = "web"))]
 #[cfg(not(target_arch = "wasm32"))]
 pub fn wake_up_ui_thread_on_each_msg<T: Send + 'static>(
     rx: re_smart_channel::Receiver<T>,
     ctx: egui::Context,
+    min_repaint_interval: std::time::Duration,
 ) -> re_smart_channel::Receiver<T> {
     // We need to intercept messages to wake up the ui thread.
     // For that, we need a new channel.
@@ -168,11 +175,12 @@ pub fn wake_up_ui_thread_on_each_msg<T: Send + 'static>(
         .name("ui_waker".to_owned())
         .spawn(move || {
             while let Ok((sent_at, msg)) = rx.recv_with_send_time() {
-                if tx.send_at(sent_at, msg).is_ok() {
-                    ctx.request_repaint();
-                } else {
+                if tx.send_at(sent_at, msg).is_err() {
                     break;
                 }
+                if tx.len() <= 1 {
+                    ctx.request_repaint_after(min_repaint_interval);
+                }
             }
             re_log::debug!("Shutting down ui_waker thread");
         })
@@ -0,0 +1,283 @@
+//! Simple alert rules on logged values, e.g. `robot/temperature > 80`,
+//! checked as the data arrives, for lightweight monitoring of live recordings.
+
+use std::collections::{HashMap, HashSet};
+
+use re_log_types::{
+    component_types::Scalar,
+    external::arrow2_convert::deserialize::arrow_array_deserialize_iterator,
+    msg_bundle::{Component as _, MsgBundle},
+    EntityPath, LogMsg, TimePoint,
+};
+
+/// Fires when the data logged to [`Self::entity_path`] meets [`Self::condition`].
+///
+/// Written as e.g. `robot/temperature > 80`, `robot/battery < 0.2` or `robot/imu silent 5s`.
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct AlertRule {
+    /// Applies to this entity and everything under it.
+    pub entity_path: EntityPath,
+
+    pub condition: AlertCondition,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum AlertCondition {
+    /// A [`Scalar`] above this value was logged.
+    Above(f64),
+
+    /// A [`Scalar`] below this value was logged.
+    Below(f64),
+
+    /// Nothing was logged for this many seconds. Only checked for live data.
+    Silent(f64),
+}
+
+impl AlertCondition {
+    fn is_met_by(&self, value: f64) -> bool {
+        match *self {
+            Self::Above(threshold) => threshold < value,
+            Self::Below(threshold) => value < threshold,
+            Self::Silent(_) => false,
+        }
+    }
+}
+
+impl AlertRule {
+    fn applies_to(&self, entity_path: &EntityPath) -> bool {
+        entity_path == &self.entity_path || entity_path.is_descendant_of(&self.entity_path)
+    }
+
+    pub fn is_silence(&self) -> bool {
+        matches!(self.condition, AlertCondition::Silent(_))
+    }
+}
+
+impl std::fmt::Display for AlertCondition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Above(threshold) => write!(f, "> {threshold}"),
+            Self::Below(threshold) => write!(f, "< {threshold}"),
+            Self::Silent(seconds) => write!(f, "silent {seconds}s"),
+        }
+    }
+}
+
+impl std::fmt::Display for AlertRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.entity_path, self.condition)
+    }
+}
+
+impl std::str::FromStr for AlertRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split_whitespace().collect();
+        let (entity_path, op, value) = match parts.as_slice() {
+            [entity_path, op, value] => (*entity_path, *op, *value),
+            _ => {
+                return Err(format!(
+                    "Expected e.g. 'robot/temperature > 80' or 'robot/imu silent 5s', got {s:?}"
+                ))
+            }
+        };
+
+        let parse_value = |value: &str| {
+            value
+                .parse::<f64>()
+                .map_err(|err| format!("Bad value {value:?}: {err}"))
+        };
+        let condition = match op {
+            ">" => AlertCondition::Above(parse_value(value)?),
+            "<" => AlertCondition::Below(parse_value(value)?),
+            "silent" => {
+                AlertCondition::Silent(parse_value(value.strip_suffix('s').unwrap_or(value))?)
+            }
+            _ => {
+                return Err(format!(
+                    "Unknown condition {op:?} - expected '>', '<' or 'silent'"
+                ))
+            }
+        };
+
+        Ok(Self {
+            entity_path: EntityPath::from(entity_path),
+            condition,
+        })
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// An alert that has fired, shown as a marker in the time panel.
+pub struct FiredAlert {
+    pub entity_path: EntityPath,
+
+    /// When the offending data was logged.
+    ///
+    /// For [`AlertCondition::Silent`] this is the last data before it went quiet.
+    pub time_point: TimePoint,
+
+    pub text: String,
+}
+
+/// The alerts of one recording.
+///
+/// Each rule fires once when its condition starts being met, and not again until the
+/// condition has stopped being met, so that a sensor stuck over a threshold doesn't
+/// fire an alert for every sample.
+#[derive(Default)]
+pub struct RecordingAlerts {
+    /// Oldest first.
+    pub fired: Vec<FiredAlert>,
+
+    /// The rules the state below is for, so that we can start over when they are edited.
+    rules: Vec<AlertRule>,
+
+    /// The rules (by index) whose condition is currently met, and for which entity.
+    active: HashSet<(usize, EntityPath)>,
+
+    /// When we last received data for each [`AlertCondition::Silent`] rule (by index),
+    /// and the time point of that data.
+    last_arrival: HashMap<usize, (instant::Instant, TimePoint)>,
+}
+
+impl RecordingAlerts {
+    /// Check the message against the rules.
+    ///
+    /// Returns the text of each alert that fired.
+    pub fn on_message(&mut self, rules: &[AlertRule], msg: &LogMsg) -> Vec<String> {
+        self.set_rules(rules);
+
+        let msg = match msg {
+            LogMsg::ArrowMsg(msg) if !rules.is_empty() => msg,
+            _ => return Vec::new(),
+        };
+        let bundle = match MsgBundle::try_from(msg) {
+            Ok(bundle) => bundle,
+            Err(err) => {
+                re_log::warn_once!("Failed to check alerts: {err}");
+                return Vec::new();
+            }
+        };
+        let scalars = scalars(&bundle);
+
+        let mut fired = Vec::new();
+        for (index, rule) in rules.iter().enumerate() {
+            if !rule.applies_to(&bundle.entity_path) {
+                continue;
+            }
+
+            if rule.is_silence() {
+                self.last_arrival
+                    .insert(index, (instant::Instant::now(), bundle.time_point.clone()));
+                self.active.remove(&(index, rule.entity_path.clone()));
+                continue;
+            }
+
+            if scalars.is_empty() {
+                continue;
+            }
+            let key = (index, bundle.entity_path.clone());
+            match scalars
+                .iter()
+                .find(|value| rule.condition.is_met_by(**value))
+            {
+                Some(value) => {
+                    if self.active.insert(key) {
+                        let text = format!("{}: {value} {}", bundle.entity_path, rule.condition);
+                        fired.push(self.fire(&bundle.entity_path, &bundle.time_point, text));
+                    }
+                }
+                None => {
+                    self.active.remove(&key);
+                }
+            }
+        }
+        fired
+    }
+
+    /// Check for [`AlertCondition::Silent`] rules that have gone quiet.
+    ///
+    /// Call this regularly, but only for live recordings.
+    /// Returns the text of each alert that fired.
+    pub fn check_silence(&mut self, rules: &[AlertRule]) -> Vec<String> {
+        self.set_rules(rules);
+
+        let mut quiet = Vec::new();
+        for (index, rule) in rules.iter().enumerate() {
+            if let AlertCondition::Silent(timeout) = rule.condition {
+                if let Some((arrival, time_point)) = self.last_arrival.get(&index) {
+                    let quiet_for = arrival.elapsed().as_secs_f64();
+                    if timeout < quiet_for && self.active.insert((index, rule.entity_path.clone()))
+                    {
+                        let text = format!("{}: no data for {quiet_for:.1}s", rule.entity_path);
+                        quiet.push((rule.entity_path.clone(), time_point.clone(), text));
+                    }
+                }
+            }
+        }
+
+        quiet
+            .into_iter()
+            .map(|(entity_path, time_point, text)| self.fire(&entity_path, &time_point, text))
+            .collect()
+    }
+
+    fn set_rules(&mut self, rules: &[AlertRule]) {
+        if self.rules != rules {
+            self.rules = rules.to_vec();
+            self.active.clear();
+            self.last_arrival.clear();
+        }
+    }
+
+    fn fire(&mut self, entity_path: &EntityPath, time_point: &TimePoint, text: String) -> String {
+        re_log::warn!("Alert: {text}");
+        self.fired.push(FiredAlert {
+            entity_path: entity_path.clone(),
+            time_point: time_point.clone(),
+            text: text.clone(),
+        });
+        text
+    }
+}
+
+fn scalars(bundle: &MsgBundle) -> Vec<f64> {
+    let index = match bundle.find_component(&Scalar::name()) {
+        Some(index) => index,
+        None => return Vec::new(),
+    };
+    let values = bundle.components[index].value_list().values();
+    match arrow_array_deserialize_iterator::<Scalar>(values.as_ref()) {
+        Ok(scalars) => scalars.map(|scalar| scalar.0).collect(),
+        Err(err) => {
+            re_log::warn_once!("Failed to check alerts: {err}");
+            Vec::new()
+        }
+    }
+}
+
+#[test]
+fn test_alert_rules() {
+    let rule: AlertRule = "robot/temperature > 80".parse().unwrap();
+    assert_eq!(rule.entity_path, EntityPath::from("robot/temperature"));
+    assert_eq!(rule.condition, AlertCondition::Above(80.0));
+    assert_eq!(rule.to_string().parse::<AlertRule>().unwrap(), rule);
+
+    let rule: AlertRule = "robot/imu silent 5s".parse().unwrap();
+    assert_eq!(rule.condition, AlertCondition::Silent(5.0));
+    assert_eq!(rule.to_string().parse::<AlertRule>().unwrap(), rule);
+
+    assert!("robot/temperature".parse::<AlertRule>().is_err());
+    assert!("robot/temperature >= 80".parse::<AlertRule>().is_err());
+    assert!("robot/temperature > hot".parse::<AlertRule>().is_err());
+
+    assert!(rule.applies_to(&EntityPath::from("robot/imu")));
+    assert!(rule.applies_to(&EntityPath::from("robot/imu/accel")));
+    assert!(!rule.applies_to(&EntityPath::from("robot")));
+
+    assert!(AlertCondition::Below(0.0).is_met_by(-1.0));
+    assert!(!AlertCondition::Below(0.0).is_met_by(0.0));
+}
@@ -7,6 +7,48 @@ pub struct AppOptions {
     pub low_latency: f32,
     pub warn_latency: f32,
 
+    /// Show how old the data in each live space view is.
+    pub show_data_age: bool,
+
+    /// Highlight live data older than this many seconds.
+    pub warn_data_age: f32,
+
+    /// Mark entities that stream live data as stalled when they have been quiet for this
+    /// many seconds, so that e.g. a crashed sensor driver is noticed. Zero to never do so.
+    pub stall_timeout: f32,
+
+    /// Checked against all incoming data, see [`super::alerts`].
+    pub alert_rules: Vec<super::alerts::AlertRule>,
+
+    /// If set, everything we receive is also written to `.rrd` files in this directory,
+    /// one per recording. Ignored on the web.
+    pub auto_save_directory: Option<std::path::PathBuf>,
+
+    /// How to trade rendering speed for image quality in the spatial views.
+    pub render_quality: RenderQuality,
+
+    /// Set on the command line, e.g. for a slow machine, and then used instead of [`Self::render_quality`].
+    #[serde(skip)]
+    pub render_quality_override: Option<RenderQuality>,
+
+    /// Lower the render quality while frames take longer than this many seconds,
+    /// and restore it once they are fast again. Zero to never do so.
+    pub frame_budget: f32,
+
+    /// Used instead of [`Self::render_quality`] if lower, because frames were over budget.
+    /// Set by [`super::FrameBudgetWatchdog`].
+    #[serde(skip)]
+    pub render_quality_fallback: Option<RenderQuality>,
+
+    /// How numbers are shown, unless overridden by a space view.
+    pub number_format: super::NumberFormat,
+
+    /// How times are shown, e.g. in UTC or local time.
+    pub time_format: re_log_types::TimeFormatOptions,
+
+    /// Given to entities when space views are created.
+    pub visible_history: super::VisibleHistoryDefaults,
+
     /// Show milliseconds, RAM usage, etc.
     #[serde(skip)] // restore to the default for the current mode (dev vs debug)
     pub show_metrics: bool,
@@ -20,7 +62,142 @@ impl Default for AppOptions {
             low_latency: 0.100,
             warn_latency: 0.200,
 
+            show_data_age: true,
+            warn_data_age: 0.500,
+
+            stall_timeout: 3.0,
+
+            alert_rules: Vec::new(),
+
+            auto_save_directory: None,
+
+            render_quality: RenderQuality::default(),
+            render_quality_override: None,
+
+            frame_budget: 1.0 / 20.0,
+            render_quality_fallback: None,
+
+            number_format: Default::default(),
+
+            time_format: Default::default(),
+
+            visible_history: Default::default(),
+
             show_metrics: cfg!(debug_assertions),
         }
     }
 }
+
+impl AppOptions {
+    /// The render quality to use, taking the command line and slow frames into account.
+    pub fn render_quality(&self) -> RenderQuality {
+        if let Some(quality) = self.render_quality_override {
+            quality
+        } else if let Some(fallback) = self.render_quality_fallback {
+            fallback.min(self.render_quality)
+        } else {
+            self.render_quality
+        }
+    }
+}
+
+/// Presets trading rendering speed for image quality.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Deserialize,
+    serde::Serialize,
+)]
+pub enum RenderQuality {
+    /// No anti-aliasing, half resolution and flat points. For slow GPUs and huge point clouds.
+    Performance,
+
+    #[default]
+    Balanced,
+
+    /// Renders at twice the resolution and scales down, for the smoothest edges.
+    Quality,
+}
+
+impl RenderQuality {
+    /// From low to high quality.
+    pub const ALL: [Self; 3] = [Self::Performance, Self::Balanced, Self::Quality];
+
+    /// The next faster preset, if any.
+    pub fn lower(self) -> Option<Self> {
+        match self {
+            Self::Performance => None,
+            Self::Balanced => Some(Self::Performance),
+            Self::Quality => Some(Self::Balanced),
+        }
+    }
+
+    /// The next prettier preset, if any.
+    pub fn higher(self) -> Option<Self> {
+        match self {
+            Self::Performance => Some(Self::Balanced),
+            Self::Balanced => Some(Self::Quality),
+            Self::Quality => None,
+        }
+    }
+
+    /// Samples per pixel for multisample anti-aliasing.
+    pub fn msaa_sample_count(self) -> u32 {
+        match self {
+            Self::Performance => 1,
+            Self::Balanced | Self::Quality => 4,
+        }
+    }
+
+    /// Resolution we render at, relative to the screen.
+    pub fn resolution_scale(self) -> f32 {
+        match self {
+            Self::Performance => 0.5,
+            Self::Balanced => 1.0,
+            Self::Quality => 2.0,
+        }
+    }
+
+    /// Draw points as flat discs instead of shaded spheres with smooth edges.
+    pub fn fast_point_splatting(self) -> bool {
+        self == Self::Performance
+    }
+}
+
+impl std::fmt::Display for RenderQuality {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Performance => "Performance",
+            Self::Balanced => "Balanced",
+            Self::Quality => "Quality",
+        })
+    }
+}
+
+impl std::str::FromStr for RenderQuality {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|quality| quality.to_string().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                format!("Unknown render quality {s:?} - expected performance, balanced or quality")
+            })
+    }
+}
+
+#[test]
+fn test_render_quality_from_str() {
+    for quality in RenderQuality::ALL {
+        assert_eq!(quality.to_string().parse::<RenderQuality>(), Ok(quality));
+    }
+    assert_eq!("performance".parse(), Ok(RenderQuality::Performance));
+    assert!("ultra".parse::<RenderQuality>().is_err());
+}
@@ -0,0 +1,214 @@
+//! Writing everything the viewer receives to `.rrd` files as it arrives,
+//! so that nothing is lost if the viewer crashes or has to purge old data to save RAM.
+
+use std::{
+    io::BufWriter,
+    path::{Path, PathBuf},
+    sync::mpsc::{Receiver, RecvTimeoutError, Sender},
+    thread::JoinHandle,
+};
+
+use nohash_hasher::IntMap;
+use re_log_types::{encoding::Encoder, LogMsg, RecordingId, Time};
+
+/// How often we flush to disk, at most.
+const FLUSH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+enum Command {
+    /// Finish the files in the old directory, and write to this one from now on.
+    SetDirectory(Option<PathBuf>),
+
+    Append(RecordingId, LogMsg),
+}
+
+/// Writes one `.rrd` file per recording to [`super::AppOptions::auto_save_directory`].
+///
+/// The encoding and writing happens on a separate thread, so a slow disk never holds up the UI.
+#[derive(Default)]
+pub struct AutoSaver {
+    /// What we last told the writer thread.
+    directory: Option<PathBuf>,
+
+    /// `None` until we have something to save, or if we failed to start the writer thread.
+    tx: Option<Sender<Command>>,
+    join: Option<JoinHandle<()>>,
+}
+
+impl AutoSaver {
+    /// Queue the message for the file of its recording, if `directory` is set.
+    pub fn on_message(
+        &mut self,
+        directory: Option<&Path>,
+        recording_id: RecordingId,
+        msg: &LogMsg,
+    ) {
+        if self.directory.as_deref() != directory {
+            self.directory = directory.map(ToOwned::to_owned);
+            self.send(Command::SetDirectory(self.directory.clone()));
+        }
+        if self.directory.is_some() {
+            self.send(Command::Append(recording_id, msg.clone()));
+        }
+    }
+
+    fn send(&mut self, command: Command) {
+        if self.join.is_none() {
+            let (tx, rx) = std::sync::mpsc::channel();
+            match std::thread::Builder::new()
+                .name("auto_save".into())
+                .spawn(move || write_files(&rx))
+            {
+                Ok(join) => {
+                    self.tx = Some(tx);
+                    self.join = Some(join);
+                }
+                Err(err) => {
+                    re_log::error_once!("Failed to start auto-saving: {err}");
+                    return;
+                }
+            }
+        }
+        if let Some(tx) = &self.tx {
+            tx.send(command).ok(); // Errors have already been logged by the thread.
+        }
+    }
+}
+
+impl Drop for AutoSaver {
+    /// Wait until everything has been written.
+    fn drop(&mut self) {
+        self.tx = None; // Hang up, so that the thread finishes the files.
+        if let Some(join) = self.join.take() {
+            join.join().ok();
+        }
+    }
+}
+
+fn write_files(rx: &Receiver<Command>) {
+    let mut writer = Writer::default();
+    loop {
+        match rx.recv_timeout(FLUSH_INTERVAL) {
+            Ok(Command::SetDirectory(directory)) => {
+                writer.finish();
+                writer.directory = directory;
+            }
+            Ok(Command::Append(recording_id, msg)) => writer.append(recording_id, &msg),
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+        writer.flush_if_needed();
+    }
+    writer.finish();
+}
+
+/// Lives on the writer thread.
+#[derive(Default)]
+struct Writer {
+    /// The directory the files below are in.
+    directory: Option<PathBuf>,
+
+    /// `None` if we failed to write to the file, so that we don't keep trying.
+    files: IntMap<RecordingId, Option<AutoSaveFile>>,
+}
+
+impl Writer {
+    /// Append the message to the file of its recording.
+    fn append(&mut self, recording_id: RecordingId, msg: &LogMsg) {
+        let Some(directory) = &self.directory else {
+            return;
+        };
+
+        let file = self.files.entry(recording_id).or_insert_with(|| {
+            match AutoSaveFile::create(directory, recording_id) {
+                Ok(file) => {
+                    re_log::info!("Auto-saving recording {recording_id} to {:?}", file.path);
+                    Some(file)
+                }
+                Err(err) => {
+                    re_log::error!("Failed to auto-save: {}", re_error::format(&err));
+                    None
+                }
+            }
+        });
+
+        if let Some(auto_save_file) = file {
+            match auto_save_file.encoder.append(msg) {
+                Ok(()) => auto_save_file.is_dirty = true,
+                Err(err) => {
+                    re_log::error!(
+                        "Failed to auto-save to {:?}: {}",
+                        auto_save_file.path,
+                        re_error::format(&err)
+                    );
+                    *file = None;
+                }
+            }
+        }
+    }
+
+    /// Make sure what was received a while ago is on disk.
+    fn flush_if_needed(&mut self) {
+        for file in self.files.values_mut() {
+            if let Some(auto_save_file) = file {
+                if auto_save_file.is_dirty && FLUSH_INTERVAL < auto_save_file.last_flush.elapsed() {
+                    if let Err(err) = auto_save_file.encoder.flush() {
+                        re_log::error!(
+                            "Failed to auto-save to {:?}: {}",
+                            auto_save_file.path,
+                            re_error::format(&err)
+                        );
+                        *file = None;
+                        continue;
+                    }
+                    auto_save_file.last_flush = std::time::Instant::now();
+                    auto_save_file.is_dirty = false;
+                }
+            }
+        }
+    }
+
+    /// Close all files.
+    fn finish(&mut self) {
+        for auto_save_file in self.files.drain().filter_map(|(_, file)| file) {
+            if let Err(err) = auto_save_file.encoder.finish() {
+                re_log::error!(
+                    "Failed to finish {:?}: {}",
+                    auto_save_file.path,
+                    re_error::format(&err)
+                );
+            }
+        }
+    }
+}
+
+struct AutoSaveFile {
+    path: PathBuf,
+    encoder: Encoder<BufWriter<std::fs::File>>,
+    last_flush: std::time::Instant,
+
+    /// Was anything appended since the last flush?
+    is_dirty: bool,
+}
+
+impl AutoSaveFile {
+    fn create(directory: &Path, recording_id: RecordingId) -> anyhow::Result<Self> {
+        use anyhow::Context as _;
+
+        std::fs::create_dir_all(directory)
+            .with_context(|| format!("Failed to create {directory:?}"))?;
+        let file_name = format!(
+            "{}_{recording_id}.rrd",
+            Time::now().format_time("%Y-%m-%dT%H-%M-%SZ")
+        );
+        let path = directory.join(file_name);
+        let file =
+            std::fs::File::create(&path).with_context(|| format!("Failed to create {path:?}"))?;
+
+        Ok(Self {
+            path,
+            encoder: Encoder::new(BufWriter::new(file))?,
+            last_flush: std::time::Instant::now(),
+            is_dirty: false,
+        })
+    }
+}
@@ -0,0 +1,160 @@
+//! Blueprints exported to and imported from files, to share a layout with teammates.
+//!
+//! Only the blueprint of one application is written, never the user settings
+//! (see [`super::AppOptions`]), so sharing a file doesn't leak e.g. recent files or alert rules.
+//!
+//! The native viewer also keeps the blueprint of each application between runs in a file
+//! of this format, see [`stored_blueprints_dir`].
+
+use anyhow::Context as _;
+
+#[cfg(not(target_arch = "wasm32"))]
+use ahash::HashMap;
+
+#[cfg(not(target_arch = "wasm32"))]
+use re_log_types::ApplicationId;
+
+use super::EntityDisplayNames;
+use crate::ui::Blueprint;
+
+/// The extension of exported blueprints.
+pub const FILE_EXTENSION: &str = "rbl";
+
+/// The contents of a blueprint file.
+#[derive(Default, serde::Deserialize)]
+#[serde(default)]
+pub struct BlueprintFile {
+    pub blueprint: Blueprint,
+    pub entity_display_names: EntityDisplayNames,
+}
+
+/// Same fields as [`BlueprintFile`], so that we can write without cloning.
+#[derive(serde::Serialize)]
+struct BlueprintFileRef<'a> {
+    blueprint: &'a Blueprint,
+    entity_display_names: &'a EntityDisplayNames,
+}
+
+pub fn save(
+    path: &std::path::Path,
+    blueprint: &Blueprint,
+    entity_display_names: &EntityDisplayNames,
+) -> anyhow::Result<()> {
+    let contents = ron::ser::to_string_pretty(
+        &BlueprintFileRef {
+            blueprint,
+            entity_display_names,
+        },
+        Default::default(),
+    )?;
+    std::fs::write(path, contents).with_context(|| format!("Failed to write {path:?}"))
+}
+
+pub fn load(path: &std::path::Path) -> anyhow::Result<BlueprintFile> {
+    let contents =
+        std::fs::read_to_string(path).with_context(|| format!("Failed to read {path:?}"))?;
+    ron::from_str(&contents).with_context(|| format!("{path:?} is not a Rerun blueprint"))
+}
+
+/// Where the native viewer keeps the blueprint of each application between runs,
+/// one file each, apart from the user settings.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn stored_blueprints_dir() -> Option<std::path::PathBuf> {
+    directories_next::ProjectDirs::from("", "", crate::APPLICATION_NAME)
+        .map(|dirs| dirs.config_dir().join("blueprints"))
+}
+
+/// All the blueprints stored in `dir` by [`save_stored`], skipping (and warning about)
+/// the files we can't read.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn load_stored(dir: &std::path::Path) -> Vec<(ApplicationId, BlueprintFile)> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return vec![]; // Nothing stored yet
+    };
+    entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension() != Some(FILE_EXTENSION.as_ref()) {
+                return None;
+            }
+            let app_id = app_id_from_file_stem(path.file_stem()?.to_str()?)?;
+            match load(&path) {
+                Ok(file) => Some((app_id, file)),
+                Err(err) => {
+                    re_log::warn!("Skipping stored blueprint: {}", re_error::format(&err));
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// Write each blueprint to its own file in `dir`,
+/// and remove the files of the applications that are no longer in `blueprints`.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save_stored(
+    dir: &std::path::Path,
+    blueprints: &HashMap<ApplicationId, Blueprint>,
+    entity_display_names: &HashMap<ApplicationId, EntityDisplayNames>,
+) -> anyhow::Result<()> {
+    std::fs::create_dir_all(dir).with_context(|| format!("Failed to create {dir:?}"))?;
+
+    let default_names = EntityDisplayNames::default();
+    for (app_id, blueprint) in blueprints {
+        let names = entity_display_names.get(app_id).unwrap_or(&default_names);
+        save(&dir.join(stored_file_name(app_id)), blueprint, names)?;
+    }
+
+    for (app_id, _) in load_stored(dir) {
+        if !blueprints.contains_key(&app_id) {
+            std::fs::remove_file(dir.join(stored_file_name(&app_id))).ok();
+        }
+    }
+    Ok(())
+}
+
+/// Application ids can contain anything, so everything but ASCII letters, digits, `-` and `_`
+/// is written as `%XX`.
+#[cfg(not(target_arch = "wasm32"))]
+fn stored_file_name(app_id: &ApplicationId) -> String {
+    let mut name = String::new();
+    for byte in app_id.0.bytes() {
+        if byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_' {
+            name.push(byte as char);
+        } else {
+            name += &format!("%{byte:02X}");
+        }
+    }
+    format!("{name}.{FILE_EXTENSION}")
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn app_id_from_file_stem(stem: &str) -> Option<ApplicationId> {
+    let mut bytes = Vec::new();
+    let mut rest = stem.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%' {
+            let hex = std::str::from_utf8(tail.get(..2)?).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+    String::from_utf8(bytes).ok().map(ApplicationId)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn test_stored_file_name() {
+    for app_id in ["my_robot", "team a/sim: v2", "日本", "100%"] {
+        let app_id = ApplicationId::from(app_id);
+        let file_name = stored_file_name(&app_id);
+        assert!(file_name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_%.".contains(c)));
+        let stem = file_name.strip_suffix(".rbl").unwrap();
+        assert_eq!(app_id_from_file_stem(stem), Some(app_id));
+    }
+}
@@ -0,0 +1,204 @@
+//! Shareable links to what is shown in the viewer, e.g.
+//! `rerun://192.168.1.42:9877/<recording-id>?timeline=frame&t=42&sel=world/points[3]`.
+//!
+//! Opening such a link connects to the Rerun Server, waits for the recording to arrive,
+//! and then moves the time cursor and selection to where they were when the link was copied.
+
+use re_data_store::InstancePath;
+use re_log_types::{
+    component_types::InstanceKey, parse_entity_path, EntityPath, PathParseError, RecordingId,
+    TimeInt, TimelineName,
+};
+
+/// A place in a recording streamed from a Rerun Server.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Bookmark {
+    /// Of the Rerun Server, e.g. `127.0.0.1:9877`.
+    pub host_and_port: String,
+
+    /// Which of the recordings the server streams, if not the first one.
+    pub recording_id: Option<RecordingId>,
+
+    /// The time cursor.
+    pub time: Option<(TimelineName, TimeInt)>,
+
+    pub selection: Vec<InstancePath>,
+}
+
+impl Bookmark {
+    /// The url scheme of the links, e.g. `rerun://127.0.0.1:9877`.
+    pub const URL_SCHEME: &str = "rerun";
+
+    /// Parse a `rerun://host:port[/recording-id][?timeline=…&t=…&sel=…]` link.
+    ///
+    /// Returns `None` if the url is not a `rerun://` link.
+    /// Parts of the link that don't make sense are ignored, with a warning.
+    pub fn from_url(url: &str) -> Option<Self> {
+        let rest = url.strip_prefix(Self::URL_SCHEME)?.strip_prefix("://")?;
+        let (address, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let (host_and_port, path) = address.split_once('/').unwrap_or((address, ""));
+
+        let mut bookmark = Self {
+            host_and_port: host_and_port.to_owned(),
+            recording_id: None,
+            time: None,
+            selection: vec![],
+        };
+
+        let path = path.trim_matches('/');
+        if !path.is_empty() {
+            match path.parse() {
+                Ok(recording_id) => bookmark.recording_id = Some(recording_id),
+                Err(err) => re_log::warn!("Bad recording id {path:?} in {url:?}: {err}"),
+            }
+        }
+
+        let mut timeline = None;
+        let mut time = None;
+        for (key, value) in query
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| pair.split_once('=').unwrap_or((pair, "")))
+        {
+            let value = percent_decode(value);
+            match key {
+                "timeline" => timeline = Some(TimelineName::from(value.as_str())),
+                "t" => match value.parse::<i64>() {
+                    Ok(t) => time = Some(TimeInt::from(t)),
+                    Err(err) => re_log::warn!("Bad time {value:?} in {url:?}: {err}"),
+                },
+                "sel" => match parse_instance_path(&value) {
+                    Ok(instance_path) => bookmark.selection.push(instance_path),
+                    Err(err) => re_log::warn!("Bad selection {value:?} in {url:?}: {err}"),
+                },
+                _ => re_log::warn!("Ignoring unknown {key:?} in {url:?}"),
+            }
+        }
+        if let (Some(timeline), Some(time)) = (timeline, time) {
+            bookmark.time = Some((timeline, time));
+        }
+
+        Some(bookmark)
+    }
+
+    pub fn to_url(&self) -> String {
+        let Self {
+            host_and_port,
+            recording_id,
+            time,
+            selection,
+        } = self;
+
+        let mut url = format!("{}://{host_and_port}", Self::URL_SCHEME);
+        if let Some(recording_id) = recording_id {
+            url += &format!("/{recording_id}");
+        }
+
+        let mut query = vec![];
+        if let Some((timeline, time)) = time {
+            query.push(format!("timeline={}", percent_encode(timeline.as_str())));
+            query.push(format!("t={}", time.as_i64()));
+        }
+        for instance_path in selection {
+            query.push(format!(
+                "sel={}",
+                percent_encode(&instance_path.to_string())
+            ));
+        }
+        if !query.is_empty() {
+            url += "?";
+            url += &query.join("&");
+        }
+        url
+    }
+
+    /// The websocket url of the Rerun Server to connect to.
+    pub fn ws_url(&self) -> String {
+        format!("{}://{}", re_ws_comms::PROTOCOL, self.host_and_port)
+    }
+
+    /// The `host:port` part of a websocket url like `ws://127.0.0.1:9877`.
+    pub fn host_and_port_of_ws_url(ws_url: &str) -> Option<&str> {
+        let (_protocol, host_and_port) = ws_url.split_once("://")?;
+        Some(host_and_port.trim_end_matches('/'))
+    }
+}
+
+/// Inverse of the [`std::fmt::Display`] of [`InstancePath`], e.g. `world/points[3]`.
+fn parse_instance_path(s: &str) -> Result<InstancePath, PathParseError> {
+    if let Some((entity_path, key)) = s.strip_suffix(']').and_then(|s| s.rsplit_once('[')) {
+        if let Ok(key) = key.parse() {
+            let entity_path = EntityPath::from(parse_entity_path(entity_path)?);
+            return Ok(InstancePath::instance(entity_path, InstanceKey(key)));
+        }
+    }
+    Ok(InstancePath::entity_splat(EntityPath::from(
+        parse_entity_path(s)?,
+    )))
+}
+
+/// Escape everything but the characters that are safe in a query value,
+/// keeping `/` for readable entity paths.
+fn percent_encode(s: &str) -> String {
+    let mut encoded = String::with_capacity(s.len());
+    for byte in s.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~/".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded += &format!("%{byte:02X}");
+        }
+    }
+    encoded
+}
+
+fn percent_decode(s: &str) -> String {
+    let mut bytes = Vec::with_capacity(s.len());
+    let mut rest = s.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        let escaped = (byte == b'%')
+            .then(|| tail.get(..2))
+            .flatten()
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        if let Some(escaped) = escaped {
+            bytes.push(escaped);
+            rest = &tail[2..];
+        } else {
+            bytes.push(if byte == b'+' { b' ' } else { byte });
+            rest = tail;
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+#[test]
+fn test_bookmark_url_roundtrip() {
+    let bookmark = Bookmark {
+        host_and_port: "192.168.1.42:9877".to_owned(),
+        recording_id: Some(RecordingId::random()),
+        time: Some((TimelineName::from("frame nr"), TimeInt::from(42))),
+        selection: vec![
+            InstancePath::entity_splat(EntityPath::from("world/points")),
+            InstancePath::instance(EntityPath::from("world/points"), InstanceKey(3)),
+        ],
+    };
+    let url = bookmark.to_url();
+    assert!(url.contains("timeline=frame%20nr&t=42"), "{url}");
+    assert_eq!(Bookmark::from_url(&url), Some(bookmark));
+
+    let bookmark = Bookmark::from_url("rerun://127.0.0.1:9877/").unwrap();
+    assert_eq!(bookmark.host_and_port, "127.0.0.1:9877");
+    assert_eq!(bookmark.recording_id, None);
+    assert_eq!(bookmark.time, None);
+    assert!(bookmark.selection.is_empty());
+    assert_eq!(
+        bookmark.ws_url(),
+        format!("{}://127.0.0.1:9877", re_ws_comms::PROTOCOL)
+    );
+
+    assert_eq!(Bookmark::from_url("ws://127.0.0.1:9877"), None);
+    assert_eq!(
+        Bookmark::host_and_port_of_ws_url("ws://127.0.0.1:9877/"),
+        Some("127.0.0.1:9877")
+    );
+}
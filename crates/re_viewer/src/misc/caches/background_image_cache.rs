@@ -0,0 +1,113 @@
+use std::path::{Path, PathBuf};
+
+use re_renderer::{
+    resource_managers::{GpuTexture2DHandle, Texture2DCreationDesc},
+    RenderContext,
+};
+
+/// An image loaded from disk, ready to be drawn underneath a 2D view.
+pub struct BackgroundTexture {
+    pub texture: GpuTexture2DHandle,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Background images of 2D views (e.g. floorplans), keyed by their path on disk.
+#[derive(Default)]
+pub struct BackgroundImageCache {
+    images: ahash::HashMap<PathBuf, CachedBackgroundImage>,
+    generation: u64,
+}
+
+struct CachedBackgroundImage {
+    /// `None` if the image failed to load, so that we don't retry every frame.
+    texture: Option<BackgroundTexture>,
+
+    /// When [`BackgroundImageCache::generation`] was we last used?
+    last_use_generation: u64,
+}
+
+impl super::GpuCacheEntry for CachedBackgroundImage {
+    fn last_use_generation(&self) -> u64 {
+        self.last_use_generation
+    }
+
+    fn gpu_bytes(&self) -> u64 {
+        self.texture.as_ref().map_or(0, |texture| {
+            texture.width as u64 * texture.height as u64 * 4 // Rgba8UnormSrgb
+        })
+    }
+}
+
+impl BackgroundImageCache {
+    pub fn get(
+        &mut self,
+        path: &Path,
+        render_ctx: &mut RenderContext,
+    ) -> Option<&BackgroundTexture> {
+        let cached = self
+            .images
+            .entry(path.to_owned())
+            .or_insert_with(|| CachedBackgroundImage {
+                texture: match load_background_image(path, render_ctx) {
+                    Ok(texture) => Some(texture),
+                    Err(err) => {
+                        re_log::warn!("Failed to load background image {path:?}: {err}");
+                        None
+                    }
+                },
+                last_use_generation: 0,
+            });
+        cached.last_use_generation = self.generation;
+        cached.texture.as_ref()
+    }
+
+    /// Call once per frame.
+    pub fn new_frame(&mut self) {
+        self.generation += 1;
+    }
+
+    /// Evict the images that were used the longest ago, keeping those used since the last
+    /// [`Self::new_frame`], until at least `bytes_to_free` bytes of GPU memory are freed.
+    ///
+    /// Returns how many bytes of GPU memory were freed.
+    pub fn evict_lru_gpu_memory(&mut self, bytes_to_free: u64) -> u64 {
+        use super::GpuCacheEntry as _;
+        super::evict_lru(&mut self.images, self.generation, bytes_to_free)
+            .iter()
+            .map(|cached| cached.gpu_bytes())
+            .sum()
+    }
+
+    /// Forget all images, so they are reloaded from disk when next needed.
+    pub fn clear(&mut self) {
+        self.images.clear();
+    }
+}
+
+fn load_background_image(
+    path: &Path,
+    render_ctx: &mut RenderContext,
+) -> anyhow::Result<BackgroundTexture> {
+    crate::profile_function!();
+
+    let image = image::open(path)?.into_rgba8();
+    let (width, height) = image.dimensions();
+
+    let texture = render_ctx.texture_manager_2d.create(
+        &mut render_ctx.gpu_resources.textures,
+        &Texture2DCreationDesc {
+            label: format!("background image {path:?}").into(),
+            data: image.as_raw(),
+            format: wgpu::TextureFormat::Rgba8UnormSrgb,
+            width,
+            height,
+        },
+    );
+
+    Ok(BackgroundTexture {
+        texture,
+        width,
+        height,
+    })
+}
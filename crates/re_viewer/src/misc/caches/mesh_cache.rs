@@ -8,7 +8,31 @@ use crate::{mesh_loader::LoadedMesh, ui::view_spatial::MeshSourceData};
 // ----------------------------------------------------------------------------
 
 #[derive(Default)]
-pub struct MeshCache(nohash_hasher::IntMap<MeshId, Option<Arc<LoadedMesh>>>);
+pub struct MeshCache {
+    meshes: nohash_hasher::IntMap<MeshId, CachedMesh>,
+    generation: u64,
+}
+
+struct CachedMesh {
+    /// `None` if the mesh failed to load, so that we don't retry every frame.
+    mesh: Option<Arc<LoadedMesh>>,
+
+    /// How much GPU memory was allocated when loading the mesh.
+    gpu_bytes: u64,
+
+    /// When [`MeshCache::generation`] was we last used?
+    last_use_generation: u64,
+}
+
+impl super::GpuCacheEntry for CachedMesh {
+    fn last_use_generation(&self) -> u64 {
+        self.last_use_generation
+    }
+
+    fn gpu_bytes(&self) -> u64 {
+        self.gpu_bytes
+    }
+}
 
 impl MeshCache {
     pub fn load(
@@ -21,31 +45,55 @@ impl MeshCache {
 
         let mesh_id = mesh_data.mesh_id();
 
-        self.0
-            .entry(mesh_id)
-            .or_insert_with(|| {
-                re_log::debug!("Loading CPU mesh {name:?}…");
-
-                let result = match mesh_data {
-                    MeshSourceData::Mesh3D(mesh3d) => {
-                        LoadedMesh::load(name.to_owned(), mesh3d, render_ctx)
-                    }
-                    MeshSourceData::StaticGlb(_mesh_id, glb_bytes) => LoadedMesh::load_raw(
-                        name.to_owned(),
-                        MeshFormat::Glb,
-                        glb_bytes,
-                        render_ctx,
-                    ),
-                };
-
-                match result {
-                    Ok(cpu_mesh) => Some(Arc::new(cpu_mesh)),
-                    Err(err) => {
-                        re_log::warn!("Failed to load mesh {name:?}: {}", re_error::format(&err));
-                        None
-                    }
+        let cached = self.meshes.entry(mesh_id).or_insert_with(|| {
+            re_log::debug!("Loading CPU mesh {name:?}…");
+            let gpu_bytes_before = render_ctx.gpu_resources.statistics().total_bytes();
+
+            let result = match mesh_data {
+                MeshSourceData::Mesh3D(mesh3d) => {
+                    LoadedMesh::load(name.to_owned(), mesh3d, render_ctx)
+                }
+                MeshSourceData::StaticGlb(_mesh_id, glb_bytes) => {
+                    LoadedMesh::load_raw(name.to_owned(), MeshFormat::Glb, glb_bytes, render_ctx)
                 }
-            })
-            .clone()
+            };
+
+            let mesh = match result {
+                Ok(cpu_mesh) => Some(Arc::new(cpu_mesh)),
+                Err(err) => {
+                    re_log::warn!("Failed to load mesh {name:?}: {}", re_error::format(&err));
+                    None
+                }
+            };
+            let gpu_bytes = render_ctx
+                .gpu_resources
+                .statistics()
+                .total_bytes()
+                .saturating_sub(gpu_bytes_before);
+
+            CachedMesh {
+                mesh,
+                gpu_bytes,
+                last_use_generation: 0,
+            }
+        });
+        cached.last_use_generation = self.generation;
+        cached.mesh.clone()
+    }
+
+    /// Call once per frame.
+    pub fn new_frame(&mut self) {
+        self.generation += 1;
+    }
+
+    /// Evict the meshes that were used the longest ago, keeping those used since the last
+    /// [`Self::new_frame`], until at least `bytes_to_free` bytes of GPU memory are freed.
+    ///
+    /// Returns how many bytes of GPU memory were freed.
+    pub fn evict_lru_gpu_memory(&mut self, bytes_to_free: u64) -> u64 {
+        super::evict_lru(&mut self.meshes, self.generation, bytes_to_free)
+            .iter()
+            .map(|cached| cached.gpu_bytes)
+            .sum()
     }
 }
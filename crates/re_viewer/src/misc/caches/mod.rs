@@ -1,3 +1,4 @@
+mod background_image_cache;
 mod mesh_cache;
 mod tensor_image_cache;
 
@@ -13,7 +14,13 @@ pub struct Caches {
     /// For displaying meshes efficiently in immediate mode.
     pub mesh: mesh_cache::MeshCache,
 
+    /// Background images of 2D views.
+    pub background_images: background_image_cache::BackgroundImageCache,
+
     pub tensor_stats: nohash_hasher::IntMap<component_types::TensorId, TensorStats>,
+
+    /// The transforms of each spatial view, resolved again only when they change.
+    pub transforms: super::TransformCaches,
 }
 
 impl Caches {
@@ -21,6 +28,28 @@ impl Caches {
     pub fn new_frame(&mut self) {
         let max_image_cache_use = 1_000_000_000;
         self.image.new_frame(max_image_cache_use);
+        self.mesh.new_frame();
+        self.background_images.new_frame();
+    }
+
+    /// Evict the GPU resources that were used the longest ago, keeping those used since the
+    /// last [`Self::new_frame`], until at least `bytes_to_free` bytes of GPU memory are freed.
+    ///
+    /// Images go first, since there are usually many more of them than meshes.
+    /// Returns how many bytes of GPU memory were freed.
+    pub fn evict_lru_gpu_memory(&mut self, bytes_to_free: u64) -> u64 {
+        crate::profile_function!();
+
+        let mut freed = self.image.evict_lru_gpu_memory(bytes_to_free);
+        if freed < bytes_to_free {
+            freed += self.mesh.evict_lru_gpu_memory(bytes_to_free - freed);
+        }
+        if freed < bytes_to_free {
+            freed += self
+                .background_images
+                .evict_lru_gpu_memory(bytes_to_free - freed);
+        }
+        freed
     }
 
     pub fn purge_memory(&mut self) {
@@ -28,9 +57,13 @@ impl Caches {
             image,
             tensor_stats,
             mesh: _, // TODO(emilk)
+            background_images,
+            transforms,
         } = self;
         image.purge_memory();
         tensor_stats.clear();
+        background_images.clear();
+        transforms.clear();
     }
 
     pub fn tensor_stats(&mut self, tensor: &re_log_types::ClassicTensor) -> &TensorStats {
@@ -40,6 +73,93 @@ impl Caches {
     }
 }
 
+/// A cache entry that holds on to GPU resources.
+trait GpuCacheEntry {
+    /// The generation of its cache in which this was last used.
+    fn last_use_generation(&self) -> u64;
+
+    /// How much GPU memory is freed by forgetting this entry.
+    fn gpu_bytes(&self) -> u64;
+}
+
+/// Removes the entries that were used the longest ago, but not in `current_generation`,
+/// until the removed ones hold at least `bytes_to_free` bytes of GPU memory.
+///
+/// The GPU resources are freed once their pools notice they are no longer used.
+fn evict_lru<K, V, S>(
+    map: &mut std::collections::HashMap<K, V, S>,
+    current_generation: u64,
+    bytes_to_free: u64,
+) -> Vec<V>
+where
+    K: Clone + Eq + std::hash::Hash,
+    V: GpuCacheEntry,
+    S: std::hash::BuildHasher,
+{
+    let mut candidates: Vec<(u64, K)> = map
+        .iter()
+        .filter(|(_, entry)| {
+            entry.gpu_bytes() > 0 && entry.last_use_generation() < current_generation
+        })
+        .map(|(key, entry)| (entry.last_use_generation(), key.clone()))
+        .collect();
+    candidates.sort_by_key(|(last_use_generation, _)| *last_use_generation);
+
+    let mut freed = 0;
+    let mut evicted = Vec::new();
+    for (_, key) in candidates {
+        if bytes_to_free <= freed {
+            break;
+        }
+        if let Some(entry) = map.remove(&key) {
+            freed += entry.gpu_bytes();
+            evicted.push(entry);
+        }
+    }
+    evicted
+}
+
+#[test]
+fn test_evict_lru() {
+    struct Entry {
+        last_use_generation: u64,
+        gpu_bytes: u64,
+    }
+
+    impl GpuCacheEntry for Entry {
+        fn last_use_generation(&self) -> u64 {
+            self.last_use_generation
+        }
+
+        fn gpu_bytes(&self) -> u64 {
+            self.gpu_bytes
+        }
+    }
+
+    let mut map = ahash::HashMap::default();
+    for (key, last_use_generation, gpu_bytes) in [
+        ("old", 1, 100),
+        ("older", 0, 100),
+        ("new", 2, 100),
+        ("failed", 0, 0),
+    ] {
+        map.insert(
+            key,
+            Entry {
+                last_use_generation,
+                gpu_bytes,
+            },
+        );
+    }
+
+    let evicted = evict_lru(&mut map, 2, 150);
+    assert_eq!(evicted.len(), 2);
+    assert!(map.contains_key("new"), "Used this generation, so kept");
+    assert!(map.contains_key("failed"), "Holds no GPU memory, so kept");
+
+    assert!(evict_lru(&mut map, 2, 1000).is_empty());
+}
+
 pub struct TensorStats {
     pub range: Option<(f64, f64)>,
 }
@@ -49,7 +169,7 @@ impl TensorStats {
         use re_log_types::TensorDataType;
         use re_tensor_ops::as_ndarray;
 
-        use half::f16;
+        use half::{bf16, f16};
 
         macro_rules! declare_tensor_range_int {
             ($name: ident, $typ: ty) => {
@@ -102,6 +222,16 @@ impl TensorStats {
             (min.to_f64(), max.to_f64())
         }
 
+        #[allow(clippy::needless_pass_by_value)]
+        fn tensor_range_bf16(tensor: ndarray::ArrayViewD<'_, bf16>) -> (f64, f64) {
+            crate::profile_function!();
+            let (min, max) = tensor.fold(
+                (bf16::INFINITY, bf16::NEG_INFINITY),
+                |(min, max), &value| (min.min(value), max.max(value)),
+            );
+            (min.to_f64(), max.to_f64())
+        }
+
         let range = match tensor.dtype() {
             TensorDataType::U8 => as_ndarray::<u8>(tensor).ok().map(tensor_range_u8),
             TensorDataType::U16 => as_ndarray::<u16>(tensor).ok().map(tensor_range_u16),
@@ -114,6 +244,7 @@ impl TensorStats {
             TensorDataType::I64 => as_ndarray::<i64>(tensor).ok().map(tensor_range_i64),
 
             TensorDataType::F16 => as_ndarray::<f16>(tensor).ok().map(tensor_range_f16),
+            TensorDataType::BF16 => as_ndarray::<bf16>(tensor).ok().map(tensor_range_bf16),
             TensorDataType::F32 => as_ndarray::<f32>(tensor).ok().map(tensor_range_f32),
             TensorDataType::F64 => as_ndarray::<f64>(tensor).ok().map(tensor_range_f64),
         };
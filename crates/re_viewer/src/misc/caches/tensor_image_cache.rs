@@ -118,6 +118,19 @@ impl ImageCache {
         self.generation += 1;
     }
 
+    /// Evict the textures that were used the longest ago, keeping those used since the last
+    /// [`Self::new_frame`], until at least `bytes_to_free` bytes of GPU memory are freed.
+    ///
+    /// Returns how many bytes of GPU memory were freed.
+    pub fn evict_lru_gpu_memory(&mut self, bytes_to_free: u64) -> u64 {
+        let mut freed = 0;
+        for ci in super::evict_lru(&mut self.images, self.generation, bytes_to_free) {
+            self.memory_used -= ci.memory_used;
+            freed += ci.gpu_bytes;
+        }
+        freed
+    }
+
     /// Attempt to free up memory.
     pub fn purge_memory(&mut self) {
         crate::profile_function!();
@@ -142,6 +155,16 @@ impl ImageCache {
     }
 }
 
+impl super::GpuCacheEntry for CachedImage {
+    fn last_use_generation(&self) -> u64 {
+        self.last_use_generation
+    }
+
+    fn gpu_bytes(&self) -> u64 {
+        self.gpu_bytes
+    }
+}
+
 struct CachedImage {
     /// For egui. `None` if the tensor was not a valid image.
     /// TODO(andreas): This is partially redundant to the renderer texture
@@ -158,6 +181,9 @@ struct CachedImage {
     /// Total memory used by this image.
     memory_used: u64,
 
+    /// GPU memory used by [`Self::texture_handle`].
+    gpu_bytes: u64,
+
     /// When [`ImageCache::generation`] was we last used?
     last_use_generation: u64,
 }
@@ -183,6 +209,7 @@ impl CachedImage {
                     texture_handle: None,
                     dynamic_img: None,
                     memory_used: 0,
+                    gpu_bytes: 0,
                     last_use_generation: 0,
                 }
             }
@@ -202,8 +229,8 @@ impl CachedImage {
         //                  See also https://github.com/rerun-io/rerun/issues/910
         let egui_color_image = dynamic_image_to_egui_color_image(&dynamic_img, meaning);
 
-        let memory_used = egui_color_image.pixels.len() * std::mem::size_of::<egui::Color32>()
-            + dynamic_img.as_bytes().len();
+        let gpu_bytes = egui_color_image.pixels.len() * std::mem::size_of::<egui::Color32>();
+        let memory_used = gpu_bytes + dynamic_img.as_bytes().len();
 
         // TODO(andreas): The renderer should ingest images with less conversion (e.g. keep luma as 8bit texture, don't flip bits on bgra etc.)
         let renderer_texture_handle = render_ctx.texture_manager_2d.create(
@@ -230,6 +257,7 @@ impl CachedImage {
             retained_img: Some(retained_img),
             texture_handle: Some(renderer_texture_handle),
             memory_used: memory_used as u64,
+            gpu_bytes: gpu_bytes as u64,
             last_use_generation: 0,
         }
     }
@@ -294,6 +322,11 @@ impl AsDynamicImage for ClassicTensor {
                     "Tensor data length doesn't match tensor shape and dtype"
                 );
 
+                if let Some(f32_tensor) = half_tensor_as_f32(tensor) {
+                    // Display half-precision floats like we do f32:
+                    return f32_tensor.as_dynamic_image(annotations);
+                }
+
                 match (depth, tensor.dtype, tensor.meaning) {
                     (1, TensorDataType::U8, TensorDataMeaning::ClassId) => {
                         // Apply annotation mapping to raw bytes interpreted as u8
@@ -469,14 +502,12 @@ impl AsDynamicImage for ClassicTensor {
                 use image::io::Reader as ImageReader;
                 let mut reader = ImageReader::new(std::io::Cursor::new(bytes));
                 reader.set_format(image::ImageFormat::Jpeg);
-                // TODO(emilk): handle grayscale JPEG:s (depth == 1)
                 let img = {
                     crate::profile_scope!("decode_jpeg");
                     reader.decode()?
-                }
-                .into_rgb8();
+                };
 
-                if depth != 3 || img.width() != width || img.height() != height {
+                if !matches!(depth, 1 | 3) || img.width() != width || img.height() != height {
                     anyhow::bail!(
                         "Tensor shape ({shape:?}) did not match jpeg dimensions ({}x{})",
                         img.width(),
@@ -484,12 +515,46 @@ impl AsDynamicImage for ClassicTensor {
                     )
                 }
 
-                Ok(DynamicImage::ImageRgb8(img))
+                if depth == 1 {
+                    Ok(DynamicImage::ImageLuma8(img.into_luma8()))
+                } else {
+                    Ok(DynamicImage::ImageRgb8(img.into_rgb8()))
+                }
             }
         }
     }
 }
 
+/// Converts [`TensorDataType::F16`] and [`TensorDataType::BF16`] tensors to `f32`.
+fn half_tensor_as_f32(tensor: &ClassicTensor) -> Option<ClassicTensor> {
+    crate::profile_function!();
+
+    let bytes = match &tensor.data {
+        TensorDataStore::Dense(bytes) => bytes,
+        TensorDataStore::Jpeg(_) => return None,
+    };
+    let bits = bytes
+        .chunks_exact(2)
+        .map(|bytes| u16::from_ne_bytes([bytes[0], bytes[1]]));
+    let floats: Vec<f32> = match tensor.dtype {
+        TensorDataType::F16 => bits
+            .map(|bits| half::f16::from_bits(bits).to_f32())
+            .collect(),
+        TensorDataType::BF16 => bits
+            .map(|bits| half::bf16::from_bits(bits).to_f32())
+            .collect(),
+        _ => return None,
+    };
+
+    Some(ClassicTensor::new(
+        tensor.id(),
+        tensor.shape().to_vec(),
+        TensorDataType::F32,
+        tensor.meaning,
+        TensorDataStore::Dense(bytemuck::cast_slice(&floats).into()),
+    ))
+}
+
 fn dynamic_image_to_egui_color_image(
     dynamic_image: &DynamicImage,
     meaning: TensorDataMeaning,
@@ -14,9 +14,9 @@ impl Clipboard {
     pub fn set_text(&mut self, text: String) {
         if let Some(clipboard) = &mut self.arboard {
             if let Err(err) = clipboard.set_text(text) {
-                re_log::error!("Failed to copy image to clipboard: {err}",);
+                re_log::error!("Failed to copy text to clipboard: {err}",);
             } else {
-                re_log::info!("Image copied to clipboard");
+                re_log::info!("Text copied to clipboard");
             }
         }
     }
@@ -0,0 +1,65 @@
+use std::collections::VecDeque;
+
+use re_data_store::LogDb;
+use re_log_types::Time;
+
+/// We estimate the clock skew from the data that arrived this recently (by our clock),
+/// so that the estimate follows the logging machine if its clock is adjusted.
+const WINDOW_NS: i64 = 30_000_000_000;
+
+/// Estimates how far ahead of ours the clock of the logging machine is,
+/// so that we can tell how old live data is.
+///
+/// We can only measure clock skew in one direction: if data arrives that was logged in
+/// the future, the clock of the logging machine must be ahead of ours by at least that much.
+/// If the logging machine is behind us instead, data will look older than it is,
+/// which is the safer mistake to make when deciding whether to trust a live view.
+#[derive(Clone, Debug, Default)]
+pub struct ClockSync {
+    /// The latest log time we have seen, so that we only measure when new data arrives.
+    latest_log_time: Option<Time>,
+
+    /// When new data arrived (by our clock), and how far ahead of ours its log time was.
+    ///
+    /// Only the last [`WINDOW_NS`], but always at least the last measurement.
+    measurements: VecDeque<(Time, i64)>,
+}
+
+impl ClockSync {
+    /// Call once per frame.
+    pub fn update(&mut self, log_db: &LogDb) {
+        let Some(latest_log_time) = log_db.entity_db.latest_log_time_of_any_entity() else {
+            return;
+        };
+        if self.latest_log_time == Some(latest_log_time) {
+            return;
+        }
+        self.latest_log_time = Some(latest_log_time);
+
+        let now = Time::now();
+        let ahead_ns = latest_log_time.nanos_since_epoch() - now.nanos_since_epoch();
+        self.measurements.push_back((now, ahead_ns));
+        while 1 < self.measurements.len()
+            && WINDOW_NS < now.nanos_since_epoch() - self.measurements[0].0.nanos_since_epoch()
+        {
+            self.measurements.pop_front();
+        }
+    }
+
+    /// How far ahead of ours the clock of the logging machine is.
+    fn offset_ns(&self) -> i64 {
+        self.measurements
+            .iter()
+            .map(|&(_, ahead_ns)| ahead_ns)
+            .max()
+            .unwrap_or_default()
+            .max(0)
+    }
+
+    /// How long ago the data was logged, in seconds, compensating for clock skew.
+    pub fn age_sec(&self, log_time: Time) -> f64 {
+        let age_ns =
+            Time::now().nanos_since_epoch() + self.offset_ns() - log_time.nanos_since_epoch();
+        age_ns as f64 * 1e-9
+    }
+}
@@ -303,3 +303,16 @@ pub fn viridis_color_map(t: f32) -> [u8; 3] {
     let index = (t * 255.0 + 0.5) as usize;
     VIRIDIS_SRGB_BYTES[index.min(255)]
 }
+
+/// Given a value in [0, 1], output the color of the given color map.
+pub fn color_map(colormap: re_data_store::Colormap, t: f32) -> egui::Color32 {
+    let t = t.clamp(0.0, 1.0);
+    match colormap {
+        re_data_store::Colormap::Turbo => turbo_color_map(t),
+        re_data_store::Colormap::Viridis => {
+            let [r, g, b] = viridis_color_map(t);
+            egui::Color32::from_rgb(r, g, b)
+        }
+        re_data_store::Colormap::Grayscale => egui::Color32::from_gray((t * 255.0 + 0.5) as u8),
+    }
+}
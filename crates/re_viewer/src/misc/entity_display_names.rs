@@ -0,0 +1,61 @@
+use std::collections::BTreeMap;
+
+use re_log_types::EntityPath;
+
+/// Human-friendly names for entities, e.g. "Left camera" for `node_17/output_3`.
+///
+/// Shown instead of the logged path in trees, legends and tooltips.
+/// Like the [`crate::ui::Blueprint`], there is one set of names per application.
+#[derive(Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct EntityDisplayNames {
+    names: BTreeMap<EntityPath, String>,
+}
+
+impl EntityDisplayNames {
+    pub fn get(&self, entity_path: &EntityPath) -> Option<&str> {
+        self.names.get(entity_path).map(String::as_str)
+    }
+
+    /// An empty (or all whitespace) name removes the display name.
+    pub fn set(&mut self, entity_path: EntityPath, name: &str) {
+        if name.trim().is_empty() {
+            self.names.remove(&entity_path);
+        } else {
+            self.names.insert(entity_path, name.to_owned());
+        }
+    }
+
+    /// The display name if there is one, otherwise the full path.
+    pub fn name_or_path(&self, entity_path: &EntityPath) -> String {
+        self.get(entity_path)
+            .map_or_else(|| entity_path.to_string(), ToOwned::to_owned)
+    }
+
+    /// The display name if there is one, otherwise the last part of the path.
+    pub fn name_or_last_part(&self, entity_path: &EntityPath) -> String {
+        match self.get(entity_path) {
+            Some(name) => name.to_owned(),
+            None => entity_path
+                .iter()
+                .last()
+                .map_or_else(|| "/".to_owned(), |part| part.to_string()),
+        }
+    }
+}
+
+#[test]
+fn test_entity_display_names() {
+    let path = EntityPath::from("node_17/output_3");
+    let mut names = EntityDisplayNames::default();
+    assert_eq!(names.name_or_path(&path), "node_17/output_3");
+    assert_eq!(names.name_or_last_part(&path), "output_3");
+
+    names.set(path.clone(), "Left camera");
+    assert_eq!(names.get(&path), Some("Left camera"));
+    assert_eq!(names.name_or_path(&path), "Left camera");
+    assert_eq!(names.name_or_last_part(&path), "Left camera");
+
+    names.set(path.clone(), " ");
+    assert_eq!(names.get(&path), None);
+}
@@ -1,28 +1,22 @@
+use chrono::{Local, Timelike as _};
+use re_log_types::{TimeFormatOptions, TimeZone};
+
 /// Useful when showing dates/times on a timeline
 /// and you want it compact.
 ///
 /// Shows dates when zoomed out, shows times when zoomed in,
 /// shows relative millisecond when really zoomed in.
-pub fn format_time_compact(time: re_log_types::Time) -> String {
+///
+/// The precision in `options` is ignored.
+pub fn format_time_compact(time: re_log_types::Time, options: &TimeFormatOptions) -> String {
     let ns = time.nanos_since_epoch();
-    let relative_ns = ns % 1_000_000_000;
+    let relative_ns = ns.rem_euclid(1_000_000_000);
     let is_whole_second = relative_ns == 0;
     if is_whole_second {
-        if let Some(datetime) = time.to_chrono() {
-            return if time.is_exactly_midnight() {
-                // Show just the date:
-                datetime.format("%Y-%m-%dZ").to_string()
-            } else {
-                // Show just the time:
-                let is_whole_minute = ns % 60_000_000_000 == 0;
-                let is_whole_second = ns % 1_000_000_000 == 0;
-                if is_whole_minute {
-                    datetime.time().format("%H:%MZ").to_string()
-                } else if is_whole_second {
-                    datetime.time().format("%H:%M:%SZ").to_string()
-                } else {
-                    datetime.time().format("%H:%M:%S%.3fZ").to_string()
-                }
+        if let Some(datetime) = time.to_chrono_with(options) {
+            return match options.timezone {
+                TimeZone::Utc => format_datetime_compact(datetime, "Z"),
+                TimeZone::Local => format_datetime_compact(datetime.with_timezone(&Local), ""),
             };
         }
 
@@ -50,6 +44,23 @@ pub fn format_time_compact(time: re_log_types::Time) -> String {
     }
 }
 
+/// The date at midnight, else just the time, for a whole second.
+fn format_datetime_compact<Tz>(datetime: chrono::DateTime<Tz>, suffix: &str) -> String
+where
+    Tz: chrono::TimeZone,
+    Tz::Offset: std::fmt::Display,
+{
+    let time = datetime.time();
+    if time.num_seconds_from_midnight() == 0 {
+        // Show just the date:
+        datetime.format(&format!("%Y-%m-%d{suffix}")).to_string()
+    } else if time.second() == 0 {
+        time.format(&format!("%H:%M{suffix}")).to_string()
+    } else {
+        time.format(&format!("%H:%M:%S{suffix}")).to_string()
+    }
+}
+
 /// When showing grid-lines representing time.
 ///
 /// Given some spacing (e.g. 10s), return the next spacing (60s).
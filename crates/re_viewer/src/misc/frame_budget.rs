@@ -0,0 +1,140 @@
+//! Lowers the render quality while frames are too slow, e.g. on a weak laptop,
+//! and restores it once they are fast again.
+
+use super::{AppOptions, RenderQuality};
+
+/// Frames must be over budget for at least this long before we lower the quality…
+const LOWER_AFTER_SECS: f64 = 2.0;
+
+/// …and this many frames, so that a single hiccup after being idle doesn't count.
+const LOWER_AFTER_FRAMES: usize = 30;
+
+/// Frames must be fast for this long before we raise the quality again.
+const RAISE_AFTER_SECS: f64 = 10.0;
+
+/// Fast means faster than this fraction of the budget,
+/// so that we don't flip back and forth between two qualities.
+const RAISE_BELOW_FRACTION: f32 = 0.5;
+
+/// What the user should be told about.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QualityChange {
+    Lowered(RenderQuality),
+    Restored(RenderQuality),
+}
+
+/// Frames in a row that were all slow, or all fast.
+#[derive(Clone, Copy)]
+struct Streak {
+    since: f64,
+    num_frames: usize,
+}
+
+/// Sets [`AppOptions::render_quality_fallback`] based on [`AppOptions::frame_budget`].
+#[derive(Default)]
+pub struct FrameBudgetWatchdog {
+    slow: Option<Streak>,
+    fast: Option<Streak>,
+}
+
+impl FrameBudgetWatchdog {
+    /// Call once per frame, with the current time and the recent average frame time in seconds.
+    pub fn update(
+        &mut self,
+        options: &mut AppOptions,
+        now: f64,
+        frame_time: f32,
+    ) -> Option<QualityChange> {
+        let budget = options.frame_budget;
+        if budget <= 0.0 || options.render_quality_override.is_some() {
+            // The user decides:
+            options.render_quality_fallback = None;
+            *self = Self::default();
+            return None;
+        }
+
+        if budget < frame_time {
+            self.fast = None;
+            let slow = extend(&mut self.slow, now);
+            if LOWER_AFTER_SECS <= now - slow.since && LOWER_AFTER_FRAMES <= slow.num_frames {
+                // Give the lower quality a chance before lowering it any further:
+                self.slow = None;
+                if let Some(lower) = options.render_quality().lower() {
+                    options.render_quality_fallback = Some(lower);
+                    return Some(QualityChange::Lowered(lower));
+                }
+            }
+        } else if frame_time < RAISE_BELOW_FRACTION * budget
+            && options.render_quality_fallback.is_some()
+        {
+            self.slow = None;
+            let fast = extend(&mut self.fast, now);
+            if RAISE_AFTER_SECS <= now - fast.since {
+                self.fast = None;
+                options.render_quality_fallback = options
+                    .render_quality()
+                    .higher()
+                    .filter(|&higher| higher < options.render_quality);
+                return Some(QualityChange::Restored(options.render_quality()));
+            }
+        } else {
+            self.slow = None;
+            self.fast = None;
+        }
+
+        None
+    }
+}
+
+fn extend(streak: &mut Option<Streak>, now: f64) -> Streak {
+    let streak = streak.get_or_insert(Streak {
+        since: now,
+        num_frames: 0,
+    });
+    streak.num_frames += 1;
+    *streak
+}
+
+#[test]
+fn test_frame_budget_watchdog() {
+    let mut options = AppOptions {
+        frame_budget: 0.030,
+        render_quality: RenderQuality::Quality,
+        ..Default::default()
+    };
+    let mut watchdog = FrameBudgetWatchdog::default();
+    let mut run = |options: &mut AppOptions, start: f64, secs: f64, frame_time: f32| {
+        let mut changes = vec![];
+        let mut now = start;
+        while now < start + secs {
+            changes.extend(watchdog.update(options, now, frame_time));
+            now += 1.0 / 60.0;
+        }
+        changes
+    };
+
+    // A short hiccup is fine:
+    assert_eq!(run(&mut options, 0.0, 1.0, 0.050), vec![]);
+    assert_eq!(run(&mut options, 1.0, 1.0, 0.010), vec![]);
+
+    // Consistently slow frames lower the quality one step at a time:
+    assert_eq!(
+        run(&mut options, 2.0, 5.0, 0.050),
+        vec![
+            QualityChange::Lowered(RenderQuality::Balanced),
+            QualityChange::Lowered(RenderQuality::Performance)
+        ]
+    );
+    assert_eq!(options.render_quality(), RenderQuality::Performance);
+    assert_eq!(options.render_quality, RenderQuality::Quality);
+
+    // Fast frames restore it, slowly:
+    assert_eq!(
+        run(&mut options, 7.0, 25.0, 0.005),
+        vec![
+            QualityChange::Restored(RenderQuality::Balanced),
+            QualityChange::Restored(RenderQuality::Quality)
+        ]
+    );
+    assert_eq!(options.render_quality_fallback, None);
+}
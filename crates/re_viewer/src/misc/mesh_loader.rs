@@ -46,8 +46,11 @@ impl LoadedMesh {
                     render_ctx,
                 )
             }
-            // TODO(cmc): support obj
-            MeshFormat::Obj => anyhow::bail!(".obj files are not supported yet"),
+            MeshFormat::Obj => re_renderer::importer::obj::load_obj_from_buffer(
+                bytes,
+                ResourceLifeTime::LongLived,
+                render_ctx,
+            ),
         }?;
         let bbox = re_renderer::importer::calculate_bounding_box(&mesh_instances);
 
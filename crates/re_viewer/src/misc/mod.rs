@@ -1,17 +1,30 @@
+pub(crate) mod alerts;
 mod app_options;
+mod bookmark;
 pub mod caches;
+mod clock_sync;
+mod entity_display_names;
 pub mod color_map;
 pub mod format_time;
+mod frame_budget;
 mod item;
 pub(crate) mod mesh_loader;
+mod number_format;
+mod paused_messages;
+mod recent;
 mod selection_state;
+mod shared_annotation_contexts;
 pub(crate) mod space_info;
 pub(crate) mod time_control;
 pub(crate) mod time_control_ui;
 mod transform_cache;
+pub(crate) mod units;
 mod viewer_context;
+mod visible_history;
 
 pub use caches::Caches;
+pub(crate) use clock_sync::ClockSync;
+pub(crate) use paused_messages::PausedMessages;
 
 #[cfg(not(target_arch = "wasm32"))]
 pub(crate) use clipboard::Clipboard;
@@ -19,20 +32,33 @@ pub(crate) use clipboard::Clipboard;
 pub(crate) use time_control::{TimeControl, TimeView};
 pub(crate) use viewer_context::*;
 
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) mod auto_save;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) mod blueprint_file;
+
 #[cfg(not(target_arch = "wasm32"))]
 pub(crate) mod profiler;
 
 #[cfg(not(target_arch = "wasm32"))]
 pub mod clipboard;
 
-pub use transform_cache::{TransformCache, UnreachableTransform};
+pub use transform_cache::{TransformCache, TransformCaches, UnreachableTransform};
 pub use {
     app_options::*,
+    bookmark::Bookmark,
+    entity_display_names::EntityDisplayNames,
+    frame_budget::{FrameBudgetWatchdog, QualityChange},
     item::{Item, ItemCollection},
+    number_format::{Notation, NumberFormat},
+    recent::{RecentItem, RecentItems},
     selection_state::{
         HoverHighlight, HoveredSpace, InteractionHighlight, OptionalSpaceViewEntityHighlight,
         SelectionHighlight, SelectionState, SpaceViewHighlights,
     },
+    shared_annotation_contexts::SharedAnnotationContexts,
+    visible_history::{HistoryDataKind, VisibleHistoryDefaults},
 };
 
 // ----------------------------------------------------------------------------
@@ -0,0 +1,137 @@
+/// How numbers are written, e.g. `0.0001235` or `1.235e-4`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum Notation {
+    /// Scientific notation for very large and very small numbers, fixed otherwise.
+    #[default]
+    Auto,
+
+    /// Always `123.45`.
+    Fixed,
+
+    /// Always `1.2345e2`.
+    Scientific,
+}
+
+impl Notation {
+    pub const ALL: [Self; 3] = [Self::Auto, Self::Fixed, Self::Scientific];
+}
+
+impl std::fmt::Display for Notation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Auto => "Auto",
+            Self::Fixed => "Fixed",
+            Self::Scientific => "Scientific",
+        })
+    }
+}
+
+/// How numeric values are shown in tooltips, the selection panel and plot readouts.
+///
+/// There is one for the whole viewer in [`super::AppOptions`],
+/// which each space view can override.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct NumberFormat {
+    pub notation: Notation,
+
+    /// Number of decimals, or `None` for a decent number depending on the magnitude.
+    ///
+    /// In scientific notation, this is the number of decimals of the mantissa.
+    pub decimals: Option<usize>,
+}
+
+impl NumberFormat {
+    /// Most decimals that can be picked in the ui.
+    pub const MAX_DECIMALS: usize = 12;
+
+    pub fn format_f64(&self, value: f64) -> String {
+        if !value.is_finite() {
+            return value.to_string();
+        }
+
+        let scientific = match self.notation {
+            Notation::Auto => {
+                let magnitude = value.abs();
+                value != 0.0 && !(1e-4..1e7).contains(&magnitude)
+            }
+            Notation::Fixed => false,
+            Notation::Scientific => true,
+        };
+
+        match (scientific, self.decimals) {
+            (false, None) => re_format::format_f64(value),
+            (false, Some(decimals)) => format!("{value:.decimals$}"),
+            (true, None) => {
+                // Four decimals, without the trailing zeros:
+                let text = format!("{value:.4e}");
+                match text.split_once('e') {
+                    Some((mantissa, exponent)) if mantissa.contains('.') => {
+                        let mantissa = mantissa.trim_end_matches('0').trim_end_matches('.');
+                        format!("{mantissa}e{exponent}")
+                    }
+                    _ => text,
+                }
+            }
+            (true, Some(decimals)) => format!("{value:.decimals$e}"),
+        }
+    }
+
+    pub fn format_f32(&self, value: f32) -> String {
+        self.format_f64(value as f64)
+    }
+
+    /// Combo boxes for the notation and number of decimals.
+    pub fn ui(&mut self, ui: &mut egui::Ui, id_source: impl std::hash::Hash) {
+        let id = egui::Id::new(id_source);
+
+        egui::ComboBox::from_id_source(id.with("notation"))
+            .selected_text(self.notation.to_string())
+            .width(90.0)
+            .show_ui(ui, |ui| {
+                for notation in Notation::ALL {
+                    ui.selectable_value(&mut self.notation, notation, notation.to_string());
+                }
+            });
+
+        egui::ComboBox::from_id_source(id.with("decimals"))
+            .selected_text(match self.decimals {
+                Some(decimals) => format!("{decimals} decimals"),
+                None => "Auto decimals".to_owned(),
+            })
+            .width(110.0)
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut self.decimals, None, "Auto");
+                for decimals in 0..=Self::MAX_DECIMALS {
+                    ui.selectable_value(&mut self.decimals, Some(decimals), decimals.to_string());
+                }
+            })
+            .response
+            .on_hover_text(format!("Example: {}", self.format_f64(1234.56789)));
+    }
+}
+
+#[test]
+fn test_number_format() {
+    let auto = NumberFormat::default();
+    assert_eq!(auto.format_f64(12.3456789), "12.35");
+    assert_eq!(auto.format_f64(0.0), "0");
+    assert_eq!(auto.format_f64(0.0000123456789), "1.2346e-5");
+    assert_eq!(auto.format_f64(123_456_789.0), "1.2346e8");
+    assert_eq!(auto.format_f64(1e9), "1e9");
+    assert_eq!(auto.format_f64(f64::NAN), "NaN");
+
+    let fixed = NumberFormat {
+        notation: Notation::Fixed,
+        decimals: Some(2),
+    };
+    assert_eq!(fixed.format_f64(0.0000123456789), "0.00");
+    assert_eq!(fixed.format_f64(-1234.5678), "-1234.57");
+
+    let scientific = NumberFormat {
+        notation: Notation::Scientific,
+        decimals: Some(3),
+    };
+    assert_eq!(scientific.format_f64(1234.5678), "1.235e3");
+    assert_eq!(scientific.format_f64(-0.0012346), "-1.235e-3");
+}
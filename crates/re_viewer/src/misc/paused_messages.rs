@@ -0,0 +1,80 @@
+use std::collections::VecDeque;
+
+use re_log_types::{external::arrow2, LogMsg};
+use re_smart_channel::Source;
+
+/// We never hold back more than this while ingestion is paused, whatever the memory limit.
+const MAX_BYTES: u64 = 2 * 1024 * 1024 * 1024;
+
+/// The messages that arrived while ingestion was paused, oldest first.
+///
+/// When they take up too much memory, the oldest are dropped.
+#[derive(Default)]
+pub struct PausedMessages {
+    msgs: VecDeque<(Source, LogMsg, u64)>,
+
+    /// Approximately, see [`approx_num_bytes`].
+    num_bytes: u64,
+
+    /// How many of the oldest messages were dropped since we last paused.
+    num_dropped: usize,
+}
+
+impl PausedMessages {
+    pub fn is_empty(&self) -> bool {
+        self.msgs.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.msgs.len()
+    }
+
+    pub fn num_dropped(&self) -> usize {
+        self.num_dropped
+    }
+
+    /// Call when pausing, to start counting the dropped messages from zero.
+    pub fn reset_num_dropped(&mut self) {
+        self.num_dropped = 0;
+    }
+
+    pub fn push_back(&mut self, source: Source, msg: LogMsg) {
+        let num_bytes = approx_num_bytes(&msg);
+        self.msgs.push_back((source, msg, num_bytes));
+        self.num_bytes += num_bytes;
+        while MAX_BYTES < self.num_bytes && self.pop_front().is_some() {
+            self.num_dropped += 1;
+        }
+    }
+
+    pub fn pop_front(&mut self) -> Option<(Source, LogMsg)> {
+        let (source, msg, num_bytes) = self.msgs.pop_front()?;
+        self.num_bytes -= num_bytes;
+        Some((source, msg))
+    }
+
+    /// Drop the oldest messages to stay below the memory limit, like the data in the store.
+    pub fn purge_fraction(&mut self, fraction_to_purge: f32) {
+        let num_to_drop = (self.msgs.len() as f32 * fraction_to_purge).ceil() as usize;
+        for _ in 0..num_to_drop {
+            if self.pop_front().is_none() {
+                break;
+            }
+            self.num_dropped += 1;
+        }
+    }
+}
+
+/// The arrow data is what takes up the memory.
+fn approx_num_bytes(msg: &LogMsg) -> u64 {
+    let data_bytes = match msg {
+        LogMsg::ArrowMsg(msg) => msg
+            .chunk
+            .arrays()
+            .iter()
+            .map(|array| arrow2::compute::aggregate::estimated_bytes_size(array.as_ref()) as u64)
+            .sum(),
+        _ => 0,
+    };
+    std::mem::size_of::<LogMsg>() as u64 + data_bytes
+}
@@ -0,0 +1,129 @@
+/// How many recent files and connections we remember.
+const MAX_RECENT_ITEMS: usize = 10;
+
+/// Something that was opened recently, and can be opened again.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RecentItem {
+    /// A `.rrd` file.
+    File(std::path::PathBuf),
+
+    /// The websocket url of a Rerun Server.
+    Connection(String),
+}
+
+/// Recently opened recordings and server connections, most recent first.
+///
+/// Persisted across sessions, since users tend to reopen the same files
+/// and reconnect to the same robots over and over again.
+#[derive(Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct RecentItems {
+    files: Vec<std::path::PathBuf>,
+    connections: Vec<String>,
+}
+
+impl RecentItems {
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty() && self.connections.is_empty()
+    }
+
+    pub fn add(&mut self, item: RecentItem) {
+        match item {
+            RecentItem::File(path) => push_front_unique(&mut self.files, path),
+            RecentItem::Connection(url) => push_front_unique(&mut self.connections, url),
+        }
+    }
+
+    /// Forget about e.g. a file that no longer exists.
+    pub fn remove(&mut self, item: &RecentItem) {
+        match item {
+            RecentItem::File(path) => self.files.retain(|p| p != path),
+            RecentItem::Connection(url) => self.connections.retain(|u| u != url),
+        }
+    }
+
+    /// Remember the data source of a recording, if it is something that can be reopened.
+    pub fn add_source(&mut self, source: &re_smart_channel::Source) {
+        match source {
+            re_smart_channel::Source::File { path }
+            | re_smart_channel::Source::FollowedFile { path } => {
+                // On web we only know the file name, which we can't reopen.
+                if path.is_absolute() {
+                    self.add(RecentItem::File(path.clone()));
+                }
+            }
+            re_smart_channel::Source::WsClient { ws_server_url } => {
+                self.add(RecentItem::Connection(ws_server_url.clone()));
+            }
+            re_smart_channel::Source::Sdk | re_smart_channel::Source::TcpServer { .. } => {}
+        }
+    }
+
+    /// Shows recent files and connections as buttons.
+    ///
+    /// Returns the item that was clicked, if any.
+    pub fn ui(&self, ui: &mut egui::Ui) -> Option<RecentItem> {
+        let mut clicked = None;
+
+        if !self.files.is_empty() {
+            ui.strong("Recent files");
+            for path in &self.files {
+                let name = path.file_name().map_or_else(
+                    || path.display().to_string(),
+                    |name| name.to_string_lossy().to_string(),
+                );
+                if ui
+                    .button(name)
+                    .on_hover_text(path.display().to_string())
+                    .clicked()
+                {
+                    clicked = Some(RecentItem::File(path.clone()));
+                }
+            }
+        }
+
+        if !self.connections.is_empty() {
+            if !self.files.is_empty() {
+                ui.separator();
+            }
+            ui.strong("Recent connections");
+            for url in &self.connections {
+                if ui.button(url).clicked() {
+                    clicked = Some(RecentItem::Connection(url.clone()));
+                }
+            }
+        }
+
+        clicked
+    }
+}
+
+fn push_front_unique<T: PartialEq>(items: &mut Vec<T>, item: T) {
+    items.retain(|existing| existing != &item);
+    items.insert(0, item);
+    items.truncate(MAX_RECENT_ITEMS);
+}
+
+#[test]
+fn test_recent_items() {
+    let mut recent = RecentItems::default();
+    assert!(recent.is_empty());
+
+    for i in 0..MAX_RECENT_ITEMS + 5 {
+        recent.add(RecentItem::Connection(format!("ws://127.0.0.1:{i}")));
+    }
+    assert_eq!(recent.connections.len(), MAX_RECENT_ITEMS);
+    assert_eq!(
+        recent.connections[0],
+        format!("ws://127.0.0.1:{}", MAX_RECENT_ITEMS + 4)
+    );
+
+    // Re-adding moves it to the front, without duplicates:
+    let url = recent.connections[3].clone();
+    recent.add(RecentItem::Connection(url.clone()));
+    assert_eq!(recent.connections.len(), MAX_RECENT_ITEMS);
+    assert_eq!(recent.connections[0], url);
+
+    recent.remove(&RecentItem::Connection(url.clone()));
+    assert!(!recent.connections.contains(&url));
+}
@@ -1,7 +1,7 @@
 use ahash::{HashMap, HashSet};
 use nohash_hasher::IntMap;
 use re_data_store::{EntityPath, LogDb};
-use re_log_types::{component_types::InstanceKey, EntityPathHash};
+use re_log_types::{component_types::InstanceKey, EntityPathHash, TimeInt, Timeline};
 
 use crate::ui::{Blueprint, HistoricalSelection, SelectionHistory, SpaceView, SpaceViewId};
 
@@ -165,6 +165,10 @@ pub struct SelectionState {
     #[serde(skip)]
     history: SelectionHistory,
 
+    /// The time cursor at the start of this frame, recorded with new selections in the history.
+    #[serde(skip)]
+    time: Option<(Timeline, TimeInt)>,
+
     /// What objects are hovered? Read from this.
     #[serde(skip)]
     hovered_previous_frame: ItemCollection,
@@ -183,11 +187,17 @@ pub struct SelectionState {
 }
 
 impl SelectionState {
-    /// Called at the start of each frame
-    pub fn on_frame_start(&mut self, log_db: &LogDb, blueprint: &Blueprint) {
+    /// Called at the start of each frame, with where the time cursor is.
+    pub fn on_frame_start(
+        &mut self,
+        log_db: &LogDb,
+        blueprint: &Blueprint,
+        time: Option<(Timeline, TimeInt)>,
+    ) {
         crate::profile_function!();
 
         self.history.on_frame_start(log_db, blueprint);
+        self.time = time;
 
         self.hovered_space_previous_frame =
             std::mem::replace(&mut self.hovered_space_this_frame, HoveredSpace::None);
@@ -195,13 +205,23 @@ impl SelectionState {
     }
 
     /// Selects the previous element in the history if any.
+    ///
+    /// Returns it, so that the time cursor can be moved to where it was, see
+    /// [`super::RecordingConfig::restore_time_of`].
     pub fn select_previous(&mut self) -> Option<HistoricalSelection> {
-        self.history.select_previous()
+        let previous = self.history.select_previous()?;
+        self.selection = previous.selection.clone();
+        Some(previous)
     }
 
-    /// Selections the next element in the history if any.
+    /// Selects the next element in the history if any.
+    ///
+    /// Returns it, so that the time cursor can be moved to where it was, see
+    /// [`super::RecordingConfig::restore_time_of`].
     pub fn select_next(&mut self) -> Option<HistoricalSelection> {
-        self.history.select_next()
+        let next = self.history.select_next()?;
+        self.selection = next.selection.clone();
+        Some(next)
     }
 
     /// Clears the current selection out.
@@ -221,7 +241,7 @@ impl SelectionState {
     /// Returns the previous selection.
     pub fn set_multi_selection(&mut self, items: impl Iterator<Item = Item>) -> ItemCollection {
         let new_selection = ItemCollection::new(items);
-        self.history.update_selection(&new_selection);
+        self.history.update_selection(&new_selection, self.time);
         std::mem::replace(&mut self.selection, new_selection)
     }
 
@@ -270,13 +290,18 @@ impl SelectionState {
         self.hovered_space_this_frame = space;
     }
 
+    /// The back and forward buttons.
+    ///
+    /// Returns the selection from the history that was gone to, if any.
     pub fn selection_ui(
         &mut self,
         re_ui: &re_ui::ReUi,
         ui: &mut egui::Ui,
         blueprint: &mut Blueprint,
-    ) -> Option<ItemCollection> {
-        self.history.selection_ui(re_ui, ui, blueprint)
+    ) -> Option<HistoricalSelection> {
+        let historical = self.history.selection_ui(re_ui, ui, blueprint)?;
+        self.selection = historical.selection.clone();
+        Some(historical)
     }
 
     pub fn highlight_for_ui_element(&self, test: &Item) -> HoverHighlight {
@@ -0,0 +1,59 @@
+use re_log_types::{
+    component_types::AnnotationContext,
+    external::arrow2_convert::deserialize::arrow_array_deserialize_iterator,
+    msg_bundle::{Component as _, MsgBundle},
+    LogMsg,
+};
+
+/// How many annotation contexts we remember.
+const MAX_SHARED_CONTEXTS: usize = 64;
+
+/// Annotation contexts seen in any recording, so that other recordings can refer to them
+/// with an [`re_log_types::component_types::AnnotationContextRef`] instead of logging them again.
+///
+/// Persisted across sessions, so a robot can log the reference from its second run on.
+#[derive(Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct SharedAnnotationContexts {
+    /// Keyed by [`AnnotationContext::content_hash`], most recently seen first.
+    contexts: Vec<(u64, AnnotationContext)>,
+}
+
+impl SharedAnnotationContexts {
+    pub fn get(&self, content_hash: u64) -> Option<&AnnotationContext> {
+        self.contexts
+            .iter()
+            .find(|(hash, _)| *hash == content_hash)
+            .map(|(_, context)| context)
+    }
+
+    /// Remember any annotation contexts in this message.
+    pub fn on_message(&mut self, msg: &LogMsg) {
+        let msg = match msg {
+            LogMsg::ArrowMsg(msg) if msg.has_component(&AnnotationContext::name()) => msg,
+            _ => return,
+        };
+
+        let bundle = match MsgBundle::try_from(msg) {
+            Ok(bundle) => bundle,
+            Err(err) => {
+                re_log::warn_once!("Failed to read annotation context: {err}");
+                return;
+            }
+        };
+        if let Some(index) = bundle.find_component(&AnnotationContext::name()) {
+            let values = bundle.components[index].value_list().values();
+            match arrow_array_deserialize_iterator::<AnnotationContext>(values.as_ref()) {
+                Ok(contexts) => contexts.for_each(|context| self.add(context)),
+                Err(err) => re_log::warn_once!("Failed to read annotation context: {err}"),
+            }
+        }
+    }
+
+    fn add(&mut self, context: AnnotationContext) {
+        let content_hash = context.content_hash();
+        self.contexts.retain(|(hash, _)| *hash != content_hash);
+        self.contexts.insert(0, (content_hash, context));
+        self.contexts.truncate(MAX_SHARED_CONTEXTS);
+    }
+}
@@ -113,6 +113,12 @@ impl Default for TimeControl {
 }
 
 impl TimeControl {
+    /// The playback speeds offered in the ui and stepped through with the keyboard.
+    ///
+    /// Playing faster doesn't mean more work: each frame only shows the latest data at the
+    /// current time, no matter how much was skipped since the previous frame.
+    pub const SPEED_PRESETS: [f32; 6] = [0.25, 0.5, 1.0, 2.0, 4.0, 8.0];
+
     /// Update the current time
     pub fn move_time(&mut self, egui_ctx: &egui::Context, times_per_timeline: &TimesPerTimeline) {
         self.select_a_valid_timeline(times_per_timeline);
@@ -350,6 +356,20 @@ impl TimeControl {
         self.speed = speed;
     }
 
+    /// Switch to the next faster of the [`Self::SPEED_PRESETS`].
+    pub fn speed_up(&mut self) {
+        if let Some(&faster) = Self::SPEED_PRESETS.iter().find(|&&s| self.speed < s) {
+            self.speed = faster;
+        }
+    }
+
+    /// Switch to the next slower of the [`Self::SPEED_PRESETS`].
+    pub fn slow_down(&mut self) {
+        if let Some(&slower) = Self::SPEED_PRESETS.iter().rev().find(|&&s| s < self.speed) {
+            self.speed = slower;
+        }
+    }
+
     /// playback fps
     pub fn fps(&self) -> Option<f32> {
         self.states.get(&self.timeline).map(|state| state.fps)
@@ -586,3 +606,23 @@ fn step_back_time_looped(
         step_back_time(time, values).into()
     }
 }
+
+#[test]
+fn test_speed_presets() {
+    let mut time_ctrl = TimeControl::default();
+    time_ctrl.speed_up();
+    assert_eq!(time_ctrl.speed(), 2.0);
+    for _ in 0..10 {
+        time_ctrl.speed_up();
+    }
+    assert_eq!(time_ctrl.speed(), 8.0);
+
+    // A custom speed goes to the nearest preset in that direction:
+    time_ctrl.set_speed(3.0);
+    time_ctrl.slow_down();
+    assert_eq!(time_ctrl.speed(), 2.0);
+    for _ in 0..10 {
+        time_ctrl.slow_down();
+    }
+    assert_eq!(time_ctrl.speed(), 0.25);
+}
@@ -123,7 +123,10 @@ impl TimeControl {
     ) {
         if re_ui
             .large_button(ui, &re_ui::icons::ARROW_LEFT)
-            .on_hover_text("Step back to previous time with any new data (left arrow)")
+            .on_hover_text(format!(
+                "Step back to previous time with any new data.{}",
+                shortcut_text(ui.ctx(), re_ui::Command::PlaybackStepBack)
+            ))
             .clicked()
         {
             self.step_time_back(times_per_timeline);
@@ -131,7 +134,10 @@ impl TimeControl {
 
         if re_ui
             .large_button(ui, &re_ui::icons::ARROW_RIGHT)
-            .on_hover_text("Step forwards to next time with any new data (right arrow)")
+            .on_hover_text(format!(
+                "Step forwards to next time with any new data.{}",
+                shortcut_text(ui.ctx(), re_ui::Command::PlaybackStepForward)
+            ))
             .clicked()
         {
             self.step_time_fwd(times_per_timeline);
@@ -186,7 +192,22 @@ impl TimeControl {
                 .speed(drag_speed)
                 .suffix("x"),
         )
-        .on_hover_text("Playback speed.");
+        .on_hover_text(format!(
+            "Playback speed. Right-click for common speeds.{}{}",
+            shortcut_text(ui.ctx(), re_ui::Command::PlaybackSpeedUp),
+            shortcut_text(ui.ctx(), re_ui::Command::PlaybackSlowDown),
+        ))
+        .context_menu(|ui| {
+            for preset in Self::SPEED_PRESETS {
+                if ui
+                    .selectable_label(speed == preset, format!("{preset}x"))
+                    .clicked()
+                {
+                    speed = preset;
+                    ui.close_menu();
+                }
+            }
+        });
         self.set_speed(speed);
     }
 }
@@ -198,3 +219,15 @@ fn toggle_playback_text(egui_ctx: &egui::Context) -> String {
         Default::default()
     }
 }
+
+fn shortcut_text(egui_ctx: &egui::Context, command: re_ui::Command) -> String {
+    if let Some(shortcut) = command.kb_shortcut() {
+        format!(
+            "\n{}: {}",
+            command.text(),
+            egui_ctx.format_shortcut(&shortcut)
+        )
+    } else {
+        Default::default()
+    }
+}
@@ -1,8 +1,14 @@
+use std::sync::Arc;
+
 use nohash_hasher::IntMap;
-use re_arrow_store::LatestAtQuery;
-use re_data_store::{log_db::EntityDb, query_transform, EntityPath, EntityPropertyMap, EntityTree};
+use re_arrow_store::{LatestAtQuery, TimeInt};
+use re_data_store::{
+    log_db::EntityDb, query_transform, EntityPath, EntityProperties, EntityPropertyMap, EntityTree,
+    LogDb,
+};
+use re_log_types::{RecordingId, Timeline};
 
-use crate::misc::TimeControl;
+use crate::{misc::TimeControl, ui::SpaceViewId};
 
 /// Provides transforms from an entity to a chosen reference space for all elements in the scene
 /// for the currently selected time & timeline.
@@ -10,7 +16,7 @@ use crate::misc::TimeControl;
 /// The renderer then uses this reference space as its world space,
 /// making world and reference space equivalent for a given space view.
 ///
-/// Should be recomputed every frame.
+/// Kept between frames by [`TransformCaches`] for as long as the transforms don't change.
 #[derive(Clone)]
 pub struct TransformCache {
     /// All transforms provided are relative to this reference path.
@@ -25,6 +31,9 @@ pub struct TransformCache {
 
     /// The first parent of reference_path that is no longer reachable.
     first_unreachable_parent: Option<(EntityPath, UnreachableTransform)>,
+
+    /// The properties of the pinhole cameras we went through, which affect their transforms.
+    pinhole_properties: Vec<(EntityPath, EntityProperties)>,
 }
 
 #[derive(Clone, Copy)]
@@ -78,6 +87,7 @@ impl TransformCache {
             reference_from_entity_per_entity: Default::default(),
             unreachable_descendants: Default::default(),
             first_unreachable_parent: None,
+            pinhole_properties: Default::default(),
         };
 
         // Find the entity path tree for the root.
@@ -170,14 +180,22 @@ impl TransformCache {
         }
 
         for child_tree in tree.children.values() {
+            let was_below_pinhole = encountered_pinhole;
             let mut encountered_pinhole = encountered_pinhole;
-            let reference_from_child = match transform_at(
+            let transform = transform_at(
                 &child_tree.path,
                 entity_db,
                 entity_properties,
                 query,
                 &mut encountered_pinhole,
-            ) {
+            );
+            if encountered_pinhole && !was_below_pinhole {
+                self.pinhole_properties.push((
+                    child_tree.path.clone(),
+                    entity_properties.get(&child_tree.path),
+                ));
+            }
+            let reference_from_child = match transform {
                 Err(unreachable_reason) => {
                     self.unreachable_descendants
                         .push((child_tree.path.clone(), unreachable_reason));
@@ -215,6 +233,89 @@ impl TransformCache {
     // }
 }
 
+// ----------------------------------------------------------------------------
+
+/// When the transforms of a [`TransformCache`] are still the same.
+#[derive(Clone, PartialEq, Eq)]
+struct TransformCacheKey {
+    recording_id: RecordingId,
+    space_path: EntityPath,
+    timeline: Timeline,
+
+    /// New entities need transforms too.
+    num_entities: usize,
+
+    /// See [`EntityDb::transform_generation`].
+    transform_generation: u64,
+
+    /// Transforms only change at the times they are logged at, see [`EntityDb::latest_transform_time`].
+    latest_transform_time: Option<TimeInt>,
+}
+
+/// Remembers the [`TransformCache`] of each space view between frames,
+/// so that we don't walk the transform hierarchy of deep scene graphs every frame.
+///
+/// The transforms are resolved again when a new transform is logged that applies at the current time,
+/// when the time moves past one that was logged, when new entities show up,
+/// or when the properties of a pinhole camera change.
+#[derive(Default)]
+pub struct TransformCaches {
+    per_space_view: ahash::HashMap<SpaceViewId, (TransformCacheKey, Arc<TransformCache>)>,
+}
+
+impl TransformCaches {
+    /// The transforms of the space view, see [`TransformCache::determine_transforms`].
+    pub fn get_or_determine(
+        &mut self,
+        space_view_id: SpaceViewId,
+        log_db: &LogDb,
+        time_ctrl: &TimeControl,
+        space_path: &EntityPath,
+        entity_prop_map: &EntityPropertyMap,
+    ) -> Arc<TransformCache> {
+        let query = time_ctrl.current_query();
+        let entity_db = &log_db.entity_db;
+        let key = TransformCacheKey {
+            recording_id: log_db.recording_id(),
+            space_path: space_path.clone(),
+            timeline: query.timeline,
+            num_entities: entity_db.entity_path_from_hash.len(),
+            transform_generation: entity_db.transform_generation(),
+            latest_transform_time: entity_db.latest_transform_time(&query.timeline, query.at),
+        };
+
+        let is_valid = |(cached_key, transforms): &(TransformCacheKey, Arc<TransformCache>)| {
+            *cached_key == key
+                && transforms
+                    .pinhole_properties
+                    .iter()
+                    .all(|(entity_path, properties)| {
+                        entity_prop_map.get(entity_path) == *properties
+                    })
+        };
+
+        if !self
+            .per_space_view
+            .get(&space_view_id)
+            .map_or(false, is_valid)
+        {
+            let transforms = TransformCache::determine_transforms(
+                entity_db,
+                time_ctrl,
+                space_path,
+                entity_prop_map,
+            );
+            self.per_space_view
+                .insert(space_view_id, (key, Arc::new(transforms)));
+        }
+        self.per_space_view[&space_view_id].1.clone()
+    }
+
+    pub fn clear(&mut self) {
+        self.per_space_view.clear();
+    }
+}
+
 fn transform_at(
     entity_path: &EntityPath,
     entity_db: &EntityDb,
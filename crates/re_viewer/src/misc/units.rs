@@ -0,0 +1,122 @@
+/// Which units to show values in, regardless of the unit they were logged in.
+///
+/// `None` means "as logged".
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct UnitConversions {
+    pub angles: Option<AngleUnit>,
+    pub lengths: Option<LengthUnit>,
+}
+
+impl UnitConversions {
+    /// How to show values logged with the given unit.
+    ///
+    /// Returns the factor to multiply the values with, and the unit to show.
+    /// Only the numerator of compound units is converted, so `rad/s` can become `°/s`.
+    pub fn convert(&self, unit: &str) -> (f64, String) {
+        let (numerator, rest) = match unit.find('/') {
+            Some(slash) => unit.split_at(slash),
+            None => (unit, ""),
+        };
+
+        let converted = AngleUnit::parse(numerator)
+            .zip(self.angles)
+            .map(|(from, to)| (from.in_radians() / to.in_radians(), to.symbol()))
+            .or_else(|| {
+                LengthUnit::parse(numerator)
+                    .zip(self.lengths)
+                    .map(|(from, to)| (from.in_meters() / to.in_meters(), to.symbol()))
+            });
+
+        match converted {
+            Some((factor, symbol)) => (factor, format!("{symbol}{rest}")),
+            None => (1.0, unit.to_owned()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum AngleUnit {
+    Radians,
+    Degrees,
+}
+
+impl AngleUnit {
+    pub const ALL: [Self; 2] = [Self::Radians, Self::Degrees];
+
+    pub fn parse(unit: &str) -> Option<Self> {
+        match unit.trim() {
+            "rad" | "radians" => Some(Self::Radians),
+            "°" | "deg" | "degrees" => Some(Self::Degrees),
+            _ => None,
+        }
+    }
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Self::Radians => "rad",
+            Self::Degrees => "°",
+        }
+    }
+
+    fn in_radians(self) -> f64 {
+        match self {
+            Self::Radians => 1.0,
+            Self::Degrees => std::f64::consts::PI / 180.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum LengthUnit {
+    Meters,
+    Feet,
+}
+
+impl LengthUnit {
+    pub const ALL: [Self; 2] = [Self::Meters, Self::Feet];
+
+    fn parse(unit: &str) -> Option<Self> {
+        match unit.trim() {
+            "m" | "meters" => Some(Self::Meters),
+            "ft" | "feet" => Some(Self::Feet),
+            _ => None,
+        }
+    }
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Self::Meters => "m",
+            Self::Feet => "ft",
+        }
+    }
+
+    fn in_meters(self) -> f64 {
+        match self {
+            Self::Meters => 1.0,
+            Self::Feet => 0.3048,
+        }
+    }
+}
+
+#[test]
+fn test_unit_conversions() {
+    let conversions = UnitConversions {
+        angles: Some(AngleUnit::Degrees),
+        lengths: Some(LengthUnit::Feet),
+    };
+
+    let (factor, unit) = conversions.convert("rad/s");
+    assert!((factor - 180.0 / std::f64::consts::PI).abs() < 1e-9);
+    assert_eq!(unit, "°/s");
+
+    let (factor, unit) = conversions.convert("m");
+    assert!((factor - 1.0 / 0.3048).abs() < 1e-9);
+    assert_eq!(unit, "ft");
+
+    assert_eq!(conversions.convert("°C"), (1.0, "°C".to_owned()));
+    assert_eq!(
+        UnitConversions::default().convert("rad"),
+        (1.0, "rad".to_owned())
+    );
+}
@@ -22,7 +22,11 @@ pub struct ViewerContext<'a> {
     /// How to display components
     pub component_ui_registry: &'a ComponentUiRegistry,
 
-    /// The current recording
+    /// The current recording.
+    ///
+    /// New data is only ingested in between frames, before any UI is shown,
+    /// so all space views of a frame are rendered from the exact same state
+    /// (see [`LogDb::generation`]).
     pub log_db: &'a LogDb,
 
     /// UI config for the current recording (found in [`LogDb`]).
@@ -32,6 +36,13 @@ pub struct ViewerContext<'a> {
     pub re_ui: &'a re_ui::ReUi,
 
     pub render_ctx: &'a mut re_renderer::RenderContext,
+
+    /// Annotation contexts from any recording, for resolving
+    /// [`re_log_types::component_types::AnnotationContextRef`]s.
+    pub shared_annotation_contexts: &'a super::SharedAnnotationContexts,
+
+    /// Human-friendly names for the entities of the current application.
+    pub entity_display_names: &'a mut super::EntityDisplayNames,
 }
 
 impl<'a> ViewerContext<'a> {
@@ -55,11 +66,12 @@ impl<'a> ViewerContext<'a> {
         space_view_id: Option<SpaceViewId>,
         entity_path: &EntityPath,
     ) -> egui::Response {
+        let text = self.entity_display_names.name_or_path(entity_path);
         self.instance_path_button_to(
             ui,
             space_view_id,
             &InstancePath::entity_splat(entity_path.clone()),
-            entity_path.to_string(),
+            text,
         )
     }
 
@@ -108,6 +120,7 @@ impl<'a> ViewerContext<'a> {
             .selectable_label(self.selection().contains(&selection), text)
             .on_hover_ui(|ui| {
                 ui.strong(subtype_string);
+                self.display_name_ui(ui, &instance_path.entity_path);
                 ui.label(format!("Path: {instance_path}"));
                 instance_path.data_ui(
                     self,
@@ -197,12 +210,20 @@ impl<'a> ViewerContext<'a> {
             .selectable_label(self.selection().contains(&selection), text)
             .on_hover_ui(|ui| {
                 ui.strong("Space View Entity");
+                self.display_name_ui(ui, entity_path);
                 ui.label(format!("Path: {entity_path}"));
                 entity_path.data_ui(self, ui, UiVerbosity::Reduced, &self.current_query());
             });
         self.cursor_interact_with_selectable(response, selection)
     }
 
+    /// Show the display name of the entity, if it has one.
+    fn display_name_ui(&self, ui: &mut egui::Ui, entity_path: &EntityPath) {
+        if let Some(name) = self.entity_display_names.get(entity_path) {
+            ui.label(format!("Name: {name}"));
+        }
+    }
+
     pub fn time_button(
         &mut self,
         ui: &mut egui::Ui,
@@ -211,7 +232,12 @@ impl<'a> ViewerContext<'a> {
     ) -> egui::Response {
         let is_selected = self.rec_cfg.time_ctrl.is_time_selected(timeline, value);
 
-        let response = ui.selectable_label(is_selected, timeline.typ().format(value));
+        let response = ui.selectable_label(
+            is_selected,
+            timeline
+                .typ()
+                .format_with(value, &self.app_options.time_format),
+        );
         if response.clicked() {
             self.rec_cfg
                 .time_ctrl
@@ -324,6 +350,30 @@ impl<'a> ViewerContext<'a> {
     pub fn current_query(&self) -> re_arrow_store::LatestAtQuery {
         self.rec_cfg.time_ctrl.current_query()
     }
+
+    /// If this entity used to stream live data but has stopped (e.g. because its sensor
+    /// driver crashed), for how many seconds it has been quiet.
+    ///
+    /// See [`super::AppOptions::stall_timeout`].
+    pub fn stalled_for_sec(&self, entity_path: &EntityPath) -> Option<f64> {
+        let is_live = matches!(
+            self.log_db.data_source,
+            Some(
+                re_smart_channel::Source::FollowedFile { .. }
+                    | re_smart_channel::Source::Sdk
+                    | re_smart_channel::Source::WsClient { .. }
+                    | re_smart_channel::Source::TcpServer { .. }
+            )
+        );
+        let timeout = self.app_options.stall_timeout as f64;
+        if !is_live || timeout <= 0.0 || !self.log_db.entity_db.is_stream(entity_path) {
+            return None;
+        }
+
+        let log_time = self.log_db.entity_db.latest_log_time(entity_path)?;
+        let age = self.rec_cfg.clock_sync.age_sec(log_time);
+        (timeout < age).then_some(age)
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -337,4 +387,28 @@ pub struct RecordingConfig {
 
     /// Selection & hovering state.
     pub selection_state: super::SelectionState,
+
+    /// For telling how old live data is.
+    #[serde(skip)]
+    pub clock_sync: super::ClockSync,
+
+    /// Entities that are logged far more often than they change,
+    /// so only the changes need to be stored.
+    ///
+    /// See [`re_arrow_store::DataStoreConfig::deduplicated_entities`].
+    pub deduplicated_entities: std::collections::BTreeSet<EntityPath>,
+
+    /// The alerts that have fired for this recording.
+    #[serde(skip)]
+    pub alerts: super::alerts::RecordingAlerts,
+}
+
+impl RecordingConfig {
+    /// Move the time cursor back to where it was when a selection from the history was made.
+    pub fn restore_time_of(&mut self, selection: &crate::ui::HistoricalSelection) {
+        if let Some((timeline, time)) = selection.time {
+            self.time_ctrl.set_timeline_and_time(timeline, time);
+            self.time_ctrl.pause();
+        }
+    }
 }
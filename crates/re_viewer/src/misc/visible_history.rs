@@ -0,0 +1,170 @@
+use std::collections::BTreeMap;
+
+use re_data_store::{log_db::EntityDb, ComponentName, EntityPath, ExtraQueryHistory};
+use re_log_types::{
+    component_types::{Box3D, LineStrip2D, LineStrip3D, Mesh3D, Point2D, Point3D, Rect2D, Tensor},
+    msg_bundle::Component as _,
+};
+
+/// The kinds of data that have their own default visible history.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, serde::Deserialize, serde::Serialize,
+)]
+pub enum HistoryDataKind {
+    /// E.g. lidar scans.
+    Points,
+
+    /// E.g. trajectories.
+    Lines,
+
+    Boxes,
+
+    Images,
+
+    Meshes,
+}
+
+impl HistoryDataKind {
+    pub const ALL: [Self; 5] = [
+        Self::Points,
+        Self::Lines,
+        Self::Boxes,
+        Self::Images,
+        Self::Meshes,
+    ];
+
+    fn of_component(component: &ComponentName) -> Option<Self> {
+        if component == &Point2D::name() || component == &Point3D::name() {
+            Some(Self::Points)
+        } else if component == &LineStrip2D::name() || component == &LineStrip3D::name() {
+            Some(Self::Lines)
+        } else if component == &Rect2D::name() || component == &Box3D::name() {
+            Some(Self::Boxes)
+        } else if component == &Tensor::name() {
+            Some(Self::Images)
+        } else if component == &Mesh3D::name() {
+            Some(Self::Meshes)
+        } else {
+            None
+        }
+    }
+}
+
+impl std::fmt::Display for HistoryDataKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Points => "Points",
+            Self::Lines => "Lines",
+            Self::Boxes => "Boxes",
+            Self::Images => "Images",
+            Self::Meshes => "Meshes",
+        })
+    }
+}
+
+/// The visible history entities get when a new space view is created, per kind of data,
+/// e.g. half a second of lidar scans but a minute of trajectories.
+///
+/// Can still be changed per entity in each space view.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct VisibleHistoryDefaults {
+    /// Missing = only the latest data.
+    per_kind: BTreeMap<HistoryDataKind, ExtraQueryHistory>,
+}
+
+impl VisibleHistoryDefaults {
+    pub fn is_empty(&self) -> bool {
+        self.per_kind
+            .values()
+            .all(|history| *history == ExtraQueryHistory::default())
+    }
+
+    pub fn get(&self, kind: HistoryDataKind) -> ExtraQueryHistory {
+        self.per_kind.get(&kind).copied().unwrap_or_default()
+    }
+
+    pub fn set(&mut self, kind: HistoryDataKind, history: ExtraQueryHistory) {
+        if history == ExtraQueryHistory::default() {
+            self.per_kind.remove(&kind);
+        } else {
+            self.per_kind.insert(kind, history);
+        }
+    }
+
+    /// The longest default of all the kinds of data logged to the entity.
+    pub fn for_entity(&self, entity_db: &EntityDb, entity_path: &EntityPath) -> ExtraQueryHistory {
+        let Some(tree) = entity_db.tree.subtree(entity_path) else {
+            return ExtraQueryHistory::default();
+        };
+
+        let mut history = ExtraQueryHistory::default();
+        for kind in tree
+            .components
+            .keys()
+            .filter_map(HistoryDataKind::of_component)
+        {
+            let default = self.get(kind);
+            history.nanos = history.nanos.max(default.nanos);
+            history.sequences = history.sequences.max(default.sequences);
+        }
+        history
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.label("Visible history of entities in new Space Views:");
+
+        egui::Grid::new("visible_history_defaults")
+            .num_columns(3)
+            .show(ui, |ui| {
+                ui.label("");
+                ui.label("Time");
+                ui.label("Sequence");
+                ui.end_row();
+
+                for kind in HistoryDataKind::ALL {
+                    let mut history = self.get(kind);
+
+                    ui.label(kind.to_string());
+
+                    let mut time_sec = history.nanos as f32 / 1e9;
+                    ui.add(
+                        egui::DragValue::new(&mut time_sec)
+                            .clamp_range(0.0..=f32::INFINITY)
+                            .speed((time_sec * 0.05).max(0.01))
+                            .suffix("s"),
+                    );
+                    history.nanos = (time_sec * 1e9).round() as _;
+
+                    ui.add(
+                        egui::DragValue::new(&mut history.sequences)
+                            .clamp_range(0.0..=f32::INFINITY)
+                            .speed((history.sequences as f32 * 0.05).max(1.0)),
+                    );
+                    ui.end_row();
+
+                    self.set(kind, history);
+                }
+            });
+
+        ui.weak("Zero shows only the latest data.");
+    }
+}
+
+#[test]
+fn test_visible_history_defaults() {
+    let mut defaults = VisibleHistoryDefaults::default();
+    assert!(defaults.is_empty());
+
+    let half_a_second = ExtraQueryHistory {
+        nanos: 500_000_000,
+        ..Default::default()
+    };
+    defaults.set(HistoryDataKind::Points, half_a_second);
+    assert!(!defaults.is_empty());
+    assert_eq!(defaults.get(HistoryDataKind::Points), half_a_second);
+    assert_eq!(defaults.get(HistoryDataKind::Images), Default::default());
+
+    defaults.set(HistoryDataKind::Points, Default::default());
+    assert!(defaults.is_empty());
+}
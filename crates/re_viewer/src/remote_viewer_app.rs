@@ -1,29 +1,51 @@
+use std::{
+    collections::{HashSet, VecDeque},
+    sync::{Arc, Mutex},
+};
+
+use re_log_types::{LogMsg, MsgId};
+
 use crate::App;
 
-/// Connects to a server over `WebSockets`.
+/// How many messages we buffer between the WebSocket connection and the [`App`]
+/// before applying back-pressure on the connection.
+#[cfg(not(target_arch = "wasm32"))]
+const MAX_QUEUED_MESSAGES: usize = 100_000;
+
+/// How many of the latest messages we remember, to skip them when the server resends them.
+///
+/// After a reconnect, messages older than this may arrive twice,
+/// if the server keeps a longer history than this.
+const MAX_REMEMBERED_MSG_IDS: usize = 1_000_000;
+
+/// Connects to one or more servers over `WebSockets`.
+///
+/// Each recording remembers which server it came from,
+/// and each server can be switched off in the "Servers" menu.
 pub struct RemoteViewerApp {
     app_env: crate::AppEnvironment,
     startup_options: crate::StartupOptions,
     re_ui: re_ui::ReUi,
-    /// The url of the remote server.
-    url: String,
-    app: Option<(re_ws_comms::Connection, App)>,
+
+    /// The urls of the remote servers.
+    urls: Vec<String>,
+    app: Option<App>,
 }
 
 impl RemoteViewerApp {
-    /// url to rerun server
+    /// urls to rerun servers, of which there should be at least one.
     pub fn new(
         app_env: crate::AppEnvironment,
         startup_options: crate::StartupOptions,
         re_ui: re_ui::ReUi,
         storage: Option<&dyn eframe::Storage>,
-        url: String,
+        urls: Vec<String>,
     ) -> Self {
         let mut slf = Self {
             app_env,
             startup_options,
             re_ui,
-            url,
+            urls,
             app: None,
         };
         slf.connect(storage);
@@ -31,48 +53,43 @@ impl RemoteViewerApp {
     }
 
     fn connect(&mut self, storage: Option<&dyn eframe::Storage>) {
-        let (tx, rx) = re_smart_channel::smart_channel(re_smart_channel::Source::WsClient {
-            ws_server_url: self.url.clone(),
-        });
-
-        let egui_ctx = self.re_ui.egui_ctx.clone();
-
-        re_log::info!("Connecting to WS server at {:?}…", self.url);
-
-        let connection =
-            re_ws_comms::Connection::viewer_to_server(self.url.clone(), move |binary: Vec<u8>| {
-                match re_ws_comms::decode_log_msg(&binary) {
-                    Ok(log_msg) => {
-                        if tx.send(log_msg).is_ok() {
-                            egui_ctx.request_repaint(); // Wake up UI thread
-                            std::ops::ControlFlow::Continue(())
-                        } else {
-                            re_log::info!("Failed to send log message to viewer - closing");
-                            std::ops::ControlFlow::Break(())
-                        }
-                    }
-                    Err(err) => {
-                        re_log::error!("Failed to parse message: {}", re_error::format(&err));
-                        std::ops::ControlFlow::Break(())
-                    }
-                }
+        let connections = self
+            .urls
+            .iter()
+            .map(|url| {
+                WsConnection::new(
+                    url.clone(),
+                    self.startup_options.ws_connection_options.clone(),
+                    self.re_ui.egui_ctx.clone(),
+                    self.startup_options.min_repaint_interval(),
+                )
+                .unwrap() // TODO(emilk): handle error
             })
-            .unwrap(); // TODO(emilk): handle error
+            .collect::<Vec<_>>();
 
-        let app = crate::App::from_receiver(
+        // All data arrives over the connections, so nothing is ever sent to the main channel.
+        let source = re_smart_channel::Source::WsClient {
+            ws_server_url: self.urls.join(", "),
+        };
+        let (_tx, rx) = re_smart_channel::smart_channel(source);
+
+        let mut app = crate::App::from_receiver(
             self.app_env.clone(),
-            self.startup_options,
+            self.startup_options.clone(),
             self.re_ui.clone(),
             storage,
             rx,
         );
+        for connection in connections {
+            app.add_ws_connection(connection);
+        }
 
-        self.app = Some((connection, app));
+        self.app = Some(app);
     }
 
     #[cfg(not(target_arch = "wasm32"))]
     pub fn set_profiler(&mut self, profiler: crate::Profiler) {
-        if let Some((_, app)) = &mut self.app {
+        if let Some(app) = &mut self.app {
             app.set_profiler(profiler);
         }
     }
@@ -84,34 +101,336 @@ impl eframe::App for RemoteViewerApp {
     }
 
     fn save(&mut self, storage: &mut dyn eframe::Storage) {
-        if let Some((_, app)) = &mut self.app {
+        if let Some(app) = &mut self.app {
             app.save(storage);
         }
     }
 
     fn update(&mut self, egui_ctx: &egui::Context, frame: &mut eframe::Frame) {
-        if false {
-            // TODO(emilk): move this url selection into the main app.
-            // but for now, just remove it, because it is ugly (see https://github.com/rerun-io/rerun/issues/1079).
-            egui::TopBottomPanel::top("server").show(egui_ctx, |ui| {
-                ui.horizontal(|ui| {
-                    ui.label("URL:");
-                    if ui.text_edit_singleline(&mut self.url).lost_focus()
-                        && ui.input(|i| i.key_pressed(egui::Key::Enter))
-                    {
-                        if let Some(storage) = frame.storage_mut() {
-                            if let Some((_, mut app)) = self.app.take() {
-                                app.save(storage);
-                            }
+        // The servers (and their auth tokens) can be changed in the "Servers" menu of the app.
+        if let Some(app) = &mut self.app {
+            app.update(egui_ctx, frame);
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// How long we wait before the first attempt to reconnect.
+const MIN_RECONNECT_DELAY: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// We double the wait after each failed attempt, up to this.
+const MAX_RECONNECT_DELAY: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// What a [`WsConnection`] is up to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum WsConnectionStatus {
+    Connecting,
+    Connected,
+
+    /// The connection dropped, and we'll try again after this many seconds.
+    WaitingToReconnect {
+        seconds_left: f32,
+    },
+
+    /// Switched off by the user, see [`WsConnection::set_enabled`].
+    Disabled,
+}
+
+/// A connection to a Rerun Server that comes back when it drops,
+/// waiting twice as long after each failed attempt.
+///
+/// All connections send to the same channel, so what we received before is kept.
+/// The server resends its whole history to each new connection,
+/// so we skip the messages we already have.
+pub(crate) struct WsConnection {
+    url: String,
+    options: re_ws_comms::ConnectionOptions,
+    egui_ctx: egui::Context,
+    on_binary_msg: Arc<dyn Fn(Vec<u8>) -> std::ops::ControlFlow<()> + Send + Sync>,
+
+    /// Where the messages of all our connections arrive.
+    rx: re_smart_channel::Receiver<LogMsg>,
+
+    /// If false, we stay disconnected until switched back on.
+    enabled: bool,
+
+    /// `None` while we wait to reconnect.
+    connection: Option<re_ws_comms::Connection>,
+
+    /// Failed attempts since we were last connected.
+    failed_attempts: u32,
+
+    /// When to try to reconnect.
+    next_attempt: instant::Instant,
+}
+
+impl WsConnection {
+    /// Keeps trying to connect until this is dropped.
+    ///
+    /// Call [`Self::update`] every frame.
+    pub fn new(
+        url: String,
+        options: re_ws_comms::ConnectionOptions,
+        egui_ctx: egui::Context,
+        min_repaint_interval: std::time::Duration,
+    ) -> anyhow::Result<Self> {
+        let source = re_smart_channel::Source::WsClient {
+            ws_server_url: url.clone(),
+        };
+
+        // On web the messages arrive on the main thread, which we must never block.
+        #[cfg(not(target_arch = "wasm32"))]
+        let (tx, rx) = re_smart_channel::smart_channel_bounded(source, MAX_QUEUED_MESSAGES);
+        #[cfg(target_arch = "wasm32")]
+        let (tx, rx) = re_smart_channel::smart_channel(source);
+
+        let received = Mutex::new(RecentMsgIds::new(MAX_REMEMBERED_MSG_IDS));
+        let egui_ctx_clone = egui_ctx.clone();
+        let on_binary_msg = move |binary: Vec<u8>| match re_ws_comms::decode_log_msg(&binary) {
+            Ok(log_msg) => {
+                if !received.lock().unwrap().insert(log_msg.id()) {
+                    return std::ops::ControlFlow::Continue(()); // Resent after a reconnect.
+                }
+                match tx.send_and_check_wake_up(log_msg) {
+                    Ok(wake_up) => {
+                        if wake_up {
+                            // Wake up UI thread
+                            egui_ctx_clone.request_repaint_after(min_repaint_interval);
                         }
-                        self.connect(frame.storage());
+                        std::ops::ControlFlow::Continue(())
+                    }
+                    Err(_) => {
+                        re_log::info!("Failed to send log message to viewer - closing");
+                        std::ops::ControlFlow::Break(())
                     }
-                });
-            });
+                }
+            }
+            Err(err) => {
+                // Breaking the connection wouldn't help: we'd get the same message after reconnecting.
+                re_log::error!(
+                    "Skipping a message we failed to parse: {}",
+                    re_error::format(&err)
+                );
+                std::ops::ControlFlow::Continue(())
+            }
+        };
+
+        re_log::info!("Connecting to WS server at {url:?}…");
+        let on_binary_msg = Arc::new(on_binary_msg);
+        let connection = connect(url.clone(), &options, on_binary_msg.clone())?;
+
+        Ok(Self {
+            url,
+            options,
+            egui_ctx,
+            on_binary_msg,
+            rx,
+            enabled: true,
+            connection: Some(connection),
+            failed_attempts: 0,
+            next_attempt: instant::Instant::now(),
+        })
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn rx(&self) -> &re_smart_channel::Receiver<LogMsg> {
+        &self.rx
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Present this token from now on, e.g. after the server rejected us, and reconnect.
+    pub fn set_auth_token(&mut self, auth_token: Option<String>) {
+        self.options.auth_token = auth_token;
+        if self.enabled {
+            self.failed_attempts = 0;
+            self.reconnect_now();
         }
+    }
 
-        if let Some((_, app)) = &mut self.app {
-            app.update(egui_ctx, frame);
+    /// Disconnect, or connect again and catch up on what we missed in the meantime.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        if self.enabled == enabled {
+            return;
+        }
+        self.enabled = enabled;
+        if enabled {
+            self.failed_attempts = 0;
+            self.reconnect_now();
+        } else {
+            re_log::info!("Disconnecting from {:?}", self.url);
+            self.connection = None;
         }
     }
+
+    pub fn status(&self) -> WsConnectionStatus {
+        if !self.enabled {
+            return WsConnectionStatus::Disabled;
+        }
+        match self
+            .connection
+            .as_ref()
+            .map(|connection| connection.status())
+        {
+            Some(re_ws_comms::ConnectionStatus::Connecting) => WsConnectionStatus::Connecting,
+            Some(re_ws_comms::ConnectionStatus::Connected) => WsConnectionStatus::Connected,
+            Some(re_ws_comms::ConnectionStatus::Closed) | None => {
+                WsConnectionStatus::WaitingToReconnect {
+                    seconds_left: self
+                        .next_attempt
+                        .saturating_duration_since(instant::Instant::now())
+                        .as_secs_f32(),
+                }
+            }
+        }
+    }
+
+    /// The streams the server is thinning out, because we can't keep up with it.
+    pub fn degraded_streams(&self) -> re_ws_comms::DegradedStreams {
+        self.connection
+            .as_ref()
+            .map(|connection| connection.degraded_streams())
+            .unwrap_or_default()
+    }
+
+    /// Reconnect if the connection has dropped and we have waited long enough.
+    pub fn update(&mut self) {
+        if !self.enabled {
+            return;
+        }
+        match self
+            .connection
+            .as_ref()
+            .map(|connection| connection.status())
+        {
+            Some(re_ws_comms::ConnectionStatus::Connecting) => {}
+            Some(re_ws_comms::ConnectionStatus::Connected) => {
+                self.failed_attempts = 0;
+                if let Some(connection) = &mut self.connection {
+                    connection.update();
+                }
+            }
+            Some(re_ws_comms::ConnectionStatus::Closed) => {
+                self.connection = None;
+                let delay = self.schedule_reconnect();
+                re_log::warn!(
+                    "Lost connection to {:?} - reconnecting in {:.1}s",
+                    self.url,
+                    delay.as_secs_f32()
+                );
+            }
+            None => {
+                let now = instant::Instant::now();
+                if self.next_attempt <= now {
+                    self.reconnect_now();
+                } else {
+                    self.egui_ctx
+                        .request_repaint_after(self.next_attempt.duration_since(now));
+                }
+            }
+        }
+    }
+
+    /// Don't wait for the next attempt.
+    pub fn reconnect_now(&mut self) {
+        re_log::info!("Reconnecting to {:?}…", self.url);
+        match connect(self.url.clone(), &self.options, self.on_binary_msg.clone()) {
+            Ok(connection) => {
+                self.connection = Some(connection);
+            }
+            Err(err) => {
+                let delay = self.schedule_reconnect();
+                re_log::warn!(
+                    "Failed to reconnect to {:?}: {} - trying again in {:.1}s",
+                    self.url,
+                    re_error::format(&err),
+                    delay.as_secs_f32()
+                );
+            }
+        }
+    }
+
+    fn schedule_reconnect(&mut self) -> std::time::Duration {
+        let delay = reconnect_delay(self.failed_attempts);
+        self.failed_attempts += 1;
+        self.next_attempt = instant::Instant::now() + delay;
+        self.egui_ctx.request_repaint_after(delay);
+        delay
+    }
+}
+
+/// The ids of the latest messages, forgetting the oldest ones beyond a capacity.
+struct RecentMsgIds {
+    capacity: usize,
+    ids: HashSet<MsgId>,
+
+    /// Oldest first.
+    order: VecDeque<MsgId>,
+}
+
+impl RecentMsgIds {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            ids: Default::default(),
+            order: Default::default(),
+        }
+    }
+
+    /// Returns `false` if we already have this message.
+    fn insert(&mut self, msg_id: MsgId) -> bool {
+        if !self.ids.insert(msg_id) {
+            return false;
+        }
+        self.order.push_back(msg_id);
+        if self.capacity < self.order.len() {
+            if let Some(oldest) = self.order.pop_front() {
+                self.ids.remove(&oldest);
+            }
+        }
+        true
+    }
+}
+
+/// How long to wait before trying again, after this many attempts failed in a row.
+fn reconnect_delay(failed_attempts: u32) -> std::time::Duration {
+    // Capping the exponent keeps the multiplication finite.
+    MIN_RECONNECT_DELAY
+        .mul_f32(2_f32.powi(failed_attempts.min(16) as i32))
+        .min(MAX_RECONNECT_DELAY)
+}
+
+fn connect(
+    url: String,
+    options: &re_ws_comms::ConnectionOptions,
+    on_binary_msg: Arc<dyn Fn(Vec<u8>) -> std::ops::ControlFlow<()> + Send + Sync>,
+) -> anyhow::Result<re_ws_comms::Connection> {
+    re_ws_comms::Connection::viewer_to_server(url, options, move |binary| on_binary_msg(binary))
+}
+
+#[test]
+fn test_reconnect_delay() {
+    assert_eq!(reconnect_delay(0), MIN_RECONNECT_DELAY);
+    assert_eq!(reconnect_delay(1), 2 * MIN_RECONNECT_DELAY);
+    assert_eq!(reconnect_delay(3), 8 * MIN_RECONNECT_DELAY);
+    assert_eq!(reconnect_delay(100), MAX_RECONNECT_DELAY);
+}
+
+#[test]
+fn test_recent_msg_ids() {
+    let [a, b, c] = [MsgId::random(), MsgId::random(), MsgId::random()];
+    let mut recent = RecentMsgIds::new(2);
+    assert!(recent.insert(a));
+    assert!(recent.insert(b));
+    assert!(!recent.insert(a));
+    assert!(recent.insert(c));
+    assert!(recent.insert(a), "Forgotten, since it was the oldest");
+    assert!(!recent.insert(c));
+    assert_eq!(recent.ids.len(), 2);
 }
@@ -6,7 +6,7 @@ use nohash_hasher::IntSet;
 use re_arrow_store::LatestAtQuery;
 use re_data_store::EntityPath;
 use re_log_types::{
-    component_types::{ClassId, KeypointId},
+    component_types::{AnnotationContextRef, ClassId, KeypointId},
     context::{AnnotationInfo, ClassDescription},
     msg_bundle::Component,
     AnnotationContext, MsgId,
@@ -142,7 +142,7 @@ impl AnnotationMap {
                     // Otherwise check the obj_store for the field.
                     // If we find one, insert it and then we can break.
                     std::collections::btree_map::Entry::Vacant(entry) => {
-                        if query_entity_with_primary::<AnnotationContext>(
+                        let annotations = query_entity_with_primary::<AnnotationContext>(
                             data_store,
                             &latest_at_query,
                             &parent,
@@ -154,13 +154,41 @@ impl AnnotationMap {
                                 entity.iter_primary().ok()?.next()?,
                                 entity.iter_component::<MsgId>().ok()?.next()?,
                             ) {
-                                Some(entry.insert(Arc::new(Annotations { msg_id, context })))
+                                Some(Annotations { msg_id, context })
                             } else {
                                 None
                             }
                         })
-                        .is_some()
-                        {
+                        .or_else(|| {
+                            // The context may have been logged in another recording:
+                            let entity = query_entity_with_primary::<AnnotationContextRef>(
+                                data_store,
+                                &latest_at_query,
+                                &parent,
+                                &[MsgId::name()],
+                            )
+                            .ok()?;
+                            if let (Some(reference), Some(msg_id)) = (
+                                entity.iter_primary().ok()?.next()?,
+                                entity.iter_component::<MsgId>().ok()?.next()?,
+                            ) {
+                                let context = ctx.shared_annotation_contexts.get(reference.0);
+                                if context.is_none() {
+                                    re_log::warn_once!(
+                                        "{parent} refers to an annotation context this viewer has not seen"
+                                    );
+                                }
+                                Some(Annotations {
+                                    msg_id,
+                                    context: context?.clone(),
+                                })
+                            } else {
+                                None
+                            }
+                        });
+
+                        if let Some(annotations) = annotations {
+                            entry.insert(Arc::new(annotations));
                             break;
                         }
                     }
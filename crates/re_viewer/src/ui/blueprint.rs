@@ -1,3 +1,7 @@
+use std::collections::BTreeSet;
+
+use re_data_store::EntityPath;
+
 use crate::misc::{space_info::SpaceInfoCollection, ViewerContext};
 
 use super::viewport::Viewport;
@@ -11,6 +15,9 @@ pub struct Blueprint {
     pub time_panel_expanded: bool,
 
     pub viewport: Viewport,
+
+    /// Entities starred by the user, shown at the top of the blueprint panel.
+    pub favorites: BTreeSet<EntityPath>,
 }
 
 impl Default for Blueprint {
@@ -20,6 +27,7 @@ impl Default for Blueprint {
             selection_panel_expanded: true,
             time_panel_expanded: true,
             viewport: Default::default(),
+            favorites: Default::default(),
         }
     }
 }
@@ -69,11 +77,43 @@ impl Blueprint {
                 ..Default::default()
             }
             .show(ui, |ui| {
+                self.favorites_ui(ctx, ui);
                 self.viewport.tree_ui(ctx, ui);
             });
         });
     }
 
+    /// Star entities in the selection panel to add them here.
+    fn favorites_ui(&mut self, ctx: &mut ViewerContext<'_>, ui: &mut egui::Ui) {
+        if self.favorites.is_empty() {
+            return;
+        }
+
+        let mut unstarred = None;
+        egui::CollapsingHeader::new("⭐ Favorites")
+            .default_open(true)
+            .show(ui, |ui| {
+                for entity_path in &self.favorites {
+                    ui.horizontal(|ui| {
+                        ctx.entity_path_button(ui, None, entity_path);
+                        if ctx
+                            .re_ui
+                            .small_icon_button(ui, &re_ui::icons::REMOVE)
+                            .on_hover_text("Remove from favorites")
+                            .clicked()
+                        {
+                            unstarred = Some(entity_path.clone());
+                        }
+                    });
+                }
+            });
+        if let Some(entity_path) = unstarred {
+            self.favorites.remove(&entity_path);
+        }
+
+        ui.separator();
+    }
+
     fn title_bar_ui(
         &mut self,
         ctx: &mut ViewerContext<'_>,
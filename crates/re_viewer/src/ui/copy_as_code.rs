@@ -0,0 +1,467 @@
+//! Generate an SDK snippet that logs the current data of an entity,
+//! to learn the API from an existing recording, or to make a minimal repro.
+
+use itertools::Itertools as _;
+
+use re_arrow_store::{DataStore, LatestAtQuery, TimeType};
+use re_log_types::{
+    component_types::{
+        Arrow3D, Box3D, ClassId, ColorRGBA, InstanceKey, KeypointId, Label, LineStrip2D,
+        LineStrip3D, Point2D, Point3D, Quaternion, Radius, Rect2D, Scalar, TextEntry, Vec3D,
+    },
+    external::arrow2_convert::{
+        deserialize::{ArrowArray, ArrowDeserialize},
+        field::ArrowField,
+    },
+    msg_bundle::Component,
+    ComponentName, EntityPath,
+};
+use re_query::get_component_with_instances;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Language {
+    Python,
+    Rust,
+}
+
+/// Code that logs the data of the entity at the time of the query.
+///
+/// Only the components we know how to write as code are included, the others are listed in a comment.
+pub fn snippet(
+    store: &DataStore,
+    query: &LatestAtQuery,
+    entity_path: &EntityPath,
+    language: Language,
+) -> String {
+    let columns = columns(store, query, entity_path);
+
+    let mut not_included = store
+        .all_components(&query.timeline, entity_path)
+        .unwrap_or_default();
+    not_included.retain(|name| {
+        *name != InstanceKey::name()
+            && *name != re_log_types::MsgId::name()
+            && columns.iter().all(|column| column.name != *name)
+    });
+    not_included.sort();
+
+    let comment = match language {
+        Language::Python => "#",
+        Language::Rust => "//",
+    };
+    let mut code = match language {
+        Language::Python => python_snippet(query, entity_path, &columns),
+        Language::Rust => rust_snippet(query, entity_path, &columns),
+    };
+    if !not_included.is_empty() {
+        code = format!(
+            "{comment} Not included: {}\n{code}",
+            not_included.iter().map(|name| name.short_name()).join(", ")
+        );
+    }
+    code
+}
+
+/// The latest values of one component, as code.
+struct Column {
+    name: ComponentName,
+
+    /// Logged as a single value for all instances.
+    is_splat: bool,
+
+    /// One value per instance.
+    rust: Vec<String>,
+
+    /// One value per instance, see [`ToCode::to_python`].
+    python: Vec<String>,
+}
+
+impl Column {
+    /// A Python list of all the values, or a single value if it is a splat.
+    fn python_list(&self) -> String {
+        if self.is_splat {
+            self.python[0].clone()
+        } else {
+            format!("[{}]", self.python.join(", "))
+        }
+    }
+}
+
+type ColumnFn = fn(&DataStore, &LatestAtQuery, &EntityPath) -> Option<Column>;
+
+fn columns(store: &DataStore, query: &LatestAtQuery, entity_path: &EntityPath) -> Vec<Column> {
+    let column_fns: [ColumnFn; 16] = [
+        column::<Point2D>,
+        column::<Point3D>,
+        column::<Rect2D>,
+        column::<Box3D>,
+        column::<Vec3D>,
+        column::<Quaternion>,
+        column::<Arrow3D>,
+        column::<LineStrip2D>,
+        column::<LineStrip3D>,
+        column::<Scalar>,
+        column::<TextEntry>,
+        column::<ColorRGBA>,
+        column::<Radius>,
+        column::<Label>,
+        column::<ClassId>,
+        column::<KeypointId>,
+    ];
+    column_fns
+        .iter()
+        .filter_map(|column_fn| column_fn(store, query, entity_path))
+        .collect()
+}
+
+fn column<C>(store: &DataStore, query: &LatestAtQuery, entity_path: &EntityPath) -> Option<Column>
+where
+    C: Component + ToCode + ArrowDeserialize + ArrowField<Type = C> + 'static,
+    C::ArrayType: ArrowArray,
+    for<'a> &'a C::ArrayType: IntoIterator,
+{
+    let data = get_component_with_instances(store, query, entity_path, C::name()).ok()?;
+    let is_splat = data
+        .iter_instance_keys()
+        .ok()?
+        .exactly_one()
+        .map_or(false, |instance_key| instance_key.is_splat());
+    let values = data.iter_values::<C>().ok()?.flatten().collect_vec();
+    if values.is_empty() {
+        return None;
+    }
+
+    Some(Column {
+        name: C::name(),
+        is_splat,
+        rust: values.iter().map(ToCode::to_rust).collect(),
+        python: values.iter().map(ToCode::to_python).collect(),
+    })
+}
+
+fn python_snippet(query: &LatestAtQuery, entity_path: &EntityPath, columns: &[Column]) -> String {
+    let get = |name: ComponentName| columns.iter().find(|column| column.name == name);
+    let first = |name: ComponentName| get(name).map(|column| column.python[0].clone());
+    let list = |name: ComponentName| get(name).map(Column::python_list);
+
+    let (function, args, kwargs) =
+        if let Some(positions) = list(Point3D::name()).or_else(|| list(Point2D::name())) {
+            let kwargs = vec![
+                ("colors", list(ColorRGBA::name())),
+                ("radii", list(Radius::name())),
+                ("labels", list(Label::name())),
+                ("class_ids", list(ClassId::name())),
+                ("keypoint_ids", list(KeypointId::name())),
+            ];
+            ("log_points", positions, kwargs)
+        } else if let Some(rects) = list(Rect2D::name()) {
+            let kwargs = vec![
+                ("colors", list(ColorRGBA::name())),
+                ("labels", list(Label::name())),
+                ("class_ids", list(ClassId::name())),
+            ];
+            ("log_rects", rects, kwargs)
+        } else if let Some(half_size) = first(Box3D::name()) {
+            let kwargs = vec![
+                ("position", first(Vec3D::name())),
+                ("rotation_q", first(Quaternion::name())),
+                ("color", first(ColorRGBA::name())),
+                ("label", first(Label::name())),
+                ("class_id", first(ClassId::name())),
+            ];
+            ("log_obb", half_size, kwargs)
+        } else if let Some(origin_and_vector) = first(Arrow3D::name()) {
+            let kwargs = vec![
+                ("color", first(ColorRGBA::name())),
+                ("label", first(Label::name())),
+            ];
+            ("log_arrow", origin_and_vector, kwargs)
+        } else if let Some(positions) =
+            first(LineStrip3D::name()).or_else(|| first(LineStrip2D::name()))
+        {
+            let kwargs = vec![("color", first(ColorRGBA::name()))];
+            ("log_line_strip", positions, kwargs)
+        } else if let Some(scalar) = first(Scalar::name()) {
+            let kwargs = vec![
+                ("label", first(Label::name())),
+                ("color", first(ColorRGBA::name())),
+                ("radius", first(Radius::name())),
+            ];
+            ("log_scalar", scalar, kwargs)
+        } else if let Some(text_and_level) = first(TextEntry::name()) {
+            let kwargs = vec![("color", first(ColorRGBA::name()))];
+            ("log_text_entry", text_and_level, kwargs)
+        } else {
+            return format!("# There is no Python function for logging the data of {entity_path}");
+        };
+
+    let kwargs = kwargs
+        .into_iter()
+        .filter_map(|(name, value)| Some(format!(", {name}={}", value?)))
+        .join("");
+
+    format!(
+        "import rerun as rr\n\n{}rr.{function}(\"{entity_path}\", {args}{kwargs})\n",
+        python_set_time(query)
+    )
+}
+
+fn python_set_time(query: &LatestAtQuery) -> String {
+    let name = query.timeline.name();
+    let time = query.at.as_i64();
+    match query.timeline.typ() {
+        TimeType::Sequence => format!("rr.set_time_sequence(\"{name}\", {time})\n"),
+        TimeType::Time => format!("rr.set_time_nanos(\"{name}\", {time})\n"),
+    }
+}
+
+fn rust_snippet(query: &LatestAtQuery, entity_path: &EntityPath, columns: &[Column]) -> String {
+    let timeline = match query.timeline.typ() {
+        TimeType::Sequence => format!("Timeline::new_sequence(\"{}\")", query.timeline.name()),
+        TimeType::Time => format!("Timeline::new_temporal(\"{}\")", query.timeline.name()),
+    };
+
+    let mut code = format!(
+        "use rerun::{{components::*, time::Timeline, MsgSender}};\n\n\
+        MsgSender::new(\"{entity_path}\")\n    .with_time({timeline}, {})\n",
+        query.at.as_i64()
+    );
+    for column in columns {
+        if column.is_splat {
+            code += &format!("    .with_splat({})?\n", column.rust[0]);
+        } else {
+            code += &format!("    .with_component(&[{}])?\n", column.rust.join(", "));
+        }
+    }
+    code += "    .send(&mut session)?;\n";
+    code
+}
+
+// ----------------------------------------------------------------------------
+
+/// How to write a component in the SDKs.
+trait ToCode {
+    fn to_rust(&self) -> String;
+
+    /// As passed to the Python logging function, e.g. `[1.0, 2.0]` for a point.
+    ///
+    /// For components that are logged with several arguments (e.g. the origin and vector of an
+    /// arrow), all of them, separated with commas.
+    fn to_python(&self) -> String;
+}
+
+impl ToCode for Point2D {
+    fn to_rust(&self) -> String {
+        format!("Point2D::new({:?}, {:?})", self.x, self.y)
+    }
+
+    fn to_python(&self) -> String {
+        format!("[{:?}, {:?}]", self.x, self.y)
+    }
+}
+
+impl ToCode for Point3D {
+    fn to_rust(&self) -> String {
+        format!("Point3D::new({:?}, {:?}, {:?})", self.x, self.y, self.z)
+    }
+
+    fn to_python(&self) -> String {
+        format!("[{:?}, {:?}, {:?}]", self.x, self.y, self.z)
+    }
+}
+
+impl ToCode for Rect2D {
+    fn to_rust(&self) -> String {
+        let [x, y] = self.top_left_corner();
+        format!(
+            "Rect2D::from_xywh({x:?}, {y:?}, {:?}, {:?})",
+            self.width(),
+            self.height()
+        )
+    }
+
+    /// In the default `XYWH` format.
+    fn to_python(&self) -> String {
+        let [x, y] = self.top_left_corner();
+        format!("[{x:?}, {y:?}, {:?}, {:?}]", self.width(), self.height())
+    }
+}
+
+impl ToCode for Box3D {
+    fn to_rust(&self) -> String {
+        format!("Box3D::new({:?}, {:?}, {:?})", self.x, self.y, self.z)
+    }
+
+    fn to_python(&self) -> String {
+        format!("[{:?}, {:?}, {:?}]", self.x, self.y, self.z)
+    }
+}
+
+impl ToCode for Vec3D {
+    fn to_rust(&self) -> String {
+        let [x, y, z] = self.0;
+        format!("Vec3D::new({x:?}, {y:?}, {z:?})")
+    }
+
+    fn to_python(&self) -> String {
+        format!("{:?}", self.0)
+    }
+}
+
+impl ToCode for Quaternion {
+    fn to_rust(&self) -> String {
+        format!(
+            "Quaternion::new({:?}, {:?}, {:?}, {:?})",
+            self.x, self.y, self.z, self.w
+        )
+    }
+
+    fn to_python(&self) -> String {
+        format!("[{:?}, {:?}, {:?}, {:?}]", self.x, self.y, self.z, self.w)
+    }
+}
+
+impl ToCode for Arrow3D {
+    fn to_rust(&self) -> String {
+        format!(
+            "Arrow3D {{ origin: {}, vector: {} }}",
+            self.origin.to_rust(),
+            self.vector.to_rust()
+        )
+    }
+
+    fn to_python(&self) -> String {
+        format!("{}, {}", self.origin.to_python(), self.vector.to_python())
+    }
+}
+
+impl ToCode for LineStrip2D {
+    fn to_rust(&self) -> String {
+        let points = self.0.iter().map(|point| format!("{:?}.into()", point.0));
+        format!("LineStrip2D(vec![{}])", points.format(", "))
+    }
+
+    fn to_python(&self) -> String {
+        format!(
+            "[{}]",
+            self.0
+                .iter()
+                .map(|point| format!("{:?}", point.0))
+                .format(", ")
+        )
+    }
+}
+
+impl ToCode for LineStrip3D {
+    fn to_rust(&self) -> String {
+        let points = self.0.iter().map(|point| format!("{:?}.into()", point.0));
+        format!("LineStrip3D(vec![{}])", points.format(", "))
+    }
+
+    fn to_python(&self) -> String {
+        format!(
+            "[{}]",
+            self.0
+                .iter()
+                .map(|point| format!("{:?}", point.0))
+                .format(", ")
+        )
+    }
+}
+
+impl ToCode for Scalar {
+    fn to_rust(&self) -> String {
+        format!("Scalar({:?})", self.0)
+    }
+
+    fn to_python(&self) -> String {
+        format!("{:?}", self.0)
+    }
+}
+
+impl ToCode for TextEntry {
+    fn to_rust(&self) -> String {
+        match &self.level {
+            Some(level) => format!(
+                "TextEntry::new({:?}, Some({level:?}.to_owned()))",
+                self.body
+            ),
+            None => format!("TextEntry::new({:?}, None)", self.body),
+        }
+    }
+
+    fn to_python(&self) -> String {
+        match &self.level {
+            Some(level) => format!("{:?}, level={level:?}", self.body),
+            None => format!("{:?}, level=None", self.body),
+        }
+    }
+}
+
+impl ToCode for ColorRGBA {
+    fn to_rust(&self) -> String {
+        format!("ColorRGBA(0x{:08X})", self.0)
+    }
+
+    fn to_python(&self) -> String {
+        format!("{:?}", self.to_array())
+    }
+}
+
+impl ToCode for Radius {
+    fn to_rust(&self) -> String {
+        format!("Radius({:?})", self.0)
+    }
+
+    fn to_python(&self) -> String {
+        format!("{:?}", self.0)
+    }
+}
+
+impl ToCode for Label {
+    fn to_rust(&self) -> String {
+        format!("Label({:?}.to_owned())", self.0)
+    }
+
+    fn to_python(&self) -> String {
+        format!("{:?}", self.0)
+    }
+}
+
+impl ToCode for ClassId {
+    fn to_rust(&self) -> String {
+        format!("ClassId({})", self.0)
+    }
+
+    fn to_python(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+impl ToCode for KeypointId {
+    fn to_rust(&self) -> String {
+        format!("KeypointId({})", self.0)
+    }
+
+    fn to_python(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+#[test]
+fn test_to_code() {
+    let point = Point3D::new(1.0, 2.5, -3.0);
+    assert_eq!(point.to_rust(), "Point3D::new(1.0, 2.5, -3.0)");
+    assert_eq!(point.to_python(), "[1.0, 2.5, -3.0]");
+
+    let color = ColorRGBA::from_rgb(255, 0, 128);
+    assert_eq!(color.to_rust(), "ColorRGBA(0xFF0080FF)");
+    assert_eq!(color.to_python(), "[255, 0, 128, 255]");
+
+    let text = TextEntry::new("say \"hi\"", Some("WARN".to_owned()));
+    assert_eq!(
+        text.to_rust(),
+        r#"TextEntry::new("say \"hi\"", Some("WARN".to_owned()))"#
+    );
+    assert_eq!(text.to_python(), r#""say \"hi\"", level="WARN""#);
+}
@@ -0,0 +1,181 @@
+//! The data of an entity or instance as JSON, to paste into chats and issues.
+
+use re_arrow_store::{DataStore, LatestAtQuery};
+use re_data_store::InstancePath;
+use re_log_types::{
+    component_types::InstanceKey,
+    external::arrow2::{
+        array::{
+            get_display, Array, BooleanArray, FixedSizeListArray, ListArray, PrimitiveArray,
+            StructArray, UnionArray, Utf8Array,
+        },
+        datatypes::DataType,
+    },
+    msg_bundle::Component as _,
+    MsgId,
+};
+use re_query::get_component_with_instances;
+use serde_json::{Map, Value};
+
+/// The latest value of each component at the time of the query, keyed by component name,
+/// e.g. `{"rerun.point3d": {"x": 1.0, "y": 2.0, "z": 3.0}}`.
+///
+/// For a whole entity, each component is a list with one value per instance.
+pub fn instance_json(
+    store: &DataStore,
+    query: &LatestAtQuery,
+    instance_path: &InstancePath,
+) -> Value {
+    let entity_path = &instance_path.entity_path;
+    let mut components = store
+        .all_components(&query.timeline, entity_path)
+        .unwrap_or_default();
+    components.retain(|name| *name != InstanceKey::name() && *name != MsgId::name());
+    components.sort();
+
+    let mut object = Map::new();
+    for name in components {
+        let Ok(component) = get_component_with_instances(store, query, entity_path, name) else {
+            continue; // Not logged yet at this time.
+        };
+
+        let value = if instance_path.instance_key.is_splat() {
+            let Ok(instance_keys) = component.iter_instance_keys() else {
+                continue;
+            };
+            Value::Array(
+                instance_keys
+                    .filter_map(|instance_key| component.lookup_arrow(&instance_key))
+                    .map(|value| to_json(value.as_ref(), 0))
+                    .collect(),
+            )
+        } else if let Some(value) = component.lookup_arrow(&instance_path.instance_key) {
+            to_json(value.as_ref(), 0)
+        } else {
+            continue;
+        };
+        object.insert(name.full_name().to_owned(), value);
+    }
+    Value::Object(object)
+}
+
+/// The value at `index`, with structs as objects and lists as arrays.
+///
+/// Types without an obvious JSON counterpart (e.g. binary blobs) become their display string.
+fn to_json(array: &dyn Array, index: usize) -> Value {
+    if array.is_null(index) {
+        return Value::Null;
+    }
+
+    macro_rules! primitive {
+        ($t:ty) => {
+            array
+                .as_any()
+                .downcast_ref::<PrimitiveArray<$t>>()
+                .map(|array| Value::from(array.value(index)))
+        };
+    }
+
+    let value = match array.data_type() {
+        DataType::Boolean => array
+            .as_any()
+            .downcast_ref::<BooleanArray>()
+            .map(|array| Value::Bool(array.value(index))),
+        DataType::Int8 => primitive!(i8),
+        DataType::Int16 => primitive!(i16),
+        DataType::Int32 => primitive!(i32),
+        DataType::Int64 => primitive!(i64),
+        DataType::UInt8 => primitive!(u8),
+        DataType::UInt16 => primitive!(u16),
+        DataType::UInt32 => primitive!(u32),
+        DataType::UInt64 => primitive!(u64),
+        DataType::Float32 => primitive!(f32),
+        DataType::Float64 => primitive!(f64),
+        DataType::Utf8 => array
+            .as_any()
+            .downcast_ref::<Utf8Array<i32>>()
+            .map(|array| Value::from(array.value(index))),
+        DataType::LargeUtf8 => array
+            .as_any()
+            .downcast_ref::<Utf8Array<i64>>()
+            .map(|array| Value::from(array.value(index))),
+        DataType::List(_) => array
+            .as_any()
+            .downcast_ref::<ListArray<i32>>()
+            .map(|array| all_to_json(array.value(index).as_ref())),
+        DataType::LargeList(_) => array
+            .as_any()
+            .downcast_ref::<ListArray<i64>>()
+            .map(|array| all_to_json(array.value(index).as_ref())),
+        DataType::FixedSizeList(_, _) => array
+            .as_any()
+            .downcast_ref::<FixedSizeListArray>()
+            .map(|array| all_to_json(array.value(index).as_ref())),
+        DataType::Struct(fields) => array.as_any().downcast_ref::<StructArray>().map(|array| {
+            Value::Object(
+                fields
+                    .iter()
+                    .zip(array.values())
+                    .map(|(field, values)| (field.name.clone(), to_json(values.as_ref(), index)))
+                    .collect(),
+            )
+        }),
+        DataType::Union(fields, _, _) => {
+            // Only one of the fields is set, so show it as e.g. `{"Rigid3": …}`:
+            array.as_any().downcast_ref::<UnionArray>().map(|array| {
+                let (field, offset) = array.index(index);
+                let mut object = Map::new();
+                object.insert(
+                    fields[field].name.clone(),
+                    to_json(array.fields()[field].as_ref(), offset),
+                );
+                Value::Object(object)
+            })
+        }
+        _ => None,
+    };
+
+    value.unwrap_or_else(|| {
+        let mut repr = String::new();
+        let display = get_display(array, "null");
+        if display(&mut repr, index).is_err() {
+            repr = format!("<{:?}>", array.data_type());
+        }
+        Value::String(repr)
+    })
+}
+
+fn all_to_json(array: &dyn Array) -> Value {
+    // E.g. the pixels of an image are of no use in a chat message:
+    const MAX_LEN: usize = 1000;
+
+    if MAX_LEN < array.len() {
+        Value::String(format!("<{} values>", array.len()))
+    } else {
+        Value::Array(
+            (0..array.len())
+                .map(|index| to_json(array, index))
+                .collect(),
+        )
+    }
+}
+
+#[test]
+fn test_to_json() {
+    use re_log_types::{
+        component_types::{Point3D, TextEntry},
+        msg_bundle::ComponentBundle,
+    };
+
+    let bundle = ComponentBundle::try_from(vec![Point3D::new(1.0, 2.5, -3.0)]).unwrap();
+    assert_eq!(
+        to_json(bundle.value_list(), 0),
+        serde_json::json!([{"x": 1.0, "y": 2.5, "z": -3.0}])
+    );
+
+    let bundle = ComponentBundle::try_from(vec![TextEntry::new("hi", None)]).unwrap();
+    assert_eq!(
+        to_json(bundle.value_list(), 0),
+        serde_json::json!([{"body": "hi", "level": null}])
+    );
+}
@@ -0,0 +1,122 @@
+use re_format::{format_bytes, format_number};
+use re_sdk_comms::{ClientInfo, ConnectedClients, TransferProgress};
+
+/// Shows the SDK:s connected to our server, and lets the user disconnect them.
+pub fn data_sources_window(
+    egui_ctx: &egui::Context,
+    open: &mut bool,
+    clients: Option<&ConnectedClients>,
+) {
+    egui::Window::new("Data sources")
+        .open(open)
+        .resizable(true)
+        .show(egui_ctx, |ui| {
+            let clients = match clients {
+                Some(clients) => clients,
+                None => {
+                    ui.label("This viewer is not hosting a server for SDK:s to connect to.");
+                    return;
+                }
+            };
+
+            let infos = clients.infos();
+            if infos.is_empty() {
+                ui.label("No SDK is connected.");
+                return;
+            }
+
+            // Keep the rates fresh:
+            ui.ctx()
+                .request_repaint_after(std::time::Duration::from_secs(1));
+
+            egui::Grid::new("data_sources")
+                .num_columns(7)
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.strong("Host");
+                    ui.strong("Application");
+                    ui.strong("Rate");
+                    ui.strong("Messages");
+                    ui.strong("Connected");
+                    ui.strong("Receiving");
+                    ui.label("");
+                    ui.end_row();
+
+                    for info in &infos {
+                        client_row_ui(ui, clients, info);
+                        ui.end_row();
+                    }
+                });
+        });
+}
+
+fn client_row_ui(ui: &mut egui::Ui, clients: &ConnectedClients, info: &ClientInfo) {
+    let ClientInfo {
+        peer_addr,
+        hostname,
+        application_id,
+        connected_at,
+        num_messages,
+        num_bytes,
+        num_dropped,
+        last_message_at: _,
+        message_rate: _,
+        incoming_transfer,
+    } = info;
+
+    ui.label(hostname.as_deref().unwrap_or("?"))
+        .on_hover_text(peer_addr.to_string());
+    ui.label(
+        application_id
+            .as_ref()
+            .map_or("?".to_owned(), ToString::to_string),
+    );
+    ui.monospace(format!("{:.0} msg/s", info.current_message_rate()));
+    ui.monospace(format_number(*num_messages as _))
+        .on_hover_text(format!(
+            "{} received, {} messages dropped",
+            format_bytes(*num_bytes as _),
+            format_number(*num_dropped as _)
+        ));
+    ui.label(format!("{:.0}s ago", connected_at.elapsed().as_secs_f32()));
+    if let Some(progress) = incoming_transfer {
+        transfer_ui(ui, clients, *peer_addr, progress);
+    } else {
+        ui.label("");
+    }
+    if ui
+        .button("Disconnect")
+        .on_hover_text("Close the connection to this SDK")
+        .clicked()
+    {
+        clients.disconnect(*peer_addr);
+    }
+}
+
+/// Progress of a large message, which the user can cancel if it is taking too long.
+fn transfer_ui(
+    ui: &mut egui::Ui,
+    clients: &ConnectedClients,
+    peer_addr: std::net::SocketAddr,
+    progress: &TransferProgress,
+) {
+    ui.horizontal(|ui| {
+        let text = format!(
+            "{} / {}",
+            format_bytes(progress.received_bytes as _),
+            format_bytes(progress.total_bytes as _)
+        );
+        ui.add(
+            egui::ProgressBar::new(progress.fraction())
+                .desired_width(160.0)
+                .text(text),
+        );
+        if ui
+            .small_button("Cancel")
+            .on_hover_text("Drop this message, but stay connected")
+            .clicked()
+        {
+            clients.cancel_transfer(peer_addr);
+        }
+    });
+}
@@ -1,5 +1,7 @@
 use std::collections::BTreeMap;
 
+use itertools::Itertools as _;
+
 use re_arrow_store::LatestAtQuery;
 use re_log_types::{
     component_types::InstanceKey,
@@ -47,12 +49,14 @@ impl Default for ComponentUiRegistry {
         // registry.add::<re_log_types::component_types::Box3D>();
         // registry.add::<re_log_types::component_types::ClassId>();
         registry.add::<re_log_types::component_types::ColorRGBA>();
+        // registry.add::<re_log_types::component_types::Group>();
         // registry.add::<re_log_types::component_types::InstanceKey>();
         // registry.add::<re_log_types::component_types::KeypointId>();
         // registry.add::<re_log_types::component_types::Label>();
         registry.add::<re_log_types::component_types::LineStrip2D>();
         registry.add::<re_log_types::component_types::LineStrip3D>();
         registry.add::<re_log_types::component_types::Mesh3D>();
+        registry.add::<re_log_types::component_types::Metadata>();
         registry.add::<re_log_types::component_types::MsgId>();
         // registry.add::<re_log_types::component_types::Point2D>();
         // registry.add::<re_log_types::component_types::Point3D>();
@@ -176,6 +180,36 @@ impl DataUi for re_log_types::component_types::TextEntry {
     }
 }
 
+impl DataUi for re_log_types::component_types::Metadata {
+    fn data_ui(
+        &self,
+        _ctx: &mut ViewerContext<'_>,
+        ui: &mut egui::Ui,
+        verbosity: UiVerbosity,
+        _query: &re_arrow_store::LatestAtQuery,
+    ) {
+        match verbosity {
+            UiVerbosity::Small | UiVerbosity::MaxHeight(_) => {
+                ui.label(
+                    self.entries
+                        .iter()
+                        .map(|entry| format!("{}: {}", entry.key, entry.value))
+                        .join(", "),
+                );
+            }
+            UiVerbosity::All | UiVerbosity::Reduced => {
+                egui::Grid::new("metadata").num_columns(2).show(ui, |ui| {
+                    for entry in &self.entries {
+                        ui.label(format!("{}:", entry.key));
+                        ui.label(&entry.value);
+                        ui.end_row();
+                    }
+                });
+            }
+        }
+    }
+}
+
 impl DataUi for re_log_types::component_types::Mesh3D {
     fn data_ui(
         &self,
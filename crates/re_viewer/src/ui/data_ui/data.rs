@@ -1,6 +1,5 @@
 use egui::Vec2;
 
-use re_format::format_f32;
 use re_log_types::{
     component_types::ColorRGBA,
     component_types::{LineStrip2D, LineStrip3D, Mat3x3, Rect2D, Vec2D, Vec3D, Vec4D},
@@ -205,24 +204,35 @@ impl DataUi for Mat3x3 {
 impl DataUi for Vec2D {
     fn data_ui(
         &self,
-        _ctx: &mut crate::misc::ViewerContext<'_>,
+        ctx: &mut crate::misc::ViewerContext<'_>,
         ui: &mut egui::Ui,
         _verbosity: UiVerbosity,
         _query: &re_arrow_store::LatestAtQuery,
     ) {
-        ui.label(self.to_string());
+        let number_format = &ctx.app_options.number_format;
+        ui.label(format!(
+            "[{}, {}]",
+            number_format.format_f32(self.x()),
+            number_format.format_f32(self.y())
+        ));
     }
 }
 
 impl DataUi for Vec3D {
     fn data_ui(
         &self,
-        _ctx: &mut crate::misc::ViewerContext<'_>,
+        ctx: &mut crate::misc::ViewerContext<'_>,
         ui: &mut egui::Ui,
         _verbosity: UiVerbosity,
         _query: &re_arrow_store::LatestAtQuery,
     ) {
-        ui.label(self.to_string());
+        let number_format = &ctx.app_options.number_format;
+        ui.label(format!(
+            "[{}, {}, {}]",
+            number_format.format_f32(self.x()),
+            number_format.format_f32(self.y()),
+            number_format.format_f32(self.z())
+        ));
     }
 }
 
@@ -263,11 +273,12 @@ impl DataUi for Rect2D {
 impl DataUi for LineStrip2D {
     fn data_ui(
         &self,
-        _ctx: &mut crate::misc::ViewerContext<'_>,
+        ctx: &mut crate::misc::ViewerContext<'_>,
         ui: &mut egui::Ui,
         verbosity: UiVerbosity,
         _query: &re_arrow_store::LatestAtQuery,
     ) {
+        let number_format = ctx.app_options.number_format;
         match verbosity {
             UiVerbosity::Small | UiVerbosity::Reduced | UiVerbosity::MaxHeight(_) => {
                 ui.label(format!("{} positions", self.0.len()));
@@ -296,10 +307,10 @@ impl DataUi for LineStrip2D {
                         body.rows(row_height, self.0.len(), |index, mut row| {
                             if let Some(pos) = self.0.get(index) {
                                 row.col(|ui| {
-                                    ui.label(format_f32(pos.x()));
+                                    ui.label(number_format.format_f32(pos.x()));
                                 });
                                 row.col(|ui| {
-                                    ui.label(format_f32(pos.y()));
+                                    ui.label(number_format.format_f32(pos.y()));
                                 });
                             }
                         });
@@ -312,11 +323,12 @@ impl DataUi for LineStrip2D {
 impl DataUi for LineStrip3D {
     fn data_ui(
         &self,
-        _ctx: &mut crate::misc::ViewerContext<'_>,
+        ctx: &mut crate::misc::ViewerContext<'_>,
         ui: &mut egui::Ui,
         verbosity: UiVerbosity,
         _query: &re_arrow_store::LatestAtQuery,
     ) {
+        let number_format = ctx.app_options.number_format;
         match verbosity {
             UiVerbosity::Small | UiVerbosity::Reduced | UiVerbosity::MaxHeight(_) => {
                 ui.label(format!("{} positions", self.0.len()));
@@ -348,13 +360,13 @@ impl DataUi for LineStrip3D {
                         body.rows(row_height, self.0.len(), |index, mut row| {
                             if let Some(pos) = self.0.get(index) {
                                 row.col(|ui| {
-                                    ui.label(format_f32(pos.x()));
+                                    ui.label(number_format.format_f32(pos.x()));
                                 });
                                 row.col(|ui| {
-                                    ui.label(format_f32(pos.y()));
+                                    ui.label(number_format.format_f32(pos.y()));
                                 });
                                 row.col(|ui| {
-                                    ui.label(format_f32(pos.z()));
+                                    ui.label(number_format.format_f32(pos.z()));
                                 });
                             }
                         });
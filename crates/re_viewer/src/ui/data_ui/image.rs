@@ -2,13 +2,13 @@ use egui::Vec2;
 use itertools::Itertools as _;
 
 use re_log_types::{
-    component_types::{ClassId, TensorDataMeaning},
+    component_types::{ClassId, TensorDataMeaning, Unit},
     ClassicTensor,
 };
 
 use crate::misc::{
     caches::{TensorImageView, TensorStats},
-    ViewerContext,
+    NumberFormat, ViewerContext,
 };
 
 use super::{DataUi, UiVerbosity};
@@ -89,6 +89,7 @@ impl DataUi for ClassicTensor {
                                 image_rect,
                                 pointer_pos,
                                 None,
+                                &ctx.app_options.number_format,
                             );
                         }
                     }
@@ -175,6 +176,7 @@ fn show_zoomed_image_region_tooltip(
     image_rect: egui::Rect,
     pointer_pos: egui::Pos2,
     meter: Option<f32>,
+    number_format: &NumberFormat,
 ) -> egui::Response {
     response
         .on_hover_cursor(egui::CursorIcon::Crosshair)
@@ -194,7 +196,7 @@ fn show_zoomed_image_region_tooltip(
                         .at_most(h),
                 ];
                 show_zoomed_image_region_area_outline(parent_ui, tensor_view, center, image_rect);
-                show_zoomed_image_region(ui, tensor_view, center, meter);
+                show_zoomed_image_region(ui, tensor_view, center, meter, None, number_format);
             });
         })
 }
@@ -234,11 +236,15 @@ pub fn show_zoomed_image_region_area_outline(
 }
 
 /// `meter`: iff this is a depth map, how long is one meter?
+///
+/// `unit`: the unit of the values, if logged.
 pub fn show_zoomed_image_region(
     tooltip_ui: &mut egui::Ui,
     tensor_view: &TensorImageView<'_, '_>,
     image_position: [isize; 2],
     meter: Option<f32>,
+    unit: Option<&Unit>,
+    number_format: &NumberFormat,
 ) {
     let Some(dynamic_img) = tensor_view.dynamic_img else { return };
 
@@ -325,10 +331,21 @@ pub fn show_zoomed_image_region(
                         let raw_value = raw_value.as_f64();
                         let meters = raw_value / meter as f64;
                         ui.label("Depth:");
-                        if meters < 1.0 {
+                        if number_format.decimals.is_none() && meters < 1.0 {
                             ui.monospace(format!("{:.1} mm", meters * 1e3));
                         } else {
-                            ui.monospace(format!("{meters:.3} m"));
+                            ui.monospace(format!("{} m", number_format.format_f64(meters)));
+                        }
+                    }
+                } else if let Some(unit) = unit {
+                    if tensor_view.tensor.num_dim() == 2 {
+                        if let Some(raw_value) = tensor_view.tensor.get(&[y, x]) {
+                            ui.label("Value:");
+                            ui.monospace(format!(
+                                "{} {unit}",
+                                number_format.format_f64(raw_value.as_f64())
+                            ));
+                            ui.end_row();
                         }
                     }
                 }
@@ -371,13 +388,29 @@ pub fn show_zoomed_image_region(
                     format!("R: {r}, G: {g}, B: {b}, A: {a}, #{r:02X}{g:02X}{b:02X}{a:02X}")
                 }
             };
-            ui.label(text);
+            ui.label(&text);
 
             color_picker::show_color(
                 ui,
                 Color32::from_rgba_unmultiplied(r, g, b, a),
                 Vec2::splat(ui.available_height()),
             );
+
+            // The tooltip follows the mouse, so it can't have a button. Use the copy shortcut instead:
+            let copy_shortcut = egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::C);
+            ui.weak(format!(
+                "{} to copy the pixel value",
+                ui.ctx().format_shortcut(&copy_shortcut)
+            ));
+            let copy_pressed = ui.input(|i| {
+                i.events
+                    .iter()
+                    .any(|event| matches!(event, egui::Event::Copy))
+            });
+            if copy_pressed {
+                let text = format!("{}, {}: {text}", image_position[0], image_position[1]);
+                ui.output_mut(|o| o.copied_text = text);
+            }
         });
     }
 }
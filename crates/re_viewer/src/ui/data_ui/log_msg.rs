@@ -1,5 +1,6 @@
 use re_log_types::{
     msg_bundle::MsgBundle, ArrowMsg, BeginRecordingMsg, EntityPathOpMsg, LogMsg, RecordingInfo,
+    TraceSpansMsg,
 };
 
 use crate::{misc::ViewerContext, ui::UiVerbosity};
@@ -21,6 +22,11 @@ impl DataUi for LogMsg {
             LogMsg::Goodbye(_) => {
                 ui.label("Goodbye");
             }
+            LogMsg::BlueprintMsg(msg) => {
+                ui.code("BlueprintMsg");
+                ui.label(format!("{:?}", msg.op));
+            }
+            LogMsg::TraceSpansMsg(msg) => msg.data_ui(ctx, ui, verbosity, query),
         }
     }
 }
@@ -28,7 +34,7 @@ impl DataUi for LogMsg {
 impl DataUi for BeginRecordingMsg {
     fn data_ui(
         &self,
-        _ctx: &mut ViewerContext<'_>,
+        ctx: &mut ViewerContext<'_>,
         ui: &mut egui::Ui,
         _verbosity: UiVerbosity,
         _query: &re_arrow_store::LatestAtQuery,
@@ -53,7 +59,7 @@ impl DataUi for BeginRecordingMsg {
             ui.end_row();
 
             ui.monospace("started:");
-            ui.label(started.format());
+            ui.label(started.format_with(&ctx.app_options.time_format));
             ui.end_row();
 
             ui.monospace("recording_source:");
@@ -131,3 +137,32 @@ impl DataUi for ArrowMsg {
         }
     }
 }
+
+impl DataUi for TraceSpansMsg {
+    fn data_ui(
+        &self,
+        _ctx: &mut ViewerContext<'_>,
+        ui: &mut egui::Ui,
+        verbosity: UiVerbosity,
+        _query: &re_arrow_store::LatestAtQuery,
+    ) {
+        ui.code("TraceSpansMsg");
+        let TraceSpansMsg { msg_id: _, spans } = self;
+
+        match verbosity {
+            UiVerbosity::Small | UiVerbosity::MaxHeight(_) => {
+                ui.label(format!("{} spans", spans.len()));
+            }
+            UiVerbosity::Reduced | UiVerbosity::All => {
+                egui::Grid::new("spans").num_columns(3).show(ui, |ui| {
+                    for span in spans {
+                        ui.label(&span.service_name);
+                        ui.label(&span.name);
+                        ui.monospace(span.duration().to_string());
+                        ui.end_row();
+                    }
+                });
+            }
+        }
+    }
+}
@@ -3,7 +3,8 @@ use itertools::Itertools as _;
 use re_arrow_store::{LatestAtQuery, TimeInt};
 use re_format::format_number;
 use re_log_types::{
-    msg_bundle::MsgBundle, BeginRecordingMsg, EntityPathOpMsg, LogMsg, RecordingInfo,
+    msg_bundle::MsgBundle, BeginRecordingMsg, BlueprintMsg, BlueprintOp, EntityPathOpMsg, LogMsg,
+    RecordingInfo, TraceSpansMsg,
 };
 
 use crate::{UiVerbosity, ViewerContext};
@@ -137,7 +138,7 @@ fn table_row(
                 });
             }
             row.col(|ui| {
-                ui.label(started.format());
+                ui.label(started.format_with(&ctx.app_options.time_format));
             });
             row.col(|ui| {
                 ui.monospace(format!("{application_id} - {recording_id:?}"));
@@ -223,5 +224,52 @@ fn table_row(
                 ui.monospace("Goodbye");
             });
         }
+        LogMsg::BlueprintMsg(BlueprintMsg { msg_id, op }) => {
+            row.col(|ui| {
+                ctx.msg_id_button(ui, *msg_id);
+            });
+            row.col(|ui| {
+                ui.monospace("BlueprintMsg");
+            });
+            for _ in ctx.log_db.timelines() {
+                row.col(|ui| {
+                    ui.label("-");
+                });
+            }
+            row.col(|ui| {
+                if let BlueprintOp::AddSpaceView(space_view) = op {
+                    ctx.entity_path_button(ui, None, &space_view.space_path);
+                }
+            });
+            row.col(|ui| {
+                ui.label(format!("{op:?}"));
+            });
+        }
+        LogMsg::TraceSpansMsg(msg) => {
+            let TraceSpansMsg { msg_id, spans } = msg;
+
+            row.col(|ui| {
+                ctx.msg_id_button(ui, *msg_id);
+            });
+            row.col(|ui| {
+                ui.monospace("TraceSpansMsg");
+            });
+            for _ in ctx.log_db.timelines() {
+                row.col(|ui| {
+                    ui.label("-");
+                });
+            }
+            row.col(|ui| {
+                ui.label("-");
+            });
+            row.col(|ui| {
+                let services = spans.iter().map(|span| &span.service_name).unique();
+                ui.label(format!(
+                    "{} spans from {}",
+                    spans.len(),
+                    services.join(", ")
+                ));
+            });
+        }
     }
 }
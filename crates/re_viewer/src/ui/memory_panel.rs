@@ -1,4 +1,5 @@
 use re_arrow_store::DataStoreStats;
+use re_data_store::LogDb;
 use re_format::{format_bytes, format_number};
 use re_memory::{util::sec_since_start, MemoryHistory, MemoryLimit, MemoryUse};
 use re_renderer::WgpuResourcePoolStatistics;
@@ -40,9 +41,11 @@ impl MemoryPanel {
     pub fn ui(
         &self,
         ui: &mut egui::Ui,
-        limit: &MemoryLimit,
+        limit: &mut MemoryLimit,
+        gpu_limit: &MemoryLimit,
         gpu_resource_stats: &WgpuResourcePoolStatistics,
         store_stats: &DataStoreStats,
+        log_dbs: &[&LogDb],
     ) {
         crate::profile_function!();
 
@@ -54,7 +57,14 @@ impl MemoryPanel {
             .min_width(250.0)
             .default_width(300.0)
             .show_inside(ui, |ui| {
-                Self::left_side(ui, limit, gpu_resource_stats, store_stats);
+                Self::left_side(
+                    ui,
+                    limit,
+                    gpu_limit,
+                    gpu_resource_stats,
+                    store_stats,
+                    log_dbs,
+                );
             });
 
         egui::CentralPanel::default().show_inside(ui, |ui| {
@@ -65,9 +75,11 @@ impl MemoryPanel {
 
     fn left_side(
         ui: &mut egui::Ui,
-        limit: &MemoryLimit,
+        limit: &mut MemoryLimit,
+        gpu_limit: &MemoryLimit,
         gpu_resource_stats: &WgpuResourcePoolStatistics,
         store_stats: &DataStoreStats,
+        log_dbs: &[&LogDb],
     ) {
         ui.strong("Rerun Viewer resource usage");
 
@@ -78,24 +90,23 @@ impl MemoryPanel {
 
         ui.separator();
         ui.collapsing("GPU Resources", |ui| {
-            Self::gpu_stats(ui, gpu_resource_stats);
+            Self::gpu_stats(ui, gpu_limit, gpu_resource_stats);
         });
 
         ui.separator();
         ui.collapsing("Datastore Resources", |ui| {
             Self::store_stats(ui, store_stats);
         });
+
+        ui.separator();
+        ui.collapsing("Recordings", |ui| {
+            Self::recordings_stats(ui, log_dbs);
+        });
     }
 
-    fn cpu_stats(ui: &mut egui::Ui, limit: &MemoryLimit) {
-        if let Some(limit) = limit.limit {
-            ui.label(format!("Memory limit: {}", format_bytes(limit as _)));
-        } else {
-            ui.label(
-                "You can set an upper limit of RAM use with the command-lime option --memory-limit",
-            );
-            ui.separator();
-        }
+    fn cpu_stats(ui: &mut egui::Ui, limit: &mut MemoryLimit) {
+        Self::memory_limit_ui(ui, limit);
+        ui.separator();
 
         let mem_use = MemoryUse::capture();
 
@@ -124,7 +135,81 @@ impl MemoryPanel {
         }
     }
 
-    fn gpu_stats(ui: &mut egui::Ui, gpu_resource_stats: &WgpuResourcePoolStatistics) {
+    /// The limit starts out as the command-line option --memory-limit, but can be changed here.
+    fn memory_limit_ui(ui: &mut egui::Ui, limit: &mut MemoryLimit) {
+        const GB: f64 = 1e9;
+
+        ui.horizontal(|ui| {
+            let mut enabled = limit.limit.is_some();
+            ui.checkbox(&mut enabled, "Memory limit:").on_hover_text(
+                "When the Rerun Viewer uses more RAM than this, the oldest data is dropped",
+            );
+
+            let mut gigabytes = limit.limit.map_or(8.0, |bytes| bytes as f64 / GB);
+            ui.add_enabled(
+                enabled,
+                egui::DragValue::new(&mut gigabytes)
+                    .clamp_range(0.1..=1024.0)
+                    .speed(0.1)
+                    .suffix(" GB"),
+            );
+
+            limit.limit = enabled.then_some((gigabytes * GB) as i64);
+        });
+    }
+
+    fn recordings_stats(ui: &mut egui::Ui, log_dbs: &[&LogDb]) {
+        egui::Grid::new("recordings grid")
+            .num_columns(3)
+            .show(ui, |ui| {
+                for log_db in log_dbs {
+                    if let Some(recording_info) = log_db.recording_info() {
+                        ui.label(recording_info.application_id.to_string());
+                    } else {
+                        ui.label("<unknown>");
+                    }
+                    ui.label(format_bytes(log_db.num_data_bytes() as _));
+
+                    let purge_stats = log_db.purge_stats();
+                    if purge_stats.num_purges == 0 {
+                        ui.label("");
+                    } else {
+                        let cutoffs = purge_stats
+                            .cutoff_times
+                            .iter()
+                            .map(|(timeline, time)| {
+                                format!("{}: {}", timeline.name(), timeline.typ().format(*time))
+                            })
+                            .collect::<Vec<_>>()
+                            .join("\n");
+                        ui.label(format!(
+                            "⚠ dropped {} messages",
+                            format_number(purge_stats.num_dropped_msgs)
+                        ))
+                        .on_hover_text(format!(
+                            "The oldest data was dropped to stay below the memory limit. \
+                            The data now starts at:\n{cutoffs}"
+                        ));
+                    }
+                    ui.end_row();
+                }
+            });
+    }
+
+    fn gpu_stats(
+        ui: &mut egui::Ui,
+        gpu_limit: &MemoryLimit,
+        gpu_resource_stats: &WgpuResourcePoolStatistics,
+    ) {
+        if let Some(limit) = gpu_limit.limit {
+            ui.label(format!("GPU memory budget: {}", format_bytes(limit as _)));
+        } else {
+            ui.label(
+                "You can set a budget for GPU memory use with the command-line option --gpu-memory-limit",
+            );
+        }
+        ui.separator();
+
         egui::Grid::new("gpu resource grid")
             .num_columns(2)
             .show(ui, |ui| {
@@ -171,6 +256,11 @@ impl MemoryPanel {
                 ui.label("Texture Memory:");
                 ui.label(re_format::format_bytes(*total_texture_size_in_bytes as _));
                 ui.end_row();
+                ui.label("Total GPU Memory:");
+                ui.label(re_format::format_bytes(
+                    gpu_resource_stats.total_bytes() as _
+                ));
+                ui.end_row();
             });
     }
 
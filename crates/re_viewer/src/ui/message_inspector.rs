@@ -0,0 +1,217 @@
+use re_data_store::LogDb;
+use re_format::format_bytes;
+use re_log_types::{LogMsg, MsgId, Time, TimeFormatOptions};
+
+use crate::misc::ItemCollection;
+use crate::Item;
+
+/// Don't show more than this many bytes of a message, to keep the ui responsive.
+const MAX_SHOWN_BYTES: usize = 64 * 1024;
+
+/// How to show the contents of a message.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum RawView {
+    /// The bytes as they are sent from the SDK.
+    #[default]
+    Hex,
+
+    /// The decoded structure.
+    Json,
+}
+
+/// Shows the raw contents of the selected message, for debugging SDK encoding bugs.
+#[derive(Default)]
+pub struct MessageInspector {
+    pub open: bool,
+    view: RawView,
+}
+
+impl MessageInspector {
+    pub fn window(
+        &mut self,
+        egui_ctx: &egui::Context,
+        log_db: &LogDb,
+        selection: &ItemCollection,
+        time_format: &TimeFormatOptions,
+    ) {
+        let Self { open, view } = self;
+
+        egui::Window::new("Message inspector")
+            .open(open)
+            .resizable(true)
+            .default_width(600.0)
+            .show(egui_ctx, |ui| {
+                let msg = selection.iter().find_map(|item| match item {
+                    Item::MsgId(msg_id) => log_db.get_log_msg(msg_id),
+                    _ => None,
+                });
+                let Some(msg) = msg else {
+                    ui.label("Select a message, e.g. in the event log, to inspect it.");
+                    return;
+                };
+
+                let bytes = re_ws_comms::encode_log_msg(msg);
+
+                metadata_ui(ui, log_db, msg, bytes.len(), time_format);
+
+                ui.separator();
+
+                ui.horizontal(|ui| {
+                    ui.selectable_value(view, RawView::Hex, "Hex");
+                    ui.selectable_value(view, RawView::Json, "JSON");
+                });
+
+                let text = match view {
+                    RawView::Hex => hex_dump(&bytes[..bytes.len().min(MAX_SHOWN_BYTES)]),
+                    RawView::Json => match serde_json::to_string_pretty(msg) {
+                        Ok(json) => truncate(json, MAX_SHOWN_BYTES),
+                        Err(err) => format!("Failed to convert message to JSON: {err}"),
+                    },
+                };
+                if *view == RawView::Hex && MAX_SHOWN_BYTES < bytes.len() {
+                    ui.weak(format!(
+                        "Only showing the first {} of {}",
+                        format_bytes(MAX_SHOWN_BYTES as _),
+                        format_bytes(bytes.len() as _)
+                    ));
+                }
+
+                if ui.button("Copy").clicked() {
+                    ui.output_mut(|o| o.copied_text = text.clone());
+                }
+
+                egui::ScrollArea::both()
+                    .auto_shrink([false; 2])
+                    .show(ui, |ui| {
+                        ui.add(egui::Label::new(egui::RichText::new(text).monospace()).wrap(false));
+                    });
+            });
+    }
+}
+
+fn metadata_ui(
+    ui: &mut egui::Ui,
+    log_db: &LogDb,
+    msg: &LogMsg,
+    num_bytes: usize,
+    time_format: &TimeFormatOptions,
+) {
+    let msg_id = msg.id();
+
+    egui::Grid::new("message_metadata")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Message ID:");
+            ui.monospace(msg_id.to_string());
+            ui.end_row();
+
+            ui.label("Kind:");
+            ui.label(msg_kind(msg));
+            ui.end_row();
+
+            ui.label("Encoded size:");
+            ui.label(format_bytes(num_bytes as _))
+                .on_hover_text("Size of the message as sent to the web viewer, before compression");
+            ui.end_row();
+
+            ui.label("Created:");
+            ui.label(creation_time(msg_id).format_with(time_format))
+                .on_hover_text("When the SDK created the message");
+            ui.end_row();
+
+            ui.label("Arrived:");
+            ui.label(log_db.arrival_time(&msg_id).map_or_else(
+                || "Unknown".to_owned(),
+                |time| time.format_with(time_format),
+            ))
+            .on_hover_text("When the viewer received the message");
+            ui.end_row();
+
+            ui.label("Source:");
+            ui.label(
+                log_db
+                    .data_source
+                    .as_ref()
+                    .map_or_else(|| "Unknown".to_owned(), source_description),
+            );
+            ui.end_row();
+        });
+}
+
+fn msg_kind(msg: &LogMsg) -> &'static str {
+    match msg {
+        LogMsg::BeginRecordingMsg(_) => "BeginRecordingMsg",
+        LogMsg::EntityPathOpMsg(_) => "EntityPathOpMsg",
+        LogMsg::ArrowMsg(_) => "ArrowMsg",
+        LogMsg::Goodbye(_) => "Goodbye",
+        LogMsg::BlueprintMsg(_) => "BlueprintMsg",
+        LogMsg::TraceSpansMsg(_) => "TraceSpansMsg",
+    }
+}
+
+/// The upper half of a message id is the time it was created, in nanoseconds since the epoch.
+fn creation_time(msg_id: MsgId) -> Time {
+    Time::from_ns_since_epoch((msg_id.as_u128() >> 64) as i64)
+}
+
+fn source_description(source: &re_smart_channel::Source) -> String {
+    use re_smart_channel::Source;
+    match source {
+        Source::File { path } => format!("File {}", path.display()),
+        Source::FollowedFile { path } => format!("Followed file {}", path.display()),
+        Source::Sdk => "SDK in the same process".to_owned(),
+        Source::WsClient { ws_server_url } => format!("WebSocket {ws_server_url}"),
+        Source::TcpServer { port } => format!("TCP server on port {port}"),
+    }
+}
+
+fn truncate(mut text: String, max_len: usize) -> String {
+    if max_len < text.len() {
+        let mut end = max_len;
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        text.truncate(end);
+        text.push_str("\n…");
+    }
+    text
+}
+
+/// Classic hex dump: offset, 16 bytes in hex, and the printable ones as ASCII.
+fn hex_dump(bytes: &[u8]) -> String {
+    use std::fmt::Write as _;
+
+    let mut out = String::with_capacity(bytes.len() * 4 + bytes.len() / 16 * 12);
+    for (row, chunk) in bytes.chunks(16).enumerate() {
+        write!(out, "{:08x}  ", row * 16).ok();
+        for i in 0..16 {
+            match chunk.get(i) {
+                Some(byte) => write!(out, "{byte:02x} ").ok(),
+                None => write!(out, "   ").ok(),
+            };
+            if i == 7 {
+                out.push(' ');
+            }
+        }
+        out.push(' ');
+        for &byte in chunk {
+            out.push(if byte.is_ascii_graphic() || byte == b' ' {
+                byte as char
+            } else {
+                '.'
+            });
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[test]
+fn test_hex_dump() {
+    assert_eq!(hex_dump(&[]), "");
+    assert_eq!(
+        hex_dump(b"RRF0\x00\x01hello, world!!"),
+        "00000000  52 52 46 30 00 01 68 65  6c 6c 6f 2c 20 77 6f 72  RRF0..hello, wor\n\
+         00000010  6c 64 21 21                                       ld!!\n"
+    );
+}
@@ -1,6 +1,8 @@
 mod annotations;
 mod auto_layout;
 mod blueprint;
+mod copy_as_code;
+mod copy_as_json;
 mod data_blueprint;
 mod scene;
 mod selection_history;
@@ -15,11 +17,15 @@ mod view_text;
 mod view_time_series;
 mod viewport;
 
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) mod data_sources_panel;
 pub(crate) mod data_ui;
 pub(crate) mod event_log_view;
 pub(crate) mod memory_panel;
+pub(crate) mod message_inspector;
 pub(crate) mod selection_panel;
 pub(crate) mod time_panel;
+pub(crate) mod welcome_screen;
 
 pub mod view_spatial;
 
@@ -1,19 +1,33 @@
 use re_data_store::LogDb;
+use re_log_types::{TimeInt, Timeline};
 
 use crate::misc::ItemCollection;
 
 use super::Blueprint;
 
-/// A `Selection` and its index into the historical stack.
+/// A `Selection`, and where the time cursor was when it was made.
+#[derive(Debug, Clone)]
+pub struct HistoryEntry {
+    pub selection: ItemCollection,
+    pub time: Option<(Timeline, TimeInt)>,
+}
+
+/// A [`HistoryEntry`] and its index into the historical stack.
 #[derive(Debug, Clone)]
 pub struct HistoricalSelection {
     pub index: usize,
     pub selection: ItemCollection,
+    pub time: Option<(Timeline, TimeInt)>,
 }
 
-impl From<(usize, ItemCollection)> for HistoricalSelection {
-    fn from((index, selection): (usize, ItemCollection)) -> Self {
-        Self { index, selection }
+impl From<(usize, HistoryEntry)> for HistoricalSelection {
+    fn from((index, entry): (usize, HistoryEntry)) -> Self {
+        let HistoryEntry { selection, time } = entry;
+        Self {
+            index,
+            selection,
+            time,
+        }
     }
 }
 
@@ -28,7 +42,7 @@ pub struct SelectionHistory {
     pub(crate) current: usize,
 
     /// Oldest first.
-    pub(crate) stack: Vec<ItemCollection>,
+    pub(crate) stack: Vec<HistoryEntry>,
 }
 
 impl SelectionHistory {
@@ -36,9 +50,9 @@ impl SelectionHistory {
         crate::profile_function!();
 
         let mut i = 0;
-        self.stack.retain_mut(|selection| {
-            selection.purge_invalid(log_db, blueprint);
-            let retain = !selection.is_empty();
+        self.stack.retain_mut(|entry| {
+            entry.selection.purge_invalid(log_db, blueprint);
+            let retain = !entry.selection.is_empty();
             if !retain && i <= self.current {
                 self.current = self.current.saturating_sub(1);
             }
@@ -69,7 +83,12 @@ impl SelectionHistory {
             .map(|sel| (self.current + 1, sel.clone()).into())
     }
 
-    pub fn update_selection(&mut self, item_collection: &ItemCollection) {
+    /// Records a new selection, made when the time cursor was at `time`.
+    pub fn update_selection(
+        &mut self,
+        item_collection: &ItemCollection,
+        time: Option<(Timeline, TimeInt)>,
+    ) {
         // Selecting nothing is irrelevant from a history standpoint.
         if item_collection.is_empty() {
             return;
@@ -85,15 +104,46 @@ impl SelectionHistory {
         // diverging timeline!
         self.stack.truncate(self.current + 1);
 
-        self.stack.push(item_collection.clone());
+        self.stack.push(HistoryEntry {
+            selection: item_collection.clone(),
+            time,
+        });
 
-        // Keep size under a certain maximum.
+        // Keep size under a certain maximum, forgetting the oldest selections first.
         if self.stack.len() > MAX_SELECTION_HISTORY_LENGTH {
             self.stack
-                .drain((self.stack.len() - MAX_SELECTION_HISTORY_LENGTH)..self.stack.len());
+                .drain(..self.stack.len() - MAX_SELECTION_HISTORY_LENGTH);
         }
 
         // Update current index last so it points to something valid!
         self.current = self.stack.len() - 1;
     }
 }
+
+#[test]
+fn test_selection_history() {
+    use crate::{ui::SpaceViewId, Item};
+
+    let selections: Vec<ItemCollection> = (0..MAX_SELECTION_HISTORY_LENGTH + 2)
+        .map(|_| ItemCollection::new(std::iter::once(Item::SpaceView(SpaceViewId::random()))))
+        .collect();
+    let time = (Timeline::new_sequence("frame"), TimeInt::from(42));
+
+    let mut history = SelectionHistory::default();
+    for selection in &selections {
+        history.update_selection(selection, Some(time));
+    }
+
+    // The oldest selections are forgotten first:
+    assert_eq!(history.stack.len(), MAX_SELECTION_HISTORY_LENGTH);
+    let current = history.current().unwrap();
+    assert_eq!(&current.selection, selections.last().unwrap());
+    assert_eq!(current.time, Some(time));
+    assert_eq!(&history.stack[0].selection, &selections[2]);
+
+    assert_eq!(
+        history.previous().unwrap().selection,
+        selections[selections.len() - 2]
+    );
+    assert!(history.next().is_none());
+}
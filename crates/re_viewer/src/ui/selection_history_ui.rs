@@ -7,14 +7,14 @@ use crate::{misc::ItemCollection, ui::Blueprint, Item};
 // ---
 
 impl SelectionHistory {
+    /// Returns the selection from the history to go to, if any.
     pub(crate) fn selection_ui(
         &mut self,
         re_ui: &re_ui::ReUi,
         ui: &mut egui::Ui,
         blueprint: &Blueprint,
-    ) -> Option<ItemCollection> {
+    ) -> Option<HistoricalSelection> {
         self.control_bar_ui(re_ui, ui, blueprint)
-            .map(|sel| sel.selection)
     }
 
     fn control_bar_ui(
@@ -38,12 +38,6 @@ impl SelectionHistory {
         }).inner
     }
 
-    // TODO(cmc): note that for now, we only check prev/next shortcuts in the UI code that
-    // shows the associated buttons... this means shortcuts only work when the selection panel
-    // is open!
-    // We might want to change this at some point, though the way things are currently designed,
-    // there isn't much point in selecting stuff while the selection panel is hidden anyway.
-
     pub fn select_previous(&mut self) -> Option<HistoricalSelection> {
         if let Some(previous) = self.previous() {
             if previous.index != self.current {
@@ -83,12 +77,16 @@ impl SelectionHistory {
                     item_collection_to_string(blueprint, &previous.selection),
                 ));
 
+            let mut picked = None;
             let response = response.context_menu(|ui| {
                 // undo: newest on top, oldest on bottom
                 for i in (0..self.current).rev() {
-                    self.history_item_ui(blueprint, ui, i);
+                    picked = picked.or(self.history_item_ui(blueprint, ui, i));
                 }
             });
+            if picked.is_some() {
+                return picked;
+            }
 
             // TODO(cmc): using the keyboard shortcut should highlight the associated
             // button or something (but then again it, it'd make more sense to do that
@@ -126,12 +124,16 @@ impl SelectionHistory {
                     item_collection_to_string(blueprint, &next.selection),
                 ));
 
+            let mut picked = None;
             let response = response.context_menu(|ui| {
                 // redo: oldest on top, most recent on bottom
                 for i in (self.current + 1)..self.stack.len() {
-                    self.history_item_ui(blueprint, ui, i);
+                    picked = picked.or(self.history_item_ui(blueprint, ui, i));
                 }
             });
+            if picked.is_some() {
+                return picked;
+            }
 
             // TODO(cmc): using the keyboard shortcut should highlight the associated
             // button or something (but then again it, it'd make more sense to do that
@@ -150,20 +152,32 @@ impl SelectionHistory {
         None
     }
 
-    fn history_item_ui(&mut self, blueprint: &Blueprint, ui: &mut egui::Ui, index: usize) {
-        if let Some(sel) = self.stack.get(index) {
-            ui.horizontal(|ui| {
-                {
-                    // borrow checker workaround
-                    let sel = item_collection_to_string(blueprint, sel);
-                    if ui.selectable_value(&mut self.current, index, sel).clicked() {
-                        ui.close_menu();
-                    }
-                }
-                if sel.len() == 1 {
-                    item_kind_ui(ui, sel.iter().next().unwrap());
+    /// Returns the selection to go to if it was clicked.
+    fn history_item_ui(
+        &mut self,
+        blueprint: &Blueprint,
+        ui: &mut egui::Ui,
+        index: usize,
+    ) -> Option<HistoricalSelection> {
+        let entry = self.stack.get(index)?;
+        let mut clicked = false;
+        ui.horizontal(|ui| {
+            {
+                // borrow checker workaround
+                let sel = item_collection_to_string(blueprint, &entry.selection);
+                if ui.selectable_value(&mut self.current, index, sel).clicked() {
+                    ui.close_menu();
+                    clicked = true;
                 }
-            });
+            }
+            if entry.selection.len() == 1 {
+                item_kind_ui(ui, entry.selection.iter().next().unwrap());
+            }
+        });
+        if clicked {
+            self.current()
+        } else {
+            None
         }
     }
 }
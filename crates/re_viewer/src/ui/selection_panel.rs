@@ -1,12 +1,20 @@
-use re_data_store::{query_transform, EntityPath, EntityProperties};
-use re_log_types::TimeType;
+use std::collections::BTreeMap;
+
+use re_data_store::{
+    query_transform, Colormap, EntityPath, EntityProperties, InstancePath, MissingScalars,
+    PointColorSource,
+};
+use re_log_types::{TimeInt, TimeType};
 
 use crate::{
-    ui::{view_spatial::SpatialNavigationMode, Blueprint},
+    ui::{view_spatial::SpatialNavigationMode, Blueprint, ViewCategory},
     Item, UiVerbosity, ViewerContext,
 };
 
-use super::{data_ui::DataUi, space_view::ViewState};
+use super::{
+    data_ui::DataUi,
+    space_view::{SpaceView, ViewState},
+};
 
 // ---
 
@@ -43,12 +51,12 @@ impl SelectionPanel {
                         ..Default::default()
                     })
                     .show_inside(ui, |ui| {
-                        if let Some(selection) = ctx
+                        if let Some(historical) = ctx
                             .rec_cfg
                             .selection_state
                             .selection_ui(ctx.re_ui, ui, blueprint)
                         {
-                            ctx.set_multi_selection(selection.iter().cloned());
+                            ctx.rec_cfg.restore_time_of(&historical);
                         }
                     });
 
@@ -91,9 +99,21 @@ impl SelectionPanel {
                 if has_data_section(selection) {
                     ctx.re_ui.large_collapsing_header(ui, "Data", true, |ui| {
                         selection.data_ui(ctx, ui, UiVerbosity::All, &query);
+
+                        if let Item::InstancePath(_, instance_path) = selection {
+                            copy_as_code_ui(ctx, ui, &instance_path.entity_path, &query);
+                            copy_as_json_ui(ctx, ui, instance_path, &query);
+                        }
                     });
                 }
 
+                if let Item::InstancePath(_, instance_path) = selection {
+                    ctx.re_ui
+                        .large_collapsing_header(ui, "History", false, |ui| {
+                            history_ui(ctx, ui, &instance_path.entity_path);
+                        });
+                }
+
                 ctx.re_ui
                     .large_collapsing_header(ui, "Blueprint", true, |ui| {
                         blueprint_ui(ui, ctx, blueprint, selection);
@@ -116,6 +136,122 @@ fn has_data_section(item: &Item) -> bool {
     }
 }
 
+/// Buttons for copying code that logs the current data of the entity.
+/// When was data logged to this entity on the current timeline?
+///
+/// Shows the latest times up to the time cursor, and clicking one moves the time cursor there.
+fn history_ui(ctx: &mut ViewerContext<'_>, ui: &mut egui::Ui, entity_path: &EntityPath) {
+    const MAX_ROWS: usize = 20;
+
+    let timeline = *ctx.rec_cfg.time_ctrl.timeline();
+    let Some(tree) = ctx.log_db.entity_db.tree.subtree(entity_path) else {
+        return;
+    };
+
+    // Number of logged components at each time:
+    let mut num_components_per_time: BTreeMap<TimeInt, usize> = Default::default();
+    for (component_name, stats) in &tree.components {
+        if component_name.as_str() == "rerun.instance_key" {
+            continue;
+        }
+        for (time, count) in stats.times.get(&timeline).into_iter().flatten() {
+            *num_components_per_time.entry(*time).or_default() += count;
+        }
+    }
+
+    let current_time = ctx.rec_cfg.time_ctrl.time_int().unwrap_or(TimeInt::MAX);
+    let earlier = num_components_per_time.range(..=current_time);
+    let num_earlier = earlier.clone().count();
+    if num_earlier == 0 {
+        ui.label(format!(
+            "Nothing logged on {} before the time cursor",
+            timeline.name()
+        ));
+        return;
+    }
+
+    egui::Grid::new("entity_history")
+        .num_columns(2)
+        .show(ui, |ui| {
+            for (i, (time, num_components)) in earlier.rev().take(MAX_ROWS).enumerate() {
+                let is_current = i == 0;
+                if ui
+                    .selectable_label(is_current, timeline.typ().format(*time))
+                    .on_hover_text("Click to move the time cursor here")
+                    .clicked()
+                {
+                    ctx.rec_cfg.time_ctrl.set_timeline_and_time(timeline, *time);
+                    ctx.rec_cfg.time_ctrl.pause();
+                }
+                ui.label(format!(
+                    "{num_components} component{}",
+                    if *num_components == 1 { "" } else { "s" }
+                ));
+                ui.end_row();
+            }
+        });
+    if MAX_ROWS < num_earlier {
+        ui.weak(format!("…and {} earlier", num_earlier - MAX_ROWS));
+    }
+}
+
+fn copy_as_code_ui(
+    ctx: &ViewerContext<'_>,
+    ui: &mut egui::Ui,
+    entity_path: &EntityPath,
+    query: &re_arrow_store::LatestAtQuery,
+) {
+    use super::copy_as_code::{snippet, Language};
+
+    ui.horizontal(|ui| {
+        ui.label("Copy as code:");
+        for (language, name) in [(Language::Python, "Python"), (Language::Rust, "Rust")] {
+            if ui
+                .small_button(name)
+                .on_hover_text(format!(
+                    "Copy {name} code that logs the data of {entity_path} \
+                    at the current time to the clipboard.\n\
+                    Useful for learning the SDK, or for making a minimal repro."
+                ))
+                .clicked()
+            {
+                let code = snippet(
+                    &ctx.log_db.entity_db.data_store,
+                    query,
+                    entity_path,
+                    language,
+                );
+                ui.output_mut(|o| o.copied_text = code);
+            }
+        }
+    });
+}
+
+fn copy_as_json_ui(
+    ctx: &ViewerContext<'_>,
+    ui: &mut egui::Ui,
+    instance_path: &InstancePath,
+    query: &re_arrow_store::LatestAtQuery,
+) {
+    if ui
+        .small_button("Copy as JSON")
+        .on_hover_text(format!(
+            "Copy the data of {instance_path} at the current time to the clipboard, as JSON."
+        ))
+        .clicked()
+    {
+        let json = super::copy_as_json::instance_json(
+            &ctx.log_db.entity_db.data_store,
+            query,
+            instance_path,
+        );
+        match serde_json::to_string_pretty(&json) {
+            Ok(json) => ui.output_mut(|o| o.copied_text = json),
+            Err(err) => re_log::error!("Failed to convert {instance_path} to JSON: {err}"),
+        }
+    }
+}
+
 /// What is selected? Not the contents, just the short id of it.
 pub fn what_is_selected_ui(
     ui: &mut egui::Ui,
@@ -172,6 +308,67 @@ pub fn what_is_selected_ui(
                         ui.end_row();
                     }
                 }
+
+                if instance_path.instance_key.is_splat() {
+                    let entity_path = &instance_path.entity_path;
+
+                    let mut display_name = ctx
+                        .entity_display_names
+                        .get(entity_path)
+                        .unwrap_or_default()
+                        .to_owned();
+                    ui.label("Display name:");
+                    if ui
+                        .add(
+                            egui::TextEdit::singleline(&mut display_name)
+                                .hint_text(entity_path.to_string()),
+                        )
+                        .on_hover_text(
+                            "Shown instead of the entity path in trees, legends and tooltips. \
+                            Doesn't change the logged path.",
+                        )
+                        .changed()
+                    {
+                        ctx.entity_display_names
+                            .set(entity_path.clone(), &display_name);
+                    }
+                    ui.end_row();
+
+                    let mut favorite = blueprint.favorites.contains(entity_path);
+                    ui.label("Favorite:");
+                    if ui
+                        .checkbox(&mut favorite, "⭐")
+                        .on_hover_text("Show at the top of the Blueprint panel")
+                        .changed()
+                    {
+                        if favorite {
+                            blueprint.favorites.insert(entity_path.clone());
+                        } else {
+                            blueprint.favorites.remove(entity_path);
+                        }
+                    }
+                    ui.end_row();
+
+                    let deduplicated_entities = &mut ctx.rec_cfg.deduplicated_entities;
+                    let mut deduplicate = deduplicated_entities.contains(entity_path);
+                    ui.label("Deduplicate:");
+                    if ui
+                        .checkbox(&mut deduplicate, "")
+                        .on_hover_text(
+                            "Only store new data for this entity (and everything under it) \
+                            when it changes, for entities that are logged far more often than that.\n\
+                            Applies to data that arrives from now on.",
+                        )
+                        .changed()
+                    {
+                        if deduplicate {
+                            deduplicated_entities.insert(entity_path.clone());
+                        } else {
+                            deduplicated_entities.remove(entity_path);
+                        }
+                    }
+                    ui.end_row();
+                }
             });
         }
         Item::DataBlueprintGroup(space_view_id, data_blueprint_group_handle) => {
@@ -280,6 +477,8 @@ fn blueprint_ui(
             ui.add_space(ui.spacing().item_spacing.y);
 
             if let Some(space_view) = blueprint.viewport.space_view_mut(space_view_id) {
+                pinned_time_ui(ctx, ui, space_view);
+                number_format_ui(ctx, ui, space_view);
                 space_view.selection_ui(ctx, ui);
             }
         }
@@ -303,6 +502,7 @@ fn blueprint_ui(
                         ui,
                         Some(&instance_path.entity_path),
                         &mut props,
+                        space_view.category,
                         &space_view.view_state,
                     );
                     data_blueprint.set(instance_path.entity_path.clone(), props);
@@ -323,6 +523,7 @@ fn blueprint_ui(
                         ui,
                         None,
                         &mut group.properties_individual,
+                        space_view.category,
                         &space_view.view_state,
                     );
                 } else {
@@ -333,6 +534,67 @@ fn blueprint_ui(
     }
 }
 
+fn pinned_time_ui(ctx: &ViewerContext<'_>, ui: &mut egui::Ui, space_view: &mut SpaceView) {
+    ui.horizontal(|ui| match space_view.pinned_time {
+        Some((timeline, time)) => {
+            ui.label(format!(
+                "Pinned to {} {}",
+                timeline.name(),
+                timeline
+                    .typ()
+                    .format_with(time, &ctx.app_options.time_format)
+            ));
+            if ui
+                .button("Unpin")
+                .on_hover_text("Follow the time panel again")
+                .clicked()
+            {
+                space_view.pinned_time = None;
+            }
+        }
+        None => {
+            let time_ctrl = &ctx.rec_cfg.time_ctrl;
+            if let Some(time) = time_ctrl.time_int() {
+                if ui
+                    .button("📌 Pin to current time")
+                    .on_hover_text(
+                        "Keep showing the data at the current time in this Space View, \
+                        while the rest of the viewer moves on",
+                    )
+                    .clicked()
+                {
+                    space_view.pinned_time = Some((*time_ctrl.timeline(), time));
+                }
+            }
+        }
+    });
+}
+
+fn number_format_ui(ctx: &ViewerContext<'_>, ui: &mut egui::Ui, space_view: &mut SpaceView) {
+    ui.horizontal(|ui| {
+        let mut is_overridden = space_view.number_format.is_some();
+        if ui
+            .checkbox(&mut is_overridden, "Number format")
+            .on_hover_text(
+                "Use a different number format in this Space View than in the rest of the viewer",
+            )
+            .changed()
+        {
+            space_view.number_format = is_overridden.then_some(ctx.app_options.number_format);
+        }
+
+        ui.add_enabled_ui(is_overridden, |ui| {
+            let mut number_format = space_view
+                .number_format
+                .unwrap_or(ctx.app_options.number_format);
+            number_format.ui(ui, ("number_format", space_view.id));
+            if is_overridden {
+                space_view.number_format = Some(number_format);
+            }
+        });
+    });
+}
+
 fn list_existing_data_blueprints(
     ui: &mut egui::Ui,
     ctx: &mut ViewerContext<'_>,
@@ -369,6 +631,7 @@ fn entity_props_ui(
     ui: &mut egui::Ui,
     entity_path: Option<&EntityPath>,
     entity_props: &mut EntityProperties,
+    category: ViewCategory,
     view_state: &ViewState,
 ) {
     use egui::NumExt;
@@ -407,7 +670,89 @@ fn entity_props_ui(
             }
             ui.end_row();
 
+            if category == ViewCategory::Spatial
+                && (visible_history.nanos != 0 || visible_history.sequences != 0)
+            {
+                ui.label("History decimation");
+                match ctx.rec_cfg.time_ctrl.timeline().typ() {
+                    TimeType::Time => {
+                        let mut samples_per_sec = if visible_history.decimation_nanos > 0 {
+                            1e9 / visible_history.decimation_nanos as f64
+                        } else {
+                            0.0
+                        };
+                        ui.add(
+                            egui::DragValue::new(&mut samples_per_sec)
+                                .clamp_range(0.0..=10_000.0)
+                                .speed(1.0)
+                                .suffix(" samples / s"),
+                        )
+                        .on_hover_text(
+                            "Show at most this many samples per second of the history, \
+                            e.g. to keep long trails of high-rate data readable and fast. \
+                            Zero shows every sample.",
+                        );
+                        visible_history.decimation_nanos = if samples_per_sec > 0.0 {
+                            (1e9 / samples_per_sec).round() as _
+                        } else {
+                            0
+                        };
+                    }
+                    TimeType::Sequence => {
+                        ui.add(
+                            egui::DragValue::new(&mut visible_history.decimation_sequences)
+                                .clamp_range(0.0..=f32::INFINITY)
+                                .speed(1.0)
+                                .prefix("1 sample per "),
+                        )
+                        .on_hover_text(
+                            "Show at most one sample of the history per this many steps, \
+                            e.g. to keep long trails of high-rate data readable and fast. \
+                            Zero shows every sample.",
+                        );
+                    }
+                }
+                ui.end_row();
+            }
+
+            if category == ViewCategory::TimeSeries {
+                ui.label("Missing values");
+                let mut missing_scalars = entity_props.missing_scalars();
+                egui::ComboBox::from_id_source("missing_scalars")
+                    .selected_text(missing_scalars.to_string())
+                    .show_ui(ui, |ui| {
+                        ui.style_mut().wrap = Some(false);
+                        for value in MissingScalars::ALL {
+                            ui.selectable_value(&mut missing_scalars, value, value.to_string());
+                        }
+                    })
+                    .response
+                    .on_hover_text("How to plot NaN and infinite values.");
+                if missing_scalars != entity_props.missing_scalars() {
+                    entity_props.set_missing_scalars(missing_scalars);
+                }
+                ui.end_row();
+
+                ui.label("Angular");
+                let mut angular = entity_props.angular();
+                if ui
+                    .checkbox(&mut angular, "")
+                    .on_hover_text(
+                        "Wrap the values to ±180° (or ±π if not logged in degrees), \
+                        without drawing a jump at the wraparound.",
+                    )
+                    .changed()
+                {
+                    entity_props.set_angular(angular);
+                }
+                ui.end_row();
+            }
+
             if view_state.state_spatial.nav_mode == SpatialNavigationMode::ThreeD {
+                ui.label("Point colors");
+                point_coloring_ui(ui, entity_props);
+                ui.end_row();
+
                 if let Some(entity_path) = entity_path {
                     let query = ctx.current_query();
                     if let Some(re_log_types::Transform::Pinhole(pinhole)) =
@@ -433,3 +778,65 @@ fn entity_props_ui(
             }
         });
 }
+
+fn point_coloring_ui(ui: &mut egui::Ui, entity_props: &mut EntityProperties) {
+    let mut coloring = entity_props.point_coloring();
+
+    ui.vertical(|ui| {
+        egui::ComboBox::from_id_source("point_color_source")
+            .selected_text(coloring.source.to_string())
+            .show_ui(ui, |ui| {
+                ui.style_mut().wrap = Some(false);
+                for source in PointColorSource::ALL {
+                    ui.selectable_value(&mut coloring.source, source, source.to_string());
+                }
+            })
+            .response
+            .on_hover_text(
+                "Color the points by height, by the Scalar logged with each point, \
+                or by when they were logged.",
+            );
+
+        if coloring.source == PointColorSource::Logged {
+            return;
+        }
+
+        egui::ComboBox::from_id_source("point_colormap")
+            .selected_text(coloring.colormap.to_string())
+            .show_ui(ui, |ui| {
+                ui.style_mut().wrap = Some(false);
+                for colormap in Colormap::ALL {
+                    ui.selectable_value(&mut coloring.colormap, colormap, colormap.to_string());
+                }
+            });
+
+        if coloring.source != PointColorSource::Time {
+            let mut auto_range = coloring.range.is_none();
+            ui.checkbox(&mut auto_range, "Auto range")
+                .on_hover_text("Use the range of the visible data.");
+            if auto_range {
+                coloring.range = None;
+            } else {
+                let [mut min, mut max] = coloring.range.map_or([0.0, 1.0], |[min, max]| {
+                    [min.into_inner(), max.into_inner()]
+                });
+                let speed = ((max - min).abs() * 0.01).max(0.001);
+                ui.horizontal(|ui| {
+                    ui.add(egui::DragValue::new(&mut min).speed(speed));
+                    ui.label("to");
+                    ui.add(egui::DragValue::new(&mut max).speed(speed));
+                });
+                if let (Ok(min), Ok(max)) = (
+                    ordered_float::NotNan::new(min),
+                    ordered_float::NotNan::new(max),
+                ) {
+                    coloring.range = Some([min, max]);
+                }
+            }
+        }
+    });
+
+    if coloring != entity_props.point_coloring() {
+        entity_props.set_point_coloring(coloring);
+    }
+}
@@ -2,7 +2,7 @@ use re_arrow_store::Timeline;
 use re_data_store::{EntityPath, EntityTree, InstancePath, TimeInt};
 
 use crate::{
-    misc::{space_info::SpaceInfoCollection, SpaceViewHighlights, TransformCache, ViewerContext},
+    misc::{space_info::SpaceInfoCollection, SpaceViewHighlights, ViewerContext},
     ui::view_category::categorize_entity_path,
 };
 
@@ -54,6 +54,17 @@ pub struct SpaceView {
 
     /// True if the user is expected to add entities themselves. False otherwise.
     pub entities_determined_by_user: bool,
+
+    /// If set, we show the data at this time instead of following the time panel,
+    /// e.g. to keep a reference frame visible during playback.
+    ///
+    /// Only applies while its timeline is selected.
+    #[serde(default)]
+    pub pinned_time: Option<(Timeline, TimeInt)>,
+
+    /// Overrides [`crate::misc::AppOptions::number_format`] in this space view.
+    #[serde(default)]
+    pub number_format: Option<crate::misc::NumberFormat>,
 }
 
 impl SpaceView {
@@ -86,9 +97,43 @@ impl SpaceView {
             view_state: ViewState::default(),
             category,
             entities_determined_by_user: false,
+            pinned_time: None,
+            number_format: None,
+        }
+    }
+
+    /// Gives all entities the default visible history for their kind of data,
+    /// see [`crate::misc::AppOptions::visible_history`].
+    pub fn apply_default_visible_history(&mut self, ctx: &ViewerContext<'_>) {
+        let entity_paths = self.data_blueprint.entity_paths().iter().cloned().collect();
+        self.set_default_visible_history(ctx, entity_paths);
+    }
+
+    fn set_default_visible_history(
+        &mut self,
+        ctx: &ViewerContext<'_>,
+        entity_paths: Vec<EntityPath>,
+    ) {
+        let defaults = &ctx.app_options.visible_history;
+        if defaults.is_empty() {
+            return;
+        }
+
+        let entity_props_map = self.data_blueprint.data_blueprints_individual();
+        for entity_path in entity_paths {
+            let mut props = entity_props_map.get(&entity_path);
+            props.visible_history = defaults.for_entity(&ctx.log_db.entity_db, &entity_path);
+            entity_props_map.set(entity_path, props);
         }
     }
 
+    /// The time we are pinned to on this timeline, if any. See [`Self::pinned_time`].
+    pub fn pinned_time_on(&self, timeline: &Timeline) -> Option<TimeInt> {
+        self.pinned_time
+            .filter(|(pinned_timeline, _)| pinned_timeline == timeline)
+            .map(|(_, time)| time)
+    }
+
     pub fn on_frame_start(
         &mut self,
         ctx: &mut ViewerContext<'_>,
@@ -100,8 +145,14 @@ impl SpaceView {
             // Add entities that have been logged since we were created
             let queries_entities =
                 default_queried_entities(ctx, &self.space_path, spaces_info, self.category);
+            let new_entities = queries_entities
+                .iter()
+                .filter(|entity_path| !self.data_blueprint.contains_entity(entity_path))
+                .cloned()
+                .collect();
             self.data_blueprint
                 .insert_entities_according_to_hierarchy(queries_entities.iter(), &self.space_path);
+            self.set_default_visible_history(ctx, new_entities);
         }
     }
 
@@ -111,7 +162,11 @@ impl SpaceView {
             ViewCategory::Text => {
                 self.view_state.state_text.selection_ui(ctx.re_ui, ui);
             }
-            ViewCategory::TimeSeries => {}
+            ViewCategory::TimeSeries => {
+                self.view_state
+                    .state_time_series
+                    .selection_ui(ctx.re_ui, ui);
+            }
             ViewCategory::BarChart => {}
             ViewCategory::Spatial => {
                 self.view_state.state_spatial.selection_ui(
@@ -150,6 +205,12 @@ impl SpaceView {
             entity_props_map: self.data_blueprint.data_blueprints_projected(),
         };
 
+        // Everything shown in here reads the format from the app options.
+        let global_number_format = ctx.app_options.number_format;
+        if let Some(number_format) = self.number_format {
+            ctx.app_options.number_format = number_format;
+        }
+
         match self.category {
             ViewCategory::Text => {
                 let mut scene = view_text::SceneText::default();
@@ -159,7 +220,7 @@ impl SpaceView {
 
             ViewCategory::TimeSeries => {
                 let mut scene = view_time_series::SceneTimeSeries::default();
-                scene.load(ctx, &query);
+                scene.load(ctx, &query, &self.view_state.state_time_series);
                 self.view_state.ui_time_series(ctx, ui, &scene);
             }
 
@@ -170,8 +231,9 @@ impl SpaceView {
             }
 
             ViewCategory::Spatial => {
-                let transforms = TransformCache::determine_transforms(
-                    &ctx.log_db.entity_db,
+                let transforms = ctx.cache.transforms.get_or_determine(
+                    self.id,
+                    ctx.log_db,
                     &ctx.rec_cfg.time_ctrl,
                     &self.space_path,
                     self.data_blueprint.data_blueprints_projected(),
@@ -188,6 +250,8 @@ impl SpaceView {
                 self.view_state.ui_tensor(ctx, ui, &scene);
             }
         };
+
+        ctx.app_options.number_format = global_number_format;
     }
 
     /// Removes a subtree of entities from the blueprint tree.
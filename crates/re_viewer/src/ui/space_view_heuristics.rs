@@ -120,7 +120,12 @@ pub fn default_created_space_views(
     spaces_info: &SpaceInfoCollection,
 ) -> Vec<SpaceView> {
     let candidates = all_possible_space_views(ctx, spaces_info);
-    default_created_space_views_from_candidates(&ctx.log_db.entity_db, candidates)
+    let mut space_views =
+        default_created_space_views_from_candidates(&ctx.log_db.entity_db, candidates);
+    for space_view in &mut space_views {
+        space_view.apply_default_visible_history(ctx);
+    }
+    space_views
 }
 
 fn default_created_space_views_from_candidates(
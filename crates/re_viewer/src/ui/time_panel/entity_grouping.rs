@@ -0,0 +1,136 @@
+use std::collections::BTreeMap;
+
+use re_arrow_store::{LatestAtQuery, TimeInt};
+use re_data_store::{EntityTree, LogDb};
+use re_log_types::{component_types::Group, msg_bundle::Component as _, EntityPath, Timeline};
+use re_query::query_entity_with_primary;
+
+use crate::ui::view_category::categorize_entity_path;
+
+/// How the entities are arranged in the streams tree of the time panel.
+///
+/// Deep hierarchies from large systems can be hard to browse by path alone.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Hash, serde::Deserialize, serde::Serialize,
+)]
+pub enum EntityGrouping {
+    /// The entity path hierarchy.
+    #[default]
+    Hierarchy,
+
+    /// The kind of data, e.g. time series or images.
+    DataType,
+
+    /// The application that logged the data.
+    Application,
+
+    /// The declared [`Group`] component.
+    Group,
+}
+
+impl EntityGrouping {
+    pub const ALL: [Self; 4] = [
+        Self::Hierarchy,
+        Self::DataType,
+        Self::Application,
+        Self::Group,
+    ];
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::Hierarchy => "The paths used during logging",
+            Self::DataType => {
+                "What kind of view the data can be shown in, e.g. time series or spatial"
+            }
+            Self::Application => "The application that logged the data",
+            Self::Group => "The group logged for the entity, e.g. with `rerun.log_group`",
+        }
+    }
+}
+
+impl std::fmt::Display for EntityGrouping {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Hierarchy => "Hierarchy",
+            Self::DataType => "Data type",
+            Self::Application => "Application",
+            Self::Group => "Group",
+        })
+    }
+}
+
+/// The entities that have data, grouped by `grouping`.
+///
+/// An entity can be in several groups, e.g. if it was logged to by several applications.
+/// Entities that don't belong to any group end up in the `"Other"` group.
+///
+/// Not used for [`EntityGrouping::Hierarchy`], which is shown as the tree itself.
+pub fn group_entities(
+    log_db: &LogDb,
+    timeline: Timeline,
+    grouping: EntityGrouping,
+) -> BTreeMap<String, Vec<EntityPath>> {
+    crate::profile_function!();
+
+    let mut entity_paths = vec![];
+    collect_entities(&log_db.entity_db.tree, &mut entity_paths);
+
+    let mut groups: BTreeMap<String, Vec<EntityPath>> = Default::default();
+    for entity_path in entity_paths {
+        let names = match grouping {
+            EntityGrouping::Hierarchy => vec![],
+            EntityGrouping::DataType => categorize_entity_path(timeline, log_db, &entity_path)
+                .iter()
+                .map(|category| category.to_string())
+                .collect(),
+            EntityGrouping::Application => log_db
+                .entity_db
+                .applications(&entity_path)
+                .map(|applications| applications.iter().map(ToString::to_string).collect())
+                .unwrap_or_default(),
+            EntityGrouping::Group => declared_group(log_db, timeline, &entity_path)
+                .into_iter()
+                .collect(),
+        };
+
+        if names.is_empty() {
+            groups
+                .entry("Other".to_owned())
+                .or_default()
+                .push(entity_path);
+        } else {
+            for name in names {
+                groups.entry(name).or_default().push(entity_path.clone());
+            }
+        }
+    }
+    groups
+}
+
+/// All entities in the tree that have components of their own.
+fn collect_entities(tree: &EntityTree, entity_paths: &mut Vec<EntityPath>) {
+    if !tree.components.is_empty() {
+        entity_paths.push(tree.path.clone());
+    }
+    for child in tree.children.values() {
+        collect_entities(child, entity_paths);
+    }
+}
+
+fn declared_group(log_db: &LogDb, timeline: Timeline, entity_path: &EntityPath) -> Option<String> {
+    let has_group = log_db
+        .entity_db
+        .tree
+        .subtree(entity_path)
+        .map_or(false, |tree| tree.components.contains_key(&Group::name()));
+    if !has_group {
+        return None; // No need to query the store.
+    }
+
+    let query = LatestAtQuery::new(timeline, TimeInt::MAX);
+    let entity_view =
+        query_entity_with_primary::<Group>(&log_db.entity_db.data_store, &query, entity_path, &[])
+            .ok()?;
+    let group = entity_view.iter_primary().ok()?.flatten().next()?;
+    Some(group.0).filter(|group| !group.trim().is_empty())
+}
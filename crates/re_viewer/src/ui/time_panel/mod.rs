@@ -1,8 +1,10 @@
 mod ball_scatterer;
+mod entity_grouping;
 mod paint_ticks;
 mod time_axis;
 mod time_ranges_ui;
 mod time_selection_ui;
+mod trace_spans_ui;
 
 use std::{collections::BTreeMap, ops::RangeInclusive};
 
@@ -12,13 +14,16 @@ use egui::{
 };
 use itertools::Itertools;
 
-use re_data_store::{EntityTree, InstancePath};
-use re_log_types::{ComponentPath, EntityPathPart, TimeInt, TimeRange, TimeReal};
+use re_data_store::{EntityPath, EntityTree, InstancePath};
+use re_log_types::{
+    ComponentPath, EntityPathPart, TimeInt, TimeRange, TimeReal, TimeType, Timeline,
+};
 
-use crate::{Item, TimeControl, TimeView, ViewerContext};
+use crate::{misc::alerts::FiredAlert, Item, TimeControl, TimeView, ViewerContext};
 
 use super::{data_ui::DataUi, selection_panel::what_is_selected_ui, Blueprint};
 
+use entity_grouping::EntityGrouping;
 use time_axis::TimelineAxis;
 use time_ranges_ui::TimeRangesUi;
 
@@ -38,6 +43,9 @@ pub(crate) struct TimePanel {
     /// The time axis view, regenerated each frame.
     #[serde(skip)]
     time_ranges_ui: TimeRangesUi,
+
+    /// How the entities in the streams tree are arranged.
+    grouping: EntityGrouping,
 }
 
 impl Default for TimePanel {
@@ -46,6 +54,7 @@ impl Default for TimePanel {
             prev_col_width: 400.0,
             next_col_right: 0.0,
             time_ranges_ui: Default::default(),
+            grouping: Default::default(),
         }
     }
 }
@@ -209,17 +218,19 @@ impl TimePanel {
             let top = ui.min_rect().bottom();
 
             let size = egui::vec2(self.prev_col_width, 28.0);
-            ui.allocate_ui_with_layout(size, egui::Layout::top_down(egui::Align::LEFT), |ui| {
-                ui.set_min_size(size);
-                ui.style_mut().wrap = Some(false);
-                ui.add_space(4.0); // hack to vertically center the text
-                ui.strong("Streams");
-            })
-            .response
-            .on_hover_text(
-                "A hierarchial view of the paths used during logging.\n\
+            ui.allocate_ui_with_layout(
+                size,
+                egui::Layout::left_to_right(egui::Align::Center),
+                |ui| {
+                    ui.set_min_size(size);
+                    ui.style_mut().wrap = Some(false);
+                    ui.strong("Streams").on_hover_text(
+                        "A hierarchial view of the paths used during logging.\n\
                         \n\
                         On the right you can see when there was a log event for a stream.",
+                    );
+                    self.grouping_ui(ui);
+                },
             );
 
             let bottom = ui.min_rect().bottom();
@@ -250,6 +261,7 @@ impl TimePanel {
             &time_area_painter,
             timeline_rect.top()..=timeline_rect.bottom(),
             ctx.rec_cfg.time_ctrl.time_type(),
+            &ctx.app_options.time_format,
         );
         paint_time_ranges_gaps(
             &self.time_ranges_ui,
@@ -323,6 +335,15 @@ impl TimePanel {
                 .draw_shadow_line(ui, rect, egui::Direction::LeftToRight);
         }
 
+        paint_alert_markers(
+            &self.time_ranges_ui,
+            &ctx.rec_cfg.alerts.fired,
+            ctx.rec_cfg.time_ctrl.timeline(),
+            ui,
+            &time_area_painter,
+            &timeline_rect,
+        );
+
         // Put time-marker on top and last, so that you can always drag it
         time_marker_ui(
             &self.time_ranges_ui,
@@ -338,6 +359,20 @@ impl TimePanel {
         self.prev_col_width = self.next_col_right - ui.min_rect().left();
     }
 
+    fn grouping_ui(&mut self, ui: &mut egui::Ui) {
+        egui::ComboBox::from_id_source("entity_grouping")
+            .selected_text(format!("By {}", self.grouping.to_string().to_lowercase()))
+            .show_ui(ui, |ui| {
+                ui.style_mut().wrap = Some(false);
+                for grouping in EntityGrouping::ALL {
+                    ui.selectable_value(&mut self.grouping, grouping, grouping.to_string())
+                        .on_hover_text(grouping.description());
+                }
+            })
+            .response
+            .on_hover_text("How to arrange the streams");
+    }
+
     fn tree_ui(
         &mut self,
         ctx: &mut ViewerContext<'_>,
@@ -346,14 +381,55 @@ impl TimePanel {
         time_area_painter: &egui::Painter,
         ui: &mut egui::Ui,
     ) {
-        self.show_children(
-            ctx,
-            blueprint,
-            time_area_response,
-            time_area_painter,
-            &ctx.log_db.entity_db.tree,
-            ui,
-        );
+        if self.grouping == EntityGrouping::Hierarchy {
+            self.show_children(
+                ctx,
+                blueprint,
+                time_area_response,
+                time_area_painter,
+                &ctx.log_db.entity_db.tree,
+                ui,
+            );
+        } else {
+            self.show_groups(ctx, blueprint, time_area_response, time_area_painter, ui);
+        }
+
+        self.show_trace_spans(ctx, time_area_painter, ui);
+    }
+
+    /// Spans from e.g. OpenTelemetry, shown as a flamegraph below the entities.
+    fn show_trace_spans(
+        &mut self,
+        ctx: &ViewerContext<'_>,
+        time_area_painter: &egui::Painter,
+        ui: &mut egui::Ui,
+    ) {
+        let trace_spans = &ctx.log_db.trace_spans;
+        if trace_spans.is_empty() || ctx.rec_cfg.time_ctrl.time_type() != TimeType::Time {
+            return; // Spans are in wall-clock time, so can't be shown against e.g. frame numbers.
+        }
+
+        let response = ui.label("Traces").on_hover_text(format!(
+            "{} trace spans, e.g. received over OTLP.\n\
+            Shown as a flamegraph, so that you can correlate them with the data above.",
+            re_format::format_number(trace_spans.len())
+        ));
+        self.next_col_right = self.next_col_right.max(response.rect.right());
+
+        let (_, rect) = ui.allocate_space(egui::vec2(
+            0.0,
+            trace_spans_ui::flamegraph_height(trace_spans),
+        ));
+        let full_width_rect = Rect::from_x_y_ranges(ui.max_rect().x_range(), rect.y_range());
+        if ui.is_rect_visible(full_width_rect) {
+            trace_spans_ui::paint_flamegraph(
+                trace_spans,
+                &self.time_ranges_ui,
+                ui,
+                time_area_painter,
+                rect.top(),
+            );
+        }
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -375,11 +451,15 @@ impl TimePanel {
             return; // ignore entities that have no data for the current timeline, nor any timeless data.
         }
 
-        // The last part of the the path component
+        // The display name, or else the last part of the the path component
+        let name = ctx
+            .entity_display_names
+            .get(&tree.path)
+            .map_or_else(|| last_path_part.to_string(), ToOwned::to_owned);
         let text = if tree.is_leaf() {
-            last_path_part.to_string()
+            name
         } else {
-            format!("{last_path_part}/") // show we have children with a /
+            format!("{name}/") // show we have children with a /
         };
 
         let collapsing_header_id = ui.make_persistent_id(&tree.path);
@@ -404,9 +484,157 @@ impl TimePanel {
                 );
             });
 
-        let is_closed = body_returned.is_none();
-        let response = custom_header_response.response;
-        let response_rect = response.rect;
+        let empty = BTreeMap::default();
+        let num_messages_at_time = tree
+            .prefix_times
+            .get(ctx.rec_cfg.time_ctrl.timeline())
+            .unwrap_or(&empty);
+
+        self.show_entity_row_data(
+            ctx,
+            blueprint,
+            time_area_response,
+            time_area_painter,
+            &tree.path,
+            custom_header_response.response.rect,
+            body_returned.is_none(),
+            tree.num_timeless_messages(),
+            num_messages_at_time,
+            ui,
+        );
+    }
+
+    /// The entities grouped by something else than their path, see [`EntityGrouping`].
+    fn show_groups(
+        &mut self,
+        ctx: &mut ViewerContext<'_>,
+        blueprint: &mut Blueprint,
+        time_area_response: &egui::Response,
+        time_area_painter: &egui::Painter,
+        ui: &mut egui::Ui,
+    ) {
+        let log_db = ctx.log_db;
+        let groups = entity_grouping::group_entities(
+            log_db,
+            *ctx.rec_cfg.time_ctrl.timeline(),
+            self.grouping,
+        );
+
+        for (name, entity_paths) in groups {
+            let collapsing_header_id =
+                ui.make_persistent_id(("entity_group", self.grouping, &name));
+            let (_collapsing_button_response, header_response, _body_returned) =
+                egui::collapsing_header::CollapsingState::load_with_default_open(
+                    ui.ctx(),
+                    collapsing_header_id,
+                    true,
+                )
+                .show_header(ui, |ui| {
+                    ui.strong(&name).on_hover_text(format!(
+                        "{} {}",
+                        entity_paths.len(),
+                        if entity_paths.len() == 1 {
+                            "entity"
+                        } else {
+                            "entities"
+                        }
+                    ))
+                })
+                .body(|ui| {
+                    for entity_path in &entity_paths {
+                        if let Some(tree) = log_db.entity_db.tree.subtree(entity_path) {
+                            self.show_grouped_entity(
+                                ctx,
+                                blueprint,
+                                time_area_response,
+                                time_area_painter,
+                                tree,
+                                ui,
+                            );
+                        }
+                    }
+                });
+            self.next_col_right = self
+                .next_col_right
+                .max(header_response.response.rect.right());
+        }
+    }
+
+    /// An entity in one of the [`Self::show_groups`]: its full path, with only its own components below it.
+    fn show_grouped_entity(
+        &mut self,
+        ctx: &mut ViewerContext<'_>,
+        blueprint: &mut Blueprint,
+        time_area_response: &egui::Response,
+        time_area_painter: &egui::Painter,
+        tree: &EntityTree,
+        ui: &mut egui::Ui,
+    ) {
+        let timeline = *ctx.rec_cfg.time_ctrl.timeline();
+
+        let mut num_timeless_messages = 0;
+        let mut num_messages_at_time = BTreeMap::<TimeInt, usize>::default();
+        for data in tree.components.values() {
+            num_timeless_messages += data.num_timeless_messages();
+            for (&time, &num_messages) in data.times.get(&timeline).into_iter().flatten() {
+                *num_messages_at_time.entry(time).or_default() += num_messages;
+            }
+        }
+        if num_messages_at_time.is_empty() && num_timeless_messages == 0 {
+            return; // ignore entities that have no data for the current timeline, nor any timeless data.
+        }
+
+        let text = ctx.entity_display_names.name_or_path(&tree.path);
+        let collapsing_header_id = ui.make_persistent_id(("grouped_entity", &tree.path));
+        let (_collapsing_button_response, custom_header_response, body_returned) =
+            egui::collapsing_header::CollapsingState::load_with_default_open(
+                ui.ctx(),
+                collapsing_header_id,
+                false,
+            )
+            .show_header(ui, |ui| {
+                ctx.entity_path_button_to(ui, None, &tree.path, text)
+            })
+            .body(|ui| {
+                self.show_components(
+                    ctx,
+                    blueprint,
+                    time_area_response,
+                    time_area_painter,
+                    tree,
+                    ui,
+                );
+            });
+
+        self.show_entity_row_data(
+            ctx,
+            blueprint,
+            time_area_response,
+            time_area_painter,
+            &tree.path,
+            custom_header_response.response.rect,
+            body_returned.is_none(),
+            num_timeless_messages,
+            &num_messages_at_time,
+            ui,
+        );
+    }
+
+    /// Paint the guide line of the row of an entity, and if it is collapsed, its data in the time area.
+    #[allow(clippy::too_many_arguments)]
+    fn show_entity_row_data(
+        &mut self,
+        ctx: &mut ViewerContext<'_>,
+        blueprint: &mut Blueprint,
+        time_area_response: &egui::Response,
+        time_area_painter: &egui::Painter,
+        entity_path: &EntityPath,
+        response_rect: Rect,
+        is_closed: bool,
+        num_timeless_messages: usize,
+        num_messages_at_time: &BTreeMap<TimeInt, usize>,
+        ui: &mut egui::Ui,
+    ) {
         self.next_col_right = self.next_col_right.max(response_rect.right());
 
         let full_width_rect = Rect::from_x_y_ranges(
@@ -430,23 +658,17 @@ impl TimePanel {
         // show the data in the time area:
 
         if is_visible && is_closed {
-            let empty = BTreeMap::default();
-            let num_messages_at_time = tree
-                .prefix_times
-                .get(ctx.rec_cfg.time_ctrl.timeline())
-                .unwrap_or(&empty);
-
             show_data_over_time(
                 ctx,
                 blueprint,
                 time_area_response,
                 time_area_painter,
                 ui,
-                tree.num_timeless_messages(),
+                num_timeless_messages,
                 num_messages_at_time,
                 full_width_rect,
                 &self.time_ranges_ui,
-                Item::InstancePath(None, InstancePath::entity_splat(tree.path.clone())),
+                Item::InstancePath(None, InstancePath::entity_splat(entity_path.clone())),
             );
         }
     }
@@ -472,6 +694,26 @@ impl TimePanel {
             );
         }
 
+        self.show_components(
+            ctx,
+            blueprint,
+            time_area_response,
+            time_area_painter,
+            tree,
+            ui,
+        );
+    }
+
+    /// The components of the entity itself, if any.
+    fn show_components(
+        &mut self,
+        ctx: &mut ViewerContext<'_>,
+        blueprint: &mut Blueprint,
+        time_area_response: &egui::Response,
+        time_area_painter: &egui::Painter,
+        tree: &EntityTree,
+        ui: &mut egui::Ui,
+    ) {
         // If this is an entity:
         if !tree.components.is_empty() {
             let indent = ui.spacing().indent;
@@ -619,7 +861,7 @@ fn current_time_ui(ctx: &ViewerContext<'_>, ui: &mut egui::Ui) {
         let timeline = ctx.rec_cfg.time_ctrl.timeline();
         if is_time_safe_to_show(ctx.log_db, timeline, time_int.into()) {
             let time_type = ctx.rec_cfg.time_ctrl.time_type();
-            ui.monospace(time_type.format(time_int));
+            ui.monospace(time_type.format_with(time_int, &ctx.app_options.time_format));
         }
     }
 }
@@ -1109,6 +1351,63 @@ fn interact_with_streams_rect(
 }
 
 /// A vertical line that shows the current time.
+/// Mark when alerts fired with a line through all the streams,
+/// and show what fired when hovering the mark in the timeline.
+fn paint_alert_markers(
+    time_ranges_ui: &TimeRangesUi,
+    fired: &[FiredAlert],
+    timeline: &Timeline,
+    ui: &egui::Ui,
+    time_area_painter: &egui::Painter,
+    timeline_rect: &Rect,
+) {
+    if fired.is_empty() {
+        return;
+    }
+
+    let color = ui.visuals().error_fg_color;
+    let pointer_pos = ui.input(|i| i.pointer.hover_pos());
+    let w = 8.0;
+
+    let mut hovered = Vec::new();
+    for alert in fired {
+        let x = match alert
+            .time_point
+            .get(timeline)
+            .and_then(|time| time_ranges_ui.x_from_time_f32(TimeReal::from(*time)))
+        {
+            Some(x) => x,
+            None => continue,
+        };
+
+        time_area_painter.vline(
+            x,
+            timeline_rect.top()..=ui.max_rect().bottom(),
+            egui::Stroke::new(1.0, color.linear_multiply(0.5)),
+        );
+        let y = timeline_rect.bottom();
+        time_area_painter.add(Shape::convex_polygon(
+            vec![pos2(x, y - w), pos2(x + 0.5 * w, y), pos2(x - 0.5 * w, y)],
+            color,
+            egui::Stroke::NONE,
+        ));
+
+        if let Some(pointer_pos) = pointer_pos {
+            if timeline_rect.contains(pointer_pos) && (pointer_pos.x - x).abs() < 0.5 * w {
+                hovered.push(alert);
+            }
+        }
+    }
+
+    if !hovered.is_empty() {
+        show_tooltip_at_pointer(ui.ctx(), Id::new("alert_marker_tooltip"), |ui| {
+            for alert in hovered {
+                ui.label(&alert.text);
+            }
+        });
+    }
+}
+
 fn time_marker_ui(
     time_ranges_ui: &TimeRangesUi,
     time_ctrl: &mut TimeControl,
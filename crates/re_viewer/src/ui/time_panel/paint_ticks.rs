@@ -2,7 +2,7 @@ use std::ops::RangeInclusive;
 
 use egui::{lerp, pos2, remap_clamp, Align2, Color32, Rect, Rgba, Shape, Stroke};
 
-use re_log_types::{Time, TimeRangeF, TimeReal, TimeType};
+use re_log_types::{Time, TimeFormatOptions, TimeRangeF, TimeReal, TimeType};
 
 use crate::misc::format_time::next_grid_tick_magnitude_ns;
 
@@ -14,6 +14,7 @@ pub fn paint_time_ranges_and_ticks(
     time_area_painter: &egui::Painter,
     line_y_range: RangeInclusive<f32>,
     time_type: TimeType,
+    time_format: &TimeFormatOptions,
 ) {
     let clip_rect = ui.clip_rect();
     let clip_left = clip_rect.left() as f64;
@@ -47,7 +48,13 @@ pub fn paint_time_ranges_and_ticks(
         let rect = Rect::from_x_y_ranges(x_range, line_y_range.clone());
         time_area_painter
             .with_clip_rect(rect)
-            .extend(paint_time_range_ticks(ui, &rect, time_type, &time_range));
+            .extend(paint_time_range_ticks(
+                ui,
+                &rect,
+                time_type,
+                &time_range,
+                time_format,
+            ));
     }
 }
 
@@ -56,6 +63,7 @@ fn paint_time_range_ticks(
     rect: &Rect,
     time_type: TimeType,
     time_range: &TimeRangeF,
+    time_format: &TimeFormatOptions,
 ) -> Vec<Shape> {
     let font_id = egui::TextStyle::Small.resolve(ui.style());
 
@@ -69,7 +77,12 @@ fn paint_time_range_ticks(
                 &ui.clip_rect(),
                 time_range, // ns
                 next_grid_tick_magnitude_ns,
-                |ns| crate::misc::format_time::format_time_compact(Time::from_ns_since_epoch(ns)),
+                &|ns| {
+                    crate::misc::format_time::format_time_compact(
+                        Time::from_ns_since_epoch(ns),
+                        time_format,
+                    )
+                },
             )
         }
         TimeType::Sequence => {
@@ -84,7 +97,7 @@ fn paint_time_range_ticks(
                 &ui.clip_rect(),
                 time_range,
                 next_power_of_10,
-                |seq| format!("#{seq}"),
+                &|seq| format!("#{seq}"),
             )
         }
     }
@@ -99,7 +112,7 @@ fn paint_ticks(
     clip_rect: &Rect,
     time_range: &TimeRangeF,
     next_time_step: fn(i64) -> i64,
-    format_tick: fn(i64) -> String,
+    format_tick: &dyn Fn(i64) -> String,
 ) -> Vec<egui::Shape> {
     crate::profile_function!();
 
@@ -0,0 +1,121 @@
+use egui::{pos2, util::hash, Rect};
+
+use re_data_store::TraceSpans;
+use re_log_types::{TimeInt, TimeReal, TraceSpan};
+
+use crate::ui::annotations::auto_color;
+
+use super::time_ranges_ui::TimeRangesUi;
+
+/// Height of each nesting level of the flamegraph.
+pub const ROW_HEIGHT: f32 = 16.0;
+
+/// How much vertical space the flamegraph needs.
+pub fn flamegraph_height(trace_spans: &TraceSpans) -> f32 {
+    (trace_spans.max_depth() + 1) as f32 * ROW_HEIGHT
+}
+
+/// Paint the trace spans as a flamegraph, aligned with the time axis, starting at `top`.
+///
+/// Shows a tooltip for the hovered span.
+pub fn paint_flamegraph(
+    trace_spans: &TraceSpans,
+    time_ranges_ui: &TimeRangesUi,
+    ui: &egui::Ui,
+    time_area_painter: &egui::Painter,
+    top: f32,
+) {
+    crate::profile_function!();
+
+    let clip_rect = time_area_painter.clip_rect();
+    let pointer_pos = ui.input(|i| i.pointer.hover_pos());
+    let font_id = egui::TextStyle::Small.resolve(ui.style());
+    let text_color = egui::Color32::WHITE;
+
+    let mut hovered_span = None;
+
+    for (span, depth) in trace_spans.iter() {
+        let x_from_time =
+            |time| time_ranges_ui.x_from_time_f32(TimeReal::from(TimeInt::from(time)));
+        let (min_x, max_x) = match (x_from_time(span.start), x_from_time(span.end)) {
+            (Some(min_x), Some(max_x)) => (min_x, max_x),
+            _ => continue,
+        };
+        let max_x = max_x.max(min_x + 1.0); // Always show something, even for very short spans.
+
+        let min_y = top + depth as f32 * ROW_HEIGHT;
+        let rect = Rect::from_x_y_ranges(min_x..=max_x, min_y..=(min_y + ROW_HEIGHT - 1.0));
+        if !clip_rect.intersects(rect) {
+            continue;
+        }
+
+        let is_hovered = pointer_pos.map_or(false, |pointer_pos| rect.contains(pointer_pos));
+        if is_hovered {
+            hovered_span = Some(span);
+        }
+
+        let mut color = auto_color(hash((&span.service_name, &span.name)) as _);
+        if is_hovered {
+            color = color.linear_multiply(1.5);
+        }
+        time_area_painter.rect_filled(rect, 2.0, color);
+
+        let visible_rect = rect.intersect(clip_rect);
+        if visible_rect.width() > 24.0 {
+            let galley =
+                ui.fonts(|f| f.layout_no_wrap(span.name.clone(), font_id.clone(), text_color));
+            let text_pos = pos2(
+                visible_rect.left() + 4.0,
+                rect.center().y - 0.5 * galley.size().y,
+            );
+            // Keep the name within the span:
+            time_area_painter
+                .with_clip_rect(visible_rect)
+                .galley(text_pos, galley);
+        }
+    }
+
+    if let Some(span) = hovered_span {
+        egui::show_tooltip_at_pointer(ui.ctx(), egui::Id::new("trace_span_tooltip"), |ui| {
+            span_tooltip_ui(ui, span);
+        });
+    }
+}
+
+fn span_tooltip_ui(ui: &mut egui::Ui, span: &TraceSpan) {
+    let TraceSpan {
+        trace_id,
+        span_id,
+        parent_span_id: _,
+        name,
+        service_name,
+        start,
+        end: _,
+    } = span;
+
+    egui::Grid::new("span").num_columns(2).show(ui, |ui| {
+        ui.label("Name");
+        ui.strong(name);
+        ui.end_row();
+
+        ui.label("Service");
+        ui.label(service_name);
+        ui.end_row();
+
+        ui.label("Start");
+        ui.monospace(start.format());
+        ui.end_row();
+
+        ui.label("Duration");
+        ui.monospace(span.duration().to_string());
+        ui.end_row();
+
+        ui.label("Trace id");
+        ui.monospace(format!("{trace_id:032x}"));
+        ui.end_row();
+
+        ui.label("Span id");
+        ui.monospace(format!("{span_id:016x}"));
+        ui.end_row();
+    });
+}
@@ -21,7 +21,7 @@ pub(crate) const HELP_TEXT: &str = "\
 pub struct BarChartState;
 
 pub(crate) fn view_bar_chart(
-    _ctx: &mut ViewerContext<'_>,
+    ctx: &mut ViewerContext<'_>,
     ui: &mut egui::Ui,
     _state: &mut BarChartState,
     scene: &SceneBarChart,
@@ -35,6 +35,7 @@ pub(crate) fn view_bar_chart(
             fn create_bar_chart<N: Into<f64>>(
                 ent_path: &EntityPath,
                 instance_key: &InstanceKey,
+                name: &str,
                 values: impl Iterator<Item = N>,
             ) -> BarChart {
                 let color = auto_color(hash((ent_path, instance_key)) as _);
@@ -45,51 +46,66 @@ pub(crate) fn view_bar_chart(
                         .map(|(i, value)| {
                             Bar::new(i as f64 + 0.5, value.into())
                                 .width(0.95)
-                                .name(format!("{ent_path}[#{instance_key}] #{i}"))
+                                .name(format!("{name}[#{instance_key}] #{i}"))
                                 .fill(fill)
                                 .stroke(egui::Stroke::NONE)
                         })
                         .collect(),
                 )
-                .name(format!("{ent_path}[#{instance_key}]"))
+                .name(format!("{name}[#{instance_key}]"))
                 .color(color)
             }
 
             for ((ent_path, instance_key), tensor) in &scene.charts {
+                let name = &ctx.entity_display_names.name_or_path(ent_path);
                 let chart = match &tensor.data {
                     component_types::TensorData::U8(data) => {
-                        create_bar_chart(ent_path, instance_key, data.iter().copied())
+                        create_bar_chart(ent_path, instance_key, name, data.iter().copied())
                     }
                     component_types::TensorData::U16(data) => {
-                        create_bar_chart(ent_path, instance_key, data.iter().copied())
+                        create_bar_chart(ent_path, instance_key, name, data.iter().copied())
                     }
                     component_types::TensorData::U32(data) => {
-                        create_bar_chart(ent_path, instance_key, data.iter().copied())
+                        create_bar_chart(ent_path, instance_key, name, data.iter().copied())
                     }
                     component_types::TensorData::U64(data) => create_bar_chart(
                         ent_path,
                         instance_key,
+                        name,
                         data.iter().copied().map(|v| v as f64),
                     ),
                     component_types::TensorData::I8(data) => {
-                        create_bar_chart(ent_path, instance_key, data.iter().copied())
+                        create_bar_chart(ent_path, instance_key, name, data.iter().copied())
                     }
                     component_types::TensorData::I16(data) => {
-                        create_bar_chart(ent_path, instance_key, data.iter().copied())
+                        create_bar_chart(ent_path, instance_key, name, data.iter().copied())
                     }
                     component_types::TensorData::I32(data) => {
-                        create_bar_chart(ent_path, instance_key, data.iter().copied())
+                        create_bar_chart(ent_path, instance_key, name, data.iter().copied())
                     }
                     component_types::TensorData::I64(data) => create_bar_chart(
                         ent_path,
                         instance_key,
+                        name,
                         data.iter().copied().map(|v| v as f64),
                     ),
+                    component_types::TensorData::F16(data) => create_bar_chart(
+                        ent_path,
+                        instance_key,
+                        name,
+                        data.iter().map(|&bits| half::f16::from_bits(bits)),
+                    ),
+                    component_types::TensorData::BF16(data) => create_bar_chart(
+                        ent_path,
+                        instance_key,
+                        name,
+                        data.iter().map(|&bits| half::bf16::from_bits(bits)),
+                    ),
                     component_types::TensorData::F32(data) => {
-                        create_bar_chart(ent_path, instance_key, data.iter().copied())
+                        create_bar_chart(ent_path, instance_key, name, data.iter().copied())
                     }
                     component_types::TensorData::F64(data) => {
-                        create_bar_chart(ent_path, instance_key, data.iter().copied())
+                        create_bar_chart(ent_path, instance_key, name, data.iter().copied())
                     }
                     component_types::TensorData::JPEG(_) => {
                         warn_once!(
@@ -57,6 +57,19 @@ impl std::fmt::Display for ViewCategory {
     }
 }
 
+impl From<re_log_types::SpaceViewKind> for ViewCategory {
+    fn from(kind: re_log_types::SpaceViewKind) -> Self {
+        use re_log_types::SpaceViewKind;
+        match kind {
+            SpaceViewKind::Spatial => ViewCategory::Spatial,
+            SpaceViewKind::TimeSeries => ViewCategory::TimeSeries,
+            SpaceViewKind::BarChart => ViewCategory::BarChart,
+            SpaceViewKind::Text => ViewCategory::Text,
+            SpaceViewKind::Tensor => ViewCategory::Tensor,
+        }
+    }
+}
+
 pub type ViewCategorySet = enumset::EnumSet<ViewCategory>;
 
 // TODO(cmc): these `categorize_*` functions below are pretty dangerous: make sure you've covered
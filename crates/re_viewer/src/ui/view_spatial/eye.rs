@@ -1,6 +1,6 @@
 use egui::{lerp, NumExt as _, Rect};
 use glam::Affine3A;
-use macaw::{vec3, IsoTransform, Mat4, Quat, Vec3};
+use macaw::{vec3, BoundingBox, IsoTransform, Mat4, Quat, Vec3};
 
 use super::SpaceCamera3D;
 
@@ -15,6 +15,34 @@ pub struct Eye {
 
     /// If no angle is present, this is an orthographic camera.
     pub fov_y: Option<f32>,
+
+    /// How much an orthographic eye in a 3D view sees.
+    ///
+    /// `None` for perspective eyes and for 2D views, where one world unit is one ui point.
+    #[serde(default)]
+    pub orthographic: Option<OrthographicExtent>,
+
+    /// Only used by perspective eyes. Orthographic eyes are moved instead, see [`Self::clip`].
+    #[serde(default)]
+    pub clip_range: Option<ClipRange>,
+}
+
+/// The part of the world an orthographic eye in a 3D view sees.
+#[derive(Clone, Copy, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct OrthographicExtent {
+    /// Height of the view, in world units.
+    pub vertical_world_size: f32,
+
+    /// Distance from the eye to the far plane. The near plane is at the eye.
+    pub far: f32,
+}
+
+/// Hides everything closer to or further from the eye than these distances,
+/// e.g. to look inside a dense reconstruction.
+#[derive(Clone, Copy, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct ClipRange {
+    pub near: f32,
+    pub far: f32,
 }
 
 impl Eye {
@@ -29,12 +57,18 @@ impl Eye {
         Some(Self {
             world_from_view: space_cameras.world_from_rub_view()?,
             fov_y: Some(fov_y),
+            orthographic: None,
+            clip_range: None,
         })
     }
 
     pub fn near(&self) -> f32 {
         if self.is_perspective() {
-            0.01 // TODO(emilk)
+            // TODO(emilk): better default near plane
+            self.clip_range
+                .map_or(0.01, |clip| clip.near.at_least(0.01))
+        } else if self.orthographic.is_some() {
+            0.0
         } else {
             -1000.0 // TODO(andreas)
         }
@@ -42,7 +76,9 @@ impl Eye {
 
     pub fn far(&self) -> f32 {
         if self.is_perspective() {
-            f32::INFINITY
+            self.clip_range.map_or(f32::INFINITY, |clip| clip.far)
+        } else if let Some(extent) = self.orthographic {
+            extent.far
         } else {
             1000.0
         }
@@ -53,6 +89,17 @@ impl Eye {
 
         let projection = if let Some(fov_y) = self.fov_y {
             Mat4::perspective_infinite_rh(fov_y, aspect_ratio, self.near())
+        } else if let Some(extent) = self.orthographic {
+            let half_height = 0.5 * extent.vertical_world_size;
+            let half_width = half_height * aspect_ratio;
+            Mat4::orthographic_rh(
+                -half_width,
+                half_width,
+                -half_height,
+                half_height,
+                self.near(),
+                self.far(),
+            )
         } else {
             Mat4::orthographic_rh(
                 rect.left(),
@@ -74,6 +121,37 @@ impl Eye {
         self.fov_y.is_some()
     }
 
+    /// Hide everything outside of the given distances from the eye.
+    pub fn clip(&mut self, clip_range: ClipRange) {
+        if self.is_perspective() {
+            self.clip_range = Some(clip_range);
+        } else if let Some(extent) = self.orthographic {
+            // The near plane of an orthographic eye is at the eye, but moving the eye forward
+            // doesn't change what it shows.
+            let near = clip_range.near.at_least(0.0);
+            self.world_from_view = IsoTransform::from_rotation_translation(
+                self.world_from_view.rotation(),
+                self.pos_in_world() + near * self.forward_in_world(),
+            );
+            self.orthographic = Some(OrthographicExtent {
+                far: (extent.far.min(clip_range.far) - near).at_least(0.0),
+                ..extent
+            });
+        }
+    }
+
+    /// Where along [`Self::picking_ray`] things are visible.
+    pub fn visible_ray_t(&self) -> std::ops::RangeInclusive<f32> {
+        if self.is_perspective() {
+            // Approximate, since the planes are at a fixed depth and not at a fixed distance.
+            self.near()..=self.far()
+        } else if let Some(extent) = self.orthographic {
+            0.0..=extent.far
+        } else {
+            f32::NEG_INFINITY..=f32::INFINITY
+        }
+    }
+
     // pub fn is_orthographic(&self) -> bool {
     //     self.fov_y.is_none()
     // }
@@ -91,6 +169,15 @@ impl Eye {
                 .world_from_view
                 .transform_vector3(glam::vec3(px, py, -1.0));
             macaw::Ray3::from_origin_dir(self.pos_in_world(), ray_dir.normalize())
+        } else if let Some(extent) = self.orthographic {
+            // The ray originates on the near plane, straight ahead of the pointer
+            let scale = extent.vertical_world_size / screen_rect.height();
+            let px = (pointer.x - screen_rect.center().x) * scale;
+            let py = (screen_rect.center().y - pointer.y) * scale;
+            let origin = self
+                .world_from_view
+                .transform_point3(glam::vec3(px, py, 0.0));
+            macaw::Ray3::from_origin_dir(origin, self.forward_in_world())
         } else {
             // The ray originates on the camera plane, not from the camera position
             let ray_dir = self.world_from_view.rotation().mul_vec3(glam::Vec3::Z);
@@ -135,9 +222,17 @@ impl Eye {
             ))
         };
 
+        let (orthographic, clip_range) = if t < 0.5 {
+            (self.orthographic, self.clip_range)
+        } else {
+            (other.orthographic, other.clip_range)
+        };
+
         Eye {
             world_from_view: IsoTransform::from_rotation_translation(rotation, translation),
             fov_y,
+            orthographic,
+            clip_range,
         }
     }
 
@@ -152,6 +247,8 @@ impl Eye {
         if let Some(fov_y) = self.fov_y {
             let distance = position.distance(self.world_from_view.translation());
             (fov_y * 0.5).tan() * 2.0 / viewport_size.y * distance
+        } else if let Some(extent) = self.orthographic {
+            extent.vertical_world_size / viewport_size.y
         } else {
             1.0 / viewport_size.y
         }
@@ -189,6 +286,50 @@ impl OrbitEye {
                 self.position(),
             ),
             fov_y: Some(self.fov_y),
+            orthographic: None,
+            clip_range: None,
+        }
+    }
+
+    /// An orthographic eye that shows as much as [`Self::to_eye`] shows at the orbit center.
+    ///
+    /// Moving an orthographic eye along its view direction doesn't change what it shows,
+    /// so we pull it back far enough for the whole scene to be in front of it.
+    pub fn to_orthographic_eye(self, scene_bbox: &BoundingBox) -> Eye {
+        let fwd = self.fwd();
+
+        // How far the scene extends behind and in front of the orbit center:
+        let (mut behind, mut ahead) = (self.orbit_radius, self.orbit_radius);
+        if !scene_bbox.is_nothing() && !scene_bbox.is_nan() {
+            let (min, max) = (scene_bbox.min, scene_bbox.max);
+            for corner in [
+                vec3(min.x, min.y, min.z),
+                vec3(min.x, min.y, max.z),
+                vec3(min.x, max.y, min.z),
+                vec3(min.x, max.y, max.z),
+                vec3(max.x, min.y, min.z),
+                vec3(max.x, min.y, max.z),
+                vec3(max.x, max.y, min.z),
+                vec3(max.x, max.y, max.z),
+            ] {
+                let depth = (corner - self.orbit_center).dot(fwd);
+                behind = behind.max(-depth);
+                ahead = ahead.max(depth);
+            }
+        }
+        let distance = 1.1 * behind;
+
+        Eye {
+            world_from_view: IsoTransform::from_rotation_translation(
+                self.world_from_view_rot,
+                self.orbit_center - distance * fwd,
+            ),
+            fov_y: None,
+            orthographic: Some(OrthographicExtent {
+                vertical_world_size: 2.0 * self.orbit_radius * (0.5 * self.fov_y).tan(),
+                far: distance + 1.1 * ahead,
+            }),
+            clip_range: None,
         }
     }
 
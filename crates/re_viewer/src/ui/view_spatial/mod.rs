@@ -11,6 +11,6 @@ pub use self::scene::{
     Image, Label2D, Label2DTarget, Label3D, MeshSource, MeshSourceData, SceneSpatial,
 };
 pub use self::space_camera_3d::SpaceCamera3D;
-pub use ui::{SpatialNavigationMode, ViewSpatialState};
+pub use ui::{CameraSyncGroup, SpatialNavigationMode, SyncedCamera, ViewSpatialState};
 pub use ui_2d::view_2d;
 pub use ui_3d::{view_3d, SpaceSpecs};
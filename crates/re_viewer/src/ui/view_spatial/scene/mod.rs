@@ -3,17 +3,14 @@ use std::sync::Arc;
 use ahash::HashMap;
 use re_data_store::{EntityPath, InstancePathHash};
 use re_log_types::{
-    component_types::{ClassId, KeypointId, Tensor},
+    component_types::{ClassId, KeypointId, Tensor, Unit},
     MeshId,
 };
 use re_renderer::{Color32, Size};
 
 use super::{eye::Eye, SpaceCamera3D, SpatialNavigationMode};
 use crate::{
-    misc::{
-        mesh_loader::LoadedMesh, HoverHighlight, InteractionHighlight, SelectionHighlight,
-        SpaceViewHighlights, TransformCache, ViewerContext,
-    },
+    misc::{mesh_loader::LoadedMesh, SpaceViewHighlights, TransformCache, ViewerContext},
     ui::{
         annotations::{auto_color, AnnotationMap},
         Annotations, SceneQuery,
@@ -70,6 +67,9 @@ pub struct Image {
     /// up to a ~65m range.
     pub meter: Option<f32>,
 
+    /// The unit of the values, e.g. `°C` for a thermal image.
+    pub unit: Option<Unit>,
+
     /// A thing that provides additional semantic context for your dtype.
     pub annotations: Arc<Annotations>,
 }
@@ -191,74 +191,12 @@ impl SceneSpatial {
         }
 
         self.primitives.recalculate_bounding_box();
+        self.primitives.collect_outlines(ctx.render_ctx, highlights);
     }
 
     // TODO(andreas): Better ways to determine these?
-    const HOVER_COLOR: Color32 = Color32::from_rgb(255, 200, 200);
-    const SELECTION_COLOR: Color32 = Color32::from_rgb(255, 170, 170);
-    const SIBLING_SELECTION_COLOR: Color32 = Color32::from_rgb(255, 140, 140);
     const CAMERA_COLOR: Color32 = Color32::from_rgb(150, 150, 150);
 
-    fn size_boost(size: Size) -> Size {
-        if size.is_auto() {
-            Size::AUTO_LARGE
-        } else {
-            size * 1.33
-        }
-    }
-
-    fn apply_hover_and_selection_effect(
-        size: &mut Size,
-        color: &mut Color32,
-        highlight: InteractionHighlight,
-    ) {
-        // TODO(#889):
-        // We want to use outlines instead of color highlighting, but this is a bigger endeavour, so for now:
-
-        let mut highlight_color = *color;
-        if highlight.selection.is_some() {
-            *size = Self::size_boost(*size);
-            highlight_color = match highlight.selection {
-                SelectionHighlight::None => unreachable!(),
-                SelectionHighlight::SiblingSelection => Self::SIBLING_SELECTION_COLOR,
-                SelectionHighlight::Selection => Self::SELECTION_COLOR,
-            };
-        }
-        match highlight.hover {
-            HoverHighlight::None => {}
-            HoverHighlight::Hovered => {
-                highlight_color = Self::HOVER_COLOR;
-            }
-        }
-
-        if highlight.is_some() {
-            // Interpolate with factor 2/3 towards the highlight color (in gamma space for speed)
-            *color = Color32::from_rgba_premultiplied(
-                ((color.r() as u32 + highlight_color.r() as u32 * 2) / 3) as u8,
-                ((color.g() as u32 + highlight_color.g() as u32 * 2) / 3) as u8,
-                ((color.b() as u32 + highlight_color.b() as u32 * 2) / 3) as u8,
-                color.a(),
-            );
-        }
-    }
-
-    fn apply_hover_and_selection_effect_color(
-        color: Color32,
-        highlight: InteractionHighlight,
-    ) -> Color32 {
-        let mut color = color;
-        // (counting on inlining to remove unused fields!)
-        Self::apply_hover_and_selection_effect(&mut Size::AUTO.clone(), &mut color, highlight);
-        color
-    }
-
-    fn apply_hover_and_selection_effect_size(size: Size, highlight: InteractionHighlight) -> Size {
-        let mut size = size;
-        // (counting on inlining to remove unused fields!)
-        Self::apply_hover_and_selection_effect(&mut size, &mut Color32::WHITE.clone(), highlight);
-        size
-    }
-
     fn load_keypoint_connections(
         &mut self,
         entity_path: &re_data_store::EntityPath,
@@ -80,6 +80,8 @@ struct PickingContext {
 }
 
 struct PickingState {
+    /// Hits outside of this are clipped away.
+    visible_ray_t: std::ops::RangeInclusive<f32>,
     closest_opaque_side_ui_dist_sq: f32,
     closest_opaque_pick: PickingRayHit,
     transparent_hits: Vec<PickingRayHit>,
@@ -87,6 +89,10 @@ struct PickingState {
 
 impl PickingState {
     fn check_hit(&mut self, side_ui_dist_sq: f32, ray_hit: PickingRayHit, transparent: bool) {
+        if !self.visible_ray_t.contains(&ray_hit.ray_t) {
+            return;
+        }
+
         let gap_to_closest_opaque = self.closest_opaque_pick.ray_t - ray_hit.ray_t;
 
         // Use depth offset if very close to each other in relative distance.
@@ -148,6 +154,7 @@ pub fn picking(
         max_side_ui_dist_sq,
     };
     let mut state = PickingState {
+        visible_ray_t: eye.visible_ray_t(),
         closest_opaque_side_ui_dist_sq: max_side_ui_dist_sq,
         closest_opaque_pick: PickingRayHit {
             instance_path_hash: InstancePathHash::NONE,
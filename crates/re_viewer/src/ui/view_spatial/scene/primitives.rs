@@ -1,6 +1,12 @@
 use egui::Color32;
+use itertools::Itertools as _;
 use re_data_store::InstancePathHash;
-use re_renderer::{renderer::MeshInstance, LineStripSeriesBuilder, PointCloudBuilder};
+use re_renderer::{
+    renderer::{MeshInstance, TexturedRect},
+    LineStripSeriesBuilder, PointCloudBuilder,
+};
+
+use crate::misc::SpaceViewHighlights;
 
 use super::MeshSource;
 
@@ -23,6 +29,42 @@ pub struct SceneSpatialPrimitives {
     pub points: PointCloudBuilder<InstancePathHash>,
 
     pub meshes: Vec<MeshSource>,
+
+    /// Copies of the selected primitives, see [`Self::collect_outlines`].
+    pub selection_outlines: OutlinePrimitives,
+
+    /// Copies of the hovered primitives, see [`Self::collect_outlines`].
+    pub hover_outlines: OutlinePrimitives,
+}
+
+/// Copies of some of the [`SceneSpatialPrimitives`], drawn into an outline mask.
+///
+/// Only the shape matters, not the color.
+#[derive(Default)]
+pub struct OutlinePrimitives {
+    pub textured_rectangles: Vec<TexturedRect>,
+    pub line_strips: LineStripSeriesBuilder<()>,
+
+    /// Only created if there are any points, since a point cloud builder reserves a lot of memory.
+    pub points: Option<PointCloudBuilder<()>>,
+
+    pub meshes: Vec<MeshInstance>,
+}
+
+impl OutlinePrimitives {
+    pub fn is_empty(&self) -> bool {
+        let Self {
+            textured_rectangles,
+            line_strips,
+            points,
+            meshes,
+        } = self;
+
+        textured_rectangles.is_empty()
+            && line_strips.is_empty()
+            && points.is_none()
+            && meshes.is_empty()
+    }
 }
 
 const AXIS_COLOR_X: Color32 = Color32::from_rgb(255, 25, 25);
@@ -38,6 +80,8 @@ impl SceneSpatialPrimitives {
             line_strips: Default::default(),
             points: PointCloudBuilder::new(re_ctx),
             meshes: Default::default(),
+            selection_outlines: Default::default(),
+            hover_outlines: Default::default(),
         }
     }
 
@@ -55,6 +99,8 @@ impl SceneSpatialPrimitives {
             line_strips,
             points,
             meshes,
+            selection_outlines: _,
+            hover_outlines: _,
         } = &self;
 
         textured_rectangles.len()
@@ -114,25 +160,106 @@ impl SceneSpatialPrimitives {
 
     pub fn mesh_instances(&self) -> Vec<MeshInstance> {
         crate::profile_function!();
-        self.meshes
+        self.meshes.iter().flat_map(mesh_instances).collect()
+    }
+
+    /// Copies the primitives of selected and hovered instances
+    /// to [`Self::selection_outlines`] and [`Self::hover_outlines`].
+    pub fn collect_outlines(
+        &mut self,
+        re_ctx: &mut re_renderer::RenderContext,
+        highlights: &SpaceViewHighlights,
+    ) {
+        crate::profile_function!();
+
+        // Which outlines an instance goes into: selection, hover.
+        let is_outlined = |instance_path_hash: &InstancePathHash| {
+            let highlight = highlights
+                .entity_highlight(instance_path_hash.entity_path_hash)
+                .index_highlight(instance_path_hash.instance_key);
+            [highlight.selection.is_some(), highlight.hover.is_some()]
+        };
+        let mut outlines = [OutlinePrimitives::default(), OutlinePrimitives::default()];
+
+        for (rect, instance_path_hash) in self
+            .textured_rectangles
             .iter()
-            .flat_map(|mesh| {
-                let (scale, rotation, translation) =
-                    mesh.world_from_mesh.to_scale_rotation_translation();
-                // TODO(andreas): The renderer should make it easy to apply a transform to a bunch of meshes
-                let base_transform =
-                    glam::Affine3A::from_scale_rotation_translation(scale, rotation, translation);
-                mesh.mesh
-                    .mesh_instances
-                    .iter()
-                    .map(move |mesh_instance| MeshInstance {
-                        gpu_mesh: mesh_instance.gpu_mesh.clone(),
-                        mesh: None, // Don't care.
-                        world_from_mesh: base_transform * mesh_instance.world_from_mesh,
-                        additive_tint: mesh.additive_tint,
-                    })
-            })
-            .collect()
+            .zip(&self.textured_rectangles_ids)
+        {
+            for (outline, is_outlined) in outlines.iter_mut().zip(is_outlined(instance_path_hash)) {
+                if is_outlined {
+                    outline.textured_rectangles.push(rect.clone());
+                }
+            }
+        }
+
+        for (batch, vertices) in self.line_strips.iter_vertices_by_batch() {
+            let [selection, hover] = &mut outlines;
+            let mut line_batches = [
+                selection.line_strips.batch(batch.label.clone()),
+                hover.line_strips.batch(batch.label.clone()),
+            ]
+            .map(|line_batch| line_batch.world_from_obj(batch.world_from_obj));
+
+            for (strip_index, vertices) in &vertices.group_by(|vertex| vertex.strip_index) {
+                let strip = &self.line_strips.strips[strip_index as usize];
+                let instance_path_hash = &self.line_strips.strip_user_data[strip_index as usize];
+                let positions = vertices.map(|vertex| vertex.position).collect_vec();
+                for (line_batch, is_outlined) in
+                    line_batches.iter_mut().zip(is_outlined(instance_path_hash))
+                {
+                    if is_outlined {
+                        line_batch
+                            .add_strip(positions.iter().copied())
+                            .radius(strip.radius)
+                            .flags(strip.flags);
+                    }
+                }
+            }
+        }
+
+        for (batch, vertices) in self.points.iter_vertices_and_userdata_by_batch() {
+            let mut outlined_vertices = [Vec::new(), Vec::new()];
+            for (vertex, instance_path_hash) in vertices {
+                for (outlined_vertices, is_outlined) in outlined_vertices
+                    .iter_mut()
+                    .zip(is_outlined(instance_path_hash))
+                {
+                    if is_outlined {
+                        outlined_vertices.push(vertex);
+                    }
+                }
+            }
+
+            for (outline, vertices) in outlines.iter_mut().zip(outlined_vertices) {
+                if vertices.is_empty() {
+                    continue;
+                }
+                outline
+                    .points
+                    .get_or_insert_with(|| PointCloudBuilder::new(re_ctx))
+                    .batch(batch.label.clone())
+                    .world_from_obj(batch.world_from_obj)
+                    .add_points(
+                        vertices.len(),
+                        vertices.iter().map(|vertex| vertex.position),
+                    )
+                    .radii(vertices.iter().map(|vertex| vertex.radius));
+            }
+        }
+
+        for mesh in &self.meshes {
+            for (outline, is_outlined) in outlines
+                .iter_mut()
+                .zip(is_outlined(&mesh.instance_path_hash))
+            {
+                if is_outlined {
+                    outline.meshes.extend(mesh_instances(mesh));
+                }
+            }
+        }
+
+        [self.selection_outlines, self.hover_outlines] = outlines;
     }
 
     pub fn add_axis_lines(
@@ -177,3 +304,19 @@ impl SceneSpatialPrimitives {
             .user_data(instance_path_hash);
     }
 }
+
+fn mesh_instances(mesh: &MeshSource) -> impl Iterator<Item = MeshInstance> + '_ {
+    let (scale, rotation, translation) = mesh.world_from_mesh.to_scale_rotation_translation();
+    // TODO(andreas): The renderer should make it easy to apply a transform to a bunch of meshes
+    let base_transform =
+        glam::Affine3A::from_scale_rotation_translation(scale, rotation, translation);
+    mesh.mesh
+        .mesh_instances
+        .iter()
+        .map(move |mesh_instance| MeshInstance {
+            gpu_mesh: mesh_instance.gpu_mesh.clone(),
+            mesh: None, // Don't care.
+            world_from_mesh: base_transform * mesh_instance.world_from_mesh,
+            additive_tint: mesh.additive_tint,
+        })
+}
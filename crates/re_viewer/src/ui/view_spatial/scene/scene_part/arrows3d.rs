@@ -57,7 +57,7 @@ impl Arrows3DPart {
             // TODO(andreas): support labels
             // TODO(andreas): support class ids for arrows
             let annotation_info = annotations.class_description(None).annotation_info();
-            let mut color =
+            let color =
                 annotation_info.color(color.map(move |c| c.to_array()).as_ref(), default_color);
             //let label = annotation_info.label(label);
 
@@ -66,17 +66,11 @@ impl Arrows3DPart {
             let vector = glam::Vec3::from(vector);
             let origin = glam::Vec3::from(origin);
 
-            let mut radius = radius.map_or(Size::AUTO, |r| Size(r.0));
+            let radius = radius.map_or(Size::AUTO, |r| Size(r.0));
             let tip_length = LineStripFlags::get_triangle_cap_tip_length(radius.0);
             let vector_len = vector.length();
             let end = origin + vector * ((vector_len - tip_length) / vector_len);
 
-            SceneSpatial::apply_hover_and_selection_effect(
-                &mut radius,
-                &mut color,
-                entity_highlight.index_highlight(instance_hash.instance_key),
-            );
-
             line_batch
                 .add_segment(origin, end)
                 .radius(radius)
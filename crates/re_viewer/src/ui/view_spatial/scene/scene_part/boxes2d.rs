@@ -8,7 +8,7 @@ use re_query::{query_primary_with_history, QueryError};
 use re_renderer::Size;
 
 use crate::{
-    misc::{OptionalSpaceViewEntityHighlight, SpaceViewHighlights, TransformCache, ViewerContext},
+    misc::{SpaceViewHighlights, TransformCache, ViewerContext},
     ui::{
         scene::SceneQuery,
         view_spatial::{
@@ -35,25 +35,18 @@ impl Boxes2DPart {
         radius: Option<Radius>,
         label: Option<Label>,
         class_id: Option<ClassId>,
-        entity_highlight: OptionalSpaceViewEntityHighlight<'_>,
     ) {
         scene.num_logged_2d_objects += 1;
 
         let annotations = scene.annotation_map.find(entity_path);
         let annotation_info = annotations.class_description(class_id).annotation_info();
-        let mut color = annotation_info.color(
+        let color = annotation_info.color(
             color.map(|c| c.to_array()).as_ref(),
             DefaultColor::EntityPath(entity_path),
         );
-        let mut radius = radius.map_or(Size::AUTO, |r| Size::new_scene(r.0));
+        let radius = radius.map_or(Size::AUTO, |r| Size::new_scene(r.0));
         let label = annotation_info.label(label.map(|l| l.0).as_ref());
 
-        SceneSpatial::apply_hover_and_selection_effect(
-            &mut radius,
-            &mut color,
-            entity_highlight.index_highlight(instance_path_hash.instance_key),
-        );
-
         let mut line_batch = scene
             .primitives
             .line_strips
@@ -137,7 +130,6 @@ impl ScenePart for Boxes2DPart {
                             radius,
                             label,
                             class_id,
-                            entity_highlight,
                         );
                     })?;
                 }
@@ -60,16 +60,10 @@ impl Boxes3DPart {
             let class_description = annotations.class_description(class_id);
             let annotation_info = class_description.annotation_info();
 
-            let mut radius = radius.map_or(Size::AUTO, |r| Size::new_scene(r.0));
-            let mut color =
+            let radius = radius.map_or(Size::AUTO, |r| Size::new_scene(r.0));
+            let color =
                 annotation_info.color(color.map(move |c| c.to_array()).as_ref(), default_color);
 
-            SceneSpatial::apply_hover_and_selection_effect(
-                &mut radius,
-                &mut color,
-                entity_highlight.index_highlight(instance_hash.instance_key),
-            );
-
             let scale = glam::Vec3::from(half_size);
             let rot = rotation.map(glam::Quat::from).unwrap_or_default();
             let tran = position.map_or(glam::Vec3::ZERO, glam::Vec3::from);
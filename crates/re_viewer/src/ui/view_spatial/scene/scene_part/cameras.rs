@@ -8,8 +8,7 @@ use re_renderer::renderer::LineStripFlags;
 
 use crate::{
     misc::{
-        space_info::query_view_coordinates, OptionalSpaceViewEntityHighlight, SpaceViewHighlights,
-        TransformCache, ViewerContext,
+        space_info::query_view_coordinates, SpaceViewHighlights, TransformCache, ViewerContext,
     },
     ui::{
         scene::SceneQuery,
@@ -65,7 +64,6 @@ impl CamerasPart {
         instance_path_hash: InstancePathHash,
         pinhole: Pinhole,
         view_coordinates: ViewCoordinates,
-        entity_highlight: OptionalSpaceViewEntityHighlight<'_>,
     ) {
         // The transform *at* this entity path already has the pinhole transformation we got passed in!
         // This makes sense, since if there's an image logged here one would expect that the transform applies.
@@ -141,13 +139,8 @@ impl CamerasPart {
             (up_triangle[2], up_triangle[0]),
         ];
 
-        let mut radius = re_renderer::Size::new_points(1.0);
-        let mut color = SceneSpatial::CAMERA_COLOR;
-        SceneSpatial::apply_hover_and_selection_effect(
-            &mut radius,
-            &mut color,
-            entity_highlight.index_highlight(instance_path_hash.instance_key),
-        );
+        let radius = re_renderer::Size::new_points(1.0);
+        let color = SceneSpatial::CAMERA_COLOR;
 
         scene
             .primitives
@@ -214,7 +207,6 @@ impl ScenePart for CamerasPart {
                         instance_hash,
                         pinhole,
                         view_coordinates,
-                        entity_highlight,
                     );
                 })
             }) {
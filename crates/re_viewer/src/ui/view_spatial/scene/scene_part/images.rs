@@ -6,11 +6,10 @@ use itertools::Itertools;
 
 use re_data_store::{EntityPath, EntityProperties, InstancePathHash};
 use re_log_types::{
-    component_types::{ColorRGBA, InstanceKey, Tensor, TensorTrait},
+    component_types::{ColorRGBA, InstanceKey, Tensor, TensorTrait, Unit},
     msg_bundle::Component,
 };
 use re_query::{query_primary_with_history, EntityView, QueryError};
-use re_renderer::Size;
 
 use crate::{
     misc::{caches::AsDynamicImage, SpaceViewHighlights, TransformCache, ViewerContext},
@@ -135,10 +134,11 @@ impl ImagesPart {
     ) -> Result<(), QueryError> {
         crate::profile_function!();
 
-        for (instance_key, tensor, color) in itertools::izip!(
+        for (instance_key, tensor, color, unit) in itertools::izip!(
             entity_view.iter_instance_keys()?,
             entity_view.iter_primary()?,
-            entity_view.iter_component::<ColorRGBA>()?
+            entity_view.iter_component::<ColorRGBA>()?,
+            entity_view.iter_component::<Unit>()?
         ) {
             crate::profile_scope!("loop_iter");
             if let Some(tensor) = tensor {
@@ -163,24 +163,6 @@ impl ImagesPart {
                     DefaultColor::OpaqueWhite,
                 );
 
-                let highlight = entity_highlight.index_highlight(instance_path_hash.instance_key);
-                if highlight.is_some() {
-                    let color = SceneSpatial::apply_hover_and_selection_effect_color(
-                        re_renderer::Color32::TRANSPARENT,
-                        highlight,
-                    );
-                    let rect =
-                        glam::vec2(tensor.shape()[1].size as f32, tensor.shape()[0].size as f32);
-                    scene
-                        .primitives
-                        .line_strips
-                        .batch("image outlines")
-                        .world_from_obj(world_from_obj)
-                        .add_axis_aligned_rectangle_outline_2d(glam::Vec2::ZERO, rect)
-                        .color(color)
-                        .radius(Size::new_points(1.0));
-                }
-
                 push_tensor_texture(
                     scene,
                     ctx,
@@ -198,6 +180,7 @@ impl ImagesPart {
                     instance_path_hash,
                     tensor,
                     meter,
+                    unit,
                     annotations,
                 });
             }
@@ -223,13 +206,18 @@ impl ScenePart for ImagesPart {
                 continue;
             };
 
-            match query_primary_with_history::<Tensor, 3>(
+            match query_primary_with_history::<Tensor, 4>(
                 &ctx.log_db.entity_db.data_store,
                 &query.timeline,
                 &query.latest_at,
                 &props.visible_history,
                 ent_path,
-                [Tensor::name(), InstanceKey::name(), ColorRGBA::name()],
+                [
+                    Tensor::name(),
+                    InstanceKey::name(),
+                    ColorRGBA::name(),
+                    Unit::name(),
+                ],
             )
             .and_then(|entities| {
                 for entity in entities {
@@ -53,16 +53,10 @@ impl Lines2DPart {
 
             // TODO(andreas): support class ids for lines
             let annotation_info = annotations.class_description(None).annotation_info();
-            let mut radius = radius.map_or(Size::AUTO, |r| Size::new_scene(r.0));
-            let mut color =
+            let radius = radius.map_or(Size::AUTO, |r| Size::new_scene(r.0));
+            let color =
                 annotation_info.color(color.map(move |c| c.to_array()).as_ref(), default_color);
 
-            SceneSpatial::apply_hover_and_selection_effect(
-                &mut radius,
-                &mut color,
-                entity_highlight.index_highlight(instance_hash.instance_key),
-            );
-
             line_batch
                 .add_strip_2d(strip.0.into_iter().map(|v| v.into()))
                 .color(color)
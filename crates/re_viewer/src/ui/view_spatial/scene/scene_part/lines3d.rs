@@ -51,19 +51,13 @@ impl Lines3DPart {
                 entity_highlight,
             );
 
-            let mut radius = radius.map_or(Size::AUTO, |r| Size::new_scene(r.0));
+            let radius = radius.map_or(Size::AUTO, |r| Size::new_scene(r.0));
 
             // TODO(andreas): support class ids for lines
             let annotation_info = annotations.class_description(None).annotation_info();
-            let mut color =
+            let color =
                 annotation_info.color(color.map(move |c| c.to_array()).as_ref(), default_color);
 
-            SceneSpatial::apply_hover_and_selection_effect(
-                &mut radius,
-                &mut color,
-                entity_highlight.index_highlight(instance_hash.instance_key),
-            );
-
             line_batch
                 .add_strip(strip.0.into_iter().map(|v| v.into()))
                 .radius(radius)
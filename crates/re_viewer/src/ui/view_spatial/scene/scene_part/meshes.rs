@@ -50,11 +50,6 @@ impl MeshPart {
                     entity_highlight,
                 );
 
-                let additive_tint = SceneSpatial::apply_hover_and_selection_effect_color(
-                    Color32::TRANSPARENT,
-                    entity_highlight.index_highlight(instance_path_hash.instance_key),
-                );
-
                 if let Some(mesh) = ctx
                     .cache
                     .mesh
@@ -67,7 +62,7 @@ impl MeshPart {
                         instance_path_hash,
                         world_from_mesh: world_from_obj_affine,
                         mesh: cpu_mesh,
-                        additive_tint,
+                        additive_tint: Color32::TRANSPARENT,
                     })
                 {
                     scene.primitives.meshes.push(mesh);
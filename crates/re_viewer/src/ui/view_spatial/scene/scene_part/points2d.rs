@@ -82,17 +82,11 @@ impl Points2DPart {
                 },
             );
 
-            let mut color =
+            let color =
                 annotation_info.color(color.map(move |c| c.to_array()).as_ref(), default_color);
-            let mut radius = radius.map_or(Size::AUTO, |r| Size::new_scene(r.0));
+            let radius = radius.map_or(Size::AUTO, |r| Size::new_scene(r.0));
             let label = annotation_info.label(label.map(|l| l.0).as_ref());
 
-            SceneSpatial::apply_hover_and_selection_effect(
-                &mut radius,
-                &mut color,
-                entity_highlight.index_highlight(instance_hash.instance_key),
-            );
-
             point_batch
                 .add_point_2d(pos)
                 .color(color)
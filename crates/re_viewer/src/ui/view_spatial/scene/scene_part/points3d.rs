@@ -3,17 +3,20 @@ use std::sync::Arc;
 use ahash::{HashMap, HashMapExt};
 use glam::Mat4;
 
-use re_data_store::{EntityPath, EntityProperties};
+use re_data_store::{EntityPath, EntityProperties, PointColorSource, PointColoring};
 use re_log_types::{
-    component_types::{ClassId, ColorRGBA, InstanceKey, KeypointId, Label, Point3D, Radius},
+    component_types::{
+        ClassId, ColorRGBA, InstanceKey, KeypointId, Label, Point3D, Radius, Scalar,
+    },
     msg_bundle::Component,
+    TimeInt, TimeType,
 };
-use re_query::{query_primary_with_history, EntityView, QueryError};
+use re_query::{query_primary_with_history_and_time, EntityView, QueryError};
 use re_renderer::Size;
 
 use crate::{
     misc::{
-        InteractionHighlight, OptionalSpaceViewEntityHighlight, SpaceViewHighlights,
+        color_map::color_map, OptionalSpaceViewEntityHighlight, SpaceViewHighlights,
         TransformCache, ViewerContext,
     },
     ui::{
@@ -29,6 +32,26 @@ use crate::{
 
 use super::ScenePart;
 
+/// The annotation info of each point.
+///
+/// Without class ids or keypoints all points share the same one,
+/// which saves cloning it for each of the millions of points of a big point cloud.
+enum AnnotationInfos {
+    Shared(ResolvedAnnotationInfo, usize),
+    PerPoint(Vec<ResolvedAnnotationInfo>),
+}
+
+impl AnnotationInfos {
+    fn iter(&self) -> impl Iterator<Item = &ResolvedAnnotationInfo> {
+        match self {
+            Self::Shared(annotation_info, num_points) => {
+                itertools::Either::Left(std::iter::repeat(annotation_info).take(*num_points))
+            }
+            Self::PerPoint(annotation_infos) => itertools::Either::Right(annotation_infos.iter()),
+        }
+    }
+}
+
 pub struct Points3DPart {
     /// If the number of points in the batch is > max_labels, don't render point labels.
     pub(crate) max_labels: usize,
@@ -39,7 +62,7 @@ impl Points3DPart {
         query: &SceneQuery<'_>,
         entity_view: &EntityView<Point3D>,
         annotations: &Arc<Annotations>,
-    ) -> Result<(Vec<ResolvedAnnotationInfo>, Keypoints), QueryError> {
+    ) -> Result<(AnnotationInfos, Keypoints), QueryError> {
         crate::profile_function!();
 
         let mut keypoints: Keypoints = HashMap::new();
@@ -48,7 +71,7 @@ impl Points3DPart {
         if !entity_view.has_component::<KeypointId>() && !entity_view.has_component::<ClassId>() {
             let resolved_annotation = annotations.class_description(None).annotation_info();
             return Ok((
-                vec![resolved_annotation; entity_view.num_instances()],
+                AnnotationInfos::Shared(resolved_annotation, entity_view.num_instances()),
                 keypoints,
             ));
         }
@@ -75,49 +98,45 @@ impl Points3DPart {
         })
         .collect();
 
-        Ok((annotation_info, keypoints))
+        Ok((AnnotationInfos::PerPoint(annotation_info), keypoints))
     }
 
     fn process_colors<'a>(
         entity_view: &'a EntityView<Point3D>,
         ent_path: &'a EntityPath,
-        highlights: &'a [InteractionHighlight],
-        annotation_infos: &'a [ResolvedAnnotationInfo],
+        annotation_infos: &'a AnnotationInfos,
+        attribute_colors: Option<&'a [Option<egui::Color32>]>,
     ) -> Result<impl Iterator<Item = egui::Color32> + 'a, QueryError> {
         crate::profile_function!();
         let default_color = DefaultColor::EntityPath(ent_path);
+        let attribute_colors = (0..)
+            .map(move |i| attribute_colors.and_then(|colors| colors.get(i).copied().flatten()));
 
         let colors = itertools::izip!(
-            highlights.iter(),
             annotation_infos.iter(),
             entity_view.iter_component::<ColorRGBA>()?,
+            attribute_colors,
         )
-        .map(move |(highlight, annotation_info, color)| {
-            SceneSpatial::apply_hover_and_selection_effect_color(
-                annotation_info.color(color.map(move |c| c.to_array()).as_ref(), default_color),
-                *highlight,
-            )
+        .map(move |(annotation_info, color, attribute_color)| {
+            attribute_color.unwrap_or_else(|| {
+                annotation_info.color(color.map(move |c| c.to_array()).as_ref(), default_color)
+            })
         });
         Ok(colors)
     }
 
     fn process_radii<'a>(
         entity_view: &'a EntityView<Point3D>,
-        highlights: &'a [InteractionHighlight],
     ) -> Result<impl Iterator<Item = Size> + 'a, QueryError> {
-        let radii = itertools::izip!(highlights.iter(), entity_view.iter_component::<Radius>()?,)
-            .map(move |(highlight, radius)| {
-                SceneSpatial::apply_hover_and_selection_effect_size(
-                    radius.map_or(Size::AUTO, |radius| Size::new_scene(radius.0)),
-                    *highlight,
-                )
-            });
+        let radii = entity_view
+            .iter_component::<Radius>()?
+            .map(|radius| radius.map_or(Size::AUTO, |radius| Size::new_scene(radius.0)));
         Ok(radii)
     }
 
     fn process_labels<'a>(
         entity_view: &'a EntityView<Point3D>,
-        annotation_infos: &'a [ResolvedAnnotationInfo],
+        annotation_infos: &'a AnnotationInfos,
         world_from_obj: Mat4,
     ) -> Result<impl Iterator<Item = Label3D> + 'a, QueryError> {
         let labels = itertools::izip!(
@@ -148,6 +167,7 @@ impl Points3DPart {
         ent_path: &EntityPath,
         world_from_obj: Mat4,
         entity_highlight: OptionalSpaceViewEntityHighlight<'_>,
+        attribute_colors: Option<&[Option<egui::Color32>]>,
     ) -> Result<(), QueryError> {
         crate::profile_function!();
 
@@ -181,17 +201,10 @@ impl Points3DPart {
                 .collect::<Vec<_>>()
         };
 
-        let highlights = {
-            crate::profile_scope!("highlights");
-            instance_path_hashes
-                .iter()
-                .map(|hash| entity_highlight.index_highlight(hash.instance_key))
-                .collect::<Vec<_>>()
-        };
-
-        let colors = Self::process_colors(entity_view, ent_path, &highlights, &annotation_infos)?;
+        let colors =
+            Self::process_colors(entity_view, ent_path, &annotation_infos, attribute_colors)?;
 
-        let radii = Self::process_radii(entity_view, &highlights)?;
+        let radii = Self::process_radii(entity_view)?;
         let labels = Self::process_labels(entity_view, &annotation_infos, world_from_obj)?;
 
         if show_labels && instance_path_hashes.len() <= self.max_labels {
@@ -214,6 +227,104 @@ impl Points3DPart {
     }
 }
 
+/// The colors of the points of each entity view according to [`PointColoring::source`],
+/// or `None` for the logged colors.
+///
+/// Uses the same range for all of the visible history.
+fn attribute_colors(
+    coloring: &PointColoring,
+    entities: &[(Option<TimeInt>, EntityView<Point3D>)],
+    query: &SceneQuery<'_>,
+    props: &EntityProperties,
+    world_from_obj: Mat4,
+) -> Result<Option<Vec<Vec<Option<egui::Color32>>>>, QueryError> {
+    crate::profile_function!();
+
+    if coloring.source == PointColorSource::Logged {
+        return Ok(None);
+    }
+
+    let visible_history = match query.timeline.typ() {
+        TimeType::Time => props.visible_history.nanos,
+        TimeType::Sequence => props.visible_history.sequences,
+    };
+    let values = entities
+        .iter()
+        .map(|(time, entity_view)| {
+            attribute_values(
+                coloring.source,
+                entity_view,
+                *time,
+                query.latest_at,
+                visible_history,
+                world_from_obj,
+            )
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let [min, max] = match (coloring.source, coloring.range) {
+        (PointColorSource::Time, _) => [0.0, 1.0],
+        (_, Some([min, max])) => [min.into_inner(), max.into_inner()],
+        (_, None) => values
+            .iter()
+            .flatten()
+            .flatten()
+            .fold([f32::INFINITY, f32::NEG_INFINITY], |[min, max], &value| {
+                [min.min(value), max.max(value)]
+            }),
+    };
+
+    let colors = values
+        .iter()
+        .map(|values| {
+            values
+                .iter()
+                .map(|value| {
+                    value.map(|value| {
+                        color_map(
+                            coloring.colormap,
+                            (value - min) / (max - min).max(f32::EPSILON),
+                        )
+                    })
+                })
+                .collect()
+        })
+        .collect();
+    Ok(Some(colors))
+}
+
+/// The value of each point to color it by.
+fn attribute_values(
+    source: PointColorSource,
+    entity_view: &EntityView<Point3D>,
+    time: Option<TimeInt>,
+    latest_at: TimeInt,
+    visible_history: i64,
+    world_from_obj: Mat4,
+) -> Result<Vec<Option<f32>>, QueryError> {
+    Ok(match source {
+        PointColorSource::Logged => Vec::new(),
+        PointColorSource::Height => entity_view
+            .iter_primary()?
+            .map(|point| point.map(|point| world_from_obj.transform_point3(point.into()).z))
+            .collect(),
+        PointColorSource::Intensity => entity_view
+            .iter_component::<Scalar>()?
+            .map(|scalar| scalar.map(|scalar| scalar.0 as f32))
+            .collect(),
+        PointColorSource::Time => {
+            // Oldest visible is 0, newest is 1:
+            let age = time.map_or(0, |time| (latest_at - time).as_i64());
+            let value = if visible_history == 0 {
+                1.0
+            } else {
+                1.0 - age as f32 / visible_history as f32
+            };
+            vec![Some(value); entity_view.num_instances()]
+        }
+    })
+}
+
 impl ScenePart for Points3DPart {
     fn load(
         &self,
@@ -231,7 +342,7 @@ impl ScenePart for Points3DPart {
             };
             let entity_highlight = highlights.entity_highlight(ent_path.hash());
 
-            match query_primary_with_history::<Point3D, 7>(
+            match query_primary_with_history_and_time::<Point3D, 8>(
                 &ctx.log_db.entity_db.data_store,
                 &query.timeline,
                 &query.latest_at,
@@ -245,18 +356,28 @@ impl ScenePart for Points3DPart {
                     Label::name(),
                     ClassId::name(),
                     KeypointId::name(),
+                    Scalar::name(),
                 ],
             )
             .and_then(|entities| {
-                for entity in entities {
+                let entities = entities.collect::<Vec<_>>();
+                let attribute_colors = attribute_colors(
+                    &props.point_coloring(),
+                    &entities,
+                    query,
+                    &props,
+                    world_from_obj,
+                )?;
+                for (i, (_, entity)) in entities.iter().enumerate() {
                     self.process_entity_view(
                         scene,
                         query,
                         &props,
-                        &entity,
+                        entity,
                         ent_path,
                         world_from_obj,
                         entity_highlight,
+                        attribute_colors.as_ref().map(|colors| colors[i].as_slice()),
                     )?;
                 }
                 Ok(())
@@ -1,5 +1,4 @@
 use re_data_store::EntityPath;
-use re_format::format_f32;
 
 use egui::{NumExt, WidgetText};
 use macaw::BoundingBox;
@@ -9,10 +8,15 @@ use crate::{
     ui::{data_blueprint::DataBlueprintTree, SpaceViewId},
 };
 
-use super::{ui_2d::View2DState, ui_3d::View3DState, SceneSpatial, SpaceSpecs};
+use super::{
+    eye::OrbitEye,
+    ui_2d::View2DState,
+    ui_3d::{AxisView, View3DState},
+    SceneSpatial, SpaceSpecs,
+};
 
 /// Describes how the scene is navigated, determining if it is a 2D or 3D experience.
-#[derive(Clone, Copy, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, serde::Deserialize, serde::Serialize)]
 pub enum SpatialNavigationMode {
     #[default]
     TwoD,
@@ -28,6 +32,49 @@ impl From<SpatialNavigationMode> for WidgetText {
     }
 }
 
+/// Views in the same group keep their cameras in sync,
+/// e.g. to compare two image layers or two robots side by side.
+///
+/// Only views with the same [`SpatialNavigationMode`] are synced with each other.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, serde::Deserialize, serde::Serialize)]
+pub struct CameraSyncGroup(u8);
+
+impl CameraSyncGroup {
+    pub const ALL: [Self; 4] = [Self(0), Self(1), Self(2), Self(3)];
+}
+
+impl std::fmt::Display for CameraSyncGroup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Group {}", (b'A' + self.0) as char)
+    }
+}
+
+/// The part of a camera that is shared within a [`CameraSyncGroup`].
+#[derive(Clone, Copy, Debug)]
+pub enum SyncedCamera {
+    /// The scale (ui points per scene unit) and center we are zoomed in at,
+    /// or `None` if we show the whole scene.
+    TwoD(Option<(f32, egui::Pos2)>),
+
+    ThreeD(OrbitEye),
+}
+
+impl SyncedCamera {
+    /// Ignores e.g. the velocity of a 3D eye.
+    pub fn shows_the_same_as(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::TwoD(a), Self::TwoD(b)) => a == b,
+            (Self::ThreeD(a), Self::ThreeD(b)) => {
+                a.orbit_center == b.orbit_center
+                    && a.orbit_radius == b.orbit_radius
+                    && a.world_from_view_rot == b.world_from_view_rot
+                    && a.fov_y == b.fov_y
+            }
+            _ => false,
+        }
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum AutoSizeUnit {
     Auto,
@@ -67,6 +114,10 @@ pub struct ViewSpatialState {
     pub(super) state_2d: View2DState,
     pub(super) state_3d: View3DState,
 
+    /// Keep the camera in sync with the other views in this group.
+    #[serde(default)]
+    pub camera_sync_group: Option<CameraSyncGroup>,
+
     /// Size of automatically sized objects. None if it wasn't configured.
     auto_size_config: re_renderer::AutoSizeConfig,
 }
@@ -80,6 +131,7 @@ impl Default for ViewSpatialState {
             scene_num_primitives: 0,
             state_2d: Default::default(),
             state_3d: Default::default(),
+            camera_sync_group: None,
             auto_size_config: re_renderer::AutoSizeConfig {
                 point_radius: re_renderer::Size::AUTO, // let re_renderer decide
                 line_radius: re_renderer::Size::AUTO,  // let re_renderer decide
@@ -89,6 +141,30 @@ impl Default for ViewSpatialState {
 }
 
 impl ViewSpatialState {
+    pub fn synced_camera(&self) -> Option<SyncedCamera> {
+        match self.nav_mode {
+            SpatialNavigationMode::TwoD => {
+                Some(SyncedCamera::TwoD(self.state_2d.scale_and_center()))
+            }
+            SpatialNavigationMode::ThreeD => self.state_3d.orbit_eye.map(SyncedCamera::ThreeD),
+        }
+    }
+
+    /// Jump to the camera of another view in our [`CameraSyncGroup`].
+    ///
+    /// Ignored if it is for another [`SpatialNavigationMode`].
+    pub fn follow_camera(&mut self, camera: &SyncedCamera) {
+        match (self.nav_mode, camera) {
+            (SpatialNavigationMode::TwoD, SyncedCamera::TwoD(scale_and_center)) => {
+                self.state_2d.set_scale_and_center(*scale_and_center);
+            }
+            (SpatialNavigationMode::ThreeD, SyncedCamera::ThreeD(orbit_eye)) => {
+                self.state_3d.follow_orbit_eye(*orbit_eye);
+            }
+            _ => {}
+        }
+    }
+
     pub fn auto_size_config(
         &self,
         viewport_size_in_points: egui::Vec2,
@@ -222,19 +298,75 @@ impl ViewSpatialState {
                         );
                     });
 
-                if self.nav_mode == SpatialNavigationMode::ThreeD {
-                    if ui.button("Reset").on_hover_text(
-                        "Resets camera position & orientation.\nYou can also double-click the 3D view.")
-                        .clicked()
-                    {
-                        self.state_3d.reset_camera(&self.scene_bbox_accum);
+                if ui.button("Fit to scene").on_hover_text(
+                    "Frame the whole scene, and keep doing so as data arrives until the camera is moved.\n\
+                    You can also double-click the view.")
+                    .clicked()
+                {
+                    match self.nav_mode {
+                        SpatialNavigationMode::TwoD => self.state_2d.reset_zoom(),
+                        SpatialNavigationMode::ThreeD => {
+                            self.state_3d.reset_camera(&self.scene_bbox_accum);
+                        }
                     }
+                }
+                egui::ComboBox::from_id_source("camera_sync_group")
+                    .selected_text(self.camera_sync_group.map_or_else(|| "Not synced".to_owned(), |group| format!("Synced: {group}")))
+                    .show_ui(ui, |ui| {
+                        ui.style_mut().wrap = Some(false);
+                        ui.selectable_value(&mut self.camera_sync_group, None, "Not synced");
+                        for group in CameraSyncGroup::ALL {
+                            ui.selectable_value(&mut self.camera_sync_group, Some(group), group.to_string());
+                        }
+                    })
+                    .response
+                    .on_hover_text("Views in the same group move their cameras together,\n\
+                    e.g. to compare two image layers side by side.");
+                if self.nav_mode == SpatialNavigationMode::TwoD {
+                    ui.checkbox(&mut self.state_2d.show_minimap, "Minimap")
+                        .on_hover_text("Show an overview of the whole scene in the corner when zoomed in.\n\
+                        Click or drag in it to move the view.");
+                }
+                if self.nav_mode == SpatialNavigationMode::ThreeD {
                     ui.checkbox(&mut self.state_3d.spin, "Spin")
                         .on_hover_text("Spin camera around the orbit center.");
+                    ui.checkbox(&mut self.state_3d.orthographic, "Orthographic")
+                        .on_hover_text("Show the scene without perspective, so that parallel lines stay parallel.\n\
+                        Useful for measuring and aligning geometry.");
+                    ui.horizontal(|ui| {
+                        for view in AxisView::ALL {
+                            if ui.button(view.to_string())
+                                .on_hover_text(format!("Look at the scene from the {}.", view.to_string().to_lowercase()))
+                                .clicked()
+                            {
+                                self.state_3d.look_from(view);
+                            }
+                        }
+                    });
                 }
             });
             ui.end_row();
 
+            if self.nav_mode == SpatialNavigationMode::ThreeD {
+                ctx.re_ui.grid_left_hand_label(ui, "Clipping")
+                    .on_hover_text("Hide everything closer to or further from the camera than this,\n\
+                    to look inside dense scenes.");
+                self.state_3d.clip_range_ui(ui);
+                ui.end_row();
+
+                ctx.re_ui.grid_left_hand_label(ui, "Lighting")
+                    .on_hover_text("The direction of the light, and whether meshes cast shadows.");
+                self.state_3d.light_ui(ui);
+                ui.end_row();
+            }
+
+            if self.nav_mode == SpatialNavigationMode::TwoD {
+                ctx.re_ui.grid_left_hand_label(ui, "Background")
+                    .on_hover_text("A static image shown underneath the data, e.g. a floorplan.");
+                self.state_2d.background_ui(ui);
+                ui.end_row();
+            }
+
             if self.nav_mode == SpatialNavigationMode::ThreeD {
                 ctx.re_ui.grid_left_hand_label(ui, "Coordinates")
                     .on_hover_text("The world coordinate system used for this view.");
@@ -256,21 +388,22 @@ impl ViewSpatialState {
                 .on_hover_text("The bounding box encompassing all Entities in the view right now.");
             ui.vertical(|ui| {
                 let BoundingBox { min, max } = self.scene_bbox;
+                let number_format = &ctx.app_options.number_format;
                 ui.label(format!(
                     "x [{} - {}]",
-                    format_f32(min.x),
-                    format_f32(max.x),
+                    number_format.format_f32(min.x),
+                    number_format.format_f32(max.x),
                 ));
                 ui.label(format!(
                     "y [{} - {}]",
-                    format_f32(min.y),
-                    format_f32(max.y),
+                    number_format.format_f32(min.y),
+                    number_format.format_f32(max.y),
                 ));
                 if self.nav_mode == SpatialNavigationMode::ThreeD {
                     ui.label(format!(
                         "z [{} - {}]",
-                        format_f32(min.z),
-                        format_f32(max.z),
+                        number_format.format_f32(min.z),
+                        number_format.format_f32(max.z),
                     ));
                 }
             });
@@ -284,7 +417,7 @@ impl ViewSpatialState {
         ctx: &mut ViewerContext<'_>,
         ui: &mut egui::Ui,
         space: &EntityPath,
-        scene: SceneSpatial,
+        mut scene: SceneSpatial,
         space_view_id: SpaceViewId,
         highlights: &SpaceViewHighlights,
     ) {
@@ -307,10 +440,15 @@ impl ViewSpatialState {
                 super::view_3d(ctx, ui, self, space, space_view_id, scene);
             }
             SpatialNavigationMode::TwoD => {
-                let scene_rect_accum = egui::Rect::from_min_max(
+                let mut scene_rect_accum = egui::Rect::from_min_max(
                     self.scene_bbox_accum.min.truncate().to_array().into(),
                     self.scene_bbox_accum.max.truncate().to_array().into(),
                 );
+                if let Some(background_rect) =
+                    self.state_2d.add_background_to_scene(ctx, &mut scene)
+                {
+                    scene_rect_accum = scene_rect_accum.union(background_rect);
+                }
                 super::view_2d(
                     ctx,
                     ui,
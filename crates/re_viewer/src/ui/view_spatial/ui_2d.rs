@@ -4,7 +4,7 @@ use egui::{
     TextFormat, TextStyle, Vec2,
 };
 use macaw::IsoTransform;
-use re_data_store::EntityPath;
+use re_data_store::{EntityPath, InstancePathHash};
 use re_log_types::component_types::TensorTrait;
 use re_renderer::view_builder::TargetConfiguration;
 
@@ -18,7 +18,10 @@ use crate::{
     ui::{
         data_ui::{self, DataUi},
         view_spatial::{
-            ui_renderer_bridge::{create_scene_paint_callback, get_viewport, ScreenBackground},
+            ui_renderer_bridge::{
+                create_scene_paint_callback, create_textured_rects_paint_callback, get_viewport,
+                ScreenBackground,
+            },
             Label2DTarget, SceneSpatial,
         },
         SpaceViewId, UiVerbosity,
@@ -34,8 +37,41 @@ pub struct View2DState {
     /// The zoom and pan state, which is either a zoom/center or `Auto` which will fill the screen
     #[serde(skip)]
     zoom: ZoomState2D,
+
+    /// Static image shown underneath everything else, e.g. a floorplan.
+    pub background: Option<BackgroundImage>,
+
+    /// Show an overview of the whole scene in a corner when zoomed in, see [`minimap_ui`].
+    pub show_minimap: bool,
+}
+
+/// A static image from disk, drawn underneath the logged 2D data to give it context.
+///
+/// For instance the floorplan of a warehouse, with the positions of the robots logged on top.
+#[derive(Clone, serde::Deserialize, serde::Serialize)]
+pub struct BackgroundImage {
+    pub path: std::path::PathBuf,
+
+    /// Where the top-left corner of the image is, in scene units.
+    pub origin: Pos2,
+
+    /// How many scene units one pixel of the image covers, e.g. meters per pixel.
+    pub scale: f32,
+}
+
+impl BackgroundImage {
+    pub fn new(path: std::path::PathBuf) -> Self {
+        Self {
+            path,
+            origin: Pos2::ZERO,
+            scale: 1.0,
+        }
+    }
 }
 
+/// Push background images behind anything that was logged.
+const BACKGROUND_DEPTH_OFFSET: re_renderer::DepthOffset = -100;
+
 #[derive(Clone, Copy)]
 /// Sub-state specific to the Zoom/Scale/Pan engine
 pub enum ZoomState2D {
@@ -59,6 +95,128 @@ impl Default for ZoomState2D {
 }
 
 impl View2DState {
+    /// Go back to showing the whole scene, as it grows.
+    pub fn reset_zoom(&mut self) {
+        self.zoom = ZoomState2D::Auto;
+    }
+
+    /// The scale (ui points per scene unit) and center we are zoomed in at,
+    /// or `None` if we show the whole scene.
+    pub(super) fn scale_and_center(&self) -> Option<(f32, Pos2)> {
+        match self.zoom {
+            ZoomState2D::Auto => None,
+            ZoomState2D::Scaled { scale, center, .. } => Some((scale, center)),
+        }
+    }
+
+    /// Jump to the zoom of another view, see [`super::ui::CameraSyncGroup`].
+    pub(super) fn set_scale_and_center(&mut self, scale_and_center: Option<(f32, Pos2)>) {
+        self.zoom = match scale_and_center {
+            None => ZoomState2D::Auto,
+            Some((scale, center)) => ZoomState2D::Scaled {
+                scale,
+                center,
+                accepting_scroll: false,
+            },
+        };
+    }
+
+    /// Adds the background image (if any) to the scene.
+    ///
+    /// Returns the area covered by the image, in scene units.
+    pub fn add_background_to_scene(
+        &self,
+        ctx: &mut ViewerContext<'_>,
+        scene: &mut SceneSpatial,
+    ) -> Option<Rect> {
+        let background = self.background.as_ref()?;
+        let texture = ctx
+            .cache
+            .background_images
+            .get(&background.path, ctx.render_ctx)?;
+
+        let size = vec2(texture.width as f32, texture.height as f32) * background.scale;
+        let rect = Rect::from_min_size(background.origin, size);
+
+        scene
+            .primitives
+            .textured_rectangles
+            .push(re_renderer::renderer::TexturedRect {
+                top_left_corner_position: glam::vec3(rect.min.x, rect.min.y, 0.0),
+                extent_u: glam::Vec3::X * size.x,
+                extent_v: glam::Vec3::Y * size.y,
+                texture: texture.texture.clone(),
+                texture_filter_magnification: re_renderer::renderer::TextureFilterMag::Linear,
+                texture_filter_minification: re_renderer::renderer::TextureFilterMin::Linear,
+                multiplicative_tint: egui::Rgba::WHITE,
+                depth_offset: BACKGROUND_DEPTH_OFFSET,
+            });
+        // Not pickable:
+        scene
+            .primitives
+            .textured_rectangles_ids
+            .push(InstancePathHash::NONE);
+
+        Some(rect)
+    }
+
+    pub fn background_ui(&mut self, ui: &mut egui::Ui) {
+        ui.vertical(|ui| {
+            ui.horizontal(|ui| {
+                if let Some(background) = &self.background {
+                    ui.label(
+                        background
+                            .path
+                            .file_name()
+                            .map_or_else(String::new, |name| name.to_string_lossy().to_string()),
+                    )
+                    .on_hover_text(background.path.display().to_string());
+                    if ui.small_button("Remove").clicked() {
+                        self.background = None;
+                    }
+                } else {
+                    ui.weak("None");
+                }
+
+                #[cfg(not(target_arch = "wasm32"))]
+                if ui.small_button("Choose…").clicked() {
+                    if let Some(path) = rfd::FileDialog::new()
+                        .add_filter("image", &["png", "jpg", "jpeg"])
+                        .pick_file()
+                    {
+                        let mut background = BackgroundImage::new(path);
+                        if let Some(previous) = &self.background {
+                            // Keep the mapping when swapping in e.g. an updated floorplan.
+                            background.origin = previous.origin;
+                            background.scale = previous.scale;
+                        }
+                        self.background = Some(background);
+                    }
+                }
+            });
+
+            if let Some(background) = &mut self.background {
+                ui.horizontal(|ui| {
+                    ui.label("Origin");
+                    ui.add(egui::DragValue::new(&mut background.origin.x).speed(0.1));
+                    ui.add(egui::DragValue::new(&mut background.origin.y).speed(0.1));
+                })
+                .response
+                .on_hover_text("Position of the top-left corner of the image, in scene units.");
+                ui.horizontal(|ui| {
+                    ui.label("Scale");
+                    ui.add(
+                        egui::DragValue::new(&mut background.scale)
+                            .speed(0.001)
+                            .clamp_range(1e-6..=f32::INFINITY),
+                    );
+                })
+                .response
+                .on_hover_text("Size of one image pixel in scene units, e.g. meters per pixel.");
+            }
+        });
+    }
+
     /// Determine the optimal sub-region and size based on the `ZoomState` and
     /// available size. This will generally be used to construct the painter and
     /// subsequent transforms
@@ -238,6 +396,13 @@ pub fn view_2d(
 
     // Save off the available_size since this is used for some of the layout updates later
     let available_size = ui.available_size();
+    let view_rect = ui.available_rect_before_wrap();
+
+    // The scene is consumed by the main view, so keep what the minimap needs.
+    let minimap_rectangles = (state.state_2d.show_minimap
+        && matches!(state.state_2d.zoom, ZoomState2D::Scaled { .. }))
+    .then(|| scene.primitives.textured_rectangles.clone());
+    let minimap_auto_size_config = state.auto_size_config(Vec2::splat(MINIMAP_SIZE));
 
     let (desired_size, offset) = state
         .state_2d
@@ -272,9 +437,121 @@ pub fn view_2d(
     state
         .state_2d
         .capture_scroll(scroll_out.state.offset, available_size, scene_rect_accum);
+
+    if let Some(textured_rectangles) = minimap_rectangles {
+        minimap_ui(
+            ctx,
+            ui,
+            &mut state.state_2d,
+            view_rect,
+            scene_rect_accum,
+            &textured_rectangles,
+            minimap_auto_size_config,
+            space_view_id,
+        );
+    }
+
     scroll_out.inner
 }
 
+/// The length of the longest side of the minimap, in ui points.
+const MINIMAP_SIZE: f32 = 160.0;
+
+/// An overview of the whole scene in the top right corner of the view, with the visible part outlined.
+///
+/// Click or drag in it to move the view there.
+/// Only images are drawn in it, since that is what large 2D scenes
+/// (stitched maps, whole-slide images, …) mostly consist of.
+#[allow(clippy::too_many_arguments)]
+fn minimap_ui(
+    ctx: &mut ViewerContext<'_>,
+    ui: &egui::Ui,
+    state_2d: &mut View2DState,
+    view_rect: Rect,
+    scene_rect_accum: Rect,
+    textured_rectangles: &[re_renderer::renderer::TexturedRect],
+    auto_size_config: re_renderer::AutoSizeConfig,
+    space_view_id: SpaceViewId,
+) {
+    let ZoomState2D::Scaled { scale, center, .. } = state_2d.zoom else {
+        return; // We already see the whole scene.
+    };
+    if !scene_rect_accum.is_positive() {
+        return;
+    }
+
+    let margin = 8.0;
+    let size = scene_rect_accum.size() * (MINIMAP_SIZE / scene_rect_accum.size().max_elem());
+    let minimap_rect = Rect::from_min_size(
+        pos2(
+            view_rect.right() - margin - size.x,
+            view_rect.top() + margin,
+        ),
+        size,
+    );
+    let ui_from_space = RectTransform::from_to(scene_rect_accum, minimap_rect);
+    let space_from_ui = ui_from_space.inverse();
+
+    // An area of its own, so the view underneath doesn't also react to the clicks and drags.
+    egui::Area::new(egui::Id::new("minimap").with(space_view_id))
+        .order(egui::Order::Middle)
+        .fixed_pos(minimap_rect.min)
+        .show(ui.ctx(), |ui| {
+            let (response, painter) = ui.allocate_painter(size, egui::Sense::click_and_drag());
+            let painter = painter.with_clip_rect(minimap_rect);
+            let background = ui.visuals().extreme_bg_color;
+
+            let space_from_pixel = space_from_ui.scale().y / painter.ctx().pixels_per_point();
+            let callback = setup_target_config(
+                &painter,
+                space_from_ui,
+                space_from_pixel,
+                "minimap",
+                auto_size_config,
+                ctx.app_options.render_quality(),
+            )
+            .and_then(|target_config| {
+                create_textured_rects_paint_callback(
+                    ctx.render_ctx,
+                    target_config,
+                    minimap_rect,
+                    textured_rectangles,
+                    background.into(),
+                )
+            });
+            match callback {
+                Ok(callback) => {
+                    painter.add(callback);
+                }
+                Err(err) => {
+                    re_log::warn_once!("Failed to draw minimap: {err}");
+                    painter.rect_filled(minimap_rect, 0.0, background);
+                }
+            }
+
+            let visible_rect = Rect::from_center_size(center, view_rect.size() / scale);
+            painter.rect_stroke(
+                ui_from_space.transform_rect(visible_rect),
+                0.0,
+                (1.5, ui.visuals().selection.stroke.color),
+            );
+            painter.rect_stroke(
+                minimap_rect,
+                0.0,
+                ui.visuals().widgets.noninteractive.bg_stroke,
+            );
+
+            if let Some(pointer_pos) = response.interact_pointer_pos() {
+                state_2d.zoom = ZoomState2D::Scaled {
+                    scale,
+                    center: space_from_ui.transform_pos(pointer_pos),
+                    accepting_scroll: false,
+                };
+            }
+            response.on_hover_cursor(egui::CursorIcon::Grab);
+        });
+}
+
 /// Create the real 2D view inside the scrollable area
 #[allow(clippy::too_many_arguments)]
 fn view_2d_scrollable(
@@ -326,6 +603,8 @@ fn view_2d_scrollable(
             &Eye {
                 world_from_view: IsoTransform::IDENTITY,
                 fov_y: None,
+                orthographic: None,
+                clip_range: None,
             },
             hover_radius,
         );
@@ -396,6 +675,8 @@ fn view_2d_scrollable(
                                         &tensor_view,
                                         center,
                                         image.meter,
+                                        image.unit.as_ref(),
+                                        &ctx.app_options.number_format,
                                     );
                                 });
                             }
@@ -437,6 +718,7 @@ fn view_2d_scrollable(
             space_from_pixel,
             &space.to_string(),
             state.auto_size_config(response.rect.size()),
+            ctx.app_options.render_quality(),
         ) else {
             return response;
         };
@@ -552,6 +834,7 @@ fn setup_target_config(
     space_from_pixel: f32,
     space_name: &str,
     auto_size_config: re_renderer::AutoSizeConfig,
+    render_quality: crate::RenderQuality,
 ) -> anyhow::Result<TargetConfiguration> {
     let pixels_from_points = painter.ctx().pixels_per_point();
     let resolution_in_pixel = get_viewport(painter.clip_rect(), pixels_from_points);
@@ -574,6 +857,10 @@ fn setup_target_config(
             },
             pixels_from_point: pixels_from_points,
             auto_size_config,
+            lighting: Default::default(),
+            resolution_scale: render_quality.resolution_scale(),
+            fast_point_splatting: render_quality.fast_point_splatting(),
+            outline_config: None, // Set by `create_scene_paint_callback` if anything is hovered or selected.
         }
     })
 }
@@ -622,7 +909,10 @@ fn show_projections_from_3d_space(
                     ));
                     shapes.push(Shape::circle_filled(pos_in_ui, radius, Color32::WHITE));
 
-                    let text = format!("Depth: {:.3} m", pos_2d.z);
+                    let text = format!(
+                        "Depth: {} m",
+                        ctx.app_options.number_format.format_f32(pos_2d.z)
+                    );
                     let font_id = egui::TextStyle::Body.resolve(ui.style());
                     let galley =
                         ui.fonts(|fonts| fonts.layout_no_wrap(text, font_id, Color32::WHITE));
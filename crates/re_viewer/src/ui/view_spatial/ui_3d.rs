@@ -5,7 +5,7 @@ use macaw::{vec3, BoundingBox, Quat, Vec3};
 use re_data_store::{InstancePath, InstancePathHash};
 use re_log_types::{EntityPath, ViewCoordinates};
 use re_renderer::{
-    view_builder::{Projection, TargetConfiguration},
+    view_builder::{Lighting, OrthographicCameraMode, Projection, TargetConfiguration},
     RenderContext, Size,
 };
 
@@ -24,12 +24,40 @@ use crate::{
 };
 
 use super::{
-    eye::{Eye, OrbitEye},
+    eye::{ClipRange, Eye, OrbitEye},
     ViewSpatialState,
 };
 
 // ---
 
+/// The directional light of the 3D view.
+#[derive(Clone, Copy, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct LightSettings {
+    /// Direction the light comes from around the up axis, in degrees.
+    pub azimuth: f32,
+
+    /// Height of the light above the horizon, in degrees.
+    pub elevation: f32,
+
+    /// Brightness of what the light doesn't reach, from 0 to 1.
+    pub ambient: f32,
+
+    /// Let meshes cast shadows.
+    pub shadows: bool,
+}
+
+impl Default for LightSettings {
+    fn default() -> Self {
+        Self {
+            azimuth: 60.0,
+            elevation: 45.0,
+            ambient: 0.2,
+            shadows: false,
+        }
+    }
+}
+
 #[derive(Clone, serde::Deserialize, serde::Serialize)]
 #[serde(default)]
 pub struct View3DState {
@@ -52,9 +80,27 @@ pub struct View3DState {
     pub spin: bool,
     pub show_axes: bool,
 
+    /// Show the scene without perspective, e.g. for measuring and aligning geometry.
+    /// Ignored while tracking a camera.
+    pub orthographic: bool,
+
+    /// Hide everything closer to or further from the camera than this,
+    /// to look inside dense scenes.
+    pub clip_range: Option<ClipRange>,
+
+    pub light: LightSettings,
+
     #[serde(skip)]
     last_eye_interact_time: f64,
 
+    /// Until the user moves the camera, we keep the whole scene in view as data arrives,
+    /// so that there is something to see from the start.
+    has_user_moved_eye: bool,
+
+    /// The scene bounds we last framed because of that.
+    #[serde(skip)]
+    auto_fitted_bbox: Option<BoundingBox>,
+
     /// Filled in at the start of each frame
     #[serde(skip)]
     pub(crate) space_specs: SpaceSpecs,
@@ -72,7 +118,12 @@ impl Default for View3DState {
             hovered_point: Default::default(),
             spin: false,
             show_axes: false,
+            orthographic: false,
+            clip_range: None,
+            light: Default::default(),
             last_eye_interact_time: f64::NEG_INFINITY,
+            has_user_moved_eye: false,
+            auto_fitted_bbox: None,
             space_specs: Default::default(),
             space_camera: Default::default(),
         }
@@ -80,10 +131,144 @@ impl Default for View3DState {
 }
 
 impl View3DState {
+    /// Frame the whole scene, and keep doing so as it grows until the user moves the camera.
     pub fn reset_camera(&mut self, scene_bbox_accum: &BoundingBox) {
         self.interpolate_to_eye(default_eye(scene_bbox_accum, &self.space_specs).to_eye());
         self.tracked_camera = None;
         self.camera_before_tracked_camera = None;
+        self.has_user_moved_eye = false;
+        self.auto_fitted_bbox = Some(*scene_bbox_accum);
+    }
+
+    /// Frame the scene if it has grown well beyond what we framed last,
+    /// e.g. when the first data arrives.
+    fn auto_fit(&mut self, scene_bbox_accum: &BoundingBox) {
+        if scene_bbox_accum.is_nothing() || scene_bbox_accum.is_nan() {
+            return;
+        }
+
+        let is_framed = self.auto_fitted_bbox.map_or(false, |fitted| {
+            let margin = 0.25 * fitted.size();
+            (fitted.min - margin).cmple(scene_bbox_accum.min).all()
+                && scene_bbox_accum.max.cmple(fitted.max + margin).all()
+        });
+        if !is_framed {
+            self.auto_fitted_bbox = Some(*scene_bbox_accum);
+            self.interpolate_to_orbit_eye(default_eye(scene_bbox_accum, &self.space_specs));
+        }
+    }
+
+    /// Jump to the camera of another view, see [`super::ui::CameraSyncGroup`].
+    pub(super) fn follow_orbit_eye(&mut self, orbit_eye: OrbitEye) {
+        self.orbit_eye = Some(orbit_eye);
+        self.eye_interpolation = None;
+        self.tracked_camera = None;
+        self.camera_before_tracked_camera = None;
+        self.has_user_moved_eye = true;
+    }
+
+    /// Look at the orbit center along one of the axes of the space.
+    pub fn look_from(&mut self, view: AxisView) {
+        let mut target = match self.orbit_eye {
+            Some(orbit_eye) => orbit_eye,
+            None => return,
+        };
+
+        let (up, right, fwd) = self.space_specs.up_right_forward();
+        let (look_dir, view_up) = match view {
+            AxisView::Top => (-up, fwd),
+            AxisView::Front => (fwd, up),
+            AxisView::Side => (-right, up),
+        };
+        target.world_from_view_rot =
+            Quat::from_affine3(&Affine3A::look_at_rh(Vec3::ZERO, look_dir, view_up).inverse());
+        target.velocity = Vec3::ZERO;
+
+        self.tracked_camera = None;
+        self.camera_before_tracked_camera = None;
+        self.has_user_moved_eye = true;
+        self.interpolate_to_orbit_eye(target);
+    }
+
+    pub fn clip_range_ui(&mut self, ui: &mut egui::Ui) {
+        let orbit_radius = self.orbit_eye.map_or(1.0, |eye| eye.orbit_radius);
+
+        ui.vertical(|ui| {
+            let mut clip = self.clip_range.is_some();
+            if ui.checkbox(&mut clip, "Clip").changed() {
+                self.clip_range = clip.then(|| ClipRange {
+                    near: 0.5 * orbit_radius,
+                    far: 1.5 * orbit_radius,
+                });
+            }
+
+            if let Some(clip_range) = &mut self.clip_range {
+                let speed = 0.005 * orbit_radius;
+                let (near, far) = (clip_range.near, clip_range.far);
+                ui.horizontal(|ui| {
+                    ui.label("Near");
+                    ui.add(
+                        egui::DragValue::new(&mut clip_range.near)
+                            .clamp_range(0.0..=far)
+                            .speed(speed),
+                    )
+                    .on_hover_text("Distance from the camera");
+                    ui.label("Far");
+                    ui.add(
+                        egui::DragValue::new(&mut clip_range.far)
+                            .clamp_range(near..=f32::INFINITY)
+                            .speed(speed),
+                    )
+                    .on_hover_text("Distance from the camera");
+                });
+            }
+        });
+    }
+
+    pub fn light_ui(&mut self, ui: &mut egui::Ui) {
+        let light = &mut self.light;
+        ui.vertical(|ui| {
+            ui.horizontal(|ui| {
+                ui.label("Azimuth");
+                ui.add(
+                    egui::DragValue::new(&mut light.azimuth)
+                        .clamp_range(0.0..=360.0)
+                        .suffix("°"),
+                )
+                .on_hover_text("Direction the light comes from, around the up axis");
+                ui.label("Elevation");
+                ui.add(
+                    egui::DragValue::new(&mut light.elevation)
+                        .clamp_range(0.0..=90.0)
+                        .suffix("°"),
+                )
+                .on_hover_text("Height of the light above the horizon");
+            });
+            ui.horizontal(|ui| {
+                ui.label("Ambient");
+                ui.add(egui::Slider::new(&mut light.ambient, 0.0..=1.0))
+                    .on_hover_text("Brightness of what the light doesn't reach");
+            });
+            ui.checkbox(&mut light.shadows, "Shadows")
+                .on_hover_text("Let meshes cast shadows");
+        });
+    }
+
+    /// The lighting to render the scene with.
+    fn lighting(&self, scene_bbox_accum: &BoundingBox) -> Lighting {
+        let (up, right, fwd) = self.space_specs.up_right_forward();
+        let azimuth = self.light.azimuth.to_radians();
+        let elevation = self.light.elevation.to_radians();
+        let horizontal = azimuth.cos() * right + azimuth.sin() * fwd;
+
+        Lighting {
+            light_direction: elevation.cos() * horizontal + elevation.sin() * up,
+            ambient_light: self.light.ambient,
+            shadow_bounds: (self.light.shadows
+                && !scene_bbox_accum.is_nothing()
+                && !scene_bbox_accum.is_nan())
+            .then_some(*scene_bbox_accum),
+        }
     }
 
     fn update_eye(
@@ -110,6 +295,10 @@ impl View3DState {
             }
         }
 
+        if !self.has_user_moved_eye && self.tracked_camera.is_none() {
+            self.auto_fit(scene_bbox_accum);
+        }
+
         let orbit_camera = self
             .orbit_eye
             .get_or_insert_with(|| default_eye(scene_bbox_accum, &self.space_specs));
@@ -177,6 +366,28 @@ impl View3DState {
     }
 }
 
+/// Axis-aligned views of a 3D scene, relative to its up and right axes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AxisView {
+    Top,
+    Front,
+    Side,
+}
+
+impl AxisView {
+    pub const ALL: [Self; 3] = [Self::Top, Self::Front, Self::Side];
+}
+
+impl std::fmt::Display for AxisView {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Top => "Top",
+            Self::Front => "Front",
+            Self::Side => "Side",
+        })
+    }
+}
+
 #[derive(Clone)]
 struct EyeInterpolation {
     elapsed_time: f32,
@@ -205,6 +416,19 @@ pub struct SpaceSpecs {
 }
 
 impl SpaceSpecs {
+    /// Orthonormal up, right and forward directions of the space (Z up if unknown).
+    fn up_right_forward(&self) -> (Vec3, Vec3, Vec3) {
+        let up = self.up.unwrap_or(Vec3::Z).normalize();
+        let any_right = if up.dot(Vec3::X).abs() < 0.9 {
+            Vec3::X
+        } else {
+            Vec3::Y
+        };
+        let right = self.right.unwrap_or(any_right);
+        let right = (right - up * right.dot(up)).normalize();
+        (up, right, up.cross(right))
+    }
+
     pub fn from_view_coordinates(coordinates: Option<ViewCoordinates>) -> Self {
         let up = (|| Some(coordinates?.up()?.as_vec3().into()))();
         let right = (|| Some(coordinates?.right()?.as_vec3().into()))();
@@ -274,10 +498,24 @@ pub fn view_3d(
     let did_interact_with_eye = orbit_eye.interact(&response, orbit_eye_drag_threshold);
 
     let orbit_eye = *orbit_eye;
-    let eye = orbit_eye.to_eye();
+    let mut eye = if state.state_3d.orthographic && state.state_3d.tracked_camera.is_none() {
+        orbit_eye.to_orthographic_eye(&state.scene_bbox_accum)
+    } else {
+        orbit_eye.to_eye()
+    };
+    if let Some(clip_range) = state.state_3d.clip_range {
+        // Measured from where the perspective eye would be, so that switching projection
+        // doesn't move the clip planes.
+        let offset = (orbit_eye.position() - eye.pos_in_world()).dot(eye.forward_in_world());
+        eye.clip(ClipRange {
+            near: clip_range.near + offset,
+            far: clip_range.far + offset,
+        });
+    }
 
     if did_interact_with_eye {
         state.state_3d.last_eye_interact_time = ui.input(|i| i.time);
+        state.state_3d.has_user_moved_eye = true;
         state.state_3d.eye_interpolation = None;
         state.state_3d.tracked_camera = None;
         state.state_3d.camera_before_tracked_camera = None;
@@ -346,6 +584,8 @@ pub fn view_3d(
                                         &tensor_view,
                                         center,
                                         image.meter,
+                                        image.unit.as_ref(),
+                                        &ctx.app_options.number_format,
                                     );
                                 });
                             }
@@ -401,6 +641,7 @@ pub fn view_3d(
                     new_orbit_eye.orbit_radius = new_orbit_eye.position().distance(clicked_point);
                     new_orbit_eye.orbit_center = clicked_point;
                     state.state_3d.interpolate_to_orbit_eye(new_orbit_eye);
+                    state.state_3d.has_user_moved_eye = true;
                 }
             }
         }
@@ -477,6 +718,8 @@ pub fn view_3d(
         ctx.render_ctx,
         &space.to_string(),
         state.auto_size_config(rect.size()),
+        state.state_3d.lighting(&state.scene_bbox_accum),
+        ctx.app_options.render_quality(),
     );
 }
 
@@ -488,6 +731,8 @@ fn paint_view(
     render_ctx: &mut RenderContext,
     name: &str,
     auto_size_config: re_renderer::AutoSizeConfig,
+    lighting: Lighting,
+    render_quality: crate::RenderQuality,
 ) {
     crate::profile_function!();
 
@@ -503,13 +748,25 @@ fn paint_view(
         resolution_in_pixel,
 
         view_from_world: eye.world_from_view.inverse(),
-        projection_from_view: Projection::Perspective {
-            vertical_fov: eye.fov_y.unwrap(),
-            near_plane_distance: eye.near(),
+        projection_from_view: match eye.orthographic {
+            Some(extent) => Projection::Orthographic {
+                camera_mode: OrthographicCameraMode::NearPlaneCenter,
+                vertical_world_size: extent.vertical_world_size,
+                far_plane_distance: extent.far,
+            },
+            None => Projection::Perspective {
+                vertical_fov: eye.fov_y.unwrap(),
+                near_plane_distance: eye.near(),
+                far_plane_distance: eye.far(),
+            },
         },
 
         pixels_from_point,
         auto_size_config,
+        lighting,
+        resolution_scale: render_quality.resolution_scale(),
+        fast_point_splatting: render_quality.fast_point_splatting(),
+        outline_config: None, // Set by `create_scene_paint_callback` if anything is hovered or selected.
     };
 
     let Ok(callback) = create_scene_paint_callback(
@@ -1,12 +1,27 @@
 use egui::mutex::Mutex;
 use re_renderer::{
-    renderer::{GenericSkyboxDrawData, MeshDrawData, RectangleDrawData},
-    view_builder::{TargetConfiguration, ViewBuilder},
+    renderer::{GenericSkyboxDrawData, MeshDrawData, RectangleDrawData, TexturedRect},
+    view_builder::{OutlineConfig, OutlineLayer, TargetConfiguration, ViewBuilder},
     RenderContext,
 };
 
 use super::scene::SceneSpatialPrimitives;
 
+/// Selected instances are outlined in this layer, see [`SceneSpatialPrimitives::selection_outlines`].
+const SELECTION_OUTLINE_LAYER: OutlineLayer = OutlineLayer::A;
+
+/// Hovered instances are outlined in this layer, on top of the selection outlines.
+const HOVER_OUTLINE_LAYER: OutlineLayer = OutlineLayer::B;
+
+/// How hovered and selected instances are outlined.
+fn outline_config(pixels_from_point: f32) -> OutlineConfig {
+    OutlineConfig {
+        outline_radius_pixel: 2.0 * pixels_from_point,
+        color_layer_a: egui::Color32::from_rgb(255, 170, 0).into(),
+        color_layer_b: re_renderer::Rgba::from_white_alpha(0.8),
+    }
+}
+
 pub fn get_viewport(clip_rect: egui::Rect, pixels_from_point: f32) -> [u32; 2] {
     let min = (clip_rect.min.to_vec2() * pixels_from_point).round();
     let max = (clip_rect.max.to_vec2() * pixels_from_point).round();
@@ -33,6 +48,28 @@ pub fn create_scene_paint_callback(
     ))
 }
 
+/// Like [`create_scene_paint_callback`], but only draws the given rectangles, e.g. for a minimap.
+pub fn create_textured_rects_paint_callback(
+    render_ctx: &mut RenderContext,
+    target_config: TargetConfiguration,
+    clip_rect: egui::Rect,
+    textured_rectangles: &[TexturedRect],
+    clear_color: re_renderer::Rgba,
+) -> anyhow::Result<egui::PaintCallback> {
+    let pixels_from_point = target_config.pixels_from_point;
+    let mut view_builder = ViewBuilder::default();
+    view_builder.setup_view(render_ctx, target_config)?;
+    view_builder.queue_draw(&RectangleDrawData::new(render_ctx, textured_rectangles)?);
+    let command_buffer = view_builder.draw(render_ctx, clear_color)?;
+    Ok(renderer_paint_callback(
+        render_ctx,
+        command_buffer,
+        view_builder,
+        clip_rect,
+        pixels_from_point,
+    ))
+}
+
 pub enum ScreenBackground {
     GenericSkybox,
     ClearColor(re_renderer::Rgba),
@@ -40,10 +77,17 @@ pub enum ScreenBackground {
 
 fn create_and_fill_view_builder(
     render_ctx: &mut RenderContext,
-    target_config: TargetConfiguration,
+    mut target_config: TargetConfiguration,
     primitives: SceneSpatialPrimitives,
     background: &ScreenBackground,
 ) -> anyhow::Result<(wgpu::CommandBuffer, ViewBuilder)> {
+    // Outlines need extra render targets, so only enable them if there is something to outline.
+    let has_outlines =
+        !primitives.selection_outlines.is_empty() || !primitives.hover_outlines.is_empty();
+    if has_outlines {
+        target_config.outline_config = Some(outline_config(target_config.pixels_from_point));
+    }
+
     let mut view_builder = ViewBuilder::default();
     view_builder.setup_view(render_ctx, target_config)?;
 
@@ -60,6 +104,24 @@ fn create_and_fill_view_builder(
         view_builder.queue_draw(&GenericSkyboxDrawData::new(render_ctx));
     }
 
+    if has_outlines {
+        for (layer, outlines) in [
+            (SELECTION_OUTLINE_LAYER, primitives.selection_outlines),
+            (HOVER_OUTLINE_LAYER, primitives.hover_outlines),
+        ] {
+            view_builder
+                .queue_outline_draw(layer, &MeshDrawData::new(render_ctx, &outlines.meshes)?)
+                .queue_outline_draw(layer, &outlines.line_strips.to_draw_data(render_ctx))
+                .queue_outline_draw(
+                    layer,
+                    &RectangleDrawData::new(render_ctx, &outlines.textured_rectangles)?,
+                );
+            if let Some(points) = outlines.points {
+                view_builder.queue_outline_draw(layer, &points.to_draw_data(render_ctx)?);
+            }
+        }
+    }
+
     let command_buffer = view_builder.draw(
         render_ctx,
         match background {
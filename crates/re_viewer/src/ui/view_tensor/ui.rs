@@ -2,7 +2,7 @@ use std::{collections::BTreeMap, fmt::Display};
 
 use eframe::emath::Align2;
 use egui::{epaint::TextShape, Color32, ColorImage, NumExt as _, Vec2};
-use half::f16;
+use half::{bf16, f16};
 use ndarray::{Axis, Ix2};
 
 use re_log_types::{component_types, ClassicTensor, TensorDataType};
@@ -303,6 +303,25 @@ fn tensor_ui(
             }
         },
 
+        TensorDataType::BF16 => match re_tensor_ops::as_ndarray::<bf16>(tensor) {
+            Ok(tensor) => {
+                let color_from_value = |value: bf16| {
+                    let (tensor_min, tensor_max) = range.unwrap_or((0.0, 1.0)); // the cache should provide the range
+                    color_mapping.color_from_normalized(egui::remap(
+                        value.to_f32(),
+                        tensor_min as f32..=tensor_max as f32,
+                        0.0..=1.0,
+                    ))
+                };
+
+                let slice = selected_tensor_slice(state, &tensor);
+                slice_ui(ctx, ui, state, tensor_shape, slice, color_from_value);
+            }
+            Err(err) => {
+                ui.label(ctx.re_ui.error_text(err.to_string()));
+            }
+        },
+
         TensorDataType::F32 => match re_tensor_ops::as_ndarray::<f32>(tensor) {
             Ok(tensor) => {
                 let color_from_value = |value: f32| {
@@ -54,13 +54,10 @@ impl SceneText {
             // Early filtering: if we're not showing it the view, there isn't much point
             // in querying it to begin with... at least for now.
             if !filters.is_entity_path_visible(ent_path) {
-                return;
+                continue;
             }
 
-            let query = re_arrow_store::RangeQuery::new(
-                query.timeline,
-                TimeRange::new(i64::MIN.into(), i64::MAX.into()),
-            );
+            let query = re_arrow_store::RangeQuery::new(query.timeline, TimeRange::EVERYTHING);
 
             let components = [
                 InstanceKey::name(),
@@ -83,7 +80,8 @@ impl SceneText {
                         // Early filtering once more, see above.
                         let is_visible = level
                             .as_ref()
-                            .map_or(true, |lvl| filters.is_log_level_visible(lvl));
+                            .map_or(true, |lvl| filters.is_log_level_visible(lvl))
+                            && filters.is_body_visible(&body);
 
                         if is_visible {
                             self.text_entries.push(TextEntry {
@@ -35,6 +35,7 @@ impl ViewTextState {
             col_log_level,
             row_entity_paths,
             row_log_levels,
+            body_contains: _,
         } = &mut self.filters;
 
         re_ui
@@ -107,6 +108,17 @@ pub(crate) fn view_text(
 
     state.latest_time = time;
 
+    ui.horizontal(|ui| {
+        ui.label("Search:");
+        ui.add(
+            egui::TextEdit::singleline(&mut state.filters.body_contains)
+                .hint_text("Only show messages containing…"),
+        );
+        if !state.filters.body_contains.is_empty() && ui.small_button("✖").clicked() {
+            state.filters.body_contains.clear();
+        }
+    });
+
     ui.with_layout(egui::Layout::top_down(egui::Align::Center), |ui| {
         egui::ScrollArea::horizontal().show(ui, |ui| {
             crate::profile_scope!("render table");
@@ -118,7 +130,6 @@ pub(crate) fn view_text(
 
 // --- Filters ---
 
-// TODO(cmc): implement "body contains <value>" filter.
 // TODO(cmc): beyond filters, it'd be nice to be able to swap columns at some point.
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
 pub struct ViewTextFilters {
@@ -131,6 +142,10 @@ pub struct ViewTextFilters {
     // Row filters: which rows should be visible?
     pub row_entity_paths: BTreeMap<EntityPath, bool>,
     pub row_log_levels: BTreeMap<String, bool>,
+
+    /// Only show entries whose body contains this, ignoring case.
+    #[serde(default)]
+    pub body_contains: String,
 }
 
 impl Default for ViewTextFilters {
@@ -141,6 +156,7 @@ impl Default for ViewTextFilters {
             col_timelines: Default::default(),
             row_entity_paths: Default::default(),
             row_log_levels: Default::default(),
+            body_contains: Default::default(),
         }
     }
 }
@@ -157,6 +173,13 @@ impl ViewTextFilters {
         self.row_log_levels.get(level).copied().unwrap_or(true)
     }
 
+    pub fn is_body_visible(&self, body: &str) -> bool {
+        self.body_contains.is_empty()
+            || body
+                .to_lowercase()
+                .contains(&self.body_contains.to_lowercase())
+    }
+
     // Checks whether new values are available for any of the filters, and updates everything
     // accordingly.
     fn update(&mut self, ctx: &mut ViewerContext<'_>, text_entries: &[TextEntry]) {
@@ -168,6 +191,7 @@ impl ViewTextFilters {
             col_log_level: _,
             row_entity_paths,
             row_log_levels,
+            body_contains: _,
         } = self;
 
         for timeline in ctx.log_db.timelines() {
@@ -352,7 +376,19 @@ fn table_ui(
                         text = text.color(Color32::from_rgba_unmultiplied(r, g, b, a));
                     }
 
-                    ui.label(text);
+                    // Clicking the body is a shortcut for clicking the time of the active timeline:
+                    let response = ui.add(egui::Label::new(text).sense(egui::Sense::click()));
+                    if let Some(row_time) = time_point.get(&global_timeline).copied() {
+                        if response
+                            .on_hover_text("Click to move the time cursor here")
+                            .clicked()
+                        {
+                            ctx.rec_cfg
+                                .time_ctrl
+                                .set_timeline_and_time(global_timeline, row_time);
+                            ctx.rec_cfg.time_ctrl.pause();
+                        }
+                    }
                 });
             });
         });
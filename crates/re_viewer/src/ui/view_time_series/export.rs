@@ -0,0 +1,271 @@
+//! Exporting a time series plot as an SVG or PNG for use in papers and reports:
+//! black on white, with configurable fonts and line widths.
+
+use std::fmt::Write as _;
+
+use super::{
+    scene::{PlotSeries, PlotSeriesKind},
+    ui::{ExportFormat, PlotExportOptions},
+};
+
+impl ExportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Svg => "svg",
+            Self::Png => "png",
+        }
+    }
+}
+
+/// The part of the plot to export, in the coordinates of the plot on screen.
+#[derive(Clone, Copy, Debug)]
+pub struct ExportBounds {
+    /// Subtracted from all times, to keep precision, see [`super::view_time_series`].
+    pub time_offset: i64,
+    pub min: [f64; 2],
+    pub max: [f64; 2],
+}
+
+/// Draw the plot as an SVG document.
+///
+/// `format_time` formats the (absolute) times of the x axis.
+pub fn plot_to_svg(
+    options: &PlotExportOptions,
+    lines: &[PlotSeries],
+    bounds: &ExportBounds,
+    x_axis_label: &str,
+    format_time: impl Fn(i64) -> String,
+) -> String {
+    let PlotExportOptions {
+        format: _,
+        size: [width, height],
+        pixels_per_point: _,
+        font_family,
+        font_size,
+        line_width_scale,
+        show_legend,
+    } = options;
+    let font_size = *font_size;
+
+    // Leave room for the tick labels and the axis label:
+    let left = 6.0 * font_size;
+    let right = width - font_size;
+    let top = font_size;
+    let bottom = height - 3.5 * font_size;
+
+    let [min_x, min_y] = bounds.min;
+    let [max_x, max_y] = bounds.max;
+    let to_x =
+        |x: f64| left + ((x - min_x) / (max_x - min_x).max(f64::EPSILON)) as f32 * (right - left);
+    let to_y =
+        |y: f64| bottom - ((y - min_y) / (max_y - min_y).max(f64::EPSILON)) as f32 * (bottom - top);
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}pt" height="{height}pt" viewBox="0 0 {width} {height}" font-family="{}" font-size="{font_size}">"#,
+        xml_escape(font_family)
+    );
+    svg.push_str("<rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n");
+    let _ = writeln!(
+        svg,
+        r#"<defs><clipPath id="plot-area"><rect x="{left}" y="{top}" width="{}" height="{}"/></clipPath></defs>"#,
+        right - left,
+        bottom - top
+    );
+
+    // Grid and tick labels:
+    let grid_style = r##"stroke="#d0d0d0" stroke-width="0.5""##;
+    for x in ticks(min_x, max_x, ((right - left) / (8.0 * font_size)) as usize) {
+        let sx = to_x(x);
+        let _ = writeln!(
+            svg,
+            r#"<line x1="{sx}" y1="{top}" x2="{sx}" y2="{bottom}" {grid_style}/>"#
+        );
+        let _ = writeln!(
+            svg,
+            r#"<text x="{sx}" y="{}" text-anchor="middle">{}</text>"#,
+            bottom + 1.3 * font_size,
+            xml_escape(&format_time(x.round() as i64 + bounds.time_offset))
+        );
+    }
+    let y_ticks = ticks(min_y, max_y, ((bottom - top) / (3.0 * font_size)) as usize);
+    let decimals = match y_ticks.as_slice() {
+        [first, second, ..] => (-(second - first).log10().floor()).max(0.0) as usize,
+        _ => 0,
+    };
+    for y in y_ticks {
+        let sy = to_y(y);
+        let _ = writeln!(
+            svg,
+            r#"<line x1="{left}" y1="{sy}" x2="{right}" y2="{sy}" {grid_style}/>"#
+        );
+        let _ = writeln!(
+            svg,
+            r#"<text x="{}" y="{sy}" text-anchor="end" dominant-baseline="middle">{y:.decimals$}</text>"#,
+            left - 0.4 * font_size
+        );
+    }
+    let _ = writeln!(
+        svg,
+        r#"<text x="{}" y="{}" text-anchor="middle">{}</text>"#,
+        0.5 * (left + right),
+        bottom + 2.8 * font_size,
+        xml_escape(x_axis_label)
+    );
+
+    // The data:
+    svg.push_str("<g clip-path=\"url(#plot-area)\">\n");
+    for line in lines {
+        let color = svg_color(line.color);
+        let width = line.width * line_width_scale;
+        let points = line
+            .points
+            .iter()
+            .map(|&(time, value)| (to_x((time - bounds.time_offset) as f64), to_y(value)));
+        match line.kind {
+            PlotSeriesKind::Continuous => {
+                let points = points
+                    .map(|(x, y)| format!("{x:.2},{y:.2}"))
+                    .collect::<Vec<_>>();
+                let _ = writeln!(
+                    svg,
+                    r#"<polyline points="{}" fill="none" stroke="{color}" stroke-width="{width}" stroke-linejoin="round"/>"#,
+                    points.join(" ")
+                );
+            }
+            PlotSeriesKind::Scatter => {
+                for (x, y) in points {
+                    let _ = writeln!(
+                        svg,
+                        r#"<circle cx="{x:.2}" cy="{y:.2}" r="{width}" fill="{color}"/>"#
+                    );
+                }
+            }
+        }
+    }
+    svg.push_str("</g>\n");
+
+    let _ = writeln!(
+        svg,
+        r#"<rect x="{left}" y="{top}" width="{}" height="{}" fill="none" stroke="black" stroke-width="0.75"/>"#,
+        right - left,
+        bottom - top
+    );
+
+    if *show_legend && !lines.is_empty() {
+        let row_height = 1.4 * font_size;
+        let legend_width = lines
+            .iter()
+            .map(|line| super::ui::legend_name(line).chars().count())
+            .max()
+            .unwrap_or(0) as f32
+            * 0.6
+            * font_size
+            + 3.0 * font_size;
+        let legend_left = right - legend_width - 0.5 * font_size;
+        let legend_top = top + 0.5 * font_size;
+        let _ = writeln!(
+            svg,
+            r##"<rect x="{legend_left}" y="{legend_top}" width="{legend_width}" height="{}" fill="white" stroke="#808080" stroke-width="0.5"/>"##,
+            lines.len() as f32 * row_height + 0.4 * font_size
+        );
+        for (i, line) in lines.iter().enumerate() {
+            let y = legend_top + (i as f32 + 0.5) * row_height + 0.2 * font_size;
+            let x = legend_left + 0.5 * font_size;
+            let _ = writeln!(
+                svg,
+                r#"<line x1="{x}" y1="{y}" x2="{}" y2="{y}" stroke="{}" stroke-width="{}"/>"#,
+                x + 1.5 * font_size,
+                svg_color(line.color),
+                (line.width * line_width_scale).max(1.0)
+            );
+            let _ = writeln!(
+                svg,
+                r#"<text x="{}" y="{y}" dominant-baseline="middle">{}</text>"#,
+                x + 2.0 * font_size,
+                xml_escape(&super::ui::legend_name(line))
+            );
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Render an SVG from [`plot_to_svg`] as a PNG, using the fonts installed on the system.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn svg_to_png(svg: &str, pixels_per_point: f32) -> anyhow::Result<Vec<u8>> {
+    use anyhow::Context as _;
+    use resvg::{tiny_skia, usvg};
+    use usvg_text_layout::TreeTextToPath as _;
+
+    let options = usvg::Options::default();
+    let mut tree = usvg::Tree::from_str(svg, &options).context("Failed to parse SVG")?;
+
+    let mut fontdb = usvg_text_layout::fontdb::Database::new();
+    fontdb.load_system_fonts();
+    tree.convert_text(&fontdb, options.keep_named_groups);
+
+    let size = tree
+        .size
+        .to_screen_size()
+        .scale_by(pixels_per_point as f64)
+        .context("Bad image size")?;
+    let mut pixmap =
+        tiny_skia::Pixmap::new(size.width(), size.height()).context("Bad image size")?;
+    resvg::render(
+        &tree,
+        usvg::FitTo::Zoom(pixels_per_point),
+        tiny_skia::Transform::default(),
+        pixmap.as_mut(),
+    )
+    .context("Failed to render SVG")?;
+
+    pixmap.encode_png().context("Failed to encode PNG")
+}
+
+/// Nicely rounded values (multiples of 1, 2 or 5 times a power of ten) in the given range.
+fn ticks(min: f64, max: f64, max_ticks: usize) -> Vec<f64> {
+    let span = max - min;
+    let max_ticks = max_ticks.max(2) as f64;
+    if !span.is_finite() || span <= 0.0 {
+        return vec![];
+    }
+
+    let magnitude = 10_f64.powf((span / max_ticks).log10().floor());
+    let step = [1.0, 2.0, 5.0, 10.0]
+        .iter()
+        .map(|factor| factor * magnitude)
+        .find(|step| span / step <= max_ticks)
+        .unwrap_or(10.0 * magnitude);
+
+    let first = (min / step).ceil() as i64;
+    let last = (max / step).floor() as i64;
+    (first..=last).map(|i| i as f64 * step).collect()
+}
+
+fn svg_color(color: egui::Color32) -> String {
+    let [r, g, b, a] = color.to_srgba_unmultiplied();
+    if a == 255 {
+        format!("#{r:02x}{g:02x}{b:02x}")
+    } else {
+        format!("rgba({r},{g},{b},{:.3})", a as f32 / 255.0)
+    }
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[test]
+fn test_ticks() {
+    let unit_ticks = ticks(0.0, 1.0, 5);
+    assert_eq!(unit_ticks.len(), 6);
+    assert!((unit_ticks[1] - 0.2).abs() < 1e-9 && (unit_ticks[5] - 1.0).abs() < 1e-9);
+    assert_eq!(ticks(-3.0, 12.0, 4), vec![0.0, 5.0, 10.0]);
+    assert!(ticks(1.0, 1.0, 5).is_empty());
+}
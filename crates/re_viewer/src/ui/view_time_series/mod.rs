@@ -1,5 +1,8 @@
+#[cfg(not(target_arch = "wasm32"))]
+mod export;
+
 mod scene;
 pub(crate) use self::scene::SceneTimeSeries;
 
 mod ui;
-pub(crate) use self::ui::{view_time_series, ViewTimeSeriesState, HELP_TEXT};
+pub(crate) use self::ui::{view_time_series, DecimationMode, ViewTimeSeriesState, HELP_TEXT};
@@ -2,7 +2,11 @@ use crate::{
     ui::{annotations::AnnotationMap, DefaultColor, SceneQuery},
     ViewerContext,
 };
+
+use super::{DecimationMode, ViewTimeSeriesState};
+use crate::misc::units::AngleUnit;
 use re_arrow_store::TimeRange;
+use re_data_store::MissingScalars;
 use re_log_types::{
     component_types::{self, InstanceKey},
     msg_bundle::Component,
@@ -56,6 +60,9 @@ pub struct PlotSeries {
     pub width: f32,
     pub kind: PlotSeriesKind,
     pub points: Vec<(i64, f64)>,
+
+    /// The unit of the values, after any unit conversions.
+    pub unit: Option<String>,
 }
 
 /// A scene for a time series plot, with everything needed to render it.
@@ -67,31 +74,43 @@ pub struct SceneTimeSeries {
 
 impl SceneTimeSeries {
     /// Loads all plots into the scene according to the given query.
-    pub(crate) fn load(&mut self, ctx: &mut ViewerContext<'_>, query: &SceneQuery<'_>) {
+    pub(crate) fn load(
+        &mut self,
+        ctx: &mut ViewerContext<'_>,
+        query: &SceneQuery<'_>,
+        state: &ViewTimeSeriesState,
+    ) {
         crate::profile_function!();
 
         self.annotation_map.load(ctx, query);
 
-        self.load_scalars(ctx, query);
+        self.load_scalars(ctx, query, state);
     }
 
-    fn load_scalars(&mut self, ctx: &mut ViewerContext<'_>, query: &SceneQuery<'_>) {
+    fn load_scalars(
+        &mut self,
+        ctx: &mut ViewerContext<'_>,
+        query: &SceneQuery<'_>,
+        state: &ViewTimeSeriesState,
+    ) {
         crate::profile_function!();
 
         let store = &ctx.log_db.entity_db.data_store;
+        let decimation = &state.decimation;
+        let decimation_interval_ns = decimation.interval_ns(query.timeline.typ());
 
         for entity_path in query.entity_paths {
             let ent_path = entity_path;
 
             let mut points = Vec::new();
+            let mut unit = None;
             let annotations = self.annotation_map.find(ent_path);
             let annotation_info = annotations.class_description(None).annotation_info();
             let default_color = DefaultColor::EntityPath(ent_path);
+            let props = query.entity_props_map.get(ent_path);
+            let missing_scalars = props.missing_scalars();
 
-            let query = re_arrow_store::RangeQuery::new(
-                query.timeline,
-                TimeRange::new(i64::MIN.into(), i64::MAX.into()),
-            );
+            let query = re_arrow_store::RangeQuery::new(query.timeline, TimeRange::EVERYTHING);
 
             let components = [
                 InstanceKey::name(),
@@ -100,19 +119,21 @@ impl SceneTimeSeries {
                 component_types::ColorRGBA::name(),
                 component_types::Radius::name(),
                 component_types::Label::name(),
+                component_types::Unit::name(),
             ];
-            let ent_views = range_entity_with_primary::<component_types::Scalar, 6>(
+            let ent_views = range_entity_with_primary::<component_types::Scalar, 7>(
                 store, &query, ent_path, components,
             );
 
             for (time, ent_view) in ent_views {
-                match ent_view.visit5(
+                match ent_view.visit6(
                     |_instance,
                      scalar: component_types::Scalar,
                      props: Option<component_types::ScalarPlotProps>,
                      color: Option<component_types::ColorRGBA>,
                      radius: Option<component_types::Radius>,
-                     label: Option<component_types::Label>| {
+                     label: Option<component_types::Label>,
+                     point_unit: Option<component_types::Unit>| {
                         // TODO(andreas): Support entity path
                         let color = annotation_info
                             .color(color.map(|c| c.to_array()).as_ref(), default_color);
@@ -120,9 +141,22 @@ impl SceneTimeSeries {
 
                         const DEFAULT_RADIUS: f32 = 0.75;
 
+                        if point_unit.is_some() {
+                            unit = point_unit; // The latest unit wins.
+                        }
+
+                        let mut value: f64 = scalar.into();
+                        if !value.is_finite() {
+                            match missing_scalars {
+                                MissingScalars::Gap => value = f64::NAN, // see `add_line_segments`
+                                MissingScalars::Connect => return,
+                                MissingScalars::Zero => value = 0.0,
+                            }
+                        }
+
                         points.push(PlotPoint {
                             time: time.unwrap().as_i64(), // scalars cannot be timeless
-                            value: scalar.into(),
+                            value,
                             attrs: PlotPointAttrs {
                                 label,
                                 color,
@@ -141,21 +175,47 @@ impl SceneTimeSeries {
 
             points.sort_by_key(|s| s.time);
 
+            let unit = unit.map(|unit| {
+                let (factor, unit) = state.unit_conversions.convert(unit.as_str());
+                if factor != 1.0 {
+                    for point in &mut points {
+                        point.value *= factor;
+                    }
+                }
+                unit
+            });
+
+            if props.angular() {
+                let is_degrees =
+                    unit.as_deref().and_then(AngleUnit::parse) == Some(AngleUnit::Degrees);
+                let period = if is_degrees {
+                    360.0
+                } else {
+                    std::f64::consts::TAU
+                };
+                points = wrap_angles(points, period);
+            }
+
+            if let Some(interval_ns) = decimation_interval_ns {
+                points = decimate(points, interval_ns, decimation.mode);
+            }
+
             if points.is_empty() {
                 continue;
             }
 
             // If all points within a line share the label (and it isn't `None`), then we use it
             // as the whole line label for the plot legend.
-            // Otherwise, we just use the entity path as-is.
+            // Otherwise, we just use the display name or entity path as-is.
             let same_label = |points: &[PlotPoint]| {
                 let label = points[0].attrs.label.as_ref();
                 (label.is_some() && points.iter().all(|p| p.attrs.label.as_ref() == label))
                     .then(|| label.cloned().unwrap())
             };
-            let line_label = same_label(&points).unwrap_or_else(|| entity_path.to_string());
+            let line_label = same_label(&points)
+                .unwrap_or_else(|| ctx.entity_display_names.name_or_path(entity_path));
 
-            self.add_line_segments(&line_label, points);
+            self.add_line_segments(&line_label, unit, points);
         }
     }
 
@@ -163,7 +223,13 @@ impl SceneTimeSeries {
     // segments.
     // A line segment is a continuous run of points with identical attributes: each time
     // we notice a change in attributes, we need a new line segment.
-    fn add_line_segments(&mut self, line_label: &str, points: Vec<PlotPoint>) {
+    // NaN points mark gaps: they end the current line segment without being plotted.
+    fn add_line_segments(
+        &mut self,
+        line_label: &str,
+        unit: Option<String>,
+        points: Vec<PlotPoint>,
+    ) {
         crate::profile_function!();
 
         let num_points = points.len();
@@ -178,10 +244,24 @@ impl SceneTimeSeries {
                 PlotSeriesKind::Continuous
             },
             points: Vec::with_capacity(num_points),
+            unit: unit.clone(),
         };
 
         for (i, p) in points.into_iter().enumerate() {
-            if p.attrs == attrs {
+            if p.value.is_nan() {
+                // Gap: end the current line segment, and don't connect it to the next one.
+                if !line.points.is_empty() {
+                    let next_line = PlotSeries {
+                        label: line_label.to_owned(),
+                        color: line.color,
+                        width: line.width,
+                        kind: line.kind,
+                        points: Vec::with_capacity(num_points - i),
+                        unit: unit.clone(),
+                    };
+                    self.lines.push(std::mem::replace(&mut line, next_line));
+                }
+            } else if p.attrs == attrs {
                 // Same attributes, just add to the current line segment.
 
                 line.points.push((p.time, p.value));
@@ -204,10 +284,13 @@ impl SceneTimeSeries {
                         width: 2.0 * attrs.radius,
                         kind,
                         points: Vec::with_capacity(num_points - i),
+                        unit: unit.clone(),
                     },
                 );
-                let prev_point = *prev_line.points.last().unwrap();
-                self.lines.push(prev_line);
+                let prev_point = prev_line.points.last().copied();
+                if !prev_line.points.is_empty() {
+                    self.lines.push(prev_line);
+                }
 
                 // If the previous point was continous and the current point is continuous
                 // too, then we want the 2 segments to appear continuous even though they
@@ -215,7 +298,9 @@ impl SceneTimeSeries {
                 let cur_continuous = matches!(kind, PlotSeriesKind::Continuous);
                 let prev_continuous = matches!(kind, PlotSeriesKind::Continuous);
                 if cur_continuous && prev_continuous {
-                    line.points.push(prev_point);
+                    if let Some(prev_point) = prev_point {
+                        line.points.push(prev_point);
+                    }
                 }
 
                 // Add the point that triggered the split to the new segment.
@@ -228,3 +313,201 @@ impl SceneTimeSeries {
         }
     }
 }
+
+/// Wraps angles to `[-period / 2, period / 2)`, and leaves a gap where they wrap around,
+/// instead of drawing a jump across the whole plot.
+fn wrap_angles(points: Vec<PlotPoint>, period: f64) -> Vec<PlotPoint> {
+    crate::profile_function!();
+
+    let mut wrapped = Vec::with_capacity(points.len());
+    let mut prev_value = None;
+
+    for mut point in points {
+        if point.value.is_finite() {
+            point.value = (point.value + 0.5 * period).rem_euclid(period) - 0.5 * period;
+            if let Some(prev_value) = prev_value {
+                let delta: f64 = point.value - prev_value;
+                if 0.5 * period < delta.abs() {
+                    wrapped.push(PlotPoint {
+                        value: f64::NAN, // a gap, see `add_line_segments`
+                        ..point.clone()
+                    });
+                }
+            }
+            prev_value = Some(point.value);
+        } else {
+            prev_value = None;
+        }
+        wrapped.push(point);
+    }
+
+    wrapped
+}
+
+/// Thins out points (sorted by time) to at most one ([`DecimationMode::Uniform`])
+/// or two ([`DecimationMode::MinMax`]) points per interval of `interval_ns`.
+fn decimate(points: Vec<PlotPoint>, interval_ns: i64, mode: DecimationMode) -> Vec<PlotPoint> {
+    crate::profile_function!();
+
+    if mode == DecimationMode::Off || interval_ns <= 1 {
+        return points;
+    }
+
+    let mut decimated = Vec::with_capacity(points.len().min(1024));
+    let mut interval_points: Vec<PlotPoint> = Vec::new();
+    let mut current_interval = None;
+
+    let flush = |interval_points: &mut Vec<PlotPoint>, decimated: &mut Vec<PlotPoint>| {
+        if interval_points.is_empty() {
+            return;
+        }
+        match mode {
+            DecimationMode::Off | DecimationMode::Uniform => {
+                decimated.push(interval_points.swap_remove(0));
+            }
+            DecimationMode::MinMax => {
+                let index_of = |ordering: std::cmp::Ordering| {
+                    (0..interval_points.len())
+                        .reduce(|best, i| {
+                            if interval_points[i]
+                                .value
+                                .total_cmp(&interval_points[best].value)
+                                == ordering
+                            {
+                                i
+                            } else {
+                                best
+                            }
+                        })
+                        .unwrap_or_default()
+                };
+                let min = index_of(std::cmp::Ordering::Less);
+                let max = index_of(std::cmp::Ordering::Greater);
+                decimated.push(interval_points[min.min(max)].clone());
+                if min != max {
+                    decimated.push(interval_points[min.max(max)].clone());
+                }
+            }
+        }
+        interval_points.clear();
+    };
+
+    for point in points {
+        if point.value.is_nan() {
+            // Keep gaps, so that they are never bridged.
+            flush(&mut interval_points, &mut decimated);
+            current_interval = None;
+            decimated.push(point);
+            continue;
+        }
+
+        let interval = point.time.div_euclid(interval_ns);
+        if current_interval != Some(interval) {
+            flush(&mut interval_points, &mut decimated);
+            current_interval = Some(interval);
+        }
+        interval_points.push(point);
+    }
+    flush(&mut interval_points, &mut decimated);
+
+    decimated
+}
+
+#[test]
+fn test_decimate() {
+    let attrs = PlotPointAttrs {
+        label: None,
+        color: egui::Color32::WHITE,
+        radius: 1.0,
+        scattered: false,
+    };
+    let points = [
+        (0, 1.0),
+        (1, 5.0),
+        (2, -3.0),
+        (3, 2.0),
+        (10, 7.0),
+        (11, 7.0),
+    ]
+    .into_iter()
+    .map(|(time, value)| PlotPoint {
+        time,
+        value,
+        attrs: attrs.clone(),
+    })
+    .collect::<Vec<_>>();
+    let decimated = |mode| {
+        decimate(points.clone(), 10, mode)
+            .into_iter()
+            .map(|point| (point.time, point.value))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(decimated(DecimationMode::Off).len(), points.len());
+    assert_eq!(
+        decimated(DecimationMode::Uniform),
+        vec![(0, 1.0), (10, 7.0)]
+    );
+    assert_eq!(
+        decimated(DecimationMode::MinMax),
+        vec![(1, 5.0), (2, -3.0), (10, 7.0)]
+    );
+}
+
+#[test]
+fn test_gaps_split_lines() {
+    let attrs = PlotPointAttrs {
+        label: None,
+        color: egui::Color32::WHITE,
+        radius: 1.0,
+        scattered: false,
+    };
+    let points = [1.0, 2.0, f64::NAN, f64::NAN, 3.0]
+        .into_iter()
+        .enumerate()
+        .map(|(time, value)| PlotPoint {
+            time: time as i64,
+            value,
+            attrs: attrs.clone(),
+        })
+        .collect();
+
+    let mut scene = SceneTimeSeries::default();
+    scene.add_line_segments("line", None, points);
+
+    let lines = scene
+        .lines
+        .iter()
+        .map(|line| line.points.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(lines, vec![vec![(0, 1.0), (1, 2.0)], vec![(4, 3.0)]]);
+}
+
+#[test]
+fn test_wrap_angles() {
+    let attrs = PlotPointAttrs {
+        label: None,
+        color: egui::Color32::WHITE,
+        radius: 1.0,
+        scattered: false,
+    };
+    let points = [170.0, 179.0, 181.0, 190.0, -530.0]
+        .into_iter()
+        .enumerate()
+        .map(|(time, value)| PlotPoint {
+            time: time as i64,
+            value,
+            attrs: attrs.clone(),
+        })
+        .collect();
+
+    let wrapped = wrap_angles(points, 360.0)
+        .into_iter()
+        .map(|point| (point.time, point.value))
+        .collect::<Vec<_>>();
+
+    assert_eq!(wrapped.len(), 6);
+    assert_eq!(wrapped[..2], [(0, 170.0), (1, 179.0)]);
+    assert!(wrapped[2].1.is_nan());
+    assert_eq!(wrapped[3..], [(2, -179.0), (3, -170.0), (4, -170.0)]);
+}
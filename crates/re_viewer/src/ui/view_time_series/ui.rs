@@ -4,9 +4,14 @@ use egui::{
 };
 
 use re_arrow_store::TimeType;
+use re_log_types::TimeFormatOptions;
 
 use crate::{
-    misc::format_time::next_grid_tick_magnitude_ns, ui::view_time_series::scene::PlotSeriesKind,
+    misc::{
+        format_time::next_grid_tick_magnitude_ns,
+        units::{AngleUnit, LengthUnit, UnitConversions},
+    },
+    ui::view_time_series::scene::{PlotSeries, PlotSeriesKind},
     ViewerContext,
 };
 
@@ -18,15 +23,329 @@ pub(crate) const HELP_TEXT: &str = "Pan by dragging, or scroll (+ shift = horizo
     Box zooming: Right click to zoom in and zoom out using a selection.\n\
     Zoom with ctrl / ⌘ + pointer wheel, or with pinch gesture.\n\
     Reset view with double-click.\n\
-    Right click to move the time cursor to the current position.";
+    Right click to move the time cursor to the current position, or drag the time cursor.";
+
+/// How close to the time cursor the pointer needs to be to drag it, in points.
+const TIME_CURSOR_GRAB_RADIUS: f32 = 4.0;
 
 #[derive(Clone, Default, serde::Deserialize, serde::Serialize)]
-pub struct ViewTimeSeriesState;
+#[serde(default)]
+pub struct ViewTimeSeriesState {
+    pub decimation: Decimation,
+    pub unit_conversions: UnitConversions,
+    pub export: PlotExportOptions,
+
+    /// Export the plot the next time it is shown.
+    #[serde(skip)]
+    pub export_requested: bool,
+
+    /// Where the time cursor was painted last frame, to know when it is hovered.
+    #[serde(skip)]
+    time_cursor_x: Option<f32>,
+
+    /// Is the time cursor being dragged, rather than the plot panned?
+    #[serde(skip)]
+    dragging_time_cursor: bool,
+}
+
+impl ViewTimeSeriesState {
+    pub fn selection_ui(&mut self, re_ui: &re_ui::ReUi, ui: &mut egui::Ui) {
+        crate::profile_function!();
+
+        let Self {
+            decimation:
+                Decimation {
+                    mode,
+                    max_samples_per_sec,
+                },
+            unit_conversions: UnitConversions { angles, lengths },
+            export,
+            export_requested,
+            time_cursor_x: _,
+            dragging_time_cursor: _,
+        } = self;
+
+        re_ui
+            .selection_grid(ui, "time_series_config")
+            .num_columns(2)
+            .show(ui, |ui| {
+                re_ui.grid_left_hand_label(ui, "Decimation").on_hover_text(
+                    "Limit how many samples per second are shown, to keep high-rate plots readable \
+                    and fast. Only applies to time-based timelines.\n\
+                    This only affects what is shown, not what is stored.",
+                );
+                ui.vertical(|ui| {
+                    egui::ComboBox::from_id_source("decimation_mode")
+                        .selected_text(*mode)
+                        .show_ui(ui, |ui| {
+                            ui.style_mut().wrap = Some(false);
+                            ui.set_min_width(64.0);
+
+                            ui.selectable_value(mode, DecimationMode::Off, DecimationMode::Off)
+                                .on_hover_text("Show every sample.");
+                            ui.selectable_value(
+                                mode,
+                                DecimationMode::Uniform,
+                                DecimationMode::Uniform,
+                            )
+                            .on_hover_text("Show the first sample of each interval.");
+                            ui.selectable_value(
+                                mode,
+                                DecimationMode::MinMax,
+                                DecimationMode::MinMax,
+                            )
+                            .on_hover_text(
+                                "Show the smallest and largest sample of each interval, \
+                                    so that spikes are never hidden.",
+                            );
+                        });
+
+                    if *mode != DecimationMode::Off {
+                        ui.horizontal(|ui| {
+                            ui.add(
+                                egui::DragValue::new(max_samples_per_sec)
+                                    .clamp_range(1.0..=10_000.0)
+                                    .speed(1.0),
+                            );
+                            ui.label("samples / s");
+                        });
+                    }
+                });
+                ui.end_row();
+
+                re_ui
+                    .grid_left_hand_label(ui, "Angles")
+                    .on_hover_text("Show values logged in radians or degrees in this unit.");
+                unit_combo_box(ui, "angle_unit", angles, &AngleUnit::ALL, |unit| {
+                    unit.symbol()
+                });
+                ui.end_row();
+
+                re_ui
+                    .grid_left_hand_label(ui, "Lengths")
+                    .on_hover_text("Show values logged in meters or feet in this unit.");
+                unit_combo_box(ui, "length_unit", lengths, &LengthUnit::ALL, |unit| {
+                    unit.symbol()
+                });
+                ui.end_row();
+
+                // TODO(emilk): support exporting plots on web
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    re_ui.grid_left_hand_label(ui, "Export").on_hover_text(
+                        "Save the plot as it is currently zoomed, black on white, \
+                        for use in papers and reports.",
+                    );
+                    ui.vertical(|ui| {
+                        export_options_ui(ui, export);
+                        if ui.button("Export plot…").clicked() {
+                            *export_requested = true;
+                        }
+                    });
+                    ui.end_row();
+                }
+                #[cfg(target_arch = "wasm32")]
+                let _ = (export, export_requested);
+            });
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn export_options_ui(ui: &mut egui::Ui, export: &mut PlotExportOptions) {
+    let PlotExportOptions {
+        format,
+        size,
+        pixels_per_point,
+        font_family,
+        font_size,
+        line_width_scale,
+        show_legend,
+    } = export;
+
+    ui.horizontal(|ui| {
+        egui::ComboBox::from_id_source("export_format")
+            .selected_text(*format)
+            .show_ui(ui, |ui| {
+                ui.selectable_value(format, ExportFormat::Svg, ExportFormat::Svg);
+                ui.selectable_value(format, ExportFormat::Png, ExportFormat::Png);
+            });
+        if *format == ExportFormat::Png {
+            ui.add(
+                egui::DragValue::new(pixels_per_point)
+                    .clamp_range(1.0..=16.0)
+                    .speed(0.1)
+                    .suffix(" px/pt"),
+            )
+            .on_hover_text("Resolution. 4 px/pt is 288 DPI.");
+        }
+    });
+    ui.horizontal(|ui| {
+        ui.add(
+            egui::DragValue::new(&mut size[0])
+                .clamp_range(100.0..=4000.0)
+                .speed(1.0),
+        );
+        ui.label("×");
+        ui.add(
+            egui::DragValue::new(&mut size[1])
+                .clamp_range(100.0..=4000.0)
+                .speed(1.0),
+        );
+        ui.label("pt");
+    });
+    ui.horizontal(|ui| {
+        ui.add(egui::TextEdit::singleline(font_family).desired_width(80.0))
+            .on_hover_text("Font family, e.g. 'serif' or 'Helvetica'");
+        ui.add(
+            egui::DragValue::new(font_size)
+                .clamp_range(4.0..=48.0)
+                .speed(0.1)
+                .suffix(" pt"),
+        );
+    });
+    ui.horizontal(|ui| {
+        ui.label("Line width");
+        ui.add(
+            egui::DragValue::new(line_width_scale)
+                .clamp_range(0.1..=10.0)
+                .speed(0.01)
+                .prefix("×"),
+        );
+    });
+    ui.checkbox(show_legend, "Legend");
+}
+
+fn unit_combo_box<U: Copy + PartialEq>(
+    ui: &mut egui::Ui,
+    id_source: &str,
+    selected: &mut Option<U>,
+    units: &[U],
+    symbol: impl Fn(U) -> &'static str,
+) {
+    egui::ComboBox::from_id_source(id_source)
+        .selected_text(selected.map_or("As logged", &symbol))
+        .show_ui(ui, |ui| {
+            ui.style_mut().wrap = Some(false);
+            ui.set_min_width(64.0);
+
+            ui.selectable_value(selected, None, "As logged");
+            for &unit in units {
+                ui.selectable_value(selected, Some(unit), symbol(unit));
+            }
+        });
+}
+
+/// How to thin out plots with more samples than can be told apart on screen.
+#[derive(Clone, Copy, Debug, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Decimation {
+    pub mode: DecimationMode,
+
+    /// For [`DecimationMode::MinMax`] this is the number of intervals per second,
+    /// each of which can show two samples.
+    pub max_samples_per_sec: f64,
+}
+
+impl Default for Decimation {
+    fn default() -> Self {
+        Self {
+            mode: DecimationMode::Off,
+            max_samples_per_sec: 100.0,
+        }
+    }
+}
+
+impl Decimation {
+    /// The length of the intervals to thin out, if any.
+    pub fn interval_ns(&self, time_type: TimeType) -> Option<i64> {
+        if self.mode == DecimationMode::Off || time_type != TimeType::Time {
+            return None;
+        }
+        Some((1e9 / self.max_samples_per_sec.max(1e-3)).max(1.0) as i64)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum DecimationMode {
+    #[default]
+    Off,
+
+    /// Keep the first sample of each interval.
+    Uniform,
+
+    /// Keep the smallest and largest sample of each interval.
+    MinMax,
+}
+
+impl From<DecimationMode> for egui::WidgetText {
+    fn from(val: DecimationMode) -> Self {
+        match val {
+            DecimationMode::Off => "Off".into(),
+            DecimationMode::Uniform => "Uniform".into(),
+            DecimationMode::MinMax => "Min/max".into(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum ExportFormat {
+    #[default]
+    Svg,
+
+    /// Rendered from the SVG.
+    Png,
+}
+
+impl From<ExportFormat> for egui::WidgetText {
+    fn from(val: ExportFormat) -> Self {
+        match val {
+            ExportFormat::Svg => "SVG".into(),
+            ExportFormat::Png => "PNG".into(),
+        }
+    }
+}
+
+/// How to style an exported plot.
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct PlotExportOptions {
+    pub format: ExportFormat,
+
+    /// Size of the whole figure, in points (1/72 inch).
+    pub size: [f32; 2],
+
+    /// Resolution of PNG exports.
+    pub pixels_per_point: f32,
+
+    /// A CSS font family, e.g. `serif` or `Helvetica, Arial, sans-serif`.
+    pub font_family: String,
+
+    pub font_size: f32,
+
+    /// Multiplies the logged line widths and point radii.
+    pub line_width_scale: f32,
+
+    pub show_legend: bool,
+}
+
+impl Default for PlotExportOptions {
+    fn default() -> Self {
+        Self {
+            format: ExportFormat::Svg,
+            size: [480.0, 320.0],
+            pixels_per_point: 4.0, // 288 DPI
+            font_family: "serif".to_owned(),
+            font_size: 10.0,
+            line_width_scale: 1.0,
+            show_legend: true,
+        }
+    }
+}
 
 pub(crate) fn view_time_series(
     ctx: &mut ViewerContext<'_>,
     ui: &mut egui::Ui,
-    _state: &mut ViewTimeSeriesState,
+    state: &mut ViewTimeSeriesState,
     scene: &SceneTimeSeries,
 ) -> egui::Response {
     crate::profile_function!();
@@ -56,6 +375,20 @@ pub(crate) fn view_time_series(
         min_time
     };
 
+    // The legend shows the unit of each series after its label, e.g. `speed [m/s]`,
+    // while the hover readout shows it after the value.
+    let units: ahash::HashMap<String, (String, String)> = scene
+        .lines
+        .iter()
+        .filter_map(|line| {
+            let unit = line.unit.clone()?;
+            Some((legend_name(line), (line.label.clone(), unit)))
+        })
+        .collect();
+
+    let number_format = ctx.app_options.number_format;
+    let time_format = ctx.app_options.time_format;
+
     // use timeline_name as part of id, so that egui stores different pan/zoom for different timelines
     let plot_id_src = ("plot", &timeline_name);
 
@@ -64,16 +397,19 @@ pub(crate) fn view_time_series(
             position: egui::plot::Corner::RightBottom,
             ..Default::default()
         })
-        .x_axis_formatter(move |time, _| format_time(time_type, time as i64 + time_offset))
+        .x_axis_formatter(move |time, _| {
+            format_time(time_type, time as i64 + time_offset, &time_format)
+        })
         .label_formatter(move |name, value| {
+            let (name, unit) = match units.get(name) {
+                Some((label, unit)) => (label.as_str(), format!(" {unit}")),
+                None => (name, String::new()),
+            };
             let name = if name.is_empty() { "y" } else { name };
-            let is_integer = value.y.round() == value.y;
-            let decimals = if is_integer { 0 } else { 5 };
             format!(
-                "{timeline_name}: {}\n{name}: {:.*}",
-                time_type.format((value.x as i64 + time_offset).into()),
-                decimals,
-                value.y,
+                "{timeline_name}: {}\n{name}: {}{unit}",
+                time_type.format_with((value.x as i64 + time_offset).into(), &time_format),
+                number_format.format_f64(value.y),
             )
         });
 
@@ -82,8 +418,17 @@ pub(crate) fn view_time_series(
         plot = plot.x_grid_spacer(move |spacer| ns_grid_spacer(canvas_size, &spacer));
     }
 
+    let plot_rect = ui.available_rect_before_wrap();
+    let hovering_time_cursor = state.time_cursor_x.map_or(false, |time_x| {
+        ui.input(|i| i.pointer.hover_pos()).map_or(false, |pos| {
+            (pos.x - time_x).abs() < TIME_CURSOR_GRAB_RADIUS && plot_rect.contains(pos)
+        })
+    });
+    // Drag the time cursor instead of panning the plot:
+    plot = plot.allow_drag(!hovering_time_cursor && !state.dragging_time_cursor);
+
     let egui::InnerResponse {
-        inner: time_x,
+        inner: (time_x, plot_bounds),
         response,
     } = plot.show(ui, |plot_ui| {
         if plot_ui.plot_secondary_clicked() {
@@ -108,27 +453,48 @@ pub(crate) fn view_time_series(
             match line.kind {
                 PlotSeriesKind::Continuous => plot_ui.line(
                     Line::new(points)
-                        .name(&line.label)
+                        .name(legend_name(line))
                         .color(color)
                         .width(line.width),
                 ),
                 PlotSeriesKind::Scatter => plot_ui.points(
                     Points::new(points)
-                        .name(&line.label)
+                        .name(legend_name(line))
                         .color(color)
                         .radius(line.width),
                 ),
             }
         }
 
-        current_time.map(|current_time| {
+        let time_x = current_time.map(|current_time| {
             let time_x = (current_time - time_offset) as f64;
             plot_ui.screen_from_plot([time_x, 0.0].into()).x
-        })
+        });
+        (time_x, plot_ui.plot_bounds())
     });
 
+    if hovering_time_cursor && response.drag_started() {
+        state.dragging_time_cursor = true;
+    } else if !response.dragged() {
+        state.dragging_time_cursor = false;
+    }
+    if state.dragging_time_cursor {
+        if let Some(pointer_pos) = response.interact_pointer_pos() {
+            let t = (pointer_pos.x - response.rect.left()) / response.rect.width();
+            let plot_x = egui::lerp(plot_bounds.min()[0]..=plot_bounds.max()[0], t as f64);
+            let timeline = *ctx.rec_cfg.time_ctrl.timeline();
+            ctx.rec_cfg
+                .time_ctrl
+                .set_timeline_and_time(timeline, plot_x as i64 + time_offset);
+            ctx.rec_cfg.time_ctrl.pause();
+        }
+    }
+    if hovering_time_cursor || state.dragging_time_cursor {
+        ui.output_mut(|o| o.cursor_icon = egui::CursorIcon::ResizeHorizontal);
+    }
+    state.time_cursor_x = time_x;
+
     if let Some(time_x) = time_x {
-        // TODO(emilk): allow interacting with the timeline (may require `egui::Plot` to return the `plot_from_screen` transform)
         let stroke = ui.visuals().widgets.inactive.fg_stroke;
         crate::ui::time_panel::paint_time_cursor(
             ui.painter(),
@@ -138,15 +504,73 @@ pub(crate) fn view_time_series(
         );
     }
 
+    if state.export_requested {
+        state.export_requested = false;
+
+        #[cfg(not(target_arch = "wasm32"))]
+        export_plot(
+            &state.export,
+            scene,
+            &super::export::ExportBounds {
+                time_offset,
+                min: plot_bounds.min(),
+                max: plot_bounds.max(),
+            },
+            &timeline_name,
+            time_type,
+            &time_format,
+        );
+    }
+
     response
 }
 
-fn format_time(time_type: TimeType, time_int: i64) -> String {
+#[cfg(not(target_arch = "wasm32"))]
+fn export_plot(
+    options: &PlotExportOptions,
+    scene: &SceneTimeSeries,
+    bounds: &super::export::ExportBounds,
+    timeline_name: &str,
+    time_type: TimeType,
+    time_format: &TimeFormatOptions,
+) {
+    let extension = options.format.extension();
+    let path = match rfd::FileDialog::new()
+        .set_file_name(format!("plot.{extension}"))
+        .add_filter(extension, &[extension])
+        .save_file()
+    {
+        Some(path) => path,
+        None => return,
+    };
+
+    let svg = super::export::plot_to_svg(options, &scene.lines, bounds, timeline_name, |time| {
+        format_time(time_type, time, time_format)
+    });
+    let result = match options.format {
+        ExportFormat::Svg => std::fs::write(&path, svg).map_err(anyhow::Error::from),
+        ExportFormat::Png => super::export::svg_to_png(&svg, options.pixels_per_point)
+            .and_then(|png| Ok(std::fs::write(&path, png)?)),
+    };
+    match result {
+        Ok(()) => re_log::info!("Plot exported to {path:?}"),
+        Err(err) => re_log::error!("Failed to export plot to {path:?}: {err}"),
+    }
+}
+
+pub(super) fn legend_name(line: &PlotSeries) -> String {
+    match &line.unit {
+        Some(unit) => format!("{} [{unit}]", line.label),
+        None => line.label.clone(),
+    }
+}
+
+fn format_time(time_type: TimeType, time_int: i64, time_format: &TimeFormatOptions) -> String {
     if time_type == TimeType::Time {
         let time = re_log_types::Time::from_ns_since_epoch(time_int);
-        crate::misc::format_time::format_time_compact(time)
+        crate::misc::format_time::format_time_compact(time, time_format)
     } else {
-        time_type.format(re_log_types::TimeInt::from(time_int))
+        time_type.format_with(re_log_types::TimeInt::from(time_int), time_format)
     }
 }
 
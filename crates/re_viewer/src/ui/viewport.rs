@@ -6,16 +6,23 @@ use ahash::HashMap;
 use itertools::Itertools as _;
 
 use re_data_store::EntityPath;
+use re_log_types::BlueprintOp;
 
 use crate::{
-    misc::{space_info::SpaceInfoCollection, Item, SpaceViewHighlights, ViewerContext},
+    misc::{
+        space_info::SpaceInfoCollection, time_control::PlayState, Item, SpaceViewHighlights,
+        ViewerContext,
+    },
     ui::space_view_heuristics::default_created_space_views,
 };
 
 use super::{
-    data_blueprint::DataBlueprintGroupHandle, space_view_entity_picker::SpaceViewEntityPicker,
-    space_view_heuristics::all_possible_space_views, view_category::ViewCategory, SpaceView,
-    SpaceViewId,
+    data_blueprint::DataBlueprintGroupHandle,
+    space_view_entity_picker::SpaceViewEntityPicker,
+    space_view_heuristics::all_possible_space_views,
+    view_category::ViewCategory,
+    view_spatial::{CameraSyncGroup, SpatialNavigationMode, SyncedCamera},
+    SpaceView, SpaceViewId,
 };
 
 // ----------------------------------------------------------------------------
@@ -50,6 +57,10 @@ pub struct Viewport {
 
     #[serde(skip)]
     space_view_entity_window: Option<SpaceViewEntityPicker>,
+
+    /// The camera each [`CameraSyncGroup`] was last synced to.
+    #[serde(skip)]
+    synced_cameras: HashMap<(CameraSyncGroup, SpatialNavigationMode), SyncedCamera>,
 }
 
 impl Viewport {
@@ -221,15 +232,22 @@ impl Viewport {
                     .data_blueprint
                     .data_blueprints_individual()
                     .get(entity_path);
+                let stalled_for = ctx.stalled_for_sec(entity_path);
                 blueprint_row_with_buttons(
                     ctx.re_ui,
                     ui,
                     group_is_visible,
                     properties.visible,
                     |ui| {
-                        let name = entity_path.iter().last().unwrap().to_string();
-                        let label = format!("🔹 {name}");
-                        ctx.data_blueprint_button_to(ui, label, space_view.id, entity_path)
+                        let name = ctx.entity_display_names.name_or_last_part(entity_path);
+                        if let Some(stalled_for) = stalled_for {
+                            let label = ctx.re_ui.warning_text(format!("🔹 {name} ⚠"));
+                            ctx.data_blueprint_button_to(ui, label, space_view.id, entity_path)
+                                .on_hover_text(format!("Stalled: no data for {stalled_for:.1}s"))
+                        } else {
+                            let label = format!("🔹 {name}");
+                            ctx.data_blueprint_button_to(ui, label, space_view.id, entity_path)
+                        }
                     },
                     |re_ui, ui| {
                         if visibility_button_ui(
@@ -331,6 +349,42 @@ impl Viewport {
         id
     }
 
+    /// Apply a layout directive that was logged from the SDK.
+    pub(crate) fn apply_blueprint_op(&mut self, op: &BlueprintOp) {
+        match op {
+            BlueprintOp::ClearSpaceViews => {
+                let space_view_ids = self.space_views.keys().copied().collect_vec();
+                for space_view_id in &space_view_ids {
+                    self.remove(space_view_id);
+                }
+            }
+            BlueprintOp::AddSpaceView(description) => {
+                // Replace rather than duplicate, so that logging the same layout twice is harmless.
+                let existing_ids = self
+                    .space_views
+                    .values()
+                    .filter(|space_view| space_view.display_name == description.name)
+                    .map(|space_view| space_view.id)
+                    .collect_vec();
+                for space_view_id in &existing_ids {
+                    self.remove(space_view_id);
+                }
+
+                let mut space_view = SpaceView::new(
+                    description.kind.into(),
+                    &description.space_path,
+                    &description.entity_paths,
+                );
+                space_view.display_name = description.name.clone();
+                space_view.entities_determined_by_user = !description.entity_paths.is_empty();
+                self.add_space_view(space_view);
+            }
+        }
+
+        // The logging code is in charge of the layout now, so don't add space views of our own.
+        self.has_been_user_edited = true;
+    }
+
     pub fn show_add_remove_entities_window(&mut self, space_view_id: SpaceViewId) {
         self.space_view_entity_window = Some(SpaceViewEntityPicker { space_view_id });
     }
@@ -472,6 +526,46 @@ impl Viewport {
             // rect/viewport can be invalid for the first frame
             space_view_options_ui(ctx, ui, self, tab_bar_rect, space_view_id, num_space_views);
         }
+
+        if self.sync_cameras() {
+            ui.ctx().request_repaint();
+        }
+    }
+
+    /// Make the cameras in each [`CameraSyncGroup`] follow the one that moved.
+    ///
+    /// Returns `true` if any camera was moved.
+    fn sync_cameras(&mut self) -> bool {
+        let mut moved = HashMap::default();
+        for space_view in self.space_views.values() {
+            let state = &space_view.view_state.state_spatial;
+            let (Some(group), Some(camera)) = (state.camera_sync_group, state.synced_camera())
+            else {
+                continue;
+            };
+            let key = (group, state.nav_mode);
+            let has_moved = self
+                .synced_cameras
+                .get(&key)
+                .map_or(true, |synced| !synced.shows_the_same_as(&camera));
+            if has_moved {
+                moved.insert(key, camera);
+            }
+        }
+        if moved.is_empty() {
+            return false;
+        }
+
+        for space_view in self.space_views.values_mut() {
+            let state = &mut space_view.view_state.state_spatial;
+            if let Some(group) = state.camera_sync_group {
+                if let Some(camera) = moved.get(&(group, state.nav_mode)) {
+                    state.follow_camera(camera);
+                }
+            }
+        }
+        self.synced_cameras.extend(moved);
+        true
     }
 
     pub fn add_new_spaceview_button_ui(
@@ -487,7 +581,7 @@ impl Viewport {
         ui.menu_image_button(texture_id, re_ui::ReUi::small_icon_size(), |ui| {
             ui.style_mut().wrap = Some(false);
 
-            for space_view in all_possible_space_views(ctx, spaces_info)
+            for mut space_view in all_possible_space_views(ctx, spaces_info)
                 .into_iter()
                 .sorted_by_key(|space_view| space_view.space_path.to_string())
             {
@@ -506,6 +600,7 @@ impl Viewport {
                     .clicked()
                 {
                     ui.close_menu();
+                    space_view.apply_default_visible_history(ctx);
                     let new_space_view_id = self.add_space_view(space_view);
                     ctx.set_single_selection(Item::SpaceView(new_space_view_id));
                 }
@@ -692,6 +787,118 @@ fn help_text_ui(ui: &mut egui::Ui, space_view: &SpaceView) {
     }
 }
 
+/// How old the data in a live space view is, so that operators know whether to trust it.
+fn pinned_time_badge_ui(ctx: &ViewerContext<'_>, ui: &mut egui::Ui, space_view: &SpaceView) {
+    if let Some((timeline, time)) = space_view.pinned_time {
+        let text = egui::RichText::new(format!(
+            "📌 {}",
+            timeline
+                .typ()
+                .format_with(time, &ctx.app_options.time_format)
+        ));
+        if timeline == *ctx.rec_cfg.time_ctrl.timeline() {
+            ui.label(text).on_hover_text(format!(
+                "Pinned to this time on the {} timeline",
+                timeline.name()
+            ));
+        } else {
+            ui.label(text.weak()).on_hover_text(format!(
+                "Pinned to this time on the {} timeline, which isn't selected",
+                timeline.name()
+            ));
+        }
+    }
+}
+
+fn data_age_ui(ctx: &ViewerContext<'_>, ui: &mut egui::Ui, space_view: &SpaceView) {
+    if !ctx.app_options.show_data_age
+        || ctx.rec_cfg.time_ctrl.play_state() != PlayState::Following
+        || space_view
+            .pinned_time_on(ctx.rec_cfg.time_ctrl.timeline())
+            .is_some()
+    {
+        return;
+    }
+
+    let entity_db = &ctx.log_db.entity_db;
+    let mut ages = space_view
+        .data_blueprint
+        .entity_paths()
+        .iter()
+        .filter_map(|entity_path| {
+            let log_time = entity_db.latest_log_time(entity_path)?;
+            Some((entity_path, ctx.rec_cfg.clock_sync.age_sec(log_time)))
+        })
+        .collect_vec();
+    if ages.is_empty() {
+        return;
+    }
+    ages.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+
+    // Ages keep growing even when no new data arrives:
+    ui.ctx()
+        .request_repaint_after(std::time::Duration::from_millis(250));
+
+    let warn_age = ctx.app_options.warn_data_age as f64;
+    let age_text = |age: f64| format!("{age:.2}s");
+
+    // The view is only as fresh as its oldest stream:
+    let (_, oldest_age) = ages[0];
+    let text = if oldest_age < warn_age {
+        egui::RichText::new(age_text(oldest_age)).weak()
+    } else {
+        ctx.re_ui.warning_text(age_text(oldest_age))
+    };
+    ui.label(text).on_hover_ui(|ui| {
+        ui.label("How long ago the data shown here was logged:");
+        egui::Grid::new("data_age").num_columns(2).show(ui, |ui| {
+            for (entity_path, age) in &ages {
+                ui.label(entity_path.to_string());
+                if *age < warn_age {
+                    ui.label(age_text(*age));
+                } else {
+                    ui.label(ctx.re_ui.warning_text(age_text(*age)));
+                }
+                ui.end_row();
+            }
+        });
+    });
+}
+
+/// Warn when any of the live streams shown in a space view has stopped producing data.
+fn stalled_badge_ui(ctx: &ViewerContext<'_>, ui: &mut egui::Ui, space_view: &SpaceView) {
+    let stalled = space_view
+        .data_blueprint
+        .entity_paths()
+        .iter()
+        .filter_map(|entity_path| Some((entity_path, ctx.stalled_for_sec(entity_path)?)))
+        .collect_vec();
+
+    // Streams can stall without anything else happening:
+    if ctx.log_db.data_source.is_some() && 0.0 < ctx.app_options.stall_timeout {
+        ui.ctx()
+            .request_repaint_after(std::time::Duration::from_secs(1));
+    }
+
+    if stalled.is_empty() {
+        return;
+    }
+
+    ui.label(ctx.re_ui.warning_text("⚠ stalled"))
+        .on_hover_ui(|ui| {
+            ui.label("These live streams have stopped producing data:");
+            egui::Grid::new("stalled_streams")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    for (entity_path, stalled_for) in &stalled {
+                        ui.label(entity_path.to_string());
+                        ui.label(format!("{stalled_for:.1}s ago"));
+                        ui.end_row();
+                    }
+                });
+        });
+}
+
 /// Shown in the right of the tab panel
 fn space_view_options_ui(
     ctx: &mut ViewerContext<'_>,
@@ -737,6 +944,10 @@ fn space_view_options_ui(
             // Show help last, since not all space views have help text
             help_text_ui(ui, space_view);
 
+            pinned_time_badge_ui(ctx, ui, space_view);
+            data_age_ui(ctx, ui, space_view);
+            stalled_badge_ui(ctx, ui, space_view);
+
             // Put a frame so that the buttons cover any labels they intersect with:
             let rect = ui.min_rect().expand2(egui::vec2(1.0, -2.0));
             ui.painter().set(
@@ -757,14 +968,29 @@ fn space_view_ui(
     space_view: &mut SpaceView,
     space_view_highlights: &SpaceViewHighlights,
 ) {
-    let Some(latest_at) = ctx.rec_cfg.time_ctrl.time_int() else {
+    let pinned_time = space_view.pinned_time_on(ctx.rec_cfg.time_ctrl.timeline());
+    let Some(latest_at) = pinned_time.or_else(|| ctx.rec_cfg.time_ctrl.time_int()) else {
         ui.centered_and_justified(|ui| {
             ui.label(ctx.re_ui.warning_text("No time selected"));
         });
         return
     };
 
-    space_view.scene_ui(ctx, ui, latest_at, space_view_highlights);
+    match pinned_time {
+        Some(pinned_time) => {
+            // Everything in the view (transforms, hover previews, …) queries the time control,
+            // so we move it to the pinned time while showing the view:
+            let time = ctx.rec_cfg.time_ctrl.time();
+            ctx.rec_cfg.time_ctrl.set_time(pinned_time);
+            space_view.scene_ui(ctx, ui, latest_at, space_view_highlights);
+            if let Some(time) = time {
+                ctx.rec_cfg.time_ctrl.set_time(time);
+            }
+        }
+        None => {
+            space_view.scene_ui(ctx, ui, latest_at, space_view_highlights);
+        }
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -0,0 +1,224 @@
+use re_log_types::LogMsg;
+use re_smart_channel::Receiver;
+
+use crate::misc::{RecentItem, RecentItems};
+
+/// A small example recording that can be downloaded and opened from the welcome screen.
+pub struct ExampleRecording {
+    /// Name of the `.rrd` file, without extension.
+    pub name: &'static str,
+    pub description: &'static str,
+}
+
+pub const EXAMPLE_RECORDINGS: &[ExampleRecording] = &[
+    ExampleRecording {
+        name: "dna",
+        description: "Simple 3D points and line strips, animated over time",
+    },
+    ExampleRecording {
+        name: "api_demo",
+        description: "A bit of everything: points, boxes, images, segmentation, text logs…",
+    },
+    ExampleRecording {
+        name: "plots",
+        description: "Scalars, bar charts and time series plots",
+    },
+];
+
+/// Where the example recordings are downloaded from.
+///
+/// Can be overridden with [`crate::env_vars::RERUN_EXAMPLES_URL`].
+#[cfg(not(target_arch = "wasm32"))]
+const DEFAULT_EXAMPLES_URL: &str = concat!(
+    "https://demo.rerun.io/version/",
+    env!("CARGO_PKG_VERSION"),
+    "/examples"
+);
+
+#[cfg(not(target_arch = "wasm32"))]
+impl ExampleRecording {
+    pub fn url(&self) -> String {
+        let base_url = std::env::var(crate::env_vars::RERUN_EXAMPLES_URL)
+            .unwrap_or_else(|_| DEFAULT_EXAMPLES_URL.to_owned());
+        format!("{}/{}.rrd", base_url.trim_end_matches('/'), self.name)
+    }
+}
+
+/// Something the user asked for on the welcome screen.
+pub enum WelcomeScreenAction {
+    OpenExample(&'static ExampleRecording),
+    ConnectToServer {
+        url: String,
+
+        /// For servers that require one.
+        auth_token: Option<String>,
+    },
+    OpenFile,
+    OpenRecent(RecentItem),
+}
+
+/// Shown when there is no data yet.
+///
+/// If we are just waiting for an SDK to connect, we also offer some quick ways to get started.
+pub struct WelcomeScreen {
+    /// The url in the "connect to server" text field.
+    server_url: String,
+
+    /// Empty if the server doesn't require one.
+    auth_token: String,
+}
+
+impl Default for WelcomeScreen {
+    fn default() -> Self {
+        Self {
+            server_url: re_ws_comms::default_server_url(),
+            auth_token: String::new(),
+        }
+    }
+}
+
+impl WelcomeScreen {
+    pub fn ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        rx: &Receiver<LogMsg>,
+        recent: &RecentItems,
+        is_downloading_example: bool,
+    ) -> Option<WelcomeScreenAction> {
+        let is_idle = matches!(
+            rx.source(),
+            re_smart_channel::Source::Sdk | re_smart_channel::Source::TcpServer { .. }
+        );
+        if !is_idle {
+            // We are about to get data from somewhere specific, so don't distract.
+            wait_screen_ui(ui, rx);
+            return None;
+        }
+
+        let mut action = None;
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            ui.vertical_centered(|ui| {
+                ui.add_space(48.0);
+                wait_screen_ui(ui, rx);
+                ui.add_space(32.0);
+
+                ui.set_max_width(400.0);
+
+                ui.group(|ui| {
+                    ui.strong("Open an example recording");
+                    if is_downloading_example {
+                        ui.horizontal(|ui| {
+                            ui.spinner();
+                            ui.label("Downloading…");
+                        });
+                    }
+                    ui.add_enabled_ui(
+                        cfg!(not(target_arch = "wasm32")) && !is_downloading_example,
+                        |ui| {
+                            for example in EXAMPLE_RECORDINGS {
+                                if ui
+                                    .button(example.name)
+                                    .on_hover_text(example.description)
+                                    .clicked()
+                                {
+                                    action = Some(WelcomeScreenAction::OpenExample(example));
+                                }
+                            }
+                        },
+                    );
+                });
+
+                ui.group(|ui| {
+                    ui.strong("Connect to a Rerun Server");
+                    ui.horizontal(|ui| {
+                        let response = ui.text_edit_singleline(&mut self.server_url);
+                        let pressed_enter =
+                            response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                        if ui.button("Connect").clicked() || pressed_enter {
+                            action = Some(WelcomeScreenAction::ConnectToServer {
+                                url: self.server_url.clone(),
+                                auth_token: (!self.auth_token.is_empty())
+                                    .then(|| self.auth_token.clone()),
+                            });
+                        }
+                    });
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.auth_token)
+                            .password(true)
+                            .hint_text("Auth token (if the server requires one)"),
+                    );
+                });
+
+                ui.group(|ui| {
+                    ui.strong("Open a file");
+                    if cfg!(target_arch = "wasm32") {
+                        ui.label("Drag-and-drop a .rrd file onto the viewer");
+                    } else if ui.button("Open…").clicked() {
+                        action = Some(WelcomeScreenAction::OpenFile);
+                    }
+                });
+
+                if !recent.is_empty() {
+                    ui.group(|ui| {
+                        if let Some(item) = recent.ui(ui) {
+                            action = Some(WelcomeScreenAction::OpenRecent(item));
+                        }
+                    });
+                }
+            });
+        });
+
+        action
+    }
+}
+
+fn wait_screen_ui(ui: &mut egui::Ui, rx: &Receiver<LogMsg>) {
+    fn ready_and_waiting(ui: &mut egui::Ui, txt: &str) {
+        let style = ui.style();
+        let mut layout_job = egui::text::LayoutJob::default();
+        layout_job.append(
+            "Ready",
+            0.0,
+            egui::TextFormat::simple(
+                egui::TextStyle::Heading.resolve(style),
+                style.visuals.strong_text_color(),
+            ),
+        );
+        layout_job.append(
+            &format!("\n\n{txt}"),
+            0.0,
+            egui::TextFormat::simple(
+                egui::TextStyle::Body.resolve(style),
+                style.visuals.text_color(),
+            ),
+        );
+        layout_job.halign = egui::Align::Center;
+        ui.label(layout_job);
+    }
+
+    match rx.source() {
+        re_smart_channel::Source::File { path } => {
+            ui.centered_and_justified(|ui| {
+                ui.strong(format!("Loading {}…", path.display()));
+            });
+        }
+        re_smart_channel::Source::FollowedFile { path } => {
+            ui.centered_and_justified(|ui| {
+                ready_and_waiting(ui, &format!("Waiting for data from {}", path.display()));
+            });
+        }
+        re_smart_channel::Source::Sdk => {
+            ready_and_waiting(ui, "Waiting for logging data from SDK");
+        }
+        re_smart_channel::Source::WsClient { ws_server_url } => {
+            // TODO(emilk): it would be even better to know wether or not we are connected, or are attempting to connect
+            ui.centered_and_justified(|ui| {
+                ready_and_waiting(ui, &format!("Waiting for data from {ws_server_url}"));
+            });
+        }
+        re_smart_channel::Source::TcpServer { port } => {
+            ready_and_waiting(ui, &format!("Listening on port {port}"));
+        }
+    };
+}
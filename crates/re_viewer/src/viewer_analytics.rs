@@ -173,6 +173,7 @@ impl ViewerAnalytics {
                 re_smart_channel::Source::Sdk => "sdk",          // show()
                 re_smart_channel::Source::WsClient { .. } => "ws_client", // spawn()
                 re_smart_channel::Source::TcpServer { .. } => "tcp_server", // connect()
+                re_smart_channel::Source::FollowedFile { .. } => "followed_file",
             };
             self.register("data_source", data_source.to_owned());
         }
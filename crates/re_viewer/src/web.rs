@@ -28,10 +28,13 @@ pub async fn start(canvas_id: &str) -> std::result::Result<(), eframe::wasm_bind
         web_options,
         Box::new(move |cc| {
             let app_env = crate::AppEnvironment::Web;
-            let startup_options = crate::StartupOptions::default();
+            let mut startup_options = crate::StartupOptions::default();
+            startup_options.ws_connection_options.auth_token =
+                re_ws_comms::auth_token_from_fragment(&cc.integration_info.web_info.location.hash);
             let re_ui = crate::customize_eframe(cc);
             let url = get_url(&cc.integration_info);
-            let app = crate::RemoteViewerApp::new(app_env, startup_options, re_ui, cc.storage, url);
+            let app =
+                crate::RemoteViewerApp::new(app_env, startup_options, re_ui, cc.storage, vec![url]);
             Box::new(app)
         }),
     )
@@ -1,59 +1,272 @@
-use std::ops::ControlFlow;
+use std::{
+    ops::ControlFlow,
+    sync::{Arc, Mutex},
+};
 
-use ewebsock::{WsEvent, WsMessage, WsSender};
+use ewebsock::{WsEvent, WsMessage};
+use re_log_types::EntityPath;
 
 use crate::Result;
 
+/// The streams the server is currently thinning out, because we can't keep up with the data.
+///
+/// Cheap to clone.
+#[derive(Clone, Default)]
+pub struct DegradedStreams(Arc<Mutex<Vec<EntityPath>>>);
+
+impl DegradedStreams {
+    /// Empty when we get all the data.
+    pub fn get(&self) -> Vec<EntityPath> {
+        self.0.lock().unwrap().clone()
+    }
+
+    fn set(&self, degraded_streams: Vec<EntityPath>) {
+        *self.0.lock().unwrap() = degraded_streams;
+    }
+}
+
+/// Whether a [`Connection`] is up.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConnectionStatus {
+    Connecting,
+    Connected,
+
+    /// The connection failed or was closed, and won't come back by itself.
+    Closed,
+}
+
+/// How to connect to the server.
+#[derive(Clone, Debug, Default)]
+pub struct ConnectionOptions {
+    /// PEM-encoded root certificates to trust for `wss://` urls,
+    /// in addition to the well-known ones, e.g. of a company-internal certificate authority.
+    ///
+    /// Ignored on web, where the browser decides which certificates to trust.
+    pub extra_root_certificates_pem: Vec<Vec<u8>>,
+
+    /// Sent to the server, for servers that only accept viewers with the right token.
+    ///
+    /// Never part of the url: it is sent in a header or as the first message.
+    /// See `Server::with_auth_token`.
+    pub auth_token: Option<String>,
+}
+
+enum Sender {
+    Ewebsock(ewebsock::WsSender),
+
+    #[cfg(not(target_arch = "wasm32"))]
+    CustomTls(crate::tls::WsSender),
+}
+
 /// Represents a connection to the server.
 /// Disconnects on drop.
 #[must_use]
-pub struct Connection(WsSender);
+pub struct Connection {
+    _sender: Sender,
+
+    /// The browser can't send before the connection is open,
+    /// so we send the auth token in [`Self::update`].
+    #[cfg(target_arch = "wasm32")]
+    pending_auth_token: Option<(ewebsock::WsSender, String)>,
+
+    degraded_streams: DegradedStreams,
+    status: Arc<Mutex<ConnectionStatus>>,
+}
 
 impl Connection {
-    /// Connect viewer to server
+    /// Connect viewer to server.
+    ///
+    /// Both `ws://` and `wss://` urls are supported.
     pub fn viewer_to_server(
         url: String,
+        options: &ConnectionOptions,
         on_binary_msg: impl Fn(Vec<u8>) -> ControlFlow<()> + Send + 'static,
     ) -> Result<Self> {
         re_log::info!("Connecting to {url:?}…");
-        let sender = ewebsock::ws_connect(
-            url,
-            Box::new(move |event: WsEvent| match event {
-                WsEvent::Opened => {
-                    re_log::info!("Connection established");
-                    ControlFlow::Continue(())
-                }
-                WsEvent::Message(message) => match message {
-                    WsMessage::Binary(binary) => on_binary_msg(binary),
-                    WsMessage::Text(text) => {
-                        re_log::warn!("Unexpected text message: {:?}", text);
-                        ControlFlow::Continue(())
+        // The server decides whether to actually compress, see `Server::with_compression`.
+        let url = crate::url_accepting_compression(&url);
+        let url = crate::url_accepting_batches(&url);
+        let url = crate::url_accepting_degraded_streams(&url);
+        let degraded_streams = DegradedStreams::default();
+        let degraded_streams_clone = degraded_streams.clone();
+        let status = Arc::new(Mutex::new(ConnectionStatus::Connecting));
+        let status_clone = status.clone();
+        let set_status = move |new_status| *status_clone.lock().unwrap() = new_status;
+
+        // What the server sends, unless it batched several of these together.
+        let on_frame = {
+            let set_status = set_status.clone();
+            move |binary: Vec<u8>| {
+                let binary = match crate::decompress_msg(binary) {
+                    Ok(binary) => binary,
+                    Err(err) => {
+                        re_log::error!("Failed to decompress message: {err}");
+                        return ControlFlow::Continue(());
                     }
-                    WsMessage::Unknown(text) => {
-                        re_log::warn!("Unknown message: {:?}", text);
-                        ControlFlow::Continue(())
+                };
+                if let Some(streams) = crate::decode_degraded_streams(&binary) {
+                    if !streams.is_empty() {
+                        re_log::debug!("Server is thinning out {} stream(s)", streams.len());
+                    }
+                    degraded_streams_clone.set(streams);
+                    ControlFlow::Continue(())
+                } else {
+                    let control_flow = on_binary_msg(binary);
+                    if control_flow.is_break() {
+                        set_status(ConnectionStatus::Closed);
                     }
-                    WsMessage::Ping(_data) => {
-                        re_log::warn!("Unexpected PING");
+                    control_flow
+                }
+            }
+        };
+
+        let on_event: ewebsock::EventHandler = Box::new(move |event: WsEvent| match event {
+            WsEvent::Opened => {
+                re_log::info!("Connection established");
+                set_status(ConnectionStatus::Connected);
+                ControlFlow::Continue(())
+            }
+            WsEvent::Message(message) => match message {
+                WsMessage::Binary(binary) => match crate::decode_batch(&binary) {
+                    None => on_frame(binary),
+                    Some(Ok(frames)) => {
+                        for frame in frames {
+                            if on_frame(frame.to_vec()).is_break() {
+                                return ControlFlow::Break(());
+                            }
+                        }
                         ControlFlow::Continue(())
                     }
-                    WsMessage::Pong(_data) => {
-                        re_log::warn!("Unexpected PONG");
+                    Some(Err(err)) => {
+                        re_log::error!("Failed to unpack batch: {err}");
                         ControlFlow::Continue(())
                     }
                 },
-                WsEvent::Error(error) => {
-                    re_log::error!("Connection error: {}", error);
-                    ControlFlow::Break(())
+                WsMessage::Text(text) => {
+                    re_log::warn!("Unexpected text message: {:?}", text);
+                    ControlFlow::Continue(())
+                }
+                WsMessage::Unknown(text) => {
+                    re_log::warn!("Unknown message: {:?}", text);
+                    ControlFlow::Continue(())
                 }
-                WsEvent::Closed => {
-                    re_log::info!("Connection to server closed.");
-                    ControlFlow::Break(())
+                WsMessage::Ping(_data) => {
+                    re_log::warn!("Unexpected PING");
+                    ControlFlow::Continue(())
                 }
-            }),
-        )
-        .map_err(|err| anyhow::format_err!("ewebsock: {err}"))?;
+                WsMessage::Pong(_data) => {
+                    re_log::warn!("Unexpected PONG");
+                    ControlFlow::Continue(())
+                }
+            },
+            WsEvent::Error(error) => {
+                re_log::error!("Connection error: {}", error);
+                set_status(ConnectionStatus::Closed);
+                ControlFlow::Break(())
+            }
+            WsEvent::Closed => {
+                re_log::info!("Connection to server closed.");
+                set_status(ConnectionStatus::Closed);
+                ControlFlow::Break(())
+            }
+        });
+
+        let sender = if options.extra_root_certificates_pem.is_empty() {
+            Sender::Ewebsock(Self::ewebsock_connect(url, options, on_event)?)
+        } else {
+            Self::connect_with_extra_root_certificates(url, options, on_event)?
+        };
+
+        Ok(Self {
+            #[cfg(target_arch = "wasm32")]
+            pending_auth_token: match (&sender, &options.auth_token) {
+                (Sender::Ewebsock(sender), Some(auth_token)) => {
+                    Some((sender.clone(), auth_token.clone()))
+                }
+                _ => None,
+            },
+            _sender: sender,
+            degraded_streams,
+            status,
+        })
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn connect_with_extra_root_certificates(
+        url: String,
+        options: &ConnectionOptions,
+        on_event: ewebsock::EventHandler,
+    ) -> Result<Sender> {
+        if url.starts_with("wss://") {
+            crate::tls::ws_connect(
+                url,
+                &options.extra_root_certificates_pem,
+                options.auth_token.as_deref(),
+                on_event,
+            )
+            .map(Sender::CustomTls)
+        } else {
+            re_log::warn!("Ignoring the extra root certificates for an unencrypted ws:// url");
+            Self::ewebsock_connect(url, options, on_event).map(Sender::Ewebsock)
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn connect_with_extra_root_certificates(
+        url: String,
+        _options: &ConnectionOptions,
+        on_event: ewebsock::EventHandler,
+    ) -> Result<Sender> {
+        re_log::warn_once!(
+            "Ignoring the extra root certificates - the browser decides which certificates to trust"
+        );
+        Self::ewebsock_connect(url, options, on_event).map(Sender::Ewebsock)
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn ewebsock_connect(
+        url: String,
+        options: &ConnectionOptions,
+        on_event: ewebsock::EventHandler,
+    ) -> Result<ewebsock::WsSender> {
+        let mut sender = ewebsock::ws_connect(url, on_event)
+            .map_err(|err| anyhow::format_err!("ewebsock: {err}"))?;
+        if let Some(auth_token) = &options.auth_token {
+            // Queued until the connection is open.
+            sender.send(WsMessage::Binary(crate::encode_auth_token(auth_token)));
+        }
+        Ok(sender)
+    }
+
+    /// The auth token is sent by [`Self::update`], once the connection is open.
+    #[cfg(target_arch = "wasm32")]
+    fn ewebsock_connect(
+        url: String,
+        _options: &ConnectionOptions,
+        on_event: ewebsock::EventHandler,
+    ) -> Result<ewebsock::WsSender> {
+        ewebsock::ws_connect(url, on_event).map_err(|err| anyhow::format_err!("ewebsock: {err}"))
+    }
+
+    /// Call regularly, e.g. once per frame.
+    ///
+    /// In the browser, this is what sends the auth token once the connection is open.
+    #[allow(clippy::unused_self)]
+    pub fn update(&mut self) {
+        #[cfg(target_arch = "wasm32")]
+        if self.status() == ConnectionStatus::Connected {
+            if let Some((mut sender, auth_token)) = self.pending_auth_token.take() {
+                sender.send(WsMessage::Binary(crate::encode_auth_token(&auth_token)));
+            }
+        }
+    }
+
+    pub fn status(&self) -> ConnectionStatus {
+        *self.status.lock().unwrap()
+    }
 
-        Ok(Self(sender))
+    /// The streams the server is dropping data from, because we can't keep up.
+    pub fn degraded_streams(&self) -> DegradedStreams {
+        self.degraded_streams.clone()
     }
 }
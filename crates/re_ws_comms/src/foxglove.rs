@@ -0,0 +1,408 @@
+//! Compatibility with the [Foxglove WebSocket protocol](https://github.com/foxglove/ws-protocol).
+//!
+//! Foxglove bridges (e.g. `foxglove_bridge` for ROS) host a server on the robot, which clients subscribe to.
+//! We connect to such a server as a client, subscribe to all JSON-encoded channels,
+//! and map the well-known [Foxglove schemas](https://foxglove.dev/docs/studio/messages/introduction)
+//! onto Rerun components.
+//!
+//! Channels using other encodings (protobuf, ros1, cdr) are ignored.
+
+use std::collections::HashMap;
+
+use anyhow::Context as _;
+use futures_util::{SinkExt as _, StreamExt as _};
+use tokio_tungstenite::tungstenite::{client::IntoClientRequest as _, http, Message};
+
+use re_log_types::{
+    component_types::{Quaternion, Rigid3, Scalar, Tensor, TextEntry, Transform, Vec3D},
+    msg_bundle::{try_build_msg_bundle1, MsgBundle},
+    ApplicationId, BeginRecordingMsg, EntityPath, EntityPathPart, LogMsg, MsgId, RecordingId,
+    RecordingInfo, RecordingSource, Time, TimePoint, Timeline,
+};
+use re_smart_channel::{Receiver, Sender};
+
+/// The websocket subprotocol that Foxglove servers speak.
+pub const SUBPROTOCOL: &str = "foxglove.websocket.v1";
+
+/// Binary opcode of the message data sent for a subscription.
+const OP_MESSAGE_DATA: u8 = 0x01;
+
+/// Connect to a Foxglove WebSocket server, and translate what it sends into [`LogMsg`]es.
+pub async fn connect(url: String) -> anyhow::Result<Receiver<LogMsg>> {
+    let mut request = url.as_str().into_client_request()?;
+    request.headers_mut().insert(
+        http::header::SEC_WEBSOCKET_PROTOCOL,
+        http::HeaderValue::from_static(SUBPROTOCOL),
+    );
+    let (ws_stream, _) = tokio_tungstenite::connect_async(request)
+        .await
+        .with_context(|| format!("Failed to connect to Foxglove server at {url:?}"))?;
+    re_log::info!("Connected to Foxglove server at {url:?}");
+
+    let (tx, rx) =
+        re_smart_channel::smart_channel(re_smart_channel::Source::WsClient { ws_server_url: url });
+
+    tokio::spawn(async move {
+        if let Err(err) = run_connection(ws_stream, tx).await {
+            re_log::error!("Foxglove connection failed: {err}");
+        }
+    });
+
+    Ok(rx)
+}
+
+/// A channel we have subscribed to.
+struct Subscription {
+    topic: String,
+    schema_name: String,
+    entity_path: EntityPath,
+}
+
+async fn run_connection(
+    ws_stream: tokio_tungstenite::WebSocketStream<
+        tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>,
+    >,
+    tx: Sender<LogMsg>,
+) -> anyhow::Result<()> {
+    let (mut write, mut read) = ws_stream.split();
+
+    let mut subscriptions: HashMap<u32, (u32, Subscription)> = Default::default();
+    let mut next_subscription_id = 0;
+
+    while let Some(message) = read.next().await {
+        match message? {
+            Message::Text(text) => match serde_json::from_str(&text)? {
+                ServerMessage::ServerInfo { name } => {
+                    tx.send(begin_recording_msg(name)).ok();
+                }
+                ServerMessage::Advertise { channels } => {
+                    let mut new_subscriptions = vec![];
+                    for channel in channels {
+                        if channel.encoding != "json" {
+                            re_log::warn!(
+                                "Ignoring {:?}: {:?} encoding is not supported, only JSON",
+                                channel.topic,
+                                channel.encoding
+                            );
+                            continue;
+                        }
+                        let id = next_subscription_id;
+                        next_subscription_id += 1;
+                        new_subscriptions.push(SubscribeTo {
+                            id,
+                            channel_id: channel.id,
+                        });
+                        let subscription = Subscription {
+                            entity_path: entity_path_from_topic(&channel.topic),
+                            topic: channel.topic,
+                            schema_name: channel.schema_name,
+                        };
+                        subscriptions.insert(id, (channel.id, subscription));
+                    }
+
+                    if !new_subscriptions.is_empty() {
+                        let subscribe = ClientMessage::Subscribe {
+                            subscriptions: new_subscriptions,
+                        };
+                        write
+                            .send(Message::Text(serde_json::to_string(&subscribe)?))
+                            .await?;
+                    }
+                }
+                ServerMessage::Unadvertise { channel_ids } => {
+                    subscriptions.retain(|_, (channel_id, _)| !channel_ids.contains(channel_id));
+                }
+                ServerMessage::Status { message } => {
+                    re_log::info!("Foxglove server: {message}");
+                }
+                ServerMessage::Other => {}
+            },
+            Message::Binary(data) => {
+                if let Some((subscription_id, timestamp_ns, payload)) = parse_message_data(&data) {
+                    if let Some((_, subscription)) = subscriptions.get(&subscription_id) {
+                        for log_msg in log_msgs_from_message(subscription, timestamp_ns, payload) {
+                            if tx.send(log_msg).is_err() {
+                                return Ok(()); // The viewer has shut down
+                            }
+                        }
+                    }
+                }
+            }
+            Message::Close(_) => break,
+            _ => {}
+        }
+    }
+
+    re_log::info!("Foxglove server closed the connection");
+    Ok(())
+}
+
+fn begin_recording_msg(server_name: String) -> LogMsg {
+    LogMsg::BeginRecordingMsg(BeginRecordingMsg {
+        msg_id: MsgId::random(),
+        info: RecordingInfo {
+            application_id: ApplicationId(server_name),
+            recording_id: RecordingId::random(),
+            is_official_example: false,
+            started: Time::now(),
+            recording_source: RecordingSource::Other("Foxglove WebSocket".to_owned()),
+        },
+    })
+}
+
+/// `/camera/image` -> `camera/image`
+fn entity_path_from_topic(topic: &str) -> EntityPath {
+    topic
+        .split('/')
+        .filter(|part| !part.is_empty())
+        .map(EntityPathPart::from)
+        .collect()
+}
+
+/// Returns the subscription id, the receive timestamp in nanoseconds, and the payload.
+fn parse_message_data(data: &[u8]) -> Option<(u32, u64, &[u8])> {
+    let (&op, data) = data.split_first()?;
+    if op != OP_MESSAGE_DATA || data.len() < 12 {
+        return None;
+    }
+    let subscription_id = u32::from_le_bytes(data[0..4].try_into().ok()?);
+    let timestamp_ns = u64::from_le_bytes(data[4..12].try_into().ok()?);
+    Some((subscription_id, timestamp_ns, &data[12..]))
+}
+
+fn log_msgs_from_message(
+    subscription: &Subscription,
+    timestamp_ns: u64,
+    payload: &[u8],
+) -> Vec<LogMsg> {
+    let time_point = TimePoint::from([(
+        Timeline::log_time(),
+        Time::from_ns_since_epoch(timestamp_ns as i64).into(),
+    )]);
+
+    let log_msgs = msg_bundles_from_json(subscription, time_point, payload).and_then(|bundles| {
+        bundles
+            .into_iter()
+            .map(|bundle| Ok(LogMsg::ArrowMsg(bundle.try_into()?)))
+            .collect::<anyhow::Result<Vec<_>>>()
+    });
+
+    log_msgs.unwrap_or_else(|err| {
+        re_log::warn_once!(
+            "Failed to read {:?} message on {:?}: {err}",
+            subscription.schema_name,
+            subscription.topic
+        );
+        vec![]
+    })
+}
+
+/// Map a JSON message onto Rerun components, based on its schema.
+///
+/// Messages with unknown schemas have their top-level numbers logged as scalars,
+/// so that they can at least be plotted.
+fn msg_bundles_from_json(
+    subscription: &Subscription,
+    time_point: TimePoint,
+    payload: &[u8],
+) -> anyhow::Result<Vec<MsgBundle>> {
+    let entity_path = subscription.entity_path.clone();
+    let msg_bundle = match subscription.schema_name.as_str() {
+        "foxglove.Log" => {
+            let log: schemas::Log = serde_json::from_slice(payload)?;
+            let text_entry = TextEntry::new(log.message, Some(log.level.as_str().to_owned()));
+            try_build_msg_bundle1(MsgId::random(), entity_path, time_point, vec![text_entry])?
+        }
+        "foxglove.CompressedImage" => {
+            let image: schemas::CompressedImage = serde_json::from_slice(payload)?;
+            use base64::Engine as _;
+            let bytes = base64::engine::general_purpose::STANDARD.decode(image.data)?;
+            let tensor = if image.format == "jpeg" {
+                Tensor::tensor_from_jpeg_bytes(bytes)?
+            } else {
+                Tensor::from_image(re_log_types::external::image::load_from_memory(&bytes)?)?
+            };
+            try_build_msg_bundle1(MsgId::random(), entity_path, time_point, vec![tensor])?
+        }
+        "foxglove.PoseInFrame" => {
+            let pose: schemas::PoseInFrame = serde_json::from_slice(payload)?;
+            let transform = transform(pose.pose.position, pose.pose.orientation);
+            try_build_msg_bundle1(MsgId::random(), entity_path, time_point, vec![transform])?
+        }
+        "foxglove.FrameTransform" => {
+            let frame_transform: schemas::FrameTransform = serde_json::from_slice(payload)?;
+            let transform = transform(frame_transform.translation, frame_transform.rotation);
+            try_build_msg_bundle1(MsgId::random(), entity_path, time_point, vec![transform])?
+        }
+        _ => {
+            let json: serde_json::Value = serde_json::from_slice(payload)?;
+            let mut msg_bundles = vec![];
+            if let serde_json::Value::Object(fields) = json {
+                for (name, value) in fields {
+                    if let Some(value) = value.as_f64() {
+                        msg_bundles.push(try_build_msg_bundle1(
+                            MsgId::random(),
+                            entity_path.join(&entity_path_from_topic(&name)),
+                            time_point.clone(),
+                            vec![Scalar(value)],
+                        )?);
+                    }
+                }
+            }
+            return Ok(msg_bundles);
+        }
+    };
+    Ok(vec![msg_bundle])
+}
+
+fn transform(translation: schemas::Vector3, rotation: schemas::Quaternion) -> Transform {
+    Transform::Rigid3(Rigid3 {
+        rotation: Quaternion {
+            x: rotation.x as f32,
+            y: rotation.y as f32,
+            z: rotation.z as f32,
+            w: rotation.w as f32,
+        },
+        translation: Vec3D([
+            translation.x as f32,
+            translation.y as f32,
+            translation.z as f32,
+        ]),
+    })
+}
+
+// ----------------------------------------------------------------------------
+// The protocol messages we care about.
+
+#[derive(serde::Deserialize)]
+#[serde(tag = "op", rename_all = "camelCase")]
+enum ServerMessage {
+    ServerInfo {
+        name: String,
+    },
+
+    Advertise {
+        channels: Vec<Channel>,
+    },
+
+    #[serde(rename_all = "camelCase")]
+    Unadvertise {
+        channel_ids: Vec<u32>,
+    },
+
+    Status {
+        message: String,
+    },
+
+    #[serde(other)]
+    Other,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Channel {
+    id: u32,
+    topic: String,
+    encoding: String,
+    schema_name: String,
+}
+
+#[derive(serde::Serialize)]
+#[serde(tag = "op", rename_all = "camelCase")]
+enum ClientMessage {
+    Subscribe { subscriptions: Vec<SubscribeTo> },
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SubscribeTo {
+    id: u32,
+    channel_id: u32,
+}
+
+/// The parts of the well-known Foxglove schemas that we use.
+mod schemas {
+    #[derive(serde::Deserialize)]
+    pub struct Log {
+        pub level: LogLevel,
+        pub message: String,
+    }
+
+    /// Either the name or the number of the enum value.
+    #[derive(serde::Deserialize)]
+    #[serde(untagged)]
+    pub enum LogLevel {
+        Name(String),
+        Number(u8),
+    }
+
+    impl LogLevel {
+        pub fn as_str(&self) -> &str {
+            match self {
+                Self::Name(name) => name,
+                Self::Number(1) => "DEBUG",
+                Self::Number(2) => "INFO",
+                Self::Number(3) => "WARN",
+                Self::Number(4) => "ERROR",
+                Self::Number(5) => "FATAL",
+                Self::Number(_) => "UNKNOWN",
+            }
+        }
+    }
+
+    #[derive(serde::Deserialize)]
+    pub struct CompressedImage {
+        /// Base64 encoded.
+        pub data: String,
+
+        /// E.g. `jpeg` or `png`.
+        pub format: String,
+    }
+
+    #[derive(serde::Deserialize)]
+    pub struct PoseInFrame {
+        pub pose: Pose,
+    }
+
+    #[derive(serde::Deserialize)]
+    pub struct Pose {
+        pub position: Vector3,
+        pub orientation: Quaternion,
+    }
+
+    #[derive(serde::Deserialize)]
+    pub struct FrameTransform {
+        pub translation: Vector3,
+        pub rotation: Quaternion,
+    }
+
+    #[derive(serde::Deserialize)]
+    pub struct Vector3 {
+        pub x: f64,
+        pub y: f64,
+        pub z: f64,
+    }
+
+    #[derive(serde::Deserialize)]
+    pub struct Quaternion {
+        pub x: f64,
+        pub y: f64,
+        pub z: f64,
+        pub w: f64,
+    }
+}
+
+#[test]
+fn test_parse_message_data() {
+    let mut data = vec![OP_MESSAGE_DATA];
+    data.extend(7_u32.to_le_bytes());
+    data.extend(123_456_u64.to_le_bytes());
+    data.extend(br#"{"value": 1.5}"#);
+
+    let (subscription_id, timestamp_ns, payload) = parse_message_data(&data).unwrap();
+    assert_eq!(subscription_id, 7);
+    assert_eq!(timestamp_ns, 123_456);
+    assert_eq!(payload, br#"{"value": 1.5}"#);
+
+    assert!(parse_message_data(&[0x02, 0, 0]).is_none());
+}
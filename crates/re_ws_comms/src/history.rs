@@ -0,0 +1,179 @@
+//! The messages the [`crate::Server`] replays to viewers that connect after logging has started.
+//!
+//! By default everything is kept, but the history can be limited to the most recent messages,
+//! e.g. for a robot that streams for days.
+//! Messages that the viewer needs to make sense of the rest (e.g. [`LogMsg::BeginRecordingMsg`]
+//! and timeless data) are always kept.
+
+use std::{collections::VecDeque, sync::Arc, time::Instant};
+
+use re_log_types::LogMsg;
+
+use crate::qos::Packet;
+
+/// How much of the history the [`crate::Server`] keeps for viewers that connect late.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HistoryLimits {
+    /// Drop the oldest messages once they take up more than this many bytes.
+    pub max_bytes: Option<u64>,
+
+    /// Drop messages that are older than this.
+    pub max_age: Option<std::time::Duration>,
+}
+
+impl HistoryLimits {
+    /// Keep everything.
+    pub const UNLIMITED: Self = Self {
+        max_bytes: None,
+        max_age: None,
+    };
+
+    /// Don't keep anything but the messages the viewer can't do without.
+    pub const LIVE_ONLY: Self = Self {
+        max_bytes: Some(0),
+        max_age: None,
+    };
+}
+
+struct Entry {
+    /// In order of arrival, so that the pinned and recent messages can be merged back together.
+    index: u64,
+    arrival: Instant,
+    packet: Arc<Packet>,
+}
+
+#[derive(Default)]
+pub struct History {
+    limits: HistoryLimits,
+
+    /// Never dropped, see [`is_pinned`].
+    pinned: Vec<Entry>,
+
+    /// Oldest first.
+    recent: VecDeque<Entry>,
+    recent_bytes: u64,
+
+    num_received: u64,
+}
+
+impl History {
+    pub fn new(limits: HistoryLimits) -> Self {
+        Self {
+            limits,
+            ..Default::default()
+        }
+    }
+
+    pub fn push(&mut self, msg: &LogMsg, packet: Arc<Packet>) {
+        let entry = Entry {
+            index: self.num_received,
+            arrival: Instant::now(),
+            packet,
+        };
+        self.num_received += 1;
+
+        if is_pinned(msg) {
+            self.pinned.push(entry);
+        } else {
+            self.recent_bytes += entry.packet.bytes.len() as u64;
+            self.recent.push_back(entry);
+        }
+
+        self.enforce_limits(Instant::now());
+    }
+
+    /// Everything to replay to a new viewer, in the order it arrived.
+    pub fn packets(&mut self) -> Vec<Arc<Packet>> {
+        self.enforce_limits(Instant::now());
+
+        let mut entries: Vec<&Entry> = self.pinned.iter().chain(&self.recent).collect();
+        entries.sort_by_key(|entry| entry.index);
+        entries
+            .into_iter()
+            .map(|entry| entry.packet.clone())
+            .collect()
+    }
+
+    fn enforce_limits(&mut self, now: Instant) {
+        let HistoryLimits { max_bytes, max_age } = self.limits;
+
+        while let Some(oldest) = self.recent.front() {
+            let too_big = max_bytes.map_or(false, |max_bytes| max_bytes < self.recent_bytes);
+            let too_old = max_age.map_or(false, |max_age| {
+                max_age < now.saturating_duration_since(oldest.arrival)
+            });
+            if !too_big && !too_old {
+                break;
+            }
+
+            self.recent_bytes -= oldest.packet.bytes.len() as u64;
+            self.recent.pop_front();
+        }
+    }
+}
+
+/// Is this needed to make sense of the messages after it, no matter how old it is?
+fn is_pinned(msg: &LogMsg) -> bool {
+    match msg {
+        LogMsg::BeginRecordingMsg(_) | LogMsg::BlueprintMsg(_) => true,
+        LogMsg::ArrowMsg(msg) => msg
+            .time_point()
+            .map_or(false, |time_point| time_point.is_timeless()),
+        LogMsg::EntityPathOpMsg(_) | LogMsg::Goodbye(_) | LogMsg::TraceSpansMsg(_) => false,
+    }
+}
+
+#[test]
+fn test_history_limits() {
+    use re_log_types::{MsgId, Time, TraceSpansMsg};
+
+    use crate::qos::Priority;
+
+    fn packet(num_bytes: usize) -> Arc<Packet> {
+        Arc::new(Packet::new(
+            vec![0; num_bytes].into(),
+            Priority::Normal,
+            None,
+        ))
+    }
+
+    let begin_recording = LogMsg::BeginRecordingMsg(re_log_types::BeginRecordingMsg {
+        msg_id: MsgId::random(),
+        info: re_log_types::RecordingInfo {
+            application_id: re_log_types::ApplicationId("test".to_owned()),
+            recording_id: re_log_types::RecordingId::random(),
+            is_official_example: false,
+            started: Time::now(),
+            recording_source: re_log_types::RecordingSource::Unknown,
+        },
+    });
+    let spans = LogMsg::TraceSpansMsg(TraceSpansMsg {
+        msg_id: MsgId::random(),
+        spans: vec![],
+    });
+
+    let mut history = History::new(HistoryLimits {
+        max_bytes: Some(250),
+        max_age: None,
+    });
+    let first = packet(100);
+    history.push(&spans, first.clone());
+    let begin = packet(1000);
+    history.push(&begin_recording, begin.clone());
+    let second = packet(100);
+    history.push(&spans, second.clone());
+    let third = packet(100);
+    history.push(&spans, third.clone());
+
+    // The oldest message is dropped, but the pinned one is kept, in its place:
+    let packets = history.packets();
+    assert_eq!(packets.len(), 3);
+    assert!(Arc::ptr_eq(&packets[0], &begin));
+    assert!(Arc::ptr_eq(&packets[1], &second));
+    assert!(Arc::ptr_eq(&packets[2], &third));
+
+    let mut history = History::new(HistoryLimits::LIVE_ONLY);
+    history.push(&begin_recording, begin);
+    history.push(&spans, first);
+    assert_eq!(history.packets().len(), 1);
+}
@@ -7,14 +7,25 @@
 #[cfg(feature = "client")]
 mod client;
 #[cfg(feature = "client")]
-pub use client::Connection;
+pub use client::{Connection, ConnectionOptions, ConnectionStatus, DegradedStreams};
+#[cfg(all(feature = "client", not(target_arch = "wasm32")))]
+mod tls;
 
+#[cfg(all(feature = "foxglove", not(target_arch = "wasm32")))]
+pub mod foxglove;
+
+#[cfg(feature = "server")]
+mod history;
+#[cfg(feature = "server")]
+mod qos;
 #[cfg(feature = "server")]
 mod server;
 #[cfg(feature = "server")]
-pub use server::Server;
+pub use history::HistoryLimits;
+#[cfg(feature = "server")]
+pub use server::{CompressionLevel, RecordingAccess, Server};
 
-use re_log_types::LogMsg;
+use re_log_types::{EntityPath, LogMsg};
 
 pub type Result<T> = anyhow::Result<T>;
 
@@ -30,6 +41,102 @@ pub fn default_server_url() -> String {
     format!("{PROTOCOL}://127.0.0.1:{DEFAULT_WS_SERVER_PORT}")
 }
 
+// ----------------------------------------------------------------------------
+// Authentication
+
+// Viewers present their auth token in an `Authorization: Bearer` header where they can,
+// and otherwise (e.g. in browsers, which can't set headers on websocket connections)
+// as the first message after connecting, so that it never ends up in a url.
+
+/// The first message a viewer sends if it didn't present its auth token in a header:
+/// this prefix followed by the token.
+const AUTH_TOKEN_PREFIX: [u8; 4] = *b"RRAT";
+
+/// How long the server waits for a viewer to send its auth token.
+#[cfg(feature = "server")]
+const AUTH_TOKEN_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+#[cfg(feature = "client")]
+fn encode_auth_token(auth_token: &str) -> Vec<u8> {
+    let mut bytes = AUTH_TOKEN_PREFIX.to_vec();
+    bytes.extend_from_slice(auth_token.as_bytes());
+    bytes
+}
+
+/// Returns `None` if this isn't a message with an auth token.
+#[cfg(feature = "server")]
+fn decode_auth_token(data: &[u8]) -> Option<&str> {
+    std::str::from_utf8(data.strip_prefix(&AUTH_TOKEN_PREFIX)?).ok()
+}
+
+/// The key of the auth token in the fragment of the url of the web viewer, e.g. `#token=secret`.
+///
+/// Browsers never send the fragment to the server, so it doesn't end up in any logs.
+const AUTH_TOKEN_FRAGMENT_KEY: &str = "token";
+
+/// Add the auth token to the fragment of the url of the web viewer.
+pub fn web_viewer_url_with_auth_token(url: &str, auth_token: &str) -> String {
+    format!(
+        "{url}#{AUTH_TOKEN_FRAGMENT_KEY}={}",
+        percent_encode(auth_token)
+    )
+}
+
+/// Find the auth token in the fragment of the url of the web viewer, e.g. `#token=secret`.
+pub fn auth_token_from_fragment(fragment: &str) -> Option<String> {
+    value_from_query(fragment.trim_start_matches('#'), AUTH_TOKEN_FRAGMENT_KEY)
+}
+
+fn url_with_query(url: &str, key: &str, value: &str) -> String {
+    let separator = if url.contains('?') { '&' } else { '?' };
+    format!("{url}{separator}{key}={}", percent_encode(value))
+}
+
+/// Compares in a time that doesn't depend on where the inputs differ,
+/// so that a token can't be guessed one byte at a time.
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+fn value_from_query(query: &str, key: &str) -> Option<String> {
+    query.split('&').find_map(|pair| {
+        let value = pair.strip_prefix(key)?.strip_prefix('=')?;
+        percent_decode(value)
+    })
+}
+
+fn percent_encode(text: &str) -> String {
+    use std::fmt::Write as _;
+    let mut encoded = String::new();
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            write!(encoded, "%{byte:02X}").unwrap();
+        }
+    }
+    encoded
+}
+
+/// Returns `None` on malformed input.
+fn percent_decode(text: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut rest = text.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%' {
+            let hex = std::str::from_utf8(tail.get(..2)?).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+// ----------------------------------------------------------------------------
+
 const PREFIX: [u8; 4] = *b"RR00";
 
 pub fn encode_log_msg(log_msg: &LogMsg) -> Vec<u8> {
@@ -49,6 +156,263 @@ pub fn decode_log_msg(data: &[u8]) -> Result<LogMsg> {
     use anyhow::Context as _;
     use bincode::Options as _;
     bincode::DefaultOptions::new()
+        // A message can't be longer than its payload, so this rejects bad lengths
+        // before they turn into huge allocations:
+        .with_limit(payload.len() as u64)
         .deserialize(payload)
         .context("bincode")
 }
+
+// ----------------------------------------------------------------------------
+// Compression
+
+/// The query parameter of the websocket url with which the viewer tells the server
+/// that it can decompress messages, so that the server only compresses for viewers that can.
+const COMPRESSION_QUERY_KEY: &str = "compression";
+
+const COMPRESSION_ZSTD: &str = "zstd";
+
+/// A zstd-compressed message. It decompresses to a whole message, with its own prefix.
+const COMPRESSED_PREFIX: [u8; 4] = *b"RRZ0";
+
+/// Tell the server that we can decompress messages.
+#[cfg(feature = "client")]
+fn url_accepting_compression(url: &str) -> String {
+    url_with_query(url, COMPRESSION_QUERY_KEY, COMPRESSION_ZSTD)
+}
+
+/// Did the viewer say it can decompress messages, in the query of its websocket url?
+#[cfg(feature = "server")]
+fn query_accepts_compression(query: &str) -> bool {
+    value_from_query(query, COMPRESSION_QUERY_KEY).as_deref() == Some(COMPRESSION_ZSTD)
+}
+
+#[cfg(feature = "server")]
+fn compress_msg(bytes: &[u8], zstd_level: i32) -> std::io::Result<Vec<u8>> {
+    let compressed = zstd::bulk::compress(bytes, zstd_level)?;
+    let mut result = Vec::with_capacity(COMPRESSED_PREFIX.len() + compressed.len());
+    result.extend_from_slice(&COMPRESSED_PREFIX);
+    result.extend_from_slice(&compressed);
+    Ok(result)
+}
+
+/// Messages that weren't compressed are returned as they are.
+#[cfg(feature = "client")]
+fn decompress_msg(data: Vec<u8>) -> Result<Vec<u8>> {
+    let Some(mut payload) = data.strip_prefix(&COMPRESSED_PREFIX) else {
+        return Ok(data);
+    };
+
+    use std::io::Read as _;
+    let mut decoder = ruzstd::StreamingDecoder::new(&mut payload)
+        .map_err(|err| anyhow::anyhow!("ruzstd: {err}"))?;
+    let mut bytes = Vec::new();
+    decoder.read_to_end(&mut bytes)?;
+    Ok(bytes)
+}
+
+// ----------------------------------------------------------------------------
+// Batching
+
+/// The query parameter of the websocket url with which the viewer tells the server
+/// that it can unpack batches, so that the server only batches for viewers that can.
+const BATCH_QUERY_KEY: &str = "batch";
+
+/// Several messages in one websocket frame, to save the per-frame overhead.
+///
+/// Each message is a little-endian `u32` length followed by what would otherwise have been
+/// a frame of its own (e.g. a compressed message), but never another batch.
+const BATCH_PREFIX: [u8; 4] = *b"RRB0";
+
+/// Tell the server that we can unpack batches.
+#[cfg(feature = "client")]
+fn url_accepting_batches(url: &str) -> String {
+    url_with_query(url, BATCH_QUERY_KEY, "1")
+}
+
+/// Did the viewer say it can unpack batches, in the query of its websocket url?
+#[cfg(feature = "server")]
+fn query_accepts_batches(query: &str) -> bool {
+    value_from_query(query, BATCH_QUERY_KEY).as_deref() == Some("1")
+}
+
+#[cfg(feature = "server")]
+fn encode_batch(msgs: &[impl AsRef<[u8]>]) -> Vec<u8> {
+    let num_bytes = msgs.iter().map(|msg| 4 + msg.as_ref().len()).sum::<usize>();
+    let mut bytes = Vec::with_capacity(BATCH_PREFIX.len() + num_bytes);
+    bytes.extend_from_slice(&BATCH_PREFIX);
+    for msg in msgs {
+        let msg = msg.as_ref();
+        bytes.extend_from_slice(&(msg.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(msg);
+    }
+    bytes
+}
+
+/// Returns `None` if this isn't a batch.
+#[cfg(feature = "client")]
+fn decode_batch(data: &[u8]) -> Option<Result<Vec<&[u8]>>> {
+    let mut rest = data.strip_prefix(&BATCH_PREFIX)?;
+    let mut msgs = Vec::new();
+    while !rest.is_empty() {
+        if rest.len() < 4 {
+            return Some(Err(anyhow::anyhow!("Truncated batch")));
+        }
+        let (len, tail) = rest.split_at(4);
+        let len = u32::from_le_bytes(len.try_into().unwrap()) as usize;
+        if tail.len() < len {
+            return Some(Err(anyhow::anyhow!("Truncated batch")));
+        }
+        let (msg, tail) = tail.split_at(len);
+        msgs.push(msg);
+        rest = tail;
+    }
+    Some(Ok(msgs))
+}
+
+// ----------------------------------------------------------------------------
+
+/// Sent from the server to tell the viewer which streams are being thinned out,
+/// because the viewer can't keep up with the data.
+const DEGRADED_STREAMS_PREFIX: [u8; 4] = *b"RRQS";
+
+/// The query parameter of the websocket url with which the viewer tells the server
+/// that it understands messages about degraded streams.
+///
+/// Older viewers would fail to decode them as log messages, so they don't get any.
+const DEGRADED_STREAMS_QUERY_KEY: &str = "degraded_streams";
+
+/// Tell the server that we understand messages about degraded streams.
+#[cfg(feature = "client")]
+fn url_accepting_degraded_streams(url: &str) -> String {
+    url_with_query(url, DEGRADED_STREAMS_QUERY_KEY, "1")
+}
+
+/// Did the viewer say it understands messages about degraded streams,
+/// in the query of its websocket url?
+#[cfg(feature = "server")]
+fn query_accepts_degraded_streams(query: &str) -> bool {
+    value_from_query(query, DEGRADED_STREAMS_QUERY_KEY).as_deref() == Some("1")
+}
+
+pub fn encode_degraded_streams(degraded_streams: &[EntityPath]) -> Vec<u8> {
+    use bincode::Options as _;
+    let mut bytes = DEGRADED_STREAMS_PREFIX.to_vec();
+    bincode::DefaultOptions::new()
+        .serialize_into(&mut bytes, degraded_streams)
+        .unwrap();
+    bytes
+}
+
+/// Returns `None` if this isn't a message about degraded streams.
+pub fn decode_degraded_streams(data: &[u8]) -> Option<Vec<EntityPath>> {
+    let payload = data.strip_prefix(&DEGRADED_STREAMS_PREFIX)?;
+
+    use bincode::Options as _;
+    match bincode::DefaultOptions::new().deserialize(payload) {
+        Ok(degraded_streams) => Some(degraded_streams),
+        Err(err) => {
+            re_log::warn_once!("Bad message about degraded streams: {err}");
+            None
+        }
+    }
+}
+
+#[test]
+fn test_auth_token_in_fragment() {
+    let auth_token = "s3cr3t/+&= ü";
+    let url = web_viewer_url_with_auth_token("http://127.0.0.1:9090?url=ws://a:9877", auth_token);
+    assert_eq!(
+        url,
+        "http://127.0.0.1:9090?url=ws://a:9877#token=s3cr3t%2F%2B%26%3D%20%C3%BC"
+    );
+    let fragment = url.split_once('#').unwrap().1;
+    assert_eq!(auth_token_from_fragment(fragment).as_deref(), Some(auth_token));
+    assert_eq!(auth_token_from_fragment("#token=abc").as_deref(), Some("abc"));
+
+    assert_eq!(auth_token_from_fragment("#foo=bar"), None);
+    assert_eq!(auth_token_from_fragment("tokens=abc"), None);
+    assert_eq!(auth_token_from_fragment("token=%zz"), None);
+}
+
+#[cfg(all(feature = "client", feature = "server"))]
+#[test]
+fn test_auth_token_msg() {
+    let msg = encode_auth_token("s3cr3t ü");
+    assert_eq!(decode_auth_token(&msg), Some("s3cr3t ü"));
+    assert_eq!(decode_auth_token(&encode_degraded_streams(&[])), None);
+}
+
+#[cfg(all(feature = "client", feature = "server"))]
+#[test]
+fn test_compression() {
+    let url = url_accepting_compression("ws://127.0.0.1:9877?token=abc");
+    let query = url.split_once('?').unwrap().1;
+    assert!(query_accepts_compression(query));
+    assert!(!query_accepts_compression("token=abc"));
+
+    let msg = encode_degraded_streams(&vec![EntityPath::from("camera/image"); 100]);
+    let compressed = compress_msg(&msg, 3).unwrap();
+    assert!(compressed.starts_with(&COMPRESSED_PREFIX));
+    assert!(compressed.len() < msg.len());
+    assert_eq!(decompress_msg(compressed).unwrap(), msg);
+
+    // Uncompressed messages pass through:
+    assert_eq!(decompress_msg(msg.clone()).unwrap(), msg);
+}
+
+#[cfg(all(feature = "client", feature = "server"))]
+#[test]
+fn test_batching() {
+    let url = url_accepting_batches("ws://127.0.0.1:9877?compression=zstd");
+    let query = url.split_once('?').unwrap().1;
+    assert!(query_accepts_batches(query));
+    assert!(!query_accepts_batches("compression=zstd"));
+
+    let msgs = [b"RR00first".to_vec(), vec![], b"RRZ0third".to_vec()];
+    let batch = encode_batch(&msgs);
+    assert_eq!(decode_batch(&batch).unwrap().unwrap(), msgs);
+
+    assert!(decode_batch(&batch[..batch.len() - 1]).unwrap().is_err());
+    assert!(decode_batch(&msgs[0]).is_none());
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn proptest_encode_decode_log_msg(
+        name in ".*",
+        trace_id in proptest::arbitrary::any::<u128>(),
+        start in proptest::arbitrary::any::<i64>(),
+    ) {
+        use re_log_types::{MsgId, Time, TraceSpan, TraceSpansMsg};
+
+        let msg = TraceSpansMsg {
+            msg_id: MsgId::random(),
+            spans: vec![TraceSpan {
+                trace_id,
+                span_id: 1,
+                parent_span_id: None,
+                name,
+                service_name: "test".to_owned(),
+                start: Time::from_ns_since_epoch(start),
+                end: Time::from_ns_since_epoch(start),
+            }],
+        };
+        let encoded = encode_log_msg(&LogMsg::TraceSpansMsg(msg.clone()));
+        match decode_log_msg(&encoded).unwrap() {
+            LogMsg::TraceSpansMsg(decoded) => proptest::prop_assert_eq!(decoded, msg),
+            decoded => proptest::prop_assert!(false, "Wrong kind of message: {decoded:?}"),
+        }
+    }
+
+    /// Messages come from the network, so garbage must be rejected without panicking.
+    #[test]
+    fn proptest_decode_garbage(
+        bytes in proptest::collection::vec(proptest::arbitrary::any::<u8>(), 0..256)
+    ) {
+        let mut msg = PREFIX.to_vec();
+        msg.extend_from_slice(&bytes);
+        let _ = decode_log_msg(&msg);
+    }
+}
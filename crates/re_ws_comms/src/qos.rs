@@ -0,0 +1,357 @@
+//! Quality of service: what to drop when a viewer can't keep up with the data.
+//!
+//! Each connected viewer has its own [`ClientQueue`]. While it is short we send everything.
+//! Once it backs up we start thinning it out: we only keep the latest image of each entity,
+//! and drop low-priority data altogether, until the viewer has caught up again.
+//! The viewer is told which streams are affected, so it can show it.
+
+use std::{
+    collections::{BTreeSet, VecDeque},
+    sync::Arc,
+};
+
+use parking_lot::Mutex;
+
+use re_log_types::{
+    component_types::{Tensor, TextEntry},
+    msg_bundle::{Component as _, MsgBundle},
+    ApplicationId, EntityPath, LogMsg,
+};
+
+/// Start thinning out the data once this much is queued up for a viewer.
+const MAX_QUEUED_BYTES: usize = 16 * 1024 * 1024;
+
+/// How important it is that a message reaches a viewer that can't keep up.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Priority {
+    /// Always sent.
+    Normal,
+
+    /// Only the latest message per entity is worth sending, e.g. video frames.
+    LatestOnly(EntityPath),
+
+    /// Dropped while the viewer can't keep up, e.g. text logs.
+    Low(Option<EntityPath>),
+}
+
+impl Priority {
+    pub fn of(msg: &LogMsg) -> Self {
+        match msg {
+            LogMsg::ArrowMsg(msg) => match MsgBundle::try_from(msg) {
+                Ok(bundle) if !bundle.time_point.is_timeless() => {
+                    if bundle.find_component(&Tensor::name()).is_some() {
+                        Self::LatestOnly(bundle.entity_path)
+                    } else if bundle.find_component(&TextEntry::name()).is_some() {
+                        Self::Low(Some(bundle.entity_path))
+                    } else {
+                        Self::Normal
+                    }
+                }
+                _ => Self::Normal,
+            },
+            LogMsg::TraceSpansMsg(_) => Self::Low(None),
+            _ => Self::Normal,
+        }
+    }
+
+    fn entity_path(&self) -> Option<&EntityPath> {
+        match self {
+            Self::Normal | Self::Low(None) => None,
+            Self::LatestOnly(entity_path) | Self::Low(Some(entity_path)) => Some(entity_path),
+        }
+    }
+}
+
+/// Messages smaller than this aren't worth compressing.
+const MIN_COMPRESSED_SIZE: usize = 1024;
+
+/// An encoded message, ready to send to the viewers.
+pub struct Packet {
+    pub bytes: Arc<[u8]>,
+    pub priority: Priority,
+
+    /// The application of the recording this is part of, see [`crate::RecordingAccess`].
+    ///
+    /// Only known if the server has one.
+    pub application_id: Option<ApplicationId>,
+
+    /// [`Self::bytes`] compressed with a zstd level, once a viewer needed them.
+    compressed: Mutex<Option<(i32, Arc<[u8]>)>>,
+}
+
+impl Packet {
+    pub fn new(
+        bytes: Arc<[u8]>,
+        priority: Priority,
+        application_id: Option<ApplicationId>,
+    ) -> Self {
+        Self {
+            bytes,
+            priority,
+            application_id,
+            compressed: Default::default(),
+        }
+    }
+
+    /// Is it worth compressing this for the viewers that can decompress it?
+    pub fn is_worth_compressing(&self) -> bool {
+        MIN_COMPRESSED_SIZE <= self.bytes.len()
+    }
+
+    /// The bytes to send to a viewer that can decompress messages.
+    ///
+    /// All viewers get the same compression level from a server, so this only compresses once,
+    /// however many viewers are connected. This blocks for a while on large messages.
+    pub fn compressed(&self, zstd_level: i32) -> Arc<[u8]> {
+        if !self.is_worth_compressing() {
+            return self.bytes.clone();
+        }
+
+        // Holding the lock while compressing, so that other viewers wait for us instead of
+        // compressing the same message too.
+        let mut compressed = self.compressed.lock();
+        if let Some((level, bytes)) = &*compressed {
+            if *level == zstd_level {
+                return bytes.clone();
+            }
+        }
+
+        let bytes: Arc<[u8]> = match crate::compress_msg(&self.bytes, zstd_level) {
+            Ok(bytes) => bytes.into(),
+            Err(err) => {
+                re_log::warn_once!("Failed to compress message: {err}");
+                self.bytes.clone()
+            }
+        };
+        *compressed = Some((zstd_level, bytes.clone()));
+        bytes
+    }
+}
+
+/// The messages waiting to be sent to one viewer.
+pub struct ClientQueue {
+    packets: VecDeque<Arc<Packet>>,
+    num_bytes: usize,
+
+    /// Are we thinning out the data?
+    degraded: bool,
+
+    /// The streams we have dropped messages from since we became [`Self::degraded`].
+    degraded_streams: BTreeSet<EntityPath>,
+
+    /// Does the viewer understand messages about [`Self::degraded_streams`]?
+    report_degraded_streams: bool,
+
+    /// Should we tell the viewer about a change in [`Self::degraded_streams`]?
+    report_pending: bool,
+
+    /// Messages dropped since we became [`Self::degraded`].
+    num_dropped: u64,
+}
+
+impl ClientQueue {
+    /// Only tell the viewer which streams we are thinning out if `report_degraded_streams`,
+    /// since older viewers don't understand those messages.
+    pub fn new(report_degraded_streams: bool) -> Self {
+        Self {
+            packets: Default::default(),
+            num_bytes: 0,
+            degraded: false,
+            degraded_streams: Default::default(),
+            report_degraded_streams,
+            report_pending: false,
+            num_dropped: 0,
+        }
+    }
+
+    /// Returns `true` if this is where we started dropping messages,
+    /// for the first time since the viewer last caught up.
+    #[must_use]
+    pub fn push(&mut self, packet: Arc<Packet>) -> bool {
+        let was_dropping = self.is_dropping();
+        self.enqueue(packet);
+        !was_dropping && self.is_dropping()
+    }
+
+    fn is_dropping(&self) -> bool {
+        self.degraded && self.num_dropped > 0
+    }
+
+    fn enqueue(&mut self, packet: Arc<Packet>) {
+        if self.degraded {
+            match &packet.priority {
+                Priority::Normal => {}
+                Priority::LatestOnly(entity_path) => {
+                    self.remove_queued(|queued| queued.priority == packet.priority);
+                    self.mark_degraded(entity_path);
+                }
+                Priority::Low(entity_path) => {
+                    if let Some(entity_path) = entity_path {
+                        self.mark_degraded(entity_path);
+                    }
+                    self.num_dropped += 1;
+                    return;
+                }
+            }
+        }
+
+        self.num_bytes += packet.bytes.len();
+        self.packets.push_back(packet);
+
+        if !self.degraded && self.num_bytes > MAX_QUEUED_BYTES {
+            re_log::debug!("Viewer can't keep up - thinning out the data");
+            self.degraded = true;
+            self.num_dropped = 0;
+            self.thin_out();
+        }
+    }
+
+    /// The next message to send to the viewer, if any.
+    pub fn pop(&mut self) -> Option<Arc<Packet>> {
+        if self.report_pending {
+            self.report_pending = false;
+            let degraded_streams: Vec<EntityPath> = self.degraded_streams.iter().cloned().collect();
+            return Some(Arc::new(Packet::new(
+                crate::encode_degraded_streams(&degraded_streams).into(),
+                Priority::Normal,
+                None,
+            )));
+        }
+
+        let packet = self.packets.pop_front()?;
+        self.num_bytes -= packet.bytes.len();
+
+        if self.degraded && self.packets.is_empty() {
+            re_log::debug!("Viewer has caught up");
+            self.degraded = false;
+            if !self.degraded_streams.is_empty() {
+                self.degraded_streams.clear();
+                self.report_pending = self.report_degraded_streams;
+            }
+        }
+
+        Some(packet)
+    }
+
+    /// Drop everything but the latest image of each entity, and all low-priority data.
+    fn thin_out(&mut self) {
+        let mut latest = BTreeSet::new();
+        let mut keep: Vec<bool> = self
+            .packets
+            .iter()
+            .rev()
+            .map(|packet| match &packet.priority {
+                Priority::Normal => true,
+                Priority::LatestOnly(entity_path) => latest.insert(entity_path.clone()),
+                Priority::Low(_) => false,
+            })
+            .collect();
+        keep.reverse();
+
+        let mut keep = keep.into_iter();
+        let mut dropped = Vec::new();
+        self.packets.retain(|packet| {
+            let keep = keep.next().unwrap_or(true);
+            if !keep {
+                dropped.push(packet.clone());
+            }
+            keep
+        });
+
+        self.num_dropped += dropped.len() as u64;
+        for packet in dropped {
+            self.num_bytes -= packet.bytes.len();
+            if let Some(entity_path) = packet.priority.entity_path() {
+                self.mark_degraded(entity_path);
+            }
+        }
+    }
+
+    fn remove_queued(&mut self, mut predicate: impl FnMut(&Packet) -> bool) {
+        let mut num_bytes_removed = 0;
+        self.packets.retain(|packet| {
+            let remove = predicate(packet);
+            if remove {
+                num_bytes_removed += packet.bytes.len();
+                self.num_dropped += 1;
+            }
+            !remove
+        });
+        self.num_bytes -= num_bytes_removed;
+    }
+
+    fn mark_degraded(&mut self, entity_path: &EntityPath) {
+        if self.degraded_streams.insert(entity_path.clone()) {
+            self.report_pending = self.report_degraded_streams;
+        }
+    }
+}
+
+#[test]
+fn test_client_queue() {
+    fn packet(num_bytes: usize, priority: Priority) -> Arc<Packet> {
+        Arc::new(Packet::new(vec![0; num_bytes].into(), priority, None))
+    }
+
+    let camera = EntityPath::from("camera");
+    let logs = EntityPath::from("logs");
+    let frame_size = MAX_QUEUED_BYTES / 4;
+
+    let mut queue = ClientQueue::new(true);
+    assert!(!queue.push(packet(1, Priority::Low(Some(logs.clone())))));
+    for _ in 0..4 {
+        assert!(!queue.push(packet(frame_size, Priority::LatestOnly(camera.clone()))));
+        assert!(!queue.push(packet(1, Priority::Normal)));
+    }
+    assert!(!queue.degraded);
+
+    // Tipping over the limit drops the old frames and the logs:
+    assert!(queue.push(packet(frame_size, Priority::LatestOnly(camera.clone()))));
+    assert!(queue.degraded);
+    assert_eq!(queue.packets.len(), 5);
+
+    // Only reported once:
+    assert!(!queue.push(packet(1, Priority::Low(Some(logs.clone())))));
+
+    let report = queue.pop().unwrap();
+    assert_eq!(
+        crate::decode_degraded_streams(&report.bytes),
+        Some(vec![camera, logs])
+    );
+
+    for _ in 0..5 {
+        assert!(queue.pop().is_some());
+    }
+
+    // Caught up:
+    assert!(!queue.degraded);
+    let report = queue.pop().unwrap();
+    assert_eq!(crate::decode_degraded_streams(&report.bytes), Some(vec![]));
+    assert!(queue.pop().is_none());
+    assert_eq!(queue.num_bytes, 0);
+
+    // Older viewers are only sent the messages:
+    let mut queue = ClientQueue::new(false);
+    for _ in 0..5 {
+        let _ = queue.push(packet(frame_size, Priority::LatestOnly(camera.clone())));
+    }
+    assert!(queue.degraded);
+    while let Some(packet) = queue.pop() {
+        assert!(crate::decode_degraded_streams(&packet.bytes).is_none());
+    }
+}
+
+#[test]
+fn test_compress_packet_once() {
+    let small = Packet::new(vec![0; 10].into(), Priority::Normal, None);
+    assert!(Arc::ptr_eq(&small.compressed(3), &small.bytes));
+
+    let large = Packet::new(
+        vec![0; 10 * MIN_COMPRESSED_SIZE].into(),
+        Priority::Normal,
+        None,
+    );
+    let compressed = large.compressed(3);
+    assert!(compressed.len() < large.bytes.len());
+    assert!(Arc::ptr_eq(&large.compressed(3), &compressed));
+}
@@ -1,7 +1,8 @@
 //! The server is a pub-sub architecture.
 //!
 //! Each incoming log message is stored, and sent to any connected client.
-//! Each connecting client is first sent the history of stored log messages.
+//! Each connecting client is first sent the history of stored log messages, see [`crate::history`].
+//! If a client can't keep up, some data is dropped for it - see [`crate::qos`].
 //!
 //! In the future thing will be changed to a protocol where the clients can query
 //! for specific data based on e.g. time.
@@ -11,15 +12,157 @@ use std::{net::SocketAddr, sync::Arc};
 use futures_util::{SinkExt, StreamExt};
 use parking_lot::Mutex;
 use tokio::net::{TcpListener, TcpStream};
-use tokio_tungstenite::{accept_async, tungstenite::Error};
+use tokio_tungstenite::{
+    accept_hdr_async,
+    tungstenite::{
+        handshake::server::{ErrorResponse, Request, Response},
+        protocol::{frame::coding::CloseCode, CloseFrame},
+        Error,
+    },
+    WebSocketStream,
+};
 
-use re_log_types::LogMsg;
+use re_log_types::{ApplicationId, LogMsg};
 use re_smart_channel::Receiver;
 
+use crate::{
+    history::{History, HistoryLimits},
+    qos::{ClientQueue, Packet, Priority},
+};
+
+// ----------------------------------------------------------------------------
+
+/// How hard the [`Server`] compresses messages to the viewers that support it.
+///
+/// Compression is done separately for each viewer, so it costs CPU on the server,
+/// but it makes e.g. streams of images much smaller.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CompressionLevel {
+    #[default]
+    Off,
+
+    /// Little CPU for decent compression.
+    Fast,
+
+    Balanced,
+
+    /// For slow networks.
+    Best,
+}
+
+impl CompressionLevel {
+    pub const ALL: [Self; 4] = [Self::Off, Self::Fast, Self::Balanced, Self::Best];
+
+    fn zstd_level(self) -> Option<i32> {
+        match self {
+            Self::Off => None,
+            Self::Fast => Some(1),
+            Self::Balanced => Some(3),
+            Self::Best => Some(19),
+        }
+    }
+}
+
+impl std::fmt::Display for CompressionLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Off => "off",
+            Self::Fast => "fast",
+            Self::Balanced => "balanced",
+            Self::Best => "best",
+        })
+    }
+}
+
+impl std::str::FromStr for CompressionLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|level| level.to_string().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                format!("Unknown compression level {s:?} - expected off, fast, balanced or best")
+            })
+    }
+}
+
+/// Frames to viewers that can unpack batches hold messages up to about this many bytes.
+///
+/// Sending many small messages one frame at a time is dominated by the per-frame overhead.
+const MAX_BATCH_BYTES: usize = 1024 * 1024;
+
+// ----------------------------------------------------------------------------
+
+/// Decides which recordings each viewer may see, based on the auth token it presents.
+///
+/// See [`Server::with_recording_access`].
+pub trait RecordingAccess: Send + Sync {
+    /// Viewers presenting a token that isn't known are rejected.
+    fn is_known_token(&self, auth_token: &str) -> bool;
+
+    /// May the viewer with this (known) token see the recordings of this application?
+    fn may_read(&self, auth_token: &str, application_id: &ApplicationId) -> bool;
+
+    /// The application of the recording this message is part of.
+    ///
+    /// Called once for each message the server receives.
+    /// This can't be worked out from the stream itself, since it may interleave the messages
+    /// of many recordings. `None` hides the message from viewers with a restricted token.
+    fn application_id_of(&self, msg: &LogMsg) -> Option<ApplicationId>;
+}
+
+/// What a connected viewer may see.
+enum ViewerAccess {
+    Everything,
+
+    Restricted {
+        auth_token: String,
+        recording_access: Arc<dyn RecordingAccess>,
+    },
+}
+
+impl ViewerAccess {
+    fn may_see(&self, packet: &Packet) -> bool {
+        match self {
+            Self::Everything => true,
+            Self::Restricted {
+                auth_token,
+                recording_access,
+            } => packet
+                .application_id
+                .as_ref()
+                .map_or(false, |application_id| {
+                    recording_access.may_read(auth_token, application_id)
+                }),
+        }
+    }
+}
+
 // ----------------------------------------------------------------------------
 
+/// Called with the address of a viewer whenever it starts falling behind,
+/// and we begin dropping messages for it.
+type OnDropping = Arc<dyn Fn(SocketAddr) + Send + Sync>;
+
 pub struct Server {
     listener: TcpListener,
+    settings: ConnectionSettings,
+    history_limits: HistoryLimits,
+}
+
+/// How the [`Server`] treats each viewer.
+#[derive(Clone)]
+struct ConnectionSettings {
+    /// If set, viewers must present this token to connect.
+    auth_token: Option<Arc<str>>,
+
+    /// If set, viewers may also connect with the tokens it knows, and see what they may read.
+    recording_access: Option<Arc<dyn RecordingAccess>>,
+
+    compression: CompressionLevel,
+
+    on_dropping: Option<OnDropping>,
 }
 
 impl Server {
@@ -37,16 +180,72 @@ impl Server {
             "Listening for websocket traffic on {bind_addr}. Connect with a web Rerun Viewer."
         );
 
-        Ok(Self { listener })
+        Ok(Self {
+            listener,
+            settings: ConnectionSettings {
+                auth_token: None,
+                recording_access: None,
+                compression: CompressionLevel::Off,
+                on_dropping: None,
+            },
+            history_limits: HistoryLimits::UNLIMITED,
+        })
+    }
+
+    /// Only let viewers that present this token connect, see `ConnectionOptions::auth_token`.
+    ///
+    /// Use `wss://` (e.g. via a reverse proxy), or the token can be read by anyone on the network.
+    pub fn with_auth_token(mut self, auth_token: impl Into<String>) -> Self {
+        self.settings.auth_token = Some(auth_token.into().into());
+        self
+    }
+
+    /// Let viewers also connect with the tokens known to `recording_access`,
+    /// and only send them the recordings their token may read.
+    ///
+    /// Viewers with the token of [`Self::with_auth_token`] still see everything.
+    pub fn with_recording_access(
+        mut self,
+        recording_access: impl RecordingAccess + 'static,
+    ) -> Self {
+        self.settings.recording_access = Some(Arc::new(recording_access));
+        self
+    }
+
+    /// Compress the messages to viewers that can decompress them.
+    ///
+    /// Off by default. Older viewers keep getting uncompressed messages.
+    pub fn with_compression(mut self, compression: CompressionLevel) -> Self {
+        self.settings.compression = compression;
+        self
+    }
+
+    /// Call `on_dropping` with the address of a viewer whenever it can't keep up,
+    /// and we start dropping messages for it, e.g. to write it to an audit log.
+    pub fn with_on_dropping(
+        mut self,
+        on_dropping: impl Fn(SocketAddr) + Send + Sync + 'static,
+    ) -> Self {
+        self.settings.on_dropping = Some(Arc::new(on_dropping));
+        self
+    }
+
+    /// How much of the stream to replay to viewers that connect after logging has started.
+    ///
+    /// Everything by default, which means memory use grows without bounds.
+    pub fn with_history_limits(mut self, history_limits: HistoryLimits) -> Self {
+        self.history_limits = history_limits;
+        self
     }
 
     /// Accept new connections forever
     pub async fn listen(self, rx: Receiver<LogMsg>) -> anyhow::Result<()> {
         use anyhow::Context as _;
 
-        let history = Arc::new(Mutex::new(Vec::new()));
+        let history = Arc::new(Mutex::new(History::new(self.history_limits)));
 
-        let log_stream = to_broadcast_stream(rx, history.clone());
+        let log_stream =
+            to_broadcast_stream(rx, history.clone(), self.settings.recording_access.clone());
 
         while let Ok((tcp_stream, _)) = self.listener.accept().await {
             let peer = tcp_stream
@@ -57,6 +256,7 @@ impl Server {
                 peer,
                 tcp_stream,
                 history.clone(),
+                self.settings.clone(),
             ));
         }
 
@@ -66,17 +266,26 @@ impl Server {
 
 fn to_broadcast_stream(
     log_rx: Receiver<LogMsg>,
-    history: Arc<Mutex<Vec<Arc<[u8]>>>>,
-) -> tokio::sync::broadcast::Sender<Arc<[u8]>> {
+    history: Arc<Mutex<History>>,
+    recording_access: Option<Arc<dyn RecordingAccess>>,
+) -> tokio::sync::broadcast::Sender<Arc<Packet>> {
     let (tx, _) = tokio::sync::broadcast::channel(1024 * 1024);
     let tx1 = tx.clone();
     tokio::task::spawn_blocking(move || {
         while let Ok(log_msg) = log_rx.recv() {
-            let bytes = crate::encode_log_msg(&log_msg);
-            let bytes: Arc<[u8]> = bytes.into();
-            history.lock().push(bytes.clone());
+            let packet = Arc::new(Packet::new(
+                crate::encode_log_msg(&log_msg).into(),
+                Priority::of(&log_msg),
+                recording_access
+                    .as_ref()
+                    .and_then(|recording_access| recording_access.application_id_of(&log_msg)),
+            ));
 
-            if let Err(tokio::sync::broadcast::error::SendError(_bytes)) = tx1.send(bytes) {
+            // Holding the lock while sending, so that a connecting viewer gets each message
+            // either in the history or live, see `handle_connection`:
+            let mut history_guard = history.lock();
+            history_guard.push(&log_msg, packet.clone());
+            if let Err(tokio::sync::broadcast::error::SendError(_packet)) = tx1.send(packet) {
                 // no receivers currently - that's fine!
             }
         }
@@ -85,70 +294,375 @@ fn to_broadcast_stream(
 }
 
 async fn accept_connection(
-    log_stream: tokio::sync::broadcast::Sender<Arc<[u8]>>,
-    _peer: SocketAddr,
+    log_stream: tokio::sync::broadcast::Sender<Arc<Packet>>,
+    peer: SocketAddr,
     tcp_stream: TcpStream,
-    history: Arc<Mutex<Vec<Arc<[u8]>>>>,
+    history: Arc<Mutex<History>>,
+    settings: ConnectionSettings,
 ) {
     // let span = re_log::span!(
     //     re_log::Level::INFO,
     //     "Connection",
-    //     peer = _peer.to_string().as_str()
+    //     peer = peer.to_string().as_str()
     // );
     // let _enter = span.enter();
 
     re_log::debug!("New WebSocket connection");
 
-    if let Err(err) = handle_connection(log_stream, tcp_stream, history).await {
+    if let Err(err) = handle_connection(log_stream, peer, tcp_stream, history, settings).await {
         match err {
+            // We've already logged why we rejected the connection.
+            Error::Http(_) => (),
             Error::ConnectionClosed | Error::Protocol(_) | Error::Utf8 => (),
             err => re_log::error!("Error processing connection: {err}"),
         }
     }
 }
 
+const REJECTED_AUTH_TOKEN: &str = "Missing or wrong auth token";
+
+/// Reject the websocket handshake if it has an `Authorization: Bearer` header with a token
+/// we don't know.
+///
+/// `None` if we need a token, but it wasn't in a header: then it must be the first message.
+fn check_auth_header(
+    expected: Option<&str>,
+    recording_access: Option<&Arc<dyn RecordingAccess>>,
+    peer: SocketAddr,
+    request: &Request,
+) -> Result<Option<ViewerAccess>, ErrorResponse> {
+    if expected.is_none() && recording_access.is_none() {
+        return Ok(Some(ViewerAccess::Everything));
+    }
+
+    let header = request
+        .headers()
+        .get(tungstenite::http::header::AUTHORIZATION);
+    let Some(header) = header else {
+        return Ok(None);
+    };
+    let token = header
+        .to_str()
+        .ok()
+        .and_then(|value| value.strip_prefix("Bearer "));
+    if let Some(viewer_access) =
+        token.and_then(|token| viewer_access_of(expected, recording_access, token))
+    {
+        return Ok(Some(viewer_access));
+    }
+
+    re_log::warn!("Rejected viewer at {peer}: wrong auth token");
+    let mut error = ErrorResponse::new(Some(REJECTED_AUTH_TOKEN.to_owned()));
+    *error.status_mut() = tungstenite::http::StatusCode::UNAUTHORIZED;
+    Err(error)
+}
+
+/// `None` if the token isn't one we know.
+fn viewer_access_of(
+    expected: Option<&str>,
+    recording_access: Option<&Arc<dyn RecordingAccess>>,
+    token: &str,
+) -> Option<ViewerAccess> {
+    if let Some(expected) = expected {
+        if crate::constant_time_eq(token.as_bytes(), expected.as_bytes()) {
+            return Some(ViewerAccess::Everything);
+        }
+    }
+    let recording_access = recording_access?;
+    recording_access
+        .is_known_token(token)
+        .then(|| ViewerAccess::Restricted {
+            auth_token: token.to_owned(),
+            recording_access: recording_access.clone(),
+        })
+}
+
+/// Wait for the viewer to send its auth token as its first message.
+///
+/// `None` if it didn't in time, or if it isn't a token we know.
+async fn receive_auth_token(
+    ws_receiver: &mut futures_util::stream::SplitStream<WebSocketStream<TcpStream>>,
+    expected: Option<&str>,
+    recording_access: Option<&Arc<dyn RecordingAccess>>,
+    peer: SocketAddr,
+) -> Option<ViewerAccess> {
+    let first_msg = tokio::time::timeout(crate::AUTH_TOKEN_TIMEOUT, ws_receiver.next()).await;
+    let token = match &first_msg {
+        Ok(Some(Ok(tungstenite::Message::Binary(data)))) => crate::decode_auth_token(data),
+        _ => None,
+    };
+    let Some(token) = token else {
+        re_log::warn!("Rejected viewer at {peer}: no auth token");
+        return None;
+    };
+    let viewer_access = viewer_access_of(expected, recording_access, token);
+    if viewer_access.is_none() {
+        re_log::warn!("Rejected viewer at {peer}: wrong auth token");
+    }
+    viewer_access
+}
+
 async fn handle_connection(
-    log_stream: tokio::sync::broadcast::Sender<Arc<[u8]>>,
+    log_stream: tokio::sync::broadcast::Sender<Arc<Packet>>,
+    peer: SocketAddr,
     tcp_stream: TcpStream,
-    history: Arc<Mutex<Vec<Arc<[u8]>>>>,
+    history: Arc<Mutex<History>>,
+    settings: ConnectionSettings,
 ) -> tungstenite::Result<()> {
-    let ws_stream = accept_async(tcp_stream).await.expect("Failed to accept");
+    let ConnectionSettings {
+        auth_token,
+        recording_access,
+        compression,
+        on_dropping,
+    } = settings;
+    let on_dropping = || {
+        if let Some(on_dropping) = &on_dropping {
+            on_dropping(peer);
+        }
+    };
+
+    let mut accepts_compression = false;
+    let mut accepts_batches = false;
+    let mut accepts_degraded_streams = false;
+    let mut viewer_access = None;
+    let ws_stream = accept_hdr_async(tcp_stream, |request: &Request, response: Response| {
+        let query = request.uri().query();
+        accepts_compression = query.map_or(false, crate::query_accepts_compression);
+        accepts_batches = query.map_or(false, crate::query_accepts_batches);
+        accepts_degraded_streams = query.map_or(false, crate::query_accepts_degraded_streams);
+        viewer_access = check_auth_header(
+            auth_token.as_deref(),
+            recording_access.as_ref(),
+            peer,
+            request,
+        )?;
+        Ok(response)
+    })
+    .await?;
     let (mut ws_sender, mut ws_receiver) = ws_stream.split();
 
-    // Re-sending packet history - this is not water tight, but better than nothing.
-    // TODO(emilk): water-proof resending of history + streaming of new stuff, without anything missed.
-    let history = history.lock().to_vec();
-    for packet in history {
+    let viewer_access = match viewer_access {
+        Some(viewer_access) => viewer_access,
+        None => {
+            let viewer_access = receive_auth_token(
+                &mut ws_receiver,
+                auth_token.as_deref(),
+                recording_access.as_ref(),
+                peer,
+            )
+            .await;
+            let Some(viewer_access) = viewer_access else {
+                let close_frame = CloseFrame {
+                    code: CloseCode::Policy,
+                    reason: REJECTED_AUTH_TOKEN.into(),
+                };
+                ws_sender
+                    .send(tungstenite::Message::Close(Some(close_frame)))
+                    .await
+                    .ok();
+                return Ok(());
+            };
+            viewer_access
+        }
+    };
+
+    let zstd_level = if accepts_compression {
+        compression.zstd_level()
+    } else {
+        None
+    };
+    let max_batch_bytes = if accepts_batches { MAX_BATCH_BYTES } else { 0 };
+
+    // Subscribing while holding the lock, so that we get everything after the history,
+    // without gaps or duplicates:
+    let (history, mut log_rx) = {
+        let mut history = history.lock();
+        (history.packets(), log_stream.subscribe())
+    };
+
+    // Catch up before streaming live. New messages wait in `log_rx` meanwhile.
+    let mut history = history
+        .into_iter()
+        .filter(|packet| viewer_access.may_see(packet));
+    loop {
+        let packets = next_batch(|| history.next(), max_batch_bytes);
+        if packets.is_empty() {
+            break;
+        }
         ws_sender
-            .send(tungstenite::Message::Binary(packet.to_vec()))
+            .send(tungstenite::Message::Binary(
+                frame_to_send(packets, zstd_level).await,
+            ))
             .await?;
     }
 
-    let mut log_rx = log_stream.subscribe();
+    // We queue up new messages for this client ourselves, so that we can decide what to drop
+    // if it can't keep up.
+    let queue = Mutex::new(ClientQueue::new(accepts_degraded_streams));
+    let queue_changed = tokio::sync::Notify::new();
 
-    loop {
-        tokio::select! {
-            ws_msg = ws_receiver.next() => {
-                match ws_msg {
-                    Some(Ok(msg)) => {
-                        re_log::debug!("Received message: {:?}", msg);
+    let receive = async {
+        loop {
+            tokio::select! {
+                ws_msg = ws_receiver.next() => {
+                    match ws_msg {
+                        Some(Ok(msg)) => {
+                            re_log::debug!("Received message: {:?}", msg);
+                        }
+                        Some(Err(err)) => {
+                            re_log::warn!("Error message: {err}");
+                            break;
+                        }
+                        None => {
+                            break;
+                        }
                     }
-                    Some(Err(err)) => {
-                        re_log::warn!("Error message: {err}");
-                        break;
-                    }
-                    None => {
-                        break;
+                }
+                packet = log_rx.recv() => {
+                    match packet {
+                        Ok(packet) => {
+                            if viewer_access.may_see(&packet) {
+                                let started_dropping = queue.lock().push(packet);
+                                queue_changed.notify_one();
+                                if started_dropping {
+                                    on_dropping();
+                                }
+                            }
+                        }
+                        Err(tokio::sync::broadcast::error::RecvError::Lagged(num_skipped)) => {
+                            re_log::warn_once!("Dropped {num_skipped} messages for a slow viewer");
+                            on_dropping();
+                        }
+                        Err(tokio::sync::broadcast::error::RecvError::Closed) => {
+                            break;
+                        }
                     }
                 }
             }
-            data_msg = log_rx.recv() => {
-                let data_msg = data_msg.unwrap();
+        }
+        Ok(())
+    };
 
-                ws_sender.send(tungstenite::Message::Binary(data_msg.to_vec())).await?;
-            }
+    let send = send_queued(
+        ws_sender,
+        &queue,
+        &queue_changed,
+        zstd_level,
+        max_batch_bytes,
+    );
+    tokio::select! {
+        result = receive => result,
+        result = send => result,
+    }
+}
+
+/// Send the queued messages to the client as fast as it can take them.
+async fn send_queued(
+    mut ws_sender: impl futures_util::Sink<tungstenite::Message, Error = Error> + Unpin,
+    queue: &Mutex<ClientQueue>,
+    queue_changed: &tokio::sync::Notify,
+    zstd_level: Option<i32>,
+    max_batch_bytes: usize,
+) -> tungstenite::Result<()> {
+    loop {
+        let packets = {
+            let mut queue = queue.lock();
+            next_batch(|| queue.pop(), max_batch_bytes)
+        };
+        if packets.is_empty() {
+            queue_changed.notified().await;
+        } else {
+            ws_sender
+                .send(tungstenite::Message::Binary(
+                    frame_to_send(packets, zstd_level).await,
+                ))
+                .await?;
         }
     }
+}
+
+/// The packets to send in the next frame: as many as add up to `max_batch_bytes`,
+/// but at least one, unless there are none.
+fn next_batch(
+    mut next_packet: impl FnMut() -> Option<Arc<Packet>>,
+    max_batch_bytes: usize,
+) -> Vec<Arc<Packet>> {
+    let mut packets = Vec::new();
+    let mut num_bytes = 0;
+    while packets.is_empty() || num_bytes < max_batch_bytes {
+        let Some(packet) = next_packet() else {
+            break;
+        };
+        num_bytes += packet.bytes.len();
+        packets.push(packet);
+    }
+    packets
+}
 
-    Ok(())
+/// One websocket frame with these packets, batched together if there are several.
+async fn frame_to_send(packets: Vec<Arc<Packet>>, zstd_level: Option<i32>) -> Vec<u8> {
+    let mut frames = Vec::with_capacity(packets.len());
+    for packet in packets {
+        frames.push(bytes_to_send(packet, zstd_level).await);
+    }
+    if frames.len() == 1 {
+        frames[0].to_vec()
+    } else {
+        crate::encode_batch(&frames)
+    }
+}
+
+/// Compressed if the viewer supports it and it is worth it.
+///
+/// Compressing happens on a blocking thread, so that it doesn't hold up the other connections.
+async fn bytes_to_send(packet: Arc<Packet>, zstd_level: Option<i32>) -> Arc<[u8]> {
+    let Some(zstd_level) = zstd_level.filter(|_| packet.is_worth_compressing()) else {
+        return packet.bytes.clone();
+    };
+    let bytes = packet.bytes.clone();
+    match tokio::task::spawn_blocking(move || packet.compressed(zstd_level)).await {
+        Ok(compressed) => compressed,
+        Err(err) => {
+            re_log::warn_once!("Failed to compress message: {err}");
+            bytes
+        }
+    }
+}
+
+#[test]
+fn test_next_batch() {
+    use crate::qos::Priority;
+
+    let packets: Vec<_> = [10, 20, 30, 40]
+        .into_iter()
+        .map(|num_bytes| {
+            Arc::new(Packet::new(
+                vec![0; num_bytes].into(),
+                Priority::Normal,
+                None,
+            ))
+        })
+        .collect();
+    let sizes = |batch: Vec<Arc<Packet>>| -> Vec<usize> {
+        batch.iter().map(|packet| packet.bytes.len()).collect()
+    };
+
+    let mut iter = packets.iter().cloned();
+    assert_eq!(sizes(next_batch(|| iter.next(), 25)), vec![10, 20]);
+    assert_eq!(sizes(next_batch(|| iter.next(), 25)), vec![30]);
+    assert_eq!(sizes(next_batch(|| iter.next(), 25)), vec![40]);
+    assert!(next_batch(|| iter.next(), 25).is_empty());
+
+    // Not batching:
+    let mut iter = packets.iter().cloned();
+    assert_eq!(sizes(next_batch(|| iter.next(), 0)), vec![10]);
+}
+
+#[test]
+fn test_compression_level_from_str() {
+    for level in CompressionLevel::ALL {
+        assert_eq!(level.to_string().parse::<CompressionLevel>(), Ok(level));
+    }
+    assert_eq!("Fast".parse(), Ok(CompressionLevel::Fast));
+    assert!("ultra".parse::<CompressionLevel>().is_err());
 }
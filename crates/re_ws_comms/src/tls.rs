@@ -0,0 +1,173 @@
+//! Native `wss://` connections that trust extra root certificates,
+//! e.g. of a company-internal certificate authority.
+//!
+//! [`ewebsock`] only trusts the well-known root certificates, so we do the connection ourselves.
+
+use std::{
+    io::BufReader,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use anyhow::Context as _;
+use ewebsock::{WsEvent, WsMessage};
+
+use crate::Result;
+
+/// Closes the connection on drop.
+pub struct WsSender {
+    shutdown: Arc<AtomicBool>,
+}
+
+impl Drop for WsSender {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Trusts the well-known root certificates, plus the given PEM-encoded ones.
+fn client_config(extra_root_certificates_pem: &[Vec<u8>]) -> Result<rustls::ClientConfig> {
+    let mut root_store = rustls::RootCertStore::empty();
+    root_store.add_server_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.0.iter().map(|root| {
+        rustls::OwnedTrustAnchor::from_subject_spki_name_constraints(
+            root.subject,
+            root.spki,
+            root.name_constraints,
+        )
+    }));
+
+    for pem in extra_root_certificates_pem {
+        let certificates = rustls_pemfile::certs(&mut BufReader::new(pem.as_slice()))
+            .context("Failed to parse PEM root certificate")?;
+        anyhow::ensure!(!certificates.is_empty(), "No certificate found in PEM");
+        for certificate in certificates {
+            root_store
+                .add(&rustls::Certificate(certificate))
+                .context("Bad root certificate")?;
+        }
+    }
+
+    Ok(rustls::ClientConfig::builder()
+        .with_safe_defaults()
+        .with_root_certificates(root_store)
+        .with_no_client_auth())
+}
+
+/// Like [`ewebsock::ws_connect`], but for a `wss://` url and with extra root certificates.
+pub fn ws_connect(
+    url: String,
+    extra_root_certificates_pem: &[Vec<u8>],
+    auth_token: Option<&str>,
+    on_event: ewebsock::EventHandler,
+) -> Result<WsSender> {
+    let config = Arc::new(client_config(extra_root_certificates_pem)?);
+    let shutdown = Arc::new(AtomicBool::new(false));
+    let shutdown_clone = shutdown.clone();
+    let auth_token = auth_token.map(ToOwned::to_owned);
+
+    std::thread::Builder::new()
+        .name("ws_connect".to_owned())
+        .spawn(move || {
+            if let Err(err) = ws_receive_blocking(
+                &url,
+                config,
+                auth_token.as_deref(),
+                &shutdown_clone,
+                &on_event,
+            ) {
+                on_event(WsEvent::Error(format!("{err:#}")));
+            }
+        })
+        .context("Failed to spawn thread")?;
+
+    Ok(WsSender { shutdown })
+}
+
+fn ws_receive_blocking(
+    url: &str,
+    config: Arc<rustls::ClientConfig>,
+    auth_token: Option<&str>,
+    shutdown: &AtomicBool,
+    on_event: &ewebsock::EventHandler,
+) -> Result<()> {
+    let mut request = tungstenite::client::IntoClientRequest::into_client_request(url)?;
+    if let Some(auth_token) = auth_token {
+        let header = format!("Bearer {auth_token}")
+            .parse()
+            .context("Bad auth token")?;
+        request
+            .headers_mut()
+            .insert(tungstenite::http::header::AUTHORIZATION, header);
+    }
+    let uri = request.uri();
+    let host = host_without_brackets(uri.host().context("No host in url")?).to_owned();
+    let port = uri.port_u16().unwrap_or(443);
+    let stream = std::net::TcpStream::connect((host.as_str(), port))
+        .with_context(|| format!("Failed to connect to {host}:{port}"))?;
+
+    let server_name = rustls::ServerName::try_from(host.as_str())
+        .with_context(|| format!("Bad server name {host:?}"))?;
+    let tls_connection =
+        rustls::ClientConnection::new(config, server_name).context("Failed to set up TLS")?;
+    let tls_stream = rustls::StreamOwned::new(tls_connection, stream.try_clone()?);
+
+    let (mut socket, _response) = tungstenite::client(request, tls_stream)
+        .map_err(|err| anyhow::format_err!("Handshake failed: {err}"))?;
+
+    // So that we notice when the [`WsSender`] is dropped, even if the server is quiet:
+    stream.set_nonblocking(true)?;
+
+    if on_event(WsEvent::Opened).is_break() {
+        return Ok(());
+    }
+
+    while !shutdown.load(Ordering::Relaxed) {
+        let message = match socket.read_message() {
+            Ok(message) => message,
+            Err(tungstenite::Error::Io(err)) if err.kind() == std::io::ErrorKind::WouldBlock => {
+                std::thread::sleep(std::time::Duration::from_millis(10));
+                continue;
+            }
+            Err(tungstenite::Error::ConnectionClosed | tungstenite::Error::AlreadyClosed) => {
+                on_event(WsEvent::Closed);
+                return Ok(());
+            }
+            Err(err) => anyhow::bail!("Read failed: {err}"),
+        };
+
+        let message = match message {
+            tungstenite::Message::Binary(binary) => WsMessage::Binary(binary),
+            tungstenite::Message::Text(text) => WsMessage::Text(text),
+            tungstenite::Message::Ping(data) => WsMessage::Ping(data),
+            tungstenite::Message::Pong(data) => WsMessage::Pong(data),
+            tungstenite::Message::Close(_) => {
+                on_event(WsEvent::Closed);
+                return Ok(());
+            }
+            tungstenite::Message::Frame(frame) => WsMessage::Unknown(format!("{frame:?}")),
+        };
+        if on_event(WsEvent::Message(message)).is_break() {
+            break;
+        }
+    }
+
+    socket.close(None).ok();
+    Ok(())
+}
+
+/// The host of an url is bracketed if it is an IPv6 address, e.g. `[::1]`,
+/// but we need it without when connecting.
+fn host_without_brackets(host: &str) -> &str {
+    host.strip_prefix('[')
+        .and_then(|host| host.strip_suffix(']'))
+        .unwrap_or(host)
+}
+
+#[test]
+fn test_host_without_brackets() {
+    assert_eq!(host_without_brackets("[::1]"), "::1");
+    assert_eq!(host_without_brackets("127.0.0.1"), "127.0.0.1");
+    assert_eq!(host_without_brackets("example.com"), "example.com");
+}
@@ -0,0 +1,138 @@
+//! `rerun audit-log …`: query the audit log written by a Rerun Server started with `--audit-log`.
+
+use std::{collections::HashMap, net::SocketAddr, path::PathBuf};
+
+use re_format::{format_bytes, format_number};
+use re_log_types::RecordingId;
+use re_sdk_comms::audit_log::{read_audit_log, AuditEvent, AuditEventKind};
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct AuditLogArgs {
+    /// The audit log to read.
+    path: PathBuf,
+
+    /// Only show the events of this client: an ip address, `ip:port`, or hostname.
+    #[clap(long)]
+    client: Option<String>,
+
+    /// Only show the events of this recording.
+    #[clap(long)]
+    recording: Option<RecordingId>,
+
+    /// Print the matching events as JSON, one per line, e.g. to pipe them into `jq`.
+    #[clap(long)]
+    json: bool,
+}
+
+pub fn run(args: &AuditLogArgs) -> anyhow::Result<()> {
+    let AuditLogArgs {
+        path,
+        client,
+        recording,
+        json,
+    } = args;
+
+    // What we know about each client at the time of each event:
+    let mut hostnames = HashMap::<SocketAddr, Option<String>>::default();
+    let mut recordings = HashMap::<SocketAddr, RecordingId>::default();
+
+    for event in read_audit_log(path)? {
+        match &event.kind {
+            AuditEventKind::Connected { hostname, .. } => {
+                hostnames.insert(event.peer_addr, hostname.clone());
+                recordings.remove(&event.peer_addr);
+            }
+            AuditEventKind::RecordingStarted { recording_id, .. } => {
+                recordings.insert(event.peer_addr, *recording_id);
+            }
+            AuditEventKind::Dropping { .. }
+            | AuditEventKind::RecordingEnded { .. }
+            | AuditEventKind::Disconnected { .. } => {}
+        }
+
+        if let Some(client) = client {
+            let hostname = hostnames.get(&event.peer_addr).cloned().flatten();
+            if *client != event.peer_addr.to_string()
+                && *client != event.peer_addr.ip().to_string()
+                && Some(client) != hostname.as_ref()
+            {
+                continue;
+            }
+        }
+        if let Some(recording) = recording {
+            let event_recording = event
+                .recording_id()
+                .or_else(|| recordings.get(&event.peer_addr).copied());
+            if event_recording != Some(*recording) {
+                continue;
+            }
+        }
+
+        if *json {
+            println!("{}", event.to_json());
+        } else {
+            println!("{}", format_event(&event));
+        }
+    }
+
+    Ok(())
+}
+
+fn format_event(event: &AuditEvent) -> String {
+    let AuditEvent {
+        time,
+        peer_addr,
+        kind,
+    } = event;
+
+    let what = match kind {
+        AuditEventKind::Connected {
+            hostname,
+            protocol_version,
+        } => format!(
+            "connected from {} (protocol version {protocol_version})",
+            hostname.as_deref().unwrap_or("an unknown host")
+        ),
+        AuditEventKind::RecordingStarted {
+            recording_id,
+            application_id,
+        } => format!("started recording {recording_id} of {application_id}"),
+        AuditEventKind::Dropping { reason } => {
+            format!("started dropping messages ({reason:?})")
+        }
+        AuditEventKind::RecordingEnded {
+            recording_id,
+            num_messages,
+            num_bytes,
+            num_dropped,
+        } => format!(
+            "ended recording {recording_id}: {}",
+            format_totals(*num_messages, *num_bytes, *num_dropped)
+        ),
+        AuditEventKind::Disconnected {
+            num_messages,
+            num_bytes,
+            num_dropped,
+            error,
+        } => {
+            let mut what = format!(
+                "disconnected: {}",
+                format_totals(*num_messages, *num_bytes, *num_dropped)
+            );
+            if let Some(error) = error {
+                what += &format!(" - {error}");
+            }
+            what
+        }
+    };
+    format!("{time}  {peer_addr}  {what}")
+}
+
+fn format_totals(num_messages: u64, num_bytes: u64, num_dropped: u64) -> String {
+    format!(
+        "{} messages ({}), {} dropped",
+        format_number(num_messages as _),
+        format_bytes(num_bytes as _),
+        format_number(num_dropped as _)
+    )
+}
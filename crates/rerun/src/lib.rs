@@ -84,13 +84,22 @@
 //! rerun --help
 //! ```
 //!
+//! To open `.rrd` files and `rerun://host:port` links with the viewer straight from your
+//! file browser or web browser, run `rerun register` once after installing.
+//!
 //! ## Feature flags
 #![doc = document_features::document_features!()]
 //!
 
 #![warn(missing_docs)] // Let's keep the this crate well-documented!
 
+#[cfg(feature = "server")]
+mod audit_log;
 mod crash_handler;
+mod os_integration;
+#[cfg(feature = "otlp")]
+mod otlp;
+mod rrd;
 mod run;
 
 pub use run::{run, CallSource};
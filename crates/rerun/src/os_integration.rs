@@ -0,0 +1,225 @@
+//! Registering Rerun with the operating system, so that double-clicking a `.rrd` file
+//! or clicking a `rerun://host:port` link opens the Rerun Viewer.
+//!
+//! This is opt-in, via `rerun register`.
+
+use anyhow::Context as _;
+
+/// The url scheme for links that should open in the Rerun Viewer, e.g. `rerun://127.0.0.1:9877`.
+pub const URL_SCHEME: &str = re_viewer::Bookmark::URL_SCHEME;
+
+/// Converts a `rerun://host:port` link into the websocket url of the Rerun Server to connect to.
+///
+/// The rest of the link, e.g. the time and selection to go to, is handled by the viewer,
+/// see [`re_viewer::Bookmark`].
+///
+/// Returns `None` if the url is not a `rerun://` link.
+pub fn ws_url_from_rerun_url(url: &str) -> Option<String> {
+    re_viewer::Bookmark::from_url(url).map(|bookmark| bookmark.ws_url())
+}
+
+/// Converts a `file://` url into the path it points to, e.g. `file:///home/me/my%20data.rrd`.
+///
+/// File managers open `.rrd` files like this, since we registered for urls too (see [`register`]).
+///
+/// Returns `None` if the url is not a local `file://` url.
+pub fn path_from_file_url(url: &str) -> Option<std::path::PathBuf> {
+    let rest = url.strip_prefix("file://")?;
+    let path = rest.strip_prefix("localhost").unwrap_or(rest);
+    if !path.starts_with('/') {
+        return None; // On another host
+    }
+
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    let path = String::from_utf8(decoded).ok()?;
+
+    // `file:///C:/data.rrd` on Windows:
+    let is_drive = |path: &str| path.as_bytes().get(2) == Some(&b':');
+    let path = if cfg!(windows) && is_drive(&path) {
+        path[1..].to_owned()
+    } else {
+        path
+    };
+    Some(path.into())
+}
+
+/// Register the currently running executable as the handler
+/// for `.rrd` files and `rerun://` links, for the current user.
+pub fn register() -> anyhow::Result<()> {
+    let exe_path = std::env::current_exe().context("Failed to find the current executable")?;
+    register_impl(&exe_path)?;
+    re_log::info!(
+        "Registered {exe_path:?} as the handler for .rrd files and {URL_SCHEME}:// links"
+    );
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn register_impl(exe_path: &std::path::Path) -> anyhow::Result<()> {
+    let data_home = std::env::var_os("XDG_DATA_HOME")
+        .map(std::path::PathBuf::from)
+        .or_else(|| {
+            std::env::var_os("HOME").map(|home| std::path::Path::new(&home).join(".local/share"))
+        })
+        .context("Neither $XDG_DATA_HOME nor $HOME is set")?;
+
+    let applications_dir = data_home.join("applications");
+    let mime_packages_dir = data_home.join("mime/packages");
+    std::fs::create_dir_all(&applications_dir)
+        .with_context(|| format!("Failed to create {applications_dir:?}"))?;
+    std::fs::create_dir_all(&mime_packages_dir)
+        .with_context(|| format!("Failed to create {mime_packages_dir:?}"))?;
+
+    let desktop_entry = format!(
+        "[Desktop Entry]\n\
+        Type=Application\n\
+        Name=Rerun Viewer\n\
+        Exec=\"{}\" %u\n\
+        Terminal=false\n\
+        NoDisplay=true\n\
+        MimeType=application/x-rerun;x-scheme-handler/{URL_SCHEME};\n",
+        exe_path.display()
+    );
+    let desktop_path = applications_dir.join("rerun.desktop");
+    std::fs::write(&desktop_path, desktop_entry)
+        .with_context(|| format!("Failed to write {desktop_path:?}"))?;
+
+    let mime_info = r#"<?xml version="1.0" encoding="UTF-8"?>
+<mime-info xmlns="http://www.freedesktop.org/standards/shared-mime-info">
+  <mime-type type="application/x-rerun">
+    <comment>Rerun recording</comment>
+    <glob pattern="*.rrd"/>
+  </mime-type>
+</mime-info>
+"#;
+    let mime_path = mime_packages_dir.join("rerun.xml");
+    std::fs::write(&mime_path, mime_info)
+        .with_context(|| format!("Failed to write {mime_path:?}"))?;
+
+    // These tools are not available everywhere, so failing to run them is not fatal:
+    let mime_dir = data_home.join("mime");
+    let scheme_mime_type = format!("x-scheme-handler/{URL_SCHEME}");
+    run_command("update-mime-database", &[&mime_dir.to_string_lossy()]).ok();
+    run_command(
+        "update-desktop-database",
+        &[&applications_dir.to_string_lossy()],
+    )
+    .ok();
+    run_command(
+        "xdg-mime",
+        &["default", "rerun.desktop", "application/x-rerun"],
+    )
+    .ok();
+    run_command("xdg-mime", &["default", "rerun.desktop", &scheme_mime_type]).ok();
+
+    Ok(())
+}
+
+#[cfg(target_os = "windows")]
+fn register_impl(exe_path: &std::path::Path) -> anyhow::Result<()> {
+    let open_command = format!("\"{}\" \"%1\"", exe_path.display());
+    let classes = r"HKCU\Software\Classes";
+
+    let scheme_key = format!(r"{classes}\{URL_SCHEME}");
+    let scheme_command_key = format!(r"{classes}\{URL_SCHEME}\shell\open\command");
+    let extension_key = format!(r"{classes}\.rrd");
+    let rrd_command_key = format!(r"{classes}\Rerun.Recording\shell\open\command");
+
+    run_command("reg", &["add", &scheme_key, "/ve", "/d", "URL:Rerun", "/f"])?;
+    run_command(
+        "reg",
+        &["add", &scheme_key, "/v", "URL Protocol", "/d", "", "/f"],
+    )?;
+    run_command(
+        "reg",
+        &["add", &scheme_command_key, "/ve", "/d", &open_command, "/f"],
+    )?;
+    run_command(
+        "reg",
+        &["add", &extension_key, "/ve", "/d", "Rerun.Recording", "/f"],
+    )?;
+    run_command(
+        "reg",
+        &["add", &rrd_command_key, "/ve", "/d", &open_command, "/f"],
+    )?;
+
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows")))]
+fn register_impl(_exe_path: &std::path::Path) -> anyhow::Result<()> {
+    // On macOS file associations and url schemes are declared in the `Info.plist` of an app bundle,
+    // which a bare executable doesn't have.
+    anyhow::bail!(
+        "Registering file associations is not supported on this platform. \
+        On macOS, use a Rerun app bundle instead."
+    )
+}
+
+#[cfg(any(target_os = "linux", target_os = "windows"))]
+fn run_command(program: &str, args: &[&str]) -> anyhow::Result<()> {
+    let status = std::process::Command::new(program)
+        .args(args)
+        .stdout(std::process::Stdio::null())
+        .status()
+        .with_context(|| format!("Failed to run {program:?}"))?;
+    anyhow::ensure!(status.success(), "{program:?} {args:?} failed: {status}");
+    Ok(())
+}
+
+#[test]
+fn test_ws_url_from_rerun_url() {
+    assert_eq!(
+        ws_url_from_rerun_url("rerun://127.0.0.1:9877").as_deref(),
+        Some(format!("{}://127.0.0.1:9877", re_ws_comms::PROTOCOL).as_str())
+    );
+    assert_eq!(
+        ws_url_from_rerun_url("rerun://example.com:9877/").as_deref(),
+        Some(format!("{}://example.com:9877", re_ws_comms::PROTOCOL).as_str())
+    );
+    assert_eq!(
+        ws_url_from_rerun_url("rerun://example.com:9877/00000000-0000-0000-0000-000000000000?t=3")
+            .as_deref(),
+        Some(format!("{}://example.com:9877", re_ws_comms::PROTOCOL).as_str())
+    );
+    assert_eq!(ws_url_from_rerun_url("ws://127.0.0.1:9877"), None);
+    assert_eq!(ws_url_from_rerun_url("recording.rrd"), None);
+}
+
+#[cfg(not(windows))]
+#[test]
+fn test_path_from_file_url() {
+    assert_eq!(
+        path_from_file_url("file:///home/me/my%20data.rrd"),
+        Some("/home/me/my data.rrd".into())
+    );
+    assert_eq!(
+        path_from_file_url("file://localhost/tmp/a.rrd"),
+        Some("/tmp/a.rrd".into())
+    );
+    assert_eq!(
+        path_from_file_url("file:///tmp/100%.rrd"),
+        Some("/tmp/100%.rrd".into())
+    );
+    assert_eq!(path_from_file_url("file://server/share/a.rrd"), None);
+    assert_eq!(path_from_file_url("/tmp/a.rrd"), None);
+    assert_eq!(path_from_file_url("rerun://127.0.0.1:9877"), None);
+}
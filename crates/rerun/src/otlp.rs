@@ -0,0 +1,319 @@
+//! Receive [OpenTelemetry](https://opentelemetry.io/) traces over OTLP/HTTP.
+//!
+//! Only the JSON encoding of OTLP is supported, so configure your exporter with
+//! `OTEL_EXPORTER_OTLP_PROTOCOL=http/json`.
+
+use std::convert::Infallible;
+
+use anyhow::Context as _;
+use hyper::{
+    service::{make_service_fn, service_fn},
+    Body, Method, Request, Response, StatusCode,
+};
+
+use re_log_types::{LogMsg, MsgId, Time, TraceSpan, TraceSpansMsg};
+use re_smart_channel::{Receiver, Sender};
+
+/// Where OTLP exporters send traces to.
+const TRACES_PATH: &str = "/v1/traces";
+
+/// Larger requests are rejected, so that no one can make us run out of memory.
+///
+/// Exporters send their spans in batches, which are far smaller than this.
+const MAX_REQUEST_SIZE: u64 = 32 * 1024 * 1024;
+
+/// Listen for OTLP traces on the given address, and add the spans to the stream of messages.
+///
+/// There is no authentication, so only listen on other addresses than localhost on trusted networks.
+///
+/// Returns the receiver to use instead of `rx`.
+pub async fn merge_otlp_traces(
+    rx: Receiver<LogMsg>,
+    bind_addr: std::net::SocketAddr,
+) -> anyhow::Result<Receiver<LogMsg>> {
+    // Share the latency stats with the original channel:
+    let (tx, new_rx) = rx.chained_channel();
+
+    let otlp_tx = tx.clone();
+    let make_service = make_service_fn(move |_conn| {
+        let tx = otlp_tx.clone();
+        async move {
+            Ok::<_, Infallible>(service_fn(move |request| {
+                handle_request(request, tx.clone())
+            }))
+        }
+    });
+    let server = hyper::Server::try_bind(&bind_addr)
+        .with_context(|| format!("Failed to bind the OTLP server to {bind_addr}"))?
+        .serve(make_service);
+    tokio::spawn(async move {
+        if let Err(err) = server.await {
+            re_log::error!("OTLP server failed: {err}");
+        }
+    });
+
+    std::thread::Builder::new()
+        .name("otlp_merge".to_owned())
+        .spawn(move || {
+            while let Ok((sent_at, msg)) = rx.recv_with_send_time() {
+                if tx.send_at(sent_at, msg).is_err() {
+                    break; // The viewer has shut down
+                }
+            }
+            re_log::debug!("Shutting down OTLP merge thread");
+        })
+        .context("Failed to spawn OTLP merge thread")?;
+
+    re_log::info!("Listening for OpenTelemetry traces on http://{bind_addr}{TRACES_PATH}");
+
+    Ok(new_rx)
+}
+
+async fn handle_request(
+    request: Request<Body>,
+    tx: Sender<LogMsg>,
+) -> Result<Response<Body>, Infallible> {
+    if request.method() != Method::POST || request.uri().path() != TRACES_PATH {
+        return Ok(response(StatusCode::NOT_FOUND, "Not found"));
+    }
+
+    let is_json = request
+        .headers()
+        .get(hyper::header::CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        .map_or(false, |content_type| {
+            content_type.starts_with("application/json")
+        });
+    if !is_json {
+        return Ok(response(
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            "Rerun only supports JSON-encoded OTLP. Set OTEL_EXPORTER_OTLP_PROTOCOL=http/json",
+        ));
+    }
+
+    let body = match read_body(request.into_body()).await {
+        Ok(body) => body,
+        Err(ReadBodyError::TooLarge) => {
+            return Ok(response(
+                StatusCode::PAYLOAD_TOO_LARGE,
+                &format!(
+                    "Requests may be at most {}",
+                    re_format::format_bytes(MAX_REQUEST_SIZE as _)
+                ),
+            ));
+        }
+        Err(ReadBodyError::Hyper(err)) => {
+            return Ok(response(StatusCode::BAD_REQUEST, &err.to_string()));
+        }
+    };
+
+    match spans_from_otlp_json(&body) {
+        Ok(spans) => {
+            if !spans.is_empty() {
+                re_log::trace!("Received {} trace spans", spans.len());
+                tx.send(LogMsg::TraceSpansMsg(TraceSpansMsg {
+                    msg_id: MsgId::random(),
+                    spans,
+                }))
+                .ok();
+            }
+            // An empty `ExportTraceServiceResponse`, meaning everything was accepted:
+            Ok(response(StatusCode::OK, "{}"))
+        }
+        Err(err) => {
+            re_log::warn_once!("Bad OTLP request: {err}");
+            Ok(response(StatusCode::BAD_REQUEST, &err.to_string()))
+        }
+    }
+}
+
+enum ReadBodyError {
+    /// Larger than [`MAX_REQUEST_SIZE`].
+    TooLarge,
+    Hyper(hyper::Error),
+}
+
+/// Like [`hyper::body::to_bytes`], but stops reading at [`MAX_REQUEST_SIZE`].
+async fn read_body(mut body: Body) -> Result<Vec<u8>, ReadBodyError> {
+    use hyper::body::HttpBody as _;
+
+    // The `Content-Length`, if given:
+    if body.size_hint().lower() > MAX_REQUEST_SIZE {
+        return Err(ReadBodyError::TooLarge);
+    }
+
+    let mut bytes = Vec::new();
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk.map_err(ReadBodyError::Hyper)?;
+        if (bytes.len() + chunk.len()) as u64 > MAX_REQUEST_SIZE {
+            return Err(ReadBodyError::TooLarge);
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+    Ok(bytes)
+}
+
+fn response(status: StatusCode, body: &str) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .body(Body::from(body.to_owned()))
+        .unwrap()
+}
+
+// ----------------------------------------------------------------------------
+// The parts of the OTLP JSON format that we care about.
+// See <https://github.com/open-telemetry/opentelemetry-proto/blob/main/opentelemetry/proto/trace/v1/trace.proto>.
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ExportTraceServiceRequest {
+    #[serde(default)]
+    resource_spans: Vec<ResourceSpans>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ResourceSpans {
+    #[serde(default)]
+    resource: Resource,
+
+    /// Called `instrumentationLibrarySpans` before OTLP 0.19.
+    #[serde(default, alias = "instrumentationLibrarySpans")]
+    scope_spans: Vec<ScopeSpans>,
+}
+
+#[derive(Default, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Resource {
+    #[serde(default)]
+    attributes: Vec<KeyValue>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct KeyValue {
+    key: String,
+    #[serde(default)]
+    value: AnyValue,
+}
+
+#[derive(Default, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AnyValue {
+    string_value: Option<String>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ScopeSpans {
+    #[serde(default)]
+    spans: Vec<Span>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Span {
+    trace_id: String,
+    span_id: String,
+    #[serde(default)]
+    parent_span_id: String,
+    #[serde(default)]
+    name: String,
+    start_time_unix_nano: UnixNanos,
+    end_time_unix_nano: UnixNanos,
+}
+
+/// 64-bit integers are usually encoded as strings in OTLP JSON, but not always.
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum UnixNanos {
+    String(String),
+    Number(u64),
+}
+
+impl UnixNanos {
+    fn time(&self) -> anyhow::Result<Time> {
+        let nanos = match self {
+            Self::String(nanos) => nanos.parse().with_context(|| format!("{nanos:?}"))?,
+            Self::Number(nanos) => *nanos,
+        };
+        let nanos = i64::try_from(nanos).with_context(|| format!("Time out of range: {nanos}"))?;
+        Ok(Time::from_ns_since_epoch(nanos))
+    }
+}
+
+/// Parse the body of an OTLP/HTTP JSON request.
+fn spans_from_otlp_json(json: &[u8]) -> anyhow::Result<Vec<TraceSpan>> {
+    let request: ExportTraceServiceRequest = serde_json::from_slice(json)?;
+
+    let mut spans = vec![];
+    for resource_spans in request.resource_spans {
+        let service_name = resource_spans
+            .resource
+            .attributes
+            .into_iter()
+            .find(|attribute| attribute.key == "service.name")
+            .and_then(|attribute| attribute.value.string_value)
+            .unwrap_or_else(|| "unknown_service".to_owned());
+
+        for span in resource_spans
+            .scope_spans
+            .into_iter()
+            .flat_map(|scope_spans| scope_spans.spans)
+        {
+            let parent_span_id = if span.parent_span_id.is_empty() {
+                None
+            } else {
+                Some(u64::from_str_radix(&span.parent_span_id, 16).context("Bad parentSpanId")?)
+            };
+
+            spans.push(TraceSpan {
+                trace_id: u128::from_str_radix(&span.trace_id, 16).context("Bad traceId")?,
+                span_id: u64::from_str_radix(&span.span_id, 16).context("Bad spanId")?,
+                parent_span_id,
+                name: span.name,
+                service_name: service_name.clone(),
+                start: span.start_time_unix_nano.time()?,
+                end: span.end_time_unix_nano.time()?,
+            });
+        }
+    }
+    Ok(spans)
+}
+
+#[test]
+fn test_spans_from_otlp_json() {
+    let json = r#"{
+        "resourceSpans": [{
+            "resource": {
+                "attributes": [{ "key": "service.name", "value": { "stringValue": "perception" } }]
+            },
+            "scopeSpans": [{
+                "scope": { "name": "my.library" },
+                "spans": [{
+                    "traceId": "5b8efff798038103d269b633813fc60c",
+                    "spanId": "eee19b7ec3c1b174",
+                    "parentSpanId": "eee19b7ec3c1b173",
+                    "name": "detect_objects",
+                    "kind": 2,
+                    "startTimeUnixNano": "1544712660000000000",
+                    "endTimeUnixNano": 1544712661000000000
+                }]
+            }]
+        }]
+    }"#;
+
+    let spans = spans_from_otlp_json(json.as_bytes()).unwrap();
+    assert_eq!(
+        spans,
+        vec![TraceSpan {
+            trace_id: 0x5b8e_fff7_9803_8103_d269_b633_813f_c60c,
+            span_id: 0xeee1_9b7e_c3c1_b174,
+            parent_span_id: Some(0xeee1_9b7e_c3c1_b173),
+            name: "detect_objects".to_owned(),
+            service_name: "perception".to_owned(),
+            start: Time::from_ns_since_epoch(1_544_712_660_000_000_000),
+            end: Time::from_ns_since_epoch(1_544_712_661_000_000_000),
+        }]
+    );
+}
@@ -0,0 +1,205 @@
+//! `rerun rrd …`: tools for working with `.rrd` files, e.g. before archiving them.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Context as _;
+
+use re_log_types::{msg_bundle::MsgBundle, EntityPath, LogMsg, MsgId};
+
+#[derive(Debug, Clone, clap::Subcommand)]
+pub enum RrdCommands {
+    /// Rewrite recordings with a stronger compression, optionally dropping some of the data.
+    ///
+    /// Several inputs (e.g. the parts of a split recording) are merged into one output,
+    /// with any duplicated messages removed.
+    ///
+    /// Example: `rerun rrd compact capture.rrd -o archive.rrd --drop debug --drop camera/raw`
+    Compact(CompactArgs),
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct CompactArgs {
+    /// The recordings to read.
+    #[clap(required = true)]
+    inputs: Vec<PathBuf>,
+
+    /// Where to write the result.
+    #[clap(long, short)]
+    output: PathBuf,
+
+    /// The zstd compression level, from 1 (fast) to 22 (small).
+    #[clap(long, default_value_t = 19)]
+    level: i32,
+
+    /// Leave out this entity and everything under it. Can be given several times.
+    #[clap(long)]
+    drop: Vec<String>,
+
+    /// Train a compression dictionary on the recording and embed it in the output.
+    ///
+    /// This makes recordings of many small messages (poses, scalars, …) a lot smaller,
+    /// but requires reading the inputs twice, and the result can't yet be opened in the web viewer.
+    #[clap(long)]
+    dictionary: bool,
+}
+
+pub fn run(command: &RrdCommands) -> anyhow::Result<()> {
+    match command {
+        RrdCommands::Compact(args) => compact(args),
+    }
+}
+
+fn compact(args: &CompactArgs) -> anyhow::Result<()> {
+    let CompactArgs {
+        inputs,
+        output,
+        level,
+        drop,
+        dictionary,
+    } = args;
+
+    // We truncate the output before reading the inputs.
+    anyhow::ensure!(
+        !inputs.iter().any(|input| is_same_file(input, output)),
+        "The output must not be one of the inputs"
+    );
+
+    let drop: Vec<EntityPath> = drop
+        .iter()
+        .map(|path| EntityPath::from(path.as_str()))
+        .collect();
+
+    let file = std::fs::File::create(output)
+        .with_context(|| format!("Failed to create file at {output:?}"))?;
+    let dictionary = if *dictionary {
+        train_dictionary(inputs, &drop)?
+    } else {
+        None
+    };
+    let mut encoder = if let Some(dictionary) = &dictionary {
+        re_log_types::encoding::Encoder::new_with_dictionary(file, *level, dictionary)?
+    } else {
+        re_log_types::encoding::Encoder::new_with_compression_level(file, *level)?
+    };
+
+    let mut seen_msg_ids = std::collections::HashSet::<MsgId>::default();
+    let mut num_written = 0;
+    let mut num_dropped = 0;
+    let mut num_duplicates = 0;
+
+    for input in inputs {
+        re_log::info!("Reading {input:?}…");
+        let file =
+            std::fs::File::open(input).with_context(|| format!("Failed to open {input:?}"))?;
+        let decoder = re_log_types::encoding::Decoder::new(file)
+            .with_context(|| format!("Failed to decode {input:?}"))?;
+
+        for msg in decoder {
+            let msg = msg.with_context(|| format!("Failed to decode {input:?}"))?;
+
+            if matches!(msg, LogMsg::Goodbye(_)) {
+                continue; // Only meaningful for live streams.
+            }
+            if !seen_msg_ids.insert(msg.id()) {
+                num_duplicates += 1;
+                continue;
+            }
+            if is_dropped(&msg, &drop) {
+                num_dropped += 1;
+                continue;
+            }
+
+            encoder.append(&msg)?;
+            num_written += 1;
+        }
+    }
+
+    encoder.finish()?;
+
+    let size_before: u64 = inputs.iter().map(|input| file_size(input)).sum();
+    let size_after = file_size(output);
+    re_log::info!(
+        "Wrote {} messages to {output:?} (dropped {}, and {} duplicates): {} -> {} ({:.0}%)",
+        re_format::format_number(num_written),
+        re_format::format_number(num_dropped),
+        re_format::format_number(num_duplicates),
+        re_format::format_bytes(size_before as _),
+        re_format::format_bytes(size_after as _),
+        100.0 * size_after as f64 / size_before.max(1) as f64,
+    );
+
+    Ok(())
+}
+
+fn train_dictionary(inputs: &[PathBuf], drop: &[EntityPath]) -> anyhow::Result<Option<Vec<u8>>> {
+    re_log::info!("Training a compression dictionary…");
+
+    let mut decoders = Vec::new();
+    for input in inputs {
+        let file =
+            std::fs::File::open(input).with_context(|| format!("Failed to open {input:?}"))?;
+        decoders.push(
+            re_log_types::encoding::Decoder::new(file)
+                .with_context(|| format!("Failed to decode {input:?}"))?,
+        );
+    }
+
+    // Decoding errors are reported when we write the output.
+    let messages = decoders
+        .into_iter()
+        .flatten()
+        .map_while(Result::ok)
+        .filter(|msg| !is_dropped(msg, drop));
+
+    let dictionary = re_log_types::encoding::train_dictionary(messages)?;
+    if dictionary.is_none() {
+        re_log::info!("Too few small messages for a dictionary to help - compressing without one");
+    }
+    Ok(dictionary)
+}
+
+fn is_dropped(msg: &LogMsg, drop: &[EntityPath]) -> bool {
+    if drop.is_empty() {
+        return false;
+    }
+
+    let entity_path = match msg {
+        LogMsg::ArrowMsg(msg) => match MsgBundle::try_from(msg) {
+            Ok(bundle) => bundle.entity_path,
+            Err(err) => {
+                re_log::warn_once!("Keeping a message we failed to decode: {err}");
+                return false;
+            }
+        },
+        LogMsg::EntityPathOpMsg(msg) => msg.path_op.entity_path().clone(),
+        LogMsg::BeginRecordingMsg(_)
+        | LogMsg::BlueprintMsg(_)
+        | LogMsg::TraceSpansMsg(_)
+        | LogMsg::Goodbye(_) => return false,
+    };
+
+    drop.iter()
+        .any(|dropped| &entity_path == dropped || entity_path.is_descendant_of(dropped))
+}
+
+/// Are these the same file, e.g. through a symlink, a hard link or `..`?
+///
+/// `false` if either doesn't exist: an output we have yet to create can't be one of the inputs.
+fn is_same_file(a: &Path, b: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt as _;
+        if let (Ok(a), Ok(b)) = (std::fs::metadata(a), std::fs::metadata(b)) {
+            return (a.dev(), a.ino()) == (b.dev(), b.ino());
+        }
+    }
+
+    match (std::fs::canonicalize(a), std::fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+fn file_size(path: &Path) -> u64 {
+    std::fs::metadata(path).map_or(0, |metadata| metadata.len())
+}
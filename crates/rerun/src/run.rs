@@ -5,6 +5,9 @@ use re_smart_channel::Receiver;
 use anyhow::Context as _;
 use clap::Subcommand;
 
+#[cfg(feature = "otlp")]
+use crate::otlp::merge_otlp_traces;
+
 // Note the extra blank lines between the point-lists below: it is required by `clap`.
 
 /// The Rerun Viewer and Server
@@ -17,12 +20,18 @@ use clap::Subcommand;
 ///
 /// * Host a Rerun Server that Rerun SDK:s can connect to.
 ///
+/// * Run headless, saving everything the SDK:s send to disk and re-serving it to viewers.
+///
 /// Environment variables:
 ///
 /// * `RERUN`: force enable/disable logging with rerun (only relevant for the Rerun API, not the Viewer itself). Either `on`/`1`/`true` or `off`/`0`/`false`
 ///
+/// * `RERUN_AUTH_TOKEN`: the auth token the Rerun SDK presents to a server started with `--acl`.
+///
 /// * `RERUN_SHADER_PATH`: change the search path for shader/shader-imports. WARNING: Shaders are embedded in some build configurations.
 ///
+/// * `RERUN_VIEWER_AUTH_TOKEN`: like `--auth-token-file`, but the token itself.
+///
 /// * `RERUN_TRACK_ALLOCATIONS`: track all allocations in order to find memory leaks in the viewer. WARNING: slows down the viewer by a lot!
 ///
 /// * `WGPU_BACKEND`: overwrites the graphics backend used, must be one of `vulkan`, `metal`, `dx12`, `dx11`, or `gl`.
@@ -34,19 +43,184 @@ use clap::Subcommand;
 struct Args {
     /// Either a path to a `.rrd` file to load, or a websocket url to a Rerun Server from which to read data
     ///
+    /// Links of the form `rerun://host:port` are also accepted, and connect to the Rerun Server at that address.
+    /// Links copied in the viewer ("Copy link to this view") also go to the same recording, time and selection.
+    ///
     /// If none is given, a server will be hosted which the Rerun SDK can connect to.
     url_or_path: Option<String>,
 
+    /// Treat the url as a server speaking the Foxglove WebSocket protocol, e.g. `ws://robot:8765`.
+    ///
+    /// Lets you view the data of an existing Foxglove bridge (e.g. `foxglove_bridge`) in Rerun.
+    /// Only JSON-encoded channels are supported.
+    /// Requires Rerun to have been compiled with the 'foxglove' feature.
+    #[clap(long)]
+    foxglove: bool,
+
+    /// Keep reading the given `.rrd` file as it grows, like `tail -f`.
+    ///
+    /// Use this to monitor a recording that another process is still writing to.
+    #[clap(long)]
+    follow: bool,
+
+    /// Also trust the root certificate(s) in this PEM file when connecting to a `wss://` url,
+    /// e.g. those of a company-internal certificate authority.
+    ///
+    /// Can be given multiple times.
+    #[clap(long)]
+    tls_root_cert: Vec<std::path::PathBuf>,
+
+    /// A file containing a shared secret that viewers must present to connect.
+    ///
+    /// Required by the websocket servers we host (for `--web-viewer` and `--relay-port`),
+    /// and presented when connecting to a Rerun Server url.
+    /// Can also be given with the `RERUN_VIEWER_AUTH_TOKEN` environment variable,
+    /// which is not presented to the server of a `rerun://` link.
+    #[clap(long)]
+    auth_token_file: Option<std::path::PathBuf>,
+
+    /// The loaded `--auth-token-file`, or `RERUN_VIEWER_AUTH_TOKEN`.
+    #[clap(skip)]
+    auth_token: Option<String>,
+
+    /// Compress the data sent by the websocket servers we host (for `--web-viewer` and `--relay-port`)
+    /// to viewers that support it: off, fast, balanced or best.
+    ///
+    /// Makes e.g. streams of images much smaller, at the cost of CPU.
+    #[cfg(feature = "web")]
+    #[clap(long, default_value = "off")]
+    ws_compression: re_ws_comms::CompressionLevel,
+
+    /// How much data the websocket servers we host keep for viewers that connect late, e.g. `1GB`.
+    ///
+    /// The oldest data is dropped first. Everything is kept by default,
+    /// except with `--headless`, which keeps 1GB since it is meant to run for a long time.
+    /// The recording info and timeless data are always kept, since the viewer needs them.
+    #[cfg(feature = "web")]
+    #[clap(long)]
+    ws_history_limit: Option<String>,
+
+    /// Only keep data this recent for viewers that connect late, e.g. `10m`.
+    #[cfg(feature = "web")]
+    #[clap(long)]
+    ws_history_duration: Option<String>,
+
+    /// When connecting to a Rerun Server url, also connect to this one, e.g. to view the data
+    /// of a robot and a simulator side by side.
+    ///
+    /// Can be given multiple times. Servers can be switched off in the "Servers" menu.
+    #[clap(long)]
+    also_connect: Vec<String>,
+
     /// What TCP port do we listen to (for SDK:s to connect to)?
     #[cfg(feature = "server")]
     #[clap(long, default_value_t = re_sdk_comms::DEFAULT_SERVER_PORT)]
     port: u16,
 
+    /// Append a JSON line to this file for every SDK that connects or disconnects,
+    /// every recording started or ended, and the first dropped message of a recording,
+    /// with the number of messages and bytes received.
+    /// Also whenever a web viewer falls behind and we start dropping messages for it.
+    ///
+    /// Use `rerun audit-log <path>` to query it.
+    #[cfg(feature = "server")]
+    #[clap(long)]
+    audit_log: Option<std::path::PathBuf>,
+
+    /// The opened `--audit-log`, shared by the SDK server and the websocket servers.
+    #[cfg(feature = "server")]
+    #[clap(skip)]
+    audit: Option<re_sdk_comms::audit_log::AuditLog>,
+
+    /// Cap the bandwidth of the SDK:s per entity path, as configured in this TOML file, e.g.:
+    ///
+    /// [[limit]]
+    /// path = "cameras/**"
+    /// max = "20 Mbps"
+    ///
+    /// Each SDK gets the full limit. Messages over it are dropped instead of queued.
+    /// The file is re-read whenever it changes.
+    #[cfg(feature = "server")]
+    #[clap(long)]
+    bandwidth_limits: Option<std::path::PathBuf>,
+
+    /// Give each team its own auth token, which decides the recordings it may write and read,
+    /// as configured in this TOML file, e.g.:
+    ///
+    /// [[token]]
+    /// name = "team-a"
+    /// secret = "correct-horse-battery-staple"
+    /// write = ["team_a_*"]
+    /// read = ["team_a_*", "shared_*"]
+    ///
+    /// Recordings are matched by their application id.
+    /// SDK:s present their token with the `RERUN_AUTH_TOKEN` environment variable,
+    /// viewers with `--auth-token-file`. The `--auth-token-file` of this server may still read everything.
+    #[cfg(feature = "server")]
+    #[clap(long)]
+    acl: Option<std::path::PathBuf>,
+
+    /// The loaded `--acl`, shared by the SDK server and the websocket servers.
+    #[cfg(feature = "server")]
+    #[clap(skip)]
+    access_control: Option<re_sdk_comms::AccessControl>,
+
     /// Start the viewer in the browser (instead of locally).
     /// Requires Rerun to have been compiled with the 'web' feature.
     #[clap(long)]
     web_viewer: bool,
 
+    /// Re-serve everything this viewer receives on a websocket server at this port.
+    ///
+    /// Other viewers on the network can then connect to this viewer (read-only),
+    /// e.g. to share the stream of a robot with teammates without putting extra load on the robot.
+    /// Requires Rerun to have been compiled with the 'web' feature.
+    #[clap(long)]
+    relay_port: Option<u16>,
+
+    /// Don't show a viewer, e.g. on a robot or a server without a display.
+    ///
+    /// Instead, save what we receive (see `--save`) and re-serve it on a websocket server
+    /// at `--relay-port` (9877 by default), so viewers can connect with `rerun ws://<this-machine>:9877`.
+    /// Viewers that connect late get the last 1GB of history (see `--ws-history-limit`).
+    /// Re-serving requires Rerun to have been compiled with the 'web' feature.
+    #[clap(long)]
+    headless: bool,
+
+    /// Save everything we receive to this `.rrd` file, e.g. the data of the connected SDK:s.
+    ///
+    /// The file is kept readable while it is written, so you can e.g. `rerun --follow` it.
+    /// Requires Rerun to have been compiled with the 'sdk' feature.
+    #[clap(long)]
+    save: Option<std::path::PathBuf>,
+
+    /// With `--save`, continue in a new file once the current one is this large, e.g. `1GB`.
+    ///
+    /// `capture.rrd` is then saved as `capture.0000.rrd`, `capture.0001.rrd`, etc.
+    /// Each part can be viewed on its own. Open them all at once to see the whole recording.
+    #[clap(long)]
+    save_part_size: Option<String>,
+
+    /// With `--save`, continue in a new file after this long, e.g. `10m`.
+    #[clap(long)]
+    save_part_duration: Option<String>,
+
+    /// Listen for OpenTelemetry traces on this port, e.g. 4318.
+    ///
+    /// Point your OTLP exporter at `http://localhost:<port>`, using `OTEL_EXPORTER_OTLP_PROTOCOL=http/json`.
+    /// The spans are shown as a flamegraph in the time panel, aligned with the `log_time` timeline.
+    /// Requires Rerun to have been compiled with the 'otlp' feature.
+    #[clap(long)]
+    otlp_port: Option<u16>,
+
+    /// The address to listen for OpenTelemetry traces on, with `--otlp-port`.
+    ///
+    /// Only local exporters can send traces by default.
+    /// Use e.g. `0.0.0.0` to accept them from other machines: anyone who can reach this machine
+    /// can then send traces, since there is no authentication.
+    #[clap(long, default_value = "127.0.0.1")]
+    otlp_bind: std::net::IpAddr,
+
     /// Start with the puffin profiler running.
     #[clap(long)]
     profile: bool,
@@ -59,6 +233,15 @@ struct Args {
     #[clap(long)]
     memory_limit: Option<String>,
 
+    /// An upper limit on how much GPU memory (VRAM) the Rerun Viewer should use.
+    ///
+    /// When this limit is used, Rerun will evict its GPU caches (e.g. image textures)
+    /// when it goes over budget.
+    ///
+    /// Example: `2GB`
+    #[clap(long)]
+    gpu_memory_limit: Option<String>,
+
     /// Set a maximum input latency, e.g. "200ms" or "10s".
     ///
     /// If we go over this, we start dropping packets.
@@ -69,6 +252,19 @@ struct Args {
     #[clap(long)]
     drop_at_latency: Option<String>,
 
+    /// The maximum rate (in Hz) at which the viewer repaints while data is streaming in.
+    ///
+    /// When no new data arrives, the viewer repaints on user interaction,
+    /// and twice a second to keep e.g. the latency indicators up to date.
+    #[clap(long, default_value_t = 60.0)]
+    max_fps: f32,
+
+    /// Trade rendering speed for image quality: `performance`, `balanced` or `quality`.
+    ///
+    /// Overrides the setting in the viewer, e.g. for a machine with a slow GPU.
+    #[clap(long)]
+    render_quality: Option<String>,
+
     #[command(subcommand)]
     commands: Option<Commands>,
 }
@@ -79,6 +275,22 @@ enum Commands {
     #[cfg(all(feature = "analytics"))]
     #[command(subcommand)]
     Analytics(AnalyticsCommands),
+
+    /// Show the events of an audit log written by a server started with `--audit-log`.
+    ///
+    /// Example: `rerun audit-log audit.jsonl --client robot-3`
+    #[cfg(feature = "server")]
+    AuditLog(crate::audit_log::AuditLogArgs),
+
+    /// Register Rerun as the handler for `.rrd` files and `rerun://` links, for the current user.
+    ///
+    /// Afterwards, double-clicking a recording or clicking a `rerun://host:port` link
+    /// opens the Rerun Viewer.
+    Register,
+
+    /// Tools for working with `.rrd` files.
+    #[command(subcommand)]
+    Rrd(crate::rrd::RrdCommands),
 }
 
 #[derive(Debug, Clone, Subcommand)]
@@ -154,8 +366,10 @@ where
         match commands {
             #[cfg(all(feature = "analytics"))]
             Commands::Analytics(analytics) => run_analytics(analytics).map_err(Into::into),
-            #[cfg(not(all(feature = "analytics")))]
-            _ => Ok(()),
+            #[cfg(feature = "server")]
+            Commands::AuditLog(audit_log) => crate::audit_log::run(audit_log),
+            Commands::Register => crate::os_integration::register(),
+            Commands::Rrd(rrd) => crate::rrd::run(rrd),
         }
     } else {
         run_impl(call_source, args).await
@@ -198,24 +412,126 @@ fn run_analytics(cmd: &AnalyticsCommands) -> Result<(), re_analytics::cli::CliEr
 async fn run_impl(call_source: CallSource, args: Args) -> anyhow::Result<()> {
     crate::crash_handler::install_crash_handlers();
 
+    #[cfg(feature = "server")]
+    let args = {
+        anyhow::ensure!(
+            args.acl.is_none() || args.url_or_path.is_none(),
+            "--acl only applies to the data SDK:s send to us, not to a url or path"
+        );
+        if args.audit_log.is_some() && args.url_or_path.is_some() {
+            re_log::warn!("Ignoring --audit-log: we only audit the SDK:s connecting to us");
+        }
+        Args {
+            access_control: args
+                .acl
+                .as_deref()
+                .map(re_sdk_comms::AccessControl::load)
+                .transpose()?,
+            audit: args
+                .audit_log
+                .as_deref()
+                .filter(|_| args.url_or_path.is_none())
+                .map(re_sdk_comms::audit_log::AuditLog::open)
+                .transpose()?,
+            ..args
+        }
+    };
+
+    // File managers open `.rrd` files with `file://` urls, see `rerun register`.
+    let args = Args {
+        url_or_path: args.url_or_path.map(|url_or_path| {
+            crate::os_integration::path_from_file_url(&url_or_path)
+                .map_or(url_or_path, |path| path.to_string_lossy().into_owned())
+        }),
+        ..args
+    };
+
+    // Never on the command line, where anyone on the machine could see it.
+    let args = Args {
+        auth_token: load_auth_token(args.auth_token_file.as_deref())?,
+        ..args
+    };
+
     let mut profiler = re_viewer::Profiler::default();
     if args.profile {
         profiler.start();
     }
 
+    let extra_root_certificates_pem = args
+        .tls_root_cert
+        .iter()
+        .map(|path| std::fs::read(path).with_context(|| format!("Bad --tls-root-cert {path:?}")))
+        .collect::<anyhow::Result<_>>()?;
+
     let startup_options = re_viewer::StartupOptions {
         memory_limit: args.memory_limit.as_ref().map_or(Default::default(), |l| {
             re_memory::MemoryLimit::parse(l)
                 .unwrap_or_else(|err| panic!("Bad --memory-limit: {err}"))
         }),
+        gpu_memory_limit: args
+            .gpu_memory_limit
+            .as_ref()
+            .map_or(Default::default(), |l| {
+                re_memory::MemoryLimit::parse(l)
+                    .unwrap_or_else(|err| panic!("Bad --gpu-memory-limit: {err}"))
+            }),
+        max_fps: args.max_fps,
+        render_quality: args.render_quality.as_ref().map(|quality| {
+            quality
+                .parse()
+                .unwrap_or_else(|err| panic!("Bad --render-quality: {err}"))
+        }),
+        ws_connection_options: re_ws_comms::ConnectionOptions {
+            extra_root_certificates_pem,
+            auth_token: args
+                .auth_token
+                .clone()
+                .filter(|_| args.auth_token_file.is_some() || !is_rerun_link(&args)),
+        },
+        bookmark: args
+            .url_or_path
+            .as_deref()
+            .and_then(re_viewer::Bookmark::from_url),
+    };
+
+    // Shared with the viewer, so it can show (and disconnect) the SDK:s connected to our server.
+    #[cfg(feature = "server")]
+    let sdk_clients = if args.url_or_path.is_none() {
+        let mut clients = re_sdk_comms::ConnectedClients::default();
+        if let Some(audit) = &args.audit {
+            clients = clients.with_audit_log(audit.clone());
+        }
+        if let Some(access_control) = &args.access_control {
+            clients = clients.with_access_control(access_control.clone());
+        }
+        if let Some(path) = &args.bandwidth_limits {
+            clients = clients.with_bandwidth_limits(re_sdk_comms::BandwidthLimits::load(path)?);
+        }
+        Some(clients)
+    } else {
+        None
     };
 
     // Where do we get the data from?
+    let is_file = |url_or_path: &str| {
+        std::path::Path::new(url_or_path).exists() || url_or_path.ends_with(".rrd")
+    };
+    anyhow::ensure!(
+        !args.follow || args.url_or_path.as_deref().map_or(false, is_file),
+        "--follow only works with an .rrd file"
+    );
     let rx = if let Some(url_or_path) = &args.url_or_path {
         let path = std::path::Path::new(url_or_path).to_path_buf();
-        if path.exists() || url_or_path.ends_with(".rrd") {
-            re_log::info!("Loading {path:?}…");
-            load_file_to_channel(&path).with_context(|| format!("{path:?}"))?
+        if is_file(url_or_path) {
+            if args.follow {
+                re_log::info!("Following {path:?}…");
+                follow_file_to_channel(&path).with_context(|| format!("{path:?}"))?
+            } else {
+                re_log::info!("Loading {path:?}…");
+                load_file_to_channel(&path).with_context(|| format!("{path:?}"))?
+            }
+        } else if args.foxglove {
+            connect_to_foxglove_server(url_or_path.clone()).await?
         } else {
             // We are connecting to a server at a websocket address:
             return connect_to_ws_url(
@@ -236,15 +552,36 @@ async fn run_impl(call_source: CallSource, args: Args) -> anyhow::Result<()> {
                 // `rerun.spawn()` doesn't ned to log that a connection has been made
                 quiet: call_source.is_python(),
             };
-            re_sdk_comms::serve(args.port, server_options)?
+            re_sdk_comms::serve_with_clients(
+                args.port,
+                server_options,
+                sdk_clients.clone().unwrap_or_default(),
+            )?
         }
 
         #[cfg(not(feature = "server"))]
         anyhow::bail!("No url or .rrd path given");
     };
 
+    let rx = if let Some(otlp_port) = args.otlp_port {
+        merge_otlp_traces(rx, std::net::SocketAddr::new(args.otlp_bind, otlp_port)).await?
+    } else {
+        rx
+    };
+
+    let rx = if let Some(path) = &args.save {
+        save_to_file(rx, path, &args)?
+    } else {
+        rx
+    };
+
     // Now what do we do with the data?
-    if args.web_viewer {
+    if args.headless {
+        if args.web_viewer {
+            re_log::warn!("Ignoring --web-viewer, since we are running --headless");
+        }
+        return run_headless(rx, &args).await;
+    } else if args.web_viewer {
         #[cfg(feature = "web")]
         {
             #[cfg(feature = "server")]
@@ -257,11 +594,11 @@ async fn run_impl(call_source: CallSource, args: Args) -> anyhow::Result<()> {
             }
 
             // This is the server which the web viewer will talk to:
-            let ws_server = re_ws_comms::Server::new(re_ws_comms::DEFAULT_WS_SERVER_PORT).await?;
+            let ws_server = ws_server(re_ws_comms::DEFAULT_WS_SERVER_PORT, &args, None).await?;
             let server_handle = tokio::spawn(ws_server.listen(rx));
 
             let rerun_ws_server_url = re_ws_comms::default_server_url();
-            host_web_viewer(rerun_ws_server_url).await?;
+            host_web_viewer(rerun_ws_server_url, args.auth_token.as_deref()).await?;
 
             return server_handle.await?;
         }
@@ -269,8 +606,18 @@ async fn run_impl(call_source: CallSource, args: Args) -> anyhow::Result<()> {
         #[cfg(not(feature = "web"))]
         anyhow::bail!("Can't host web-viewer - rerun was not compiled with the 'web' feature");
     } else {
+        let rx = if let Some(relay_port) = args.relay_port {
+            relay_to_ws_server(rx, relay_port, &args).await?
+        } else {
+            rx
+        };
+
         re_viewer::run_native_app(Box::new(move |cc, re_ui| {
-            let rx = re_viewer::wake_up_ui_thread_on_each_msg(rx, cc.egui_ctx.clone());
+            let rx = re_viewer::wake_up_ui_thread_on_each_msg(
+                rx,
+                cc.egui_ctx.clone(),
+                startup_options.min_repaint_interval(),
+            );
             let mut app = re_viewer::App::from_receiver(
                 call_source.app_env(),
                 startup_options,
@@ -279,35 +626,79 @@ async fn run_impl(call_source: CallSource, args: Args) -> anyhow::Result<()> {
                 rx,
             );
             app.set_profiler(profiler);
+            #[cfg(feature = "server")]
+            if let Some(sdk_clients) = sdk_clients {
+                app.set_sdk_clients(sdk_clients);
+            }
             Box::new(app)
         }))?;
     }
     Ok(())
 }
 
+/// Did we get a `rerun://` link, e.g. clicked on some web page?
+///
+/// We don't know its server, so we shouldn't present it the token from our environment.
+fn is_rerun_link(args: &Args) -> bool {
+    args.url_or_path.as_deref().map_or(false, |url| {
+        crate::os_integration::ws_url_from_rerun_url(url).is_some()
+    })
+}
+
+/// Turn `rerun://` links and bare `host:port` addresses into websocket urls.
+fn normalize_ws_url(url: String) -> String {
+    if let Some(ws_url) = crate::os_integration::ws_url_from_rerun_url(&url) {
+        ws_url
+    } else if !url.contains("://") {
+        format!("{}://{url}", re_ws_comms::PROTOCOL)
+    } else {
+        url
+    }
+}
+
 async fn connect_to_ws_url(
     args: &Args,
     app_env: re_viewer::AppEnvironment,
     startup_options: re_viewer::StartupOptions,
     profiler: re_viewer::Profiler,
-    mut rerun_server_ws_url: String,
+    rerun_server_ws_url: String,
 ) -> anyhow::Result<()> {
-    if !rerun_server_ws_url.contains("://") {
-        rerun_server_ws_url = format!("{}://{rerun_server_ws_url}", re_ws_comms::PROTOCOL);
+    let rerun_server_ws_url = normalize_ws_url(rerun_server_ws_url);
+
+    if args.relay_port.is_some() {
+        re_log::warn!(
+            "Ignoring --relay-port: other viewers can connect to {rerun_server_ws_url} directly"
+        );
+    }
+    if args.headless || args.save.is_some() {
+        anyhow::bail!(
+            "--headless and --save are not supported when connecting to {rerun_server_ws_url}"
+        );
+    }
+    if args.otlp_port.is_some() {
+        re_log::warn!(
+            "Ignoring --otlp-port: send the traces to the server at {rerun_server_ws_url}"
+        );
     }
 
     if args.web_viewer {
-        host_web_viewer(rerun_server_ws_url).await?;
+        if !args.also_connect.is_empty() {
+            re_log::warn!("Ignoring --also-connect: the web viewer connects to a single server");
+        }
+        host_web_viewer(
+            rerun_server_ws_url,
+            startup_options.ws_connection_options.auth_token.as_deref(),
+        )
+        .await?;
     } else {
-        // By using RemoteViewerApp we let the user change the server they are connected to.
+        let urls = std::iter::once(rerun_server_ws_url)
+            .chain(args.also_connect.iter().cloned().map(normalize_ws_url))
+            .collect();
+
+        // By using RemoteViewerApp we let the user change the servers they are connected to.
         re_viewer::run_native_app(Box::new(move |cc, re_ui| {
-            let mut app = re_viewer::RemoteViewerApp::new(
-                app_env,
-                startup_options,
-                re_ui,
-                cc.storage,
-                rerun_server_ws_url,
-            );
+            let mut app =
+                re_viewer::RemoteViewerApp::new(app_env, startup_options, re_ui, cc.storage, urls);
             app.set_profiler(profiler);
             Box::new(app)
         }))?;
@@ -336,10 +727,187 @@ fn load_file_to_channel(path: &std::path::Path) -> anyhow::Result<Receiver<LogMs
     Ok(rx)
 }
 
+/// Like [`load_file_to_channel`], but keeps reading the file as it grows.
+fn follow_file_to_channel(path: &std::path::Path) -> anyhow::Result<Receiver<LogMsg>> {
+    use anyhow::Context as _;
+    let file = std::fs::File::open(path).context("Failed to open file")?;
+    let (tx, rx) = re_smart_channel::smart_channel(re_smart_channel::Source::FollowedFile {
+        path: path.to_owned(),
+    });
+
+    let decoder = re_log_types::encoding::Decoder::new(FollowedFile {
+        file,
+        tx: tx.clone(),
+    })?;
+
+    std::thread::Builder::new()
+        .name("rrd_file_follower".into())
+        .spawn(move || {
+            for msg in decoder {
+                match msg {
+                    Ok(msg) => {
+                        if tx.send(msg).is_err() {
+                            break; // The viewer has shut down
+                        }
+                    }
+                    Err(_) if tx.is_disconnected() => {
+                        break; // The viewer has shut down while we waited for more data
+                    }
+                    Err(err) => {
+                        re_log::error!("Failed to decode message: {}", re_error::format(&err));
+                        break;
+                    }
+                }
+            }
+        })
+        .expect("Failed to spawn thread");
+
+    Ok(rx)
+}
+
+/// A file that is still being written to.
+///
+/// Instead of reporting end-of-file, reads will wait for more data to be appended,
+/// until no one is receiving the messages anymore.
+struct FollowedFile {
+    file: std::fs::File,
+
+    /// Where the decoded messages go.
+    tx: re_smart_channel::Sender<LogMsg>,
+}
+
+impl std::io::Read for FollowedFile {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        loop {
+            let num_bytes = self.file.read(buf)?;
+            if num_bytes > 0 || buf.is_empty() {
+                return Ok(num_bytes);
+            }
+            if self.tx.is_disconnected() {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::BrokenPipe,
+                    "Stopped following: no one is receiving",
+                ));
+            }
+            std::thread::sleep(std::time::Duration::from_millis(100));
+        }
+    }
+}
+
+/// From `--auth-token-file`, or else the `RERUN_VIEWER_AUTH_TOKEN` environment variable.
+fn load_auth_token(path: Option<&std::path::Path>) -> anyhow::Result<Option<String>> {
+    if let Some(path) = path {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read --auth-token-file {path:?}"))?;
+        let auth_token = contents.trim();
+        anyhow::ensure!(
+            !auth_token.is_empty(),
+            "--auth-token-file {path:?} is empty"
+        );
+        Ok(Some(auth_token.to_owned()))
+    } else {
+        Ok(std::env::var("RERUN_VIEWER_AUTH_TOKEN")
+            .ok()
+            .filter(|auth_token| !auth_token.is_empty()))
+    }
+}
+
+/// A websocket server that only lets viewers with the `--auth-token-file` connect, if given,
+/// or those with a token of the `--acl`, to see what they may read.
+///
+/// Keeps `default_history_bytes` of history for late viewers, unless `--ws-history-limit` is given.
 #[cfg(feature = "web")]
-async fn host_web_viewer(rerun_ws_server_url: String) -> anyhow::Result<()> {
+async fn ws_server(
+    port: u16,
+    args: &Args,
+    default_history_bytes: Option<u64>,
+) -> anyhow::Result<re_ws_comms::Server> {
+    let history_limits = re_ws_comms::HistoryLimits {
+        max_bytes: args
+            .ws_history_limit
+            .as_deref()
+            .map(|limit| {
+                re_format::parse_bytes(limit)
+                    .and_then(|bytes| u64::try_from(bytes).ok())
+                    .with_context(|| format!("Failed to parse --ws-history-limit {limit:?}"))
+            })
+            .transpose()?
+            .or(default_history_bytes),
+        max_age: args
+            .ws_history_duration
+            .as_deref()
+            .map(|duration| {
+                parse_duration(duration)
+                    .and_then(|secs| {
+                        std::time::Duration::try_from_secs_f32(secs).map_err(|err| err.to_string())
+                    })
+                    .map_err(|err| anyhow::anyhow!("Failed to parse --ws-history-duration: {err}"))
+            })
+            .transpose()?,
+    };
+
+    let ws_server = re_ws_comms::Server::new(port)
+        .await?
+        .with_compression(args.ws_compression)
+        .with_history_limits(history_limits);
+    let ws_server = match args.auth_token.as_deref() {
+        Some(auth_token) => ws_server.with_auth_token(auth_token),
+        None => ws_server,
+    };
+
+    #[cfg(feature = "server")]
+    let ws_server = match &args.access_control {
+        Some(access_control) => ws_server.with_recording_access(ReadAccess(access_control.clone())),
+        None => ws_server,
+    };
+    #[cfg(feature = "server")]
+    let ws_server = match args.audit.clone() {
+        Some(audit) => ws_server.with_on_dropping(move |viewer_addr| {
+            use re_sdk_comms::audit_log::{AuditEventKind, DropReason};
+            audit.log(
+                viewer_addr,
+                AuditEventKind::Dropping {
+                    reason: DropReason::SlowViewer,
+                },
+            );
+        }),
+        None => ws_server,
+    };
+
+    Ok(ws_server)
+}
+
+/// Lets viewers see the recordings the `--acl` allows their token to read.
+#[cfg(all(feature = "server", feature = "web"))]
+struct ReadAccess(re_sdk_comms::AccessControl);
+
+#[cfg(all(feature = "server", feature = "web"))]
+impl re_ws_comms::RecordingAccess for ReadAccess {
+    fn is_known_token(&self, auth_token: &str) -> bool {
+        self.0.token_name(auth_token).is_some()
+    }
+
+    fn may_read(&self, auth_token: &str, application_id: &re_log_types::ApplicationId) -> bool {
+        self.0.may_read(Some(auth_token), application_id)
+    }
+
+    fn application_id_of(&self, msg: &LogMsg) -> Option<re_log_types::ApplicationId> {
+        self.0.take_application_id_of(msg.id())
+    }
+}
+
+#[cfg(feature = "web")]
+async fn host_web_viewer(
+    rerun_ws_server_url: String,
+    auth_token: Option<&str>,
+) -> anyhow::Result<()> {
     let web_port = 9090;
-    let viewer_url = format!("http://127.0.0.1:{web_port}?url={rerun_ws_server_url}");
+    let mut viewer_url = format!("http://127.0.0.1:{web_port}?url={rerun_ws_server_url}");
+    if let Some(auth_token) = auth_token {
+        // The web viewer passes it on to the server.
+        // It is in the fragment, which the browser never sends to the web server.
+        viewer_url = re_ws_comms::web_viewer_url_with_auth_token(&viewer_url, auth_token);
+    }
 
     let web_server = re_web_server::WebServer::new(web_port);
     let web_server_handle = tokio::spawn(web_server.serve());
@@ -355,10 +923,187 @@ async fn host_web_viewer(rerun_ws_server_url: String) -> anyhow::Result<()> {
 }
 
 #[cfg(not(feature = "web"))]
-async fn host_web_viewer(_rerun_ws_server_url: String) -> anyhow::Result<()> {
+async fn host_web_viewer(
+    _rerun_ws_server_url: String,
+    _auth_token: Option<&str>,
+) -> anyhow::Result<()> {
     panic!("Can't host web-viewer - rerun was not compiled with the 'web' feature");
 }
 
+/// Re-serve all messages we receive on a websocket server,
+/// so that other viewers can connect to us instead of to the original source.
+///
+/// Returns the receiver to use for our own viewer.
+#[cfg(feature = "web")]
+async fn relay_to_ws_server(
+    rx: Receiver<LogMsg>,
+    port: u16,
+    args: &Args,
+) -> anyhow::Result<Receiver<LogMsg>> {
+    let ws_server = ws_server(port, args, None).await?;
+    let (relay_tx, relay_rx) = re_smart_channel::smart_channel(rx.source().clone());
+    tokio::spawn(async move {
+        if let Err(err) = ws_server.listen(relay_rx).await {
+            re_log::error!("Relay server failed: {err}");
+        }
+    });
+
+    // Keep the latency numbers from the start of the original channel, like `wake_up_ui_thread_on_each_msg` does.
+    let (tx, new_rx) = rx.chained_channel();
+    std::thread::Builder::new()
+        .name("relay".to_owned())
+        .spawn(move || {
+            while let Ok((sent_at, msg)) = rx.recv_with_send_time() {
+                // The relay must never hold up our own viewer, so ignore its errors.
+                relay_tx.send(msg.clone()).ok();
+                if tx.send_at(sent_at, msg).is_err() {
+                    break; // The viewer has shut down
+                }
+            }
+            re_log::debug!("Shutting down relay thread");
+        })
+        .context("Failed to spawn relay thread")?;
+
+    re_log::info!("Relaying to other viewers. Connect with: rerun ws://<this-machine>:{port}");
+
+    Ok(new_rx)
+}
+
+#[cfg(not(feature = "web"))]
+async fn relay_to_ws_server(
+    _rx: Receiver<LogMsg>,
+    _port: u16,
+    _args: &Args,
+) -> anyhow::Result<Receiver<LogMsg>> {
+    anyhow::bail!("Can't relay to other viewers - rerun was not compiled with the 'web' feature");
+}
+
+/// Save all messages we receive to an `.rrd` file (see `--save`).
+///
+/// Returns the receiver to use for the rest of the messages' journey.
+#[cfg(feature = "sdk")]
+fn save_to_file(
+    rx: Receiver<LogMsg>,
+    path: &std::path::Path,
+    args: &Args,
+) -> anyhow::Result<Receiver<LogMsg>> {
+    let split = re_sdk::FileSplit {
+        max_duration: args
+            .save_part_duration
+            .as_deref()
+            .map(|duration| {
+                parse_duration(duration)
+                    .and_then(|secs| {
+                        std::time::Duration::try_from_secs_f32(secs).map_err(|err| err.to_string())
+                    })
+                    .map_err(|err| anyhow::anyhow!("Failed to parse --save-part-duration: {err}"))
+            })
+            .transpose()?,
+        max_bytes: args
+            .save_part_size
+            .as_deref()
+            .map(|size| {
+                re_format::parse_bytes(size)
+                    .and_then(|bytes| u64::try_from(bytes).ok())
+                    .with_context(|| format!("Failed to parse --save-part-size {size:?}"))
+            })
+            .transpose()?,
+    };
+
+    // Writes on its own thread, so a slow disk doesn't hold up the viewers.
+    let file_sink = re_sdk::FileSink::new(path, split)?;
+
+    let (tx, new_rx) = rx.chained_channel();
+    std::thread::Builder::new()
+        .name("save".to_owned())
+        .spawn(move || {
+            while let Ok((sent_at, msg)) = rx.recv_with_send_time() {
+                file_sink.send(msg.clone());
+                if tx.send_at(sent_at, msg).is_err() {
+                    break; // The viewer has shut down
+                }
+            }
+            re_log::debug!("Shutting down save thread");
+            // Dropping the sink finishes the file.
+        })
+        .context("Failed to spawn save thread")?;
+
+    Ok(new_rx)
+}
+
+#[cfg(not(feature = "sdk"))]
+fn save_to_file(
+    _rx: Receiver<LogMsg>,
+    _path: &std::path::Path,
+    _args: &Args,
+) -> anyhow::Result<Receiver<LogMsg>> {
+    anyhow::bail!("Can't --save - rerun was not compiled with the 'sdk' feature");
+}
+
+/// How much history a `--headless` server keeps for late viewers, unless told otherwise.
+///
+/// It runs for a long time, so keeping everything would eventually run out of memory.
+#[cfg(feature = "web")]
+const HEADLESS_WS_HISTORY_LIMIT: u64 = 1024 * 1024 * 1024;
+
+/// Run without a viewer until the source of the data hangs up, which for a server is never.
+#[cfg(feature = "web")]
+async fn run_headless(rx: Receiver<LogMsg>, args: &Args) -> anyhow::Result<()> {
+    let port = args
+        .relay_port
+        .unwrap_or(re_ws_comms::DEFAULT_WS_SERVER_PORT);
+
+    #[cfg(feature = "server")]
+    if args.url_or_path.is_none() && args.port == port {
+        anyhow::bail!(
+            "Both the SDK:s and the viewers would connect to port {port}. \
+            Please specify a different --port or --relay-port."
+        );
+    }
+
+    let ws_server = ws_server(port, args, Some(HEADLESS_WS_HISTORY_LIMIT)).await?;
+    re_log::info!("Running headless. Connect with: rerun ws://<this-machine>:{port}");
+    ws_server.listen(rx).await
+}
+
+#[cfg(not(feature = "web"))]
+async fn run_headless(rx: Receiver<LogMsg>, args: &Args) -> anyhow::Result<()> {
+    if args.save.is_none() {
+        anyhow::bail!(
+            "Nothing to do with the data: use --save, or compile rerun with the 'web' feature"
+        );
+    }
+    if args.relay_port.is_some() {
+        re_log::warn!("Ignoring --relay-port - rerun was not compiled with the 'web' feature");
+    }
+
+    re_log::info!("Running headless");
+    tokio::task::spawn_blocking(move || while rx.recv().is_ok() {}).await?;
+    Ok(())
+}
+
+#[cfg(feature = "foxglove")]
+async fn connect_to_foxglove_server(url: String) -> anyhow::Result<Receiver<LogMsg>> {
+    re_ws_comms::foxglove::connect(url).await
+}
+
+#[cfg(not(feature = "foxglove"))]
+async fn connect_to_foxglove_server(_url: String) -> anyhow::Result<Receiver<LogMsg>> {
+    anyhow::bail!(
+        "Can't connect to Foxglove servers - rerun was not compiled with the 'foxglove' feature"
+    );
+}
+
+#[cfg(not(feature = "otlp"))]
+async fn merge_otlp_traces(
+    _rx: Receiver<LogMsg>,
+    _bind_addr: std::net::SocketAddr,
+) -> anyhow::Result<Receiver<LogMsg>> {
+    anyhow::bail!(
+        "Can't receive OpenTelemetry traces - rerun was not compiled with the 'otlp' feature"
+    );
+}
+
 #[cfg(feature = "server")]
 fn parse_max_latency(max_latency: Option<&String>) -> f32 {
     max_latency.as_ref().map_or(f32::INFINITY, |time| {
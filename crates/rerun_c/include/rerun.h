@@ -0,0 +1,120 @@
+// A C API for the Rerun logging SDK.
+//
+// Link with `librerun_c` (built with `cargo build -p rerun_c --release`).
+// Keep in sync with `crates/rerun_c/src/lib.rs`.
+//
+// Everything you pass in (strings, point and pixel buffers) is only borrowed for the
+// duration of the call and copied before it returns, so you can free or reuse it right away.
+// The session is owned by Rerun until you call `rr_session_shutdown`.
+//
+// Strings are nul-terminated UTF-8.
+
+#ifndef RERUN_H
+#define RERUN_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef enum {
+    RR_OK = 0,
+    RR_ERROR_NULL_POINTER = 1,
+    RR_ERROR_INVALID_UTF8 = 2,
+    RR_ERROR_INVALID_ARGUMENT = 3,
+    RR_ERROR_LOGGING = 4,
+    RR_ERROR_PANIC = 5,
+} rr_error;
+
+typedef struct rr_session rr_session;
+
+// The message of the last error returned on this thread.
+// Valid until the next call to a Rerun function on this thread.
+const char* rr_last_error(void);
+
+// Start a new recording. Returns NULL on error.
+// Everything logged is buffered until `rr_session_connect` is called.
+rr_session* rr_session_init(const char* application_id);
+
+// Send everything logged so far, close the connection and free the session. NULL is ignored.
+void rr_session_shutdown(rr_session* session);
+
+// Stream to a Rerun Viewer or Server at `addr`, e.g. "127.0.0.1:9876" or "lab-server:9876".
+// NULL for the default address.
+// The auth token for a server with an access control list is read from `RERUN_AUTH_TOKEN`.
+rr_error rr_session_connect(rr_session* session, const char* addr);
+
+// Block until everything logged so far has been sent.
+rr_error rr_session_flush(rr_session* session);
+
+// Stamp everything logged from now on with this sequence number (e.g. a frame index).
+rr_error rr_set_time_sequence(rr_session* session, const char* timeline, int64_t sequence);
+
+// Stamp everything logged from now on with this time, in seconds since the unix epoch.
+rr_error rr_set_time_seconds(rr_session* session, const char* timeline, double seconds);
+
+// Stop stamping with the times set with `rr_set_time_sequence` and `rr_set_time_seconds`.
+rr_error rr_reset_time(rr_session* session);
+
+// `positions` are `x, y, z, x, y, z, ...` (3 * num_points floats).
+// `colors` are `r, g, b, a, ...` (4 * num_points bytes), or NULL.
+rr_error rr_log_points3d(
+    rr_session* session,
+    const char* entity_path,
+    const float* positions,
+    size_t num_points,
+    const uint8_t* colors
+);
+
+// `positions` are `x, y, x, y, ...` (2 * num_points floats).
+// `colors` are `r, g, b, a, ...` (4 * num_points bytes), or NULL.
+rr_error rr_log_points2d(
+    rr_session* session,
+    const char* entity_path,
+    const float* positions,
+    size_t num_points,
+    const uint8_t* colors
+);
+
+// An 8-bit image in row-major order, with 1 (gray), 3 (RGB) or 4 (RGBA) channels.
+// `num_bytes` must be `width * height * channels`.
+rr_error rr_log_image(
+    rr_session* session,
+    const char* entity_path,
+    uint32_t width,
+    uint32_t height,
+    uint32_t channels,
+    const uint8_t* pixels,
+    size_t num_bytes
+);
+
+// A depth image in row-major order. `num_pixels` must be `width * height`.
+// `meter` is how many units make a meter (e.g. 1000 for millimeters), or zero if unknown.
+rr_error rr_log_depth_image(
+    rr_session* session,
+    const char* entity_path,
+    uint32_t width,
+    uint32_t height,
+    const float* depth,
+    size_t num_pixels,
+    float meter
+);
+
+// One value of a time series, plotted over time.
+rr_error rr_log_scalar(rr_session* session, const char* entity_path, double value);
+
+// A log line, with an optional `level` like "INFO" or "WARN" (or NULL).
+rr_error rr_log_text(
+    rr_session* session,
+    const char* entity_path,
+    const char* text,
+    const char* level
+);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif // RERUN_H
@@ -0,0 +1,583 @@
+//! A C API for the Rerun logging SDK, declared in `include/rerun.h`.
+//!
+//! Lets C and C++ applications (e.g. robotics stacks) stream to a Rerun Viewer or Server
+//! without linking to Rust directly.
+//!
+//! ## Ownership
+//! Everything the caller passes in (strings, point and pixel buffers) is only borrowed for
+//! the duration of the call: we copy what we need before returning, so the caller stays the
+//! owner and may free or reuse the buffers right away.
+//! The only thing we own on behalf of the caller is the session, which is freed by
+//! [`rr_session_shutdown`].
+//!
+//! ## Errors
+//! Functions return an [`RrError`], with a description of the last error available from
+//! [`rr_last_error`]. Panics are caught, since they must never unwind into C.
+
+// This is all about raw pointers from C:
+#![allow(unsafe_code)]
+
+use std::{
+    cell::RefCell,
+    ffi::{c_char, CStr, CString},
+};
+
+use re_sdk::{
+    components::{ColorRGBA, Point2D, Point3D, Scalar, Tensor, TextEntry},
+    MsgSender, Session,
+};
+
+/// Keep in sync with `rr_error` in `include/rerun.h`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RrError {
+    Ok = 0,
+    NullPointer = 1,
+    InvalidUtf8 = 2,
+    InvalidArgument = 3,
+    Logging = 4,
+    Panic = 5,
+}
+
+struct Error {
+    code: RrError,
+    message: String,
+}
+
+impl Error {
+    fn new(code: RrError, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<re_sdk::MsgSenderError> for Error {
+    fn from(err: re_sdk::MsgSenderError) -> Self {
+        Self::new(RrError::Logging, err.to_string())
+    }
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<CString> = RefCell::new(CString::default());
+}
+
+/// Run `f`, turning errors and panics into an error code and [`LAST_ERROR`].
+fn catch(f: impl FnOnce() -> Result<(), Error>) -> RrError {
+    let result =
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)).unwrap_or_else(|panic| {
+            let message = if let Some(message) = panic.downcast_ref::<&str>() {
+                (*message).to_owned()
+            } else if let Some(message) = panic.downcast_ref::<String>() {
+                message.clone()
+            } else {
+                "Unknown panic".to_owned()
+            };
+            Err(Error::new(RrError::Panic, message))
+        });
+
+    match result {
+        Ok(()) => RrError::Ok,
+        Err(Error { code, message }) => {
+            re_log::debug!("{message}");
+            let message = CString::new(message.replace('\0', " ")).unwrap_or_default();
+            LAST_ERROR.with(|last_error| *last_error.borrow_mut() = message);
+            code
+        }
+    }
+}
+
+/// # Safety
+/// `session` must be null or come from [`rr_session_init`], and not have been shut down.
+unsafe fn session<'a>(session: *mut Session) -> Result<&'a mut Session, Error> {
+    // SAFETY: guaranteed by the caller.
+    unsafe { session.as_mut() }.ok_or_else(|| Error::new(RrError::NullPointer, "session is null"))
+}
+
+/// # Safety
+/// `ptr` must be null or point to a nul-terminated string.
+unsafe fn string<'a>(name: &str, ptr: *const c_char) -> Result<&'a str, Error> {
+    if ptr.is_null() {
+        return Err(Error::new(RrError::NullPointer, format!("{name} is null")));
+    }
+    // SAFETY: guaranteed by the caller.
+    unsafe { CStr::from_ptr(ptr) }
+        .to_str()
+        .map_err(|err| Error::new(RrError::InvalidUtf8, format!("{name}: {err}")))
+}
+
+/// `count * values_per_item`, checking for overflow.
+fn num_values(name: &str, count: usize, values_per_item: usize) -> Result<usize, Error> {
+    count.checked_mul(values_per_item).ok_or_else(|| {
+        Error::new(
+            RrError::InvalidArgument,
+            format!("{name}: {count} items of {values_per_item} values is too many"),
+        )
+    })
+}
+
+/// # Safety
+/// `ptr` must be null or point to at least `len` values of `T`.
+unsafe fn slice<'a, T>(name: &str, ptr: *const T, len: usize) -> Result<&'a [T], Error> {
+    if len == 0 {
+        Ok(&[])
+    } else if ptr.is_null() {
+        Err(Error::new(RrError::NullPointer, format!("{name} is null")))
+    } else if len
+        .checked_mul(std::mem::size_of::<T>())
+        .map_or(true, |num_bytes| num_bytes > isize::MAX as usize)
+    {
+        Err(Error::new(
+            RrError::InvalidArgument,
+            format!("{name}: {len} values is too many"),
+        ))
+    } else {
+        // SAFETY: guaranteed by the caller.
+        Ok(unsafe { std::slice::from_raw_parts(ptr, len) })
+    }
+}
+
+/// # Safety
+/// `colors` must be null or point to `4 * num_instances` bytes.
+unsafe fn colors(colors: *const u8, num_instances: usize) -> Result<Option<Vec<ColorRGBA>>, Error> {
+    if colors.is_null() {
+        return Ok(None);
+    }
+    let num_bytes = num_values("colors", num_instances, 4)?;
+    // SAFETY: guaranteed by the caller.
+    let colors = unsafe { slice("colors", colors, num_bytes) }?;
+    Ok(Some(
+        colors
+            .chunks_exact(4)
+            .map(|rgba| ColorRGBA::from_rgba(rgba[0], rgba[1], rgba[2], rgba[3]))
+            .collect(),
+    ))
+}
+
+// ----------------------------------------------------------------------------
+
+/// The message of the last error returned on this thread.
+///
+/// Valid until the next call to a Rerun function on this thread.
+#[no_mangle]
+pub extern "C" fn rr_last_error() -> *const c_char {
+    LAST_ERROR.with(|last_error| last_error.borrow().as_ptr())
+}
+
+/// Start a new recording. Returns null on error.
+///
+/// Buffers everything logged until [`rr_session_connect`] is called.
+///
+/// # Safety
+/// `application_id` must be a nul-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn rr_session_init(application_id: *const c_char) -> *mut Session {
+    let mut result = std::ptr::null_mut();
+    catch(|| {
+        // SAFETY: guaranteed by the caller.
+        let application_id = unsafe { string("application_id", application_id) }?;
+        result = Box::into_raw(Box::new(Session::init(application_id, true)));
+        Ok(())
+    });
+    result
+}
+
+/// Send everything logged so far, close the connection and free the session.
+///
+/// # Safety
+/// `session` must be null or come from [`rr_session_init`], and not have been shut down.
+#[no_mangle]
+pub unsafe extern "C" fn rr_session_shutdown(session: *mut Session) {
+    if session.is_null() {
+        return;
+    }
+    catch(|| {
+        // SAFETY: guaranteed by the caller.
+        let mut session = unsafe { Box::from_raw(session) };
+        session.flush();
+        session.disconnect();
+        Ok(())
+    });
+}
+
+/// Stream to a Rerun Viewer or Server at `addr`, e.g. `127.0.0.1:9876`.
+///
+/// Null for the default address.
+///
+/// # Safety
+/// `session` must come from [`rr_session_init`], and `addr` must be null or a nul-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn rr_session_connect(session: *mut Session, addr: *const c_char) -> RrError {
+    catch(|| {
+        // SAFETY: guaranteed by the caller.
+        let session = unsafe { self::session(session) }?;
+        let addr = if addr.is_null() {
+            re_sdk::default_server_addr()
+        } else {
+            use std::net::ToSocketAddrs as _;
+            // SAFETY: guaranteed by the caller.
+            let addr = unsafe { string("addr", addr) }?;
+            addr.to_socket_addrs()
+                .map_err(|err| Error::new(RrError::InvalidArgument, format!("{addr:?}: {err}")))?
+                .next()
+                .ok_or_else(|| {
+                    Error::new(RrError::InvalidArgument, format!("{addr:?}: no address"))
+                })?
+        };
+        session.connect(addr);
+        Ok(())
+    })
+}
+
+/// Block until everything logged so far has been sent.
+///
+/// # Safety
+/// `session` must come from [`rr_session_init`].
+#[no_mangle]
+pub unsafe extern "C" fn rr_session_flush(session: *mut Session) -> RrError {
+    catch(|| {
+        // SAFETY: guaranteed by the caller.
+        unsafe { self::session(session) }?.flush();
+        Ok(())
+    })
+}
+
+/// Stamp everything logged from now on with this sequence number (e.g. a frame index).
+///
+/// # Safety
+/// `session` must come from [`rr_session_init`], and `timeline` must be a nul-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn rr_set_time_sequence(
+    session: *mut Session,
+    timeline: *const c_char,
+    sequence: i64,
+) -> RrError {
+    catch(|| {
+        // SAFETY: guaranteed by the caller.
+        let (session, timeline) =
+            unsafe { (self::session(session)?, string("timeline", timeline)?) };
+        session.set_time_sequence(timeline, Some(sequence));
+        Ok(())
+    })
+}
+
+/// Stamp everything logged from now on with this time, in seconds since the unix epoch.
+///
+/// # Safety
+/// `session` must come from [`rr_session_init`], and `timeline` must be a nul-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn rr_set_time_seconds(
+    session: *mut Session,
+    timeline: *const c_char,
+    seconds: f64,
+) -> RrError {
+    catch(|| {
+        // SAFETY: guaranteed by the caller.
+        let (session, timeline) =
+            unsafe { (self::session(session)?, string("timeline", timeline)?) };
+        session.set_time_seconds(timeline, Some(seconds));
+        Ok(())
+    })
+}
+
+/// Stop stamping with the times set with [`rr_set_time_sequence`] and [`rr_set_time_seconds`].
+///
+/// # Safety
+/// `session` must come from [`rr_session_init`].
+#[no_mangle]
+pub unsafe extern "C" fn rr_reset_time(session: *mut Session) -> RrError {
+    catch(|| {
+        // SAFETY: guaranteed by the caller.
+        unsafe { self::session(session) }?.reset_time();
+        Ok(())
+    })
+}
+
+/// `positions` are `x, y, z, x, y, z, …`, and `colors` (optional) are `r, g, b, a, …`.
+///
+/// # Safety
+/// `session` must come from [`rr_session_init`], `entity_path` must be a nul-terminated string,
+/// `positions` must point to `3 * num_points` floats, and `colors` must be null or point to
+/// `4 * num_points` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn rr_log_points3d(
+    session: *mut Session,
+    entity_path: *const c_char,
+    positions: *const f32,
+    num_points: usize,
+    colors: *const u8,
+) -> RrError {
+    catch(|| {
+        let num_floats = num_values("positions", num_points, 3)?;
+        // SAFETY: guaranteed by the caller.
+        let (session, entity_path, positions, colors) = unsafe {
+            (
+                self::session(session)?,
+                string("entity_path", entity_path)?,
+                slice("positions", positions, num_floats)?,
+                self::colors(colors, num_points)?,
+            )
+        };
+        let positions: Vec<Point3D> = positions
+            .chunks_exact(3)
+            .map(|xyz| Point3D::new(xyz[0], xyz[1], xyz[2]))
+            .collect();
+        let mut msg = MsgSender::new(entity_path).with_component(&positions)?;
+        if let Some(colors) = colors {
+            msg = msg.with_component(&colors)?;
+        }
+        msg.send(session)?;
+        Ok(())
+    })
+}
+
+/// `positions` are `x, y, x, y, …`, and `colors` (optional) are `r, g, b, a, …`.
+///
+/// # Safety
+/// `session` must come from [`rr_session_init`], `entity_path` must be a nul-terminated string,
+/// `positions` must point to `2 * num_points` floats, and `colors` must be null or point to
+/// `4 * num_points` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn rr_log_points2d(
+    session: *mut Session,
+    entity_path: *const c_char,
+    positions: *const f32,
+    num_points: usize,
+    colors: *const u8,
+) -> RrError {
+    catch(|| {
+        let num_floats = num_values("positions", num_points, 2)?;
+        // SAFETY: guaranteed by the caller.
+        let (session, entity_path, positions, colors) = unsafe {
+            (
+                self::session(session)?,
+                string("entity_path", entity_path)?,
+                slice("positions", positions, num_floats)?,
+                self::colors(colors, num_points)?,
+            )
+        };
+        let positions: Vec<Point2D> = positions
+            .chunks_exact(2)
+            .map(|xy| Point2D::new(xy[0], xy[1]))
+            .collect();
+        let mut msg = MsgSender::new(entity_path).with_component(&positions)?;
+        if let Some(colors) = colors {
+            msg = msg.with_component(&colors)?;
+        }
+        msg.send(session)?;
+        Ok(())
+    })
+}
+
+/// An 8-bit image in row-major order, with 1 (gray), 3 (RGB) or 4 (RGBA) channels.
+///
+/// # Safety
+/// `session` must come from [`rr_session_init`], `entity_path` must be a nul-terminated string,
+/// and `pixels` must point to `num_bytes` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn rr_log_image(
+    session: *mut Session,
+    entity_path: *const c_char,
+    width: u32,
+    height: u32,
+    channels: u32,
+    pixels: *const u8,
+    num_bytes: usize,
+) -> RrError {
+    catch(|| {
+        // SAFETY: guaranteed by the caller.
+        let (session, entity_path, pixels) = unsafe {
+            (
+                self::session(session)?,
+                string("entity_path", entity_path)?,
+                slice("pixels", pixels, num_bytes)?,
+            )
+        };
+        let pixels = pixels.to_vec();
+        let tensor = match channels {
+            1 => Tensor::from_mono8(width, height, pixels),
+            3 => Tensor::from_rgb8(width, height, pixels),
+            4 => Tensor::from_rgba8(width, height, pixels),
+            _ => {
+                return Err(Error::new(
+                    RrError::InvalidArgument,
+                    format!("Expected 1, 3 or 4 channels, got {channels}"),
+                ))
+            }
+        }
+        .map_err(|err| Error::new(RrError::InvalidArgument, err.to_string()))?;
+        MsgSender::new(entity_path)
+            .with_component(&[tensor])?
+            .send(session)?;
+        Ok(())
+    })
+}
+
+/// A depth image in row-major order, where `meter` is how many units make a meter
+/// (e.g. `1000` for millimeters), or zero if unknown.
+///
+/// # Safety
+/// `session` must come from [`rr_session_init`], `entity_path` must be a nul-terminated string,
+/// and `depth` must point to `num_pixels` floats.
+#[no_mangle]
+pub unsafe extern "C" fn rr_log_depth_image(
+    session: *mut Session,
+    entity_path: *const c_char,
+    width: u32,
+    height: u32,
+    depth: *const f32,
+    num_pixels: usize,
+    meter: f32,
+) -> RrError {
+    catch(|| {
+        // SAFETY: guaranteed by the caller.
+        let (session, entity_path, depth) = unsafe {
+            (
+                self::session(session)?,
+                string("entity_path", entity_path)?,
+                slice("depth", depth, num_pixels)?,
+            )
+        };
+        let meter = (meter > 0.0).then_some(meter);
+        let tensor = Tensor::from_depth_f32(width, height, depth.to_vec(), meter)
+            .map_err(|err| Error::new(RrError::InvalidArgument, err.to_string()))?;
+        MsgSender::new(entity_path)
+            .with_component(&[tensor])?
+            .send(session)?;
+        Ok(())
+    })
+}
+
+/// One value of a time series, plotted over time.
+///
+/// # Safety
+/// `session` must come from [`rr_session_init`], and `entity_path` must be a nul-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn rr_log_scalar(
+    session: *mut Session,
+    entity_path: *const c_char,
+    value: f64,
+) -> RrError {
+    catch(|| {
+        // SAFETY: guaranteed by the caller.
+        let (session, entity_path) =
+            unsafe { (self::session(session)?, string("entity_path", entity_path)?) };
+        MsgSender::new(entity_path)
+            .with_component(&[Scalar(value)])?
+            .send(session)?;
+        Ok(())
+    })
+}
+
+/// A log line, with an optional level like `"INFO"` or `"WARN"`.
+///
+/// # Safety
+/// `session` must come from [`rr_session_init`], `entity_path` and `text` must be nul-terminated
+/// strings, and `level` must be null or a nul-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn rr_log_text(
+    session: *mut Session,
+    entity_path: *const c_char,
+    text: *const c_char,
+    level: *const c_char,
+) -> RrError {
+    catch(|| {
+        // SAFETY: guaranteed by the caller.
+        let (session, entity_path, text) = unsafe {
+            (
+                self::session(session)?,
+                string("entity_path", entity_path)?,
+                string("text", text)?,
+            )
+        };
+        let level = if level.is_null() {
+            None
+        } else {
+            // SAFETY: guaranteed by the caller.
+            Some(unsafe { string("level", level) }?.to_owned())
+        };
+        MsgSender::new(entity_path)
+            .with_component(&[TextEntry::new(text, level)])?
+            .send(session)?;
+        Ok(())
+    })
+}
+
+#[test]
+fn test_c_api() {
+    use re_sdk::Component as _;
+
+    fn c(s: &str) -> CString {
+        CString::new(s).unwrap()
+    }
+
+    let server = re_sdk::test::MockServer::new().unwrap();
+
+    // SAFETY: all pointers are valid for the duration of the calls.
+    let (bundles, error) = unsafe {
+        let session = rr_session_init(c("c_api_test").as_ptr());
+        assert!(!session.is_null());
+        let addr = c(&server.addr().to_string());
+        assert_eq!(rr_session_connect(session, addr.as_ptr()), RrError::Ok);
+        assert_eq!(
+            rr_set_time_sequence(session, c("frame").as_ptr(), 42),
+            RrError::Ok
+        );
+
+        let positions = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let colors = [255, 0, 0, 255, 0, 255, 0, 255];
+        assert_eq!(
+            rr_log_points3d(
+                session,
+                c("points").as_ptr(),
+                positions.as_ptr(),
+                2,
+                colors.as_ptr()
+            ),
+            RrError::Ok
+        );
+
+        let pixels = [0_u8; 5];
+        assert_eq!(
+            rr_log_image(
+                session,
+                c("image").as_ptr(),
+                2,
+                2,
+                3,
+                pixels.as_ptr(),
+                pixels.len()
+            ),
+            RrError::InvalidArgument
+        );
+        let error = CStr::from_ptr(rr_last_error()).to_str().unwrap().to_owned();
+
+        assert_eq!(
+            rr_log_points3d(
+                session,
+                c("points").as_ptr(),
+                positions.as_ptr(),
+                usize::MAX / 2,
+                colors.as_ptr()
+            ),
+            RrError::InvalidArgument
+        );
+
+        assert_eq!(
+            rr_log_scalar(std::ptr::null_mut(), c("scalar").as_ptr(), 1.0),
+            RrError::NullPointer
+        );
+
+        assert_eq!(rr_session_flush(session), RrError::Ok);
+        let bundles = server.wait_for_bundles(1, std::time::Duration::from_secs(30));
+        rr_session_shutdown(session);
+        (bundles, error)
+    };
+
+    assert!(error.contains("needs 12 values"), "{error}");
+    assert_eq!(bundles.len(), 1);
+    assert_eq!(bundles[0].entity_path, "points".into());
+    assert!(bundles[0].find_component(&Point3D::name()).is_some());
+    assert!(bundles[0].find_component(&ColorRGBA::name()).is_some());
+}
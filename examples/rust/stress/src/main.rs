@@ -0,0 +1,254 @@
+//! Generates configurable synthetic data streams, for profiling and demoing Rerun without real hardware.
+//!
+//! Simulates `N` cameras at `X` fps, `M` point clouds at `Y` Hz and `K` scalar series,
+//! all on a shared `sim_time` timeline.
+//!
+//! Usage:
+//! ```
+//! cargo run --release -p stress -- --cameras 4 --camera-fps 30 --point-clouds 2 --points 100000
+//! ```
+//!
+//! Stream to a viewer running elsewhere, or to a file:
+//! ```
+//! cargo run --release -p stress -- --connect 127.0.0.1:9876
+//! cargo run --release -p stress -- --save stress.rrd
+//! ```
+
+use rerun::{
+    components::{ColorRGBA, Point3D, Scalar, Tensor},
+    external::re_log,
+    time::{Time, TimePoint, TimeType, Timeline},
+    MsgSender, Session,
+};
+
+// --- Args ---
+
+#[derive(Debug, clap::Parser)]
+#[clap(author, version, about)]
+struct Args {
+    #[command(flatten)]
+    rerun: rerun::clap::RerunArgs,
+
+    /// For how long to generate data, in seconds of simulated time.
+    #[clap(long, default_value_t = 10.0)]
+    duration: f64,
+
+    /// Throttle the generation so that simulated time matches wall-clock time.
+    ///
+    /// By default data is generated as fast as possible.
+    #[clap(long, default_value_t = false)]
+    realtime: bool,
+
+    /// Number of simulated cameras.
+    #[clap(long, default_value_t = 1)]
+    cameras: usize,
+
+    /// Frame rate of each camera.
+    #[clap(long, default_value_t = 30.0, value_parser = parse_rate)]
+    camera_fps: f64,
+
+    /// Width of each camera image.
+    #[clap(long, default_value_t = 640)]
+    image_width: usize,
+
+    /// Height of each camera image.
+    #[clap(long, default_value_t = 480)]
+    image_height: usize,
+
+    /// Number of simulated point clouds (e.g. lidars).
+    #[clap(long, default_value_t = 1)]
+    point_clouds: usize,
+
+    /// Number of points in each point cloud.
+    #[clap(long, default_value_t = 10_000)]
+    points: usize,
+
+    /// Rate at which each point cloud is updated.
+    #[clap(long, default_value_t = 10.0, value_parser = parse_rate)]
+    point_cloud_hz: f64,
+
+    /// Number of scalar time series.
+    #[clap(long, default_value_t = 10)]
+    scalars: usize,
+
+    /// Rate at which each scalar is updated.
+    #[clap(long, default_value_t = 100.0, value_parser = parse_rate)]
+    scalar_hz: f64,
+}
+
+/// A rate in Hz, which must be positive so that each stream has a finite period.
+fn parse_rate(arg: &str) -> Result<f64, String> {
+    let hz: f64 = arg.parse().map_err(|err| format!("{err}"))?;
+    if hz > 0.0 && hz.is_finite() {
+        Ok(hz)
+    } else {
+        Err(format!("expected a positive rate, got {hz}"))
+    }
+}
+
+// --- Streams ---
+
+#[derive(Clone, Copy, Debug)]
+enum StreamKind {
+    Camera,
+    PointCloud,
+    Scalar,
+}
+
+/// A single periodic source of data.
+struct Stream {
+    kind: StreamKind,
+    index: usize,
+    period: f64,
+    next_time: f64,
+}
+
+impl Stream {
+    fn new(kind: StreamKind, index: usize, hz: f64) -> Self {
+        Self {
+            kind,
+            index,
+            period: 1.0 / hz,
+            next_time: 0.0,
+        }
+    }
+}
+
+fn sim_time(at: f64) -> TimePoint {
+    let timeline_sim_time = Timeline::new("sim_time", TimeType::Time);
+    let time = Time::from_seconds_since_epoch(at);
+    [(timeline_sim_time, time.into())].into()
+}
+
+fn log_camera(session: &mut Session, args: &Args, index: usize, t: f64) -> anyhow::Result<()> {
+    // A gradient scrolling over time, so consecutive frames actually differ.
+    let offset = (t * 100.0) as usize + index * 50;
+    let image = ndarray::Array3::from_shape_fn(
+        (args.image_height, args.image_width, 3),
+        |(y, x, c)| match c {
+            0 => (x + offset) as u8,
+            1 => (y + offset) as u8,
+            _ => (index * 64) as u8,
+        },
+    );
+
+    MsgSender::new(format!("cameras/{index}"))
+        .with_timepoint(sim_time(t))
+        .with_component(&[Tensor::try_from(image.view())?])?
+        .send(session)?;
+
+    Ok(())
+}
+
+fn log_point_cloud(session: &mut Session, args: &Args, index: usize, t: f64) -> anyhow::Result<()> {
+    let n = args.points;
+    let (points, colors): (Vec<_>, Vec<_>) = (0..n)
+        .map(|i| {
+            let f = i as f32 / n as f32;
+            let angle = f * std::f32::consts::TAU * 20.0 + t as f32;
+            let radius = 1.0 + f * 5.0;
+            (
+                Point3D::new(
+                    radius * angle.cos(),
+                    radius * angle.sin(),
+                    f * 10.0 + index as f32 * 2.0,
+                ),
+                ColorRGBA::from_rgb((f * 255.0) as u8, 128, (index * 64) as u8),
+            )
+        })
+        .unzip();
+
+    MsgSender::new(format!("point_clouds/{index}"))
+        .with_timepoint(sim_time(t))
+        .with_component(&points)?
+        .with_component(&colors)?
+        .send(session)?;
+
+    Ok(())
+}
+
+fn log_scalar(session: &mut Session, index: usize, t: f64) -> anyhow::Result<()> {
+    let value = (t * (1.0 + index as f64 * 0.1)).sin() * (1.0 + index as f64);
+
+    MsgSender::new(format!("scalars/{index}"))
+        .with_timepoint(sim_time(t))
+        .with_component(&[Scalar(value)])?
+        .send(session)?;
+
+    Ok(())
+}
+
+fn run(session: &mut Session, args: &Args) -> anyhow::Result<()> {
+    let mut streams = create_streams(args);
+    anyhow::ensure!(!streams.is_empty(), "No streams configured");
+
+    let start = std::time::Instant::now();
+    let mut num_messages = 0_usize;
+
+    loop {
+        // Pick whichever stream is due next.
+        let stream = streams
+            .iter_mut()
+            .min_by(|a, b| a.next_time.total_cmp(&b.next_time))
+            .expect("checked above that there are streams");
+
+        let t = stream.next_time;
+        if t > args.duration {
+            break;
+        }
+        stream.next_time += stream.period;
+
+        if args.realtime {
+            let elapsed = start.elapsed().as_secs_f64();
+            if elapsed < t {
+                std::thread::sleep(std::time::Duration::from_secs_f64(t - elapsed));
+            }
+        }
+
+        match stream.kind {
+            StreamKind::Camera => log_camera(session, args, stream.index, t)?,
+            StreamKind::PointCloud => log_point_cloud(session, args, stream.index, t)?,
+            StreamKind::Scalar => log_scalar(session, stream.index, t)?,
+        }
+        num_messages += 1;
+    }
+
+    let elapsed = start.elapsed().as_secs_f64();
+    re_log::info!(
+        "Logged {num_messages} messages ({:.1}s of simulated time) in {elapsed:.1}s: {:.0} msg/s",
+        args.duration,
+        num_messages as f64 / elapsed,
+    );
+
+    Ok(())
+}
+
+fn create_streams(args: &Args) -> Vec<Stream> {
+    let cameras = (0..args.cameras).map(|i| Stream::new(StreamKind::Camera, i, args.camera_fps));
+    let point_clouds =
+        (0..args.point_clouds).map(|i| Stream::new(StreamKind::PointCloud, i, args.point_cloud_hz));
+    let scalars = (0..args.scalars).map(|i| Stream::new(StreamKind::Scalar, i, args.scalar_hz));
+    cameras.chain(point_clouds).chain(scalars).collect()
+}
+
+fn main() -> anyhow::Result<()> {
+    re_log::setup_native_logging();
+
+    use clap::Parser as _;
+    let args = Args::parse();
+
+    let mut session = rerun::Session::init("stress_rs", true);
+
+    let should_spawn = args.rerun.on_startup(&mut session);
+    if should_spawn {
+        return session
+            .spawn(move |mut session| run(&mut session, &args))
+            .map_err(Into::into);
+    }
+
+    run(&mut session, &args)?;
+
+    args.rerun.on_teardown(&mut session)?;
+
+    Ok(())
+}
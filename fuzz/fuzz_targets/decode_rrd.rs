@@ -0,0 +1,9 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(decoder) = re_log_types::encoding::Decoder::new(data) {
+        for _ in decoder {}
+    }
+});
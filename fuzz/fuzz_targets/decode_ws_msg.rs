@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = re_ws_comms::decode_log_msg(data);
+    let _ = re_ws_comms::decode_degraded_streams(data);
+});
@@ -0,0 +1,16 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use re_log_types::{Duration, Time};
+
+fuzz_target!(|s: &str| {
+    if let Ok(duration) = Duration::parse(s) {
+        // Formatting truncates to milliseconds, after which it round-trips exactly:
+        let truncated = Duration::parse(&duration.to_string()).unwrap();
+        assert_eq!(Duration::parse(&truncated.to_string()), Ok(truncated));
+    }
+
+    if let Ok(time) = Time::parse(s) {
+        let _ = time.format();
+    }
+});
@@ -93,6 +93,11 @@ SECTION_TABLE: Final[List[Section]] = [
         module_summary=None,
         func_list=["log_annotation_context"],
     ),
+    Section(
+        title="Grouping",
+        module_summary=None,
+        func_list=["log_group"],
+    ),
     Section(
         title="Extension Components",
         module_summary=None,
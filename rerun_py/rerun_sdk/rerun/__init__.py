@@ -12,6 +12,7 @@ from rerun.log.bounding_box import log_obb
 from rerun.log.camera import log_pinhole
 from rerun.log.extension_components import log_extension_components
 from rerun.log.file import log_image_file, log_mesh_file
+from rerun.log.group import log_group
 from rerun.log.image import log_depth_image, log_image, log_segmentation_image
 from rerun.log.lines import log_line_segments, log_line_strip, log_path
 from rerun.log.mesh import log_mesh, log_meshes
@@ -32,6 +33,7 @@ __all__ = [
     "log_cleared",
     "log_depth_image",
     "log_extension_components",
+    "log_group",
     "log_image_file",
     "log_image",
     "log_line_segments",
@@ -218,14 +220,19 @@ def set_enabled(enabled: bool) -> None:
 
 def connect(addr: Optional[str] = None) -> None:
     """
-    Connect to a remote Rerun Viewer on the given ip:port.
+    Connect to a remote Rerun Viewer on the given ip:port or hostname:port.
 
-    Requires that you first start a Rerun Viewer, e.g. with 'python -m rerun'
+    Requires that you first start a Rerun Viewer, e.g. with 'python -m rerun',
+    or a headless Rerun Server, e.g. with 'python -m rerun --headless --save recording.rrd'.
+
+    If the server has an access control list ('--acl'), set the environment variable
+    'RERUN_AUTH_TOKEN' to your team's token.
 
     Parameters
     ----------
     addr
-        The ip:port to connect to
+        The address to connect to, e.g. "127.0.0.1:9876" or "lab-server:9876".
+        Defaults to "127.0.0.1:9876".
 
     """
 
@@ -347,6 +354,39 @@ def save(path: str) -> None:
     bindings.save(path)
 
 
+def save_while_logging(
+    path: str, split_minutes: Optional[float] = None, split_gigabytes: Optional[float] = None
+) -> None:
+    """
+    Also save everything logged from now on to a file.
+
+    Unlike `save`, this works together with `connect`, `spawn` and `serve`,
+    so that you always have a local copy of the data, even when streaming it live.
+    The file is written in the background, and completed when the program exits.
+
+    Long captures can be split into several files, e.g. to upload them while still capturing.
+    `field_run.rrd` is then saved as `field_run.0000.rrd`, `field_run.0001.rrd`, etc.
+    Each part can be viewed on its own. Open them all at once to see the whole recording.
+
+    Parameters
+    ----------
+    path : str
+        The path to save the data to, e.g. `"field_run.rrd"`.
+    split_minutes : Optional[float]
+        Continue in a new file after this many minutes.
+    split_gigabytes : Optional[float]
+        Continue in a new file once the current one is this large.
+
+    """
+
+    if not bindings.is_enabled():
+        print("Rerun is disabled - save_while_logging() call ignored")
+        return
+
+    split_bytes = None if split_gigabytes is None else int(split_gigabytes * 1e9)
+    bindings.save_while_logging(path, split_minutes, split_bytes)
+
+
 def set_time_sequence(timeline: str, sequence: Optional[int]) -> None:
     """
     Set the current time for this thread as an integer sequence.
@@ -13,7 +13,9 @@ all = [
     "arrow",
     "box",
     "color",
+    "group",
     "label",
+    "metadata",
     "point",
     "quaternion",
     "radius",
@@ -22,6 +24,7 @@ all = [
     "scalar_plot_props",
     "tensor",
     "text_entry",
+    "unit",
     "vec",
 ]
 
@@ -0,0 +1,24 @@
+from __future__ import annotations
+
+from typing import Sequence
+
+import pyarrow as pa
+
+from rerun.components import REGISTERED_COMPONENT_NAMES, ComponentTypeFactory
+
+__all__ = [
+    "GroupArray",
+    "GroupType",
+]
+
+
+class GroupArray(pa.ExtensionArray):  # type: ignore[misc]
+    def new(groups: Sequence[str]) -> GroupArray:
+        """Build a `GroupArray` from a sequence of str."""
+        storage = pa.array(groups, type=GroupType.storage_type)
+        return storage  # type: ignore[no-any-return]
+
+
+GroupType = ComponentTypeFactory("GroupType", GroupArray, REGISTERED_COMPONENT_NAMES["rerun.group"])
+
+pa.register_extension_type(GroupType())
@@ -0,0 +1,28 @@
+from __future__ import annotations
+
+from typing import Any, Mapping, Sequence
+
+import pyarrow as pa
+
+from rerun.components import REGISTERED_COMPONENT_NAMES, ComponentTypeFactory
+
+__all__ = [
+    "MetadataArray",
+    "MetadataType",
+]
+
+
+class MetadataArray(pa.ExtensionArray):  # type: ignore[misc]
+    def from_dicts(metadata: Sequence[Mapping[str, Any]]) -> MetadataArray:
+        """Build a `MetadataArray` from a sequence of dicts, converting all keys and values to str."""
+        entries = [
+            {"entries": [{"key": str(key), "value": str(value)} for key, value in instance.items()]}
+            for instance in metadata
+        ]
+        storage = pa.array(entries, type=MetadataType.storage_type)
+        return storage  # type: ignore[no-any-return]
+
+
+MetadataType = ComponentTypeFactory("MetadataType", MetadataArray, REGISTERED_COMPONENT_NAMES["rerun.metadata"])
+
+pa.register_extension_type(MetadataType())
@@ -1,5 +1,6 @@
 from __future__ import annotations
 
+import sys
 import uuid
 from typing import Final, Iterable, Union, cast
 
@@ -50,6 +51,30 @@ DTYPE_MAP: Final[dict[npt.DTypeLike, str]] = {
 }
 
 
+def _flat_data_and_strides(array: npt.NDArray[TensorDType]) -> tuple[npt.NDArray[TensorDType], list[int] | None]:
+    """
+    Flatten the array, avoiding a copy when possible.
+
+    Returns the flat data, and the strides (in elements) to index into it with,
+    or `None` if the data is in the default contiguous row-major order.
+    """
+    if array.flags.c_contiguous:
+        return array.reshape(-1), None
+
+    itemsize = array.dtype.itemsize
+    if all(stride >= 0 and stride % itemsize == 0 for stride in array.strides):
+        # Send all the memory the array spans, including any gaps from slicing,
+        # unless the gaps would make us send a lot more data than a copy would.
+        # Broadcast arrays (with zero strides) span less memory than they have elements,
+        # which the viewer doesn't accept, so those are copied.
+        extent = sum((size - 1) * stride for size, stride in zip(array.shape, array.strides)) // itemsize + 1
+        if array.size <= extent <= 2 * array.size:
+            flat = np.lib.stride_tricks.as_strided(array, shape=(extent,), strides=(itemsize,), writeable=False)
+            return flat, [stride // itemsize for stride in array.strides]
+
+    return np.ascontiguousarray(array).reshape(-1), None
+
+
 class TensorArray(pa.ExtensionArray):  # type: ignore[misc]
     def from_numpy(
         array: npt.NDArray[TensorDType],
@@ -66,15 +91,33 @@ class TensorArray(pa.ExtensionArray):  # type: ignore[misc]
         shape_data = [[{"name": x[0], "size": x[1]} for x in zip(names, array.shape)]]
         shape = pa.array(shape_data, type=TensorType.storage_type["shape"].type)
 
+        flat, flat_strides = _flat_data_and_strides(array)
+        if flat_strides is None:
+            strides = pa.array([None], type=TensorType.storage_type["strides"].type)
+        else:
+            strides = pa.array([flat_strides], type=TensorType.storage_type["strides"].type)
+
+        # Arrow only deals in native byte order, so we reinterpret the bytes and let the viewer swap them.
+        big_endian = array.dtype.byteorder == ">" or (array.dtype.byteorder == "=" and sys.byteorder == "big")
+        flat = flat.view(flat.dtype.newbyteorder("="))
+
+        if array.dtype.name == "bfloat16":
+            discriminant = "BF16"
+        else:
+            discriminant = DTYPE_MAP[cast(TensorDType, array.dtype.type)]
+
         if array.dtype == np.uint8:
-            data_inner = pa.array([array.flatten().tobytes()], type=pa.binary())
+            data_inner = pa.array([flat.tobytes()], type=pa.binary())
         else:
-            data_storage = pa.array(array.flatten())
+            if discriminant in ("F16", "BF16"):
+                # Half-precision floats are sent as their raw bits, see `TensorData::F16`.
+                flat = flat.view(np.uint16)
+            data_storage = pa.array(flat)
             data_inner = pa.ListArray.from_arrays(pa.array([0, len(data_storage)]), data_storage)
 
         data = build_dense_union(
             TensorType.storage_type["data"].type,
-            discriminant=DTYPE_MAP[cast(TensorDType, array.dtype.type)],
+            discriminant=discriminant,
             child=data_inner,
         )
 
@@ -105,6 +148,8 @@ class TensorArray(pa.ExtensionArray):  # type: ignore[misc]
                 data,
                 meaning,
                 meter,
+                strides,
+                pa.array([big_endian], type=pa.bool_()),
             ],
             fields=list(TensorType.storage_type),
         ).cast(TensorType.storage_type)
@@ -0,0 +1,24 @@
+from __future__ import annotations
+
+from typing import Sequence
+
+import pyarrow as pa
+
+from rerun.components import REGISTERED_COMPONENT_NAMES, ComponentTypeFactory
+
+__all__ = [
+    "UnitArray",
+    "UnitType",
+]
+
+
+class UnitArray(pa.ExtensionArray):  # type: ignore[misc]
+    def new(units: Sequence[str]) -> UnitArray:
+        """Build a `UnitArray` from a sequence of str."""
+        storage = pa.array(units, type=UnitType.storage_type)
+        return storage  # type: ignore[no-any-return]
+
+
+UnitType = ComponentTypeFactory("UnitType", UnitArray, REGISTERED_COMPONENT_NAMES["rerun.unit"])
+
+pa.register_extension_type(UnitType())
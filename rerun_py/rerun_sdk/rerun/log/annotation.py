@@ -77,6 +77,7 @@ def log_annotation_context(
     class_descriptions: Union[ClassDescriptionLike, Iterable[ClassDescriptionLike]],
     *,
     timeless: bool = True,
+    reference_only: bool = False,
 ) -> None:
     """
     Log an annotation context made up of a collection of [ClassDescription][rerun.log.annotation.ClassDescription]s.
@@ -111,6 +112,10 @@ def log_annotation_context(
         A single ClassDescription or a collection of ClassDescriptions.
     timeless:
         If true, the annotation context will be timeless (default: True).
+    reference_only:
+        If true, only a hash of the annotation context is logged, and the viewer looks up the full
+        context from another recording it has already seen, e.g. an earlier session of the same robot.
+        This avoids logging the same large annotation context over and over (default: False).
 
     """
 
@@ -153,4 +158,4 @@ def log_annotation_context(
     ]
 
     # AnnotationContext arrow handling happens inside the python bridge
-    bindings.log_annotation_context(entity_path, tuple_class_descriptions, timeless)
+    bindings.log_annotation_context(entity_path, tuple_class_descriptions, timeless, reference_only)
@@ -0,0 +1,45 @@
+from rerun import bindings
+from rerun.components.group import GroupArray
+from rerun.components.instance import InstanceArray
+
+__all__ = [
+    "log_group",
+]
+
+
+def log_group(
+    entity_path: str,
+    group: str,
+    *,
+    timeless: bool = False,
+) -> None:
+    """
+    Declare which semantic group an entity belongs to, e.g. "perception" or "left arm".
+
+    The viewer can group the entity tree by this instead of by the entity path hierarchy,
+    which helps when browsing deep hierarchies from large systems.
+
+    Example
+    -------
+    ```
+    rerun.log_group("robot/arm_l/joint_3/torque", "left arm", timeless=True)
+    ```
+
+    Parameters
+    ----------
+    entity_path:
+        The path of the entity.
+    group:
+        The name of the group.
+    timeless:
+        If true, the group will be timeless (default: False).
+
+    """
+    if not bindings.is_enabled():
+        return
+
+    splats = {
+        "rerun.group": GroupArray.new([group]),
+        "rerun.instance_key": InstanceArray.splat(),
+    }
+    bindings.log_arrow_msg(entity_path, components=splats, timeless=timeless)
@@ -1,4 +1,4 @@
-from typing import Any, Dict, Optional, Sequence, Union
+from typing import Any, Dict, Mapping, Optional, Sequence, Union
 
 import numpy as np
 import numpy.typing as npt
@@ -8,6 +8,7 @@ from rerun.components.annotation import ClassIdArray
 from rerun.components.color import ColorRGBAArray
 from rerun.components.instance import InstanceArray
 from rerun.components.label import LabelArray
+from rerun.components.metadata import MetadataArray
 from rerun.components.point import Point2DArray, Point3DArray
 from rerun.components.radius import RadiusArray
 from rerun.log import (
@@ -141,6 +142,7 @@ def log_points(
     labels: Optional[Sequence[str]] = None,
     class_ids: OptionalClassIds = None,
     keypoint_ids: OptionalKeyPointIds = None,
+    metadata: Optional[Union[Mapping[str, Any], Sequence[Mapping[str, Any]]]] = None,
     ext: Optional[Dict[str, Any]] = None,
     timeless: bool = False,
 ) -> None:
@@ -184,6 +186,10 @@ def log_points(
         This is useful to identify points within a single classification (which is identified with class_id).
         E.g. the classification might be 'Person' and the keypoints refer to joints on a detected skeleton.
         See [rerun.log_annotation_context][]
+    metadata:
+        Optional key/value pairs shown when hovering or selecting a point, e.g. the model version
+        or track age of a detection. Either one dict per point, or a single dict for all of them.
+        Keys and values are converted to strings.
     ext:
         Optional dictionary of extension components. See [rerun.log_extension_components][]
     timeless:
@@ -250,6 +256,12 @@ def log_points(
         is_splat = len(keypoint_ids) == 1
         comps[is_splat]["rerun.keypoint_id"] = ClassIdArray.from_numpy(keypoint_ids)
 
+    if metadata:
+        if isinstance(metadata, Mapping):
+            comps[1]["rerun.metadata"] = MetadataArray.from_dicts([metadata])
+        else:
+            comps[0]["rerun.metadata"] = MetadataArray.from_dicts(metadata)
+
     if ext:
         _add_extension_components(comps[0], comps[1], ext, identifiers_np)
 
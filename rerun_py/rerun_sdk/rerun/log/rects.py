@@ -1,4 +1,4 @@
-from typing import Any, Dict, Optional, Sequence, Union
+from typing import Any, Dict, Mapping, Optional, Sequence, Union
 
 import numpy as np
 import numpy.typing as npt
@@ -8,6 +8,7 @@ from rerun.components.annotation import ClassIdArray
 from rerun.components.color import ColorRGBAArray
 from rerun.components.instance import InstanceArray
 from rerun.components.label import LabelArray
+from rerun.components.metadata import MetadataArray
 from rerun.components.rect2d import Rect2DArray, RectFormat
 from rerun.log import (
     Color,
@@ -110,6 +111,7 @@ def log_rects(
     colors: Optional[Union[Color, Colors]] = None,
     labels: Optional[Sequence[str]] = None,
     class_ids: OptionalClassIds = None,
+    metadata: Optional[Union[Mapping[str, Any], Sequence[Mapping[str, Any]]]] = None,
     ext: Optional[Dict[str, Any]] = None,
     timeless: bool = False,
 ) -> None:
@@ -146,6 +148,10 @@ def log_rects(
         Optional class ids for the rectangles.
         The class id provides colors and labels if not specified explicitly.
         See [rerun.log_annotation_context][]
+    metadata:
+        Optional key/value pairs shown when hovering or selecting a rectangle, e.g. the model version
+        or track age of a detection. Either one dict per rectangle, or a single dict for all of them.
+        Keys and values are converted to strings.
     ext:
         Optional dictionary of extension components. See [rerun.log_extension_components][]
     timeless:
@@ -196,6 +202,12 @@ def log_rects(
         is_splat = len(class_ids) == 1
         comps[is_splat]["rerun.class_id"] = ClassIdArray.from_numpy(class_ids)
 
+    if metadata:
+        if isinstance(metadata, Mapping):
+            comps[1]["rerun.metadata"] = MetadataArray.from_dicts([metadata])
+        else:
+            comps[0]["rerun.metadata"] = MetadataArray.from_dicts(metadata)
+
     if ext:
         _add_extension_components(comps[0], comps[1], ext, identifiers_np)
 
@@ -8,6 +8,7 @@ from rerun.components.instance import InstanceArray
 from rerun.components.label import LabelArray
 from rerun.components.radius import RadiusArray
 from rerun.components.scalar import ScalarArray, ScalarPlotPropsArray
+from rerun.components.unit import UnitArray
 from rerun.log import _normalize_colors
 from rerun.log.extension_components import _add_extension_components
 
@@ -23,6 +24,7 @@ def log_scalar(
     color: Optional[Sequence[int]] = None,
     radius: Optional[float] = None,
     scattered: Optional[bool] = None,
+    unit: Optional[str] = None,
     ext: Optional[Dict[str, Any]] = None,
 ) -> None:
     """
@@ -105,6 +107,11 @@ def log_scalar(
         Points within a single line do not have to all share the same scatteredness:
         the line will switch between a scattered and a continuous representation as
         required.
+    unit:
+        An optional unit for the scalar, e.g. "m", "m/s", "rad" or "°C".
+
+        It is shown in the plot legend and hover readouts. The viewer can
+        convert some units for display, e.g. radians to degrees.
     ext:
         Optional dictionary of extension components. See [rerun.log_extension_components][]
 
@@ -132,6 +139,9 @@ def log_scalar(
         props = [{"scattered": scattered}]
         instanced["rerun.scalar_plot_props"] = ScalarPlotPropsArray.from_props(props)
 
+    if unit:
+        instanced["rerun.unit"] = UnitArray.new([unit])
+
     if ext:
         _add_extension_components(instanced, splats, ext, None)
 
@@ -6,6 +6,7 @@ import numpy.typing as npt
 from rerun import bindings
 from rerun.components.instance import InstanceArray
 from rerun.components.tensor import TensorArray
+from rerun.components.unit import UnitArray
 from rerun.log.error_utils import _send_warning
 from rerun.log.extension_components import _add_extension_components
 
@@ -43,6 +44,7 @@ def log_tensor(
     *,
     names: Optional[Iterable[str]] = None,
     meter: Optional[float] = None,
+    unit: Optional[str] = None,
     ext: Optional[Dict[str, Any]] = None,
     timeless: bool = False,
 ) -> None:
@@ -59,6 +61,8 @@ def log_tensor(
         Optional names for each dimension of the tensor.
     meter:
         Optional scale of the tensor (e.g. meters per cell).
+    unit:
+        Optional unit of the tensor values, e.g. "°C" for a thermal image.
     ext:
         Optional dictionary of extension components. See [rerun.log_extension_components][]
     timeless:
@@ -70,6 +74,7 @@ def log_tensor(
         tensor=_to_numpy(tensor),
         names=names,
         meter=meter,
+        unit=unit,
         ext=ext,
         timeless=timeless,
     )
@@ -81,6 +86,7 @@ def _log_tensor(
     names: Optional[Iterable[Optional[str]]] = None,
     meter: Optional[float] = None,
     meaning: bindings.TensorDataMeaning = None,
+    unit: Optional[str] = None,
     ext: Optional[Dict[str, Any]] = None,
     timeless: bool = False,
 ) -> None:
@@ -116,12 +122,8 @@ def _log_tensor(
         np.float64,
     ]
 
-    # We don't support float16 -- upscale to f32
-    # TODO(#854): Native F16 support for arrow tensors
-    if tensor.dtype == np.float16:
-        tensor = np.asarray(tensor, dtype="float32")
-
-    if tensor.dtype not in SUPPORTED_DTYPES:
+    # bfloat16 isn't part of numpy, but comes from e.g. `ml_dtypes` (used by JAX)
+    if tensor.dtype.type not in SUPPORTED_DTYPES and tensor.dtype.name != "bfloat16":
         _send_warning(f"Unsupported dtype: {tensor.dtype}. Expected a numeric type. Skipping this tensor.", 2)
         return
 
@@ -130,6 +132,9 @@ def _log_tensor(
 
     instanced["rerun.tensor"] = TensorArray.from_numpy(tensor, names, meaning, meter)
 
+    if unit:
+        instanced["rerun.unit"] = UnitArray.new([unit])
+
     if ext:
         _add_extension_components(instanced, splats, ext, None)
 
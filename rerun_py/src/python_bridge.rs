@@ -19,11 +19,12 @@ use rerun::{
 
 pub use rerun::{
     components::{
-        AnnotationContext, AnnotationInfo, Arrow3D, Box3D, ClassDescription, ClassId, ColorRGBA,
-        EncodedMesh3D, InstanceKey, KeypointId, Label, LineStrip2D, LineStrip3D, Mat3x3, Mesh3D,
-        MeshFormat, MeshId, Pinhole, Point2D, Point3D, Quaternion, Radius, RawMesh3D, Rect2D,
-        Rigid3, Scalar, ScalarPlotProps, Size3D, Tensor, TensorData, TensorDimension, TensorId,
-        TensorTrait, TextEntry, Transform, Vec2D, Vec3D, Vec4D, ViewCoordinates,
+        AnnotationContext, AnnotationContextRef, AnnotationInfo, Arrow3D, Box3D, ClassDescription,
+        ClassId, ColorRGBA, EncodedMesh3D, InstanceKey, KeypointId, Label, LineStrip2D,
+        LineStrip3D, Mat3x3, Mesh3D, MeshFormat, MeshId, Pinhole, Point2D, Point3D, Quaternion,
+        Radius, RawMesh3D, Rect2D, Rigid3, Scalar, ScalarPlotProps, Size3D, Tensor, TensorData,
+        TensorDimension, TensorId, TensorTrait, TextEntry, Transform, Vec2D, Vec3D, Vec4D,
+        ViewCoordinates,
     },
     coordinates::{Axis3, Handedness, Sign, SignedAxis3},
 };
@@ -124,6 +125,7 @@ fn rerun_bindings(py: Python<'_>, m: &PyModule) -> PyResult<()> {
         m.add_function(wrap_pyfunction!(show, m)?)?;
     }
     m.add_function(wrap_pyfunction!(save, m)?)?;
+    m.add_function(wrap_pyfunction!(save_while_logging, m)?)?;
 
     m.add_function(wrap_pyfunction!(set_time_sequence, m)?)?;
     m.add_function(wrap_pyfunction!(set_time_seconds, m)?)?;
@@ -267,10 +269,15 @@ fn init(application_id: String, application_path: Option<PathBuf>, default_enabl
     session.set_application_id(ApplicationId(application_id), is_official_example);
 }
 
+/// Connect to a Rerun Viewer or Server at `addr`, e.g. `127.0.0.1:9876` or `lab-server:9876`.
 #[pyfunction]
 fn connect(addr: Option<String>) -> PyResult<()> {
     let addr = if let Some(addr) = addr {
-        addr.parse()?
+        use std::net::ToSocketAddrs as _;
+        addr.to_socket_addrs()
+            .map_err(|err| PyRuntimeError::new_err(format!("Bad address {addr:?}: {err}")))?
+            .next()
+            .ok_or_else(|| PyRuntimeError::new_err(format!("{addr:?} resolved to no address")))?
     } else {
         re_sdk_comms::default_server_addr()
     };
@@ -306,6 +313,7 @@ fn shutdown(py: Python<'_>) {
         let mut session = global_session();
         session.drop_msgs_if_disconnected();
         session.flush();
+        session.stop_saving_while_logging();
         session.disconnect();
     });
 }
@@ -371,6 +379,26 @@ fn save(path: &str) -> PyResult<()> {
         .map_err(|err| PyRuntimeError::new_err(err.to_string()))
 }
 
+/// Also write everything logged from now on to a file, e.g. while streaming it to a viewer.
+///
+/// Continues in a new file after `split_minutes` or `split_bytes`, if set.
+#[pyfunction]
+fn save_while_logging(
+    path: &str,
+    split_minutes: Option<f64>,
+    split_bytes: Option<u64>,
+) -> PyResult<()> {
+    let split = rerun::FileSplit {
+        max_duration: split_minutes
+            .map(|minutes| std::time::Duration::from_secs_f64(60.0 * minutes)),
+        max_bytes: split_bytes,
+    };
+    let mut session = global_session();
+    session
+        .save_while_logging_in_parts(path, split)
+        .map_err(|err| PyRuntimeError::new_err(format!("{err:#}")))
+}
+
 // ----------------------------------------------------------------------------
 
 /// Set the current time globally. Used for all subsequent logging,
@@ -691,14 +719,8 @@ fn log_mesh_file(
             )));
         }
     };
-    let bytes = bytes.into();
     let transform = if transform.is_empty() {
-        [
-            [1.0, 0.0, 0.0], // col 0
-            [0.0, 1.0, 0.0], // col 1
-            [0.0, 0.0, 1.0], // col 2
-            [0.0, 0.0, 0.0], // col 3 = translation
-        ]
+        EncodedMesh3D::IDENTITY_TRANSFORM
     } else {
         if transform.shape() != [3, 4] {
             return Err(PyTypeError::new_err(format!(
@@ -721,12 +743,7 @@ fn log_mesh_file(
 
     let time_point = time(timeless);
 
-    let mesh3d = Mesh3D::Encoded(EncodedMesh3D {
-        mesh_id: MeshId::random(),
-        format,
-        bytes,
-        transform,
-    });
+    let mesh3d = Mesh3D::Encoded(EncodedMesh3D::new(format, bytes).with_transform(transform));
 
     // We currently log `Mesh3D` from inside the bridge.
     //
@@ -826,6 +843,8 @@ fn log_image_file(
             data: re_log_types::component_types::TensorData::JPEG(img_bytes),
             meaning: re_log_types::component_types::TensorDataMeaning::Unknown,
             meter: None,
+            strides: None,
+            big_endian: None,
         }]
         .try_into()
         .unwrap()],
@@ -862,6 +881,7 @@ fn log_annotation_context(
     entity_path_str: &str,
     class_descriptions: Vec<ClassDescriptionTuple>,
     timeless: bool,
+    reference_only: bool,
 ) -> PyResult<()> {
     let mut session = global_session();
 
@@ -899,12 +919,14 @@ fn log_annotation_context(
     // implementation.
     //
     // TODO(jleibs) replace with python-native implementation
-    let bundle = MsgBundle::new(
-        MsgId::random(),
-        entity_path,
-        time_point,
-        vec![vec![annotation_context.clone()].try_into().unwrap()],
-    );
+    let component = if reference_only {
+        vec![AnnotationContextRef(annotation_context.content_hash())]
+            .try_into()
+            .unwrap()
+    } else {
+        vec![annotation_context].try_into().unwrap()
+    };
+    let bundle = MsgBundle::new(MsgId::random(), entity_path, time_point, vec![component]);
 
     let msg = bundle.try_into().unwrap();
 